@@ -1,3 +1,70 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// アプリ単位の録音に使うヘルパーのサイドカー名（`capture_source::APPLICATION_CAPTURE_HELPER`）
+const APPLICATION_CAPTURE_HELPER: &str = "app-audio-capture";
+
 fn main() {
+    // gRPC API のコード生成（protocはベンダー版を使用し、別途インストール不要にする）
+    std::env::set_var(
+        "PROTOC",
+        protoc_bin_vendored::protoc_bin_path().expect("Failed to locate vendored protoc"),
+    );
+    tonic_build::compile_protos("proto/meeting_summarizer.proto").expect("Failed to compile gRPC proto");
+
+    // サイドカーは tauri_build が同梱前に存在を確かめるので、先にビルドしておく
+    build_capture_helper();
+
     tauri_build::build()
 }
+
+/// アプリ単位の録音のヘルパーをビルドして `binaries/<名前>-<ターゲット>` に置く
+fn build_capture_helper() {
+    let target = std::env::var("TARGET").expect("TARGET is set by cargo");
+    match std::env::var("CARGO_CFG_TARGET_OS").as_deref() {
+        Ok("macos") => build_macos_helper(&target),
+        Ok("windows") => build_windows_helper(&target),
+        _ => {}
+    }
+}
+
+fn build_macos_helper(target: &str) {
+    let source = Path::new("helpers/app-audio-capture/main.swift");
+    println!("cargo:rerun-if-changed={}", source.display());
+    let output = sidecar_path(target, "");
+
+    // ScreenCaptureKit の音声は macOS 13 から使える
+    let arch = target.split('-').next().unwrap_or_default().replace("aarch64", "arm64");
+    let status = Command::new("xcrun")
+        .args(["swiftc", "-O", "-target", &format!("{}-apple-macos13.0", arch), "-o"])
+        .arg(&output)
+        .arg(source)
+        .status()
+        .expect("Failed to run swiftc (install the Xcode command line tools)");
+    assert!(status.success(), "Failed to compile {}", source.display());
+}
+
+fn build_windows_helper(target: &str) {
+    let source = Path::new("helpers/app-audio-capture/Program.cs");
+    println!("cargo:rerun-if-changed={}", source.display());
+    let output = sidecar_path(target, ".exe");
+
+    // .NET Framework 4 に含まれる csc を使う（Windows 10 以降なら標準で入っている）
+    let windir = std::env::var("WINDIR").unwrap_or_else(|_| r"C:\Windows".to_string());
+    let framework = if target.starts_with("x86_64") || target.starts_with("aarch64") { "Framework64" } else { "Framework" };
+    let csc = Path::new(&windir).join("Microsoft.NET").join(framework).join("v4.0.30319").join("csc.exe");
+    let status = Command::new(&csc)
+        .args(["/nologo", "/optimize+", "/target:exe"])
+        .arg(format!("/out:{}", output.display()))
+        .arg(source)
+        .status()
+        .unwrap_or_else(|e| panic!("Failed to run {}: {}", csc.display(), e));
+    assert!(status.success(), "Failed to compile {}", source.display());
+}
+
+/// tauri_build が探すサイドカーのファイル（`<名前>-<ターゲット><拡張子>`）
+fn sidecar_path(target: &str, extension: &str) -> PathBuf {
+    let dir = Path::new("binaries");
+    std::fs::create_dir_all(dir).expect("Failed to create binaries directory");
+    dir.join(format!("{}-{}{}", APPLICATION_CAPTURE_HELPER, target, extension))
+}
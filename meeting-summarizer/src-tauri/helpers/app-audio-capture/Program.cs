@@ -0,0 +1,147 @@
+// Windows のプロセス単位のループバック（ActivateAudioInterfaceAsync）で、指定したプロセスとその子プロセスの
+// 音声を標準出力へ書き出すヘルパー（Windows 10 2004 以降）
+//
+// 使い方: app-audio-capture.exe <プロセス名> <サンプルレート>
+// f32 リトルエンディアン・モノラルで書き出す。ビルド時に build.rs が .NET Framework の csc で
+// コンパイルし、Tauri のサイドカーとしてアプリに同梱する。
+
+using System;
+using System.IO;
+using System.Runtime.InteropServices;
+using System.Threading;
+
+public static class ProcessLoopback
+{
+    [StructLayout(LayoutKind.Sequential)]
+    struct ActivationParams { public int ActivationType; public uint TargetProcessId; public int LoopbackMode; }
+
+    [StructLayout(LayoutKind.Sequential)]
+    struct BlobPropVariant { public ushort vt; public ushort r1; public ushort r2; public ushort r3; public int size; public IntPtr data; }
+
+    [StructLayout(LayoutKind.Sequential)]
+    struct WaveFormatEx { public ushort tag; public ushort channels; public uint rate; public uint bytesPerSec; public ushort blockAlign; public ushort bits; public ushort size; }
+
+    [ComImport, Guid("1CB9AD4C-DBFA-4c32-B178-C2F568A703B2"), InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    interface IAudioClient
+    {
+        [PreserveSig] int Initialize(int shareMode, uint flags, long bufferDuration, long periodicity, ref WaveFormatEx format, IntPtr session);
+        [PreserveSig] int GetBufferSize(out uint frames);
+        [PreserveSig] int GetStreamLatency(out long latency);
+        [PreserveSig] int GetCurrentPadding(out uint padding);
+        [PreserveSig] int IsFormatSupported(int shareMode, IntPtr format, out IntPtr closest);
+        [PreserveSig] int GetMixFormat(out IntPtr format);
+        [PreserveSig] int GetDevicePeriod(out long defaultPeriod, out long minimumPeriod);
+        [PreserveSig] int Start();
+        [PreserveSig] int Stop();
+        [PreserveSig] int Reset();
+        [PreserveSig] int SetEventHandle(IntPtr handle);
+        [PreserveSig] int GetService(ref Guid iid, [MarshalAs(UnmanagedType.IUnknown)] out object service);
+    }
+
+    [ComImport, Guid("C8ADBD64-E71E-48a0-A4DE-185C395CD317"), InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    interface IAudioCaptureClient
+    {
+        [PreserveSig] int GetBuffer(out IntPtr data, out uint frames, out uint flags, out ulong position, out ulong qpc);
+        [PreserveSig] int ReleaseBuffer(uint frames);
+        [PreserveSig] int GetNextPacketSize(out uint frames);
+    }
+
+    [ComImport, Guid("72A22D78-CDE4-431D-B8CC-843A71199B6D"), InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    interface IActivateAudioInterfaceAsyncOperation
+    {
+        [PreserveSig] int GetActivateResult(out int result, [MarshalAs(UnmanagedType.IUnknown)] out object activated);
+    }
+
+    [ComImport, Guid("41D949AB-9862-444A-80F6-C261334DA5EB"), InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    interface IActivateAudioInterfaceCompletionHandler
+    {
+        void ActivateCompleted(IActivateAudioInterfaceAsyncOperation operation);
+    }
+
+    [ComImport, Guid("94ea2b94-e9cc-49e0-c0ff-ee64ca8f5b90"), InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    interface IAgileObject { }
+
+    class CompletionHandler : IActivateAudioInterfaceCompletionHandler, IAgileObject
+    {
+        public readonly ManualResetEvent Done = new ManualResetEvent(false);
+        public void ActivateCompleted(IActivateAudioInterfaceAsyncOperation operation) { Done.Set(); }
+    }
+
+    [DllImport("Mmdevapi.dll", ExactSpelling = true, PreserveSig = false)]
+    static extern void ActivateAudioInterfaceAsync(
+        [MarshalAs(UnmanagedType.LPWStr)] string path, ref Guid iid, ref BlobPropVariant parameters,
+        IActivateAudioInterfaceCompletionHandler handler, out IActivateAudioInterfaceAsyncOperation operation);
+
+    /// 引数はプロセス名とサンプルレート。最初に起動したプロセスを親とみなし、子プロセス（描画や通話用のプロセス）の
+    /// 音声もまとめて取り込む
+    public static int Main(string[] args)
+    {
+        System.Diagnostics.Process target = null;
+        foreach (var process in System.Diagnostics.Process.GetProcessesByName(args[0]))
+        {
+            try { if (target == null || process.StartTime < target.StartTime) target = process; }
+            catch (Exception) { }
+        }
+        if (target == null) { Console.Error.WriteLine("Application is not running: " + args[0]); return 2; }
+        return Run(target.Id, args.Length > 1 ? int.Parse(args[1]) : 48000);
+    }
+
+    public static int Run(int processId, int sampleRate)
+    {
+        // AUDIOCLIENT_ACTIVATION_TYPE_PROCESS_LOOPBACK / PROCESS_LOOPBACK_MODE_INCLUDE_TARGET_PROCESS_TREE
+        var activation = new ActivationParams { ActivationType = 1, TargetProcessId = (uint)processId, LoopbackMode = 0 };
+        IntPtr activationPtr = Marshal.AllocHGlobal(Marshal.SizeOf(activation));
+        Marshal.StructureToPtr(activation, activationPtr, false);
+        var parameters = new BlobPropVariant { vt = 65, size = Marshal.SizeOf(activation), data = activationPtr };
+
+        var handler = new CompletionHandler();
+        var iid = typeof(IAudioClient).GUID;
+        IActivateAudioInterfaceAsyncOperation operation;
+        ActivateAudioInterfaceAsync(@"VAD\Process_Loopback", ref iid, ref parameters, handler, out operation);
+        handler.Done.WaitOne();
+
+        int hr;
+        object activated;
+        operation.GetActivateResult(out hr, out activated);
+        Marshal.FreeHGlobal(activationPtr);
+        if (hr != 0) { Console.Error.WriteLine("Failed to activate process loopback: 0x" + hr.ToString("X8")); return 1; }
+
+        var client = (IAudioClient)activated;
+        // 32bit float モノラル
+        var format = new WaveFormatEx { tag = 3, channels = 1, rate = (uint)sampleRate, bits = 32, blockAlign = 4, bytesPerSec = (uint)sampleRate * 4 };
+        // AUDCLNT_STREAMFLAGS_LOOPBACK | AUTOCONVERTPCM | SRC_DEFAULT_QUALITY
+        hr = client.Initialize(0, 0x00020000 | 0x80000000 | 0x08000000, 2000000, 0, ref format, IntPtr.Zero);
+        if (hr != 0) { Console.Error.WriteLine("Failed to initialize audio client: 0x" + hr.ToString("X8")); return 1; }
+
+        var captureIid = typeof(IAudioCaptureClient).GUID;
+        object service;
+        client.GetService(ref captureIid, out service);
+        var capture = (IAudioCaptureClient)service;
+        client.Start();
+
+        var output = Console.OpenStandardOutput();
+        var buffer = new byte[0];
+        var target = System.Diagnostics.Process.GetProcessById(processId);
+        while (!target.HasExited)
+        {
+            uint packet;
+            capture.GetNextPacketSize(out packet);
+            while (packet > 0)
+            {
+                IntPtr data; uint frames; uint flags; ulong position; ulong qpc;
+                capture.GetBuffer(out data, out frames, out flags, out position, out qpc);
+                int bytes = (int)frames * 4;
+                if (buffer.Length < bytes) buffer = new byte[bytes];
+                // AUDCLNT_BUFFERFLAGS_SILENT
+                if ((flags & 2) != 0) Array.Clear(buffer, 0, bytes); else Marshal.Copy(data, buffer, 0, bytes);
+                output.Write(buffer, 0, bytes);
+                capture.ReleaseBuffer(frames);
+                capture.GetNextPacketSize(out packet);
+            }
+            output.Flush();
+            Thread.Sleep(10);
+        }
+        client.Stop();
+        return 0;
+    }
+}
@@ -0,0 +1,65 @@
+// ScreenCaptureKit で指定したアプリの音声を標準出力へ書き出すヘルパー（macOS 13 以降）
+//
+// 使い方: app-audio-capture <バンドル ID> <サンプルレート>
+// f32 リトルエンディアン・モノラルで書き出す。ビルド時に build.rs が swiftc でコンパイルし、
+// Tauri のサイドカーとしてアプリに同梱する。
+
+import Foundation
+import CoreMedia
+import ScreenCaptureKit
+
+let bundleId = CommandLine.arguments[1]
+let sampleRate = Int(CommandLine.arguments[2]) ?? 48000
+
+final class AudioOutput: NSObject, SCStreamOutput, SCStreamDelegate {
+    func stream(_ stream: SCStream, didOutputSampleBuffer sampleBuffer: CMSampleBuffer, of type: SCStreamOutputType) {
+        guard type == .audio, sampleBuffer.isValid else { return }
+        try? sampleBuffer.withAudioBufferList { buffers, _ in
+            guard let buffer = buffers.first, let data = buffer.mData else { return }
+            FileHandle.standardOutput.write(Data(bytes: data, count: Int(buffer.mDataByteSize)))
+        }
+    }
+
+    func stream(_ stream: SCStream, didStopWithError error: Error) {
+        FileHandle.standardError.write("Capture stopped: \(error.localizedDescription)\n".data(using: .utf8)!)
+        exit(1)
+    }
+}
+
+let output = AudioOutput()
+var activeStream: SCStream?
+
+Task {
+    do {
+        let content = try await SCShareableContent.excludingDesktopWindows(false, onScreenWindowsOnly: false)
+        guard let app = content.applications.first(where: { $0.bundleIdentifier == bundleId }) else {
+            FileHandle.standardError.write("Application is not running: \(bundleId)\n".data(using: .utf8)!)
+            exit(2)
+        }
+        guard let display = content.displays.first else {
+            FileHandle.standardError.write("No display available\n".data(using: .utf8)!)
+            exit(3)
+        }
+
+        let filter = SCContentFilter(display: display, including: [app], exceptingWindows: [])
+        let configuration = SCStreamConfiguration()
+        configuration.capturesAudio = true
+        configuration.excludesCurrentProcessAudio = true
+        configuration.sampleRate = sampleRate
+        configuration.channelCount = 1
+        // 映像は使わないので最小にする
+        configuration.width = 2
+        configuration.height = 2
+        configuration.minimumFrameInterval = CMTime(value: 1, timescale: 1)
+
+        let stream = SCStream(filter: filter, configuration: configuration, delegate: output)
+        try stream.addStreamOutput(output, type: .audio, sampleHandlerQueue: DispatchQueue(label: "app-audio"))
+        try await stream.startCapture()
+        activeStream = stream
+    } catch {
+        FileHandle.standardError.write("\(error.localizedDescription)\n".data(using: .utf8)!)
+        exit(1)
+    }
+}
+
+RunLoop.main.run()
@@ -0,0 +1,252 @@
+//! GUIなしで書き起こし・要約・エクスポートを行うヘッドレスCLI
+//!
+//! デスクトップアプリと同じデータディレクトリ（DB・録音ファイル）を共有するため、
+//! サーバー上でのバッチ処理結果はそのままアプリから参照できる。アプリと同じく
+//! 選択中のワークスペースのデータを使い、`--workspace` で別のワークスペースを指定できる。
+
+use clap::{Parser, Subcommand};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::errors::{validate_audio_format, AppError, AppResult};
+use meeting_summarizer_lib::models::{LLMConfig, LLMProvider, Recording, Summary, SummaryStatus, Transcription};
+use meeting_summarizer_lib::services::{audio_probe, corrections, demo_mode, export, LLMService, WhisperService, WorkspaceManager};
+use std::path::{Path, PathBuf};
+
+/// Tauriの `identifier`（tauri.conf.json）と同じディレクトリ名
+const APP_IDENTIFIER: &str = "com.kenshiroebisu.meeting-summarizer";
+
+#[derive(Parser)]
+#[command(name = "meeting-summarizer-cli", version, about = "Headless transcription and summarization")]
+struct Cli {
+    /// アプリデータディレクトリ（省略時はデスクトップアプリと共有）
+    #[arg(long, global = true)]
+    data_dir: Option<PathBuf>,
+
+    /// 使うワークスペースのID（省略時はアプリで選択中のワークスペース）
+    #[arg(long, global = true)]
+    workspace: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 音声ファイルを書き起こす（複数指定で順次処理）
+    Transcribe {
+        files: Vec<PathBuf>,
+        #[arg(long)]
+        language: Option<String>,
+        /// 録音と書き起こし結果をデータベースに保存する
+        #[arg(long)]
+        save: bool,
+    },
+    /// 音声ファイルまたはテキストファイルを要約する
+    Summarize {
+        file: PathBuf,
+        #[arg(long)]
+        language: Option<String>,
+        #[arg(long, default_value = "Ollama")]
+        provider: LLMProvider,
+        #[arg(long)]
+        model: Option<String>,
+        #[arg(long)]
+        base_url: Option<String>,
+        /// 結果をJSONで出力する
+        #[arg(long)]
+        json: bool,
+    },
+    /// 保存済みの録音をエクスポートする
+    Export {
+        recording_id: String,
+        /// 出力形式（json / text / ics）
+        #[arg(long, default_value = "text")]
+        format: String,
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// 保存済みの録音一覧を表示する
+    List {
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).try_init();
+
+    let cli = Cli::parse();
+    if demo_mode::requested_by_env() {
+        demo_mode::set_enabled(true);
+    }
+    if let Err(e) = run(cli).await {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> AppResult<()> {
+    let data_dir = match cli.data_dir {
+        Some(dir) => dir,
+        None => dirs::data_dir()
+            .map(|dir| dir.join(APP_IDENTIFIER))
+            .ok_or_else(|| AppError::InvalidOperation {
+                message: "Could not determine the app data directory; pass --data-dir".to_string(),
+            })?,
+    };
+    std::fs::create_dir_all(&data_dir)?;
+
+    // アプリと同じくワークスペースごとのDB・録音ディレクトリを使う（Whisperモデルは共有）
+    let workspaces = WorkspaceManager::load(&data_dir)?;
+    let workspace_paths = match cli.workspace.as_deref() {
+        Some(workspace_id) => {
+            let paths = workspaces.paths(workspace_id)?;
+            std::fs::create_dir_all(&paths.root)?;
+            paths
+        }
+        None => workspaces.active_paths()?,
+    };
+    let recordings_dir = workspace_paths.recordings_dir;
+    let database = Database::new(workspace_paths.db_path)?;
+
+    match cli.command {
+        Command::Transcribe { files, language, save } => {
+            let whisper_service =
+                WhisperService::new(data_dir.join("models").join("ggml-base.bin"), recordings_dir.clone());
+            whisper_service.initialize().await?;
+
+            for file in files {
+                let saved_to = save.then_some(recordings_dir.as_path());
+                let transcription = transcribe_file(&whisper_service, &database, &file, language.clone(), saved_to).await?;
+                println!("=== {} ===", file.display());
+                println!("{}\n", transcription.text);
+            }
+        }
+        Command::Summarize { file, language, provider, model, base_url, json } => {
+            let text = if validate_audio_format(&file).is_ok() {
+                let whisper_service = WhisperService::new(data_dir.join("models").join("ggml-base.bin"), recordings_dir);
+                whisper_service.initialize().await?;
+                transcribe_file(&whisper_service, &database, &file, language, None).await?.text
+            } else {
+                std::fs::read_to_string(&file)?
+            };
+
+            let defaults = LLMConfig::default();
+            let config = LLMConfig {
+                provider,
+                base_url: base_url.unwrap_or(defaults.base_url),
+                model_name: model.unwrap_or(defaults.model_name),
+                ..defaults
+            };
+
+            let summary = LLMService::new(config)
+                .summarize_text(&text, file.to_string_lossy().to_string())
+                .await?;
+
+            if let SummaryStatus::Failed(error) = &summary.status {
+                return Err(AppError::LLMError { message: error.clone() });
+            }
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&summary)?);
+            } else {
+                print_summary(&summary);
+            }
+        }
+        Command::Export { recording_id, format, output } => {
+            let exported = export::export_recording(&database, &recording_id, &format).await?;
+            match output {
+                Some(path) => std::fs::write(path, exported)?,
+                None => println!("{}", exported),
+            }
+        }
+        Command::List { json } => {
+            let recordings = database.get_all_recordings().await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&recordings)?);
+            } else {
+                for recording in recordings {
+                    println!(
+                        "{}  {}  {}  {}",
+                        recording.id,
+                        recording.created_at.format("%Y-%m-%d %H:%M"),
+                        recording.duration.map(|d| format!("{}s", d)).unwrap_or_else(|| "-".to_string()),
+                        recording.title.as_deref().unwrap_or(&recording.filename),
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 音声ファイルを書き起こす
+///
+/// `recordings_dir` を指定すると、ファイルを録音ディレクトリにコピーして録音と書き起こしを保存する。
+/// アプリから録音を削除・編集しても、指定された元のファイルには触れない。
+async fn transcribe_file(
+    whisper_service: &WhisperService,
+    database: &Database,
+    file: &Path,
+    language: Option<String>,
+    recordings_dir: Option<&Path>,
+) -> AppResult<Transcription> {
+    let file = file.canonicalize()?;
+    validate_audio_format(&file)?;
+
+    let filename = file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut recording = Recording::new(filename, file.to_string_lossy().to_string())
+        .with_file_size(std::fs::metadata(&file)?.len() as i64);
+
+    let mut transcription = whisper_service
+        .transcribe_audio_file(&file, recording.id.clone(), language)
+        .await?;
+    corrections::apply_corrections(database, &mut transcription).await?;
+
+    if let Some(recordings_dir) = recordings_dir {
+        std::fs::create_dir_all(recordings_dir)?;
+        let extension = file
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("wav")
+            .to_lowercase();
+        let audio_path = recordings_dir.join(format!("cli_{}.{}", recording.id, extension));
+        std::fs::copy(&file, &audio_path)?;
+
+        recording.title = file.file_stem().map(|stem| stem.to_string_lossy().to_string());
+        recording.filename = audio_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        recording.file_path = audio_path.to_string_lossy().to_string();
+        audio_probe::fill_recording(&mut recording, std::slice::from_ref(&audio_path));
+        database.create_recording(&recording).await?;
+        database.create_transcription(&transcription).await?;
+        log::info!("💾 Saved recording {} with transcription {}", recording.id, transcription.id);
+    }
+
+    Ok(transcription)
+}
+
+fn print_summary(summary: &Summary) {
+    println!("## Summary\n{}\n", summary.summary_text);
+
+    if !summary.key_points.is_empty() {
+        println!("## Key points");
+        for point in &summary.key_points {
+            println!("- {}", point);
+        }
+        println!();
+    }
+
+    if !summary.action_items.is_empty() {
+        println!("## Action items");
+        for item in &summary.action_items {
+            println!("- {}", item);
+        }
+    }
+}
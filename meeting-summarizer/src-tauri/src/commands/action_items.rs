@@ -0,0 +1,54 @@
+use crate::database::Database;
+use crate::models::{ActionItem, ActionItemRef, ActionItemStatus};
+use crate::services::action_items::{self, ActionItemQuery, AssigneeGroup, DueDateGroup};
+use std::sync::Arc;
+use tauri::State;
+
+type DbState = Arc<Database>;
+
+#[tauri::command]
+pub async fn get_action_items(
+    db: State<'_, DbState>,
+    query: Option<ActionItemQuery>,
+) -> Result<Vec<ActionItem>, String> {
+    action_items::list_action_items(&db, &query.unwrap_or_default())
+        .await
+        .map_err(String::from)
+}
+
+/// 担当者ごとのアクションアイテム（「自分のタスク」画面用）
+#[tauri::command]
+pub async fn get_action_items_by_assignee(
+    db: State<'_, DbState>,
+    query: Option<ActionItemQuery>,
+) -> Result<Vec<AssigneeGroup>, String> {
+    let items = action_items::list_action_items(&db, &query.unwrap_or_default())
+        .await
+        .map_err(String::from)?;
+    Ok(action_items::group_by_assignee(items))
+}
+
+/// 期限区分（期限切れ / 今日 / 今週 / それ以降 / 期限なし）ごとのアクションアイテム
+#[tauri::command]
+pub async fn get_action_items_by_due_date(
+    db: State<'_, DbState>,
+    query: Option<ActionItemQuery>,
+) -> Result<Vec<DueDateGroup>, String> {
+    let items = action_items::list_action_items(&db, &query.unwrap_or_default())
+        .await
+        .map_err(String::from)?;
+    Ok(action_items::group_by_due_date(items, chrono::Local::now().date_naive()))
+}
+
+/// 複数のアクションアイテムの状態をまとめて更新し、更新件数を返す
+#[tauri::command]
+pub async fn update_action_item_statuses(
+    db: State<'_, DbState>,
+    items: Vec<ActionItemRef>,
+    status: ActionItemStatus,
+) -> Result<usize, String> {
+    log::info!("✅ Updating {} action items to {}", items.len(), status.as_str());
+    db.set_action_item_statuses(&items, status)
+        .await
+        .map_err(String::from)
+}
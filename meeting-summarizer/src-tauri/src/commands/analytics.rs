@@ -0,0 +1,74 @@
+use crate::database::Database;
+use crate::models::{PersonTalkStats, SpeakerTalkStats, SpeechMetrics};
+use crate::services::speaker_analytics::{self, FillerWordStats, SpeakerOverlap};
+use crate::services::voice_activity;
+use std::sync::Arc;
+use tauri::State;
+
+type DbState = Arc<Database>;
+
+/// 録音の話者ごとの発話量を計算し直して保存する
+#[tauri::command]
+pub async fn analyze_speaker_talk_time(
+    db: State<'_, DbState>,
+    recording_id: String,
+) -> Result<Vec<SpeakerTalkStats>, String> {
+    speaker_analytics::analyze_recording(&db, &recording_id)
+        .await
+        .map_err(String::from)
+}
+
+/// 保存済みの話者ごとの発話量（発話時間の長い順）
+#[tauri::command]
+pub async fn get_speaker_talk_stats(
+    db: State<'_, DbState>,
+    recording_id: String,
+) -> Result<Vec<SpeakerTalkStats>, String> {
+    db.get_speaker_stats(&recording_id).await.map_err(String::from)
+}
+
+/// 会議を横断した人ごとの発話量
+#[tauri::command]
+pub async fn get_person_talk_stats(db: State<'_, DbState>) -> Result<Vec<PersonTalkStats>, String> {
+    db.get_person_talk_stats().await.map_err(String::from)
+}
+
+/// 話者の組ごとの発話の重なり・割り込み回数
+#[tauri::command]
+pub async fn get_speaker_overlaps(
+    db: State<'_, DbState>,
+    recording_id: String,
+) -> Result<Vec<SpeakerOverlap>, String> {
+    let segments = speaker_analytics::diarized_segments(&db, &recording_id)
+        .await
+        .map_err(String::from)?;
+    Ok(speaker_analytics::compute_overlaps(&segments))
+}
+
+/// 話者ごとのフィラーの回数（`filler_words` 省略時は既定の一覧）
+#[tauri::command]
+pub async fn get_filler_word_stats(
+    db: State<'_, DbState>,
+    recording_id: String,
+    filler_words: Option<Vec<String>>,
+) -> Result<Vec<FillerWordStats>, String> {
+    speaker_analytics::filler_word_stats(&db, &recording_id, filler_words)
+        .await
+        .map_err(String::from)
+}
+
+/// 録音の発話区間を解析して無音率などを保存する
+#[tauri::command]
+pub async fn analyze_speech_activity(db: State<'_, DbState>, recording_id: String) -> Result<SpeechMetrics, String> {
+    voice_activity::analyze_recording(&db, &recording_id)
+        .await
+        .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn get_speech_metrics(
+    db: State<'_, DbState>,
+    recording_id: String,
+) -> Result<Option<SpeechMetrics>, String> {
+    db.get_speech_metrics(&recording_id).await.map_err(String::from)
+}
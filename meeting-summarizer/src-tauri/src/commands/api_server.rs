@@ -0,0 +1,154 @@
+use crate::database::Database;
+use crate::services::control_server;
+use crate::services::{ApiServer, ApiServerState, ApiServerStatus, AppSettingsManager, GrpcServer, GrpcServerStatus, RecordingService, Summarizer, WhisperService};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type DbState = Arc<Database>;
+type ApiServerHandle = Arc<Mutex<ApiServer>>;
+type GrpcServerHandle = Arc<Mutex<GrpcServer>>;
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+#[tauri::command]
+pub async fn get_api_server_status(
+    api_server: State<'_, ApiServerHandle>,
+) -> Result<ApiServerStatus, String> {
+    let server = api_server.lock().await;
+    Ok(server.get_status())
+}
+
+#[tauri::command]
+pub async fn start_api_server(
+    api_server: State<'_, ApiServerHandle>,
+    settings_manager: State<'_, AppSettingsState>,
+    db: State<'_, DbState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+    summarizer: State<'_, Arc<Summarizer>>,
+    port: Option<u16>,
+) -> Result<ApiServerStatus, String> {
+    let port = match port {
+        Some(port) => port,
+        None => settings_manager.lock().await.get_settings().api_server.port,
+    };
+
+    log::info!("🌐 Starting local API server on port {}", port);
+
+    // 外部操作用サーバーと同じトークンで保護する
+    let token = control_server::load_or_create_token().map_err(String::from)?;
+    let state = ApiServerState {
+        db: db.inner().clone(),
+        recording_service: recording_service.inner().clone(),
+        whisper_service: whisper_service.inner().clone(),
+        summarizer: summarizer.inner().clone(),
+        token,
+    };
+
+    let mut server = api_server.lock().await;
+    server.start(state, port).await.map_err(String::from)?;
+    Ok(server.get_status())
+}
+
+#[tauri::command]
+pub async fn stop_api_server(
+    api_server: State<'_, ApiServerHandle>,
+) -> Result<(), String> {
+    let mut server = api_server.lock().await;
+    server.stop().await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn set_api_server_enabled(
+    settings_manager: State<'_, AppSettingsState>,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<(), String> {
+    log::info!("🌐 Setting API server enabled: {} (port: {:?})", enabled, port);
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| {
+        settings.api_server.enabled = enabled;
+        if let Some(port) = port {
+            settings.api_server.port = port;
+        }
+    });
+
+    manager.save_settings().await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn get_grpc_server_status(
+    grpc_server: State<'_, GrpcServerHandle>,
+) -> Result<GrpcServerStatus, String> {
+    let server = grpc_server.lock().await;
+    Ok(server.get_status())
+}
+
+#[tauri::command]
+pub async fn start_grpc_server(
+    grpc_server: State<'_, GrpcServerHandle>,
+    settings_manager: State<'_, AppSettingsState>,
+    db: State<'_, DbState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+    summarizer: State<'_, Arc<Summarizer>>,
+) -> Result<GrpcServerStatus, String> {
+    let settings = settings_manager.lock().await.get_settings().grpc_server.clone();
+
+    log::info!("📡 Starting local gRPC server on {}:{}", settings.bind_address, settings.port);
+
+    // 外部操作用サーバーと同じトークンで保護する
+    let token = control_server::load_or_create_token().map_err(String::from)?;
+    let state = ApiServerState {
+        db: db.inner().clone(),
+        recording_service: recording_service.inner().clone(),
+        whisper_service: whisper_service.inner().clone(),
+        summarizer: summarizer.inner().clone(),
+        token,
+    };
+
+    let mut server = grpc_server.lock().await;
+    server
+        .start(state, &settings.bind_address, settings.port)
+        .await
+        .map_err(String::from)?;
+    Ok(server.get_status())
+}
+
+#[tauri::command]
+pub async fn stop_grpc_server(
+    grpc_server: State<'_, GrpcServerHandle>,
+) -> Result<(), String> {
+    let mut server = grpc_server.lock().await;
+    server.stop().await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn set_grpc_server_settings(
+    settings_manager: State<'_, AppSettingsState>,
+    enabled: bool,
+    bind_address: Option<String>,
+    port: Option<u16>,
+) -> Result<(), String> {
+    log::info!("📡 Setting gRPC server enabled: {} ({:?}:{:?})", enabled, bind_address, port);
+
+    if let Some(address) = &bind_address {
+        address
+            .parse::<std::net::IpAddr>()
+            .map_err(|_| format!("Invalid bind address: {}", address))?;
+    }
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| {
+        settings.grpc_server.enabled = enabled;
+        if let Some(bind_address) = bind_address {
+            settings.grpc_server.bind_address = bind_address;
+        }
+        if let Some(port) = port {
+            settings.grpc_server.port = port;
+        }
+    });
+
+    manager.save_settings().await.map_err(String::from)
+}
@@ -0,0 +1,48 @@
+use crate::services::audio_edit::{AudioEdit, EditedRecording};
+use crate::services::RecordingService;
+use std::sync::Arc;
+use tauri::State;
+
+/// `start_ms`〜`end_ms` だけを残す（元の録音は backups に残る）
+#[tauri::command]
+pub async fn trim_recording(
+    recording_service: State<'_, Arc<RecordingService>>,
+    recording_id: String,
+    start_ms: i64,
+    end_ms: i64,
+    invalidate_transcriptions: Option<bool>,
+) -> Result<EditedRecording, String> {
+    log::info!("✂️ trim_recording command called: {} ({}ms - {}ms)", recording_id, start_ms, end_ms);
+    recording_service
+        .edit_recording(
+            &recording_id,
+            AudioEdit::Trim,
+            start_ms,
+            end_ms,
+            invalidate_transcriptions.unwrap_or(false),
+        )
+        .await
+        .map_err(String::from)
+}
+
+/// `start_ms`〜`end_ms` を取り除いて前後をつなぐ（元の録音は backups に残る）
+#[tauri::command]
+pub async fn delete_range(
+    recording_service: State<'_, Arc<RecordingService>>,
+    recording_id: String,
+    start_ms: i64,
+    end_ms: i64,
+    invalidate_transcriptions: Option<bool>,
+) -> Result<EditedRecording, String> {
+    log::info!("✂️ delete_range command called: {} ({}ms - {}ms)", recording_id, start_ms, end_ms);
+    recording_service
+        .edit_recording(
+            &recording_id,
+            AudioEdit::DeleteRange,
+            start_ms,
+            end_ms,
+            invalidate_transcriptions.unwrap_or(false),
+        )
+        .await
+        .map_err(String::from)
+}
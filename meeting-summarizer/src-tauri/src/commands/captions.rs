@@ -0,0 +1,75 @@
+use crate::services::{AppSettingsManager, CaptionEvent, CaptionSocketServer, CaptionSocketStatus, LiveCaptionHub};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type CaptionState = Arc<LiveCaptionHub>;
+type CaptionSocketHandle = Arc<Mutex<CaptionSocketServer>>;
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+/// ライブ字幕セッションを開始（以降の字幕は `live-caption` イベントで届く）
+#[tauri::command]
+pub async fn start_live_captions(captions: State<'_, CaptionState>) -> Result<String, String> {
+    Ok(captions.start_session())
+}
+
+#[tauri::command]
+pub async fn stop_live_captions(captions: State<'_, CaptionState>) -> Result<Option<String>, String> {
+    Ok(captions.end_session())
+}
+
+/// 現在のセッションで表示中の字幕（字幕ウィンドウを開いた直後の同期用）
+#[tauri::command]
+pub async fn get_live_captions(captions: State<'_, CaptionState>) -> Result<Vec<CaptionEvent>, String> {
+    Ok(captions.captions())
+}
+
+#[tauri::command]
+pub async fn get_caption_socket_status(
+    caption_socket: State<'_, CaptionSocketHandle>,
+) -> Result<CaptionSocketStatus, String> {
+    Ok(caption_socket.lock().await.get_status())
+}
+
+/// OBS などの外部表示向けに字幕のWebSocket配信を開始する
+#[tauri::command]
+pub async fn start_caption_socket(
+    caption_socket: State<'_, CaptionSocketHandle>,
+    captions: State<'_, CaptionState>,
+    settings_manager: State<'_, AppSettingsState>,
+    port: Option<u16>,
+) -> Result<CaptionSocketStatus, String> {
+    let port = match port {
+        Some(port) => port,
+        None => settings_manager.lock().await.get_settings().caption_socket.port,
+    };
+
+    let mut server = caption_socket.lock().await;
+    server.start(captions.inner().clone(), port).await.map_err(String::from)?;
+    Ok(server.get_status())
+}
+
+#[tauri::command]
+pub async fn stop_caption_socket(caption_socket: State<'_, CaptionSocketHandle>) -> Result<(), String> {
+    caption_socket.lock().await.stop().await.map_err(String::from)
+}
+
+/// 起動時に字幕のWebSocket配信を開始するか
+#[tauri::command]
+pub async fn set_caption_socket_enabled(
+    settings_manager: State<'_, AppSettingsState>,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<(), String> {
+    log::info!("💬 Setting caption WebSocket enabled: {} (port: {:?})", enabled, port);
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| {
+        settings.caption_socket.enabled = enabled;
+        if let Some(port) = port {
+            settings.caption_socket.port = port;
+        }
+    });
+
+    manager.save_settings().await.map_err(String::from)
+}
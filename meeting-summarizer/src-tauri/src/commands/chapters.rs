@@ -0,0 +1,119 @@
+use crate::database::Database;
+use crate::errors::AppError;
+use crate::models::{Chapter, ChapterSource};
+use crate::services::chapters::{self, ChapterDraft};
+use crate::services::i18n::tr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::State;
+
+type DbState = Arc<Database>;
+
+#[tauri::command]
+pub async fn get_recording_chapters(
+    db: State<'_, DbState>,
+    recording_id: String,
+) -> Result<Vec<Chapter>, String> {
+    db.get_chapters_by_recording(&recording_id)
+        .await
+        .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn add_chapter(
+    db: State<'_, DbState>,
+    recording_id: String,
+    title: String,
+    start_ms: i64,
+    end_ms: Option<i64>,
+) -> Result<Chapter, String> {
+    db.get_recording(&recording_id)
+        .await
+        .map_err(String::from)?
+        .ok_or_else(|| tr("command.recording_not_found", &[("id", &recording_id)]))?;
+
+    let mut chapter = Chapter::new(recording_id, title.trim().to_string(), start_ms);
+    chapter.end_ms = end_ms;
+    chapters::validate_chapter(&chapter).map_err(String::from)?;
+
+    db.create_chapter(&chapter).await.map_err(String::from)?;
+    Ok(chapter)
+}
+
+#[tauri::command]
+pub async fn update_chapter(db: State<'_, DbState>, chapter: Chapter) -> Result<bool, String> {
+    chapters::validate_chapter(&chapter).map_err(String::from)?;
+    db.update_chapter(&chapter).await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn delete_chapter(db: State<'_, DbState>, id: String) -> Result<bool, String> {
+    db.delete_chapter(&id).await.map_err(String::from)
+}
+
+/// トピック分割の結果でチャプターを置き換える（手動で追加したチャプターは残す）
+#[tauri::command]
+pub async fn set_topic_chapters(
+    db: State<'_, DbState>,
+    recording_id: String,
+    drafts: Vec<ChapterDraft>,
+) -> Result<Vec<Chapter>, String> {
+    log::info!("📑 Setting {} topic chapters for recording {}", drafts.len(), recording_id);
+
+    let topic_chapters = drafts
+        .into_iter()
+        .map(|draft| {
+            let mut chapter = Chapter::new(recording_id.clone(), draft.title.trim().to_string(), draft.start_ms)
+                .with_source(ChapterSource::Topic);
+            chapter.end_ms = draft.end_ms;
+            chapters::validate_chapter(&chapter).map(|_| chapter)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(String::from)?;
+
+    db.replace_chapters(&recording_id, ChapterSource::Topic, &topic_chapters)
+        .await
+        .map_err(String::from)?;
+    db.get_chapters_by_recording(&recording_id)
+        .await
+        .map_err(String::from)
+}
+
+/// MP3 ファイルにチャプターメタデータを書き込む（省略時は録音ファイル自体が MP3 の場合に限る）
+#[tauri::command]
+pub async fn write_mp3_chapters(
+    db: State<'_, DbState>,
+    recording_id: String,
+    mp3_path: Option<String>,
+) -> Result<(), String> {
+    let recording = db
+        .get_recording(&recording_id)
+        .await
+        .map_err(String::from)?
+        .ok_or_else(|| tr("command.recording_not_found", &[("id", &recording_id)]))?;
+
+    let path = PathBuf::from(mp3_path.as_deref().unwrap_or(&recording.file_path));
+    let is_mp3 = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("mp3"));
+    if !is_mp3 {
+        return Err(AppError::ValidationError {
+            message: format!("Chapter metadata can only be written to MP3 files: {}", path.display()),
+        }
+        .into());
+    }
+
+    let recording_chapters = db
+        .get_chapters_by_recording(&recording_id)
+        .await
+        .map_err(String::from)?;
+    let title = recording.title.clone();
+    let total_ms = recording.duration.map(|seconds| seconds * 1000);
+
+    tokio::task::spawn_blocking(move || {
+        chapters::write_mp3_chapters(&path, title.as_deref(), &recording_chapters, total_ms)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(String::from)
+}
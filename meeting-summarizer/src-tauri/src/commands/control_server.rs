@@ -0,0 +1,101 @@
+use crate::database::Database;
+use crate::services::control_server;
+use crate::services::{ApiServer, AppSettingsManager, GrpcServer, ControlServer, ControlServerStatus, JobQueue, RecordingService};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type DbState = Arc<Database>;
+type ControlServerHandle = Arc<Mutex<ControlServer>>;
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+type ApiServerHandle = Arc<Mutex<ApiServer>>;
+type GrpcServerHandle = Arc<Mutex<GrpcServer>>;
+
+#[tauri::command]
+pub async fn get_control_server_status(
+    control_server: State<'_, ControlServerHandle>,
+) -> Result<ControlServerStatus, String> {
+    Ok(control_server.lock().await.get_status())
+}
+
+/// Stream Deck などの外部ツールから操作するためのローカルサーバーを開始する
+#[tauri::command]
+pub async fn start_control_server(
+    control_server: State<'_, ControlServerHandle>,
+    settings_manager: State<'_, AppSettingsState>,
+    db: State<'_, DbState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    job_queue: State<'_, Arc<JobQueue>>,
+    port: Option<u16>,
+) -> Result<ControlServerStatus, String> {
+    let port = match port {
+        Some(port) => port,
+        None => settings_manager.lock().await.get_settings().control_server.port,
+    };
+    let token = control_server::load_or_create_token().map_err(String::from)?;
+
+    let mut server = control_server.lock().await;
+    server
+        .start(db.inner().clone(), recording_service.inner().clone(), job_queue.inner().clone(), token, port)
+        .await
+        .map_err(String::from)?;
+    Ok(server.get_status())
+}
+
+#[tauri::command]
+pub async fn stop_control_server(control_server: State<'_, ControlServerHandle>) -> Result<(), String> {
+    control_server.lock().await.stop().await.map_err(String::from)
+}
+
+/// 起動時に外部操作用のサーバーを開始するか
+#[tauri::command]
+pub async fn set_control_server_enabled(
+    settings_manager: State<'_, AppSettingsState>,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<(), String> {
+    log::info!("🎛️ Setting control server enabled: {} (port: {:?})", enabled, port);
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| {
+        settings.control_server.enabled = enabled;
+        if let Some(port) = port {
+            settings.control_server.port = port;
+        }
+    });
+
+    manager.save_settings().await.map_err(String::from)
+}
+
+/// 外部ツールに設定するトークン
+#[tauri::command]
+pub async fn get_control_token() -> Result<String, String> {
+    control_server::load_or_create_token().map_err(String::from)
+}
+
+/// トークンを作り直す（同じトークンを使う REST / gRPC API も含め、起動中なら新しいトークンで開き直す）
+#[tauri::command]
+pub async fn regenerate_control_token(
+    control_server: State<'_, ControlServerHandle>,
+    api_server: State<'_, ApiServerHandle>,
+    grpc_server: State<'_, GrpcServerHandle>,
+    db: State<'_, DbState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    job_queue: State<'_, Arc<JobQueue>>,
+) -> Result<String, String> {
+    let token = control_server::regenerate_token().map_err(String::from)?;
+
+    let mut server = control_server.lock().await;
+    if let Some(address) = server.address() {
+        server.stop().await.map_err(String::from)?;
+        server
+            .start(db.inner().clone(), recording_service.inner().clone(), job_queue.inner().clone(), token.clone(), address.port())
+            .await
+            .map_err(String::from)?;
+    }
+    drop(server);
+
+    api_server.lock().await.restart_with_token(token.clone()).await.map_err(String::from)?;
+    grpc_server.lock().await.restart_with_token(token.clone()).await.map_err(String::from)?;
+    Ok(token)
+}
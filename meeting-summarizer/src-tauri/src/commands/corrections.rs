@@ -0,0 +1,40 @@
+use crate::database::Database;
+use crate::models::CorrectionEntry;
+use crate::services::corrections;
+use std::sync::Arc;
+use tauri::State;
+
+type DbState = Arc<Database>;
+
+/// 補正辞書の一覧（置き換え回数の多い順）
+#[tauri::command]
+pub async fn get_correction_entries(db: State<'_, DbState>) -> Result<Vec<CorrectionEntry>, String> {
+    db.get_correction_entries().await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn add_correction_entry(
+    db: State<'_, DbState>,
+    wrong: String,
+    correct: String,
+) -> Result<CorrectionEntry, String> {
+    let wrong = wrong.trim().to_string();
+    let correct = correct.trim().to_string();
+    corrections::validate_entry(&wrong, &correct).map_err(String::from)?;
+
+    let entry = CorrectionEntry::new(wrong, correct);
+    db.create_correction_entry(&entry).await.map_err(String::from)?;
+    log::info!("📝 Added correction: {} → {}", entry.wrong, entry.correct);
+    Ok(entry)
+}
+
+#[tauri::command]
+pub async fn update_correction_entry(db: State<'_, DbState>, entry: CorrectionEntry) -> Result<bool, String> {
+    corrections::validate_entry(&entry.wrong, &entry.correct).map_err(String::from)?;
+    db.update_correction_entry(&entry).await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn delete_correction_entry(db: State<'_, DbState>, id: String) -> Result<bool, String> {
+    db.delete_correction_entry(&id).await.map_err(String::from)
+}
@@ -0,0 +1,29 @@
+use crate::services::demo_mode;
+use crate::services::AppSettingsManager;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+#[tauri::command]
+pub async fn get_demo_mode() -> Result<bool, String> {
+    Ok(demo_mode::is_enabled())
+}
+
+/// デモモードを切り替えて保存（次の録音・書き起こし・要約から反映）
+#[tauri::command]
+pub async fn set_demo_mode(
+    settings_manager: State<'_, AppSettingsState>,
+    enabled: bool,
+) -> Result<bool, String> {
+    demo_mode::set_enabled(enabled);
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| {
+        settings.demo_mode = enabled;
+    });
+    manager.save_settings().await.map_err(String::from)?;
+
+    Ok(enabled)
+}
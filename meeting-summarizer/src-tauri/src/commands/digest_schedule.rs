@@ -0,0 +1,78 @@
+use crate::services::digest_schedule::{self, ScheduledDigestRun};
+use crate::services::{
+    AppSettingsManager, DigestDelivery, DigestFrequency, DigestScheduleSettings, Summarizer,
+};
+use crate::models::LLMConfig;
+use chrono::{Local, Utc};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+type SummarizerState = Arc<Summarizer>;
+
+#[tauri::command]
+pub async fn get_digest_schedule(
+    settings_manager: State<'_, AppSettingsState>,
+) -> Result<DigestScheduleSettings, String> {
+    Ok(settings_manager.lock().await.get_settings().digest_schedule.clone())
+}
+
+/// 定期ダイジェストの設定を保存（有効にした時点より前の回はさかのぼって作らない）
+#[tauri::command]
+pub async fn set_digest_schedule(
+    settings_manager: State<'_, AppSettingsState>,
+    enabled: bool,
+    frequency: DigestFrequency,
+    delivery: Option<DigestDelivery>,
+    model_config: Option<LLMConfig>,
+) -> Result<DigestScheduleSettings, String> {
+    frequency.validate().map_err(String::from)?;
+    match &delivery {
+        Some(delivery) => delivery.validate().map_err(String::from)?,
+        None if enabled => return Err("A delivery channel is required for scheduled digests".to_string()),
+        None => {}
+    }
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| {
+        let schedule = &mut settings.digest_schedule;
+        if enabled && (!schedule.enabled || schedule.frequency != frequency) {
+            schedule.last_run_at = Some(Utc::now());
+        }
+        schedule.enabled = enabled;
+        schedule.frequency = frequency;
+        schedule.delivery = delivery;
+        schedule.model_config = model_config;
+    });
+    manager.save_settings().await.map_err(String::from)?;
+
+    log::info!("📰 Digest schedule {}", if enabled { "enabled" } else { "disabled" });
+    Ok(manager.get_settings().digest_schedule.clone())
+}
+
+/// メール配信に使う SMTP のパスワード（キーチェーンに保存）
+#[tauri::command]
+pub async fn set_digest_smtp_password(password: Option<String>) -> Result<(), String> {
+    match password.filter(|password| !password.is_empty()) {
+        Some(password) => digest_schedule::save_smtp_password(&password),
+        None => digest_schedule::clear_smtp_password(),
+    }
+    .map_err(String::from)
+}
+
+/// 予定を待たずに直前の期間のダイジェストを作って届ける（配信の確認用。実行済みの記録は変えない）
+#[tauri::command]
+pub async fn run_digest_schedule_now(
+    settings_manager: State<'_, AppSettingsState>,
+    summarizer: State<'_, SummarizerState>,
+) -> Result<ScheduledDigestRun, String> {
+    let schedule = settings_manager.lock().await.get_settings().digest_schedule.clone();
+    let delivery = schedule
+        .delivery
+        .ok_or_else(|| "A delivery channel is required for scheduled digests".to_string())?;
+
+    let now = Local::now();
+    let from = digest_schedule::period_start(&schedule.frequency, &now).with_timezone(&Utc);
+    Ok(digest_schedule::run_scheduled_digest(summarizer.inner(), schedule.model_config, &delivery, from, now.with_timezone(&Utc)).await)
+}
@@ -1,21 +1,47 @@
 use crate::database::Database;
-use crate::models::{Recording, Transcription, RecordingQuery, RecordingStats, SortBy, SortOrder};
+use crate::errors::AppError;
+use crate::services::i18n::tr;
+use crate::models::{CategoryLanguage, ColorLabel, LLMConfig, QueryCondition, Recording, RecordingListItem, RedactionEntry, Summary, Transcription, RecordingQuery, RecordingStats, SortBy, SortOrder, TaxonomyNode, TranscriptionStatusFilter};
+use crate::services::pdf_export::{self, PdfProtection};
+use crate::services::recording_conversion::{self, RecordingConversion};
+use crate::services::redaction::{self, RedactedExport, RedactionOptions};
+use crate::services::anonymize::{self, AnonymizeOptions, AnonymizedExport};
+use crate::services::share_bundle::{self, ShareBundle};
+use crate::services::speaker_tracks::{self, SpeakerTrack};
+use crate::services::{audio_stream, export, html_export, taxonomy, LLMService};
 use std::sync::Arc;
 use tauri::State;
-use tokio::sync::Mutex;
 
-type DbState = Arc<Mutex<Database>>;
+type DbState = Arc<Database>;
 
 #[tauri::command]
-pub async fn get_all_recordings_fm(db: State<'_, DbState>) -> Result<Vec<Recording>, String> {
-    let database = db.lock().await;
-    database.get_all_recordings().await.map_err(|e| e.to_string())
+pub async fn get_all_recordings_fm(db: State<'_, DbState>, include_archived: Option<bool>) -> Result<Vec<RecordingListItem>, String> {
+    let database = db.inner();
+    database.get_recordings_with_status(include_archived.unwrap_or(false)).await.map_err(String::from)
 }
 
 #[tauri::command]
 pub async fn get_recording_by_id(db: State<'_, DbState>, id: String) -> Result<Option<Recording>, String> {
-    let database = db.lock().await;
-    database.get_recording(&id).await.map_err(|e| e.to_string())
+    let database = db.inner();
+    database.get_recording(&id).await.map_err(String::from)
+}
+
+/// 条件の組み合わせに含まれるカテゴリ・タグの階層パスを正規化する
+fn normalize_condition(condition: QueryCondition) -> QueryCondition {
+    match condition {
+        QueryCondition::And { conditions } => QueryCondition::And {
+            conditions: conditions.into_iter().map(normalize_condition).collect(),
+        },
+        QueryCondition::Or { conditions } => QueryCondition::Or {
+            conditions: conditions.into_iter().map(normalize_condition).collect(),
+        },
+        QueryCondition::Not { condition } => QueryCondition::Not {
+            condition: Box::new(normalize_condition(*condition)),
+        },
+        QueryCondition::Category { path } => QueryCondition::Category { path: taxonomy::normalize_path(&path) },
+        QueryCondition::Tag { path } => QueryCondition::Tag { path: taxonomy::normalize_path(&path) },
+        other => other,
+    }
 }
 
 #[tauri::command]
@@ -28,12 +54,22 @@ pub async fn search_recordings(
     date_to: Option<String>,
     min_duration: Option<i64>,
     max_duration: Option<i64>,
+    min_silence_ratio: Option<f64>,
+    max_silence_ratio: Option<f64>,
+    category_path: Option<String>,
+    tag_paths: Option<Vec<String>>,
+    color_labels: Option<Vec<ColorLabel>>,
+    include_archived: Option<bool>,
+    has_transcription: Option<bool>,
+    has_summary: Option<bool>,
+    transcription_statuses: Option<Vec<TranscriptionStatusFilter>>,
+    condition: Option<QueryCondition>,
     sort_by: Option<String>,
     sort_order: Option<String>,
     limit: Option<i32>,
     offset: Option<i32>,
-) -> Result<Vec<Recording>, String> {
-    let database = db.lock().await;
+) -> Result<Vec<RecordingListItem>, String> {
+    let database = db.inner();
     
     // Parse dates
     let date_from_parsed = if let Some(date_str) = date_from {
@@ -77,13 +113,27 @@ pub async fn search_recordings(
         date_to: date_to_parsed,
         min_duration,
         max_duration,
+        min_silence_ratio,
+        max_silence_ratio,
+        category_path: category_path.map(|path| taxonomy::normalize_path(&path)),
+        tag_paths: tag_paths
+            .unwrap_or_default()
+            .iter()
+            .map(|path| taxonomy::normalize_path(path))
+            .collect(),
+        color_labels: color_labels.unwrap_or_default(),
+        include_archived: include_archived.unwrap_or(false),
+        has_transcription,
+        has_summary,
+        transcription_statuses: transcription_statuses.unwrap_or_default(),
+        condition: condition.map(normalize_condition),
         limit: Some(limit.unwrap_or(50)),
         offset: Some(offset.unwrap_or(0)),
         sort_by: sort_by_parsed,
         sort_order: sort_order_parsed,
     };
 
-    database.search_recordings(&query).await.map_err(|e| e.to_string())
+    database.search_recordings_with_status(&query).await.map_err(String::from)
 }
 
 #[tauri::command]
@@ -95,14 +145,14 @@ pub async fn update_recording_metadata(
     category: Option<String>,
     tags: Option<Vec<String>>,
 ) -> Result<(), String> {
-    let database = db.lock().await;
+    let database = db.inner();
     
     // Get existing recording
     let mut recording = database
         .get_recording(&id)
         .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Recording with id {} not found", id))?;
+        .map_err(String::from)?
+        .ok_or_else(|| tr("command.recording_not_found", &[("id", &id)]))?;
 
     // Update fields
     if let Some(title) = title {
@@ -111,38 +161,122 @@ pub async fn update_recording_metadata(
     if let Some(description) = description {
         recording.description = Some(description);
     }
+    // カテゴリ・タグは "Clients/ACME" のような階層パスとして正規化する
     if let Some(category) = category {
-        recording.category = Some(category);
+        let category = taxonomy::normalize_path(&category);
+        recording.category = (!category.is_empty()).then_some(category);
     }
     if let Some(tags) = tags {
-        recording.tags = tags;
+        recording.tags = tags
+            .iter()
+            .map(|tag| taxonomy::normalize_path(tag))
+            .filter(|tag| !tag.is_empty())
+            .collect();
     }
 
-    database.update_recording(&recording).await.map_err(|e| e.to_string())
+    database.update_recording(&recording).await.map_err(String::from)
+}
+
+/// カテゴリの階層ツリー（親ノードは子孫の録音も含めて集計）
+#[tauri::command]
+pub async fn get_category_tree(db: State<'_, DbState>) -> Result<Vec<TaxonomyNode>, String> {
+    let assignments = db.get_category_assignments().await.map_err(String::from)?;
+    Ok(taxonomy::build_tree(&assignments))
+}
+
+/// タグの階層ツリー（親ノードは子孫の録音も含めて集計）
+#[tauri::command]
+pub async fn get_tag_tree(db: State<'_, DbState>) -> Result<Vec<TaxonomyNode>, String> {
+    let assignments = db.get_tag_assignments().await.map_err(String::from)?;
+    Ok(taxonomy::build_tree(&assignments))
+}
+
+/// 複数の録音に色ラベルを設定（color_label が None なら解除）し、更新件数を返す
+#[tauri::command]
+pub async fn set_recording_color_label(
+    db: State<'_, DbState>,
+    recording_ids: Vec<String>,
+    color_label: Option<ColorLabel>,
+) -> Result<usize, String> {
+    db.set_color_labels(&recording_ids, color_label).await.map_err(String::from)
+}
+
+/// 複数の録音をアーカイブ（削除せず既定の一覧・検索から隠す）
+#[tauri::command]
+pub async fn archive_recordings(db: State<'_, DbState>, recording_ids: Vec<String>) -> Result<usize, String> {
+    db.set_archived(&recording_ids, true).await.map_err(String::from)
+}
+
+/// 複数の録音のアーカイブを解除
+#[tauri::command]
+pub async fn unarchive_recordings(db: State<'_, DbState>, recording_ids: Vec<String>) -> Result<usize, String> {
+    db.set_archived(&recording_ids, false).await.map_err(String::from)
 }
 
 #[tauri::command]
 pub async fn delete_recording_fm(db: State<'_, DbState>, id: String) -> Result<bool, String> {
-    let database = db.lock().await;
-    database.delete_recording(&id).await.map_err(|e| e.to_string())
+    let database = db.inner();
+    database.delete_recording(&id).await.map_err(String::from)
 }
 
 #[tauri::command]
 pub async fn get_recording_stats(db: State<'_, DbState>) -> Result<RecordingStats, String> {
-    let database = db.lock().await;
-    database.get_recording_stats().await.map_err(|e| e.to_string())
+    let database = db.inner();
+    database.get_recording_stats().await.map_err(String::from)
 }
 
 #[tauri::command]
 pub async fn get_all_categories(db: State<'_, DbState>) -> Result<Vec<String>, String> {
-    let database = db.lock().await;
-    database.get_all_categories().await.map_err(|e| e.to_string())
+    let database = db.inner();
+    database.get_all_categories().await.map_err(String::from)
+}
+
+/// カテゴリごとの既定の書き起こし言語
+#[tauri::command]
+pub async fn get_category_languages(db: State<'_, DbState>) -> Result<Vec<CategoryLanguage>, String> {
+    let database = db.inner();
+    database.get_category_languages().await.map_err(String::from)
+}
+
+/// カテゴリの既定の書き起こし言語を設定（language が None なら解除）
+#[tauri::command]
+pub async fn set_category_language(
+    db: State<'_, DbState>,
+    category: String,
+    language: Option<String>,
+) -> Result<(), String> {
+    let database = db.inner();
+    let category = taxonomy::normalize_path(&category);
+    let category = category.as_str();
+    if category.is_empty() {
+        return Err(AppError::ValidationError { message: "Category must not be empty".to_string() }.into());
+    }
+
+    match language.as_deref().map(str::trim).filter(|language| !language.is_empty()) {
+        Some(language) => {
+            if language.len() > 10 || !language.chars().all(|c| c.is_ascii_alphabetic() || c == '-') {
+                return Err(AppError::ValidationError {
+                    message: format!("Invalid language code: {}", language),
+                }
+                .into());
+            }
+            database
+                .set_category_language(category, &language.to_lowercase())
+                .await
+                .map_err(String::from)
+        }
+        None => database
+            .delete_category_language(category)
+            .await
+            .map(|_| ())
+            .map_err(String::from),
+    }
 }
 
 #[tauri::command]
 pub async fn get_all_tags(db: State<'_, DbState>) -> Result<Vec<String>, String> {
-    let database = db.lock().await;
-    database.get_all_tags().await.map_err(|e| e.to_string())
+    let database = db.inner();
+    database.get_all_tags().await.map_err(String::from)
 }
 
 // Transcription management commands
@@ -151,11 +285,11 @@ pub async fn get_transcriptions_by_recording(
     db: State<'_, DbState>,
     recording_id: String,
 ) -> Result<Vec<Transcription>, String> {
-    let database = db.lock().await;
+    let database = db.inner();
     database
         .get_transcriptions_by_recording(&recording_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(String::from)
 }
 
 #[tauri::command]
@@ -163,8 +297,8 @@ pub async fn get_transcription_by_id(
     db: State<'_, DbState>,
     id: String,
 ) -> Result<Option<Transcription>, String> {
-    let database = db.lock().await;
-    database.get_transcription(&id).await.map_err(|e| e.to_string())
+    let database = db.inner();
+    database.get_transcription(&id).await.map_err(String::from)
 }
 
 // File export functionality
@@ -174,70 +308,228 @@ pub async fn export_recording_data(
     recording_id: String,
     format: String,
 ) -> Result<String, String> {
-    let database = db.lock().await;
-    
-    let recording = database
-        .get_recording(&recording_id)
+    let database = db.inner();
+    export::export_recording(database, &recording_id, &format)
         .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Recording with id {} not found", recording_id))?;
+        .map_err(String::from)
+}
 
-    let transcriptions = database
-        .get_transcriptions_by_recording(&recording_id)
+/// メールアドレス・電話番号（任意で人名）をプレースホルダーに置き換えて書き出す
+#[tauri::command]
+pub async fn export_recording_redacted(
+    db: State<'_, DbState>,
+    recording_id: String,
+    format: String,
+    options: Option<RedactionOptions>,
+    model_config: Option<LLMConfig>,
+) -> Result<RedactedExport, String> {
+    let database = db.inner();
+    let llm_service = LLMService::new(model_config.unwrap_or_default());
+    redaction::export_recording_redacted(
+        database,
+        Some(&llm_service),
+        &recording_id,
+        &format,
+        &options.unwrap_or_default(),
+    )
+    .await
+    .map_err(String::from)
+}
+
+/// 個人情報を置き換えた要約のコピーを取得（保存済みの要約は変更しない）
+#[tauri::command]
+pub async fn get_redacted_summary(
+    db: State<'_, DbState>,
+    summary_id: String,
+    options: Option<RedactionOptions>,
+    model_config: Option<LLMConfig>,
+) -> Result<Summary, String> {
+    let database = db.inner();
+    let llm_service = LLMService::new(model_config.unwrap_or_default());
+    redaction::redact_summary(database, Some(&llm_service), &summary_id, &options.unwrap_or_default())
         .await
-        .map_err(|e| e.to_string())?;
-
-    match format.as_str() {
-        "json" => {
-            let export_data = serde_json::json!({
-                "recording": recording,
-                "transcriptions": transcriptions,
-                "exported_at": chrono::Utc::now().to_rfc3339(),
-            });
-            Ok(serde_json::to_string_pretty(&export_data).map_err(|e| e.to_string())?)
-        }
-        "text" => {
-            let mut result = String::new();
-            result.push_str(&format!("=== Recording: {} ===\n", recording.filename));
-            result.push_str(&format!("Created: {}\n", recording.created_at.format("%Y-%m-%d %H:%M:%S")));
-            
-            if let Some(title) = &recording.title {
-                result.push_str(&format!("Title: {}\n", title));
-            }
-            if let Some(description) = &recording.description {
-                result.push_str(&format!("Description: {}\n", description));
-            }
-            if let Some(category) = &recording.category {
-                result.push_str(&format!("Category: {}\n", category));
-            }
-            if !recording.tags.is_empty() {
-                result.push_str(&format!("Tags: {}\n", recording.tags.join(", ")));
-            }
-            if let Some(duration) = recording.duration {
-                result.push_str(&format!("Duration: {}s\n", duration));
-            }
+        .map_err(String::from)
+}
 
-            result.push_str("\n=== Transcriptions ===\n");
-            for transcription in transcriptions {
-                result.push_str(&format!("\n--- {} (Confidence: {:.2}) ---\n", 
-                    transcription.language,
-                    transcription.confidence.unwrap_or(0.0)
-                ));
-                result.push_str(&transcription.text);
-                result.push_str("\n");
-            }
+/// 話者・参加者を「Participant A」のようなラベルに置き換えて書き出す（要約も同じラベルで置き換える）
+#[tauri::command]
+pub async fn export_recording_anonymized(
+    db: State<'_, DbState>,
+    recording_id: String,
+    format: String,
+    options: Option<AnonymizeOptions>,
+    model_config: Option<LLMConfig>,
+) -> Result<AnonymizedExport, String> {
+    let database = db.inner();
+    let llm_service = LLMService::new(model_config.unwrap_or_default());
+    anonymize::export_recording_anonymized(
+        database,
+        Some(&llm_service),
+        &recording_id,
+        &format,
+        &options.unwrap_or_default(),
+    )
+    .await
+    .map_err(String::from)
+}
 
-            Ok(result)
-        }
-        _ => Err(format!("Unsupported export format: {}", format)),
-    }
+#[tauri::command]
+pub async fn get_anonymized_summary(
+    db: State<'_, DbState>,
+    summary_id: String,
+    options: Option<AnonymizeOptions>,
+    model_config: Option<LLMConfig>,
+) -> Result<Summary, String> {
+    let database = db.inner();
+    let llm_service = LLMService::new(model_config.unwrap_or_default());
+    anonymize::anonymize_summary(database, Some(&llm_service), &summary_id, &options.unwrap_or_default())
+        .await
+        .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn get_redaction_mappings(
+    db: State<'_, DbState>,
+    recording_id: String,
+) -> Result<Vec<RedactionEntry>, String> {
+    let database = db.inner();
+    database.get_redaction_mappings(&recording_id).await.map_err(String::from)
+}
+
+/// 対応表を削除する（以降の書き出しではプレースホルダーが振り直される）
+#[tauri::command]
+pub async fn clear_redaction_mappings(
+    db: State<'_, DbState>,
+    recording_id: String,
+) -> Result<usize, String> {
+    let database = db.inner();
+    database.delete_redaction_mappings(&recording_id).await.map_err(String::from)
+}
+
+/// 要約とタイムスタンプ付き書き起こしを含む単体の HTML を生成（音声の埋め込みは任意）
+#[tauri::command]
+pub async fn export_recording_html(
+    db: State<'_, DbState>,
+    recording_id: String,
+    include_audio: Option<bool>,
+) -> Result<String, String> {
+    let database = db.inner();
+    html_export::export_recording_html(database, &recording_id, include_audio.unwrap_or(false))
+        .await
+        .map_err(String::from)
+}
+
+/// 要約・書き起こし（任意で音声）をパスワード付きの暗号化 ZIP にまとめる（パスワードは保存しない）
+#[tauri::command]
+pub async fn create_share_bundle(
+    db: State<'_, DbState>,
+    recording_id: String,
+    output_path: String,
+    password: String,
+    include_audio: Option<bool>,
+) -> Result<ShareBundle, String> {
+    log::info!("🔐 Creating share bundle for recording {}", recording_id);
+    let database = db.inner();
+    share_bundle::create_share_bundle(
+        database,
+        &recording_id,
+        std::path::Path::new(&output_path),
+        &password,
+        include_audio.unwrap_or(false),
+    )
+    .await
+    .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn export_summary_ical(
+    db: State<'_, DbState>,
+    summary_id: String,
+) -> Result<String, String> {
+    let database = db.inner();
+    export::export_summary_ical(database, &summary_id)
+        .await
+        .map_err(String::from)
+}
+
+/// 要約を PDF に書き出す（パスワードを指定した場合は暗号化する）
+#[tauri::command]
+pub async fn export_summary_pdf(
+    db: State<'_, DbState>,
+    summary_id: String,
+    output_path: String,
+    protection: Option<PdfProtection>,
+) -> Result<(), String> {
+    let database = db.inner();
+    pdf_export::export_summary_pdf(
+        database,
+        &summary_id,
+        std::path::Path::new(&output_path),
+        &protection.unwrap_or_default(),
+    )
+    .await
+    .map_err(String::from)
+}
+
+/// 話者ごとの音声トラック（他の話者を無音にした WAV）を `output_dir` に書き出す
+#[tauri::command]
+pub async fn export_speaker_tracks(
+    db: State<'_, DbState>,
+    recording_id: String,
+    output_dir: String,
+) -> Result<Vec<SpeakerTrack>, String> {
+    let database = db.inner();
+    speaker_tracks::export_speaker_tracks(database, &recording_id, std::path::Path::new(&output_dir))
+        .await
+        .map_err(String::from)
+}
+
+/// 録音を別形式（wav / flac / whisper）に変換
+#[tauri::command]
+pub async fn convert_recording(
+    db: State<'_, DbState>,
+    recording_id: String,
+    target_format: String,
+    replace_original: Option<bool>,
+) -> Result<RecordingConversion, String> {
+    let database = db.inner();
+    recording_conversion::convert_recording(
+        database,
+        &recording_id,
+        &target_format,
+        replace_original.unwrap_or(false),
+    )
+    .await
+    .map_err(String::from)
+}
+
+/// 波形表示用のピーク値を取得（WAVをチャンク読み込み）
+#[tauri::command]
+pub async fn get_waveform_peaks(
+    db: State<'_, DbState>,
+    recording_id: String,
+    buckets: Option<usize>,
+) -> Result<Vec<f32>, String> {
+    let recording = db
+        .get_recording(&recording_id)
+        .await
+        .map_err(String::from)?
+        .ok_or_else(|| tr("command.recording_not_found", &[("id", &recording_id)]))?;
+
+    let buckets = buckets.unwrap_or(800).min(10_000);
+    tokio::task::spawn_blocking(move || {
+        audio_stream::waveform_peaks(std::path::Path::new(&recording.file_path), buckets)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(String::from)
 }
 
 // File management utility functions
 #[tauri::command]
 pub async fn get_recordings_count_fm(db: State<'_, DbState>) -> Result<i64, String> {
-    let database = db.lock().await;
-    database.get_recordings_count().await.map_err(|e| e.to_string())
+    let database = db.inner();
+    database.get_recordings_count().await.map_err(String::from)
 }
 
 #[tauri::command]
@@ -245,24 +537,28 @@ pub async fn cleanup_orphaned_files(
     db: State<'_, DbState>,
     recordings_dir: String,
 ) -> Result<Vec<String>, String> {
-    let database = db.lock().await;
-    let recordings = database.get_all_recordings().await.map_err(|e| e.to_string())?;
+    let database = db.inner();
+    let recordings = database.get_all_recordings().await.map_err(String::from)?;
+    let segment_paths = database.get_all_recording_segment_paths().await.map_err(String::from)?;
+    let track_paths = database.get_all_recording_track_paths().await.map_err(String::from)?;
     
     let mut orphaned_files = Vec::new();
     let recordings_path = std::path::Path::new(&recordings_dir);
     
     if recordings_path.exists() && recordings_path.is_dir() {
-        let entries = std::fs::read_dir(recordings_path).map_err(|e| e.to_string())?;
+        let entries = std::fs::read_dir(recordings_path).map_err(|e| AppError::from(e).localized())?;
         
         for entry in entries {
-            let entry = entry.map_err(|e| e.to_string())?;
+            let entry = entry.map_err(|e| AppError::from(e).localized())?;
             let file_path = entry.path();
             
             if file_path.is_file() {
                 let file_path_str = file_path.to_string_lossy().to_string();
                 
                 // Check if this file is referenced by any recording
-                let is_referenced = recordings.iter().any(|r| r.file_path == file_path_str);
+                let is_referenced = recordings.iter().any(|r| r.file_path == file_path_str)
+                    || segment_paths.contains(&file_path_str)
+                    || track_paths.contains(&file_path_str);
                 
                 if !is_referenced {
                     orphaned_files.push(file_path_str);
@@ -0,0 +1,183 @@
+use crate::database::Database;
+use crate::models::{Attachment, Participant, TranscriptSegment, Transcription};
+use crate::services::{channel_separation, transcript_import};
+use crate::services::watched_folders::{self, WatchedImport};
+use crate::services::{
+    AppSettingsManager, ImportedMeeting, ImportedTranscript, JobQueue, RecordingService, TranscriptFormat,
+    WatchedFolderRule, WhisperService,
+};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type DbState = Arc<Database>;
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+#[tauri::command]
+pub async fn import_meeting_folder(
+    recording_service: State<'_, Arc<RecordingService>>,
+    folder_path: String,
+) -> Result<ImportedMeeting, String> {
+    log::info!("📥 import_meeting_folder command called: {}", folder_path);
+
+    let folder = PathBuf::from(&folder_path);
+    recording_service
+        .import_meeting_folder(&folder)
+        .await
+        .map_err(|e| {
+            log::error!("❌ Failed to import meeting folder {}: {}", folder_path, e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn get_recording_participants(
+    db: State<'_, DbState>,
+    recording_id: String,
+) -> Result<Vec<Participant>, String> {
+    let database = db.inner();
+    database
+        .get_participants_by_recording(&recording_id)
+        .await
+        .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn get_recording_attachments(
+    db: State<'_, DbState>,
+    recording_id: String,
+) -> Result<Vec<Attachment>, String> {
+    let database = db.inner();
+    database
+        .get_attachments_by_recording(&recording_id)
+        .await
+        .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn import_transcript(
+    db: State<'_, DbState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    file_path: String,
+    recording_id: Option<String>,
+    format: Option<TranscriptFormat>,
+) -> Result<ImportedTranscript, String> {
+    log::info!("📥 import_transcript command called: {}", file_path);
+
+    let database = db.inner();
+    transcript_import::import_transcript_file(
+        database,
+        recording_service.recordings_dir(),
+        &PathBuf::from(&file_path),
+        recording_id.as_deref(),
+        format,
+    )
+    .await
+    .map_err(|e| {
+        log::error!("❌ Failed to import transcript {}: {}", file_path, e);
+        e.to_string()
+    })
+}
+
+/// ステレオの通話録音をチャンネルごとに書き起こし、`speakers` の話者名を付けて保存する
+#[tauri::command]
+pub async fn transcribe_by_channel(
+    db: State<'_, DbState>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+    recording_id: String,
+    language: Option<String>,
+    speakers: Option<Vec<String>>,
+) -> Result<Transcription, String> {
+    log::info!("🔀 transcribe_by_channel command called for id: {}", recording_id);
+
+    let database = db.inner();
+    channel_separation::transcribe_by_channel(
+        database,
+        &whisper_service,
+        &recording_id,
+        language,
+        &speakers.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| {
+        log::error!("❌ Failed to transcribe recording {} by channel: {}", recording_id, e);
+        e.localized()
+    })
+}
+
+#[tauri::command]
+pub async fn get_transcript_segments(
+    db: State<'_, DbState>,
+    transcription_id: String,
+) -> Result<Vec<TranscriptSegment>, String> {
+    let database = db.inner();
+    database
+        .get_segments_by_transcription(&transcription_id)
+        .await
+        .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn get_watched_folders(
+    settings_manager: State<'_, AppSettingsState>,
+) -> Result<Vec<WatchedFolderRule>, String> {
+    Ok(settings_manager.lock().await.get_settings().watched_folders.clone())
+}
+
+/// 監視フォルダのルールを追加または更新（id が一致するルールを置き換える）
+#[tauri::command]
+pub async fn save_watched_folder(
+    settings_manager: State<'_, AppSettingsState>,
+    mut rule: WatchedFolderRule,
+) -> Result<WatchedFolderRule, String> {
+    if rule.id.trim().is_empty() {
+        rule.id = uuid::Uuid::new_v4().to_string();
+    }
+    watched_folders::validate_rule(&rule).map_err(String::from)?;
+    log::info!("👀 Saving watched folder {}", rule.path);
+
+    let mut manager = settings_manager.lock().await;
+    let saved = rule.clone();
+    manager.update_settings(|settings| {
+        match settings.watched_folders.iter_mut().find(|existing| existing.id == rule.id) {
+            Some(existing) => *existing = rule,
+            None => settings.watched_folders.push(rule),
+        }
+    });
+    manager.save_settings().await.map_err(String::from)?;
+    Ok(saved)
+}
+
+#[tauri::command]
+pub async fn remove_watched_folder(
+    settings_manager: State<'_, AppSettingsState>,
+    id: String,
+) -> Result<bool, String> {
+    let mut manager = settings_manager.lock().await;
+    let before = manager.get_settings().watched_folders.len();
+    manager.update_settings(|settings| {
+        settings.watched_folders.retain(|rule| rule.id != id);
+    });
+    let removed = manager.get_settings().watched_folders.len() != before;
+    if removed {
+        manager.save_settings().await.map_err(String::from)?;
+    }
+    Ok(removed)
+}
+
+/// 次の定期走査を待たずに監視フォルダを走査
+#[tauri::command]
+pub async fn scan_watched_folders(
+    db: State<'_, DbState>,
+    job_queue: State<'_, Arc<JobQueue>>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    settings_manager: State<'_, AppSettingsState>,
+) -> Result<Vec<WatchedImport>, String> {
+    let rules = settings_manager.lock().await.get_settings().watched_folders.clone();
+    let imports = watched_folders::scan_all(&db, &job_queue, &rules, recording_service.recordings_dir()).await;
+    for import in &imports {
+        recording_service.prepare_for_transcription(&import.recording).await;
+    }
+    Ok(imports)
+}
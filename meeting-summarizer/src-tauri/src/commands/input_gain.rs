@@ -0,0 +1,30 @@
+use crate::services::{AppSettingsManager, InputGainSettings, RecordingService};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+#[tauri::command]
+pub async fn get_input_gain(
+    recording_service: State<'_, Arc<RecordingService>>,
+) -> Result<InputGainSettings, String> {
+    Ok(recording_service.input_gain().await)
+}
+
+/// 録音時の入力ゲインを設定する（録音中でもすぐに反映）
+#[tauri::command]
+pub async fn set_input_gain(
+    settings_manager: State<'_, AppSettingsState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    settings: InputGainSettings,
+) -> Result<InputGainSettings, String> {
+    settings.validate().map_err(String::from)?;
+    log::info!("🎚️ Setting input gain: {:?}", settings);
+    recording_service.set_input_gain(settings).await;
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|app_settings| app_settings.input_gain = settings);
+    manager.save_settings().await.map_err(String::from)?;
+    Ok(settings)
+}
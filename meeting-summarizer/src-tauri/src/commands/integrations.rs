@@ -0,0 +1,213 @@
+use crate::database::Database;
+use crate::errors::AppError;
+use crate::models::{Summary, Transcription};
+use crate::services::confluence::{ConfluencePage, ConfluenceService};
+use crate::services::google_docs::{DeviceAuthorization, GoogleDocsService, GoogleDocument};
+use crate::services::i18n::tr;
+use crate::services::teams::TeamsService;
+use crate::services::{AppSettingsManager, ConfluenceSettings};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type DbState = Arc<Database>;
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+async fn google_docs_service(settings_manager: &AppSettingsState) -> Result<GoogleDocsService, String> {
+    let manager = settings_manager.lock().await;
+    let settings = &manager.get_settings().google_docs;
+    let client_id = settings.client_id.clone().ok_or_else(|| {
+        AppError::Integration {
+            message: "Google OAuth client ID is not configured".to_string(),
+        }
+        .to_string()
+    })?;
+    Ok(GoogleDocsService::new(client_id, settings.client_secret.clone()))
+}
+
+#[tauri::command]
+pub async fn set_google_docs_client(
+    settings_manager: State<'_, AppSettingsState>,
+    client_id: String,
+    client_secret: Option<String>,
+) -> Result<(), String> {
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| {
+        settings.google_docs.client_id = Some(client_id);
+        settings.google_docs.client_secret = client_secret;
+    });
+    manager.save_settings().await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn google_docs_start_auth(
+    settings_manager: State<'_, AppSettingsState>,
+) -> Result<DeviceAuthorization, String> {
+    log::info!("🔑 Starting Google Docs device authorization");
+    let service = google_docs_service(&settings_manager).await?;
+    service.start_device_authorization().await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn google_docs_complete_auth(
+    settings_manager: State<'_, AppSettingsState>,
+    authorization: DeviceAuthorization,
+) -> Result<(), String> {
+    let service = google_docs_service(&settings_manager).await?;
+    service
+        .complete_device_authorization(&authorization)
+        .await
+        .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn google_docs_status() -> Result<bool, String> {
+    GoogleDocsService::is_authorized().map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn google_docs_disconnect() -> Result<(), String> {
+    log::info!("🔌 Disconnecting Google Docs");
+    GoogleDocsService::disconnect().map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn export_summary_to_google_docs(
+    db: State<'_, DbState>,
+    settings_manager: State<'_, AppSettingsState>,
+    summary_id: String,
+    title: Option<String>,
+) -> Result<GoogleDocument, String> {
+    log::info!("📄 Exporting summary {} to Google Docs", summary_id);
+
+    let (summary, _, default_title) = load_summary_context(&db, &summary_id).await?;
+
+    let service = google_docs_service(&settings_manager).await?;
+    service
+        .create_document(&title.unwrap_or(default_title), &summary)
+        .await
+        .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn set_teams_webhook(webhook_url: String) -> Result<(), String> {
+    log::info!("💬 Saving Teams webhook");
+    TeamsService::save_webhook(&webhook_url).map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn teams_status() -> Result<bool, String> {
+    TeamsService::is_configured().map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn teams_disconnect() -> Result<(), String> {
+    log::info!("🔌 Removing Teams webhook");
+    TeamsService::clear_webhook().map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn post_summary_to_teams(
+    db: State<'_, DbState>,
+    summary_id: String,
+    transcript_url: Option<String>,
+) -> Result<(), String> {
+    log::info!("💬 Posting summary {} to Teams", summary_id);
+
+    let (summary, transcription, title) = load_summary_context(&db, &summary_id).await?;
+
+    let service = TeamsService::from_stored_webhook().map_err(String::from)?;
+    service
+        .post_summary(&title, &summary, transcription.as_ref(), transcript_url.as_deref())
+        .await
+        .map_err(String::from)
+}
+
+/// Confluence の接続先を保存（API トークンを渡した場合はキーチェーンに保存）
+#[tauri::command]
+pub async fn set_confluence_settings(
+    settings_manager: State<'_, AppSettingsState>,
+    confluence: ConfluenceSettings,
+    api_token: Option<String>,
+) -> Result<(), String> {
+    confluence.validate().map_err(String::from)?;
+    log::info!("📘 Saving Confluence settings for space {}", confluence.space_key);
+
+    if let Some(api_token) = api_token {
+        ConfluenceService::save_api_token(&api_token).map_err(String::from)?;
+    }
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| {
+        settings.confluence = confluence;
+    });
+    manager.save_settings().await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn get_confluence_settings(
+    settings_manager: State<'_, AppSettingsState>,
+) -> Result<ConfluenceSettings, String> {
+    Ok(settings_manager.lock().await.get_settings().confluence.clone())
+}
+
+#[tauri::command]
+pub async fn confluence_status() -> Result<bool, String> {
+    ConfluenceService::is_configured().map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn confluence_disconnect() -> Result<(), String> {
+    log::info!("🔌 Removing Confluence API token");
+    ConfluenceService::clear_api_token().map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn export_summary_to_confluence(
+    db: State<'_, DbState>,
+    settings_manager: State<'_, AppSettingsState>,
+    summary_id: String,
+    title: Option<String>,
+) -> Result<ConfluencePage, String> {
+    log::info!("📘 Exporting summary {} to Confluence", summary_id);
+
+    let (summary, _, default_title) = load_summary_context(&db, &summary_id).await?;
+
+    let settings = settings_manager.lock().await.get_settings().confluence.clone();
+    let service = ConfluenceService::from_settings(&settings).map_err(String::from)?;
+    service
+        .publish_summary(&title.unwrap_or(default_title), &summary)
+        .await
+        .map_err(String::from)
+}
+
+/// 要約と元の書き起こし、表示用タイトル（録音タイトル or 作成日）を取得
+async fn load_summary_context(
+    db: &DbState,
+    summary_id: &str,
+) -> Result<(Summary, Option<Transcription>, String), String> {
+    let database = db;
+    let summary = database
+        .get_summary(summary_id)
+        .await
+        .map_err(String::from)?
+        .ok_or_else(|| tr("command.summary_not_found", &[("id", &summary_id)]))?;
+
+    let transcription = database
+        .get_transcription(&summary.transcription_id)
+        .await
+        .map_err(String::from)?;
+
+    let recording_title = match &transcription {
+        Some(transcription) => database
+            .get_recording(&transcription.recording_id)
+            .await
+            .map_err(String::from)?
+            .map(|recording| recording.title.unwrap_or(recording.filename)),
+        None => None,
+    };
+
+    let title = recording_title
+        .unwrap_or_else(|| format!("Meeting Summary {}", summary.created_at.format("%Y-%m-%d")));
+    Ok((summary, transcription, title))
+}
@@ -0,0 +1,54 @@
+use crate::models::{Job, JobPayload, JobStatus};
+use crate::services::JobQueue;
+use std::sync::Arc;
+use tauri::State;
+
+type JobQueueState = Arc<JobQueue>;
+
+#[tauri::command]
+pub async fn enqueue_job(
+    job_queue: State<'_, JobQueueState>,
+    payload: JobPayload,
+    priority: Option<i32>,
+    max_attempts: Option<i32>,
+) -> Result<Job, String> {
+    let mut job = Job::new(payload).with_priority(priority.unwrap_or(0));
+    if let Some(max_attempts) = max_attempts {
+        job = job.with_max_attempts(max_attempts);
+    }
+
+    job_queue.enqueue(job).await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn list_jobs(
+    job_queue: State<'_, JobQueueState>,
+    status: Option<String>,
+) -> Result<Vec<Job>, String> {
+    let status = status.map(|s| s.parse::<JobStatus>()).transpose()?;
+    job_queue.list_jobs(status).await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn get_job(
+    job_queue: State<'_, JobQueueState>,
+    id: String,
+) -> Result<Option<Job>, String> {
+    job_queue.get_job(&id).await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn retry_job(
+    job_queue: State<'_, JobQueueState>,
+    id: String,
+) -> Result<Job, String> {
+    job_queue.retry_job(&id).await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn cancel_job(
+    job_queue: State<'_, JobQueueState>,
+    id: String,
+) -> Result<Job, String> {
+    job_queue.cancel_job(&id).await.map_err(String::from)
+}
@@ -0,0 +1,99 @@
+use crate::database::Database;
+use crate::models::{KeywordAlert, WatchWord};
+use crate::services::keyword_alerts;
+use crate::services::keyword_timeline::{self, KeywordTimeline, DEFAULT_TIMELINE_BUCKETS};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tauri::State;
+
+type DbState = Arc<Database>;
+
+/// 一覧取得時の既定の件数
+const DEFAULT_ALERT_LIMIT: i64 = 100;
+
+#[tauri::command]
+pub async fn get_watch_words(db: State<'_, DbState>) -> Result<Vec<WatchWord>, String> {
+    db.get_watch_words().await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn add_watch_word(
+    db: State<'_, DbState>,
+    word: String,
+    tag: Option<String>,
+) -> Result<WatchWord, String> {
+    keyword_alerts::validate_watch_word(&word).map_err(String::from)?;
+
+    let mut watch_word = WatchWord::new(word.trim().to_string());
+    if let Some(tag) = tag.map(|tag| tag.trim().to_string()).filter(|tag| !tag.is_empty()) {
+        watch_word = watch_word.with_tag(tag);
+    }
+    db.create_watch_word(&watch_word).await.map_err(String::from)?;
+    log::info!("👀 Added watch word: {}", watch_word.word);
+    Ok(watch_word)
+}
+
+#[tauri::command]
+pub async fn update_watch_word(db: State<'_, DbState>, watch_word: WatchWord) -> Result<bool, String> {
+    keyword_alerts::validate_watch_word(&watch_word.word).map_err(String::from)?;
+    db.update_watch_word(&watch_word).await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn delete_watch_word(db: State<'_, DbState>, id: String) -> Result<bool, String> {
+    db.delete_watch_word(&id).await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn get_keyword_alerts(
+    db: State<'_, DbState>,
+    unacknowledged_only: Option<bool>,
+    limit: Option<i64>,
+) -> Result<Vec<KeywordAlert>, String> {
+    db.get_keyword_alerts(unacknowledged_only.unwrap_or(false), limit.unwrap_or(DEFAULT_ALERT_LIMIT))
+        .await
+        .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn acknowledge_keyword_alerts(db: State<'_, DbState>, ids: Vec<String>) -> Result<usize, String> {
+    db.acknowledge_keyword_alerts(&ids).await.map_err(String::from)
+}
+
+/// 既存の書き起こしを現在の監視ワードで再走査する
+#[tauri::command]
+pub async fn rescan_recording_keywords(
+    db: State<'_, DbState>,
+    recording_id: String,
+) -> Result<Vec<KeywordAlert>, String> {
+    keyword_alerts::rescan_recording(db.inner(), &recording_id)
+        .await
+        .map_err(String::from)
+}
+
+/// 録音内でキーワードが出現した時刻と区間ごとの密度
+#[tauri::command]
+pub async fn get_keyword_timeline(
+    db: State<'_, DbState>,
+    recording_id: String,
+    keyword: String,
+    buckets: Option<usize>,
+) -> Result<Option<KeywordTimeline>, String> {
+    keyword_timeline::recording_timeline(&db, &recording_id, &keyword, buckets.unwrap_or(DEFAULT_TIMELINE_BUCKETS))
+        .await
+        .map_err(String::from)
+}
+
+/// 期間内の録音をまたいだキーワードのタイムライン（出現した録音のみ）
+#[tauri::command]
+pub async fn get_keyword_timelines(
+    db: State<'_, DbState>,
+    keyword: String,
+    date_from: Option<DateTime<Utc>>,
+    date_to: Option<DateTime<Utc>>,
+    buckets: Option<usize>,
+) -> Result<Vec<KeywordTimeline>, String> {
+    keyword_timeline::timelines_in_range(&db, &keyword, date_from, date_to, buckets.unwrap_or(DEFAULT_TIMELINE_BUCKETS))
+        .await
+        .map_err(String::from)
+}
@@ -0,0 +1,83 @@
+use crate::database::Database;
+use crate::services::live_transcription::{LiveSource, LIVE_TRANSCRIPTION_SAVED_EVENT, LIVE_TRANSCRIPT_EVENT};
+use crate::services::{
+    LiveCaptionHub, LiveTranscriber, LiveTranscriptSegment, RecordingService, StoppedRecording, WhisperService,
+};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager, State, Window};
+
+type LiveTranscriberState = Arc<LiveTranscriber>;
+
+/// 録音中の書き起こしを開始する（途中経過は `live-transcript` イベントで届く）
+#[tauri::command]
+pub async fn start_live_transcription(
+    window: Window,
+    db: State<'_, Arc<Database>>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+    captions: State<'_, Arc<LiveCaptionHub>>,
+    live_transcriber: State<'_, LiveTranscriberState>,
+    language: Option<String>,
+) -> Result<String, String> {
+    let recording_session_id = recording_service
+        .current_session_id()
+        .await
+        .filter(|_| recording_service.is_recording())
+        .ok_or_else(|| "No active recording session".to_string())?;
+    if !whisper_service.is_initialized().await {
+        whisper_service.initialize().await.map_err(String::from)?;
+    }
+
+    Ok(live_transcriber.start(
+        LiveSource {
+            recording_session_id,
+            chunks: recording_service.subscribe_audio_chunks(),
+        },
+        db.inner().clone(),
+        whisper_service.inner().clone(),
+        Some(captions.inner().clone()),
+        language,
+        move |segment| {
+            let _ = window.emit(LIVE_TRANSCRIPT_EVENT, segment.clone());
+        },
+    ))
+}
+
+/// 保存せずに止める（録音を止めたときは確定した発言が自動で保存される）
+#[tauri::command]
+pub async fn stop_live_transcription(live_transcriber: State<'_, LiveTranscriberState>) -> Result<bool, String> {
+    Ok(live_transcriber.stop())
+}
+
+/// これまでの書き起こし（画面を開き直したときの同期用）
+#[tauri::command]
+pub async fn get_live_transcript(
+    live_transcriber: State<'_, LiveTranscriberState>,
+) -> Result<Vec<LiveTranscriptSegment>, String> {
+    Ok(live_transcriber.segments())
+}
+
+/// 録音の停止後、ライブ書き起こしの確定した発言を録音の書き起こしとして保存する
+///
+/// どの経路で録音を止めても呼ばれるよう、`RecordingService::subscribe_stopped_recordings` から呼ぶ。
+/// 最後のチャンクの書き起こしを待つため裏で実行し、保存できたら `live-transcription-saved` で通知する。
+pub fn save_after_stop(app_handle: &AppHandle, stopped: StoppedRecording) {
+    let live_transcriber = app_handle.state::<LiveTranscriberState>().inner().clone();
+    if live_transcriber.session_id().is_none() {
+        return;
+    }
+    let database = app_handle.state::<Arc<Database>>().inner().clone();
+    let app_handle = app_handle.clone();
+    let recording_id = stopped.recording.id;
+    tauri::async_runtime::spawn(async move {
+        match live_transcriber.finish(&database, &stopped.session_id, &recording_id).await {
+            Ok(Some(transcription)) => {
+                if let Err(e) = app_handle.emit(LIVE_TRANSCRIPTION_SAVED_EVENT, transcription) {
+                    log::warn!("⚠️ Failed to emit saved live transcription: {}", e);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("⚠️ Failed to save live transcription of recording {}: {}", recording_id, e),
+        }
+    });
+}
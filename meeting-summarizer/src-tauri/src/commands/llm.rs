@@ -1,50 +1,82 @@
 use crate::database::Database;
-use crate::models::{LLMConfig, LLMProvider, Summary};
-use crate::services::LLMService;
+use crate::models::{LLMConfig, LLMConnection, LLMProvider, Summary};
+use crate::services::i18n::t;
+use crate::services::model_comparison::{self, ModelComparisonResult};
+use crate::services::model_selection::{self, AutoSelection};
+use crate::services::digest::{DigestScope, MeetingDigest};
+use crate::services::{demo_mode, summary_review};
+use crate::services::{LLMModelManager, LLMService, ModelSettingsManager, OllamaHostStatus, OllamaPool, Summarizer};
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use tauri::State;
 use tokio::sync::Mutex;
 
-type DbState = Arc<Mutex<Database>>;
+type DbState = Arc<Database>;
+type ModelSettingsState = Arc<Mutex<ModelSettingsManager>>;
+type ModelManagerState = Arc<Mutex<LLMModelManager>>;
+type OllamaPoolState = Arc<OllamaPool>;
+type SummarizerState = Arc<Summarizer>;
 
 #[tauri::command]
 pub async fn generate_summary(
     db: State<'_, DbState>,
+    summarizer: State<'_, SummarizerState>,
     transcription_text: String,
     transcription_id: String,
     model_config: Option<LLMConfig>,
+    include_notes: Option<bool>,
 ) -> Result<Summary, String> {
-    let database = db.lock().await;
-    
-    // Use provided config or default
-    let config = model_config.unwrap_or_default();
-    let llm_service = LLMService::new(config);
+    let database = db.inner();
+
+    // 承認済みで固定された要約は再生成しない
+    summary_review::ensure_can_regenerate(database, &transcription_id)
+        .await
+        .map_err(String::from)?;
     
     log::info!("🤖 Generating summary for transcription: {}", transcription_id);
-    
-    // Generate summary using LLM
-    let result = llm_service
-        .summarize_text(&transcription_text, transcription_id.clone())
+
+    // Generate summary using LLM (会議中のメモとマーカー付近の発言も一緒に渡す)
+    // 同時要約・自動切り替え・複数のOllamaホストへの振り分けは共通経路で行う
+    let result = summarizer
+        .summarize(&transcription_id, &transcription_text, model_config, include_notes)
         .await
-        .map_err(|e| e.to_string())?;
+    .map_err(String::from)?;
     
     // Save summary to database
     database
         .create_summary(&result)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(String::from)?;
     
     log::info!("✅ Summary generated and saved: {}", result.id);
     Ok(result)
 }
 
+/// 複数の会議をまとめたダイジェストを作る（録音IDか期間のどちらかを指定）
+#[tauri::command]
+pub async fn generate_digest(
+    summarizer: State<'_, SummarizerState>,
+    recording_ids: Option<Vec<String>>,
+    date_from: Option<DateTime<Utc>>,
+    date_to: Option<DateTime<Utc>>,
+    model_config: Option<LLMConfig>,
+) -> Result<MeetingDigest, String> {
+    let scope = match (recording_ids.filter(|ids| !ids.is_empty()), date_from, date_to) {
+        (Some(recording_ids), _, _) => DigestScope::Recordings { recording_ids },
+        (None, Some(from), Some(to)) => DigestScope::DateRange { from, to },
+        _ => return Err("Specify recording IDs or both dates for the digest".to_string()),
+    };
+
+    summarizer.generate_digest(model_config, &scope).await.map_err(String::from)
+}
+
 #[tauri::command]
 pub async fn get_summary_by_id(
     db: State<'_, DbState>,
     id: String,
 ) -> Result<Option<Summary>, String> {
-    let database = db.lock().await;
-    database.get_summary(&id).await.map_err(|e| e.to_string())
+    let database = db.inner();
+    database.get_summary(&id).await.map_err(String::from)
 }
 
 #[tauri::command]
@@ -52,11 +84,11 @@ pub async fn get_summaries_for_transcription(
     db: State<'_, DbState>,
     transcription_id: String,
 ) -> Result<Vec<Summary>, String> {
-    let database = db.lock().await;
+    let database = db.inner();
     database
         .get_summaries_by_transcription(&transcription_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(String::from)
 }
 
 #[tauri::command]
@@ -64,8 +96,8 @@ pub async fn update_summary(
     db: State<'_, DbState>,
     summary: Summary,
 ) -> Result<(), String> {
-    let database = db.lock().await;
-    database.update_summary(&summary).await.map_err(|e| e.to_string())
+    let database = db.inner();
+    database.update_summary(&summary).await.map_err(String::from)
 }
 
 #[tauri::command]
@@ -73,16 +105,63 @@ pub async fn delete_summary(
     db: State<'_, DbState>,
     id: String,
 ) -> Result<bool, String> {
-    let database = db.lock().await;
-    database.delete_summary(&id).await.map_err(|e| e.to_string())
+    let database = db.inner();
+    database.delete_summary(&id).await.map_err(String::from)
+}
+
+/// 要約を承認する（lock を指定すると再生成・編集できないよう固定する）
+#[tauri::command]
+pub async fn approve_summary(
+    db: State<'_, DbState>,
+    summary_id: String,
+    approved_by: Option<String>,
+    lock: Option<bool>,
+) -> Result<Summary, String> {
+    summary_review::approve_summary(&db, &summary_id, approved_by, lock.unwrap_or(false))
+        .await
+        .map_err(String::from)
+}
+
+/// 承認を取り消して下書きに戻す
+#[tauri::command]
+pub async fn reopen_summary(
+    db: State<'_, DbState>,
+    summary_id: String,
+) -> Result<Summary, String> {
+    summary_review::reopen_summary(&db, &summary_id)
+        .await
+        .map_err(String::from)
+}
+
+/// 承認済みの要約を固定・固定解除する
+#[tauri::command]
+pub async fn set_summary_locked(
+    db: State<'_, DbState>,
+    summary_id: String,
+    locked: bool,
+) -> Result<Summary, String> {
+    summary_review::set_summary_locked(&db, &summary_id, locked)
+        .await
+        .map_err(String::from)
 }
 
 #[tauri::command]
 pub async fn check_llm_connection(
     config: LLMConfig,
 ) -> Result<bool, String> {
-    let llm_service = LLMService::new(config);
-    llm_service.check_connection().await.map_err(|e| e.to_string())
+    // URL・認証・CA証明書の設定を検証してから接続を確認する
+    let llm_service = LLMService::try_new(config).map_err(String::from)?;
+    llm_service.check_connection().await.map_err(String::from)
+}
+
+/// 設定されたOllamaホストの接続を確認し、実行中のリクエスト数などと一緒に返す
+#[tauri::command]
+pub async fn get_ollama_host_status(
+    settings_manager: State<'_, ModelSettingsState>,
+    ollama_pool: State<'_, OllamaPoolState>,
+) -> Result<Vec<OllamaHostStatus>, String> {
+    let hosts = settings_manager.lock().await.get_settings().ollama_hosts.clone();
+    Ok(ollama_pool.check_hosts(&hosts, &LLMConfig::default()).await)
 }
 
 #[tauri::command]
@@ -112,8 +191,10 @@ pub async fn validate_llm_config(
     }
     
     // Try to connect to validate the configuration
-    let llm_service = LLMService::new(config);
-    llm_service.check_connection().await.map_err(|e| e.to_string())
+    let Ok(llm_service) = LLMService::try_new(config) else {
+        return Ok(false);
+    };
+    llm_service.check_connection().await.map_err(String::from)
 }
 
 #[tauri::command]
@@ -137,7 +218,8 @@ pub async fn get_provider_default_config(
         "GPT4All" => LLMProvider::GPT4All,
         "LMStudio" => LLMProvider::LMStudio,
         "Custom" => LLMProvider::Custom,
-        _ => return Err("Invalid provider".to_string()),
+        "Demo" => LLMProvider::Demo,
+        _ => return Err(t("command.invalid_provider")),
     };
 
     let config = match provider_enum {
@@ -148,6 +230,7 @@ pub async fn get_provider_default_config(
             temperature: 0.7,
             max_tokens: 2048,
             timeout_seconds: 120,
+            connection: LLMConnection::default(),
         },
         LLMProvider::OpenAI => LLMConfig {
             provider: LLMProvider::OpenAI,
@@ -156,6 +239,7 @@ pub async fn get_provider_default_config(
             temperature: 0.7,
             max_tokens: 2048,
             timeout_seconds: 60,
+            connection: LLMConnection::default(),
         },
         LLMProvider::GPT4All => LLMConfig {
             provider: LLMProvider::GPT4All,
@@ -164,6 +248,7 @@ pub async fn get_provider_default_config(
             temperature: 0.7,
             max_tokens: 2048,
             timeout_seconds: 120,
+            connection: LLMConnection::default(),
         },
         LLMProvider::LMStudio => LLMConfig {
             provider: LLMProvider::LMStudio,
@@ -172,6 +257,7 @@ pub async fn get_provider_default_config(
             temperature: 0.7,
             max_tokens: 2048,
             timeout_seconds: 120,
+            connection: LLMConnection::default(),
         },
         LLMProvider::Custom => LLMConfig {
             provider: LLMProvider::Custom,
@@ -180,7 +266,9 @@ pub async fn get_provider_default_config(
             temperature: 0.7,
             max_tokens: 2048,
             timeout_seconds: 120,
+            connection: LLMConnection::default(),
         },
+        LLMProvider::Demo => demo_mode::llm_config(),
     };
 
     Ok(config)
@@ -201,5 +289,29 @@ pub async fn test_summarization(
     llm_service
         .summarize_text(&sample_text, test_transcription_id)
         .await
-        .map_err(|e| e.to_string())
-}
\ No newline at end of file
+        .map_err(String::from)
+}
+/// 同じ書き起こしを 2〜3 個のモデルで同時に要約して比較する
+#[tauri::command]
+pub async fn compare_summary_models(
+    db: State<'_, DbState>,
+    transcription_id: String,
+    model_configs: Vec<LLMConfig>,
+    save_summaries: Option<bool>,
+) -> Result<Vec<ModelComparisonResult>, String> {
+    model_comparison::compare_models(&db, &transcription_id, model_configs, save_summaries.unwrap_or(false))
+        .await
+        .map_err(String::from)
+}
+
+/// 自動切り替えで選ばれるモデルとその理由を確認する（自動切り替えが無効なら None）
+#[tauri::command]
+pub async fn preview_auto_model_selection(
+    settings_manager: State<'_, ModelSettingsState>,
+    model_manager: State<'_, ModelManagerState>,
+    text_length: usize,
+) -> Result<Option<AutoSelection>, String> {
+    model_selection::auto_select(&settings_manager, &model_manager, text_length)
+        .await
+        .map_err(String::from)
+}
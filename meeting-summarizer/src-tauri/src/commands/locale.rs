@@ -0,0 +1,35 @@
+use crate::services::i18n::{self, Locale};
+use crate::services::AppSettingsManager;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+#[tauri::command]
+pub async fn get_locale() -> Result<Locale, String> {
+    Ok(i18n::current_locale())
+}
+
+#[tauri::command]
+pub async fn get_supported_locales() -> Result<Vec<Locale>, String> {
+    Ok(Locale::all().to_vec())
+}
+
+/// 表示言語を切り替えて保存（"ja-JP" のような地域付き指定も可）
+#[tauri::command]
+pub async fn set_locale(
+    settings_manager: State<'_, AppSettingsState>,
+    locale: String,
+) -> Result<Locale, String> {
+    let locale: Locale = locale.parse()?;
+    i18n::set_locale(locale);
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| {
+        settings.locale = locale;
+    });
+    manager.save_settings().await.map_err(String::from)?;
+
+    Ok(locale)
+}
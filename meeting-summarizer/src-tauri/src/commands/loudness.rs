@@ -0,0 +1,30 @@
+use crate::services::{AppSettingsManager, LoudnessSettings, RecordingService};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+#[tauri::command]
+pub async fn get_loudness_settings(
+    recording_service: State<'_, Arc<RecordingService>>,
+) -> Result<LoudnessSettings, String> {
+    Ok(recording_service.loudness())
+}
+
+/// 書き起こし用のコピーの音量の正規化を設定する（次の録音・取り込みから反映）
+#[tauri::command]
+pub async fn set_loudness_settings(
+    settings_manager: State<'_, AppSettingsState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    settings: LoudnessSettings,
+) -> Result<LoudnessSettings, String> {
+    settings.validate().map_err(String::from)?;
+    log::info!("🔊 Setting loudness normalization: {:?}", settings);
+    recording_service.set_loudness(settings);
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|app_settings| app_settings.loudness = settings);
+    manager.save_settings().await.map_err(String::from)?;
+    Ok(settings)
+}
@@ -1,7 +1,18 @@
 use crate::errors::AppError;
-use crate::models::{Recording, Transcription};
-use crate::services::{RecordingService, WhisperService};
-use tauri::{AppHandle, State};
+use crate::services::i18n::{t, tr};
+use crate::models::{Recording, RecordingListItem, RecordingSegment, RecordingTrack, TranscriptEdit, Transcription};
+use crate::database::Database;
+use crate::services::batch_transcription::{self, BatchTranscriptionResult};
+use crate::services::confidence_regions::{self, LowConfidenceRegion};
+use crate::services::environment_doctor::{self, EnvironmentReport};
+use crate::services::recording_progress::RecordingProgress;
+use crate::services::recording_segments;
+use crate::services::transcript_edits;
+use crate::services::transcription_estimate::{self, TranscriptionEstimate};
+use crate::services::transcription_language;
+use crate::services::{AppSettingsManager, AudioDevice, InFlightTranscription, RecordingQuality, RecordingService, WhisperService};
+use tauri::{AppHandle, Emitter, State, Window};
+use tokio::sync::Mutex;
 use std::sync::Arc;
 use std::path::PathBuf;
 
@@ -42,32 +53,67 @@ fn sanitize_string_input(input: &str, max_length: usize) -> Result<String, AppEr
 
 #[tauri::command]
 pub async fn start_recording(
+    app_handle: AppHandle,
     recording_service: State<'_, Arc<RecordingService>>,
+    quality: Option<RecordingQuality>,
 ) -> Result<String, String> {
-    recording_service
-        .start_recording()
+    let session_id = recording_service
+        .start_recording_with_quality(quality)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(String::from)?;
+    tray::refresh_tray(&app_handle, &recording_service).await;
+    Ok(session_id)
 }
 
 #[tauri::command]
 pub async fn stop_recording(
+    app_handle: AppHandle,
     recording_service: State<'_, Arc<RecordingService>>,
 ) -> Result<Recording, String> {
-    recording_service
+    let recording = recording_service
         .stop_recording()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(String::from)?;
+    tray::refresh_tray(&app_handle, &recording_service).await;
+    Ok(recording)
+}
+
+/// 録音を一時停止する（再開するまでの音声は保存しない）
+#[tauri::command]
+pub async fn pause_recording(
+    app_handle: AppHandle,
+    recording_service: State<'_, Arc<RecordingService>>,
+) -> Result<(), String> {
+    recording_service
+        .pause_recording()
+        .await
+        .map_err(String::from)?;
+    tray::refresh_tray(&app_handle, &recording_service).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn resume_recording(
+    app_handle: AppHandle,
+    recording_service: State<'_, Arc<RecordingService>>,
+) -> Result<(), String> {
+    recording_service
+        .resume_recording()
+        .await
+        .map_err(String::from)?;
+    tray::refresh_tray(&app_handle, &recording_service).await;
+    Ok(())
 }
 
 #[tauri::command]
 pub async fn get_recordings(
     recording_service: State<'_, Arc<RecordingService>>,
-) -> Result<Vec<Recording>, String> {
+    include_archived: Option<bool>,
+) -> Result<Vec<RecordingListItem>, String> {
     recording_service
-        .get_recordings()
+        .get_recordings_with_status(include_archived.unwrap_or(false))
         .await
-        .map_err(|e| e.to_string())
+        .map_err(String::from)
 }
 
 #[tauri::command]
@@ -78,7 +124,31 @@ pub async fn get_recording(
     recording_service
         .get_recording(&id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(String::from)
+}
+
+/// 区間ごとに分けて保存した録音のファイル（再生時に順につなげる。分けていない録音は空）
+#[tauri::command]
+pub async fn get_recording_segments(
+    recording_service: State<'_, Arc<RecordingService>>,
+    id: String,
+) -> Result<Vec<RecordingSegment>, String> {
+    recording_service
+        .get_recording_segments(&id)
+        .await
+        .map_err(String::from)
+}
+
+/// 別の入力デバイスから同時に録音したトラック（話者ごとの書き起こしに使える）
+#[tauri::command]
+pub async fn get_recording_tracks(
+    recording_service: State<'_, Arc<RecordingService>>,
+    id: String,
+) -> Result<Vec<RecordingTrack>, String> {
+    recording_service
+        .get_recording_tracks(&id)
+        .await
+        .map_err(String::from)
 }
 
 #[tauri::command]
@@ -92,11 +162,11 @@ pub async fn delete_recording(
     // 認証チェック
     validate_request(&app_handle)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(String::from)?;
     
     // 入力の検証とサニタイゼーション
     let sanitized_id = sanitize_string_input(&id, 50)
-        .map_err(|e| e.to_string())?;
+        .map_err(String::from)?;
     
     log::info!("🔍 Attempting to delete recording with sanitized id: {}", sanitized_id);
     
@@ -124,6 +194,14 @@ pub async fn is_recording(
     Ok(recording_service.is_recording())
 }
 
+/// 録音中の進捗（リロード直後の表示用。以降は recording-progress イベントで届く）
+#[tauri::command]
+pub async fn get_recording_progress(
+    recording_service: State<'_, Arc<RecordingService>>,
+) -> Result<Option<RecordingProgress>, String> {
+    Ok(recording_service.current_progress().await)
+}
+
 #[tauri::command]
 pub async fn get_recordings_count(
     recording_service: State<'_, Arc<RecordingService>>,
@@ -131,16 +209,16 @@ pub async fn get_recordings_count(
     recording_service
         .get_recordings_count()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(String::from)
 }
 
 #[tauri::command]
 pub async fn get_audio_devices(
     recording_service: State<'_, Arc<RecordingService>>,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<AudioDevice>, String> {
     recording_service
         .get_audio_devices()
-        .map_err(|e| e.to_string())
+        .map_err(String::from)
 }
 
 // Whisper 書き起こし関連コマンド
@@ -148,6 +226,7 @@ pub async fn get_audio_devices(
 #[tauri::command]
 pub async fn transcribe_recording(
     app_handle: AppHandle,
+    db: State<'_, Arc<Database>>,
     recording_service: State<'_, Arc<RecordingService>>,
     whisper_service: State<'_, Arc<WhisperService>>,
     recording_id: String,
@@ -158,15 +237,15 @@ pub async fn transcribe_recording(
     // 認証チェック
     validate_request(&app_handle)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(String::from)?;
     
     // 入力の検証とサニタイゼーション
     let sanitized_recording_id = sanitize_string_input(&recording_id, 50)
-        .map_err(|e| e.to_string())?;
+        .map_err(String::from)?;
     
     let sanitized_language = if let Some(lang) = language {
         Some(sanitize_string_input(&lang, 10)
-            .map_err(|e| e.to_string())?)
+            .map_err(String::from)?)
     } else {
         None
     };
@@ -177,20 +256,22 @@ pub async fn transcribe_recording(
     let recording = recording_service
         .get_recording(&sanitized_recording_id)
         .await
-        .map_err(|e| e.to_string())?
+        .map_err(String::from)?
         .ok_or_else(|| {
             log::error!("❌ Recording not found: {}", sanitized_recording_id);
-            "Recording not found".to_string()
+            tr("command.recording_not_found", &[("id", &sanitized_recording_id)])
         })?;
 
-    // 音声ファイルが存在するかチェック
-    let audio_path = PathBuf::from(&recording.file_path);
-    if !audio_path.exists() {
-        log::error!("❌ Audio file not found: {:?}", audio_path);
-        return Err("Audio file not found".to_string());
+    // 音声ファイルが存在するかチェック（分けた録音は全区間）
+    let audio_files = recording_segments::audio_files(db.inner(), &recording)
+        .await
+        .map_err(String::from)?;
+    if let Some(missing) = audio_files.iter().find(|path| !path.exists()) {
+        log::error!("❌ Audio file not found: {:?}", missing);
+        return Err(t("command.audio_file_not_found"));
     }
     
-    log::info!("📁 Audio file found: {:?}", audio_path);
+    log::info!("📁 Audio file found: {:?}", audio_files);
 
     // Whisper初期化状態確認
     let is_initialized = whisper_service.is_initialized().await;
@@ -200,24 +281,169 @@ pub async fn transcribe_recording(
         log::info!("🔄 Initializing Whisper service...");
         whisper_service.initialize().await.map_err(|e| {
             log::error!("❌ Failed to initialize Whisper: {}", e);
-            format!("Failed to initialize Whisper: {}", e)
+            e.localized()
         })?;
     }
 
+    // 言語が指定されていなければカテゴリの既定言語を使う
+    let sanitized_language = transcription_language::resolve_language(db.inner(), &recording, sanitized_language)
+        .await
+        .map_err(String::from)?;
+
     // 書き起こし実行（セキュリティ検証は WhisperService 内で実行）
     log::info!("🎵 Starting transcription...");
-    whisper_service
-        .transcribe_audio_file(&audio_path, sanitized_recording_id, sanitized_language)
+    let mut transcription = whisper_service
+        .transcribe_audio_files(&audio_files, sanitized_recording_id, sanitized_language)
         .await
-        .map_err(|e| {
-            // エラーログを記録（本番環境では詳細なエラー情報を隠蔽）
-            log::error!("❌ Transcription failed for recording {}: {}", recording_id, e);
-            format!("Transcription failed: {}", e)
-        })
-        .map(|result| {
-            log::info!("✅ Transcription completed for recording: {}", recording_id);
-            result
-        })
+        .map_err(|e| match e {
+            // 実行中のジョブIDをそのまま返す
+            AppError::TranscriptionInProgress { .. } => e.localized(),
+            _ => {
+                // エラーログを記録（本番環境では詳細なエラー情報を隠蔽）
+                log::error!("❌ Transcription failed for recording {}: {}", recording_id, e);
+                tr("command.transcription_failed", &[("error", &e.localized())])
+            }
+        })?;
+
+    // ユーザー辞書による誤認識の補正
+    crate::services::corrections::apply_corrections(db.inner(), &mut transcription)
+        .await
+        .map_err(String::from)?;
+
+    log::info!("✅ Transcription completed for recording: {}", recording_id);
+    Ok(transcription)
+}
+
+/// 複数の録音を並列に書き起こし、ファイルごとの進捗を "batch-transcription-progress" で通知
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn transcribe_recordings_batch(
+    app_handle: AppHandle,
+    window: Window,
+    db: State<'_, Arc<Database>>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+    settings_manager: State<'_, Arc<Mutex<AppSettingsManager>>>,
+    recording_ids: Vec<String>,
+    language: Option<String>,
+    max_workers: Option<usize>,
+) -> Result<BatchTranscriptionResult, String> {
+    log::info!("📚 transcribe_recordings_batch called for {} recordings", recording_ids.len());
+
+    validate_request(&app_handle)
+        .await
+        .map_err(String::from)?;
+
+    let recording_ids = recording_ids
+        .iter()
+        .map(|id| sanitize_string_input(id, 50))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(String::from)?;
+
+    let language = language
+        .map(|lang| sanitize_string_input(&lang, 10))
+        .transpose()
+        .map_err(String::from)?;
+
+    let configured = max_workers.or(settings_manager.lock().await.get_settings().batch_transcription.max_workers);
+    let workers = batch_transcription::resolve_worker_count(configured, &whisper_service.get_current_model_size());
+
+    batch_transcription::transcribe_recordings(
+        db.inner().clone(),
+        whisper_service.inner().clone(),
+        recording_ids,
+        language,
+        workers,
+        move |progress| {
+            let _ = window.emit("batch-transcription-progress", progress);
+        },
+    )
+    .await
+    .map_err(String::from)
+}
+
+/// 一括書き起こしの同時実行数を設定（None で自動）
+#[tauri::command]
+pub async fn set_batch_transcription_workers(
+    settings_manager: State<'_, Arc<Mutex<AppSettingsManager>>>,
+    max_workers: Option<usize>,
+) -> Result<(), String> {
+    log::info!("📚 Setting batch transcription workers: {:?}", max_workers);
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| {
+        settings.batch_transcription.max_workers = max_workers;
+    });
+    manager.save_settings().await.map_err(String::from)
+}
+
+/// 実行中の書き起こし一覧（録音IDとジョブID）
+#[tauri::command]
+pub async fn get_active_transcriptions(
+    whisper_service: State<'_, Arc<WhisperService>>,
+) -> Result<Vec<InFlightTranscription>, String> {
+    Ok(whisper_service.transcription_locks().list())
+}
+
+/// 信頼度が閾値を下回る区間（時刻付き）。UI で聞き直すべき箇所のハイライトに使う
+#[tauri::command]
+pub async fn get_low_confidence_regions(
+    db: State<'_, Arc<Database>>,
+    transcription_id: String,
+    threshold: Option<f32>,
+) -> Result<Vec<LowConfidenceRegion>, String> {
+    confidence_regions::get_low_confidence_regions(db.inner(), &transcription_id, threshold)
+        .await
+        .map_err(String::from)
+}
+
+/// 録音を停止して差し替え用スニペットとして保存（録音一覧には追加しない）
+#[tauri::command]
+pub async fn stop_snippet_recording(
+    recording_service: State<'_, Arc<RecordingService>>,
+) -> Result<String, String> {
+    recording_service
+        .stop_snippet_recording()
+        .await
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(String::from)
+}
+
+/// 録り直したスニペットだけを書き起こし、該当セグメントと本文を差し替える
+#[tauri::command]
+pub async fn rerecord_segment(
+    db: State<'_, Arc<Database>>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+    segment_id: String,
+    snippet_path: String,
+    language: Option<String>,
+) -> Result<TranscriptEdit, String> {
+    let snippet_path = PathBuf::from(&snippet_path);
+    if !snippet_path.exists() {
+        return Err(t("command.audio_file_not_found"));
+    }
+
+    transcript_edits::rerecord_segment(db.inner(), whisper_service.inner(), &segment_id, &snippet_path, language)
+        .await
+        .map_err(|e| e.localized())
+}
+
+#[tauri::command]
+pub async fn edit_transcript_segment(
+    db: State<'_, Arc<Database>>,
+    segment_id: String,
+    text: String,
+) -> Result<TranscriptEdit, String> {
+    transcript_edits::edit_segment_text(db.inner(), &segment_id, &text)
+        .await
+        .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn get_transcript_edits(
+    db: State<'_, Arc<Database>>,
+    transcription_id: String,
+) -> Result<Vec<TranscriptEdit>, String> {
+    db.get_transcript_edits(&transcription_id).await.map_err(String::from)
 }
 
 #[tauri::command]
@@ -227,7 +453,31 @@ pub async fn initialize_whisper(
     whisper_service
         .initialize()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(String::from)
+}
+
+/// Python・whisper・ffmpeg・モデル・書き込み権限を確認し、対処方法付きで返す
+#[tauri::command]
+pub async fn check_transcription_environment(
+    whisper_service: State<'_, Arc<WhisperService>>,
+) -> Result<EnvironmentReport, String> {
+    Ok(environment_doctor::check_transcription_environment(&whisper_service).await)
+}
+
+/// 書き起こし開始前の所要時間・メモリの見積もり（models 未指定なら全モデル）
+#[tauri::command]
+pub async fn estimate_transcription(
+    db: State<'_, Arc<Database>>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+    settings_manager: State<'_, Arc<Mutex<AppSettingsManager>>>,
+    recording_id: String,
+    models: Option<Vec<String>>,
+) -> Result<Vec<TranscriptionEstimate>, String> {
+    let models = models.unwrap_or_else(transcription_estimate::known_models);
+    let acceleration = settings_manager.lock().await.get_settings().whisper_acceleration.clone();
+    transcription_estimate::estimate_for_recording_on(&db, &recording_id, &models, whisper_service.device(), &acceleration)
+        .await
+        .map_err(String::from)
 }
 
 #[tauri::command]
@@ -243,3 +493,34 @@ pub mod streaming;
 pub mod model_management;
 pub mod model_settings;
 pub mod model_downloader;
+pub mod api_server;
+pub mod phone_mic;
+pub mod voice_memo;
+pub mod control_server;
+pub mod recording_profile;
+pub mod whisper_acceleration;
+pub mod whisper_backend;
+pub mod silence_trim;
+pub mod input_gain;
+pub mod noise_suppression;
+pub mod sleep_prevention;
+pub mod loudness;
+pub mod playback;
+pub mod audio_edit;
+pub mod import;
+pub mod chapters;
+pub mod captions;
+pub mod live_transcription;
+pub mod action_items;
+pub mod keyword_alerts;
+pub mod corrections;
+pub mod analytics;
+pub mod integrations;
+pub mod jobs;
+pub mod locale;
+pub mod demo_mode;
+pub mod digest_schedule;
+pub mod storage;
+pub mod notes;
+pub mod workspaces;
+pub mod tray;
@@ -1,3 +1,4 @@
+use crate::services::i18n::{t, tr};
 use crate::services::{ModelDownloader, DownloadableModel, SystemCompatibility, DownloadProgress};
 use std::sync::Arc;
 use tauri::State;
@@ -41,7 +42,7 @@ pub async fn check_system_requirements(
 ) -> Result<SystemCompatibility, String> {
     let downloader = downloader.lock().await;
     let compatibility = downloader.check_system_requirements(&model_id)
-        .map_err(|e| e.to_string())?;
+        .map_err(String::from)?;
     
     log::info!("🔍 System compatibility check for {}: compatible={}", 
                model_id, compatibility.is_fully_compatible());
@@ -60,7 +61,7 @@ pub async fn start_model_download(
     // モデルIDを分解
     let parts: Vec<&str> = model_id.split(':').collect();
     if parts.len() != 2 {
-        return Err("Invalid model ID format".to_string());
+        return Err(t("command.invalid_model_id"));
     }
     
     let provider = parts[0];
@@ -70,10 +71,10 @@ pub async fn start_model_download(
         "ollama" => {
             downloader.start_download_ollama(model_name)
                 .await
-                .map_err(|e| e.to_string())
+                .map_err(String::from)
         }
         _ => {
-            Err(format!("Download not supported for provider: {}", provider))
+            Err(tr("command.download_not_supported", &[("provider", &provider)]))
         }
     }
 }
@@ -88,7 +89,7 @@ pub async fn get_download_command(
     
     let model = models.iter()
         .find(|m| m.id == model_id)
-        .ok_or_else(|| format!("Model not found: {}", model_id))?;
+        .ok_or_else(|| tr("command.model_not_found", &[("id", &model_id)]))?;
     
     log::info!("📋 Retrieved download command for: {}", model_id);
     Ok(model.download_command.clone())
@@ -133,7 +134,7 @@ pub async fn get_gpt4all_download_info(
 ) -> Result<String, String> {
     let downloader = downloader.lock().await;
     let download_url = downloader.get_gpt4all_download_info(&model_name)
-        .map_err(|e| e.to_string())?;
+        .map_err(String::from)?;
     
     log::info!("📥 GPT4All download info for {}: {}", model_name, download_url);
     Ok(download_url)
@@ -224,11 +225,11 @@ pub async fn estimate_download_time(
     
     let model = models.iter()
         .find(|m| m.id == model_id)
-        .ok_or_else(|| format!("Model not found: {}", model_id))?;
+        .ok_or_else(|| tr("command.model_not_found", &[("id", &model_id)]))?;
     
     let file_size_mb = match model.file_size {
         Some(bytes) => bytes as f64 / (1024.0 * 1024.0),
-        None => return Err("Model file size unknown".to_string()),
+        None => return Err(t("command.model_size_unknown")),
     };
     
     // ダウンロード時間を秒単位で計算
@@ -272,30 +272,15 @@ fn get_use_cases_for_model(model_name: &str) -> Vec<String> {
 
 #[tauri::command]
 pub async fn estimate_processing_time(
+    model_manager: State<'_, ModelManagerState>,
     model_id: String,
     text_length: u32,
 ) -> Result<f64, String> {
     log::debug!("⏱️ Estimating processing time for model: {} (text length: {})", model_id, text_length);
     
-    // モデルサイズに基づく処理速度の推定
-    let model_name = model_id.split(':').nth(1).unwrap_or("");
-    let tokens_per_second = if model_name.contains("1b") {
-        50.0 // 高速
-    } else if model_name.contains("3b") {
-        30.0 // 中速
-    } else if model_name.contains("7b") {
-        15.0 // 標準
-    } else if model_name.contains("13b") {
-        8.0 // やや低速
-    } else if model_name.contains("70b") {
-        2.0 // 低速
-    } else {
-        20.0 // デフォルト
-    };
-    
-    // テキスト長からトークン数を推定（1トークン ≈ 4文字）
-    let estimated_tokens = text_length as f64 / 4.0;
-    let estimated_time = estimated_tokens / tokens_per_second;
+    // ベンチマーク済みのモデルは実測の速度、それ以外はモデルサイズから推定
+    let manager = model_manager.lock().await;
+    let estimated_time = manager.estimate_processing_time(&model_id, text_length);
     
     log::debug!("⏱️ Estimated processing time: {:.2}s", estimated_time);
     Ok(estimated_time)
@@ -1,9 +1,13 @@
-use crate::services::{ModelSettings, ModelPreference, PerformancePriority, ModelSettingsManager};
+use crate::errors::AppError;
+use crate::services::i18n::{t, tr};
+use crate::services::{model_selection, ollama_pool};
+use crate::services::{LLMModelManager, ModelSettings, ModelPreference, PerformancePriority, ModelSettingsManager, ProviderRace};
 use std::sync::Arc;
 use tauri::State;
 use tokio::sync::Mutex;
 
 type ModelSettingsState = Arc<Mutex<ModelSettingsManager>>;
+type ModelManagerState = Arc<Mutex<LLMModelManager>>;
 
 #[tauri::command]
 pub async fn get_model_settings(
@@ -22,7 +26,7 @@ pub async fn save_model_settings(
     
     let mut manager = settings_manager.lock().await;
     let changed = manager.auto_save_if_changed(new_settings).await
-        .map_err(|e| e.to_string())?;
+        .map_err(String::from)?;
     
     if changed {
         log::info!("✅ Model settings saved successfully");
@@ -45,7 +49,7 @@ pub async fn set_default_model(
         settings.set_default_model(model_id.clone());
     });
     
-    manager.save_settings().await.map_err(|e| e.to_string())?;
+    manager.save_settings().await.map_err(String::from)?;
     log::info!("✅ Default model updated to: {}", model_id);
     
     Ok(())
@@ -64,7 +68,7 @@ pub async fn set_use_case_default(
         settings.set_use_case_default(use_case.clone(), model_id.clone());
     });
     
-    manager.save_settings().await.map_err(|e| e.to_string())?;
+    manager.save_settings().await.map_err(String::from)?;
     log::info!("✅ Use case default updated: {} -> {}", use_case, model_id);
     
     Ok(())
@@ -81,7 +85,7 @@ pub async fn add_model_preference(
     log::info!("⚙️ Adding model preference: {} (enabled: {}, priority: {})", model_id, enabled, priority);
     
     if priority > 10 {
-        return Err("Priority must be between 1 and 10".to_string());
+        return Err(t("command.priority_out_of_range"));
     }
     
     let preference = ModelPreference {
@@ -97,7 +101,7 @@ pub async fn add_model_preference(
         settings.set_model_preference(model_id.clone(), preference);
     });
     
-    manager.save_settings().await.map_err(|e| e.to_string())?;
+    manager.save_settings().await.map_err(String::from)?;
     log::info!("✅ Model preference added for: {}", model_id);
     
     Ok(())
@@ -115,7 +119,7 @@ pub async fn remove_model_preference(
         settings.model_preferences.remove(&model_id);
     });
     
-    manager.save_settings().await.map_err(|e| e.to_string())?;
+    manager.save_settings().await.map_err(String::from)?;
     log::info!("✅ Model preference removed for: {}", model_id);
     
     Ok(())
@@ -133,7 +137,7 @@ pub async fn set_performance_priority(
         "quality" => PerformancePriority::Quality,
         "balance" => PerformancePriority::Balance,
         "memory" => PerformancePriority::Memory,
-        _ => return Err("Invalid performance priority".to_string()),
+        _ => return Err(t("command.invalid_performance_priority")),
     };
     
     let mut manager = settings_manager.lock().await;
@@ -141,7 +145,7 @@ pub async fn set_performance_priority(
         settings.performance_priority = priority_enum;
     });
     
-    manager.save_settings().await.map_err(|e| e.to_string())?;
+    manager.save_settings().await.map_err(String::from)?;
     log::info!("✅ Performance priority updated to: {}", priority);
     
     Ok(())
@@ -159,12 +163,56 @@ pub async fn set_auto_switch_enabled(
         settings.auto_switch_enabled = enabled;
     });
     
-    manager.save_settings().await.map_err(|e| e.to_string())?;
+    manager.save_settings().await.map_err(String::from)?;
     log::info!("✅ Auto-switch updated to: {}", enabled);
     
     Ok(())
 }
 
+/// 要約を同時に依頼する2つのプロバイダーを設定（None で無効）
+#[tauri::command]
+pub async fn set_provider_race(
+    settings_manager: State<'_, ModelSettingsState>,
+    race: Option<ProviderRace>,
+) -> Result<(), String> {
+    log::info!("🏁 Setting provider race: {}", race.is_some());
+
+    let mut manager = settings_manager.lock().await;
+    let mut updated = manager.get_settings().clone();
+    updated.provider_race = race;
+
+    let errors = updated.validate();
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+
+    manager.update_settings(|settings| *settings = updated);
+    manager.save_settings().await.map_err(String::from)?;
+    Ok(())
+}
+
+/// 要約を振り分けるOllamaホストを設定（空なら振り分けない）
+#[tauri::command]
+pub async fn set_ollama_hosts(
+    settings_manager: State<'_, ModelSettingsState>,
+    hosts: Vec<String>,
+) -> Result<(), String> {
+    log::info!("🖧 Setting {} Ollama hosts", hosts.len());
+
+    let mut manager = settings_manager.lock().await;
+    let mut updated = manager.get_settings().clone();
+    updated.ollama_hosts = ollama_pool::normalize_hosts(&hosts);
+
+    let errors = updated.validate();
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+
+    manager.update_settings(|settings| *settings = updated);
+    manager.save_settings().await.map_err(String::from)?;
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_optimal_model_for_use_case(
     settings_manager: State<'_, ModelSettingsState>,
@@ -215,7 +263,7 @@ pub async fn reset_model_settings(
         settings.reset_to_defaults();
     });
     
-    manager.save_settings().await.map_err(|e| e.to_string())?;
+    manager.save_settings().await.map_err(String::from)?;
     log::info!("✅ Model settings reset to defaults");
     
     Ok(())
@@ -227,7 +275,7 @@ pub async fn export_model_settings(
 ) -> Result<String, String> {
     let manager = settings_manager.lock().await;
     let settings_json = serde_json::to_string_pretty(manager.get_settings())
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| AppError::from(e).localized())?;
     
     log::info!("📤 Model settings exported");
     Ok(settings_json)
@@ -242,12 +290,12 @@ pub async fn import_model_settings(
     log::info!("📥 Importing model settings (merge: {})", merge_with_existing);
     
     let imported_settings: ModelSettings = serde_json::from_str(&settings_json)
-        .map_err(|e| format!("Invalid settings format: {}", e))?;
+        .map_err(|e| tr("command.invalid_settings_format", &[("error", &e)]))?;
     
     // 設定のバリデーション
     let validation_errors = imported_settings.validate();
     if !validation_errors.is_empty() {
-        return Err(format!("Settings validation failed: {:?}", validation_errors));
+        return Err(tr("command.settings_validation_failed", &[("errors", &validation_errors.join(", "))]));
     }
     
     let mut manager = settings_manager.lock().await;
@@ -259,7 +307,7 @@ pub async fn import_model_settings(
         }
     });
     
-    manager.save_settings().await.map_err(|e| e.to_string())?;
+    manager.save_settings().await.map_err(String::from)?;
     log::info!("✅ Model settings imported successfully");
     
     Ok(())
@@ -267,6 +315,8 @@ pub async fn import_model_settings(
 
 #[tauri::command]
 pub async fn get_performance_recommendations(
+    settings_manager: State<'_, ModelSettingsState>,
+    model_manager: State<'_, ModelManagerState>,
     use_case: String,
     text_length: u32,
     available_memory_mb: Option<u32>,
@@ -275,61 +325,20 @@ pub async fn get_performance_recommendations(
     log::debug!("🎯 Getting performance recommendations for: {} (length: {}, speed_priority: {})", 
                use_case, text_length, speed_priority);
     
-    let mut recommendations = Vec::new();
-    
     // メモリ制約の考慮
     let memory_limit = available_memory_mb.unwrap_or(8192); // デフォルト8GB
-    
-    // テキスト長に基づく推奨
-    if text_length < 1000 {
-        // 短いテキスト - 高速モデル推奨
-        if speed_priority > 0.7 {
-            recommendations.extend(vec![
-                "ollama:llama3.2:1b".to_string(),
-                "gpt4all:orca-mini".to_string(),
-            ]);
-        }
-        recommendations.push("ollama:llama3.2:3b".to_string());
-    } else if text_length < 10000 {
-        // 中程度のテキスト - バランス型推奨
-        recommendations.extend(vec![
-            "ollama:llama3.2:3b".to_string(),
-            "ollama:mistral:7b".to_string(),
-        ]);
-        
-        if speed_priority < 0.5 && memory_limit >= 16000 {
-            recommendations.push("ollama:llama3.2:7b".to_string());
-        }
-    } else {
-        // 長いテキスト - 高品質モデル推奨
-        if memory_limit >= 16000 {
-            recommendations.push("ollama:llama3.2:7b".to_string());
-        }
-        recommendations.extend(vec![
-            "ollama:llama3.2:3b".to_string(),
-            "ollama:mistral:7b".to_string(),
-        ]);
-    }
-    
-    // 用途別フィルタ
-    match use_case.as_str() {
-        "japanese" => {
-            // 日本語対応を優先
-            recommendations.retain(|model| {
-                model.contains("llama") || model.contains("mistral")
-            });
-        }
-        "code" => {
-            // コード関連を優先  
-            recommendations.insert(0, "ollama:codellama:7b".to_string());
-        }
-        _ => {}
-    }
-    
-    // 重複除去と最大5個に制限
-    recommendations.dedup();
-    recommendations.truncate(5);
-    
-    log::debug!("🎯 Generated {} recommendations", recommendations.len());
+    let candidates = model_selection::heuristic_recommendations(&use_case, text_length, memory_limit, speed_priority);
+    
+    // 実際に利用できるモデルとユーザーの優先度で絞り込み・並べ替え
+    let settings = settings_manager.lock().await.get_settings().clone();
+    let installed = model_manager
+        .lock()
+        .await
+        .discover_available_models()
+        .await
+        .map_err(String::from)?;
+    let recommendations = model_selection::rank_recommendations(&candidates, &installed, &settings, memory_limit as u64);
+    
+    log::debug!("🎯 Generated {} recommendations from {} installed models", recommendations.len(), installed.len());
     Ok(recommendations)
-}
\ No newline at end of file
+}
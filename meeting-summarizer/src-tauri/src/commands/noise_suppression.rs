@@ -0,0 +1,29 @@
+use crate::services::{AppSettingsManager, NoiseSuppressionSettings, RecordingService};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+#[tauri::command]
+pub async fn get_noise_suppression(
+    recording_service: State<'_, Arc<RecordingService>>,
+) -> Result<NoiseSuppressionSettings, String> {
+    Ok(recording_service.noise_suppression().await)
+}
+
+/// 録音時のノイズ除去を切り替える（録音中でもすぐに反映）
+#[tauri::command]
+pub async fn set_noise_suppression(
+    settings_manager: State<'_, AppSettingsState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    settings: NoiseSuppressionSettings,
+) -> Result<NoiseSuppressionSettings, String> {
+    log::info!("🔇 Setting noise suppression: {:?}", settings);
+    recording_service.set_noise_suppression(settings).await;
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|app_settings| app_settings.noise_suppression = settings);
+    manager.save_settings().await.map_err(String::from)?;
+    Ok(settings)
+}
@@ -0,0 +1,169 @@
+use crate::database::Database;
+use crate::models::{MeetingNote, RecordingMarker};
+use crate::services::i18n::tr;
+use crate::services::{meeting_notes, recording_markers};
+use crate::services::{AppSettingsManager, RecordingService, UserProfile};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type DbState = Arc<Database>;
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+/// 現在のユーザーの表示名（メモの入力者として記録する）
+async fn current_author(settings_manager: &AppSettingsState) -> Option<String> {
+    settings_manager.lock().await.get_settings().user_profile.author_name()
+}
+
+/// 録音中にメモを追加（停止時に録音へ紐付けて保存される）
+#[tauri::command]
+pub async fn add_live_note(
+    recording_service: State<'_, Arc<RecordingService>>,
+    settings_manager: State<'_, AppSettingsState>,
+    text: String,
+    important: Option<bool>,
+) -> Result<MeetingNote, String> {
+    let author = current_author(&settings_manager).await;
+    recording_service
+        .add_live_note(&text, important.unwrap_or(false), author)
+        .await
+        .map_err(String::from)
+}
+
+/// 録音中に入力したメモの一覧
+#[tauri::command]
+pub async fn get_live_notes(
+    recording_service: State<'_, Arc<RecordingService>>,
+) -> Result<Vec<MeetingNote>, String> {
+    Ok(recording_service.live_notes().await)
+}
+
+/// 録音後にメモを追加
+#[tauri::command]
+pub async fn add_meeting_note(
+    db: State<'_, DbState>,
+    settings_manager: State<'_, AppSettingsState>,
+    recording_id: String,
+    text: String,
+    offset_ms: Option<i64>,
+    important: Option<bool>,
+) -> Result<MeetingNote, String> {
+    db.get_recording(&recording_id)
+        .await
+        .map_err(String::from)?
+        .ok_or_else(|| tr("command.recording_not_found", &[("id", &recording_id)]))?;
+
+    let text = meeting_notes::validate_note_text(&text).map_err(String::from)?;
+    let mut note = MeetingNote::new(recording_id, text).with_author(current_author(&settings_manager).await);
+    note.offset_ms = offset_ms.map(|ms| ms.max(0));
+    note.important = important.unwrap_or(false);
+
+    db.create_meeting_note(&note).await.map_err(String::from)?;
+    Ok(note)
+}
+
+#[tauri::command]
+pub async fn get_meeting_notes(
+    db: State<'_, DbState>,
+    recording_id: String,
+) -> Result<Vec<MeetingNote>, String> {
+    db.get_meeting_notes(&recording_id).await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn update_meeting_note(
+    db: State<'_, DbState>,
+    mut note: MeetingNote,
+) -> Result<bool, String> {
+    note.text = meeting_notes::validate_note_text(&note.text).map_err(String::from)?;
+    db.update_meeting_note(&note).await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn delete_meeting_note(
+    db: State<'_, DbState>,
+    id: String,
+) -> Result<bool, String> {
+    db.delete_meeting_note(&id).await.map_err(String::from)
+}
+
+/// この端末のユーザープロフィール
+#[tauri::command]
+pub async fn get_user_profile(settings_manager: State<'_, AppSettingsState>) -> Result<UserProfile, String> {
+    Ok(settings_manager.lock().await.get_settings().user_profile.clone())
+}
+
+/// 表示名を保存（以降に追加するメモの入力者になる。空なら OS のユーザー名を使う）
+#[tauri::command]
+pub async fn set_user_profile(
+    settings_manager: State<'_, AppSettingsState>,
+    mut profile: UserProfile,
+) -> Result<UserProfile, String> {
+    profile.display_name = profile
+        .display_name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+    log::info!("👤 Setting user profile: {:?}", profile.display_name);
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| {
+        settings.user_profile = profile.clone();
+    });
+    manager.save_settings().await.map_err(String::from)?;
+    Ok(profile)
+}
+
+/// 録音中にマーカーを付ける（ホットキーから呼ぶ想定。停止時に録音へ紐付けて保存される）
+#[tauri::command]
+pub async fn add_recording_marker(
+    recording_service: State<'_, Arc<RecordingService>>,
+    label: Option<String>,
+) -> Result<RecordingMarker, String> {
+    recording_service.add_marker(label).await.map_err(String::from)
+}
+
+/// 録音中に付けたマーカーの一覧
+#[tauri::command]
+pub async fn get_live_markers(
+    recording_service: State<'_, Arc<RecordingService>>,
+) -> Result<Vec<RecordingMarker>, String> {
+    Ok(recording_service.live_markers().await)
+}
+
+/// 録音のマーカー（波形表示用、時刻順）
+#[tauri::command]
+pub async fn get_recording_markers(
+    db: State<'_, DbState>,
+    recording_id: String,
+) -> Result<Vec<RecordingMarker>, String> {
+    db.get_markers(&recording_id).await.map_err(String::from)
+}
+
+/// 書き起こしの発言の間にマーカーを並べた一覧（書き起こしの表示用）
+#[tauri::command]
+pub async fn get_transcript_timeline(
+    db: State<'_, DbState>,
+    transcription_id: String,
+) -> Result<Vec<recording_markers::TimelineItem>, String> {
+    recording_markers::timeline_for_transcription(&db, &transcription_id)
+        .await
+        .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn update_recording_marker_label(
+    db: State<'_, DbState>,
+    id: String,
+    label: Option<String>,
+) -> Result<bool, String> {
+    let label = recording_markers::normalize_label(label);
+    db.update_marker_label(&id, label.as_deref()).await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn delete_recording_marker(
+    db: State<'_, DbState>,
+    id: String,
+) -> Result<bool, String> {
+    db.delete_marker(&id).await.map_err(String::from)
+}
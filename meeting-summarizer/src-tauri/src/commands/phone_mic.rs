@@ -0,0 +1,48 @@
+use crate::database::Database;
+use crate::services::phone_mic;
+use crate::services::{AppSettingsManager, PhoneMicServer, PhoneMicStatus, RecordingService};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type DbState = Arc<Database>;
+type PhoneMicHandle = Arc<Mutex<PhoneMicServer>>;
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+fn lan_host() -> String {
+    phone_mic::lan_address()
+        .map(|ip| ip.to_string())
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+#[tauri::command]
+pub async fn get_phone_mic_status(phone_mic: State<'_, PhoneMicHandle>) -> Result<PhoneMicStatus, String> {
+    Ok(phone_mic.lock().await.get_status(&lan_host()))
+}
+
+/// スマートフォンからの音声取り込みを開始し、スマートフォンで開くURLを返す
+#[tauri::command]
+pub async fn start_phone_mic(
+    phone_mic: State<'_, PhoneMicHandle>,
+    settings_manager: State<'_, AppSettingsState>,
+    db: State<'_, DbState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    port: Option<u16>,
+) -> Result<PhoneMicStatus, String> {
+    let port = match port {
+        Some(port) => port,
+        None => settings_manager.lock().await.get_settings().phone_mic.port,
+    };
+
+    let mut server = phone_mic.lock().await;
+    server
+        .start(db.inner().clone(), recording_service.inner().clone(), port)
+        .await
+        .map_err(String::from)?;
+    Ok(server.get_status(&lan_host()))
+}
+
+#[tauri::command]
+pub async fn stop_phone_mic(phone_mic: State<'_, PhoneMicHandle>) -> Result<(), String> {
+    phone_mic.lock().await.stop().await.map_err(String::from)
+}
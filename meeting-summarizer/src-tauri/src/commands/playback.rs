@@ -0,0 +1,71 @@
+use crate::database::Database;
+use crate::services::playback;
+use crate::services::{PlaybackService, PlaybackStatus};
+use std::sync::Arc;
+use tauri::State;
+
+type DbState = Arc<Database>;
+
+/// 録音を再生する（`start_ms` を省略すると先頭から）
+#[tauri::command]
+pub async fn play_recording(
+    db: State<'_, DbState>,
+    playback_service: State<'_, Arc<PlaybackService>>,
+    recording_id: String,
+    start_ms: Option<i64>,
+) -> Result<PlaybackStatus, String> {
+    log::info!("▶️ play_recording command called: {} from {:?}", recording_id, start_ms);
+    let database = db.inner();
+    let recording = database
+        .get_recording(&recording_id)
+        .await
+        .map_err(String::from)?
+        .ok_or_else(|| format!("Recording not found: {}", recording_id))?;
+    let (tracks, duration_ms) = playback::recording_tracks(database, &recording)
+        .await
+        .map_err(String::from)?;
+
+    let playback_service = playback_service.inner().clone();
+    let start_ms = start_ms.unwrap_or(0).max(0);
+    // 出力デバイスを開くまで待つため、非同期ランタイムを止めないよう別スレッドで呼ぶ
+    tokio::task::spawn_blocking(move || {
+        playback_service
+            .play(&recording_id, tracks, duration_ms, start_ms)
+            .map(|_| playback_service.status())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn pause_playback(playback_service: State<'_, Arc<PlaybackService>>) -> Result<(), String> {
+    playback_service.pause().map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn resume_playback(playback_service: State<'_, Arc<PlaybackService>>) -> Result<(), String> {
+    playback_service.resume().map_err(String::from)
+}
+
+/// 再生位置を移す（書き起こしのタイムスタンプをクリックしたときなど）
+#[tauri::command]
+pub async fn seek_playback(
+    playback_service: State<'_, Arc<PlaybackService>>,
+    position_ms: i64,
+) -> Result<(), String> {
+    playback_service.seek(position_ms).map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn stop_playback(playback_service: State<'_, Arc<PlaybackService>>) -> Result<(), String> {
+    playback_service.stop();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_playback_status(
+    playback_service: State<'_, Arc<PlaybackService>>,
+) -> Result<PlaybackStatus, String> {
+    Ok(playback_service.status())
+}
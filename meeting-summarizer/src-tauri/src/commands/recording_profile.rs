@@ -0,0 +1,73 @@
+use crate::services::capture_source;
+use crate::services::recording_encoder;
+use crate::services::recording_quality::{self, RecordingQualityPreset};
+use crate::services::{AppSettingsManager, CaptureApplication, RecordingFormat, RecordingProfile, RecordingService};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+#[tauri::command]
+pub async fn get_recording_profile(settings_manager: State<'_, AppSettingsState>) -> Result<RecordingProfile, String> {
+    Ok(settings_manager.lock().await.get_settings().recording_profile.clone())
+}
+
+/// 録音の設定を保存し、次の録音から反映する
+#[tauri::command]
+pub async fn set_recording_profile(
+    settings_manager: State<'_, AppSettingsState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    profile: RecordingProfile,
+) -> Result<RecordingProfile, String> {
+    log::info!(
+        "🎚️ Setting recording source: {:?} (dual: {:?}, echo cancellation: {}, quality: {:?})",
+        profile.source,
+        profile.dual_source,
+        profile.echo_cancellation,
+        profile.quality
+    );
+    let format = profile.format;
+    tokio::task::spawn_blocking(move || recording_encoder::ensure_supported(format))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(String::from)?;
+    recording_service
+        .apply_recording_profile(&profile)
+        .await
+        .map_err(String::from)?;
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| settings.recording_profile = profile.clone());
+    manager.save_settings().await.map_err(String::from)?;
+    Ok(profile)
+}
+
+/// アプリ単位で録音できる会議アプリ
+#[tauri::command]
+pub async fn get_capture_applications() -> Result<Vec<CaptureApplication>, String> {
+    Ok(capture_source::supported_applications())
+}
+
+/// この環境でアプリ単位に録音できる会議アプリ（起動しているものが先頭）
+#[tauri::command]
+pub async fn list_capturable_applications() -> Result<Vec<CaptureApplication>, String> {
+    tokio::task::spawn_blocking(capture_source::capturable_applications)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(String::from)
+}
+
+/// 選べる録音の音質
+#[tauri::command]
+pub async fn get_recording_qualities() -> Result<Vec<RecordingQualityPreset>, String> {
+    Ok(recording_quality::presets())
+}
+
+/// 選べる保存形式（ffmpeg が無い環境では Opus・MP3 を出さない）
+#[tauri::command]
+pub async fn get_recording_formats() -> Result<Vec<RecordingFormat>, String> {
+    tokio::task::spawn_blocking(recording_encoder::format_options)
+        .await
+        .map_err(|e| e.to_string())
+}
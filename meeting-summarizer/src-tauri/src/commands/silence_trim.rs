@@ -0,0 +1,30 @@
+use crate::services::{AppSettingsManager, SilenceTrimSettings, WhisperService};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+#[tauri::command]
+pub async fn get_silence_trim_settings(
+    whisper_service: State<'_, Arc<WhisperService>>,
+) -> Result<SilenceTrimSettings, String> {
+    Ok(whisper_service.silence_trim())
+}
+
+/// 書き起こし前の無音の除去を設定する（次の書き起こしから反映）
+#[tauri::command]
+pub async fn set_silence_trim_settings(
+    settings_manager: State<'_, AppSettingsState>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+    settings: SilenceTrimSettings,
+) -> Result<SilenceTrimSettings, String> {
+    settings.validate().map_err(String::from)?;
+    log::info!("✂️ Setting silence trimming: {:?}", settings);
+    whisper_service.set_silence_trim(settings.clone());
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|app_settings| app_settings.silence_trim = settings.clone());
+    manager.save_settings().await.map_err(String::from)?;
+    Ok(settings)
+}
@@ -0,0 +1,29 @@
+use crate::services::{AppSettingsManager, RecordingService, SleepPreventionSettings};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+#[tauri::command]
+pub async fn get_sleep_prevention(
+    recording_service: State<'_, Arc<RecordingService>>,
+) -> Result<SleepPreventionSettings, String> {
+    Ok(recording_service.sleep_prevention())
+}
+
+/// 録音中のスリープ防止を切り替える（録音中でもすぐに反映）
+#[tauri::command]
+pub async fn set_sleep_prevention(
+    settings_manager: State<'_, AppSettingsState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    settings: SleepPreventionSettings,
+) -> Result<SleepPreventionSettings, String> {
+    log::info!("☕ Setting sleep prevention: {:?}", settings);
+    recording_service.set_sleep_prevention(settings).await;
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|app_settings| app_settings.sleep_prevention = settings);
+    manager.save_settings().await.map_err(String::from)?;
+    Ok(settings)
+}
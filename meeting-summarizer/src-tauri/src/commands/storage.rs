@@ -0,0 +1,51 @@
+use crate::database::Database;
+use crate::services::storage_quota::{self, StorageReport};
+use crate::services::{AppSettingsManager, RecordingService, StorageSettings};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type DbState = Arc<Database>;
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+/// 録音ディレクトリの使用量と、容量を空けられる古い録音の候補
+#[tauri::command]
+pub async fn get_storage_report(
+    db: State<'_, DbState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    settings_manager: State<'_, AppSettingsState>,
+) -> Result<StorageReport, String> {
+    let settings = settings_manager.lock().await.get_settings().storage.clone();
+    storage_quota::storage_report(&db, recording_service.recordings_dir(), &settings)
+        .await
+        .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn get_storage_settings(settings_manager: State<'_, AppSettingsState>) -> Result<StorageSettings, String> {
+    Ok(settings_manager.lock().await.get_settings().storage.clone())
+}
+
+/// 容量上限と警告のしきい値を保存（quota_bytes が None なら警告しない）
+#[tauri::command]
+pub async fn set_storage_settings(
+    settings_manager: State<'_, AppSettingsState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    storage: StorageSettings,
+) -> Result<(), String> {
+    if storage
+        .warning_thresholds
+        .iter()
+        .any(|threshold| !(0.0..=1.0).contains(threshold))
+    {
+        return Err("Warning thresholds must be between 0.0 and 1.0".to_string());
+    }
+    log::info!("💽 Setting storage quota: {:?} bytes", storage.quota_bytes);
+    recording_service.set_min_free_space(storage.min_free_bytes).await;
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| {
+        settings.storage = storage;
+    });
+    manager.save_settings().await.map_err(String::from)
+}
@@ -1,12 +1,18 @@
 use crate::database::Database;
 use crate::models::{LLMConfig, Summary};
-use crate::services::LLMService;
+use crate::services::i18n::{t, tr};
+use crate::services::rolling_summary::{self, RollingSummarizer, RollingSummary};
+use crate::services::summarization_status::{SummarizationStatus, SummarizationStatusRegistry};
+use crate::services::summarizer::SummaryRoute;
+use crate::services::{LLMService, Summarizer};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tauri::{Emitter, State, Window};
-use tokio::sync::Mutex;
 
-type DbState = Arc<Mutex<Database>>;
+type DbState = Arc<Database>;
+type StatusRegistryState = Arc<SummarizationStatusRegistry>;
+type RollingSummaryState = Arc<RollingSummarizer>;
+type SummarizerState = Arc<Summarizer>;
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct SummarizationProgress {
@@ -22,87 +28,98 @@ pub struct SummarizationProgress {
 pub async fn generate_summary_with_progress(
     window: Window,
     db: State<'_, DbState>,
+    status_registry: State<'_, StatusRegistryState>,
+    summarizer: State<'_, SummarizerState>,
     transcription_text: String,
     transcription_id: String,
     model_config: Option<LLMConfig>,
+    include_notes: Option<bool>,
 ) -> Result<Summary, String> {
-    let database = db.lock().await;
+    let database = db.inner();
     
-    // Use provided config or default
-    let config = model_config.unwrap_or_default();
-    let llm_service = LLMService::new(config.clone());
+    // Use provided config, the auto-selected model, the provider race, or default
+    let route = summarizer.route(model_config, &transcription_text).await;
     
     log::info!("🤖 Starting summarization with progress tracking for transcription: {}", transcription_id);
+    status_registry.start(&transcription_id, t("summarization.initializing"));
     
     // Emit initial progress
-    let _ = window.emit("summarization-progress", SummarizationProgress {
+    report(&window, &status_registry, &transcription_id, SummarizationProgress {
         stage: "initializing".to_string(),
-        message: "LLM接続を初期化中...".to_string(),
+        message: t("summarization.initializing"),
         progress: 0.1,
         summary_id: None,
         completed: false,
         error: None,
     });
     
-    // Check LLM connection
-    match llm_service.check_connection().await {
-        Ok(true) => {
-            let _ = window.emit("summarization-progress", SummarizationProgress {
-                stage: "connected".to_string(),
-                message: format!("{}に接続済み", config.model_name),
-                progress: 0.2,
-                summary_id: None,
-                completed: false,
-                error: None,
-            });
-        }
-        Ok(false) => {
-            let error_msg = format!("LLMサーバーに接続できません: {}", config.base_url);
-            let _ = window.emit("summarization-progress", SummarizationProgress {
-                stage: "error".to_string(),
-                message: error_msg.clone(),
-                progress: 0.0,
-                summary_id: None,
-                completed: false,
-                error: Some(error_msg.clone()),
-            });
-            return Err(error_msg);
-        }
-        Err(e) => {
-            let error_msg = format!("接続チェック中にエラー: {}", e);
-            let _ = window.emit("summarization-progress", SummarizationProgress {
-                stage: "error".to_string(),
-                message: error_msg.clone(),
-                progress: 0.0,
-                summary_id: None,
-                completed: false,
-                error: Some(error_msg.clone()),
-            });
-            return Err(error_msg);
+    // Check LLM connection（同時要約は先に成功した方を使うので個別には確認しない）
+    if let SummaryRoute::Single(config) = &route {
+        let llm_service = LLMService::new(config.clone());
+        match llm_service.check_connection().await {
+            Ok(true) => {
+                report(&window, &status_registry, &transcription_id, SummarizationProgress {
+                    stage: "connected".to_string(),
+                    message: tr("summarization.connected", &[("model", &config.model_name)]),
+                    progress: 0.2,
+                    summary_id: None,
+                    completed: false,
+                    error: None,
+                });
+            }
+            Ok(false) => {
+                let error_msg = tr("summarization.connection_failed", &[("url", &config.base_url)]);
+                report(&window, &status_registry, &transcription_id, SummarizationProgress {
+                    stage: "error".to_string(),
+                    message: error_msg.clone(),
+                    progress: 0.0,
+                    summary_id: None,
+                    completed: false,
+                    error: Some(error_msg.clone()),
+                });
+                return Err(error_msg);
+            }
+            Err(e) => {
+                let error_msg = tr("summarization.connection_check_failed", &[("error", &e.localized())]);
+                report(&window, &status_registry, &transcription_id, SummarizationProgress {
+                    stage: "error".to_string(),
+                    message: error_msg.clone(),
+                    progress: 0.0,
+                    summary_id: None,
+                    completed: false,
+                    error: Some(error_msg.clone()),
+                });
+                return Err(error_msg);
+            }
         }
     }
     
     // Emit processing start
-    let _ = window.emit("summarization-progress", SummarizationProgress {
+    report(&window, &status_registry, &transcription_id, SummarizationProgress {
         stage: "processing".to_string(),
-        message: format!("{}で要約を生成中...", config.model_name),
+        message: tr("summarization.processing", &[("model", &route.model_label())]),
         progress: 0.3,
         summary_id: None,
         completed: false,
         error: None,
     });
     
-    // Generate summary
-    let result = llm_service
-        .summarize_text(&transcription_text, transcription_id.clone())
+    // Generate summary (会議中のメモとマーカー付近の発言も一緒に渡す)
+    let context = summarizer.context(&transcription_id, include_notes).await;
+    let result = summarizer
+        .summarize_via(&route, &transcription_id, &transcription_text, &context)
         .await;
     
     match result {
         Ok(summary) => {
+            status_registry.update(&transcription_id, |status| {
+                status.partial_text_length = summary.summary_text.chars().count();
+            });
+
             // Emit processing completion
-            let _ = window.emit("summarization-progress", SummarizationProgress {
+            report(&window, &status_registry, &transcription_id, SummarizationProgress {
                 stage: "saving".to_string(),
-                message: "要約をデータベースに保存中...".to_string(),
+                message: t("summarization.saving"),
                 progress: 0.8,
                 summary_id: Some(summary.id.clone()),
                 completed: false,
@@ -113,9 +130,9 @@ pub async fn generate_summary_with_progress(
             match database.create_summary(&summary).await {
                 Ok(_) => {
                     // Emit completion
-                    let _ = window.emit("summarization-progress", SummarizationProgress {
+                    report(&window, &status_registry, &transcription_id, SummarizationProgress {
                         stage: "completed".to_string(),
-                        message: "要約の生成が完了しました".to_string(),
+                        message: t("summarization.completed"),
                         progress: 1.0,
                         summary_id: Some(summary.id.clone()),
                         completed: true,
@@ -126,8 +143,8 @@ pub async fn generate_summary_with_progress(
                     Ok(summary)
                 }
                 Err(e) => {
-                    let error_msg = format!("データベース保存エラー: {}", e);
-                    let _ = window.emit("summarization-progress", SummarizationProgress {
+                    let error_msg = tr("summarization.save_failed", &[("error", &e.localized())]);
+                    report(&window, &status_registry, &transcription_id, SummarizationProgress {
                         stage: "error".to_string(),
                         message: error_msg.clone(),
                         progress: 0.8,
@@ -140,8 +157,8 @@ pub async fn generate_summary_with_progress(
             }
         }
         Err(e) => {
-            let error_msg = format!("要約生成エラー: {}", e);
-            let _ = window.emit("summarization-progress", SummarizationProgress {
+            let error_msg = tr("summarization.failed", &[("error", &e.localized())]);
+            report(&window, &status_registry, &transcription_id, SummarizationProgress {
                 stage: "error".to_string(),
                 message: error_msg.clone(),
                 progress: 0.3,
@@ -157,36 +174,99 @@ pub async fn generate_summary_with_progress(
 #[tauri::command]
 pub async fn cancel_summarization(
     window: Window,
+    status_registry: State<'_, StatusRegistryState>,
     summary_id: Option<String>,
+    transcription_id: Option<String>,
 ) -> Result<(), String> {
     // Note: In a full implementation, this would cancel the ongoing LLM request
     // For now, we just emit a cancellation event
     
-    let _ = window.emit("summarization-progress", SummarizationProgress {
+    let progress = SummarizationProgress {
         stage: "cancelled".to_string(),
-        message: "要約生成がキャンセルされました".to_string(),
+        message: t("summarization.cancelled"),
         progress: 0.0,
         summary_id,
         completed: false,
         error: Some("User cancelled".to_string()),
-    });
+    };
+
+    match transcription_id {
+        Some(transcription_id) => report(&window, &status_registry, &transcription_id, progress),
+        None => {
+            let _ = window.emit("summarization-progress", progress);
+        }
+    }
     
     log::info!("🛑 Summarization cancelled by user");
     Ok(())
 }
 
+/// 書き起こしIDに対する直近の要約状態（リロード後の復元用）
 #[tauri::command]
 pub async fn get_summarization_status(
-    summary_id: String,
-) -> Result<SummarizationProgress, String> {
-    // This would typically check the status of an ongoing summarization
-    // For now, return a default status
-    Ok(SummarizationProgress {
-        stage: "unknown".to_string(),
-        message: "ステータス不明".to_string(),
-        progress: 0.0,
-        summary_id: Some(summary_id),
-        completed: false,
-        error: None,
-    })
-}
\ No newline at end of file
+    status_registry: State<'_, StatusRegistryState>,
+    transcription_id: String,
+) -> Result<Option<SummarizationStatus>, String> {
+    Ok(status_registry.get(&transcription_id))
+}
+
+/// 進行中の要約一覧
+#[tauri::command]
+pub async fn get_active_summarizations(
+    status_registry: State<'_, StatusRegistryState>,
+) -> Result<Vec<SummarizationStatus>, String> {
+    Ok(status_registry.active())
+}
+
+/// 会議中の途中経過要約を開始（更新ごとに `rolling-summary` イベントを送信）
+#[tauri::command]
+pub async fn start_rolling_summary(
+    window: Window,
+    rolling_summarizer: State<'_, RollingSummaryState>,
+    summarizer: State<'_, SummarizerState>,
+    model_config: Option<LLMConfig>,
+    interval_seconds: Option<u64>,
+    token_budget: Option<usize>,
+) -> Result<(), String> {
+    let llm_service = LLMService::new(summarizer.resolve_config(model_config, "").await);
+    rolling_summarizer.start(
+        llm_service,
+        interval_seconds.unwrap_or(rolling_summary::DEFAULT_INTERVAL_SECONDS),
+        token_budget.unwrap_or(rolling_summary::DEFAULT_TOKEN_BUDGET),
+        move |summary| {
+            let _ = window.emit(rolling_summary::ROLLING_SUMMARY_EVENT, summary.clone());
+        },
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_rolling_summary(rolling_summarizer: State<'_, RollingSummaryState>) -> Result<bool, String> {
+    Ok(rolling_summarizer.stop())
+}
+
+/// 最新の途中経過要約（途中参加・リロード時の表示用）
+#[tauri::command]
+pub async fn get_rolling_summary(
+    rolling_summarizer: State<'_, RollingSummaryState>,
+) -> Result<Option<RollingSummary>, String> {
+    Ok(rolling_summarizer.current())
+}
+
+/// 進捗イベントを送信し、レジストリにも記録
+fn report(
+    window: &Window,
+    registry: &SummarizationStatusRegistry,
+    transcription_id: &str,
+    progress: SummarizationProgress,
+) {
+    registry.update(transcription_id, |status| {
+        status.stage = progress.stage.clone();
+        status.message = progress.message.clone();
+        status.progress = progress.progress;
+        status.summary_id = progress.summary_id.clone().or(status.summary_id.take());
+        status.completed = progress.completed;
+        status.error = progress.error.clone();
+    });
+    let _ = window.emit("summarization-progress", progress);
+}
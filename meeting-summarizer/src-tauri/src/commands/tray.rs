@@ -0,0 +1,111 @@
+use crate::services::tray::{TrayAction, TrayState, TRAY_ID, TRAY_STATE_EVENT};
+use crate::services::RecordingService;
+use std::sync::Arc;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Emitter, Manager, State, Wry};
+
+/// 状態に合わせて有効・無効を切り替えるトレイのメニュー項目
+struct TrayMenuItems(Vec<(TrayAction, MenuItem<Wry>)>);
+
+/// トレイアイコンとメニューを作る（起動時に一度だけ呼ぶ）
+pub fn create_tray(app: &AppHandle, recording_service: Arc<RecordingService>) -> tauri::Result<()> {
+    let state = TrayState::default();
+    let items = TrayAction::ALL
+        .into_iter()
+        .map(|action| {
+            let item = MenuItem::with_id(app, action.id(), action.label(), state.is_enabled(action), None::<&str>)?;
+            Ok((action, item))
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+
+    let menu = Menu::new(app)?;
+    for (action, item) in &items {
+        // 録音操作とウィンドウ操作の間に区切りを入れる
+        if *action == TrayAction::ShowWindow {
+            menu.append(&PredefinedMenuItem::separator(app)?)?;
+        }
+        menu.append(item)?;
+    }
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .tooltip(state.tooltip())
+        .on_menu_event(move |app, event| {
+            if let Some(action) = TrayAction::from_id(event.id().as_ref()) {
+                handle_action(app.clone(), recording_service.clone(), action);
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    app.manage(TrayMenuItems(items));
+    log::info!("🧭 System tray created");
+    Ok(())
+}
+
+fn handle_action(app: AppHandle, recording_service: Arc<RecordingService>, action: TrayAction) {
+    match action {
+        TrayAction::ShowWindow => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+        TrayAction::Quit => app.exit(0),
+        _ => {
+            tauri::async_runtime::spawn(async move {
+                let result = match action {
+                    TrayAction::Start => recording_service.start_recording().await.map(|_| ()),
+                    TrayAction::Pause => recording_service.pause_recording().await,
+                    TrayAction::Resume => recording_service.resume_recording().await,
+                    TrayAction::Stop => recording_service.stop_recording().await.map(|_| ()),
+                    TrayAction::ShowWindow | TrayAction::Quit => Ok(()),
+                };
+                match result {
+                    Ok(()) => log::info!("🧭 Tray action executed: {:?}", action),
+                    Err(e) => log::warn!("⚠️ Tray action {:?} failed: {}", action, e),
+                }
+                refresh_tray(&app, &recording_service).await;
+            });
+        }
+    }
+}
+
+/// トレイの表示を録音の状態に合わせ、画面にも `tray-state` で通知する
+pub async fn refresh_tray(app: &AppHandle, recording_service: &RecordingService) -> TrayState {
+    let state = TrayState::from_progress(recording_service.current_progress().await.as_ref());
+    apply_tray_state(app, &state);
+    state
+}
+
+pub fn apply_tray_state(app: &AppHandle, state: &TrayState) {
+    if let Some(items) = app.try_state::<TrayMenuItems>() {
+        for (action, item) in &items.0 {
+            let _ = item.set_enabled(state.is_enabled(*action));
+        }
+    }
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_title(state.title());
+        let _ = tray.set_tooltip(Some(state.tooltip()));
+    }
+    if let Err(e) = app.emit(TRAY_STATE_EVENT, state) {
+        log::warn!("⚠️ Failed to emit tray state: {}", e);
+    }
+}
+
+#[tauri::command]
+pub async fn get_tray_state(recording_service: State<'_, Arc<RecordingService>>) -> Result<TrayState, String> {
+    Ok(TrayState::from_progress(recording_service.current_progress().await.as_ref()))
+}
+
+/// トレイの表示を現在の録音の状態に合わせ直す
+#[tauri::command]
+pub async fn update_tray_state(
+    app_handle: AppHandle,
+    recording_service: State<'_, Arc<RecordingService>>,
+) -> Result<TrayState, String> {
+    Ok(refresh_tray(&app_handle, &recording_service).await)
+}
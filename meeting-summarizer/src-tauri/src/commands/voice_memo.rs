@@ -0,0 +1,54 @@
+use crate::database::Database;
+use crate::services::voice_memo::{VoiceMemo, VoiceMemoRecorder};
+use crate::services::{AppSettingsManager, RecordingService, VoiceMemoSettings, WhisperService};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type DbState = Arc<Database>;
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+/// ボイスメモを録音する（話し終えて無音が続くか stop_voice_memo で止まり、書き起こした結果を返す）
+#[tauri::command]
+pub async fn capture_voice_memo(
+    db: State<'_, DbState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+    settings_manager: State<'_, AppSettingsState>,
+    recorder: State<'_, Arc<VoiceMemoRecorder>>,
+) -> Result<VoiceMemo, String> {
+    let settings = settings_manager.lock().await.get_settings().voice_memo.clone();
+    recorder
+        .capture(db.inner(), recording_service.inner(), whisper_service.inner(), &settings)
+        .await
+        .map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn stop_voice_memo(recorder: State<'_, Arc<VoiceMemoRecorder>>) -> Result<bool, String> {
+    Ok(recorder.request_stop())
+}
+
+#[tauri::command]
+pub async fn is_voice_memo_active(recorder: State<'_, Arc<VoiceMemoRecorder>>) -> Result<bool, String> {
+    Ok(recorder.is_active())
+}
+
+#[tauri::command]
+pub async fn get_voice_memo_settings(settings_manager: State<'_, AppSettingsState>) -> Result<VoiceMemoSettings, String> {
+    Ok(settings_manager.lock().await.get_settings().voice_memo.clone())
+}
+
+#[tauri::command]
+pub async fn set_voice_memo_settings(
+    settings_manager: State<'_, AppSettingsState>,
+    settings: VoiceMemoSettings,
+) -> Result<(), String> {
+    settings.validate().map_err(String::from)?;
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|app_settings| {
+        app_settings.voice_memo = settings;
+    });
+    manager.save_settings().await.map_err(String::from)
+}
@@ -0,0 +1,88 @@
+use crate::database::Database;
+use crate::services::whisper_acceleration::{self, AccelerationBenchmark, AccelerationInfo, AccelerationStatus};
+use crate::services::{AppSettingsManager, WhisperService};
+use std::path::Path;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type DbState = Arc<Database>;
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+/// GPU の利用状況と、モデルごとの計測済みの速度比
+#[tauri::command]
+pub async fn get_whisper_acceleration(
+    settings_manager: State<'_, AppSettingsState>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+) -> Result<AccelerationInfo, String> {
+    let manager = settings_manager.lock().await;
+    Ok(whisper_acceleration::acceleration_info(
+        &manager.get_settings().whisper_acceleration,
+        whisper_service.device(),
+        &whisper_service.get_current_model_size(),
+    ))
+}
+
+/// 見つかった GPU と、書き起こしに使うデバイス・見込みの速度比
+#[tauri::command]
+pub async fn get_acceleration_status(
+    settings_manager: State<'_, AppSettingsState>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+) -> Result<AccelerationStatus, String> {
+    let manager = settings_manager.lock().await;
+    Ok(whisper_acceleration::acceleration_status(
+        &manager.get_settings().whisper_acceleration,
+        whisper_service.device(),
+        whisper_service.backend().backend,
+        &whisper_service.get_current_model_size(),
+    ))
+}
+
+#[tauri::command]
+pub async fn set_whisper_acceleration_enabled(
+    settings_manager: State<'_, AppSettingsState>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+    enabled: bool,
+) -> Result<AccelerationInfo, String> {
+    log::info!("⚡ Setting Whisper GPU acceleration: {}", enabled);
+    whisper_service.set_acceleration_enabled(enabled);
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| settings.whisper_acceleration.enabled = enabled);
+    manager.save_settings().await.map_err(String::from)?;
+    Ok(whisper_acceleration::acceleration_info(
+        &manager.get_settings().whisper_acceleration,
+        whisper_service.device(),
+        &whisper_service.get_current_model_size(),
+    ))
+}
+
+/// 録音を CPU と GPU の両方で書き起こして速度比を測り、保存する（model 未指定なら現在のモデル）
+#[tauri::command]
+pub async fn benchmark_whisper_acceleration(
+    settings_manager: State<'_, AppSettingsState>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+    db: State<'_, DbState>,
+    recording_id: String,
+    model: Option<String>,
+) -> Result<AccelerationBenchmark, String> {
+    let recording = db
+        .get_recording(&recording_id)
+        .await
+        .map_err(String::from)?
+        .ok_or_else(|| format!("Recording with id {} not found", recording_id))?;
+    let model = model.unwrap_or_else(|| whisper_service.get_current_model_size());
+
+    if !whisper_service.is_initialized().await {
+        whisper_service.initialize().await.map_err(String::from)?;
+    }
+    let benchmark = whisper_service
+        .benchmark_acceleration(Path::new(&recording.file_path), recording.duration.unwrap_or(0) as f64, &model)
+        .await
+        .map_err(String::from)?;
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|settings| settings.whisper_acceleration.record_benchmark(benchmark.clone()));
+    manager.save_settings().await.map_err(String::from)?;
+    Ok(benchmark)
+}
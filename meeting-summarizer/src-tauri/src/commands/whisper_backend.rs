@@ -0,0 +1,43 @@
+use crate::services::{AppSettingsManager, WhisperBackendSettings, WhisperService};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type AppSettingsState = Arc<Mutex<AppSettingsManager>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhisperBackendStatus {
+    #[serde(flatten)]
+    pub settings: WhisperBackendSettings,
+    /// 現在のモデルの GGML ファイルがあるか
+    pub ggml_model_downloaded: bool,
+}
+
+fn status(whisper_service: &WhisperService) -> WhisperBackendStatus {
+    WhisperBackendStatus {
+        settings: whisper_service.backend(),
+        ggml_model_downloaded: whisper_service.ggml_model_path(&whisper_service.get_current_model_size()).exists(),
+    }
+}
+
+#[tauri::command]
+pub async fn get_whisper_backend(whisper_service: State<'_, Arc<WhisperService>>) -> Result<WhisperBackendStatus, String> {
+    Ok(status(&whisper_service))
+}
+
+/// Python の whisper と whisper.cpp を切り替える（次の書き起こしの前に初期化し直す）
+#[tauri::command]
+pub async fn set_whisper_backend(
+    settings_manager: State<'_, AppSettingsState>,
+    whisper_service: State<'_, Arc<WhisperService>>,
+    settings: WhisperBackendSettings,
+) -> Result<WhisperBackendStatus, String> {
+    log::info!("🔀 Setting Whisper backend: {:?}", settings.backend);
+    whisper_service.set_backend(settings.clone()).await;
+
+    let mut manager = settings_manager.lock().await;
+    manager.update_settings(|app_settings| app_settings.whisper_backend = settings.clone());
+    manager.save_settings().await.map_err(String::from)?;
+    Ok(status(&whisper_service))
+}
@@ -0,0 +1,52 @@
+use crate::services::{RecordingService, Workspace, WorkspaceManager};
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+use tokio::sync::Mutex;
+
+type WorkspaceState = Arc<Mutex<WorkspaceManager>>;
+
+/// 切り替え後、応答を返してから再起動するまでの待ち時間
+const RESTART_DELAY_MS: u64 = 300;
+
+#[tauri::command]
+pub async fn list_workspaces(workspace_manager: State<'_, WorkspaceState>) -> Result<Vec<Workspace>, String> {
+    Ok(workspace_manager.lock().await.list())
+}
+
+#[tauri::command]
+pub async fn get_active_workspace(workspace_manager: State<'_, WorkspaceState>) -> Result<Workspace, String> {
+    Ok(workspace_manager.lock().await.active().clone())
+}
+
+#[tauri::command]
+pub async fn create_workspace(
+    workspace_manager: State<'_, WorkspaceState>,
+    name: String,
+) -> Result<Workspace, String> {
+    workspace_manager.lock().await.create(&name).map_err(String::from)
+}
+
+/// ワークスペースを切り替えてアプリを再起動する（各サービスを新しい保存先で初期化し直すため）
+#[tauri::command]
+pub async fn switch_workspace(
+    app_handle: AppHandle,
+    workspace_manager: State<'_, WorkspaceState>,
+    recording_service: State<'_, Arc<RecordingService>>,
+    workspace_id: String,
+) -> Result<Workspace, String> {
+    if recording_service.is_recording() {
+        return Err("Cannot switch workspaces while recording".to_string());
+    }
+
+    let mut manager = workspace_manager.lock().await;
+    if manager.active().id == workspace_id {
+        return Ok(manager.active().clone());
+    }
+    let workspace = manager.switch(&workspace_id).map_err(String::from)?;
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(RESTART_DELAY_MS)).await;
+        app_handle.restart();
+    });
+    Ok(workspace)
+}
@@ -0,0 +1,90 @@
+//! 集計クエリ（統計・カテゴリ・タグ一覧）のメモリキャッシュ
+//!
+//! 録音の書き込み時に `invalidate` で破棄する。統計の「直近7日」件数は時間で変わり、
+//! CLI など別のプロセスからの書き込みはこのキャッシュを破棄しないため、すべて TTL でも失効させる。
+
+use crate::models::RecordingStats;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// キャッシュの有効期間
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+struct Entry<T> {
+    value: T,
+    cached_at: Instant,
+}
+
+#[derive(Default)]
+pub(crate) struct QueryCache {
+    /// 書き込みごとに進める世代番号。読み込み中に書き込みがあった結果は保存しない
+    generation: AtomicU64,
+    stats: RwLock<Option<Entry<RecordingStats>>>,
+    categories: RwLock<Option<Entry<Vec<String>>>>,
+    tags: RwLock<Option<Entry<Vec<String>>>>,
+}
+
+impl QueryCache {
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        Self::clear(&self.stats);
+        Self::clear(&self.categories);
+        Self::clear(&self.tags);
+    }
+
+    pub(crate) fn stats(&self) -> Option<RecordingStats> {
+        Self::get(&self.stats)
+    }
+
+    pub(crate) fn store_stats(&self, generation: u64, value: &RecordingStats) {
+        self.store(&self.stats, generation, value);
+    }
+
+    pub(crate) fn categories(&self) -> Option<Vec<String>> {
+        Self::get(&self.categories)
+    }
+
+    pub(crate) fn store_categories(&self, generation: u64, value: &[String]) {
+        self.store(&self.categories, generation, &value.to_vec());
+    }
+
+    pub(crate) fn tags(&self) -> Option<Vec<String>> {
+        Self::get(&self.tags)
+    }
+
+    pub(crate) fn store_tags(&self, generation: u64, value: &[String]) {
+        self.store(&self.tags, generation, &value.to_vec());
+    }
+
+    fn get<T: Clone>(slot: &RwLock<Option<Entry<T>>>) -> Option<T> {
+        let guard = slot.read().ok()?;
+        let entry = guard.as_ref()?;
+        if entry.cached_at.elapsed() > CACHE_TTL {
+            return None;
+        }
+        Some(entry.value.clone())
+    }
+
+    fn store<T: Clone>(&self, slot: &RwLock<Option<Entry<T>>>, generation: u64, value: &T) {
+        if let Ok(mut guard) = slot.write() {
+            // ロック取得後に再確認し、invalidate と競合した古い結果を書き戻さない
+            if self.generation() == generation {
+                *guard = Some(Entry {
+                    value: value.clone(),
+                    cached_at: Instant::now(),
+                });
+            }
+        }
+    }
+
+    fn clear<T>(slot: &RwLock<Option<Entry<T>>>) {
+        if let Ok(mut guard) = slot.write() {
+            *guard = None;
+        }
+    }
+}
@@ -1,20 +1,98 @@
-use crate::errors::AppResult;
-use crate::models::{Recording, Transcription, TranscriptionStatus, RecordingQuery, RecordingStats, CategoryStats, SortBy, SortOrder, Summary, SummaryStatus};
+use crate::errors::{AppError, AppResult};
+use crate::models::{Recording, Transcription, TranscriptionStatus, RecordingQuery, RecordingStats, CategoryStats, SortBy, SortOrder, QueryCondition, RecordingListItem, TranscriptionStatusFilter, Summary, SummaryStatus, Participant, Attachment, TranscriptSegment, Job, JobStatus, Chapter, ChapterSource, ActionItemRef, ActionItemStatus, PiiKind, RedactionEntry, WatchWord, KeywordAlert, CorrectionEntry, TranscriptEdit, TranscriptEditSource, SpeakerTalkStats, PersonTalkStats, SpeechMetrics, CategoryLanguage, ReviewStatus, MeetingNote, RecordingMarker, RecordingSegment, RecordingTrack, TaxonomyAssignment, ColorLabel, TranscriptionThroughput};
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection, Row};
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{params, Connection, OptionalExtension, Row};
 use std::path::Path;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::broadcast;
 
+mod cache;
+use cache::QueryCache;
+
+/// ファイルDBのプール上限（読み取りはWALで並行実行される）
+const MAX_POOL_SIZE: u32 = 8;
+
+/// 書き起こし作成通知のバッファ（受信側が遅れた分は読み飛ばされる）
+const TRANSCRIPTION_EVENT_CAPACITY: usize = 64;
+
+/// SQLiteコネクションプール。呼び出しごとに接続を借りるため、
+/// 検索・書き込み・統計クエリが互いをブロックしない。
+#[derive(Clone)]
 pub struct Database {
-    conn: Arc<Mutex<Connection>>,
+    pool: Pool<SqliteConnectionManager>,
+    /// 統計・カテゴリ・タグ一覧のキャッシュ（録音の書き込みで破棄）
+    cache: Arc<QueryCache>,
+    /// 書き起こしの作成通知（キーワード監視などの後処理用）
+    transcription_events: broadcast::Sender<Transcription>,
 }
 
 impl Database {
     pub fn new<P: AsRef<Path>>(db_path: P) -> AppResult<Self> {
-        let conn = Connection::open(db_path)?;
-        
+        let manager = SqliteConnectionManager::file(db_path).with_init(|conn| {
+            conn.execute_batch(
+                "PRAGMA journal_mode = WAL;
+                 PRAGMA synchronous = NORMAL;
+                 PRAGMA foreign_keys = ON;",
+            )?;
+            conn.busy_timeout(Duration::from_secs(5))
+        });
+
+        let pool = Pool::builder().max_size(MAX_POOL_SIZE).build(manager)?;
+
         // 同期的にテーブル初期化
+        Self::initialize_schema(&*pool.get()?)?;
+
+        Ok(Self {
+            pool,
+            cache: Arc::new(QueryCache::default()),
+            transcription_events: broadcast::channel(TRANSCRIPTION_EVENT_CAPACITY).0,
+        })
+    }
+
+    pub fn in_memory() -> AppResult<Self> {
+        // インメモリDBは接続ごとに別DBになるため、接続を1本に固定する
+        let manager = SqliteConnectionManager::memory()
+            .with_init(|conn| conn.execute_batch("PRAGMA foreign_keys = ON;"));
+        let pool = Pool::builder().max_size(1).build(manager)?;
+
+        Self::initialize_schema(&*pool.get()?)?;
+
+        Ok(Self {
+            pool,
+            cache: Arc::new(QueryCache::default()),
+            transcription_events: broadcast::channel(TRANSCRIPTION_EVENT_CAPACITY).0,
+        })
+    }
+
+    /// 作成された書き起こしを受け取る（どの経路で作成されたかに関わらず通知される）
+    pub fn subscribe_transcriptions(&self) -> broadcast::Receiver<Transcription> {
+        self.transcription_events.subscribe()
+    }
+
+    /// プールから接続を取得
+    fn conn(&self) -> AppResult<PooledConnection<SqliteConnectionManager>> {
+        Ok(self.pool.get()?)
+    }
+
+    /// 列が無ければ追加する（既存のテーブルへの列追加用）
+    fn ensure_column(conn: &Connection, table: &str, column: &str, definition: &str) -> AppResult<()> {
+        let exists = conn
+            .prepare(&format!("PRAGMA table_info({})", table))?
+            .query_map([], |row| row.get::<_, String>("name"))?
+            .collect::<Result<Vec<_>, _>>()?
+            .iter()
+            .any(|name| name == column);
+
+        if !exists {
+            conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+        }
+        Ok(())
+    }
+
+    fn initialize_schema(conn: &Connection) -> AppResult<()> {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS recordings (
                 id TEXT PRIMARY KEY,
@@ -28,6 +106,8 @@ impl Database {
                 file_size INTEGER,
                 sample_rate INTEGER,
                 channels INTEGER,
+                color_label TEXT,
+                archived INTEGER NOT NULL DEFAULT 0,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             )",
@@ -42,6 +122,7 @@ impl Database {
                 language TEXT NOT NULL,
                 confidence REAL,
                 processing_time_ms INTEGER,
+                model_used TEXT,
                 status TEXT NOT NULL,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
@@ -62,12 +143,62 @@ impl Database {
             [],
         )?;
 
+        // カテゴリ絞り込み + 日付順の検索用（単独のcategoryインデックスを兼ねる）
+        conn.execute("DROP INDEX IF EXISTS idx_recordings_category", [])?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_recordings_category_created_at 
+             ON recordings(category, created_at DESC)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_recordings_duration 
+             ON recordings(duration)",
+            [],
+        )?;
+
+        // 色ラベル追加前に作成されたデータベース向け
+        Self::ensure_column(conn, "recordings", "color_label", "TEXT")?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_recordings_color_label
+             ON recordings(color_label)",
+            [],
+        )?;
+
+        // アーカイブ機能追加前に作成されたデータベース向け
+        // （値の偏りが大きく検索の絞り込みに向かないため、インデックスは作らない）
+        Self::ensure_column(conn, "recordings", "archived", "INTEGER NOT NULL DEFAULT 0")?;
+
+        // Normalized tags (recordings.tags のJSON列はそのまま残し、検索はこちらを使う)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS recording_tags (
+                recording_id TEXT NOT NULL,
+                tag TEXT NOT NULL,
+                PRIMARY KEY (recording_id, tag),
+                FOREIGN KEY (recording_id) REFERENCES recordings (id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_recordings_category 
-             ON recordings(category)",
+            "CREATE INDEX IF NOT EXISTS idx_recording_tags_tag 
+             ON recording_tags(tag, recording_id)",
             [],
         )?;
 
+        // 既存データの移行: recording_tags が空ならJSON列から展開
+        let tag_rows: i64 = conn.query_row("SELECT COUNT(*) FROM recording_tags", [], |row| row.get(0))?;
+        if tag_rows == 0 {
+            conn.execute(
+                "INSERT OR IGNORE INTO recording_tags (recording_id, tag)
+                 SELECT r.id, j.value FROM recordings r, json_each(r.tags) j
+                 WHERE r.tags IS NOT NULL AND json_valid(r.tags) AND json_type(r.tags) = 'array'",
+                [],
+            )?;
+        }
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_transcriptions_recording_id 
              ON transcriptions(recording_id)",
@@ -80,6 +211,9 @@ impl Database {
             [],
         )?;
 
+        // 使用モデルの記録追加前に作成されたデータベース向け
+        Self::ensure_column(conn, "transcriptions", "model_used", "TEXT")?;
+
         // Summaries table for LLM-generated summaries
         conn.execute(
             "CREATE TABLE IF NOT EXISTS summaries (
@@ -91,48 +225,83 @@ impl Database {
                 model_used TEXT NOT NULL,
                 processing_time_ms INTEGER,
                 status TEXT NOT NULL,
+                review_status TEXT NOT NULL DEFAULT 'draft',
+                approved_by TEXT,
+                approved_at TEXT,
+                locked INTEGER NOT NULL DEFAULT 0,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             )",
             [],
         )?;
 
+        // 承認ワークフロー追加前に作成されたデータベース向け
+        Self::ensure_column(conn, "summaries", "review_status", "TEXT NOT NULL DEFAULT 'draft'")?;
+        Self::ensure_column(conn, "summaries", "approved_by", "TEXT")?;
+        Self::ensure_column(conn, "summaries", "approved_at", "TEXT")?;
+        Self::ensure_column(conn, "summaries", "locked", "INTEGER NOT NULL DEFAULT 0")?;
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_summaries_transcription_id 
              ON summaries(transcription_id)",
             [],
         )?;
 
+        // アクションアイテムの対応状況（本文は summaries.action_items に保持）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS action_item_states (
+                summary_id TEXT NOT NULL,
+                item_index INTEGER NOT NULL,
+                item_text TEXT NOT NULL,
+                status TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (summary_id, item_index),
+                FOREIGN KEY (summary_id) REFERENCES summaries (id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_summaries_status 
              ON summaries(status)",
             [],
         )?;
 
-        let db = Self { 
-            conn: Arc::new(Mutex::new(conn)) 
-        };
-        
-        Ok(db)
-    }
+        // Timestamped transcript segments
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS transcript_segments (
+                id TEXT PRIMARY KEY,
+                transcription_id TEXT NOT NULL,
+                segment_index INTEGER NOT NULL,
+                start_ms INTEGER NOT NULL,
+                end_ms INTEGER NOT NULL,
+                speaker TEXT,
+                text TEXT NOT NULL,
+                confidence REAL,
+                FOREIGN KEY (transcription_id) REFERENCES transcriptions (id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
 
-    pub fn in_memory() -> AppResult<Self> {
-        let conn = Connection::open_in_memory()?;
-        
-        // 同期的にテーブル初期化 - 上記と同じ構造
         conn.execute(
-            "CREATE TABLE IF NOT EXISTS recordings (
+            "CREATE INDEX IF NOT EXISTS idx_transcript_segments_transcription_id 
+             ON transcript_segments(transcription_id, segment_index)",
+            [],
+        )?;
+
+        // Persistent background job queue
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS jobs (
                 id TEXT PRIMARY KEY,
-                filename TEXT NOT NULL,
-                file_path TEXT NOT NULL UNIQUE,
-                title TEXT,
-                description TEXT,
-                category TEXT,
-                tags TEXT, -- JSON array as string
-                duration INTEGER,
-                file_size INTEGER,
-                sample_rate INTEGER,
-                channels INTEGER,
+                kind TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending',
+                priority INTEGER NOT NULL DEFAULT 0,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                max_attempts INTEGER NOT NULL DEFAULT 3,
+                last_error TEXT,
+                result TEXT,
+                run_after TEXT NOT NULL,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             )",
@@ -140,95 +309,302 @@ impl Database {
         )?;
 
         conn.execute(
-            "CREATE TABLE IF NOT EXISTS transcriptions (
+            "CREATE INDEX IF NOT EXISTS idx_jobs_status_priority 
+             ON jobs(status, priority DESC, created_at)",
+            [],
+        )?;
+
+        // Participants and attachments for imported meetings (Zoom/Teams)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS participants (
                 id TEXT PRIMARY KEY,
                 recording_id TEXT NOT NULL,
-                text TEXT NOT NULL,
-                language TEXT NOT NULL,
-                confidence REAL,
-                processing_time_ms INTEGER,
-                status TEXT NOT NULL,
+                name TEXT NOT NULL,
+                email TEXT,
+                join_time TEXT,
+                leave_time TEXT,
+                FOREIGN KEY (recording_id) REFERENCES recordings (id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_participants_recording_id 
+             ON participants(recording_id)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS attachments (
+                id TEXT PRIMARY KEY,
+                recording_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                filename TEXT NOT NULL,
+                file_path TEXT NOT NULL,
                 created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
                 FOREIGN KEY (recording_id) REFERENCES recordings (id) ON DELETE CASCADE
             )",
             [],
         )?;
 
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_recordings_created_at 
-             ON recordings(created_at DESC)",
+            "CREATE INDEX IF NOT EXISTS idx_attachments_recording_id 
+             ON attachments(recording_id)",
             [],
         )?;
 
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_recordings_filename 
-             ON recordings(filename)",
+            "CREATE TABLE IF NOT EXISTS chapters (
+                id TEXT PRIMARY KEY,
+                recording_id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                start_ms INTEGER NOT NULL,
+                end_ms INTEGER,
+                source TEXT NOT NULL DEFAULT 'manual',
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (recording_id) REFERENCES recordings (id) ON DELETE CASCADE
+            )",
             [],
         )?;
 
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_recordings_category 
-             ON recordings(category)",
+            "CREATE INDEX IF NOT EXISTS idx_chapters_recording_id
+             ON chapters(recording_id, start_ms)",
             [],
         )?;
 
+        // 録音ごとの無音率などの指標（再解析で置き換える）
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_transcriptions_recording_id 
-             ON transcriptions(recording_id)",
+            "CREATE TABLE IF NOT EXISTS speech_metrics (
+                recording_id TEXT PRIMARY KEY,
+                duration_ms INTEGER NOT NULL,
+                speech_ms INTEGER NOT NULL,
+                silence_ratio REAL NOT NULL,
+                average_pause_ms INTEGER NOT NULL,
+                pause_count INTEGER NOT NULL,
+                speech_density REAL NOT NULL,
+                analyzed_at TEXT NOT NULL,
+                FOREIGN KEY (recording_id) REFERENCES recordings (id) ON DELETE CASCADE
+            )",
             [],
         )?;
 
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_transcriptions_status 
-             ON transcriptions(status)",
+            "CREATE INDEX IF NOT EXISTS idx_speech_metrics_silence_ratio
+             ON speech_metrics(silence_ratio)",
             [],
         )?;
 
-        // Summaries table for LLM-generated summaries
+        // 話者別の発話量（録音ごと。再計算で置き換える）
         conn.execute(
-            "CREATE TABLE IF NOT EXISTS summaries (
+            "CREATE TABLE IF NOT EXISTS speaker_stats (
+                recording_id TEXT NOT NULL,
+                speaker TEXT NOT NULL,
+                talk_time_ms INTEGER NOT NULL,
+                turn_count INTEGER NOT NULL,
+                longest_monologue_ms INTEGER NOT NULL,
+                talk_share REAL NOT NULL,
+                computed_at TEXT NOT NULL,
+                PRIMARY KEY (recording_id, speaker),
+                FOREIGN KEY (recording_id) REFERENCES recordings (id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // 書き起こしセグメントの修正履歴
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS transcript_edits (
                 id TEXT PRIMARY KEY,
                 transcription_id TEXT NOT NULL,
-                summary_text TEXT NOT NULL,
-                key_points TEXT, -- JSON array as string
-                action_items TEXT, -- JSON array as string
-                model_used TEXT NOT NULL,
-                processing_time_ms INTEGER,
-                status TEXT NOT NULL,
+                segment_id TEXT NOT NULL,
+                previous_text TEXT NOT NULL,
+                new_text TEXT NOT NULL,
+                source TEXT NOT NULL,
+                snippet_path TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (transcription_id) REFERENCES transcriptions (id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_transcript_edits_transcription_id
+             ON transcript_edits(transcription_id, created_at)",
+            [],
+        )?;
+
+        // 会議中に入力したメモ
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS meeting_notes (
+                id TEXT PRIMARY KEY,
+                recording_id TEXT NOT NULL,
+                text TEXT NOT NULL,
+                offset_ms INTEGER,
+                important INTEGER NOT NULL DEFAULT 0,
+                author TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (recording_id) REFERENCES recordings (id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // 入力者の記録を追加する前に作成されたデータベース向け
+        Self::ensure_column(conn, "meeting_notes", "author", "TEXT")?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_meeting_notes_recording_id
+             ON meeting_notes(recording_id, offset_ms)",
+            [],
+        )?;
+
+        // 録音中に付けたマーカー
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS markers (
+                id TEXT PRIMARY KEY,
+                recording_id TEXT NOT NULL,
+                offset_ms INTEGER NOT NULL,
+                label TEXT,
                 created_at TEXT NOT NULL,
+                FOREIGN KEY (recording_id) REFERENCES recordings (id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_markers_recording_id
+             ON markers(recording_id, offset_ms)",
+            [],
+        )?;
+
+        // 長い録音を分けたファイル
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS recording_segments (
+                id TEXT PRIMARY KEY,
+                recording_id TEXT NOT NULL,
+                segment_index INTEGER NOT NULL,
+                file_path TEXT NOT NULL,
+                offset_ms INTEGER NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                file_size INTEGER NOT NULL DEFAULT 0,
+                FOREIGN KEY (recording_id) REFERENCES recordings (id) ON DELETE CASCADE,
+                UNIQUE (recording_id, segment_index)
+            )",
+            [],
+        )?;
+
+        // 同じ録音で別の入力デバイスから録音したトラック
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS recording_tracks (
+                id TEXT PRIMARY KEY,
+                recording_id TEXT NOT NULL,
+                track_index INTEGER NOT NULL,
+                device_name TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                file_size INTEGER NOT NULL DEFAULT 0,
+                FOREIGN KEY (recording_id) REFERENCES recordings (id) ON DELETE CASCADE,
+                UNIQUE (recording_id, track_index)
+            )",
+            [],
+        )?;
+
+        // 監視フォルダから取り込み済みのファイル（録音を削除しても再取り込みしない）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS watched_imports (
+                source_path TEXT PRIMARY KEY,
+                folder_id TEXT NOT NULL,
+                recording_id TEXT NOT NULL,
+                imported_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // 誤認識の補正辞書
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS correction_dictionary (
+                id TEXT PRIMARY KEY,
+                wrong TEXT NOT NULL UNIQUE,
+                correct TEXT NOT NULL,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                hit_count INTEGER NOT NULL DEFAULT 0,
+                last_hit_at TEXT,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // カテゴリごとの既定の書き起こし言語
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS category_languages (
+                category TEXT PRIMARY KEY COLLATE NOCASE,
+                language TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             )",
             [],
         )?;
 
+        // キーワード監視
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_summaries_transcription_id 
-             ON summaries(transcription_id)",
+            "CREATE TABLE IF NOT EXISTS watch_words (
+                id TEXT PRIMARY KEY,
+                word TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                tag TEXT,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                created_at TEXT NOT NULL
+            )",
             [],
         )?;
 
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_summaries_status 
-             ON summaries(status)",
+            "CREATE TABLE IF NOT EXISTS keyword_alerts (
+                id TEXT PRIMARY KEY,
+                watch_word_id TEXT NOT NULL,
+                word TEXT NOT NULL,
+                recording_id TEXT NOT NULL,
+                transcription_id TEXT NOT NULL,
+                occurrences INTEGER NOT NULL,
+                snippet TEXT NOT NULL,
+                acknowledged INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                UNIQUE (watch_word_id, transcription_id),
+                FOREIGN KEY (recording_id) REFERENCES recordings (id) ON DELETE CASCADE
+            )",
             [],
         )?;
 
-        let db = Self { 
-            conn: Arc::new(Mutex::new(conn)) 
-        };
-        
-        Ok(db)
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_keyword_alerts_created_at
+             ON keyword_alerts(acknowledged, created_at)",
+            [],
+        )?;
+
+        // 匿名化エクスポートのプレースホルダー対応表（元の書き起こし・要約は変更しない）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS redaction_mappings (
+                recording_id TEXT NOT NULL,
+                placeholder TEXT NOT NULL,
+                original TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (recording_id, placeholder),
+                UNIQUE (recording_id, original),
+                FOREIGN KEY (recording_id) REFERENCES recordings (id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        Ok(())
     }
 
     // Recording CRUD operations with Phase 2 enhancements
     pub async fn create_recording(&self, recording: &Recording) -> AppResult<()> {
-        let conn = self.conn.lock().await;
+        let mut conn = self.conn()?;
         let tags_json = serde_json::to_string(&recording.tags).unwrap_or_else(|_| "[]".to_string());
+        let tx = conn.transaction()?;
         
-        conn.execute(
-            "INSERT INTO recordings (id, filename, file_path, title, description, category, tags, duration, file_size, sample_rate, channels, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        tx.execute(
+            "INSERT INTO recordings (id, filename, file_path, title, description, category, tags, duration, file_size, sample_rate, channels, created_at, updated_at, color_label, archived)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
             params![
                 recording.id,
                 recording.filename,
@@ -243,15 +619,30 @@ impl Database {
                 recording.channels,
                 recording.created_at.to_rfc3339(),
                 recording.updated_at.to_rfc3339(),
+                recording.color_label.map(|label| label.as_str()),
+                recording.archived,
             ],
         )?;
+        Self::replace_recording_tags(&tx, &recording.id, &recording.tags)?;
+        tx.commit()?;
+        self.cache.invalidate();
+        Ok(())
+    }
+
+    /// recording_tags を指定タグで置き換える
+    fn replace_recording_tags(conn: &Connection, recording_id: &str, tags: &[String]) -> AppResult<()> {
+        conn.execute("DELETE FROM recording_tags WHERE recording_id = ?1", params![recording_id])?;
+        let mut stmt = conn.prepare("INSERT OR IGNORE INTO recording_tags (recording_id, tag) VALUES (?1, ?2)")?;
+        for tag in tags {
+            stmt.execute(params![recording_id, tag])?;
+        }
         Ok(())
     }
 
     pub async fn get_recording(&self, id: &str) -> AppResult<Option<Recording>> {
-        let conn = self.conn.lock().await;
+        let conn = self.conn()?;
         let mut stmt = conn.prepare(
-            "SELECT id, filename, file_path, title, description, category, tags, duration, file_size, sample_rate, channels, created_at, updated_at 
+            "SELECT id, filename, file_path, title, description, category, tags, duration, file_size, sample_rate, channels, color_label, archived, created_at, updated_at 
              FROM recordings WHERE id = ?1"
         )?;
 
@@ -263,10 +654,19 @@ impl Database {
         }
     }
 
+    /// 既定の一覧用（include_archived が false ならアーカイブ済みを除く）
+    pub async fn get_recordings(&self, include_archived: bool) -> AppResult<Vec<Recording>> {
+        let mut recordings = self.get_all_recordings().await?;
+        if !include_archived {
+            recordings.retain(|recording| !recording.archived);
+        }
+        Ok(recordings)
+    }
+
     pub async fn get_all_recordings(&self) -> AppResult<Vec<Recording>> {
-        let conn = self.conn.lock().await;
+        let conn = self.conn()?;
         let mut stmt = conn.prepare(
-            "SELECT id, filename, file_path, title, description, category, tags, duration, file_size, sample_rate, channels, created_at, updated_at 
+            "SELECT id, filename, file_path, title, description, category, tags, duration, file_size, sample_rate, channels, color_label, archived, created_at, updated_at 
              FROM recordings ORDER BY created_at DESC"
         )?;
 
@@ -279,12 +679,13 @@ impl Database {
     pub async fn update_recording(&self, recording: &Recording) -> AppResult<()> {
         let updated_at = Utc::now().to_rfc3339();
         let tags_json = serde_json::to_string(&recording.tags).unwrap_or_else(|_| "[]".to_string());
-        let conn = self.conn.lock().await;
+        let mut conn = self.conn()?;
+        let tx = conn.transaction()?;
         
-        conn.execute(
+        tx.execute(
             "UPDATE recordings 
              SET filename = ?2, file_path = ?3, title = ?4, description = ?5, category = ?6, tags = ?7, 
-                 duration = ?8, file_size = ?9, sample_rate = ?10, channels = ?11, updated_at = ?12
+                 duration = ?8, file_size = ?9, sample_rate = ?10, channels = ?11, updated_at = ?12, color_label = ?13, archived = ?14
              WHERE id = ?1",
             params![
                 recording.id,
@@ -299,22 +700,99 @@ impl Database {
                 recording.sample_rate,
                 recording.channels,
                 updated_at,
+                recording.color_label.map(|label| label.as_str()),
+                recording.archived,
             ],
         )?;
+        Self::replace_recording_tags(&tx, &recording.id, &recording.tags)?;
+        tx.commit()?;
+        self.cache.invalidate();
         Ok(())
     }
 
+    /// 録音にタグを追加する（既に付いているタグは無視し、タグ以外の列には触れない）
+    ///
+    /// 追加したタグがあれば true を返す。録音が無ければ何もしない。
+    pub async fn add_recording_tags(&self, recording_id: &str, tags: &[String]) -> AppResult<bool> {
+        let mut conn = self.conn()?;
+        let tx = conn.transaction()?;
+
+        let current: Option<String> = tx
+            .query_row("SELECT tags FROM recordings WHERE id = ?1", params![recording_id], |row| row.get(0))
+            .optional()?;
+        let Some(current) = current else {
+            return Ok(false);
+        };
+        let mut merged: Vec<String> = serde_json::from_str(&current).unwrap_or_default();
+        let before = merged.len();
+        for tag in tags {
+            if !merged.contains(tag) {
+                merged.push(tag.clone());
+            }
+        }
+        if merged.len() == before {
+            return Ok(false);
+        }
+
+        let tags_json = serde_json::to_string(&merged).unwrap_or_else(|_| "[]".to_string());
+        tx.execute("UPDATE recordings SET tags = ?2 WHERE id = ?1", params![recording_id, tags_json])?;
+        let mut stmt = tx.prepare("INSERT OR IGNORE INTO recording_tags (recording_id, tag) VALUES (?1, ?2)")?;
+        for tag in &merged[before..] {
+            stmt.execute(params![recording_id, tag])?;
+        }
+        drop(stmt);
+        tx.commit()?;
+        self.cache.invalidate();
+        Ok(true)
+    }
+
+    /// 録音と、録音に紐付くデータ（書き起こし・要約・区間・分割ファイル・トラックなど）をまとめて削除する
+    ///
+    /// 外部キーの `ON DELETE CASCADE` が無い古いテーブルや要約のため、子のテーブルも明示的に消す。
     pub async fn delete_recording(&self, id: &str) -> AppResult<bool> {
-        let conn = self.conn.lock().await;
-        let rows_affected = conn.execute(
-            "DELETE FROM recordings WHERE id = ?1",
+        let mut conn = self.conn()?;
+        let tx = conn.transaction()?;
+
+        let transcriptions = "SELECT id FROM transcriptions WHERE recording_id = ?1";
+        tx.execute(
+            &format!(
+                "DELETE FROM action_item_states WHERE summary_id IN (SELECT id FROM summaries WHERE transcription_id IN ({}))",
+                transcriptions
+            ),
             params![id],
         )?;
+        for table in ["summaries", "transcript_segments", "transcript_edits"] {
+            tx.execute(
+                &format!("DELETE FROM {} WHERE transcription_id IN ({})", table, transcriptions),
+                params![id],
+            )?;
+        }
+        for table in [
+            "transcriptions",
+            "participants",
+            "attachments",
+            "recording_tags",
+            "chapters",
+            "redaction_mappings",
+            "keyword_alerts",
+            "speaker_stats",
+            "speech_metrics",
+            "meeting_notes",
+            "markers",
+            "recording_segments",
+            "recording_tracks",
+        ] {
+            tx.execute(&format!("DELETE FROM {} WHERE recording_id = ?1", table), params![id])?;
+        }
+        let rows_affected = tx.execute("DELETE FROM recordings WHERE id = ?1", params![id])?;
+
+        tx.commit()?;
+        self.cache.invalidate();
         Ok(rows_affected > 0)
     }
 
     pub async fn get_recordings_count(&self) -> AppResult<i64> {
-        let conn = self.conn.lock().await;
+        let conn = self.conn()?;
         let count: i64 = conn.query_row(
             "SELECT COUNT(*) FROM recordings",
             [],
@@ -323,10 +801,47 @@ impl Database {
         Ok(count)
     }
 
-    fn row_to_recording(row: &Row) -> rusqlite::Result<Recording> {
-        let created_at_str: String = row.get("created_at")?;
-        let updated_at_str: String = row.get("updated_at")?;
-
+    fn parse_transcription_status(status: &str) -> TranscriptionStatus {
+        if let Some(reason) = status.strip_prefix("failed:") {
+            return TranscriptionStatus::Failed(reason.to_string());
+        }
+        match status {
+            "pending" => TranscriptionStatus::Pending,
+            "processing" => TranscriptionStatus::Processing,
+            "completed" => TranscriptionStatus::Completed,
+            _ => TranscriptionStatus::Failed("Unknown status".to_string()),
+        }
+    }
+
+    fn parse_summary_status(status: &str) -> SummaryStatus {
+        if let Some(reason) = status.strip_prefix("failed:") {
+            return SummaryStatus::Failed(reason.to_string());
+        }
+        match status {
+            "pending" => SummaryStatus::Pending,
+            "processing" => SummaryStatus::Processing,
+            "completed" => SummaryStatus::Completed,
+            _ => SummaryStatus::Failed("Unknown status".to_string()),
+        }
+    }
+
+    /// 一覧用の行（録音の列に最新の書き起こし・要約の状態を加えたもの）
+    fn row_to_recording_list_item(row: &Row) -> rusqlite::Result<RecordingListItem> {
+        let latest_transcription_status: Option<String> = row.get("latest_transcription_status")?;
+        let latest_summary_status: Option<String> = row.get("latest_summary_status")?;
+        Ok(RecordingListItem {
+            recording: Self::row_to_recording(row)?,
+            has_transcription: latest_transcription_status.is_some(),
+            has_summary: latest_summary_status.is_some(),
+            latest_transcription_status: latest_transcription_status.as_deref().map(Self::parse_transcription_status),
+            latest_summary_status: latest_summary_status.as_deref().map(Self::parse_summary_status),
+        })
+    }
+
+    fn row_to_recording(row: &Row) -> rusqlite::Result<Recording> {
+        let created_at_str: String = row.get("created_at")?;
+        let updated_at_str: String = row.get("updated_at")?;
+
         let created_at = DateTime::parse_from_rfc3339(&created_at_str)
             .map_err(|_e| rusqlite::Error::InvalidColumnType(0, "created_at".to_string(), rusqlite::types::Type::Text))?
             .with_timezone(&Utc);
@@ -338,6 +853,11 @@ impl Database {
         let tags_json: String = row.get("tags").unwrap_or_else(|_| "[]".to_string());
         let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_else(|_| Vec::new());
 
+        // 未知の値は無視する
+        let color_label = row
+            .get::<_, Option<String>>("color_label")?
+            .and_then(|label| label.parse::<ColorLabel>().ok());
+
         Ok(Recording {
             id: row.get("id")?,
             filename: row.get("filename")?,
@@ -350,14 +870,17 @@ impl Database {
             file_size: row.get("file_size")?,
             sample_rate: row.get("sample_rate")?,
             channels: row.get("channels")?,
+            color_label,
+            archived: row.get("archived")?,
             created_at,
             updated_at,
         })
     }
 
     // Transcription CRUD operations
+    /// 書き起こしを保存する（Whisper が返した区間も一緒に保存する）
     pub async fn create_transcription(&self, transcription: &Transcription) -> AppResult<()> {
-        let conn = self.conn.lock().await;
+        let mut conn = self.conn()?;
         let status_str = match &transcription.status {
             TranscriptionStatus::Pending => "pending",
             TranscriptionStatus::Processing => "processing", 
@@ -365,9 +888,10 @@ impl Database {
             TranscriptionStatus::Failed(err) => &format!("failed:{}", err),
         };
 
-        conn.execute(
-            "INSERT INTO transcriptions (id, recording_id, text, language, confidence, processing_time_ms, status, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO transcriptions (id, recording_id, text, language, confidence, processing_time_ms, status, created_at, updated_at, model_used)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             params![
                 transcription.id,
                 transcription.recording_id,
@@ -378,15 +902,37 @@ impl Database {
                 status_str,
                 transcription.created_at.to_rfc3339(),
                 transcription.updated_at.to_rfc3339(),
+                transcription.model_used,
             ],
         )?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO transcript_segments (id, transcription_id, segment_index, start_ms, end_ms, speaker, text, confidence)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+            )?;
+            for (index, segment) in transcription.segments.iter().enumerate() {
+                stmt.execute(params![
+                    segment.id,
+                    transcription.id,
+                    index as i32,
+                    segment.start_ms,
+                    segment.end_ms,
+                    segment.speaker,
+                    segment.text,
+                    segment.confidence,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        // 購読者がいない場合の送信エラーは無視する
+        let _ = self.transcription_events.send(transcription.clone());
         Ok(())
     }
 
     pub async fn get_transcription(&self, id: &str) -> AppResult<Option<Transcription>> {
-        let conn = self.conn.lock().await;
+        let conn = self.conn()?;
         let mut stmt = conn.prepare(
-            "SELECT id, recording_id, text, language, confidence, processing_time_ms, status, created_at, updated_at 
+            "SELECT id, recording_id, text, language, confidence, processing_time_ms, model_used, status, created_at, updated_at 
              FROM transcriptions WHERE id = ?1"
         )?;
 
@@ -399,9 +945,9 @@ impl Database {
     }
 
     pub async fn get_transcriptions_by_recording(&self, recording_id: &str) -> AppResult<Vec<Transcription>> {
-        let conn = self.conn.lock().await;
+        let conn = self.conn()?;
         let mut stmt = conn.prepare(
-            "SELECT id, recording_id, text, language, confidence, processing_time_ms, status, created_at, updated_at 
+            "SELECT id, recording_id, text, language, confidence, processing_time_ms, model_used, status, created_at, updated_at 
              FROM transcriptions WHERE recording_id = ?1 ORDER BY created_at DESC"
         )?;
 
@@ -419,11 +965,11 @@ impl Database {
             TranscriptionStatus::Completed => "completed",
             TranscriptionStatus::Failed(err) => &format!("failed:{}", err),
         };
-        let conn = self.conn.lock().await;
+        let conn = self.conn()?;
         
         conn.execute(
             "UPDATE transcriptions 
-             SET text = ?2, language = ?3, confidence = ?4, processing_time_ms = ?5, status = ?6, updated_at = ?7
+             SET text = ?2, language = ?3, confidence = ?4, processing_time_ms = ?5, status = ?6, updated_at = ?7, model_used = ?8
              WHERE id = ?1",
             params![
                 transcription.id,
@@ -433,20 +979,154 @@ impl Database {
                 transcription.processing_time_ms,
                 status_str,
                 updated_at,
+                transcription.model_used,
             ],
         )?;
         Ok(())
     }
 
     pub async fn delete_transcription(&self, id: &str) -> AppResult<bool> {
-        let conn = self.conn.lock().await;
+        let conn = self.conn()?;
         let rows_affected = conn.execute(
             "DELETE FROM transcriptions WHERE id = ?1",
             params![id],
         )?;
+        conn.execute("DELETE FROM transcript_segments WHERE transcription_id = ?1", params![id])?;
+        conn.execute("DELETE FROM transcript_edits WHERE transcription_id = ?1", params![id])?;
         Ok(rows_affected > 0)
     }
 
+    // Transcript segment operations
+    pub async fn create_transcript_segments(&self, segments: &[TranscriptSegment]) -> AppResult<()> {
+        let mut conn = self.conn()?;
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO transcript_segments (id, transcription_id, segment_index, start_ms, end_ms, speaker, text, confidence)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+            )?;
+            for segment in segments {
+                stmt.execute(params![
+                    segment.id,
+                    segment.transcription_id,
+                    segment.segment_index,
+                    segment.start_ms,
+                    segment.end_ms,
+                    segment.speaker,
+                    segment.text,
+                    segment.confidence,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub async fn get_segments_by_transcription(&self, transcription_id: &str) -> AppResult<Vec<TranscriptSegment>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, transcription_id, segment_index, start_ms, end_ms, speaker, text, confidence 
+             FROM transcript_segments WHERE transcription_id = ?1 ORDER BY segment_index"
+        )?;
+
+        let segments = stmt.query_map(params![transcription_id], Self::row_to_segment)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(segments)
+    }
+
+    pub async fn get_segment(&self, id: &str) -> AppResult<Option<TranscriptSegment>> {
+        let conn = self.conn()?;
+        let segment = conn
+            .query_row(
+                "SELECT id, transcription_id, segment_index, start_ms, end_ms, speaker, text, confidence
+                 FROM transcript_segments WHERE id = ?1",
+                params![id],
+                Self::row_to_segment,
+            )
+            .optional()?;
+        Ok(segment)
+    }
+
+    fn row_to_segment(row: &Row) -> rusqlite::Result<TranscriptSegment> {
+        Ok(TranscriptSegment {
+            id: row.get("id")?,
+            transcription_id: row.get("transcription_id")?,
+            segment_index: row.get("segment_index")?,
+            start_ms: row.get("start_ms")?,
+            end_ms: row.get("end_ms")?,
+            speaker: row.get("speaker")?,
+            text: row.get("text")?,
+            confidence: row.get("confidence")?,
+        })
+    }
+
+    /// セグメントの修正を書き起こし本文・履歴とまとめて保存する
+    pub async fn apply_transcript_edit(
+        &self,
+        edit: &TranscriptEdit,
+        segment_confidence: Option<f32>,
+        transcription_text: &str,
+    ) -> AppResult<()> {
+        let mut conn = self.conn()?;
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "UPDATE transcript_segments SET text = ?2, confidence = ?3 WHERE id = ?1",
+            params![edit.segment_id, edit.new_text, segment_confidence],
+        )?;
+        tx.execute(
+            "UPDATE transcriptions SET text = ?2, updated_at = ?3 WHERE id = ?1",
+            params![edit.transcription_id, transcription_text, Utc::now().to_rfc3339()],
+        )?;
+        tx.execute(
+            "INSERT INTO transcript_edits (id, transcription_id, segment_id, previous_text, new_text, source, snippet_path, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                edit.id,
+                edit.transcription_id,
+                edit.segment_id,
+                edit.previous_text,
+                edit.new_text,
+                edit.source.as_str(),
+                edit.snippet_path,
+                edit.created_at.to_rfc3339(),
+            ],
+        )?;
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub async fn get_transcript_edits(&self, transcription_id: &str) -> AppResult<Vec<TranscriptEdit>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, transcription_id, segment_id, previous_text, new_text, source, snippet_path, created_at
+             FROM transcript_edits WHERE transcription_id = ?1 ORDER BY created_at"
+        )?;
+
+        let edits = stmt.query_map(params![transcription_id], |row| {
+            let created_at_str: String = row.get("created_at")?;
+            let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+                .map_err(|_e| rusqlite::Error::InvalidColumnType(0, "created_at".to_string(), rusqlite::types::Type::Text))?
+                .with_timezone(&Utc);
+            let source_str: String = row.get("source")?;
+            Ok(TranscriptEdit {
+                id: row.get("id")?,
+                transcription_id: row.get("transcription_id")?,
+                segment_id: row.get("segment_id")?,
+                previous_text: row.get("previous_text")?,
+                new_text: row.get("new_text")?,
+                source: source_str.parse().unwrap_or(TranscriptEditSource::Manual),
+                snippet_path: row.get("snippet_path")?,
+                created_at,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(edits)
+    }
+
     fn row_to_transcription(row: &Row) -> rusqlite::Result<Transcription> {
         let created_at_str: String = row.get("created_at")?;
         let updated_at_str: String = row.get("updated_at")?;
@@ -460,16 +1140,7 @@ impl Database {
             .with_timezone(&Utc);
 
         let status_str: String = row.get("status")?;
-        let status = if status_str.starts_with("failed:") {
-            TranscriptionStatus::Failed(status_str[7..].to_string())
-        } else {
-            match status_str.as_str() {
-                "pending" => TranscriptionStatus::Pending,
-                "processing" => TranscriptionStatus::Processing,
-                "completed" => TranscriptionStatus::Completed,
-                _ => TranscriptionStatus::Failed("Unknown status".to_string()),
-            }
-        };
+        let status = Self::parse_transcription_status(&status_str);
 
         Ok(Transcription {
             id: row.get("id")?,
@@ -478,15 +1149,49 @@ impl Database {
             language: row.get("language")?,
             confidence: row.get("confidence")?,
             processing_time_ms: row.get("processing_time_ms")?,
+            model_used: row.get("model_used")?,
             status,
             created_at,
             updated_at,
+            segments: Vec::new(),
         })
     }
 
+    /// 完了した書き起こしのモデル別実績（録音の長さが分かるものだけ）
+    pub async fn get_transcription_throughput(&self) -> AppResult<Vec<TranscriptionThroughput>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT t.model_used, COUNT(*), SUM(r.duration), SUM(t.processing_time_ms)
+             FROM transcriptions t
+             JOIN recordings r ON r.id = t.recording_id
+             WHERE t.status = 'completed' AND t.model_used IS NOT NULL
+               AND t.processing_time_ms IS NOT NULL AND r.duration > 0
+             GROUP BY t.model_used
+             ORDER BY t.model_used"
+        )?;
+
+        let throughput = stmt.query_map([], |row| {
+            Ok(TranscriptionThroughput {
+                model: row.get(0)?,
+                runs: row.get(1)?,
+                audio_seconds: row.get(2)?,
+                processing_ms: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(throughput)
+    }
+
     // Summary CRUD operations (Phase 3)
     pub async fn create_summary(&self, summary: &Summary) -> AppResult<()> {
-        let conn = self.conn.lock().await;
+        if self.has_locked_summary(&summary.transcription_id).await? {
+            return Err(AppError::InvalidOperation {
+                message: format!("Summaries for transcription {} are locked after approval", summary.transcription_id),
+            });
+        }
+
+        let conn = self.conn()?;
         let status_str = match &summary.status {
             SummaryStatus::Pending => "pending",
             SummaryStatus::Processing => "processing", 
@@ -498,8 +1203,8 @@ impl Database {
         let action_items_json = serde_json::to_string(&summary.action_items).unwrap_or_else(|_| "[]".to_string());
 
         conn.execute(
-            "INSERT INTO summaries (id, transcription_id, summary_text, key_points, action_items, model_used, processing_time_ms, status, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            "INSERT INTO summaries (id, transcription_id, summary_text, key_points, action_items, model_used, processing_time_ms, status, review_status, approved_by, approved_at, locked, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
             params![
                 summary.id,
                 summary.transcription_id,
@@ -509,6 +1214,10 @@ impl Database {
                 summary.model_used,
                 summary.processing_time_ms,
                 status_str,
+                summary.review_status.as_str(),
+                summary.approved_by,
+                summary.approved_at.map(|at| at.to_rfc3339()),
+                summary.locked,
                 summary.created_at.to_rfc3339(),
                 summary.updated_at.to_rfc3339(),
             ],
@@ -517,9 +1226,10 @@ impl Database {
     }
 
     pub async fn get_summary(&self, id: &str) -> AppResult<Option<Summary>> {
-        let conn = self.conn.lock().await;
+        let conn = self.conn()?;
         let mut stmt = conn.prepare(
-            "SELECT id, transcription_id, summary_text, key_points, action_items, model_used, processing_time_ms, status, created_at, updated_at 
+            "SELECT id, transcription_id, summary_text, key_points, action_items, model_used, processing_time_ms, status,
+                    review_status, approved_by, approved_at, locked, created_at, updated_at
              FROM summaries WHERE id = ?1"
         )?;
 
@@ -532,9 +1242,10 @@ impl Database {
     }
 
     pub async fn get_summaries_by_transcription(&self, transcription_id: &str) -> AppResult<Vec<Summary>> {
-        let conn = self.conn.lock().await;
+        let conn = self.conn()?;
         let mut stmt = conn.prepare(
-            "SELECT id, transcription_id, summary_text, key_points, action_items, model_used, processing_time_ms, status, created_at, updated_at 
+            "SELECT id, transcription_id, summary_text, key_points, action_items, model_used, processing_time_ms, status,
+                    review_status, approved_by, approved_at, locked, created_at, updated_at
              FROM summaries WHERE transcription_id = ?1 ORDER BY created_at DESC"
         )?;
 
@@ -544,7 +1255,14 @@ impl Database {
         Ok(summaries)
     }
 
+    /// 要約の本文を更新する（承認状態は `set_summary_review` / `set_summary_locked` で変更する）
     pub async fn update_summary(&self, summary: &Summary) -> AppResult<()> {
+        if self.get_summary(&summary.id).await?.is_some_and(|existing| existing.locked) {
+            return Err(AppError::InvalidOperation {
+                message: format!("Summary {} is locked after approval", summary.id),
+            });
+        }
+
         let updated_at = Utc::now().to_rfc3339();
         let status_str = match &summary.status {
             SummaryStatus::Pending => "pending",
@@ -556,7 +1274,7 @@ impl Database {
         let key_points_json = serde_json::to_string(&summary.key_points).unwrap_or_else(|_| "[]".to_string());
         let action_items_json = serde_json::to_string(&summary.action_items).unwrap_or_else(|_| "[]".to_string());
         
-        let conn = self.conn.lock().await;
+        let conn = self.conn()?;
         
         conn.execute(
             "UPDATE summaries 
@@ -577,14 +1295,155 @@ impl Database {
     }
 
     pub async fn delete_summary(&self, id: &str) -> AppResult<bool> {
-        let conn = self.conn.lock().await;
+        if self.get_summary(id).await?.is_some_and(|existing| existing.locked) {
+            return Err(AppError::InvalidOperation {
+                message: format!("Summary {} is locked after approval", id),
+            });
+        }
+
+        let conn = self.conn()?;
         let rows_affected = conn.execute(
             "DELETE FROM summaries WHERE id = ?1",
             params![id],
         )?;
+        conn.execute("DELETE FROM action_item_states WHERE summary_id = ?1", params![id])?;
+        Ok(rows_affected > 0)
+    }
+
+    /// 承認状態を変更する（承認を取り消した場合は固定も解除する）
+    pub async fn set_summary_review(
+        &self,
+        id: &str,
+        review_status: ReviewStatus,
+        approved_by: Option<&str>,
+        approved_at: Option<DateTime<Utc>>,
+    ) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute(
+            "UPDATE summaries
+             SET review_status = ?2, approved_by = ?3, approved_at = ?4,
+                 locked = CASE WHEN ?2 = 'approved' THEN locked ELSE 0 END, updated_at = ?5
+             WHERE id = ?1",
+            params![
+                id,
+                review_status.as_str(),
+                approved_by,
+                approved_at.map(|at| at.to_rfc3339()),
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(rows_affected > 0)
+    }
+
+    pub async fn set_summary_locked(&self, id: &str, locked: bool) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute(
+            "UPDATE summaries SET locked = ?2, updated_at = ?3 WHERE id = ?1",
+            params![id, locked, Utc::now().to_rfc3339()],
+        )?;
         Ok(rows_affected > 0)
     }
 
+    /// 書き起こしに固定された（承認済みで再生成を禁止した）要約があるか
+    pub async fn has_locked_summary(&self, transcription_id: &str) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let locked: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM summaries WHERE transcription_id = ?1 AND locked = 1)",
+            params![transcription_id],
+            |row| row.get(0),
+        )?;
+        Ok(locked)
+    }
+
+    /// 書き起こしごとの最新の完了済み要約と、その録音ID・タイトル
+    pub async fn get_latest_summaries_with_recordings(&self) -> AppResult<Vec<(Summary, String, Option<String>)>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT s.id, s.transcription_id, s.summary_text, s.key_points, s.action_items, s.model_used,
+                    s.processing_time_ms, s.status, s.review_status, s.approved_by, s.approved_at, s.locked,
+                    s.created_at, s.updated_at,
+                    t.recording_id AS recording_id, r.title AS recording_title
+             FROM summaries s
+             JOIN transcriptions t ON t.id = s.transcription_id
+             LEFT JOIN recordings r ON r.id = t.recording_id
+             WHERE s.status = 'completed'
+               AND s.created_at = (SELECT MAX(created_at) FROM summaries WHERE transcription_id = s.transcription_id)
+             ORDER BY s.created_at DESC"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((Self::row_to_summary(row)?, row.get("recording_id")?, row.get("recording_title")?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    /// 登録済みの対応状況（要約の再生成などで本文が変わった項目は呼び出し側で無視する）
+    pub async fn get_action_item_states(
+        &self,
+    ) -> AppResult<Vec<(ActionItemRef, String, ActionItemStatus, DateTime<Utc>)>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT summary_id, item_index, item_text, status, updated_at FROM action_item_states"
+        )?;
+
+        let states = stmt.query_map([], |row| {
+            let status_str: String = row.get("status")?;
+            let updated_at_str: String = row.get("updated_at")?;
+            let item_index: i64 = row.get("item_index")?;
+            Ok((
+                ActionItemRef {
+                    summary_id: row.get("summary_id")?,
+                    item_index: item_index as usize,
+                },
+                row.get("item_text")?,
+                status_str.parse().unwrap_or(ActionItemStatus::Open),
+                Self::parse_optional_datetime(&updated_at_str).unwrap_or_else(Utc::now),
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(states)
+    }
+
+    /// アクションアイテムの状態を一括更新。存在しない項目は無視し、更新件数を返す
+    pub async fn set_action_item_statuses(&self, items: &[ActionItemRef], status: ActionItemStatus) -> AppResult<usize> {
+        let mut conn = self.conn()?;
+        let tx = conn.transaction()?;
+        let now = Utc::now().to_rfc3339();
+        let mut updated = 0;
+
+        for item in items {
+            let action_items_json: Option<String> = tx
+                .query_row(
+                    "SELECT action_items FROM summaries WHERE id = ?1",
+                    params![item.summary_id],
+                    |row| row.get(0),
+                )
+                .optional()?
+                .flatten();
+            let action_items: Vec<String> = action_items_json
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default();
+            let Some(text) = action_items.get(item.item_index) else {
+                continue;
+            };
+
+            tx.execute(
+                "INSERT INTO action_item_states (summary_id, item_index, item_text, status, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(summary_id, item_index) DO UPDATE SET
+                    item_text = excluded.item_text, status = excluded.status, updated_at = excluded.updated_at",
+                params![item.summary_id, item.item_index as i64, text, status.as_str(), now],
+            )?;
+            updated += 1;
+        }
+
+        tx.commit()?;
+        Ok(updated)
+    }
+
     fn row_to_summary(row: &Row) -> rusqlite::Result<Summary> {
         let created_at_str: String = row.get("created_at")?;
         let updated_at_str: String = row.get("updated_at")?;
@@ -598,16 +1457,7 @@ impl Database {
             .with_timezone(&Utc);
 
         let status_str: String = row.get("status")?;
-        let status = if status_str.starts_with("failed:") {
-            SummaryStatus::Failed(status_str[7..].to_string())
-        } else {
-            match status_str.as_str() {
-                "pending" => SummaryStatus::Pending,
-                "processing" => SummaryStatus::Processing,
-                "completed" => SummaryStatus::Completed,
-                _ => SummaryStatus::Failed("Unknown status".to_string()),
-            }
-        };
+        let status = Self::parse_summary_status(&status_str);
 
         let key_points_json: String = row.get("key_points").unwrap_or_else(|_| "[]".to_string());
         let key_points: Vec<String> = serde_json::from_str(&key_points_json).unwrap_or_else(|_| Vec::new());
@@ -615,6 +1465,12 @@ impl Database {
         let action_items_json: String = row.get("action_items").unwrap_or_else(|_| "[]".to_string());
         let action_items: Vec<String> = serde_json::from_str(&action_items_json).unwrap_or_else(|_| Vec::new());
 
+        let review_status_str: String = row.get("review_status")?;
+        let approved_at = row
+            .get::<_, Option<String>>("approved_at")?
+            .and_then(|at| DateTime::parse_from_rfc3339(&at).ok())
+            .map(|at| at.with_timezone(&Utc));
+
         Ok(Summary {
             id: row.get("id")?,
             transcription_id: row.get("transcription_id")?,
@@ -624,152 +1480,1488 @@ impl Database {
             model_used: row.get("model_used")?,
             processing_time_ms: row.get("processing_time_ms")?,
             status,
+            review_status: review_status_str.parse().unwrap_or_default(),
+            approved_by: row.get("approved_by")?,
+            approved_at,
+            locked: row.get("locked")?,
             created_at,
             updated_at,
         })
     }
 
-    // Phase 2 advanced features - Search and filtering functions
-    pub async fn search_recordings(&self, query: &RecordingQuery) -> AppResult<Vec<Recording>> {
-        let conn = self.conn.lock().await;
-        
-        let mut sql = String::from(
-            "SELECT id, filename, file_path, title, description, category, tags, duration, file_size, sample_rate, channels, created_at, updated_at 
-             FROM recordings WHERE 1=1"
-        );
-        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
-        let mut param_index = 1;
-
-        // Search text filter (filename, title, description)
-        if let Some(search_text) = &query.search_text {
-            sql.push_str(&format!(" AND (filename LIKE ?{} OR title LIKE ?{} OR description LIKE ?{})", 
-                                param_index, param_index + 1, param_index + 2));
-            let search_pattern = format!("%{}%", search_text);
-            params.push(Box::new(search_pattern.clone()));
-            params.push(Box::new(search_pattern.clone()));
-            params.push(Box::new(search_pattern));
-            param_index += 3;
-        }
+    // Background job operations
+    pub async fn create_job(&self, job: &Job) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO jobs (id, kind, payload, status, priority, attempts, max_attempts, last_error, result, run_after, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                job.id,
+                job.payload.kind(),
+                serde_json::to_string(&job.payload)?,
+                job.status.as_str(),
+                job.priority,
+                job.attempts,
+                job.max_attempts,
+                job.last_error,
+                job.result.as_ref().map(|r| r.to_string()),
+                job.run_after.to_rfc3339(),
+                job.created_at.to_rfc3339(),
+                job.updated_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
 
-        // Category filter
-        if let Some(category) = &query.category {
-            sql.push_str(&format!(" AND category = ?{}", param_index));
-            params.push(Box::new(category.clone()));
-            param_index += 1;
-        }
+    pub async fn get_job(&self, id: &str) -> AppResult<Option<Job>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare("SELECT * FROM jobs WHERE id = ?1")?;
+        let mut rows = stmt.query_map(params![id], Self::row_to_job)?;
 
-        // Tags filter
-        for tag in &query.tags {
-            sql.push_str(&format!(" AND tags LIKE ?{}", param_index));
-            params.push(Box::new(format!("%\"{}\"", tag)));
-            param_index += 1;
+        match rows.next() {
+            Some(job) => Ok(Some(job?)),
+            None => Ok(None),
         }
+    }
 
-        // Date range filter
-        if let Some(date_from) = &query.date_from {
-            sql.push_str(&format!(" AND created_at >= ?{}", param_index));
-            params.push(Box::new(date_from.to_rfc3339()));
-            param_index += 1;
-        }
+    pub async fn list_jobs(&self, status: Option<&JobStatus>) -> AppResult<Vec<Job>> {
+        let conn = self.conn()?;
+        let jobs = match status {
+            Some(status) => {
+                let mut stmt = conn.prepare("SELECT * FROM jobs WHERE status = ?1 ORDER BY created_at DESC")?;
+                let jobs = stmt.query_map(params![status.as_str()], Self::row_to_job)?
+                    .collect::<Result<Vec<_>, _>>()?;
+                jobs
+            }
+            None => {
+                let mut stmt = conn.prepare("SELECT * FROM jobs ORDER BY created_at DESC")?;
+                let jobs = stmt.query_map([], Self::row_to_job)?
+                    .collect::<Result<Vec<_>, _>>()?;
+                jobs
+            }
+        };
+        Ok(jobs)
+    }
 
-        if let Some(date_to) = &query.date_to {
-            sql.push_str(&format!(" AND created_at <= ?{}", param_index));
-            params.push(Box::new(date_to.to_rfc3339()));
-            param_index += 1;
-        }
+    /// 実行可能な最優先ジョブを1件取得し、running に更新する
+    pub async fn claim_next_job(&self) -> AppResult<Option<Job>> {
+        let conn = self.conn()?;
+        let now = Utc::now().to_rfc3339();
+        let mut stmt = conn.prepare(
+            "UPDATE jobs SET status = 'running', attempts = attempts + 1, updated_at = ?1
+             WHERE id = (
+                 SELECT id FROM jobs
+                 WHERE status = 'pending' AND run_after <= ?1
+                 ORDER BY priority DESC, created_at
+                 LIMIT 1
+             ) AND status = 'pending'
+             RETURNING *"
+        )?;
+        let mut rows = stmt.query_map(params![now], Self::row_to_job)?;
 
-        // Duration range filter
-        if let Some(min_duration) = query.min_duration {
-            sql.push_str(&format!(" AND duration >= ?{}", param_index));
-            params.push(Box::new(min_duration));
-            param_index += 1;
+        match rows.next() {
+            Some(job) => Ok(Some(job?)),
+            None => Ok(None),
         }
+    }
 
-        if let Some(max_duration) = query.max_duration {
-            sql.push_str(&format!(" AND duration <= ?{}", param_index));
-            params.push(Box::new(max_duration));
-            param_index += 1;
-        }
+    pub async fn update_job(&self, job: &Job) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "UPDATE jobs 
+             SET status = ?2, priority = ?3, attempts = ?4, max_attempts = ?5, last_error = ?6, result = ?7, run_after = ?8, updated_at = ?9
+             WHERE id = ?1",
+            params![
+                job.id,
+                job.status.as_str(),
+                job.priority,
+                job.attempts,
+                job.max_attempts,
+                job.last_error,
+                job.result.as_ref().map(|r| r.to_string()),
+                job.run_after.to_rfc3339(),
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
 
-        // Sort by
-        let sort_column = match query.sort_by {
-            SortBy::CreatedAt => "created_at",
-            SortBy::UpdatedAt => "updated_at", 
-            SortBy::Filename => "filename",
-            SortBy::Duration => "duration",
-            SortBy::FileSize => "file_size",
-        };
+    /// 前回終了時に実行中だったジョブを pending に戻す
+    pub async fn requeue_interrupted_jobs(&self) -> AppResult<usize> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute(
+            "UPDATE jobs SET status = 'pending', attempts = MAX(attempts - 1, 0), updated_at = ?1 WHERE status = 'running'",
+            params![Utc::now().to_rfc3339()],
+        )?;
+        Ok(rows_affected)
+    }
 
-        let sort_direction = match query.sort_order {
-            SortOrder::Asc => "ASC",
-            SortOrder::Desc => "DESC",
+    fn row_to_job(row: &Row) -> rusqlite::Result<Job> {
+        let parse_datetime = |column: &str| -> rusqlite::Result<DateTime<Utc>> {
+            let value: String = row.get(column)?;
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|_e| rusqlite::Error::InvalidColumnType(0, column.to_string(), rusqlite::types::Type::Text))
         };
 
-        sql.push_str(&format!(" ORDER BY {} {}", sort_column, sort_direction));
+        let payload_str: String = row.get("payload")?;
+        let payload = serde_json::from_str(&payload_str)
+            .map_err(|_e| rusqlite::Error::InvalidColumnType(0, "payload".to_string(), rusqlite::types::Type::Text))?;
 
-        // Limit and offset
-        if let Some(limit) = query.limit {
-            sql.push_str(&format!(" LIMIT {}", limit));
-            if let Some(offset) = query.offset {
-                sql.push_str(&format!(" OFFSET {}", offset));
-            }
-        }
+        let status_str: String = row.get("status")?;
+        let status = status_str.parse::<JobStatus>().unwrap_or(JobStatus::Failed);
 
-        let mut stmt = conn.prepare(&sql)?;
-        let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
-        let recordings = stmt.query_map(&param_refs[..], Self::row_to_recording)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let result: Option<String> = row.get("result")?;
 
-        Ok(recordings)
+        Ok(Job {
+            id: row.get("id")?,
+            payload,
+            status,
+            priority: row.get("priority")?,
+            attempts: row.get("attempts")?,
+            max_attempts: row.get("max_attempts")?,
+            last_error: row.get("last_error")?,
+            result: result.and_then(|r| serde_json::from_str(&r).ok()),
+            run_after: parse_datetime("run_after")?,
+            created_at: parse_datetime("created_at")?,
+            updated_at: parse_datetime("updated_at")?,
+        })
     }
 
-    pub async fn get_recording_stats(&self) -> AppResult<RecordingStats> {
-        let conn = self.conn.lock().await;
-        
-        // Total counts and sizes
-        let (total_count, total_duration, total_size): (i64, i64, i64) = conn.query_row(
-            "SELECT COUNT(*), COALESCE(SUM(duration), 0), COALESCE(SUM(file_size), 0) FROM recordings",
-            [],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))
-        )?;
-
-        // Recent count (last 7 days)
-        let seven_days_ago = Utc::now() - chrono::Duration::days(7);
-        let recent_count: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM recordings WHERE created_at >= ?1",
-            params![seven_days_ago.to_rfc3339()],
-            |row| row.get(0)
+    // Participant and attachment operations (meeting import)
+    pub async fn create_participant(&self, participant: &Participant) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO participants (id, recording_id, name, email, join_time, leave_time)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                participant.id,
+                participant.recording_id,
+                participant.name,
+                participant.email,
+                participant.join_time.map(|t| t.to_rfc3339()),
+                participant.leave_time.map(|t| t.to_rfc3339()),
+            ],
         )?;
+        Ok(())
+    }
 
-        // Category stats
+    pub async fn get_participants_by_recording(&self, recording_id: &str) -> AppResult<Vec<Participant>> {
+        let conn = self.conn()?;
         let mut stmt = conn.prepare(
-            "SELECT category, COUNT(*), COALESCE(SUM(duration), 0) 
-             FROM recordings 
-             WHERE category IS NOT NULL 
-             GROUP BY category 
-             ORDER BY COUNT(*) DESC"
+            "SELECT id, recording_id, name, email, join_time, leave_time 
+             FROM participants WHERE recording_id = ?1 ORDER BY join_time, name"
         )?;
 
-        let categories = stmt.query_map([], |row| {
-            Ok(CategoryStats {
-                name: row.get(0)?,
-                count: row.get(1)?,
-                total_duration: row.get(2)?,
+        let participants = stmt.query_map(params![recording_id], |row| {
+            let join_time: Option<String> = row.get("join_time")?;
+            let leave_time: Option<String> = row.get("leave_time")?;
+            Ok(Participant {
+                id: row.get("id")?,
+                recording_id: row.get("recording_id")?,
+                name: row.get("name")?,
+                email: row.get("email")?,
+                join_time: join_time.as_deref().and_then(Self::parse_optional_datetime),
+                leave_time: leave_time.as_deref().and_then(Self::parse_optional_datetime),
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(RecordingStats {
-            total_count,
-            total_duration,
-            total_size,
-            categories,
-            recent_count,
+        Ok(participants)
+    }
+
+    pub async fn create_attachment(&self, attachment: &Attachment) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO attachments (id, recording_id, kind, filename, file_path, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                attachment.id,
+                attachment.recording_id,
+                attachment.kind,
+                attachment.filename,
+                attachment.file_path,
+                attachment.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub async fn get_attachments_by_recording(&self, recording_id: &str) -> AppResult<Vec<Attachment>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, recording_id, kind, filename, file_path, created_at 
+             FROM attachments WHERE recording_id = ?1 ORDER BY created_at"
+        )?;
+
+        let attachments = stmt.query_map(params![recording_id], |row| {
+            let created_at_str: String = row.get("created_at")?;
+            let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+                .map_err(|_e| rusqlite::Error::InvalidColumnType(0, "created_at".to_string(), rusqlite::types::Type::Text))?
+                .with_timezone(&Utc);
+            Ok(Attachment {
+                id: row.get("id")?,
+                recording_id: row.get("recording_id")?,
+                kind: row.get("kind")?,
+                filename: row.get("filename")?,
+                file_path: row.get("file_path")?,
+                created_at,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(attachments)
+    }
+
+    // Chapter operations
+    pub async fn create_chapter(&self, chapter: &Chapter) -> AppResult<()> {
+        let conn = self.conn()?;
+        Self::insert_chapter(&conn, chapter)?;
+        Ok(())
+    }
+
+    pub async fn get_chapters_by_recording(&self, recording_id: &str) -> AppResult<Vec<Chapter>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, recording_id, title, start_ms, end_ms, source, created_at
+             FROM chapters WHERE recording_id = ?1 ORDER BY start_ms, created_at"
+        )?;
+
+        let chapters = stmt.query_map(params![recording_id], Self::row_to_chapter)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(chapters)
+    }
+
+    pub async fn update_chapter(&self, chapter: &Chapter) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute(
+            "UPDATE chapters SET title = ?2, start_ms = ?3, end_ms = ?4, source = ?5 WHERE id = ?1",
+            params![
+                chapter.id,
+                chapter.title,
+                chapter.start_ms,
+                chapter.end_ms,
+                chapter.source.as_str(),
+            ],
+        )?;
+        Ok(rows_affected > 0)
+    }
+
+    pub async fn delete_chapter(&self, id: &str) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute("DELETE FROM chapters WHERE id = ?1", params![id])?;
+        Ok(rows_affected > 0)
+    }
+
+    /// 指定した由来のチャプターをまとめて置き換える（トピック分割の再実行時など。他の由来のものは残す）
+    pub async fn replace_chapters(
+        &self,
+        recording_id: &str,
+        source: ChapterSource,
+        chapters: &[Chapter],
+    ) -> AppResult<()> {
+        let mut conn = self.conn()?;
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "DELETE FROM chapters WHERE recording_id = ?1 AND source = ?2",
+            params![recording_id, source.as_str()],
+        )?;
+        for chapter in chapters {
+            Self::insert_chapter(&tx, chapter)?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn insert_chapter(conn: &Connection, chapter: &Chapter) -> rusqlite::Result<usize> {
+        conn.execute(
+            "INSERT INTO chapters (id, recording_id, title, start_ms, end_ms, source, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                chapter.id,
+                chapter.recording_id,
+                chapter.title,
+                chapter.start_ms,
+                chapter.end_ms,
+                chapter.source.as_str(),
+                chapter.created_at.to_rfc3339(),
+            ],
+        )
+    }
+
+    fn row_to_chapter(row: &Row) -> rusqlite::Result<Chapter> {
+        let created_at_str: String = row.get("created_at")?;
+        let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+            .map_err(|_e| rusqlite::Error::InvalidColumnType(0, "created_at".to_string(), rusqlite::types::Type::Text))?
+            .with_timezone(&Utc);
+        let source_str: String = row.get("source")?;
+
+        Ok(Chapter {
+            id: row.get("id")?,
+            recording_id: row.get("recording_id")?,
+            title: row.get("title")?,
+            start_ms: row.get("start_ms")?,
+            end_ms: row.get("end_ms")?,
+            source: source_str.parse().unwrap_or(ChapterSource::Manual),
+            created_at,
         })
     }
 
+    // Speaker analytics operations
+    pub async fn replace_speaker_stats(&self, recording_id: &str, stats: &[SpeakerTalkStats]) -> AppResult<()> {
+        let mut conn = self.conn()?;
+        let tx = conn.transaction()?;
+
+        tx.execute("DELETE FROM speaker_stats WHERE recording_id = ?1", params![recording_id])?;
+        let now = Utc::now().to_rfc3339();
+        for stat in stats {
+            tx.execute(
+                "INSERT INTO speaker_stats (recording_id, speaker, talk_time_ms, turn_count, longest_monologue_ms, talk_share, computed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    recording_id,
+                    stat.speaker,
+                    stat.talk_time_ms,
+                    stat.turn_count,
+                    stat.longest_monologue_ms,
+                    stat.talk_share,
+                    now,
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub async fn get_speaker_stats(&self, recording_id: &str) -> AppResult<Vec<SpeakerTalkStats>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT recording_id, speaker, talk_time_ms, turn_count, longest_monologue_ms, talk_share
+             FROM speaker_stats WHERE recording_id = ?1 ORDER BY talk_time_ms DESC"
+        )?;
+
+        let stats = stmt.query_map(params![recording_id], |row| {
+            Ok(SpeakerTalkStats {
+                recording_id: row.get("recording_id")?,
+                speaker: row.get("speaker")?,
+                talk_time_ms: row.get("talk_time_ms")?,
+                turn_count: row.get("turn_count")?,
+                longest_monologue_ms: row.get("longest_monologue_ms")?,
+                talk_share: row.get("talk_share")?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(stats)
+    }
+
+    pub async fn save_speech_metrics(&self, metrics: &SpeechMetrics) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT OR REPLACE INTO speech_metrics (recording_id, duration_ms, speech_ms, silence_ratio, average_pause_ms, pause_count, speech_density, analyzed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                metrics.recording_id,
+                metrics.duration_ms,
+                metrics.speech_ms,
+                metrics.silence_ratio,
+                metrics.average_pause_ms,
+                metrics.pause_count,
+                metrics.speech_density,
+                metrics.analyzed_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub async fn get_speech_metrics(&self, recording_id: &str) -> AppResult<Option<SpeechMetrics>> {
+        let conn = self.conn()?;
+        let metrics = conn
+            .query_row(
+                "SELECT recording_id, duration_ms, speech_ms, silence_ratio, average_pause_ms, pause_count, speech_density, analyzed_at
+                 FROM speech_metrics WHERE recording_id = ?1",
+                params![recording_id],
+                |row| {
+                    let analyzed_at: String = row.get("analyzed_at")?;
+                    Ok(SpeechMetrics {
+                        recording_id: row.get("recording_id")?,
+                        duration_ms: row.get("duration_ms")?,
+                        speech_ms: row.get("speech_ms")?,
+                        silence_ratio: row.get("silence_ratio")?,
+                        average_pause_ms: row.get("average_pause_ms")?,
+                        pause_count: row.get("pause_count")?,
+                        speech_density: row.get("speech_density")?,
+                        analyzed_at: Self::parse_optional_datetime(&analyzed_at).unwrap_or_else(Utc::now),
+                    })
+                },
+            )
+            .optional()?;
+        Ok(metrics)
+    }
+
+    /// 話者名ごとに全録音の発話量を集計する
+    pub async fn get_person_talk_stats(&self) -> AppResult<Vec<PersonTalkStats>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT speaker,
+                    COUNT(DISTINCT recording_id) AS meeting_count,
+                    SUM(talk_time_ms) AS total_talk_time_ms,
+                    SUM(turn_count) AS total_turns,
+                    MAX(longest_monologue_ms) AS longest_monologue_ms,
+                    AVG(talk_share) AS average_talk_share
+             FROM speaker_stats
+             GROUP BY speaker
+             ORDER BY total_talk_time_ms DESC"
+        )?;
+
+        let stats = stmt.query_map([], |row| {
+            Ok(PersonTalkStats {
+                speaker: row.get("speaker")?,
+                meeting_count: row.get("meeting_count")?,
+                total_talk_time_ms: row.get("total_talk_time_ms")?,
+                total_turns: row.get("total_turns")?,
+                longest_monologue_ms: row.get("longest_monologue_ms")?,
+                average_talk_share: row.get("average_talk_share")?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(stats)
+    }
+
+    // Correction dictionary operations
+    pub async fn create_correction_entry(&self, entry: &CorrectionEntry) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO correction_dictionary (id, wrong, correct, enabled, hit_count, last_hit_at, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                entry.id,
+                entry.wrong,
+                entry.correct,
+                entry.enabled,
+                entry.hit_count,
+                entry.last_hit_at.map(|dt| dt.to_rfc3339()),
+                entry.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub async fn get_correction_entries(&self) -> AppResult<Vec<CorrectionEntry>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, wrong, correct, enabled, hit_count, last_hit_at, created_at
+             FROM correction_dictionary ORDER BY hit_count DESC, wrong"
+        )?;
+
+        let entries = stmt.query_map([], |row| {
+            let created_at_str: String = row.get("created_at")?;
+            let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+                .map_err(|_e| rusqlite::Error::InvalidColumnType(0, "created_at".to_string(), rusqlite::types::Type::Text))?
+                .with_timezone(&Utc);
+            let last_hit_at: Option<String> = row.get("last_hit_at")?;
+            Ok(CorrectionEntry {
+                id: row.get("id")?,
+                wrong: row.get("wrong")?,
+                correct: row.get("correct")?,
+                enabled: row.get("enabled")?,
+                hit_count: row.get("hit_count")?,
+                last_hit_at: last_hit_at.as_deref().and_then(Self::parse_optional_datetime),
+                created_at,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(entries)
+    }
+
+    pub async fn update_correction_entry(&self, entry: &CorrectionEntry) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute(
+            "UPDATE correction_dictionary SET wrong = ?2, correct = ?3, enabled = ?4 WHERE id = ?1",
+            params![entry.id, entry.wrong, entry.correct, entry.enabled],
+        )?;
+        Ok(rows_affected > 0)
+    }
+
+    pub async fn delete_correction_entry(&self, id: &str) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute("DELETE FROM correction_dictionary WHERE id = ?1", params![id])?;
+        Ok(rows_affected > 0)
+    }
+
+    /// 置き換え回数を加算する（エントリIDと今回の回数）
+    pub async fn record_correction_hits(&self, hits: &[(String, i64)]) -> AppResult<()> {
+        let mut conn = self.conn()?;
+        let tx = conn.transaction()?;
+
+        let now = Utc::now().to_rfc3339();
+        for (id, count) in hits {
+            tx.execute(
+                "UPDATE correction_dictionary SET hit_count = hit_count + ?2, last_hit_at = ?3 WHERE id = ?1",
+                params![id, count, now],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    // Meeting note operations
+    pub async fn create_meeting_note(&self, note: &MeetingNote) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO meeting_notes (id, recording_id, text, offset_ms, important, author, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                note.id,
+                note.recording_id,
+                note.text,
+                note.offset_ms,
+                note.important,
+                note.author,
+                note.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 録音のメモ（会議中の時刻順、時刻の無いメモは最後）
+    pub async fn get_meeting_notes(&self, recording_id: &str) -> AppResult<Vec<MeetingNote>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, recording_id, text, offset_ms, important, author, created_at FROM meeting_notes
+             WHERE recording_id = ?1 ORDER BY offset_ms IS NULL, offset_ms, created_at"
+        )?;
+
+        let notes = stmt.query_map(params![recording_id], |row| {
+            let created_at_str: String = row.get("created_at")?;
+            let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+                .map_err(|_e| rusqlite::Error::InvalidColumnType(0, "created_at".to_string(), rusqlite::types::Type::Text))?
+                .with_timezone(&Utc);
+            Ok(MeetingNote {
+                id: row.get("id")?,
+                recording_id: row.get("recording_id")?,
+                text: row.get("text")?,
+                offset_ms: row.get("offset_ms")?,
+                important: row.get("important")?,
+                author: row.get("author")?,
+                created_at,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(notes)
+    }
+
+    pub async fn update_meeting_note(&self, note: &MeetingNote) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute(
+            "UPDATE meeting_notes SET text = ?2, offset_ms = ?3, important = ?4 WHERE id = ?1",
+            params![note.id, note.text, note.offset_ms, note.important],
+        )?;
+        Ok(rows_affected > 0)
+    }
+
+    pub async fn delete_meeting_note(&self, id: &str) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute("DELETE FROM meeting_notes WHERE id = ?1", params![id])?;
+        Ok(rows_affected > 0)
+    }
+
+    // Watched folder import operations
+    pub async fn is_watched_file_imported(&self, source_path: &str) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM watched_imports WHERE source_path = ?1",
+            params![source_path],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    pub async fn record_watched_import(&self, source_path: &str, folder_id: &str, recording_id: &str) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT OR REPLACE INTO watched_imports (source_path, folder_id, recording_id, imported_at) VALUES (?1, ?2, ?3, ?4)",
+            params![source_path, folder_id, recording_id, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    // Recording marker operations
+    pub async fn create_marker(&self, marker: &RecordingMarker) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO markers (id, recording_id, offset_ms, label, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                marker.id,
+                marker.recording_id,
+                marker.offset_ms,
+                marker.label,
+                marker.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub async fn get_markers(&self, recording_id: &str) -> AppResult<Vec<RecordingMarker>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, recording_id, offset_ms, label, created_at FROM markers
+             WHERE recording_id = ?1 ORDER BY offset_ms"
+        )?;
+
+        let markers = stmt.query_map(params![recording_id], |row| {
+            let created_at_str: String = row.get("created_at")?;
+            let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+                .map_err(|_e| rusqlite::Error::InvalidColumnType(0, "created_at".to_string(), rusqlite::types::Type::Text))?
+                .with_timezone(&Utc);
+            Ok(RecordingMarker {
+                id: row.get("id")?,
+                recording_id: row.get("recording_id")?,
+                offset_ms: row.get("offset_ms")?,
+                label: row.get("label")?,
+                created_at,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(markers)
+    }
+
+    pub async fn update_marker_label(&self, id: &str, label: Option<&str>) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute("UPDATE markers SET label = ?2 WHERE id = ?1", params![id, label])?;
+        Ok(rows_affected > 0)
+    }
+
+    pub async fn update_marker_offset(&self, id: &str, offset_ms: i64) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute("UPDATE markers SET offset_ms = ?2 WHERE id = ?1", params![id, offset_ms])?;
+        Ok(rows_affected > 0)
+    }
+
+    pub async fn delete_marker(&self, id: &str) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute("DELETE FROM markers WHERE id = ?1", params![id])?;
+        Ok(rows_affected > 0)
+    }
+
+    // Recording segment operations
+    pub async fn create_recording_segment(&self, segment: &RecordingSegment) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO recording_segments (id, recording_id, segment_index, file_path, offset_ms, duration_ms, file_size)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                segment.id,
+                segment.recording_id,
+                segment.segment_index,
+                segment.file_path,
+                segment.offset_ms,
+                segment.duration_ms,
+                segment.file_size,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub async fn get_recording_segments(&self, recording_id: &str) -> AppResult<Vec<RecordingSegment>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, recording_id, segment_index, file_path, offset_ms, duration_ms, file_size
+             FROM recording_segments WHERE recording_id = ?1 ORDER BY segment_index"
+        )?;
+
+        let segments = stmt.query_map(params![recording_id], |row| {
+            Ok(RecordingSegment {
+                id: row.get("id")?,
+                recording_id: row.get("recording_id")?,
+                segment_index: row.get("segment_index")?,
+                file_path: row.get("file_path")?,
+                offset_ms: row.get("offset_ms")?,
+                duration_ms: row.get("duration_ms")?,
+                file_size: row.get("file_size")?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(segments)
+    }
+
+    /// 全録音の区間のファイル
+    pub async fn get_all_recording_segment_paths(&self) -> AppResult<Vec<String>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare("SELECT file_path FROM recording_segments")?;
+        let paths = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(paths)
+    }
+
+    // Recording track operations
+    pub async fn create_recording_track(&self, track: &RecordingTrack) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO recording_tracks (id, recording_id, track_index, device_name, file_path, duration_ms, file_size)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                track.id,
+                track.recording_id,
+                track.track_index,
+                track.device_name,
+                track.file_path,
+                track.duration_ms,
+                track.file_size,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub async fn get_recording_tracks(&self, recording_id: &str) -> AppResult<Vec<RecordingTrack>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, recording_id, track_index, device_name, file_path, duration_ms, file_size
+             FROM recording_tracks WHERE recording_id = ?1 ORDER BY track_index"
+        )?;
+
+        let tracks = stmt.query_map(params![recording_id], |row| {
+            Ok(RecordingTrack {
+                id: row.get("id")?,
+                recording_id: row.get("recording_id")?,
+                track_index: row.get("track_index")?,
+                device_name: row.get("device_name")?,
+                file_path: row.get("file_path")?,
+                duration_ms: row.get("duration_ms")?,
+                file_size: row.get("file_size")?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(tracks)
+    }
+
+    /// 全録音のトラックのファイル
+    pub async fn get_all_recording_track_paths(&self) -> AppResult<Vec<String>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare("SELECT file_path FROM recording_tracks")?;
+        let paths = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(paths)
+    }
+
+    // Category language operations
+    pub async fn set_category_language(&self, category: &str, language: &str) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO category_languages (category, language, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(category) DO UPDATE SET language = excluded.language, updated_at = excluded.updated_at",
+            params![category, language, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    pub async fn get_category_language(&self, category: &str) -> AppResult<Option<String>> {
+        let conn = self.conn()?;
+        let language = conn
+            .query_row(
+                "SELECT language FROM category_languages WHERE category = ?1",
+                params![category],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(language)
+    }
+
+    pub async fn get_category_languages(&self) -> AppResult<Vec<CategoryLanguage>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT category, language, updated_at FROM category_languages ORDER BY category COLLATE NOCASE"
+        )?;
+
+        let languages = stmt.query_map([], |row| {
+            let updated_at_str: String = row.get("updated_at")?;
+            let updated_at = DateTime::parse_from_rfc3339(&updated_at_str)
+                .map_err(|_e| rusqlite::Error::InvalidColumnType(0, "updated_at".to_string(), rusqlite::types::Type::Text))?
+                .with_timezone(&Utc);
+            Ok(CategoryLanguage {
+                category: row.get("category")?,
+                language: row.get("language")?,
+                updated_at,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(languages)
+    }
+
+    pub async fn delete_category_language(&self, category: &str) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute("DELETE FROM category_languages WHERE category = ?1", params![category])?;
+        Ok(rows_affected > 0)
+    }
+
+    // Watch word operations
+    pub async fn create_watch_word(&self, watch_word: &WatchWord) -> AppResult<()> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO watch_words (id, word, tag, enabled, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                watch_word.id,
+                watch_word.word,
+                watch_word.tag,
+                watch_word.enabled,
+                watch_word.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub async fn get_watch_words(&self) -> AppResult<Vec<WatchWord>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, word, tag, enabled, created_at FROM watch_words ORDER BY word COLLATE NOCASE"
+        )?;
+
+        let words = stmt.query_map([], |row| {
+            let created_at_str: String = row.get("created_at")?;
+            let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+                .map_err(|_e| rusqlite::Error::InvalidColumnType(0, "created_at".to_string(), rusqlite::types::Type::Text))?
+                .with_timezone(&Utc);
+            Ok(WatchWord {
+                id: row.get("id")?,
+                word: row.get("word")?,
+                tag: row.get("tag")?,
+                enabled: row.get("enabled")?,
+                created_at,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(words)
+    }
+
+    pub async fn update_watch_word(&self, watch_word: &WatchWord) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute(
+            "UPDATE watch_words SET word = ?2, tag = ?3, enabled = ?4 WHERE id = ?1",
+            params![watch_word.id, watch_word.word, watch_word.tag, watch_word.enabled],
+        )?;
+        Ok(rows_affected > 0)
+    }
+
+    pub async fn delete_watch_word(&self, id: &str) -> AppResult<bool> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute("DELETE FROM watch_words WHERE id = ?1", params![id])?;
+        Ok(rows_affected > 0)
+    }
+
+    /// 検出記録を保存する。同じ書き起こしで既に記録済みのワードは無視し、新規に保存した分を返す
+    pub async fn create_keyword_alerts(&self, alerts: &[KeywordAlert]) -> AppResult<Vec<KeywordAlert>> {
+        let mut conn = self.conn()?;
+        let tx = conn.transaction()?;
+
+        let mut inserted = Vec::new();
+        for alert in alerts {
+            let rows = tx.execute(
+                "INSERT OR IGNORE INTO keyword_alerts
+                    (id, watch_word_id, word, recording_id, transcription_id, occurrences, snippet, acknowledged, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    alert.id,
+                    alert.watch_word_id,
+                    alert.word,
+                    alert.recording_id,
+                    alert.transcription_id,
+                    alert.occurrences,
+                    alert.snippet,
+                    alert.acknowledged,
+                    alert.created_at.to_rfc3339(),
+                ],
+            )?;
+            if rows > 0 {
+                inserted.push(alert.clone());
+            }
+        }
+
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    pub async fn get_keyword_alerts(&self, unacknowledged_only: bool, limit: i64) -> AppResult<Vec<KeywordAlert>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, watch_word_id, word, recording_id, transcription_id, occurrences, snippet, acknowledged, created_at
+             FROM keyword_alerts WHERE (?1 = 0 OR acknowledged = 0)
+             ORDER BY created_at DESC LIMIT ?2"
+        )?;
+
+        let alerts = stmt.query_map(params![unacknowledged_only, limit], |row| {
+            let created_at_str: String = row.get("created_at")?;
+            let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+                .map_err(|_e| rusqlite::Error::InvalidColumnType(0, "created_at".to_string(), rusqlite::types::Type::Text))?
+                .with_timezone(&Utc);
+            Ok(KeywordAlert {
+                id: row.get("id")?,
+                watch_word_id: row.get("watch_word_id")?,
+                word: row.get("word")?,
+                recording_id: row.get("recording_id")?,
+                transcription_id: row.get("transcription_id")?,
+                occurrences: row.get("occurrences")?,
+                snippet: row.get("snippet")?,
+                acknowledged: row.get("acknowledged")?,
+                created_at,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(alerts)
+    }
+
+    pub async fn acknowledge_keyword_alerts(&self, ids: &[String]) -> AppResult<usize> {
+        let mut conn = self.conn()?;
+        let tx = conn.transaction()?;
+
+        let mut updated = 0;
+        for id in ids {
+            updated += tx.execute("UPDATE keyword_alerts SET acknowledged = 1 WHERE id = ?1", params![id])?;
+        }
+
+        tx.commit()?;
+        Ok(updated)
+    }
+
+    // Redaction mapping operations
+    pub async fn get_redaction_mappings(&self, recording_id: &str) -> AppResult<Vec<RedactionEntry>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT recording_id, placeholder, original, kind, created_at
+             FROM redaction_mappings WHERE recording_id = ?1 ORDER BY kind, created_at, placeholder"
+        )?;
+
+        let entries = stmt.query_map(params![recording_id], |row| {
+            let kind_str: String = row.get("kind")?;
+            let created_at_str: String = row.get("created_at")?;
+            let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+                .map_err(|_e| rusqlite::Error::InvalidColumnType(0, "created_at".to_string(), rusqlite::types::Type::Text))?
+                .with_timezone(&Utc);
+            Ok(RedactionEntry {
+                recording_id: row.get("recording_id")?,
+                placeholder: row.get("placeholder")?,
+                original: row.get("original")?,
+                kind: kind_str.parse().unwrap_or(PiiKind::PersonName),
+                created_at,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(entries)
+    }
+
+    /// 新しく割り当てたプレースホルダーを追加する（既存の対応は変更しない）
+    pub async fn save_redaction_mappings(&self, entries: &[RedactionEntry]) -> AppResult<usize> {
+        let mut conn = self.conn()?;
+        let tx = conn.transaction()?;
+
+        let mut inserted = 0;
+        for entry in entries {
+            inserted += tx.execute(
+                "INSERT OR IGNORE INTO redaction_mappings (recording_id, placeholder, original, kind, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    entry.recording_id,
+                    entry.placeholder,
+                    entry.original,
+                    entry.kind.as_str(),
+                    entry.created_at.to_rfc3339(),
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    pub async fn delete_redaction_mappings(&self, recording_id: &str) -> AppResult<usize> {
+        let conn = self.conn()?;
+        let rows_affected = conn.execute(
+            "DELETE FROM redaction_mappings WHERE recording_id = ?1",
+            params![recording_id],
+        )?;
+        Ok(rows_affected)
+    }
+
+    fn parse_optional_datetime(value: &str) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+
+    // Phase 2 advanced features - Search and filtering functions
+    pub async fn search_recordings(&self, query: &RecordingQuery) -> AppResult<Vec<Recording>> {
+        let conn = self.conn()?;
+        let (sql, params) = Self::build_search_sql(query, false);
+
+        let mut stmt = conn.prepare(&sql)?;
+        let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let recordings = stmt.query_map(&param_refs[..], Self::row_to_recording)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(recordings)
+    }
+
+    /// 検索結果に書き起こし・要約の有無と最新の状態を付けて返す（1回のクエリで取得）
+    pub async fn search_recordings_with_status(&self, query: &RecordingQuery) -> AppResult<Vec<RecordingListItem>> {
+        let conn = self.conn()?;
+        let (sql, params) = Self::build_search_sql(query, true);
+
+        let mut stmt = conn.prepare(&sql)?;
+        let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let items = stmt.query_map(&param_refs[..], Self::row_to_recording_list_item)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(items)
+    }
+
+    /// 録音一覧（新しい順）に書き起こし・要約の状態を付けて返す
+    pub async fn get_recordings_with_status(&self, include_archived: bool) -> AppResult<Vec<RecordingListItem>> {
+        let query = RecordingQuery {
+            include_archived,
+            limit: None,
+            offset: None,
+            ..RecordingQuery::default()
+        };
+        self.search_recordings_with_status(&query).await
+    }
+
+    /// 検索クエリの実行計画（EXPLAIN QUERY PLAN の detail 列）を返す
+    pub async fn explain_search_query(&self, query: &RecordingQuery) -> AppResult<Vec<String>> {
+        let conn = self.conn()?;
+        let (sql, params) = Self::build_search_sql(query, false);
+
+        let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {}", sql))?;
+        let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let plan = stmt.query_map(&param_refs[..], |row| row.get::<_, String>("detail"))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(plan)
+    }
+
+    /// LIKE 検索用に % と _ をエスケープする（ESCAPE '\' と組み合わせる）
+    fn escape_like(text: &str) -> String {
+        text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+    }
+
+    /// 条件の組み合わせを SQL の式にする（値はすべてパラメータで渡す）
+    fn condition_sql(
+        condition: &QueryCondition,
+        params: &mut Vec<Box<dyn rusqlite::ToSql>>,
+        param_index: &mut usize,
+    ) -> String {
+        let mut placeholder = |value: Box<dyn rusqlite::ToSql>| {
+            params.push(value);
+            *param_index += 1;
+            format!("?{}", *param_index - 1)
+        };
+
+        match condition {
+            QueryCondition::And { conditions } | QueryCondition::Or { conditions } if conditions.is_empty() => {
+                if matches!(condition, QueryCondition::And { .. }) { "1=1" } else { "1=0" }.to_string()
+            }
+            QueryCondition::And { conditions } | QueryCondition::Or { conditions } => {
+                let separator = if matches!(condition, QueryCondition::And { .. }) { " AND " } else { " OR " };
+                let parts: Vec<String> = conditions
+                    .iter()
+                    .map(|condition| Self::condition_sql(condition, params, param_index))
+                    .collect();
+                format!("({})", parts.join(separator))
+            }
+            QueryCondition::Not { condition } => {
+                format!("NOT {}", Self::condition_sql(condition, params, param_index))
+            }
+            QueryCondition::Text { text } => {
+                let pattern = format!("%{}%", Self::escape_like(text));
+                let filename = placeholder(Box::new(pattern.clone()));
+                let title = placeholder(Box::new(pattern.clone()));
+                let description = placeholder(Box::new(pattern));
+                format!(
+                    "(filename LIKE {} ESCAPE '\\' OR COALESCE(title, '') LIKE {} ESCAPE '\\' OR COALESCE(description, '') LIKE {} ESCAPE '\\')",
+                    filename, title, description
+                )
+            }
+            QueryCondition::Category { path } => {
+                let exact = placeholder(Box::new(path.clone()));
+                let descendants = placeholder(Box::new(format!("{}/%", Self::escape_like(path))));
+                format!("COALESCE(category = {} OR category LIKE {} ESCAPE '\\', 0)", exact, descendants)
+            }
+            QueryCondition::Tag { path } => {
+                let exact = placeholder(Box::new(path.clone()));
+                let descendants = placeholder(Box::new(format!("{}/%", Self::escape_like(path))));
+                format!(
+                    "EXISTS (SELECT 1 FROM recording_tags rt WHERE rt.recording_id = recordings.id AND (rt.tag = {} OR rt.tag LIKE {} ESCAPE '\\'))",
+                    exact, descendants
+                )
+            }
+            QueryCondition::ColorLabel { label } => {
+                format!("COALESCE(color_label = {}, 0)", placeholder(Box::new(label.as_str())))
+            }
+            QueryCondition::HasTranscription { value } => format!(
+                "{}EXISTS (SELECT 1 FROM transcriptions t WHERE t.recording_id = recordings.id)",
+                if *value { "" } else { "NOT " }
+            ),
+            QueryCondition::HasSummary { value } => format!(
+                "{}EXISTS (SELECT 1 FROM transcriptions t JOIN summaries s ON s.transcription_id = t.id WHERE t.recording_id = recordings.id)",
+                if *value { "" } else { "NOT " }
+            ),
+            QueryCondition::TranscriptionStatus { status } => {
+                let status_sql = match status {
+                    TranscriptionStatusFilter::Pending => format!("t.status = {}", placeholder(Box::new("pending"))),
+                    TranscriptionStatusFilter::Processing => format!("t.status = {}", placeholder(Box::new("processing"))),
+                    TranscriptionStatusFilter::Completed => format!("t.status = {}", placeholder(Box::new("completed"))),
+                    TranscriptionStatusFilter::Failed => "t.status LIKE 'failed:%'".to_string(),
+                };
+                format!(
+                    "EXISTS (SELECT 1 FROM transcriptions t WHERE t.recording_id = recordings.id AND {})",
+                    status_sql
+                )
+            }
+        }
+    }
+
+    fn build_search_sql(query: &RecordingQuery, with_status: bool) -> (String, Vec<Box<dyn rusqlite::ToSql>>) {
+        let mut sql = String::from(
+            "SELECT id, filename, file_path, title, description, category, tags, duration, file_size, sample_rate, channels, color_label, archived, created_at, updated_at"
+        );
+        if with_status {
+            // 録音ごとに最新の書き起こしと、その録音で最新の要約の状態を結合する
+            sql.push_str(
+                ", lt.status AS latest_transcription_status, ls.status AS latest_summary_status
+                 FROM recordings
+                 LEFT JOIN (
+                     SELECT recording_id, status FROM (
+                         SELECT recording_id, status,
+                                ROW_NUMBER() OVER (PARTITION BY recording_id ORDER BY created_at DESC, id DESC) AS rn
+                         FROM transcriptions
+                     ) WHERE rn = 1
+                 ) lt ON lt.recording_id = recordings.id
+                 LEFT JOIN (
+                     SELECT recording_id, status FROM (
+                         SELECT t.recording_id, s.status,
+                                ROW_NUMBER() OVER (PARTITION BY t.recording_id ORDER BY s.created_at DESC, s.id DESC) AS rn
+                         FROM summaries s JOIN transcriptions t ON t.id = s.transcription_id
+                     ) WHERE rn = 1
+                 ) ls ON ls.recording_id = recordings.id
+                 WHERE 1=1",
+            );
+        } else {
+            sql.push_str(" FROM recordings WHERE 1=1");
+        }
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        let mut param_index = 1;
+
+        if !query.include_archived {
+            sql.push_str(" AND archived = 0");
+        }
+
+        // Search text filter (filename, title, description)
+        if let Some(search_text) = &query.search_text {
+            sql.push_str(&format!(" AND (filename LIKE ?{} OR title LIKE ?{} OR description LIKE ?{})", 
+                                param_index, param_index + 1, param_index + 2));
+            let search_pattern = format!("%{}%", search_text);
+            params.push(Box::new(search_pattern.clone()));
+            params.push(Box::new(search_pattern.clone()));
+            params.push(Box::new(search_pattern));
+            param_index += 3;
+        }
+
+        // Category filter
+        if let Some(category) = &query.category {
+            sql.push_str(&format!(" AND category = ?{}", param_index));
+            params.push(Box::new(category.clone()));
+            param_index += 1;
+        }
+
+        // Category hierarchy filter (the node itself or its descendants)
+        if let Some(category_path) = query.category_path.as_ref().filter(|p| !p.is_empty()) {
+            sql.push_str(&format!(
+                " AND (category = ?{} OR category LIKE ?{} ESCAPE '\\')",
+                param_index,
+                param_index + 1
+            ));
+            params.push(Box::new(category_path.clone()));
+            params.push(Box::new(format!("{}/%", Self::escape_like(category_path))));
+            param_index += 2;
+        }
+
+        // Tags filter (all tags must match)
+        for tag in &query.tags {
+            sql.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM recording_tags rt WHERE rt.recording_id = recordings.id AND rt.tag = ?{})",
+                param_index
+            ));
+            params.push(Box::new(tag.clone()));
+            param_index += 1;
+        }
+
+        // Tag hierarchy filter (all paths must match)
+        for tag_path in query.tag_paths.iter().filter(|p| !p.is_empty()) {
+            sql.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM recording_tags rt WHERE rt.recording_id = recordings.id AND (rt.tag = ?{} OR rt.tag LIKE ?{} ESCAPE '\\'))",
+                param_index,
+                param_index + 1
+            ));
+            params.push(Box::new(tag_path.clone()));
+            params.push(Box::new(format!("{}/%", Self::escape_like(tag_path))));
+            param_index += 2;
+        }
+        // Color label filter (any of the labels)
+        if !query.color_labels.is_empty() {
+            let placeholders: Vec<String> = (0..query.color_labels.len())
+                .map(|offset| format!("?{}", param_index + offset))
+                .collect();
+            sql.push_str(&format!(" AND color_label IN ({})", placeholders.join(", ")));
+            for label in &query.color_labels {
+                params.push(Box::new(label.as_str()));
+            }
+            param_index += query.color_labels.len();
+        }
+
+        // Date range filter
+        if let Some(date_from) = &query.date_from {
+            sql.push_str(&format!(" AND created_at >= ?{}", param_index));
+            params.push(Box::new(date_from.to_rfc3339()));
+            param_index += 1;
+        }
+
+        if let Some(date_to) = &query.date_to {
+            sql.push_str(&format!(" AND created_at <= ?{}", param_index));
+            params.push(Box::new(date_to.to_rfc3339()));
+            param_index += 1;
+        }
+
+        // Duration range filter
+        if let Some(min_duration) = query.min_duration {
+            sql.push_str(&format!(" AND duration >= ?{}", param_index));
+            params.push(Box::new(min_duration));
+            param_index += 1;
+        }
+
+        if let Some(max_duration) = query.max_duration {
+            sql.push_str(&format!(" AND duration <= ?{}", param_index));
+            params.push(Box::new(max_duration));
+            param_index += 1;
+        }
+
+        // Silence ratio filter (only analyzed recordings match)
+        if let Some(min_silence_ratio) = query.min_silence_ratio {
+            sql.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM speech_metrics sm WHERE sm.recording_id = recordings.id AND sm.silence_ratio >= ?{})",
+                param_index
+            ));
+            params.push(Box::new(min_silence_ratio));
+            param_index += 1;
+        }
+
+        if let Some(max_silence_ratio) = query.max_silence_ratio {
+            sql.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM speech_metrics sm WHERE sm.recording_id = recordings.id AND sm.silence_ratio <= ?{})",
+                param_index
+            ));
+            params.push(Box::new(max_silence_ratio));
+            param_index += 1;
+        }
+
+        // Transcription / summary predicates
+        if let Some(value) = query.has_transcription {
+            let condition = QueryCondition::HasTranscription { value };
+            sql.push_str(&format!(" AND {}", Self::condition_sql(&condition, &mut params, &mut param_index)));
+        }
+
+        if let Some(value) = query.has_summary {
+            let condition = QueryCondition::HasSummary { value };
+            sql.push_str(&format!(" AND {}", Self::condition_sql(&condition, &mut params, &mut param_index)));
+        }
+
+        // Transcription status filter (any of the statuses)
+        if !query.transcription_statuses.is_empty() {
+            let condition = QueryCondition::Or {
+                conditions: query
+                    .transcription_statuses
+                    .iter()
+                    .map(|&status| QueryCondition::TranscriptionStatus { status })
+                    .collect(),
+            };
+            sql.push_str(&format!(" AND {}", Self::condition_sql(&condition, &mut params, &mut param_index)));
+        }
+
+        // Boolean combination of conditions
+        if let Some(condition) = &query.condition {
+            sql.push_str(&format!(" AND {}", Self::condition_sql(condition, &mut params, &mut param_index)));
+        }
+
+        // Sort by
+        let sort_column = match query.sort_by {
+            SortBy::CreatedAt => "created_at",
+            SortBy::UpdatedAt => "updated_at", 
+            SortBy::Filename => "filename",
+            SortBy::Duration => "duration",
+            SortBy::FileSize => "file_size",
+        };
+
+        let sort_direction = match query.sort_order {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        };
+
+        sql.push_str(&format!(" ORDER BY {} {}", sort_column, sort_direction));
+
+        // Limit and offset
+        if let Some(limit) = query.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+            if let Some(offset) = query.offset {
+                sql.push_str(&format!(" OFFSET {}", offset));
+            }
+        }
+
+        (sql, params)
+    }
+
+    pub async fn get_recording_stats(&self) -> AppResult<RecordingStats> {
+        if let Some(stats) = self.cache.stats() {
+            return Ok(stats);
+        }
+
+        let generation = self.cache.generation();
+        let conn = self.conn()?;
+        
+        // Total counts and sizes
+        let (total_count, total_duration, total_size): (i64, i64, i64) = conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(duration), 0), COALESCE(SUM(file_size), 0) FROM recordings WHERE archived = 0",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        )?;
+
+        // Recent count (last 7 days)
+        let seven_days_ago = Utc::now() - chrono::Duration::days(7);
+        let recent_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM recordings WHERE archived = 0 AND created_at >= ?1",
+            params![seven_days_ago.to_rfc3339()],
+            |row| row.get(0)
+        )?;
+
+        // Category stats
+        let mut stmt = conn.prepare(
+            "SELECT category, COUNT(*), COALESCE(SUM(duration), 0) 
+             FROM recordings 
+             WHERE category IS NOT NULL AND archived = 0
+             GROUP BY category 
+             ORDER BY COUNT(*) DESC"
+        )?;
+
+        let categories = stmt.query_map([], |row| {
+            Ok(CategoryStats {
+                name: row.get(0)?,
+                count: row.get(1)?,
+                total_duration: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let stats = RecordingStats {
+            total_count,
+            total_duration,
+            total_size,
+            categories,
+            recent_count,
+        };
+        self.cache.store_stats(generation, &stats);
+
+        Ok(stats)
+    }
+
+    /// 複数の録音をまとめてアーカイブ（または解除）し、状態が変わった件数を返す
+    pub async fn set_archived(&self, recording_ids: &[String], archived: bool) -> AppResult<usize> {
+        let mut conn = self.conn()?;
+        let updated_at = Utc::now().to_rfc3339();
+        let tx = conn.transaction()?;
+        let mut updated = 0;
+        {
+            let mut stmt = tx.prepare("UPDATE recordings SET archived = ?2, updated_at = ?3 WHERE id = ?1 AND archived != ?2")?;
+            for id in recording_ids {
+                updated += stmt.execute(params![id, archived, updated_at])?;
+            }
+        }
+        tx.commit()?;
+        self.cache.invalidate();
+        Ok(updated)
+    }
+
+    /// 複数の録音の色ラベルをまとめて設定（None で解除）し、更新した件数を返す
+    pub async fn set_color_labels(&self, recording_ids: &[String], label: Option<ColorLabel>) -> AppResult<usize> {
+        let mut conn = self.conn()?;
+        let updated_at = Utc::now().to_rfc3339();
+        let tx = conn.transaction()?;
+        let mut updated = 0;
+        {
+            let mut stmt = tx.prepare("UPDATE recordings SET color_label = ?2, updated_at = ?3 WHERE id = ?1")?;
+            for id in recording_ids {
+                updated += stmt.execute(params![id, label.map(|label| label.as_str()), updated_at])?;
+            }
+        }
+        tx.commit()?;
+        self.cache.invalidate();
+        Ok(updated)
+    }
+
+    /// 録音ごとのカテゴリ（階層集計用）
+    pub async fn get_category_assignments(&self) -> AppResult<Vec<TaxonomyAssignment>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, category, COALESCE(duration, 0) FROM recordings WHERE category IS NOT NULL AND category != ''"
+        )?;
+        let assignments = stmt.query_map([], |row| {
+            Ok(TaxonomyAssignment {
+                recording_id: row.get(0)?,
+                path: row.get(1)?,
+                duration: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(assignments)
+    }
+
+    /// 録音ごとのタグ（階層集計用）
+    pub async fn get_tag_assignments(&self) -> AppResult<Vec<TaxonomyAssignment>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT r.id, rt.tag, COALESCE(r.duration, 0) FROM recording_tags rt
+             JOIN recordings r ON r.id = rt.recording_id"
+        )?;
+        let assignments = stmt.query_map([], |row| {
+            Ok(TaxonomyAssignment {
+                recording_id: row.get(0)?,
+                path: row.get(1)?,
+                duration: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(assignments)
+    }
+
     pub async fn get_all_categories(&self) -> AppResult<Vec<String>> {
-        let conn = self.conn.lock().await;
+        if let Some(categories) = self.cache.categories() {
+            return Ok(categories);
+        }
+
+        let generation = self.cache.generation();
+        let conn = self.conn()?;
         let mut stmt = conn.prepare(
             "SELECT DISTINCT category FROM recordings WHERE category IS NOT NULL ORDER BY category"
         )?;
@@ -779,31 +2971,24 @@ impl Database {
             Ok(category)
         })?
         .collect::<Result<Vec<_>, _>>()?;
+        self.cache.store_categories(generation, &categories);
 
         Ok(categories)
     }
 
     pub async fn get_all_tags(&self) -> AppResult<Vec<String>> {
-        let conn = self.conn.lock().await;
-        let mut stmt = conn.prepare("SELECT tags FROM recordings WHERE tags IS NOT NULL AND tags != '[]'")?;
-
-        let mut all_tags = std::collections::HashSet::new();
-        let rows = stmt.query_map([], |row| {
-            let tags_json: String = row.get(0)?;
-            Ok(tags_json)
-        })?;
-
-        for row in rows {
-            let tags_json = row?;
-            if let Ok(tags) = serde_json::from_str::<Vec<String>>(&tags_json) {
-                for tag in tags {
-                    all_tags.insert(tag);
-                }
-            }
+        if let Some(tags) = self.cache.tags() {
+            return Ok(tags);
         }
 
-        let mut tags: Vec<String> = all_tags.into_iter().collect();
-        tags.sort();
+        let generation = self.cache.generation();
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare("SELECT DISTINCT tag FROM recording_tags ORDER BY tag")?;
+
+        let tags = stmt.query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        self.cache.store_tags(generation, &tags);
+
         Ok(tags)
     }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+use crate::services::i18n::tr;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -6,6 +7,9 @@ pub enum AppError {
     #[error("Database error: {0}")]
     Database(#[from] rusqlite::Error),
 
+    #[error("Database pool error: {0}")]
+    Pool(#[from] r2d2::Error),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -59,16 +63,60 @@ pub enum AppError {
     
     #[error("LLM configuration error: {message}")]
     LLMConfigError { message: String },
+
+    #[error("Integration error: {message}")]
+    Integration { message: String },
+
+    #[error("Audio conversion error: {message}")]
+    AudioConversion { message: String },
+
+    #[error("Playback error: {message}")]
+    Playback { message: String },
+
+    #[error("Transcription already in progress for recording {recording_id} (job {job_id})")]
+    TranscriptionInProgress { recording_id: String, job_id: String },
+}
+
+impl AppError {
+    /// ユーザー向けのメッセージ（現在のロケールで表示）
+    ///
+    /// 本番環境ではパス関連の詳細なエラー情報を隠蔽する。
+    pub fn localized(&self) -> String {
+        match self {
+            AppError::Database(e) => tr("error.database", &[("detail", e)]),
+            AppError::Pool(e) => tr("error.pool", &[("detail", e)]),
+            AppError::Io(e) => tr("error.io", &[("detail", e)]),
+            AppError::Serialization(e) => tr("error.serialization", &[("detail", e)]),
+            AppError::Uuid(e) => tr("error.uuid", &[("detail", e)]),
+            AppError::Recording { message } => tr("error.recording", &[("message", message)]),
+            AppError::FileNotFound { path } => tr("error.file_not_found", &[("path", path)]),
+            AppError::InvalidOperation { message } => tr("error.invalid_operation", &[("message", message)]),
+            AppError::PermissionDenied { .. } => tr("error.permission_denied", &[]),
+            AppError::InvalidPath { .. } => tr("error.invalid_path", &[]),
+            AppError::ValidationError { message } => tr("error.validation", &[("message", message)]),
+            AppError::TranscriptionFailed { message } => tr("error.transcription_failed", &[("message", message)]),
+            AppError::WhisperService { message } => tr("error.whisper_service", &[("message", message)]),
+            AppError::WhisperInit { message } => tr("error.whisper_init", &[("message", message)]),
+            AppError::WhisperNotInitialized { message } => tr("error.whisper_not_initialized", &[("message", message)]),
+            AppError::Reqwest(e) => tr("error.http", &[("detail", e)]),
+            AppError::LLMError { message } => tr("error.llm", &[("message", message)]),
+            AppError::LLMConnectionError { message } => tr("error.llm_connection", &[("message", message)]),
+            AppError::LLMTimeout { message } => tr("error.llm_timeout", &[("message", message)]),
+            AppError::LLMConfigError { message } => tr("error.llm_config", &[("message", message)]),
+            AppError::Integration { message } => tr("error.integration", &[("message", message)]),
+            AppError::AudioConversion { message } => tr("error.audio_conversion", &[("message", message)]),
+            AppError::Playback { message } => tr("error.playback", &[("message", message)]),
+            AppError::TranscriptionInProgress { recording_id, job_id } => tr(
+                "error.transcription_in_progress",
+                &[("recording_id", recording_id), ("job_id", job_id)],
+            ),
+        }
+    }
 }
 
 impl From<AppError> for String {
     fn from(error: AppError) -> Self {
-        // 本番環境では詳細なエラー情報を隠蔽
-        match error {
-            AppError::InvalidPath { .. } => "Invalid file path".to_string(),
-            AppError::PermissionDenied { .. } => "Access denied".to_string(),
-            _ => error.to_string(),
-        }
+        error.localized()
     }
 }
 
@@ -195,7 +243,7 @@ pub fn validate_audio_format(file_path: &PathBuf) -> AppResult<()> {
             message: "File has no extension".to_string(),
         })?;
     
-    let allowed_extensions = ["wav", "mp3", "m4a", "flac", "ogg"];
+    let allowed_extensions = ["wav", "mp3", "m4a", "mp4", "flac", "ogg", "opus"];
     if !allowed_extensions.iter().any(|&ext| ext.eq_ignore_ascii_case(extension)) {
         return Err(AppError::ValidationError {
             message: format!("Unsupported audio format: {}", extension),
@@ -4,11 +4,11 @@ pub mod errors;
 pub mod models;
 pub mod services;
 
-use crate::commands::{*, file_management, llm, streaming, model_management, model_settings, model_downloader};
+use crate::commands::{*, file_management, llm, streaming, model_management, model_settings, model_downloader, api_server, phone_mic, voice_memo, control_server, recording_profile, whisper_acceleration, whisper_backend, silence_trim, input_gain, noise_suppression, sleep_prevention, loudness, playback, audio_edit, tray, import, integrations, jobs, locale, demo_mode, digest_schedule, storage, notes, workspaces, chapters, captions, live_transcription, action_items, keyword_alerts, corrections, analytics};
 use crate::database::Database;
-use crate::services::{RecordingService, WhisperService, LLMModelManager, ModelSettingsManager, ModelDownloader};
+use crate::services::{RecordingService, WhisperService, LLMModelManager, ModelSettingsManager, ModelDownloader, OllamaPool, AppSettingsManager, ApiServer, ApiServerState, GrpcServer, CaptionSocketServer, ControlServer, PhoneMicServer, JobQueue, Summarizer, SummarizationStatusRegistry, LiveCaptionHub, LiveTranscriber, RollingSummarizer};
 use std::sync::Arc;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 use tokio::sync::Mutex;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -30,21 +30,27 @@ pub fn run() {
                     .expect("Failed to create app data directory");
             }
 
+            // 選択中のワークスペース（データベース・録音・設定はワークスペースごとに分離）
+            let workspace_manager = services::WorkspaceManager::load(&app_data_dir)
+                .expect("Failed to load workspaces");
+            let workspace_paths = workspace_manager.active_paths()
+                .expect("Failed to prepare workspace directory");
+            log::info!("🗂️ Using workspace {}", workspace_manager.active().name);
+            services::credentials::set_workspace(&workspace_manager.active().id);
+            let workspace_manager = Arc::new(Mutex::new(workspace_manager));
+
             // データベースファイルパス
-            let db_path = app_data_dir.join("recordings.db");
+            let db_path = workspace_paths.db_path.clone();
             
             // 録音ファイル保存ディレクトリ
-            let recordings_dir = app_data_dir.join("recordings");
-
-            // データベースを初期化（LLM用のMutex包装版）
-            let database = Arc::new(Mutex::new(Database::new(&db_path).expect("Failed to initialize database")));
+            let recordings_dir = workspace_paths.recordings_dir.clone();
 
-            // 録音サービス用のデータベース（独立インスタンス）
-            let recording_db = Arc::new(Database::new(&db_path).expect("Failed to initialize recording database"));
+            // データベースを初期化（コネクションプール。全サービスで共有）
+            let database = Arc::new(Database::new(&db_path).expect("Failed to initialize database"));
             
             // 録音サービスを初期化
             let recording_service = Arc::new(
-                RecordingService::new(recording_db, recordings_dir.clone())
+                RecordingService::new(database.clone(), recordings_dir.clone())
                     .expect("Failed to initialize recording service")
             );
 
@@ -54,71 +60,666 @@ pub fn run() {
             // Whisperサービスを初期化（セキュリティ強化：許可されたディレクトリを指定）
             let whisper_service = Arc::new(WhisperService::new(whisper_model_path, recordings_dir));
 
+            // キーワード監視（どの経路で作成された書き起こしも走査し、検出を通知）
+            {
+                let mut receiver = database.subscribe_transcriptions();
+                let database = database.clone();
+                let app_handle = app.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        match receiver.recv().await {
+                            Ok(transcription) => {
+                                match services::keyword_alerts::scan_transcription(&database, &transcription).await {
+                                    Ok(alerts) => {
+                                        for alert in alerts {
+                                            if let Err(e) = app_handle.emit(services::keyword_alerts::KEYWORD_ALERT_EVENT, alert) {
+                                                log::warn!("⚠️ Failed to emit keyword alert: {}", e);
+                                            }
+                                        }
+                                    }
+                                    Err(e) => log::warn!("⚠️ Keyword scan failed for transcription {}: {}", transcription.id, e),
+                                }
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                                log::warn!("⚠️ Keyword watcher lagged, skipped {} transcriptions", skipped);
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+
+            // システムトレイ（録音の開始・一時停止・停止と録音中の表示）
+            if let Err(e) = tray::create_tray(app.app_handle(), recording_service.clone()) {
+                log::warn!("⚠️ Failed to create system tray: {}", e);
+            }
+
+            // 録音の進捗ハートビートをフロントエンドとトレイの表示へ転送
+            {
+                let mut receiver = recording_service.subscribe_progress();
+                let app_handle = app.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        match receiver.recv().await {
+                            Ok(progress) => {
+                                tray::apply_tray_state(&app_handle, &services::TrayState::from_progress(Some(&progress)));
+                                if let Err(e) = app_handle.emit(services::recording_progress::RECORDING_PROGRESS_EVENT, progress) {
+                                    log::warn!("⚠️ Failed to emit recording progress: {}", e);
+                                }
+                            }
+                            // 古い進捗は捨ててよい
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+
+            // Whisperモデルのダウンロード進捗をフロントエンドへ転送
+            {
+                let mut receiver = whisper_service.subscribe_download_progress();
+                let app_handle = app.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        match receiver.recv().await {
+                            Ok(progress) => {
+                                if let Err(e) = app_handle.emit(services::whisper_download_progress::WHISPER_DOWNLOAD_PROGRESS_EVENT, progress) {
+                                    log::warn!("⚠️ Failed to emit model download progress: {}", e);
+                                }
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+
             // LLMモデル管理サービスを初期化
             let llm_model_manager = Arc::new(Mutex::new(LLMModelManager::new()));
 
             // モデル設定管理サービスを初期化
-            let model_settings_path = app_data_dir.join("model_settings.json");
+            let model_settings_path = workspace_paths.model_settings_path.clone();
             let model_settings_manager = ModelSettingsManager::new(model_settings_path);
             
             // 設定の読み込みは後でランタイム時に行う
             let model_settings_manager = Arc::new(Mutex::new(model_settings_manager));
 
+            // 要約の共通経路（画面・ジョブキュー・REST/gRPC で共有）
+            let ollama_pool = Arc::new(OllamaPool::new());
+            let summarizer = Arc::new(Summarizer::new(
+                database.clone(),
+                model_settings_manager.clone(),
+                llm_model_manager.clone(),
+                ollama_pool.clone(),
+            ));
+
+            // バックグラウンドジョブキュー（中断ジョブを再投入してワーカー起動）
+            let job_queue = Arc::new(JobQueue::new(database.clone(), whisper_service.clone(), summarizer.clone()));
+            {
+                let job_queue = job_queue.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = job_queue.start(2).await {
+                        log::error!("❌ Failed to start job queue: {}", e);
+                    }
+                });
+            }
+
+            // 要約の進捗レジストリ（リロード後の状態復元用）
+            let summarization_status = Arc::new(SummarizationStatusRegistry::new());
+
+            // ライブ字幕（フローティング字幕ウィンドウ向けに専用イベントへ転送し、途中経過要約にも蓄積）
+            let live_captions = Arc::new(LiveCaptionHub::new());
+            let rolling_summarizer = Arc::new(RollingSummarizer::new());
+            let live_transcriber = Arc::new(LiveTranscriber::new());
+            {
+                let mut receiver = live_captions.subscribe();
+                let app_handle = app.app_handle().clone();
+                let rolling_summarizer = rolling_summarizer.clone();
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        match receiver.recv().await {
+                            Ok(event) => {
+                                rolling_summarizer.record_caption(&event);
+                                if let Err(e) = app_handle.emit(services::live_captions::LIVE_CAPTION_EVENT, event) {
+                                    log::warn!("⚠️ Failed to emit live caption: {}", e);
+                                }
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                                log::warn!("⚠️ Live caption forwarder lagged, skipped {} events", skipped);
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+
             // モデルダウンロードサービスを初期化
             let model_downloader = Arc::new(Mutex::new(ModelDownloader::new()));
 
+            // アプリ設定を読み込み
+            let app_settings_path = workspace_paths.app_settings_path.clone();
+            let mut app_settings_manager = AppSettingsManager::new(app_settings_path);
+            if let Err(e) = tauri::async_runtime::block_on(app_settings_manager.load_settings()) {
+                log::warn!("⚠️ Failed to load app settings, using defaults: {}", e);
+            }
+            services::i18n::set_locale(app_settings_manager.get_settings().locale);
+            services::demo_mode::set_enabled(
+                app_settings_manager.get_settings().demo_mode || services::demo_mode::requested_by_env(),
+            );
+            let api_server_settings = app_settings_manager.get_settings().api_server.clone();
+            let grpc_server_settings = app_settings_manager.get_settings().grpc_server.clone();
+            let caption_socket_settings = app_settings_manager.get_settings().caption_socket.clone();
+            let control_server_settings = app_settings_manager.get_settings().control_server.clone();
+            whisper_service.set_acceleration_enabled(app_settings_manager.get_settings().whisper_acceleration.enabled);
+            tauri::async_runtime::block_on(whisper_service.set_backend(app_settings_manager.get_settings().whisper_backend.clone()));
+            whisper_service.set_silence_trim(app_settings_manager.get_settings().silence_trim.clone());
+            if let Err(e) = tauri::async_runtime::block_on(
+                recording_service.apply_recording_profile(&app_settings_manager.get_settings().recording_profile),
+            ) {
+                log::warn!("⚠️ Invalid capture source in settings, using the microphone: {}", e);
+            }
+            tauri::async_runtime::block_on(recording_service.set_input_gain(app_settings_manager.get_settings().input_gain));
+            tauri::async_runtime::block_on(recording_service.set_noise_suppression(app_settings_manager.get_settings().noise_suppression));
+            recording_service.set_loudness(app_settings_manager.get_settings().loudness);
+            tauri::async_runtime::block_on(recording_service.set_sleep_prevention(app_settings_manager.get_settings().sleep_prevention));
+            tauri::async_runtime::block_on(
+                recording_service.set_min_free_space(app_settings_manager.get_settings().storage.min_free_bytes),
+            );
+            let app_settings_manager = Arc::new(Mutex::new(app_settings_manager));
+
+            // 録音中に空き容量が下限を下回ったら録音を止めて保存する
+            {
+                let mut receiver = recording_service.subscribe_low_disk_space();
+                let recording_service = recording_service.clone();
+                let app_handle = app.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        match receiver.recv().await {
+                            Ok(mut notice) => {
+                                if notice.stopped {
+                                    match recording_service.stop_recording().await {
+                                        Ok(recording) => {
+                                            log::info!("💽 Recording saved after running out of disk space: {}", recording.id);
+                                            notice.recording_id = Some(recording.id);
+                                        }
+                                        Err(e) => log::error!("❌ Failed to save recording stopped for low disk space: {}", e),
+                                    }
+                                    tray::refresh_tray(&app_handle, &recording_service).await;
+                                }
+                                if let Err(e) = app_handle.emit(services::disk_space::LOW_DISK_SPACE_EVENT, notice) {
+                                    log::warn!("⚠️ Failed to emit low disk space notice: {}", e);
+                                }
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+
+            // 録音を止めたら（画面・トレイ・空き容量不足などの経路を問わず）ライブ書き起こしを保存する
+            {
+                let mut receiver = recording_service.subscribe_stopped_recordings();
+                let app_handle = app.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        match receiver.recv().await {
+                            Ok(stopped) => live_transcription::save_after_stop(&app_handle, stopped),
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+
+            // 録音の再生位置を画面へ伝える（書き起こしの表示を再生位置に合わせる）
+            let playback_service = Arc::new(services::PlaybackService::new());
+            {
+                let mut receiver = playback_service.subscribe();
+                let app_handle = app.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        match receiver.recv().await {
+                            Ok(status) => {
+                                if let Err(e) = app_handle.emit(services::playback::PLAYBACK_EVENT, status) {
+                                    log::warn!("⚠️ Failed to emit playback position: {}", e);
+                                }
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+
+            // 録音中の入力デバイスの取り外しと再開を画面へ伝える
+            {
+                let mut receiver = recording_service.subscribe_device_changes();
+                let app_handle = app.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        match receiver.recv().await {
+                            Ok(event) => {
+                                if let Err(e) = app_handle.emit(services::device_fallback::CAPTURE_DEVICE_EVENT, event) {
+                                    log::warn!("⚠️ Failed to emit capture device change: {}", e);
+                                }
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+
+            // 前回の起動中に落ちて残った録音の一時ファイルを録音として登録する（終わるまで録音は始まらない）
+            {
+                let recording_service = recording_service.clone();
+                let app_handle = app.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    match recording_service.recover_orphaned_recordings().await {
+                        Ok(recordings) if !recordings.is_empty() => {
+                            log::info!("🩹 Recovered {} interrupted recording(s)", recordings.len());
+                            if let Err(e) = app_handle.emit(services::recording_spool::RECORDINGS_RECOVERED_EVENT, recordings) {
+                                log::warn!("⚠️ Failed to emit recovered recordings: {}", e);
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(e) => log::warn!("⚠️ Recording recovery failed: {}", e),
+                    }
+                });
+            }
+
+            // 録音ディレクトリの容量チェック（しきい値を新たに超えたときだけ警告イベントを送る）
+            {
+                let database = database.clone();
+                let recording_service = recording_service.clone();
+                let app_settings_manager = app_settings_manager.clone();
+                let app_handle = app.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let monitor = services::storage_quota::StorageQuotaMonitor::new();
+                    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+                        services::storage_quota::STORAGE_CHECK_INTERVAL_SECS,
+                    ));
+                    loop {
+                        interval.tick().await;
+                        let settings = app_settings_manager.lock().await.get_settings().storage.clone();
+                        if settings.quota_bytes.is_none() {
+                            continue;
+                        }
+                        match services::storage_quota::storage_report(&database, recording_service.recordings_dir(), &settings).await {
+                            Ok(report) => {
+                                if monitor.should_warn(&report.usage) {
+                                    log::warn!("💽 Recordings directory usage crossed {:?}", report.usage.crossed_threshold);
+                                    if let Err(e) = app_handle.emit(services::storage_quota::STORAGE_WARNING_EVENT, report) {
+                                        log::warn!("⚠️ Failed to emit storage warning: {}", e);
+                                    }
+                                }
+                            }
+                            Err(e) => log::warn!("⚠️ Storage check failed: {}", e),
+                        }
+                    }
+                });
+            }
+
+            // 監視フォルダの自動取り込み（新しい音声ファイルを登録し、ルールに従って書き起こし・要約を投入）
+            {
+                let database = database.clone();
+                let job_queue = job_queue.clone();
+                let recording_service = recording_service.clone();
+                let app_settings_manager = app_settings_manager.clone();
+                let app_handle = app.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+                        services::watched_folders::WATCH_INTERVAL_SECS,
+                    ));
+                    loop {
+                        interval.tick().await;
+                        let rules = app_settings_manager.lock().await.get_settings().watched_folders.clone();
+                        if rules.is_empty() {
+                            continue;
+                        }
+                        let imports = services::watched_folders::scan_all(&database, &job_queue, &rules, recording_service.recordings_dir()).await;
+                        for import in imports {
+                            recording_service.prepare_for_transcription(&import.recording).await;
+                            if let Err(e) = app_handle.emit(services::watched_folders::WATCHED_IMPORT_EVENT, import) {
+                                log::warn!("⚠️ Failed to emit watched folder import: {}", e);
+                            }
+                        }
+                    }
+                });
+            }
+
+            // 定期ダイジェスト（予定時刻を過ぎたら直前の期間のダイジェストを作って届ける）
+            {
+                let app_settings_manager = app_settings_manager.clone();
+                let summarizer = summarizer.clone();
+                let app_handle = app.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+                        services::digest_schedule::DIGEST_SCHEDULE_CHECK_INTERVAL_SECS,
+                    ));
+                    loop {
+                        interval.tick().await;
+                        let schedule = app_settings_manager.lock().await.get_settings().digest_schedule.clone();
+                        let Some((from, to)) = services::digest_schedule::due_period(&schedule, &chrono::Local::now()) else {
+                            continue;
+                        };
+                        let Some(delivery) = schedule.delivery else {
+                            continue;
+                        };
+
+                        let run = services::digest_schedule::run_scheduled_digest(
+                            &summarizer,
+                            schedule.model_config,
+                            &delivery,
+                            from,
+                            to,
+                        )
+                        .await;
+
+                        // 失敗した回も実行済みにする（毎分作り直さないように）
+                        {
+                            let mut manager = app_settings_manager.lock().await;
+                            manager.update_settings(|settings| settings.digest_schedule.last_run_at = Some(to));
+                            if let Err(e) = manager.save_settings().await {
+                                log::warn!("⚠️ Failed to save digest schedule: {}", e);
+                            }
+                        }
+                        if let Err(e) = app_handle.emit(services::digest_schedule::SCHEDULED_DIGEST_EVENT, run) {
+                            log::warn!("⚠️ Failed to emit scheduled digest: {}", e);
+                        }
+                    }
+                });
+            }
+
+            // ローカルAPIサーバー（設定で有効な場合のみ起動）
+            let api_server = Arc::new(Mutex::new(ApiServer::new()));
+            if api_server_settings.enabled {
+                let api_server = api_server.clone();
+                let database = database.clone();
+                let recording_service = recording_service.clone();
+                let whisper_service = whisper_service.clone();
+                let summarizer = summarizer.clone();
+                tauri::async_runtime::spawn(async move {
+                    // 外部操作用サーバーと同じトークンで保護する
+                    let token = match services::control_server::load_or_create_token() {
+                        Ok(token) => token,
+                        Err(e) => {
+                            log::error!("❌ Failed to load token for the local API server: {}", e);
+                            return;
+                        }
+                    };
+                    let state = ApiServerState {
+                        db: database,
+                        recording_service,
+                        whisper_service,
+                        summarizer,
+                        token,
+                    };
+                    let mut server = api_server.lock().await;
+                    if let Err(e) = server.start(state, api_server_settings.port).await {
+                        log::error!("❌ Failed to start local API server: {}", e);
+                    }
+                });
+            }
+
+            // ローカルgRPCサーバー（設定で有効な場合のみ起動）
+            let grpc_server = Arc::new(Mutex::new(GrpcServer::new()));
+            if grpc_server_settings.enabled {
+                let grpc_server = grpc_server.clone();
+                let database = database.clone();
+                let recording_service = recording_service.clone();
+                let whisper_service = whisper_service.clone();
+                let summarizer = summarizer.clone();
+                tauri::async_runtime::spawn(async move {
+                    // 外部操作用サーバーと同じトークンで保護する
+                    let token = match services::control_server::load_or_create_token() {
+                        Ok(token) => token,
+                        Err(e) => {
+                            log::error!("❌ Failed to load token for the local gRPC server: {}", e);
+                            return;
+                        }
+                    };
+                    let state = ApiServerState {
+                        db: database,
+                        recording_service,
+                        whisper_service,
+                        summarizer,
+                        token,
+                    };
+                    let mut server = grpc_server.lock().await;
+                    if let Err(e) = server.start(state, &grpc_server_settings.bind_address, grpc_server_settings.port).await {
+                        log::error!("❌ Failed to start local gRPC server: {}", e);
+                    }
+                });
+            }
+
+            // ライブ字幕のWebSocket配信（設定で有効な場合のみ起動）
+            let caption_socket = Arc::new(Mutex::new(CaptionSocketServer::new()));
+            if caption_socket_settings.enabled {
+                let caption_socket = caption_socket.clone();
+                let live_captions = live_captions.clone();
+                tauri::async_runtime::spawn(async move {
+                    let mut server = caption_socket.lock().await;
+                    if let Err(e) = server.start(live_captions, caption_socket_settings.port).await {
+                        log::error!("❌ Failed to start caption WebSocket server: {}", e);
+                    }
+                });
+            }
+
+            // 外部ツールからの操作用サーバー（設定で有効な場合のみ起動）
+            let control_server = ControlServer::new();
+            {
+                let mut receiver = control_server.subscribe();
+                let app_handle = app.app_handle().clone();
+                let recording_service = recording_service.clone();
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        match receiver.recv().await {
+                            Ok(event) => {
+                                // 外部から録音を操作された場合もトレイの表示を合わせる
+                                tray::refresh_tray(&app_handle, &recording_service).await;
+                                if let Err(e) = app_handle.emit(services::control_server::CONTROL_EVENT, event) {
+                                    log::warn!("⚠️ Failed to emit external control event: {}", e);
+                                }
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+            let control_server = Arc::new(Mutex::new(control_server));
+            if control_server_settings.enabled {
+                let control_server = control_server.clone();
+                let database = database.clone();
+                let recording_service = recording_service.clone();
+                let job_queue = job_queue.clone();
+                tauri::async_runtime::spawn(async move {
+                    let token = match services::control_server::load_or_create_token() {
+                        Ok(token) => token,
+                        Err(e) => {
+                            log::error!("❌ Failed to load control server token: {}", e);
+                            return;
+                        }
+                    };
+                    let mut server = control_server.lock().await;
+                    if let Err(e) = server.start(database, recording_service, job_queue, token, control_server_settings.port).await {
+                        log::error!("❌ Failed to start control server: {}", e);
+                    }
+                });
+            }
+
+            // スマートフォンマイクの取り込みサーバー（ユーザーが開始するまで起動しない）
+            let phone_mic_server = PhoneMicServer::new();
+            {
+                let mut receiver = phone_mic_server.subscribe();
+                let app_handle = app.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        match receiver.recv().await {
+                            Ok(event) => {
+                                if let Err(e) = app_handle.emit(services::phone_mic::PHONE_MIC_EVENT, event) {
+                                    log::warn!("⚠️ Failed to emit phone mic event: {}", e);
+                                }
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+
             // サービスをアプリケーション状態に追加
             app.manage(database);
             app.manage(recording_service);
+            app.manage(playback_service);
             app.manage(whisper_service);
+            app.manage(summarizer);
             app.manage(llm_model_manager);
+            app.manage(summarization_status);
+            app.manage(live_captions);
+            app.manage(rolling_summarizer);
+            app.manage(live_transcriber);
             app.manage(model_settings_manager);
+            app.manage(ollama_pool);
             app.manage(model_downloader);
+            app.manage(app_settings_manager);
+            app.manage(api_server);
+            app.manage(grpc_server);
+            app.manage(caption_socket);
+            app.manage(control_server);
+            app.manage(Arc::new(Mutex::new(phone_mic_server)));
+            app.manage(Arc::new(services::voice_memo::VoiceMemoRecorder::new()));
+            app.manage(job_queue);
+            app.manage(workspace_manager);
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             start_recording,
             stop_recording,
+            pause_recording,
+            resume_recording,
             get_recordings,
             get_recording,
+            get_recording_segments,
+            get_recording_tracks,
             delete_recording,
             is_recording,
+            get_recording_progress,
             get_recordings_count,
             get_audio_devices,
+            recording_profile::get_recording_profile,
+            recording_profile::set_recording_profile,
+            recording_profile::get_capture_applications,
+            recording_profile::list_capturable_applications,
+            recording_profile::get_recording_qualities,
+            recording_profile::get_recording_formats,
             transcribe_recording,
+            transcribe_recordings_batch,
+            set_batch_transcription_workers,
+            get_active_transcriptions,
+            get_low_confidence_regions,
+            stop_snippet_recording,
+            rerecord_segment,
+            edit_transcript_segment,
+            get_transcript_edits,
             initialize_whisper,
             is_whisper_initialized,
+            whisper_acceleration::get_whisper_acceleration,
+            whisper_acceleration::get_acceleration_status,
+            whisper_acceleration::set_whisper_acceleration_enabled,
+            whisper_acceleration::benchmark_whisper_acceleration,
+            whisper_backend::get_whisper_backend,
+            whisper_backend::set_whisper_backend,
+            silence_trim::get_silence_trim_settings,
+            silence_trim::set_silence_trim_settings,
+            input_gain::get_input_gain,
+            input_gain::set_input_gain,
+            noise_suppression::get_noise_suppression,
+            noise_suppression::set_noise_suppression,
+            sleep_prevention::get_sleep_prevention,
+            sleep_prevention::set_sleep_prevention,
+            loudness::get_loudness_settings,
+            loudness::set_loudness_settings,
+            playback::play_recording,
+            playback::pause_playback,
+            playback::resume_playback,
+            playback::seek_playback,
+            playback::stop_playback,
+            playback::get_playback_status,
+            audio_edit::trim_recording,
+            audio_edit::delete_range,
+            tray::get_tray_state,
+            tray::update_tray_state,
+            check_transcription_environment,
+            estimate_transcription,
             // File management commands (Phase 2)
             file_management::get_all_recordings_fm,
             file_management::get_recording_by_id,
             file_management::search_recordings,
             file_management::update_recording_metadata,
             file_management::delete_recording_fm,
+            file_management::get_category_tree,
+            file_management::get_tag_tree,
+            file_management::set_recording_color_label,
+            file_management::archive_recordings,
+            file_management::unarchive_recordings,
             file_management::get_recording_stats,
             file_management::get_all_categories,
+            file_management::get_category_languages,
+            file_management::set_category_language,
             file_management::get_all_tags,
             file_management::get_transcriptions_by_recording,
             file_management::get_transcription_by_id,
             file_management::export_recording_data,
+            file_management::export_recording_html,
+            file_management::create_share_bundle,
+            file_management::export_summary_ical,
+            file_management::export_summary_pdf,
+            file_management::export_speaker_tracks,
+            file_management::convert_recording,
+            file_management::export_recording_redacted,
+            file_management::get_redacted_summary,
+            file_management::export_recording_anonymized,
+            file_management::get_anonymized_summary,
+            file_management::get_redaction_mappings,
+            file_management::clear_redaction_mappings,
+            file_management::get_waveform_peaks,
             file_management::get_recordings_count_fm,
             file_management::cleanup_orphaned_files,
             // LLM commands (Phase 3)
             llm::generate_summary,
+            llm::generate_digest,
+            digest_schedule::get_digest_schedule,
+            digest_schedule::set_digest_schedule,
+            digest_schedule::set_digest_smtp_password,
+            digest_schedule::run_digest_schedule_now,
             llm::get_summary_by_id,
             llm::get_summaries_for_transcription,
             llm::update_summary,
             llm::delete_summary,
+            llm::approve_summary,
+            llm::reopen_summary,
+            llm::set_summary_locked,
             llm::check_llm_connection,
+            llm::get_ollama_host_status,
             llm::get_default_llm_config,
             llm::validate_llm_config,
             llm::get_available_llm_providers,
             llm::get_provider_default_config,
             llm::test_summarization,
+            llm::compare_summary_models,
+            llm::preview_auto_model_selection,
             // Streaming commands (Phase 3)
             streaming::generate_summary_with_progress,
             streaming::cancel_summarization,
             streaming::get_summarization_status,
+            streaming::get_active_summarizations,
+            streaming::start_rolling_summary,
+            streaming::stop_rolling_summary,
+            streaming::get_rolling_summary,
             // Model Management commands (Phase 4)
             model_management::discover_available_models,
             model_management::get_cached_models,
@@ -137,6 +738,8 @@ pub fn run() {
             model_settings::remove_model_preference,
             model_settings::set_performance_priority,
             model_settings::set_auto_switch_enabled,
+            model_settings::set_provider_race,
+            model_settings::set_ollama_hosts,
             model_settings::get_optimal_model_for_use_case,
             model_settings::get_enabled_models_by_priority,
             model_settings::validate_model_settings,
@@ -157,7 +760,140 @@ pub fn run() {
             model_downloader::get_recommended_models_for_system,
             model_downloader::estimate_download_time,
             model_downloader::get_model_categories,
-            model_downloader::get_model_tags
+            model_downloader::get_model_tags,
+            // Locale commands
+            locale::get_locale,
+            locale::get_supported_locales,
+            locale::set_locale,
+            demo_mode::get_demo_mode,
+            demo_mode::set_demo_mode,
+            // Meeting note commands
+            notes::add_live_note,
+            notes::get_live_notes,
+            notes::add_meeting_note,
+            notes::get_meeting_notes,
+            notes::update_meeting_note,
+            notes::delete_meeting_note,
+            notes::get_user_profile,
+            notes::set_user_profile,
+            // Workspace commands
+            workspaces::list_workspaces,
+            workspaces::get_active_workspace,
+            workspaces::create_workspace,
+            workspaces::switch_workspace,
+            // Recording marker commands
+            notes::add_recording_marker,
+            notes::get_live_markers,
+            notes::get_recording_markers,
+            notes::get_transcript_timeline,
+            notes::update_recording_marker_label,
+            notes::delete_recording_marker,
+            // Storage quota commands
+            storage::get_storage_report,
+            storage::get_storage_settings,
+            storage::set_storage_settings,
+            // Local API server commands
+            api_server::get_api_server_status,
+            api_server::start_api_server,
+            api_server::stop_api_server,
+            api_server::set_api_server_enabled,
+            phone_mic::get_phone_mic_status,
+            phone_mic::start_phone_mic,
+            phone_mic::stop_phone_mic,
+            voice_memo::capture_voice_memo,
+            voice_memo::stop_voice_memo,
+            voice_memo::is_voice_memo_active,
+            voice_memo::get_voice_memo_settings,
+            voice_memo::set_voice_memo_settings,
+            api_server::get_grpc_server_status,
+            api_server::start_grpc_server,
+            api_server::stop_grpc_server,
+            api_server::set_grpc_server_settings,
+            // Background job commands
+            jobs::enqueue_job,
+            jobs::list_jobs,
+            jobs::get_job,
+            jobs::retry_job,
+            jobs::cancel_job,
+            // Meeting import commands
+            import::import_meeting_folder,
+            import::get_recording_participants,
+            import::get_recording_attachments,
+            import::import_transcript,
+            import::transcribe_by_channel,
+            import::get_transcript_segments,
+            import::get_watched_folders,
+            import::save_watched_folder,
+            import::remove_watched_folder,
+            import::scan_watched_folders,
+            // Live caption commands
+            captions::start_live_captions,
+            captions::stop_live_captions,
+            captions::get_live_captions,
+            live_transcription::start_live_transcription,
+            live_transcription::stop_live_transcription,
+            live_transcription::get_live_transcript,
+            captions::get_caption_socket_status,
+            captions::start_caption_socket,
+            captions::stop_caption_socket,
+            captions::set_caption_socket_enabled,
+            control_server::get_control_server_status,
+            control_server::start_control_server,
+            control_server::stop_control_server,
+            control_server::set_control_server_enabled,
+            control_server::get_control_token,
+            control_server::regenerate_control_token,
+            // Action item dashboard commands
+            action_items::get_action_items,
+            action_items::get_action_items_by_assignee,
+            action_items::get_action_items_by_due_date,
+            action_items::update_action_item_statuses,
+            // Correction dictionary
+            corrections::get_correction_entries,
+            corrections::add_correction_entry,
+            corrections::update_correction_entry,
+            corrections::delete_correction_entry,
+            // Keyword alerts
+            keyword_alerts::get_watch_words,
+            keyword_alerts::add_watch_word,
+            keyword_alerts::update_watch_word,
+            keyword_alerts::delete_watch_word,
+            keyword_alerts::get_keyword_alerts,
+            keyword_alerts::acknowledge_keyword_alerts,
+            keyword_alerts::rescan_recording_keywords,
+            keyword_alerts::get_keyword_timeline,
+            keyword_alerts::get_keyword_timelines,
+            // Speaker analytics
+            analytics::analyze_speaker_talk_time,
+            analytics::get_speaker_talk_stats,
+            analytics::get_person_talk_stats,
+            analytics::get_speaker_overlaps,
+            analytics::get_filler_word_stats,
+            analytics::analyze_speech_activity,
+            analytics::get_speech_metrics,
+            // Chapter commands
+            chapters::get_recording_chapters,
+            chapters::add_chapter,
+            chapters::update_chapter,
+            chapters::delete_chapter,
+            chapters::set_topic_chapters,
+            chapters::write_mp3_chapters,
+            // External integration commands
+            integrations::set_google_docs_client,
+            integrations::google_docs_start_auth,
+            integrations::google_docs_complete_auth,
+            integrations::google_docs_status,
+            integrations::google_docs_disconnect,
+            integrations::export_summary_to_google_docs,
+            integrations::set_teams_webhook,
+            integrations::teams_status,
+            integrations::teams_disconnect,
+            integrations::post_summary_to_teams,
+            integrations::set_confluence_settings,
+            integrations::get_confluence_settings,
+            integrations::confluence_status,
+            integrations::confluence_disconnect,
+            integrations::export_summary_to_confluence
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
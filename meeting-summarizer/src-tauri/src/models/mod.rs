@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -15,10 +15,73 @@ pub struct Recording {
     pub file_size: Option<i64>, // bytes
     pub sample_rate: Option<i32>,
     pub channels: Option<i32>,
+    /// ライブラリで目印にする色ラベル
+    #[serde(default)]
+    pub color_label: Option<ColorLabel>,
+    /// アーカイブ済み（既定の一覧・検索・統計から除外される）
+    #[serde(default)]
+    pub archived: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// 一覧表示用の録音（状態バッジのため、書き起こし・要約の有無と最新の状態を付ける）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingListItem {
+    #[serde(flatten)]
+    pub recording: Recording,
+    pub has_transcription: bool,
+    pub has_summary: bool,
+    /// 最新の書き起こしの状態
+    pub latest_transcription_status: Option<TranscriptionStatus>,
+    /// 最新の要約の状態（どの書き起こしの要約かは問わない）
+    pub latest_summary_status: Option<SummaryStatus>,
+}
+
+/// 録音の色ラベル（ライブラリでの簡易的な仕分け用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorLabel {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+    Gray,
+}
+
+impl ColorLabel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColorLabel::Red => "red",
+            ColorLabel::Orange => "orange",
+            ColorLabel::Yellow => "yellow",
+            ColorLabel::Green => "green",
+            ColorLabel::Blue => "blue",
+            ColorLabel::Purple => "purple",
+            ColorLabel::Gray => "gray",
+        }
+    }
+}
+
+impl std::str::FromStr for ColorLabel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "red" => Ok(ColorLabel::Red),
+            "orange" => Ok(ColorLabel::Orange),
+            "yellow" => Ok(ColorLabel::Yellow),
+            "green" => Ok(ColorLabel::Green),
+            "blue" => Ok(ColorLabel::Blue),
+            "purple" => Ok(ColorLabel::Purple),
+            "gray" => Ok(ColorLabel::Gray),
+            _ => Err(format!("Invalid color label: {}", s)),
+        }
+    }
+}
+
 impl Recording {
     pub fn new(filename: String, file_path: String) -> Self {
         let now = Utc::now();
@@ -34,6 +97,8 @@ impl Recording {
             file_size: None,
             sample_rate: None,
             channels: None,
+            color_label: None,
+            archived: false,
             created_at: now,
             updated_at: now,
         }
@@ -100,6 +165,33 @@ pub struct RecordingQuery {
     pub date_to: Option<DateTime<Utc>>,
     pub min_duration: Option<i64>,
     pub max_duration: Option<i64>,
+    /// 無音率の範囲（発話区間を解析済みの録音のみが対象になる）
+    pub min_silence_ratio: Option<f64>,
+    pub max_silence_ratio: Option<f64>,
+    /// カテゴリの階層（指定したノードとその子孫に一致）
+    #[serde(default)]
+    pub category_path: Option<String>,
+    /// タグの階層（すべてのパスについて、そのノードか子孫のタグを持つ録音に一致）
+    #[serde(default)]
+    pub tag_paths: Vec<String>,
+    /// いずれかの色ラベルが付いた録音に一致
+    #[serde(default)]
+    pub color_labels: Vec<ColorLabel>,
+    /// アーカイブ済みの録音も含める
+    #[serde(default)]
+    pub include_archived: bool,
+    /// 書き起こしの有無（None なら問わない）
+    #[serde(default)]
+    pub has_transcription: Option<bool>,
+    /// 要約の有無（None なら問わない）
+    #[serde(default)]
+    pub has_summary: Option<bool>,
+    /// いずれかの状態の書き起こしを持つ録音に一致
+    #[serde(default)]
+    pub transcription_statuses: Vec<TranscriptionStatusFilter>,
+    /// 上の条件に AND で加える条件の組み合わせ
+    #[serde(default)]
+    pub condition: Option<QueryCondition>,
     pub limit: Option<i32>,
     pub offset: Option<i32>,
     pub sort_by: SortBy,
@@ -121,6 +213,37 @@ pub enum SortOrder {
     Desc,
 }
 
+/// 書き起こしの状態での絞り込み（失敗は理由を問わない）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptionStatusFilter {
+    Pending,
+    Processing,
+    Completed,
+    Failed,
+}
+
+/// 検索条件の組み合わせ（AND/OR のグループは入れ子にできる）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum QueryCondition {
+    /// すべてに一致（空なら常に一致）
+    And { conditions: Vec<QueryCondition> },
+    /// いずれかに一致（空なら一致しない）
+    Or { conditions: Vec<QueryCondition> },
+    Not { condition: Box<QueryCondition> },
+    /// ファイル名・タイトル・説明の部分一致
+    Text { text: String },
+    /// カテゴリの階層（そのノードか子孫）
+    Category { path: String },
+    /// タグの階層（そのノードか子孫のタグを持つ）
+    Tag { path: String },
+    ColorLabel { label: ColorLabel },
+    HasTranscription { value: bool },
+    HasSummary { value: bool },
+    TranscriptionStatus { status: TranscriptionStatusFilter },
+}
+
 impl Default for RecordingQuery {
     fn default() -> Self {
         Self {
@@ -131,6 +254,16 @@ impl Default for RecordingQuery {
             date_to: None,
             min_duration: None,
             max_duration: None,
+            min_silence_ratio: None,
+            max_silence_ratio: None,
+            category_path: None,
+            tag_paths: Vec::new(),
+            color_labels: Vec::new(),
+            include_archived: false,
+            has_transcription: None,
+            has_summary: None,
+            transcription_statuses: Vec::new(),
+            condition: None,
             limit: Some(50),
             offset: Some(0),
             sort_by: SortBy::CreatedAt,
@@ -155,6 +288,39 @@ pub struct CategoryStats {
     pub total_duration: i64,
 }
 
+/// Whisperモデルごとの書き起こし実績（所要時間の見積もり用）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionThroughput {
+    pub model: String,
+    pub runs: i64,
+    /// 書き起こした音声の合計（秒）
+    pub audio_seconds: i64,
+    pub processing_ms: i64,
+}
+
+/// 録音に付いたカテゴリまたはタグ（階層集計の入力）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxonomyAssignment {
+    pub recording_id: String,
+    pub path: String,
+    pub duration: i64,
+}
+
+/// カテゴリ・タグの階層ノード
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxonomyNode {
+    /// 最後の階層の名前
+    pub name: String,
+    pub path: String,
+    /// このノードに直接割り当てられた件数
+    pub count: i64,
+    /// 子孫を含む録音数
+    pub total_count: i64,
+    /// 子孫を含む合計時間（秒）
+    pub total_duration: i64,
+    pub children: Vec<TaxonomyNode>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecordingSession {
     pub id: String,
@@ -187,9 +353,210 @@ pub struct Transcription {
     pub language: String,
     pub confidence: Option<f32>,
     pub processing_time_ms: Option<u64>,
+    /// 書き起こしに使ったWhisperモデル（取り込んだ書き起こしは None）
+    #[serde(default)]
+    pub model_used: Option<String>,
     pub status: TranscriptionStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Whisper が返したタイムスタンプ付きの区間（保存時に transcript_segments へ書き込む。読み込んだ書き起こしでは空）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<TranscriptSegment>,
+}
+
+/// 書き起こしのタイムスタンプ付き区間
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    pub id: String,
+    pub transcription_id: String,
+    pub segment_index: i32,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub speaker: Option<String>,
+    pub text: String,
+    pub confidence: Option<f32>,
+}
+
+impl TranscriptSegment {
+    pub fn new(transcription_id: String, segment_index: i32, start_ms: i64, end_ms: i64, text: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            transcription_id,
+            segment_index,
+            start_ms,
+            end_ms,
+            speaker: None,
+            text,
+            confidence: None,
+        }
+    }
+}
+
+/// 書き起こしセグメントの修正方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptEditSource {
+    /// 差し替え用に録り直した音声を書き起こした
+    Rerecording,
+    /// 手入力で修正した
+    Manual,
+}
+
+impl TranscriptEditSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TranscriptEditSource::Rerecording => "rerecording",
+            TranscriptEditSource::Manual => "manual",
+        }
+    }
+}
+
+impl std::str::FromStr for TranscriptEditSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rerecording" => Ok(TranscriptEditSource::Rerecording),
+            "manual" => Ok(TranscriptEditSource::Manual),
+            _ => Err(format!("Invalid transcript edit source: {}", s)),
+        }
+    }
+}
+
+/// 会議中に入力したメモ（要約時に書き起こしと一緒にLLMへ渡す）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingNote {
+    pub id: String,
+    pub recording_id: String,
+    pub text: String,
+    /// 録音開始からの経過時間
+    pub offset_ms: Option<i64>,
+    /// 重要と印を付けたメモは要約の重要ポイントに必ず含める
+    pub important: bool,
+    /// 入力したユーザーの表示名（共有端末で誰のメモか分かるように）
+    #[serde(default)]
+    pub author: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl MeetingNote {
+    pub fn new(recording_id: String, text: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            recording_id,
+            text,
+            offset_ms: None,
+            important: false,
+            author: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_author(mut self, author: Option<String>) -> Self {
+        self.author = author;
+        self
+    }
+}
+
+/// 録音中に付けたマーカー
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingMarker {
+    pub id: String,
+    pub recording_id: String,
+    /// 録音開始からの経過時間
+    pub offset_ms: i64,
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RecordingMarker {
+    pub fn new(recording_id: String, offset_ms: i64, label: Option<String>) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            recording_id,
+            offset_ms,
+            label,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// 長い録音を一定時間ごとに分けたファイル（先頭のファイルは録音の `file_path`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingSegment {
+    pub id: String,
+    pub recording_id: String,
+    pub segment_index: i32,
+    pub file_path: String,
+    /// 録音開始からの経過時間
+    pub offset_ms: i64,
+    pub duration_ms: i64,
+    pub file_size: i64,
+}
+
+impl RecordingSegment {
+    pub fn new(recording_id: String, segment_index: i32, file_path: String, offset_ms: i64, duration_ms: i64) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            recording_id,
+            segment_index,
+            file_path,
+            offset_ms,
+            duration_ms,
+            file_size: 0,
+        }
+    }
+
+    pub fn with_file_size(mut self, file_size: i64) -> Self {
+        self.file_size = file_size;
+        self
+    }
+}
+
+/// 同じ録音で別の入力デバイスから録音したトラック（入力元の音声は録音の `file_path`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingTrack {
+    pub id: String,
+    pub recording_id: String,
+    /// 1 から（録音の設定の追加トラックの順）
+    pub track_index: i32,
+    pub device_name: String,
+    pub file_path: String,
+    pub duration_ms: i64,
+    pub file_size: i64,
+}
+
+impl RecordingTrack {
+    pub fn new(recording_id: String, track_index: i32, device_name: String, file_path: String, duration_ms: i64) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            recording_id,
+            track_index,
+            device_name,
+            file_path,
+            duration_ms,
+            file_size: 0,
+        }
+    }
+
+    pub fn with_file_size(mut self, file_size: i64) -> Self {
+        self.file_size = file_size;
+        self
+    }
+}
+
+/// セグメント修正の履歴
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptEdit {
+    pub id: String,
+    pub transcription_id: String,
+    pub segment_id: String,
+    pub previous_text: String,
+    pub new_text: String,
+    pub source: TranscriptEditSource,
+    /// 録り直した音声（手入力の場合は None）
+    pub snippet_path: Option<String>,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -210,9 +577,11 @@ impl Transcription {
             language,
             confidence: None,
             processing_time_ms: None,
+            model_used: None,
             status: TranscriptionStatus::Pending,
             created_at: now,
             updated_at: now,
+            segments: Vec::new(),
         }
     }
     
@@ -225,9 +594,11 @@ impl Transcription {
             language,
             confidence: None,
             processing_time_ms: None,
+            model_used: None,
             status: TranscriptionStatus::Pending,
             created_at: now,
             updated_at: now,
+            segments: Vec::new(),
         }
     }
 
@@ -268,6 +639,11 @@ impl Transcription {
         self.updated_at = Utc::now();
         self
     }
+
+    pub fn with_model_used(mut self, model: Option<String>) -> Self {
+        self.model_used = model;
+        self
+    }
     
     pub fn with_status(mut self, status: TranscriptionStatus) -> Self {
         self.status = status;
@@ -286,10 +662,103 @@ pub struct Summary {
     pub model_used: String,
     pub processing_time_ms: Option<u64>,
     pub status: SummaryStatus,
+    #[serde(default)]
+    pub review_status: ReviewStatus,
+    #[serde(default)]
+    pub approved_by: Option<String>,
+    #[serde(default)]
+    pub approved_at: Option<DateTime<Utc>>,
+    /// 承認済みの要約を固定し、同じ書き起こしの要約の再生成・編集を禁止する
+    #[serde(default)]
+    pub locked: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// 要約の承認状態（議事録として配布する前の確認用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewStatus {
+    #[default]
+    Draft,
+    Approved,
+}
+
+impl ReviewStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewStatus::Draft => "draft",
+            ReviewStatus::Approved => "approved",
+        }
+    }
+}
+
+impl std::str::FromStr for ReviewStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "draft" => Ok(ReviewStatus::Draft),
+            "approved" => Ok(ReviewStatus::Approved),
+            _ => Err(format!("Invalid review status: {}", s)),
+        }
+    }
+}
+
+/// 要約から抽出したアクションアイテムの対応状況
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActionItemStatus {
+    Open,
+    Done,
+    Cancelled,
+}
+
+impl ActionItemStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActionItemStatus::Open => "open",
+            ActionItemStatus::Done => "done",
+            ActionItemStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl std::str::FromStr for ActionItemStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(ActionItemStatus::Open),
+            "done" => Ok(ActionItemStatus::Done),
+            "cancelled" => Ok(ActionItemStatus::Cancelled),
+            _ => Err(format!("Invalid action item status: {}", s)),
+        }
+    }
+}
+
+/// 要約内のアクションアイテムの参照（要約ID + 要約内の位置）
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ActionItemRef {
+    pub summary_id: String,
+    pub item_index: usize,
+}
+
+/// 録音をまたいだアクションアイテム一覧の1件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub summary_id: String,
+    pub item_index: usize,
+    pub text: String,
+    pub assignee: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    pub status: ActionItemStatus,
+    pub recording_id: String,
+    pub recording_title: Option<String>,
+    pub meeting_date: DateTime<Utc>,
+    pub status_updated_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SummaryStatus {
     Pending,
@@ -310,6 +779,10 @@ impl Summary {
             model_used,
             processing_time_ms: None,
             status: SummaryStatus::Pending,
+            review_status: ReviewStatus::Draft,
+            approved_by: None,
+            approved_at: None,
+            locked: false,
             created_at: now,
             updated_at: now,
         }
@@ -343,6 +816,292 @@ impl Summary {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Participant {
+    pub id: String,
+    pub recording_id: String,
+    pub name: String,
+    pub email: Option<String>,
+    pub join_time: Option<DateTime<Utc>>,
+    pub leave_time: Option<DateTime<Utc>>,
+}
+
+impl Participant {
+    pub fn new(recording_id: String, name: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            recording_id,
+            name,
+            email: None,
+            join_time: None,
+            leave_time: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: String,
+    pub recording_id: String,
+    pub kind: String, // "chat", "metadata", "transcript" など
+    pub filename: String,
+    pub file_path: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Attachment {
+    pub fn new(recording_id: String, kind: String, filename: String, file_path: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            recording_id,
+            kind,
+            filename,
+            file_path,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// チャプターの由来（トピック分割による自動生成 / 手動入力）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChapterSource {
+    Manual,
+    Topic,
+}
+
+impl ChapterSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChapterSource::Manual => "manual",
+            ChapterSource::Topic => "topic",
+        }
+    }
+}
+
+impl std::str::FromStr for ChapterSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "manual" => Ok(ChapterSource::Manual),
+            "topic" => Ok(ChapterSource::Topic),
+            _ => Err(format!("Invalid chapter source: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chapter {
+    pub id: String,
+    pub recording_id: String,
+    pub title: String,
+    pub start_ms: i64,
+    pub end_ms: Option<i64>, // None の場合は次のチャプター（または録音の終端）まで
+    pub source: ChapterSource,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Chapter {
+    pub fn new(recording_id: String, title: String, start_ms: i64) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            recording_id,
+            title,
+            start_ms,
+            end_ms: None,
+            source: ChapterSource::Manual,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_end(mut self, end_ms: i64) -> Self {
+        self.end_ms = Some(end_ms);
+        self
+    }
+
+    pub fn with_source(mut self, source: ChapterSource) -> Self {
+        self.source = source;
+        self
+    }
+}
+
+/// 録音ごとの話者別の発話量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerTalkStats {
+    pub recording_id: String,
+    pub speaker: String,
+    pub talk_time_ms: i64,
+    /// 連続して話した回数（他の話者に交代するまでを1回と数える）
+    pub turn_count: i64,
+    /// 1回の発話の最長時間
+    pub longest_monologue_ms: i64,
+    /// 録音内の全発話時間に占める割合（0.0〜1.0）
+    pub talk_share: f64,
+}
+
+/// 会議を横断した話者（人）ごとの集計
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonTalkStats {
+    pub speaker: String,
+    pub meeting_count: i64,
+    pub total_talk_time_ms: i64,
+    pub total_turns: i64,
+    pub longest_monologue_ms: i64,
+    pub average_talk_share: f64,
+}
+
+/// 録音の無音率・間の長さなどの指標（発話区間検出の結果から計算）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechMetrics {
+    pub recording_id: String,
+    pub duration_ms: i64,
+    pub speech_ms: i64,
+    /// 録音全体に占める無音の割合（0.0〜1.0）
+    pub silence_ratio: f64,
+    /// 発話と発話の間の平均の長さ
+    pub average_pause_ms: i64,
+    pub pause_count: i64,
+    /// 1分あたりの発話区間の数
+    pub speech_density: f64,
+    pub analyzed_at: DateTime<Utc>,
+}
+
+/// 誤認識語の補正辞書エントリ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrectionEntry {
+    pub id: String,
+    /// 書き起こしに現れる誤認識の表記（例: "クバネテス"）
+    pub wrong: String,
+    /// 置き換え後の表記（例: "Kubernetes"）
+    pub correct: String,
+    pub enabled: bool,
+    /// これまでに置き換えた回数
+    pub hit_count: i64,
+    pub last_hit_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CorrectionEntry {
+    pub fn new(wrong: String, correct: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            wrong,
+            correct,
+            enabled: true,
+            hit_count: 0,
+            last_hit_at: None,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// カテゴリごとの既定の書き起こし言語
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoryLanguage {
+    pub category: String,
+    pub language: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 書き起こしに現れたら通知する監視ワード
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchWord {
+    pub id: String,
+    pub word: String,
+    /// 一致した録音に付けるタグ（未指定なら `watch:<word>`）
+    pub tag: Option<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WatchWord {
+    pub fn new(word: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            word,
+            tag: None,
+            enabled: true,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_tag(mut self, tag: String) -> Self {
+        self.tag = Some(tag);
+        self
+    }
+
+    pub fn recording_tag(&self) -> String {
+        self.tag.clone().unwrap_or_else(|| format!("watch:{}", self.word.trim()))
+    }
+}
+
+/// 監視ワードの検出記録
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeywordAlert {
+    pub id: String,
+    pub watch_word_id: String,
+    pub word: String,
+    pub recording_id: String,
+    pub transcription_id: String,
+    pub occurrences: i64,
+    /// 最初に一致した箇所の前後
+    pub snippet: String,
+    pub acknowledged: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 匿名化で置き換えた個人情報の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiKind {
+    Email,
+    PhoneNumber,
+    PersonName,
+}
+
+impl PiiKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PiiKind::Email => "email",
+            PiiKind::PhoneNumber => "phone_number",
+            PiiKind::PersonName => "person_name",
+        }
+    }
+
+    /// プレースホルダーの接頭辞（例: `[EMAIL_1]`）
+    pub fn placeholder_prefix(&self) -> &'static str {
+        match self {
+            PiiKind::Email => "EMAIL",
+            PiiKind::PhoneNumber => "PHONE",
+            PiiKind::PersonName => "NAME",
+        }
+    }
+}
+
+impl std::str::FromStr for PiiKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "email" => Ok(PiiKind::Email),
+            "phone_number" => Ok(PiiKind::PhoneNumber),
+            "person_name" => Ok(PiiKind::PersonName),
+            _ => Err(format!("Invalid PII kind: {}", s)),
+        }
+    }
+}
+
+/// 録音ごとのプレースホルダーと元の値の対応（ローカルDBにのみ保存する）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionEntry {
+    pub recording_id: String,
+    pub placeholder: String,
+    pub original: String,
+    pub kind: PiiKind,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LLMConfig {
     pub provider: LLMProvider,
@@ -351,6 +1110,24 @@ pub struct LLMConfig {
     pub temperature: f32,
     pub max_tokens: u32,
     pub timeout_seconds: u64,
+    /// リモートホスト向けの接続設定（HTTPS用のCA証明書・認証ヘッダー）
+    #[serde(default)]
+    pub connection: LLMConnection,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LLMConnection {
+    /// 自己署名などのサーバー証明書を検証するためのCA証明書（PEM）
+    pub ca_cert_path: Option<String>,
+    pub auth: Option<LLMAuth>,
+}
+
+/// リクエストに付ける Authorization ヘッダー
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum LLMAuth {
+    Bearer { token: String },
+    Basic { username: String, password: String },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -360,6 +1137,24 @@ pub enum LLMProvider {
     GPT4All,
     LMStudio,
     Custom,
+    /// デモモード用（決まった応答を返し、通信しない）
+    Demo,
+}
+
+impl std::str::FromStr for LLMProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Ollama" | "ollama" => Ok(LLMProvider::Ollama),
+            "OpenAI" | "openai" => Ok(LLMProvider::OpenAI),
+            "GPT4All" | "gpt4all" => Ok(LLMProvider::GPT4All),
+            "LMStudio" | "lmstudio" => Ok(LLMProvider::LMStudio),
+            "Custom" | "custom" => Ok(LLMProvider::Custom),
+            "Demo" | "demo" => Ok(LLMProvider::Demo),
+            _ => Err(format!("Invalid provider: {}", s)),
+        }
+    }
 }
 
 impl Default for LLMConfig {
@@ -371,6 +1166,137 @@ impl Default for LLMConfig {
             temperature: 0.7,
             max_tokens: 2048,
             timeout_seconds: 120,
+            connection: LLMConnection::default(),
+        }
+    }
+}
+/// バックグラウンドジョブの処理内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "params")]
+pub enum JobPayload {
+    Transcription {
+        recording_id: String,
+        language: Option<String>,
+    },
+    Summarization {
+        transcription_id: String,
+        model_config: Option<LLMConfig>,
+    },
+    ModelDownload {
+        model_name: String,
+    },
+    Export {
+        recording_id: String,
+        format: String,
+        output_path: String,
+    },
+    /// 書き起こしてから続けて要約する（監視フォルダからの自動取り込み用）
+    TranscribeAndSummarize {
+        recording_id: String,
+        language: Option<String>,
+        model_config: Option<LLMConfig>,
+    },
+    /// ステレオの通話録音をチャンネルごとに書き起こし、話者名を付けて並べる（監視フォルダからの自動取り込み用）
+    ChannelTranscription {
+        recording_id: String,
+        language: Option<String>,
+        /// チャンネルの順の話者名
+        speakers: Vec<String>,
+        /// 書き起こし後に要約する
+        summarize: bool,
+        model_config: Option<LLMConfig>,
+    },
+}
+
+impl JobPayload {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JobPayload::Transcription { .. } => "transcription",
+            JobPayload::Summarization { .. } => "summarization",
+            JobPayload::ModelDownload { .. } => "model_download",
+            JobPayload::Export { .. } => "export",
+            JobPayload::TranscribeAndSummarize { .. } => "transcribe_and_summarize",
+            JobPayload::ChannelTranscription { .. } => "channel_transcription",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Pending => "pending",
+            JobStatus::Running => "running",
+            JobStatus::Completed => "completed",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
         }
     }
-}
\ No newline at end of file
+}
+
+impl std::str::FromStr for JobStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(JobStatus::Pending),
+            "running" => Ok(JobStatus::Running),
+            "completed" => Ok(JobStatus::Completed),
+            "failed" => Ok(JobStatus::Failed),
+            "cancelled" => Ok(JobStatus::Cancelled),
+            _ => Err(format!("Invalid job status: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    pub payload: JobPayload,
+    pub status: JobStatus,
+    pub priority: i32, // 大きいほど優先
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub last_error: Option<String>,
+    pub result: Option<serde_json::Value>,
+    pub run_after: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Job {
+    pub fn new(payload: JobPayload) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            payload,
+            status: JobStatus::Pending,
+            priority: 0,
+            attempts: 0,
+            max_attempts: 3,
+            last_error: None,
+            result: None,
+            run_after: now,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: i32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+}
@@ -0,0 +1,179 @@
+//! 録音をまたいだアクションアイテムの集計（「自分のタスク」画面用）
+//!
+//! 本文は各書き起こしの最新の要約から取り出し、担当者・期限は本文から推定する。
+//! 対応状況は action_item_states に保存し、要約の再生成で本文が変わった項目は未対応に戻る。
+
+use crate::database::Database;
+use crate::errors::AppResult;
+use crate::models::{ActionItem, ActionItemRef, ActionItemStatus};
+use crate::services::ical;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActionItemQuery {
+    /// 未指定なら未対応（open）のみ
+    pub status: Option<ActionItemStatus>,
+    /// 担当者名（部分一致、大文字小文字を区別しない）
+    pub assignee: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssigneeGroup {
+    /// None は担当者未定
+    pub assignee: Option<String>,
+    pub items: Vec<ActionItem>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DueBucket {
+    Overdue,
+    Today,
+    ThisWeek,
+    Later,
+    NoDueDate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DueDateGroup {
+    pub bucket: DueBucket,
+    pub items: Vec<ActionItem>,
+}
+
+/// 条件に合うアクションアイテムを期限の近い順に返す
+pub async fn list_action_items(database: &Database, query: &ActionItemQuery) -> AppResult<Vec<ActionItem>> {
+    let states: HashMap<ActionItemRef, _> = database
+        .get_action_item_states()
+        .await?
+        .into_iter()
+        .map(|(item_ref, text, status, updated_at)| (item_ref, (text, status, updated_at)))
+        .collect();
+
+    let wanted_status = query.status.unwrap_or(ActionItemStatus::Open);
+    let assignee_filter = query.assignee.as_deref().map(str::to_lowercase);
+
+    let mut items = Vec::new();
+    for (summary, recording_id, recording_title) in database.get_latest_summaries_with_recordings().await? {
+        for (item_index, text) in summary.action_items.iter().enumerate() {
+            let item_ref = ActionItemRef {
+                summary_id: summary.id.clone(),
+                item_index,
+            };
+            let (status, status_updated_at) = match states.get(&item_ref) {
+                Some((saved_text, status, updated_at)) if saved_text == text => (*status, Some(*updated_at)),
+                _ => (ActionItemStatus::Open, None),
+            };
+            if status != wanted_status {
+                continue;
+            }
+
+            let assignee = extract_assignee(text);
+            if let Some(filter) = &assignee_filter {
+                if !assignee.as_deref().is_some_and(|name| name.to_lowercase().contains(filter.as_str())) {
+                    continue;
+                }
+            }
+
+            items.push(ActionItem {
+                summary_id: summary.id.clone(),
+                item_index,
+                text: text.clone(),
+                assignee,
+                due_date: ical::extract_due_date(text),
+                status,
+                recording_id: recording_id.clone(),
+                recording_title: recording_title.clone(),
+                meeting_date: summary.created_at,
+                status_updated_at,
+            });
+        }
+    }
+
+    // 期限のあるものを先に、同じ期限なら新しい会議を先に
+    items.sort_by(|a, b| {
+        (a.due_date.is_none(), a.due_date, std::cmp::Reverse(a.meeting_date))
+            .cmp(&(b.due_date.is_none(), b.due_date, std::cmp::Reverse(b.meeting_date)))
+    });
+    Ok(items)
+}
+
+/// 担当者ごとにまとめる（担当者未定は最後）
+pub fn group_by_assignee(items: Vec<ActionItem>) -> Vec<AssigneeGroup> {
+    let mut groups: Vec<AssigneeGroup> = Vec::new();
+    for item in items {
+        match groups.iter_mut().find(|group| group.assignee == item.assignee) {
+            Some(group) => group.items.push(item),
+            None => groups.push(AssigneeGroup {
+                assignee: item.assignee.clone(),
+                items: vec![item],
+            }),
+        }
+    }
+    groups.sort_by(|a, b| (a.assignee.is_none(), &a.assignee).cmp(&(b.assignee.is_none(), &b.assignee)));
+    groups
+}
+
+/// 期限の区分（期限切れ / 今日 / 今週 / それ以降 / 期限なし）ごとにまとめる
+pub fn group_by_due_date(items: Vec<ActionItem>, today: NaiveDate) -> Vec<DueDateGroup> {
+    let mut groups: Vec<DueDateGroup> = Vec::new();
+    for item in items {
+        let bucket = due_bucket(item.due_date, today);
+        match groups.iter_mut().find(|group| group.bucket == bucket) {
+            Some(group) => group.items.push(item),
+            None => groups.push(DueDateGroup { bucket, items: vec![item] }),
+        }
+    }
+    groups.sort_by_key(|group| group.bucket);
+    groups
+}
+
+pub fn due_bucket(due_date: Option<NaiveDate>, today: NaiveDate) -> DueBucket {
+    let Some(due_date) = due_date else {
+        return DueBucket::NoDueDate;
+    };
+    // 週の終わりは日曜日
+    let end_of_week = today + chrono::Duration::days(6 - today.weekday().num_days_from_monday() as i64);
+
+    if due_date < today {
+        DueBucket::Overdue
+    } else if due_date == today {
+        DueBucket::Today
+    } else if due_date <= end_of_week {
+        DueBucket::ThisWeek
+    } else {
+        DueBucket::Later
+    }
+}
+
+/// 本文から担当者名を推定（「担当: 田中」「担当者：田中」「Owner: Alice」「@alice」）
+pub fn extract_assignee(text: &str) -> Option<String> {
+    const LABELS: [&str; 6] = ["担当者", "担当", "assignee", "owner", "Assignee", "Owner"];
+
+    for label in LABELS {
+        let mut search_from = 0;
+        while let Some(position) = text[search_from..].find(label) {
+            let rest = &text[search_from + position + label.len()..];
+            let rest = rest.trim_start();
+            if let Some(value) = rest.strip_prefix(':').or_else(|| rest.strip_prefix('：')) {
+                if let Some(name) = take_name(value) {
+                    return Some(name);
+                }
+            }
+            search_from += position + label.len();
+        }
+    }
+
+    text.split('@').skip(1).find_map(take_name)
+}
+
+fn take_name(value: &str) -> Option<String> {
+    let name: String = value
+        .trim_start()
+        .chars()
+        .take_while(|c| !c.is_whitespace() && !"、。，,.;；:：()（）[]【】「」/".contains(*c))
+        .collect();
+    let name = name.strip_suffix("さん").unwrap_or(&name);
+    (!name.is_empty()).then(|| name.to_string())
+}
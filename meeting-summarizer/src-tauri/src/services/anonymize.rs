@@ -0,0 +1,203 @@
+//! 参加者を匿名化した書き出し
+//!
+//! 社外へ例として共有できるよう、話者ラベル・メモの入力者・（任意で LLM が検出した）人名を
+//! 「Participant A」のような中立的なラベルに置き換える。`redaction` と違い対応表は保存せず、
+//! 元に戻せない。ラベルは話者の登場順に振るため、同じ録音なら書き起こしと要約で同じ人は同じラベルになる。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::Summary;
+use crate::services::{export, LLMService};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnonymizeOptions {
+    /// 話者ラベル以外に、LLM で書き起こし中の人名も検出して置き換える
+    #[serde(default)]
+    pub detect_names: bool,
+}
+
+/// 匿名化した書き出し結果（元の名前は含めない）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnonymizedExport {
+    pub content: String,
+    pub summaries: Vec<Summary>,
+    pub participant_count: usize,
+}
+
+/// 0 → "Participant A"、25 → "Participant Z"、26 → "Participant AA"
+pub fn participant_label(index: usize) -> String {
+    let mut letters = Vec::new();
+    let mut n = index + 1;
+    while n > 0 {
+        n -= 1;
+        letters.push((b'A' + (n % 26) as u8) as char);
+        n /= 26;
+    }
+    format!("Participant {}", letters.into_iter().rev().collect::<String>())
+}
+
+/// 参加者名とラベルの対応（登録順にラベルを振る）
+#[derive(Debug, Default)]
+pub struct Anonymizer {
+    participants: Vec<(String, String)>,
+}
+
+impl Anonymizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 参加者を登録してラベルを返す（1文字の名前は誤置換が多いため対象外）
+    pub fn add_participant(&mut self, name: &str) -> Option<String> {
+        let name = name.trim();
+        if name.chars().count() < 2 {
+            return None;
+        }
+        if let Some((_, label)) = self.participants.iter().find(|(original, _)| original == name) {
+            return Some(label.clone());
+        }
+        let label = participant_label(self.participants.len());
+        self.participants.push((name.to_string(), label.clone()));
+        Some(label)
+    }
+
+    pub fn participant_count(&self) -> usize {
+        self.participants.len()
+    }
+
+    /// 登録した名前をすべてラベルに置き換える（長い名前を優先し、英字の名前は単語の途中に一致させない）
+    pub fn anonymize(&self, text: &str) -> String {
+        let mut names: Vec<&(String, String)> = self.participants.iter().collect();
+        names.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+
+        let mut result = String::with_capacity(text.len());
+        let mut cursor = 0;
+        while cursor < text.len() {
+            let rest = &text[cursor..];
+            let matched = names.iter().find(|(name, _)| {
+                rest.starts_with(name.as_str()) && !inside_word(text, cursor, cursor + name.len(), name)
+            });
+            match matched {
+                Some((name, label)) => {
+                    result.push_str(label);
+                    cursor += name.len();
+                }
+                None => {
+                    let c = rest.chars().next().unwrap_or_default();
+                    result.push(c);
+                    cursor += c.len_utf8();
+                }
+            }
+        }
+        result
+    }
+
+    pub fn anonymize_summary(&self, summary: &Summary) -> Summary {
+        let mut summary = summary.clone();
+        summary.summary_text = self.anonymize(&summary.summary_text);
+        summary.key_points = summary.key_points.iter().map(|point| self.anonymize(point)).collect();
+        summary.action_items = summary.action_items.iter().map(|item| self.anonymize(item)).collect();
+        summary.approved_by = summary.approved_by.as_deref().map(|name| self.anonymize(name));
+        summary
+    }
+}
+
+fn inside_word(text: &str, start: usize, end: usize, name: &str) -> bool {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    name.is_ascii()
+        && (text[..start].chars().next_back().is_some_and(is_word) || text[end..].chars().next().is_some_and(is_word))
+}
+
+/// 録音の参加者を集める（話者の登場順 → メモの入力者 → LLM が検出した人名）
+pub async fn collect_participants(
+    database: &Database,
+    llm: Option<&LLMService>,
+    recording_id: &str,
+    options: &AnonymizeOptions,
+) -> AppResult<Anonymizer> {
+    let mut anonymizer = Anonymizer::new();
+    let transcriptions = database.get_transcriptions_by_recording(recording_id).await?;
+
+    for transcription in &transcriptions {
+        for segment in database.get_segments_by_transcription(&transcription.id).await? {
+            if let Some(speaker) = &segment.speaker {
+                anonymizer.add_participant(speaker);
+            }
+        }
+    }
+    for note in database.get_meeting_notes(recording_id).await? {
+        if let Some(author) = &note.author {
+            anonymizer.add_participant(author);
+        }
+    }
+
+    if options.detect_names {
+        if let Some(llm) = llm {
+            // 検出に失敗した場合は書き出しを失敗させる（名前が残ったまま共有されないように）
+            for transcription in transcriptions.iter().filter(|t| !t.text.trim().is_empty()) {
+                for name in llm.extract_person_names(&transcription.text).await? {
+                    anonymizer.add_participant(&name);
+                }
+            }
+        }
+    }
+
+    Ok(anonymizer)
+}
+
+/// 録音を指定形式で書き出し、書き起こしと要約の参加者をラベルに置き換える
+pub async fn export_recording_anonymized(
+    database: &Database,
+    llm: Option<&LLMService>,
+    recording_id: &str,
+    format: &str,
+    options: &AnonymizeOptions,
+) -> AppResult<AnonymizedExport> {
+    let content = export::export_recording(database, recording_id, format).await?;
+    let anonymizer = collect_participants(database, llm, recording_id, options).await?;
+
+    let mut summaries = Vec::new();
+    for transcription in database.get_transcriptions_by_recording(recording_id).await? {
+        for summary in database.get_summaries_by_transcription(&transcription.id).await? {
+            summaries.push(anonymizer.anonymize_summary(&summary));
+        }
+    }
+
+    log::info!(
+        "🕶️ Anonymized {} participant(s) in {} export of recording {}",
+        anonymizer.participant_count(),
+        format,
+        recording_id
+    );
+
+    Ok(AnonymizedExport {
+        content: anonymizer.anonymize(&content),
+        summaries,
+        participant_count: anonymizer.participant_count(),
+    })
+}
+
+/// 参加者をラベルに置き換えた要約のコピーを返す（DB の要約は変更しない）
+pub async fn anonymize_summary(
+    database: &Database,
+    llm: Option<&LLMService>,
+    summary_id: &str,
+    options: &AnonymizeOptions,
+) -> AppResult<Summary> {
+    let summary = database
+        .get_summary(summary_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Summary with id {} not found", summary_id),
+        })?;
+    let transcription = database
+        .get_transcription(&summary.transcription_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Transcription with id {} not found", summary.transcription_id),
+        })?;
+
+    let anonymizer = collect_participants(database, llm, &transcription.recording_id, options).await?;
+    Ok(anonymizer.anonymize_summary(&summary))
+}
@@ -0,0 +1,321 @@
+//! ローカルREST APIサーバー
+//!
+//! 127.0.0.1 でのみ待ち受け、外部操作用サーバーと同じトークンを `X-Control-Token` ヘッダーで
+//! 渡さない要求は 401 で拒否する（URL に残らないよう `?token=` では受け付けない）。要約には画面で設定したモデルを使い、
+//! 要求ごとに接続先は変えられない。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Recording, Summary, Transcription};
+use crate::services::control_server::{token_matches, CONTROL_TOKEN_HEADER};
+use crate::services::{corrections, recording_segments, transcription_language, RecordingService, Summarizer, WhisperService};
+use axum::extract::{Path, Request, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+/// APIハンドラーから参照するサービス群
+#[derive(Clone)]
+pub struct ApiServerState {
+    pub db: Arc<Database>,
+    pub recording_service: Arc<RecordingService>,
+    pub whisper_service: Arc<WhisperService>,
+    pub summarizer: Arc<Summarizer>,
+    /// 外部操作用サーバーと共通のトークン
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiServerStatus {
+    pub running: bool,
+    pub address: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TranscribeRequest {
+    pub language: Option<String>,
+}
+
+/// ローカルREST APIサーバー（127.0.0.1のみにバインド）
+pub struct ApiServer {
+    address: Option<SocketAddr>,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+    state: Option<ApiServerState>,
+}
+
+impl ApiServer {
+    pub fn new() -> Self {
+        Self {
+            address: None,
+            shutdown_tx: None,
+            handle: None,
+            state: None,
+        }
+    }
+
+    /// サーバーを起動
+    pub async fn start(&mut self, state: ApiServerState, port: u16) -> AppResult<SocketAddr> {
+        if self.is_running() {
+            return Err(AppError::InvalidOperation {
+                message: "API server is already running".to_string(),
+            });
+        }
+        if state.token.trim().is_empty() {
+            return Err(AppError::ValidationError {
+                message: "API token must not be empty".to_string(),
+            });
+        }
+
+        let address = SocketAddr::from(([127, 0, 0, 1], port));
+        let listener = tokio::net::TcpListener::bind(address).await?;
+        let address = listener.local_addr()?;
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let router = Self::router(state.clone());
+
+        let handle = tokio::spawn(async move {
+            let result = axum::serve(listener, router)
+                .with_graceful_shutdown(async {
+                    let _ = shutdown_rx.await;
+                })
+                .await;
+
+            if let Err(e) = result {
+                log::error!("❌ API server terminated with error: {}", e);
+            }
+        });
+
+        self.address = Some(address);
+        self.shutdown_tx = Some(shutdown_tx);
+        self.handle = Some(handle);
+        self.state = Some(state);
+
+        log::info!("🌐 Local API server listening on http://{}", address);
+        Ok(address)
+    }
+
+    /// サーバーを停止
+    pub async fn stop(&mut self) -> AppResult<()> {
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(());
+        }
+
+        if let Some(handle) = self.handle.take() {
+            handle.await.map_err(|e| AppError::InvalidOperation {
+                message: format!("Failed to stop API server: {}", e),
+            })?;
+        }
+
+        self.address = None;
+        log::info!("🛑 Local API server stopped");
+        Ok(())
+    }
+
+    /// トークンを作り直した後、起動中なら新しいトークンで開き直す
+    pub async fn restart_with_token(&mut self, token: String) -> AppResult<()> {
+        let (Some(address), Some(state)) = (self.address.filter(|_| self.is_running()), self.state.clone()) else {
+            return Ok(());
+        };
+        self.stop().await?;
+        self.start(ApiServerState { token, ..state }, address.port()).await?;
+        Ok(())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.handle.as_ref().is_some_and(|handle| !handle.is_finished())
+    }
+
+    pub fn get_status(&self) -> ApiServerStatus {
+        let running = self.is_running();
+        ApiServerStatus {
+            running,
+            address: if running {
+                self.address.map(|addr| format!("http://{}", addr))
+            } else {
+                None
+            },
+        }
+    }
+
+    fn router(state: ApiServerState) -> Router {
+        Router::new()
+            .route("/api/recordings", get(list_recordings))
+            .route("/api/recordings/:id", get(get_recording))
+            .route("/api/recordings/:id/transcriptions", get(list_transcriptions))
+            .route("/api/recordings/:id/transcribe", post(transcribe_recording))
+            .route("/api/transcriptions/:id", get(get_transcription))
+            .route("/api/transcriptions/:id/summaries", get(list_summaries))
+            .route("/api/transcriptions/:id/summarize", post(summarize_transcription))
+            .route("/api/summaries/:id", get(get_summary))
+            .layer(middleware::from_fn_with_state(state.clone(), require_token))
+            .with_state(state)
+    }
+}
+
+impl Default for ApiServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// AppErrorをHTTPレスポンスに変換するためのラッパー
+pub struct ApiError(AppError);
+
+impl From<AppError> for ApiError {
+    fn from(error: AppError) -> Self {
+        Self(error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match &self.0 {
+            AppError::FileNotFound { .. } => StatusCode::NOT_FOUND,
+            AppError::ValidationError { .. } | AppError::InvalidPath { .. } => StatusCode::BAD_REQUEST,
+            AppError::PermissionDenied { .. } => StatusCode::FORBIDDEN,
+            AppError::InvalidOperation { .. } | AppError::TranscriptionInProgress { .. } => StatusCode::CONFLICT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        let message: String = self.0.into();
+        (status, Json(serde_json::json!({ "error": message }))).into_response()
+    }
+}
+
+async fn require_token(
+    State(state): State<ApiServerState>,
+    headers: HeaderMap,
+    request: Request,
+    next: Next,
+) -> Response {
+    let header = headers.get(CONTROL_TOKEN_HEADER).and_then(|value| value.to_str().ok());
+    if !token_matches(header, &state.token) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    next.run(request).await
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+fn not_found(kind: &str, id: &str) -> ApiError {
+    ApiError(AppError::FileNotFound {
+        path: format!("{}/{}", kind, id),
+    })
+}
+
+async fn list_recordings(State(state): State<ApiServerState>) -> ApiResult<Vec<Recording>> {
+    Ok(Json(state.db.get_recordings(false).await?))
+}
+
+async fn get_recording(
+    State(state): State<ApiServerState>,
+    Path(id): Path<String>,
+) -> ApiResult<Recording> {
+    state
+        .db
+        .get_recording(&id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| not_found("recordings", &id))
+}
+
+async fn list_transcriptions(
+    State(state): State<ApiServerState>,
+    Path(id): Path<String>,
+) -> ApiResult<Vec<Transcription>> {
+    Ok(Json(state.db.get_transcriptions_by_recording(&id).await?))
+}
+
+async fn get_transcription(
+    State(state): State<ApiServerState>,
+    Path(id): Path<String>,
+) -> ApiResult<Transcription> {
+    state
+        .db
+        .get_transcription(&id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| not_found("transcriptions", &id))
+}
+
+async fn list_summaries(
+    State(state): State<ApiServerState>,
+    Path(id): Path<String>,
+) -> ApiResult<Vec<Summary>> {
+    Ok(Json(state.db.get_summaries_by_transcription(&id).await?))
+}
+
+async fn get_summary(
+    State(state): State<ApiServerState>,
+    Path(id): Path<String>,
+) -> ApiResult<Summary> {
+    state
+        .db
+        .get_summary(&id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| not_found("summaries", &id))
+}
+
+async fn transcribe_recording(
+    State(state): State<ApiServerState>,
+    Path(id): Path<String>,
+    request: Option<Json<TranscribeRequest>>,
+) -> ApiResult<Transcription> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    log::info!("🌐 API transcription requested for recording: {}", id);
+
+    let recording = state
+        .recording_service
+        .get_recording(&id)
+        .await?
+        .ok_or_else(|| not_found("recordings", &id))?;
+
+    let audio_files = recording_segments::audio_files(&state.db, &recording).await?;
+
+    if !state.whisper_service.is_initialized().await {
+        state.whisper_service.initialize().await?;
+    }
+
+    let language = transcription_language::resolve_language(&state.db, &recording, request.language).await?;
+    let mut transcription = state
+        .whisper_service
+        .transcribe_audio_files(&audio_files, recording.id.clone(), language)
+        .await?;
+
+    corrections::apply_corrections(&state.db, &mut transcription).await?;
+    state.db.create_transcription(&transcription).await?;
+
+    Ok(Json(transcription))
+}
+
+async fn summarize_transcription(
+    State(state): State<ApiServerState>,
+    Path(id): Path<String>,
+) -> ApiResult<Summary> {
+    log::info!("🌐 API summarization requested for transcription: {}", id);
+
+    let transcription = state
+        .db
+        .get_transcription(&id)
+        .await?
+        .ok_or_else(|| not_found("transcriptions", &id))?;
+
+    // 接続先は要求から受け取らず、画面で設定したモデルで要約する
+    let summary = state
+        .summarizer
+        .summarize(&transcription.id, &transcription.text, None, None)
+        .await?;
+
+    state.db.create_summary(&summary).await?;
+
+    Ok(Json(summary))
+}
@@ -0,0 +1,692 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::LLMConfig;
+use crate::services::capture_source::{CaptureSource, DualSourceMode};
+use crate::services::recording_encoder::RecordingFormat;
+use crate::services::recording_quality::RecordingQuality;
+use crate::services::i18n::Locale;
+use crate::services::whisper_acceleration::AccelerationBenchmark;
+use crate::services::whisper_cpp::WhisperBackend;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// アプリケーション全体の設定（モデル設定以外）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppSettings {
+    /// 画面・通知・エラーメッセージの表示言語
+    #[serde(default)]
+    pub locale: Locale,
+    #[serde(default)]
+    pub api_server: ApiServerSettings,
+    #[serde(default)]
+    pub google_docs: GoogleDocsSettings,
+    #[serde(default)]
+    pub grpc_server: GrpcServerSettings,
+    #[serde(default)]
+    pub batch_transcription: BatchTranscriptionSettings,
+    #[serde(default)]
+    pub storage: StorageSettings,
+    #[serde(default)]
+    pub user_profile: UserProfile,
+    #[serde(default)]
+    pub confluence: ConfluenceSettings,
+    #[serde(default)]
+    pub watched_folders: Vec<WatchedFolderRule>,
+    #[serde(default)]
+    pub phone_mic: PhoneMicSettings,
+    #[serde(default)]
+    pub caption_socket: CaptionSocketSettings,
+    /// マイク・Whisper・LLMを使わずに決まった結果で動かすデモモード
+    #[serde(default)]
+    pub demo_mode: bool,
+    #[serde(default)]
+    pub digest_schedule: DigestScheduleSettings,
+    #[serde(default)]
+    pub voice_memo: VoiceMemoSettings,
+    #[serde(default)]
+    pub control_server: ControlServerSettings,
+    #[serde(default)]
+    pub recording_profile: RecordingProfile,
+    #[serde(default)]
+    pub whisper_acceleration: WhisperAccelerationSettings,
+    #[serde(default)]
+    pub whisper_backend: WhisperBackendSettings,
+    #[serde(default)]
+    pub silence_trim: SilenceTrimSettings,
+    #[serde(default)]
+    pub input_gain: InputGainSettings,
+    #[serde(default)]
+    pub noise_suppression: NoiseSuppressionSettings,
+    #[serde(default)]
+    pub loudness: LoudnessSettings,
+    #[serde(default)]
+    pub sleep_prevention: SleepPreventionSettings,
+}
+
+/// 監視フォルダと、新しい音声ファイルを見つけたときの処理
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchedFolderRule {
+    pub id: String,
+    pub path: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 取り込み後に書き起こす
+    #[serde(default = "default_true")]
+    pub transcribe: bool,
+    /// 書き起こし後に要約する
+    #[serde(default)]
+    pub summarize: bool,
+    #[serde(default)]
+    pub language: Option<String>,
+    /// 取り込んだ録音に設定するカテゴリ
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 要約に使うモデル（未指定なら既定）
+    #[serde(default)]
+    pub model_config: Option<LLMConfig>,
+    /// ステレオの通話録音として、チャンネルごとに書き起こして話者名を付ける
+    #[serde(default)]
+    pub separate_channels: bool,
+    /// チャンネルの順の話者名（空なら「Speaker 1」のように番号を付ける）
+    #[serde(default)]
+    pub channel_speakers: Vec<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// この端末で操作しているユーザー（メモの入力者として記録する）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserProfile {
+    /// 表示名。未設定ならOSのユーザー名を使う
+    pub display_name: Option<String>,
+}
+
+impl UserProfile {
+    /// メモに記録する入力者名
+    pub fn author_name(&self) -> Option<String> {
+        self.display_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .or_else(|| {
+                std::env::var("USER")
+                    .or_else(|_| std::env::var("USERNAME"))
+                    .ok()
+                    .filter(|name| !name.trim().is_empty())
+            })
+    }
+}
+
+/// ローカルREST APIサーバーの設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiServerSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for ApiServerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8765,
+        }
+    }
+}
+
+/// ローカルgRPCサーバーの設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcServerSettings {
+    pub enabled: bool,
+    pub bind_address: String,
+    pub port: u16,
+}
+
+impl Default for GrpcServerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "127.0.0.1".to_string(),
+            port: 50051,
+        }
+    }
+}
+
+/// スマートフォンをマイクにするLAN取り込みサーバーの設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhoneMicSettings {
+    pub port: u16,
+}
+
+impl Default for PhoneMicSettings {
+    fn default() -> Self {
+        Self { port: 8766 }
+    }
+}
+
+/// ライブ字幕のWebSocket配信の設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptionSocketSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for CaptionSocketSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8767,
+        }
+    }
+}
+
+/// 録音の設定
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordingProfile {
+    /// 録音の入力元
+    #[serde(default)]
+    pub source: CaptureSource,
+    /// システム音声も一緒に録音するか
+    #[serde(default)]
+    pub dual_source: DualSourceMode,
+    /// スピーカーで流した会議の音声がマイクに入った分を、システム音声を参照して取り除く（同時録音が必要）
+    #[serde(default)]
+    pub echo_cancellation: bool,
+    /// 入力元と同時に、別のトラックとして録音する入力デバイス（会議室の2本目のマイクなど）
+    #[serde(default)]
+    pub extra_tracks: Vec<CaptureSource>,
+    /// 保存する音質（録音ごとに変更することもできる）
+    #[serde(default)]
+    pub quality: RecordingQuality,
+    /// 録音の停止後に変換する保存形式
+    #[serde(default)]
+    pub format: RecordingFormat,
+    /// 長い録音をこの長さ（分）ごとに別のファイルへ分ける（None なら分けない）
+    #[serde(default)]
+    pub max_segment_minutes: Option<u32>,
+}
+
+/// 書き起こしの前に長い無音を取り除く設定
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilenceTrimSettings {
+    pub enabled: bool,
+    /// これ以上続く無音を取り除く
+    pub min_silence_ms: u32,
+    /// 発話の前後に残す無音
+    pub keep_silence_ms: u32,
+}
+
+impl Default for SilenceTrimSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_silence_ms: 2_000,
+            keep_silence_ms: 300,
+        }
+    }
+}
+
+impl SilenceTrimSettings {
+    pub fn validate(&self) -> AppResult<()> {
+        if self.min_silence_ms < 500 {
+            return Err(AppError::ValidationError {
+                message: "min_silence_ms must be at least 500".to_string(),
+            });
+        }
+        if self.keep_silence_ms * 2 > self.min_silence_ms {
+            return Err(AppError::ValidationError {
+                message: "keep_silence_ms must be at most half of min_silence_ms".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// 録音時の入力ゲイン
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct InputGainSettings {
+    /// 固定ゲイン（自動ゲインが無効の場合に使う）
+    pub gain: f32,
+    /// 入力の大きさに合わせてゲインを自動で調整する
+    pub auto_gain: bool,
+}
+
+impl Default for InputGainSettings {
+    fn default() -> Self {
+        Self {
+            gain: 2.0,
+            auto_gain: false,
+        }
+    }
+}
+
+impl InputGainSettings {
+    pub const MIN_GAIN: f32 = 0.1;
+    pub const MAX_GAIN: f32 = 10.0;
+
+    pub fn validate(&self) -> AppResult<()> {
+        if !(Self::MIN_GAIN..=Self::MAX_GAIN).contains(&self.gain) {
+            return Err(AppError::ValidationError {
+                message: format!("gain must be between {} and {}", Self::MIN_GAIN, Self::MAX_GAIN),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// 録音時のノイズ除去
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoiseSuppressionSettings {
+    /// 空調などの定常的なノイズを取り除いてから保存する
+    pub enabled: bool,
+}
+
+/// 録音中のスリープ防止
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SleepPreventionSettings {
+    /// 録音している間はスリープさせない
+    pub enabled: bool,
+}
+
+impl Default for SleepPreventionSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// 録音の停止時・取り込み時に、書き起こし用のコピーの音量をそろえる設定
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LoudnessSettings {
+    pub enabled: bool,
+    /// 目標のラウドネス（LUFS）
+    pub target_lufs: f32,
+}
+
+impl Default for LoudnessSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            target_lufs: -23.0,
+        }
+    }
+}
+
+impl LoudnessSettings {
+    pub fn validate(&self) -> AppResult<()> {
+        if !(-40.0..=-10.0).contains(&self.target_lufs) {
+            return Err(AppError::ValidationError {
+                message: "target_lufs must be between -40 and -10".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// GPU での Whisper の高速化（Metal / CUDA）と、モデルごとの計測結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhisperAccelerationSettings {
+    pub enabled: bool,
+    #[serde(default)]
+    pub benchmarks: Vec<AccelerationBenchmark>,
+}
+
+impl Default for WhisperAccelerationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            benchmarks: Vec::new(),
+        }
+    }
+}
+
+impl WhisperAccelerationSettings {
+    /// 同じモデルの以前の計測結果は置き換える
+    pub fn record_benchmark(&mut self, benchmark: AccelerationBenchmark) {
+        self.benchmarks.retain(|existing| existing.model != benchmark.model);
+        self.benchmarks.push(benchmark);
+        self.benchmarks.sort_by(|a, b| a.model.cmp(&b.model));
+    }
+
+    pub fn speedup_for(&self, model: &str) -> Option<f64> {
+        self.benchmarks
+            .iter()
+            .find(|benchmark| benchmark.model == model)
+            .map(AccelerationBenchmark::speedup)
+    }
+}
+
+/// 書き起こしの方式（Python の whisper か、アプリに組み込んだ whisper.cpp か）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WhisperBackendSettings {
+    #[serde(default)]
+    pub backend: WhisperBackend,
+}
+
+/// 外部ツールからの操作用ローカルサーバーの設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlServerSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for ControlServerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8768,
+        }
+    }
+}
+
+/// 一括書き起こしの設定
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchTranscriptionSettings {
+    /// 同時実行数（未指定ならCPUコア数とモデルサイズから自動決定）
+    pub max_workers: Option<usize>,
+}
+
+/// 録音ディレクトリの容量上限
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageSettings {
+    /// 上限（バイト）。None なら警告しない
+    pub quota_bytes: Option<u64>,
+    /// 警告する使用率（0.0〜1.0）
+    pub warning_thresholds: Vec<f64>,
+    /// 容量を空ける候補にする録音の経過日数
+    pub candidate_min_age_days: i64,
+    /// ディスクの空き容量がこれを下回ったら録音を始めず、録音中なら停止する（None なら確認しない）
+    #[serde(default = "default_min_free_bytes")]
+    pub min_free_bytes: Option<u64>,
+}
+
+fn default_min_free_bytes() -> Option<u64> {
+    Some(500 * 1024 * 1024)
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            quota_bytes: None,
+            warning_thresholds: vec![0.8, 0.95],
+            candidate_min_age_days: 30,
+            min_free_bytes: default_min_free_bytes(),
+        }
+    }
+}
+
+/// Confluence 連携の設定（API トークンはOSのキーチェーンに保存）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfluenceSettings {
+    /// 例: https://example.atlassian.net/wiki
+    pub base_url: String,
+    /// API トークンの持ち主（Cloud ではメールアドレス）
+    pub username: String,
+    pub space_key: String,
+    /// 指定するとこのページの子ページとして作成する
+    pub parent_page_id: Option<String>,
+}
+
+impl ConfluenceSettings {
+    pub fn validate(&self) -> AppResult<()> {
+        let url = reqwest::Url::parse(self.base_url.trim()).map_err(|e| AppError::ValidationError {
+            message: format!("Invalid Confluence URL: {}", e),
+        })?;
+        if url.scheme() != "https" {
+            return Err(AppError::ValidationError {
+                message: "Confluence URL must use https".to_string(),
+            });
+        }
+        if self.username.trim().is_empty() || self.space_key.trim().is_empty() {
+            return Err(AppError::ValidationError {
+                message: "Confluence username and space key are required".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// ボイスメモの設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceMemoSettings {
+    /// 書き起こしに使う Whisper モデル（速さを優先）
+    pub model_size: String,
+    /// メモの録音に付けるカテゴリ（空なら付けない）
+    pub category: String,
+    /// 指定すると、このフォルダの日付ごとの Markdown（YYYY-MM-DD.md）にも追記する
+    pub daily_notes_directory: Option<String>,
+    pub language: Option<String>,
+    /// このピーク音量を下回る間を無音とみなす
+    pub silence_threshold: f32,
+    /// 話し終えてから無音がこの秒数続いたら止める
+    pub silence_timeout_secs: u64,
+    /// 無音にならなくてもこの秒数で止める
+    pub max_duration_secs: u64,
+}
+
+impl Default for VoiceMemoSettings {
+    fn default() -> Self {
+        Self {
+            model_size: "tiny".to_string(),
+            category: "memos".to_string(),
+            daily_notes_directory: None,
+            language: None,
+            silence_threshold: 0.02,
+            silence_timeout_secs: 2,
+            max_duration_secs: 120,
+        }
+    }
+}
+
+impl VoiceMemoSettings {
+    pub fn validate(&self) -> AppResult<()> {
+        if !(0.0..1.0).contains(&self.silence_threshold) {
+            return Err(AppError::ValidationError {
+                message: "Silence threshold must be between 0.0 and 1.0".to_string(),
+            });
+        }
+        if self.silence_timeout_secs == 0 || !(5..=600).contains(&self.max_duration_secs) {
+            return Err(AppError::ValidationError {
+                message: "Silence timeout must be at least 1 second and the maximum duration between 5 and 600 seconds".to_string(),
+            });
+        }
+        if let Some(directory) = self.daily_notes_directory.as_deref().filter(|dir| !dir.trim().is_empty()) {
+            if !Path::new(directory.trim()).is_absolute() {
+                return Err(AppError::ValidationError {
+                    message: "Daily notes directory must be an absolute path".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 定期ダイジェストの設定
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DigestScheduleSettings {
+    pub enabled: bool,
+    pub frequency: DigestFrequency,
+    /// 届け先（未設定の間は作らない）
+    pub delivery: Option<DigestDelivery>,
+    /// 要約に使うモデル（未指定なら既定）
+    pub model_config: Option<LLMConfig>,
+    /// 最後に作った回の予定時刻
+    pub last_run_at: Option<DateTime<Utc>>,
+}
+
+/// ダイジェストを作る頻度と時刻（ローカル時刻）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DigestFrequency {
+    /// weekday: 0 = 月曜 〜 6 = 日曜
+    Weekly { weekday: u32, hour: u32, minute: u32 },
+    /// day: 1〜28（月の長さで飛ばないよう28日まで）
+    Monthly { day: u32, hour: u32, minute: u32 },
+}
+
+impl Default for DigestFrequency {
+    fn default() -> Self {
+        Self::Weekly { weekday: 0, hour: 9, minute: 0 }
+    }
+}
+
+impl DigestFrequency {
+    pub fn validate(&self) -> AppResult<()> {
+        let (hour, minute) = match *self {
+            Self::Weekly { weekday, hour, minute } => {
+                if weekday > 6 {
+                    return Err(AppError::ValidationError {
+                        message: "Digest weekday must be between 0 (Monday) and 6 (Sunday)".to_string(),
+                    });
+                }
+                (hour, minute)
+            }
+            Self::Monthly { day, hour, minute } => {
+                if !(1..=28).contains(&day) {
+                    return Err(AppError::ValidationError {
+                        message: "Digest day of month must be between 1 and 28".to_string(),
+                    });
+                }
+                (hour, minute)
+            }
+        };
+        if hour > 23 || minute > 59 {
+            return Err(AppError::ValidationError {
+                message: "Digest time must be between 00:00 and 23:59".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// ダイジェストの届け先（SMTP のパスワードはOSのキーチェーンに保存）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DigestDelivery {
+    /// フォルダに Markdown で保存
+    File { directory: String },
+    Email {
+        smtp_host: String,
+        smtp_port: u16,
+        username: Option<String>,
+        from: String,
+        to: Vec<String>,
+    },
+    /// ダイジェストを JSON で POST
+    Webhook { url: String },
+}
+
+impl DigestDelivery {
+    pub fn validate(&self) -> AppResult<()> {
+        match self {
+            Self::File { directory } => {
+                if !Path::new(directory.trim()).is_absolute() {
+                    return Err(AppError::ValidationError {
+                        message: "Digest directory must be an absolute path".to_string(),
+                    });
+                }
+            }
+            Self::Email { smtp_host, from, to, .. } => {
+                if smtp_host.trim().is_empty() || from.trim().is_empty() || to.is_empty() {
+                    return Err(AppError::ValidationError {
+                        message: "SMTP server, sender and at least one recipient are required".to_string(),
+                    });
+                }
+            }
+            Self::Webhook { url } => {
+                let parsed = reqwest::Url::parse(url.trim()).map_err(|e| AppError::ValidationError {
+                    message: format!("Invalid webhook URL: {}", e),
+                })?;
+                if !matches!(parsed.scheme(), "http" | "https") {
+                    return Err(AppError::ValidationError {
+                        message: "Digest webhook URL must use http or https".to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Google Docs 連携の設定（トークン自体はOSのキーチェーンに保存）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GoogleDocsSettings {
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+impl AppSettings {
+    /// 設定ファイルから読み込み
+    pub async fn load_from_file<P: AsRef<Path>>(path: P) -> AppResult<Self> {
+        let path_ref = path.as_ref();
+
+        if !path_ref.exists() {
+            log::info!("📄 App settings file not found, using defaults");
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path_ref).await?;
+        let settings: AppSettings = serde_json::from_str(&content)?;
+
+        log::info!("✅ App settings loaded from: {:?}", path_ref);
+        Ok(settings)
+    }
+
+    /// 設定ファイルに保存
+    pub async fn save_to_file<P: AsRef<Path>>(&self, path: P) -> AppResult<()> {
+        let path_ref = path.as_ref();
+
+        if let Some(parent) = path_ref.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path_ref, content).await?;
+
+        log::info!("💾 App settings saved to: {:?}", path_ref);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AppSettingsManager {
+    settings: AppSettings,
+    settings_path: PathBuf,
+}
+
+impl AppSettingsManager {
+    pub fn new(settings_path: PathBuf) -> Self {
+        Self {
+            settings: AppSettings::default(),
+            settings_path,
+        }
+    }
+
+    /// 設定を読み込み
+    pub async fn load_settings(&mut self) -> AppResult<()> {
+        self.settings = AppSettings::load_from_file(&self.settings_path).await?;
+        Ok(())
+    }
+
+    /// 設定を保存
+    pub async fn save_settings(&self) -> AppResult<()> {
+        self.settings.save_to_file(&self.settings_path).await
+    }
+
+    /// 現在の設定を取得
+    pub fn get_settings(&self) -> &AppSettings {
+        &self.settings
+    }
+
+    /// 設定を更新
+    pub fn update_settings<F>(&mut self, updater: F)
+    where
+        F: FnOnce(&mut AppSettings),
+    {
+        updater(&mut self.settings);
+    }
+}
@@ -1,24 +1,175 @@
 use crate::errors::{AppError, AppResult};
+use crate::services::capture_source::{
+    self, AudioDevice, AudioSourceType, CaptureSource, DualSourceMode, ProcessCapture, PROCESS_CAPTURE_SAMPLE_RATE,
+};
+use crate::services::recording_encoder::RecordingFormat;
+use crate::services::recording_quality::{AudioFormat, RecordingQuality};
+use crate::services::recording_spool::{self, SpoolTarget};
+use crate::services::recording_tracks;
+use crate::services::app_settings::InputGainSettings;
+use crate::services::capture_feed::{CaptureFeed, FEED_SAMPLE_RATE};
+use crate::services::device_fallback::{self, CaptureDeviceEvent};
+use crate::services::disk_space::{self, DiskSpaceStatus, LowDiskSpace};
+use crate::services::echo_cancellation;
+use crate::services::input_gain::GainStage;
+use crate::services::noise_suppression::DenoiseStage;
+use crate::services::{demo_mode, pipewire, recording_segments};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::StreamConfig;
 use hound::{WavSpec, WavWriter};
 use std::fs::File;
 use std::io::BufWriter;
 use std::path::Path;
-use std::sync::{Arc, Mutex};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
-use std::collections::VecDeque;
 use std::thread::{self, JoinHandle};
+use tokio::sync::broadcast;
 
 const SAMPLE_RATE: u32 = 16000; // 16kHz for Whisper compatibility
-const CHANNELS: u16 = 1; // Mono
+
+/// 録音スレッドから進捗と音量を伝え、一時停止と入力ゲインを受け取る
+#[derive(Clone)]
+struct CaptureMeters {
+    captured_frames: Arc<AtomicU64>,
+    capture_sample_rate: Arc<AtomicU32>,
+    input_level: Arc<AtomicU32>,
+    /// 一時停止中は受け取った音声を捨てる
+    paused: Arc<AtomicBool>,
+    input_gain: Arc<RwLock<InputGainSettings>>,
+    /// スプールへ書く前にノイズ除去をかけるか
+    noise_suppression: Arc<AtomicBool>,
+    /// 入力デバイスの取り外しと開き直しの通知
+    device_events: broadcast::Sender<CaptureDeviceEvent>,
+    /// 録音中の音声をチャンクで配信する（入力元だけ）
+    feed: Option<CaptureFeed>,
+}
+
+impl CaptureMeters {
+    /// 画面に表示しない入力用（同時録音のシステム音声）。一時停止は共有する
+    fn detached(&self) -> Self {
+        Self {
+            captured_frames: Arc::new(AtomicU64::new(0)),
+            capture_sample_rate: Arc::new(AtomicU32::new(0)),
+            input_level: Arc::new(AtomicU32::new(0)),
+            paused: self.paused.clone(),
+            input_gain: self.input_gain.clone(),
+            noise_suppression: self.noise_suppression.clone(),
+            device_events: self.device_events.clone(),
+            feed: None,
+        }
+    }
+}
+
+/// モノラルのサンプルを `channels` 個のチャンネルへ複製する
+fn expand_channels(mono: &[f32], channels: u16) -> Vec<f32> {
+    if channels <= 1 {
+        return mono.to_vec();
+    }
+    mono.iter()
+        .flat_map(|sample| std::iter::repeat_n(*sample, channels as usize))
+        .collect()
+}
+
+/// チャンネル数を変える（1チャンネルへは平均して混ぜ、それ以外は先頭から使い、足りなければ最後のチャンネルを複製する）
+fn convert_channels(samples: &[f32], from: u16, to: u16) -> Vec<f32> {
+    if from == to {
+        return samples.to_vec();
+    }
+    let (from, to) = (from.max(1) as usize, to.max(1) as usize);
+    samples
+        .chunks(from)
+        .flat_map(|frame| {
+            let mixed = frame.iter().sum::<f32>() / frame.len() as f32;
+            (0..to).map(move |channel| if to == 1 { mixed } else { frame[channel.min(frame.len() - 1)] })
+        })
+        .collect()
+}
+
+/// チャンネルが交互に並んだサンプルをチャンネルごとにリサンプリングする
+fn resample_interleaved(samples: &[f32], channels: usize, from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if channels <= 1 {
+        return AudioCapture::downsample(samples, from_rate, to_rate);
+    }
+    let resampled: Vec<Vec<f32>> = (0..channels)
+        .map(|channel| {
+            let plane: Vec<f32> = samples.iter().skip(channel).step_by(channels).copied().collect();
+            AudioCapture::downsample(&plane, from_rate, to_rate)
+        })
+        .collect();
+    let frames = resampled.iter().map(Vec::len).min().unwrap_or(0);
+    (0..frames)
+        .flat_map(|frame| resampled.iter().map(move |plane| plane[frame]))
+        .collect()
+}
+
+/// 1本の入力の録音方法
+enum StreamSource {
+    /// 外部プロセスの標準出力
+    Process(Command),
+    /// CPAL のデバイス（`loopback` なら既定の出力デバイス、`input` があればその名前の入力デバイス）
+    Device { loopback: bool, input: Option<String> },
+}
+
+impl StreamSource {
+    fn of(source: &CaptureSource) -> AppResult<Self> {
+        Ok(match source.process_command()? {
+            Some(command) => Self::Process(command),
+            None => Self::Device {
+                loopback: source.is_loopback(),
+                input: match source {
+                    CaptureSource::InputDevice { name } => Some(name.clone()),
+                    _ => None,
+                },
+            },
+        })
+    }
+}
 
 /// CPAL音声キャプチャ実装（スレッドベース）
 pub struct AudioCapture {
     is_recording: Arc<Mutex<bool>>,
     start_time: Arc<Mutex<Option<Instant>>>,
-    audio_buffer: Arc<Mutex<VecDeque<f32>>>,
+    /// 録音中の音声のチャンク配信
+    feed: CaptureFeed,
     thread_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// デバイスから受け取ったフレーム数（進捗表示用）
+    captured_frames: Arc<AtomicU64>,
+    /// キャプチャ中のデバイスのサンプルレート（未確定なら 0）
+    capture_sample_rate: Arc<AtomicU32>,
+    /// 直近に受け取ったバッファのピーク音量（f32 のビット列）
+    input_level: Arc<AtomicU32>,
+    /// 一時停止中か
+    paused: Arc<AtomicBool>,
+    /// 一時停止を始めた時刻（一時停止中のみ）
+    pause_started: Option<Instant>,
+    /// これまでに一時停止していた時間の合計
+    paused_duration: Duration,
+    /// 録音の入力元（次の録音から反映）
+    source: CaptureSource,
+    /// システム音声も一緒に録音するか（次の録音から反映）
+    dual_source: DualSourceMode,
+    /// 同時録音したシステム音声を使ってマイクのエコーを取り除くか（次の録音から反映）
+    echo_cancellation: bool,
+    /// 別のトラックとして同時に録音する入力（次の録音から反映）
+    extra_tracks: Vec<CaptureSource>,
+    /// 保存する音質（次の録音から反映）
+    quality: RecordingQuality,
+    /// 停止後に変換する保存形式
+    encoding: RecordingFormat,
+    /// 録音中のファイルの形式
+    output_format: AudioFormat,
+    /// 録音を続けるのに必要なディスクの空き容量（0 なら確認しない）
+    min_free_bytes: Arc<AtomicU64>,
+    low_disk_sender: broadcast::Sender<LowDiskSpace>,
+    device_events: broadcast::Sender<CaptureDeviceEvent>,
+    /// 入力ゲイン（録音中でもすぐに反映）
+    input_gain: Arc<RwLock<InputGainSettings>>,
+    /// ノイズ除去（録音中でもすぐに反映）
+    noise_suppression: Arc<AtomicBool>,
+    /// 長い録音を分ける区間の長さ（次の録音から反映）
+    max_segment: Option<Duration>,
 }
 
 // TODO: https://chatgpt.com/c/68a1cb5b-ed9c-832e-91a2-e2277eb5cb10
@@ -28,12 +179,160 @@ impl AudioCapture {
         Ok(Self {
             is_recording: Arc::new(Mutex::new(false)),
             start_time: Arc::new(Mutex::new(None)),
-            audio_buffer: Arc::new(Mutex::new(VecDeque::new())),
+            feed: CaptureFeed::new(),
             thread_handle: Arc::new(Mutex::new(None)),
+            captured_frames: Arc::new(AtomicU64::new(0)),
+            capture_sample_rate: Arc::new(AtomicU32::new(0)),
+            input_level: Arc::new(AtomicU32::new(0)),
+            paused: Arc::new(AtomicBool::new(false)),
+            pause_started: None,
+            paused_duration: Duration::ZERO,
+            source: CaptureSource::default(),
+            dual_source: DualSourceMode::default(),
+            echo_cancellation: false,
+            extra_tracks: Vec::new(),
+            quality: RecordingQuality::default(),
+            encoding: RecordingFormat::default(),
+            output_format: RecordingQuality::default().format(),
+            min_free_bytes: Arc::new(AtomicU64::new(0)),
+            low_disk_sender: broadcast::channel(8).0,
+            device_events: broadcast::channel(8).0,
+            input_gain: Arc::new(RwLock::new(InputGainSettings::default())),
+            noise_suppression: Arc::new(AtomicBool::new(false)),
+            max_segment: None,
         })
     }
 
+    pub fn source(&self) -> &CaptureSource {
+        &self.source
+    }
+
+    pub fn set_source(&mut self, source: CaptureSource) {
+        self.source = source;
+    }
+
+    pub fn dual_source(&self) -> DualSourceMode {
+        self.dual_source
+    }
+
+    pub fn set_dual_source(&mut self, dual_source: DualSourceMode) {
+        self.dual_source = dual_source;
+    }
+
+    pub fn echo_cancellation(&self) -> bool {
+        self.echo_cancellation
+    }
+
+    pub fn set_echo_cancellation(&mut self, enabled: bool) {
+        self.echo_cancellation = enabled;
+    }
+
+    pub fn extra_tracks(&self) -> &[CaptureSource] {
+        &self.extra_tracks
+    }
+
+    pub fn set_extra_tracks(&mut self, tracks: Vec<CaptureSource>) {
+        self.extra_tracks = tracks;
+    }
+
+    pub fn quality(&self) -> RecordingQuality {
+        self.quality
+    }
+
+    pub fn set_quality(&mut self, quality: RecordingQuality) {
+        self.quality = quality;
+    }
+
+    pub fn encoding(&self) -> RecordingFormat {
+        self.encoding
+    }
+
+    pub fn set_encoding(&mut self, encoding: RecordingFormat) {
+        self.encoding = encoding;
+    }
+
+    pub fn input_gain(&self) -> InputGainSettings {
+        self.input_gain.read().map(|settings| *settings).unwrap_or_default()
+    }
+
+    pub fn set_input_gain(&self, settings: InputGainSettings) {
+        if let Ok(mut input_gain) = self.input_gain.write() {
+            *input_gain = settings;
+        }
+    }
+
+    pub fn noise_suppression(&self) -> bool {
+        self.noise_suppression.load(Ordering::Relaxed)
+    }
+
+    pub fn set_noise_suppression(&self, enabled: bool) {
+        self.noise_suppression.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn max_segment_duration(&self) -> Option<Duration> {
+        self.max_segment
+    }
+
+    pub fn set_max_segment_duration(&mut self, max_segment: Option<Duration>) {
+        self.max_segment = max_segment;
+    }
+
+    pub fn min_free_bytes(&self) -> u64 {
+        self.min_free_bytes.load(Ordering::Relaxed)
+    }
+
+    /// 録音を続けるのに必要な空き容量（録音中でもすぐに反映）
+    pub fn set_min_free_bytes(&self, min_free_bytes: Option<u64>) {
+        self.min_free_bytes.store(min_free_bytes.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    /// 録音中に空き容量が減ったときの警告と停止の送信元
+    pub fn low_disk_sender(&self) -> broadcast::Sender<LowDiskSpace> {
+        self.low_disk_sender.clone()
+    }
+
+    pub fn device_event_sender(&self) -> broadcast::Sender<CaptureDeviceEvent> {
+        self.device_events.clone()
+    }
+
+    /// 録音中の音声をチャンクで受け取るためのリングバッファ
+    pub fn capture_feed(&self) -> CaptureFeed {
+        self.feed.clone()
+    }
+
     pub async fn start_recording(&mut self, output_path: &Path) -> AppResult<()> {
+        self.start_recording_with_quality(output_path, self.quality).await
+    }
+
+    /// 今回の録音だけ音質を指定して録音を始める
+    pub async fn start_recording_with_quality(&mut self, output_path: &Path, quality: RecordingQuality) -> AppResult<()> {
+        // デモモードは入力元を使わない
+        let demo = demo_mode::is_enabled();
+        let (primary, secondary, tracks) = if demo {
+            (None, None, Vec::new())
+        } else {
+            self.source.ensure_supported()?;
+            let secondary = if self.dual_source == DualSourceMode::Off {
+                None
+            } else {
+                self.dual_source.validate_with(&self.source)?;
+                CaptureSource::SystemAudio.ensure_supported()?;
+                Some(StreamSource::of(&CaptureSource::SystemAudio)?)
+            };
+            recording_tracks::validate_extra_tracks(&self.source, &self.extra_tracks)?;
+            let tracks = self
+                .extra_tracks
+                .iter()
+                .map(|track| {
+                    track.ensure_supported()?;
+                    StreamSource::of(track)
+                })
+                .collect::<AppResult<Vec<_>>>()?;
+            (Some(StreamSource::of(&self.source)?), secondary, tracks)
+        };
+        let dual_source = self.dual_source;
+        let echo_cancellation = self.echo_cancellation;
+
         {
             let mut is_recording = self.is_recording.lock()
                 .map_err(|_| AppError::Recording {
@@ -58,14 +357,23 @@ impl AudioCapture {
             *start_time = Some(Instant::now());
         }
 
-        // オーディオバッファをクリア
-        {
-            let mut buffer = self.audio_buffer.lock()
-                .map_err(|_| AppError::Recording {
-                    message: "Failed to acquire audio buffer lock".to_string(),
-                })?;
-            buffer.clear();
-        }
+        // 前の録音のチャンクの残りを消す
+        self.feed.reset();
+        self.captured_frames.store(0, Ordering::Relaxed);
+        self.capture_sample_rate.store(0, Ordering::Relaxed);
+        self.input_level.store(0, Ordering::Relaxed);
+        self.paused.store(false, Ordering::Relaxed);
+        self.pause_started = None;
+        self.paused_duration = Duration::ZERO;
+        // デモモードは書き起こし向けの音質で書き出す
+        let format = if demo {
+            RecordingQuality::Voice.format()
+        } else {
+            let format = quality.format();
+            let channels = if secondary.is_some() { dual_source.channels(format.channels) } else { format.channels };
+            AudioFormat { channels, ..format }
+        };
+        self.output_format = format;
 
         // 出力パスの事前検証（親ディレクトリ作成＋書き込み可否テスト）
         if let Some(parent) = output_path.parent() {
@@ -82,19 +390,52 @@ impl AudioCapture {
         // CPALを使った音声録音をスレッドで開始
         let output_path_clone = output_path.to_path_buf();
         let output_path_log = output_path.to_path_buf();
+        let max_segment = self.max_segment;
         let is_recording_clone = self.is_recording.clone();
-        let audio_buffer_clone = self.audio_buffer.clone();
+        let meters = CaptureMeters {
+            captured_frames: self.captured_frames.clone(),
+            capture_sample_rate: self.capture_sample_rate.clone(),
+            input_level: self.input_level.clone(),
+            paused: self.paused.clone(),
+            input_gain: self.input_gain.clone(),
+            noise_suppression: self.noise_suppression.clone(),
+            device_events: self.device_events.clone(),
+            feed: Some(self.feed.clone()),
+        };
 
         // 録音スレッドを開始（チャネル通知なしでUIブロック回避）
         let handle = thread::spawn(move || {
             log::info!("Recording thread starting for file: {:?}", output_path_clone);
-            if let Err(e) = Self::record_audio_thread(output_path_clone, is_recording_clone, audio_buffer_clone) {
+            let result = match primary {
+                None => Self::record_demo_thread(output_path_clone, is_recording_clone, meters),
+                Some(primary) => Self::record_spooled(
+                    primary,
+                    secondary,
+                    tracks,
+                    dual_source,
+                    echo_cancellation,
+                    format,
+                    SpoolTarget::new(&output_path_clone, max_segment),
+                    is_recording_clone,
+                    meters,
+                ),
+            };
+            if let Err(e) = result {
                 log::error!("Audio recording thread failed: {}", e);
             } else {
                 log::info!("Recording thread completed successfully");
             }
         });
 
+        if let Some(dir) = output_path.parent() {
+            Self::spawn_disk_watchdog(
+                dir.to_path_buf(),
+                self.is_recording.clone(),
+                self.min_free_bytes.clone(),
+                self.low_disk_sender.clone(),
+            );
+        }
+
         // スレッドハンドルを保存
         {
             let mut thread_handle = self.thread_handle.lock()
@@ -112,20 +453,14 @@ impl AudioCapture {
     }
 
     pub async fn stop_recording(&mut self) -> AppResult<()> {
-        {
+        let was_recording = {
             let mut is_recording = self.is_recording.lock()
                 .map_err(|_| AppError::Recording {
                     message: "Failed to acquire recording lock".to_string(),
                 })?;
-
-            if !*is_recording {
-                return Err(AppError::Recording {
-                    message: "No recording in progress".to_string(),
-                });
-            }
-
-            *is_recording = false;
-        }
+            std::mem::replace(&mut *is_recording, false)
+        };
+        self.resume();
 
         // スレッドハンドルを取得して終了を待つ
         let handle = {
@@ -136,6 +471,13 @@ impl AudioCapture {
             thread_handle.take()
         };
 
+        // 空き容量不足で録音スレッドが先に止まった場合も、スレッドの終了を待って保存を済ませる
+        if !was_recording && handle.is_none() {
+            return Err(AppError::Recording {
+                message: "No recording in progress".to_string(),
+            });
+        }
+
         if let Some(handle) = handle {
             // 非同期でスレッドの終了を待つ
             tokio::task::spawn_blocking(move || {
@@ -150,6 +492,7 @@ impl AudioCapture {
             })?;
         }
 
+        self.feed.flush();
         log::info!("CPAL audio recording stopped");
         Ok(())
     }
@@ -160,33 +503,456 @@ impl AudioCapture {
             .unwrap_or(false)
     }
 
+    /// 録音を始めてからの経過時間（一時停止していた時間を除く）
     pub fn get_recording_duration(&self) -> Duration {
         let start_time = self.start_time.lock()
             .ok()
             .and_then(|guard| *guard);
 
         if let Some(start) = start_time {
-            start.elapsed()
+            start.elapsed().saturating_sub(self.paused_duration())
         } else {
             Duration::from_secs(0)
         }
     }
 
-    // 別スレッドで実行される録音機能
-    fn record_audio_thread(
+    /// 一時停止する（停止・再開までの音声は保存しない）
+    pub fn pause(&mut self) -> AppResult<()> {
+        if !self.is_recording() {
+            return Err(AppError::Recording {
+                message: "No recording in progress".to_string(),
+            });
+        }
+        if !self.paused.swap(true, Ordering::Relaxed) {
+            self.pause_started = Some(Instant::now());
+            self.input_level.store(0, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// 一時停止から再開する（一時停止していなければ何もしない）
+    pub fn resume(&mut self) {
+        if self.paused.swap(false, Ordering::Relaxed) {
+            if let Some(started) = self.pause_started.take() {
+                self.paused_duration += started.elapsed();
+            }
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// 今回の録音で一時停止していた時間（一時停止中ならその分も含む）
+    pub fn paused_duration(&self) -> Duration {
+        self.paused_duration + self.pause_started.map(|started| started.elapsed()).unwrap_or_default()
+    }
+
+    /// 保存されるWAVのサンプルレートに換算した、ここまでの録音サンプル数
+    pub fn recorded_sample_count(&self) -> u64 {
+        let rate = self.capture_sample_rate.load(Ordering::Relaxed);
+        if rate == 0 {
+            return 0;
+        }
+        self.captured_frames.load(Ordering::Relaxed) * self.output_format.sample_rate as u64 / rate as u64
+    }
+
+    /// 直近の入力のピーク音量（0.0〜1.0。録音中でなければ 0）
+    pub fn input_level(&self) -> f32 {
+        if !self.is_recording() {
+            return 0.0;
+        }
+        f32::from_bits(self.input_level.load(Ordering::Relaxed))
+    }
+
+    /// 保存されるWAVの形式
+    pub fn output_format(&self) -> AudioFormat {
+        self.output_format
+    }
+
+    /// 録音中に空き容量を確認し、少なくなったら警告、下限を下回ったら録音を止める
+    fn spawn_disk_watchdog(
+        dir: std::path::PathBuf,
+        is_recording: Arc<Mutex<bool>>,
+        min_free_bytes: Arc<AtomicU64>,
+        sender: broadcast::Sender<LowDiskSpace>,
+    ) {
+        thread::spawn(move || {
+            let mut warned = false;
+            let mut last_check: Option<Instant> = None;
+            while is_recording.lock().map(|guard| *guard).unwrap_or(false) {
+                if last_check.is_some_and(|checked| checked.elapsed() < Duration::from_millis(disk_space::DISK_CHECK_INTERVAL_MS)) {
+                    thread::sleep(Duration::from_millis(100));
+                    continue;
+                }
+                last_check = Some(Instant::now());
+
+                let min_free_bytes = min_free_bytes.load(Ordering::Relaxed);
+                let free_bytes = match disk_space::free_space(&dir) {
+                    Ok(free_bytes) => free_bytes,
+                    Err(e) => {
+                        log::warn!("⚠️ Failed to check free disk space: {}", e);
+                        continue;
+                    }
+                };
+                let notice = |stopped| LowDiskSpace {
+                    free_bytes,
+                    min_free_bytes,
+                    stopped,
+                    recording_id: None,
+                };
+                match disk_space::status(free_bytes, min_free_bytes) {
+                    DiskSpaceStatus::Ok => {}
+                    DiskSpaceStatus::Low => {
+                        if !warned {
+                            warned = true;
+                            log::warn!("💽 Free disk space is running low: {} MB", free_bytes / (1024 * 1024));
+                            let _ = sender.send(notice(false));
+                        }
+                    }
+                    DiskSpaceStatus::Exhausted => {
+                        log::error!("💽 Free disk space dropped below {} bytes, stopping the recording", min_free_bytes);
+                        if let Ok(mut recording) = is_recording.lock() {
+                            *recording = false;
+                        }
+                        let _ = sender.send(notice(true));
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    /// デモモード：マイクを使わず、録音時間分（最低1秒）の決まった波形を書き出す
+    fn record_demo_thread(
         output_path: std::path::PathBuf,
         is_recording: Arc<Mutex<bool>>,
-        _audio_buffer: Arc<Mutex<VecDeque<f32>>>,
+        meters: CaptureMeters,
     ) -> AppResult<()> {
-        log::info!("Recording thread started, output path: {:?}", output_path);
-        
+        meters.capture_sample_rate.store(SAMPLE_RATE, Ordering::Relaxed);
+        while is_recording.lock().map(|guard| *guard).unwrap_or(false) {
+            thread::sleep(Duration::from_millis(100));
+            if meters.paused.load(Ordering::Relaxed) {
+                continue;
+            }
+            let start = meters.captured_frames.fetch_add(SAMPLE_RATE as u64 / 10, Ordering::Relaxed);
+            let samples: Vec<f32> = (start..start + SAMPLE_RATE as u64 / 10)
+                .map(|index| demo_mode::demo_sample(index, SAMPLE_RATE))
+                .collect();
+            let peak = samples.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+            meters.input_level.store(peak.to_bits(), Ordering::Relaxed);
+            if let Some(feed) = &meters.feed {
+                feed.push(&samples);
+            }
+        }
+
+        let frames = meters.captured_frames.load(Ordering::Relaxed).max(SAMPLE_RATE as u64);
+        let samples: Vec<f32> = (0..frames).map(|index| demo_mode::demo_sample(index, SAMPLE_RATE)).collect();
+        Self::save_samples_to_file(&samples, RecordingQuality::Voice.format(), &output_path)
+    }
+
+    /// 入力をスプールへ書きながら停止まで録音し、停止後に `format` の WAV へまとめる
+    ///
+    /// 途中でアプリが落ちてもスプールが残り、次の起動時に復元される。
+    ///
+    /// 区間の長さが設定されていれば、区間ごとに別の WAV（`recording_segments::segment_path`）にまとめる。
+    /// 追加のトラックは区間に分けず、それぞれモノラルの WAV（`recording_tracks::track_path`）にまとめる。
+    #[allow(clippy::too_many_arguments)]
+    fn record_spooled(
+        primary: StreamSource,
+        secondary: Option<StreamSource>,
+        tracks: Vec<StreamSource>,
+        mode: DualSourceMode,
+        echo_cancellation: bool,
+        format: AudioFormat,
+        target: SpoolTarget,
+        is_recording: Arc<Mutex<bool>>,
+        meters: CaptureMeters,
+    ) -> AppResult<()> {
+        let system_target = target.system();
+        let track_targets: Vec<SpoolTarget> = (1..=tracks.len())
+            .map(|index| SpoolTarget::new(&recording_tracks::track_path(&target.output, index), None))
+            .collect();
+        let track_threads: Vec<_> = tracks
+            .into_iter()
+            .zip(track_targets.clone())
+            .map(|(track, track_target)| {
+                let is_recording = is_recording.clone();
+                let meters = meters.detached();
+                thread::spawn(move || Self::capture_samples(track, 1, &track_target, is_recording, meters))
+            })
+            .collect();
+
+        let captured = match secondary {
+            Some(secondary) => Self::capture_dual(primary, secondary, &target, &system_target, is_recording, meters),
+            None => Self::capture_samples(primary, format.channels, &target, is_recording, meters),
+        };
+        let track_results: Vec<AppResult<()>> = track_threads
+            .into_iter()
+            .map(|handle| {
+                handle.join().unwrap_or_else(|_| {
+                    Err(AppError::Recording {
+                        message: "Track capture thread panicked".to_string(),
+                    })
+                })
+            })
+            .collect();
+        if let Err(e) = captured {
+            target.remove_all();
+            system_target.remove_all();
+            track_targets.iter().for_each(SpoolTarget::remove_all);
+            return Err(e);
+        }
+
+        // 追加のトラックは録音できなかったものだけを捨てる
+        let track_format = AudioFormat { channels: 1, ..format };
+        for (index, (track_target, result)) in track_targets.iter().zip(track_results).enumerate() {
+            let saved = result.and_then(|()| {
+                Self::finalize_spools(&track_target.spool_path(0), None, DualSourceMode::Off, false, track_format, &track_target.output)
+            });
+            if let Err(e) = saved {
+                log::warn!("⚠️ Track {} was not recorded: {}", index + 1, e);
+                track_target.remove_all();
+            }
+        }
+
+        for index in 0.. {
+            let primary_spool = target.spool_path(index);
+            let system_spool = system_target.spool_path(index);
+            if !primary_spool.exists() && !system_spool.exists() {
+                break;
+            }
+            let output = recording_segments::segment_path(&target.output, index);
+            let secondary_spool = Some(system_spool.as_path()).filter(|path| path.exists());
+            match Self::finalize_spools(&primary_spool, secondary_spool, mode, echo_cancellation, format, &output) {
+                Ok(()) => {}
+                // 切り替えた直後に停止した区間は空なので捨てる
+                Err(e) if index > 0 => {
+                    log::info!("Dropping empty recording segment {}: {}", index + 1, e);
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// スプールを `format` の WAV にまとめてスプールを削除する（録音の停止時とクラッシュ後の復元で使う）
+    ///
+    /// `secondary` があれば同時録音として `mode` で組み合わせる。`echo_cancellation` なら、組み合わせる前に
+    /// `secondary`（システム音声）を参照してマイクのエコーを取り除く。保存に失敗した場合はスプールを残す。
+    pub(crate) fn finalize_spools(
+        primary: &Path,
+        secondary: Option<&Path>,
+        mode: DualSourceMode,
+        echo_cancellation: bool,
+        format: AudioFormat,
+        output_path: &Path,
+    ) -> AppResult<()> {
+        let load = |path: &Path, channels: u16| match recording_spool::read_spool(path) {
+            Ok(spool) if !spool.samples.is_empty() => {
+                let resampled = resample_interleaved(
+                    &spool.samples,
+                    spool.channels.max(1) as usize,
+                    spool.sample_rate,
+                    format.sample_rate,
+                );
+                convert_channels(&resampled, spool.channels, channels)
+            }
+            Ok(_) => Vec::new(),
+            Err(e) => {
+                log::warn!("⚠️ {}", e);
+                Vec::new()
+            }
+        };
+
+        let samples = match secondary {
+            None => load(primary, format.channels),
+            Some(secondary) => {
+                // それぞれモノラルにしてから組み合わせる
+                let (mut primary, secondary) = (load(primary, 1), load(secondary, 1));
+                if echo_cancellation && !secondary.is_empty() {
+                    log::info!("🔇 Cancelling system audio echo from the microphone");
+                    primary = echo_cancellation::cancel_echo(&primary, &secondary, format.sample_rate);
+                }
+                log::info!("🎧 Combining microphone ({} samples) and system audio ({} samples): {:?}", primary.len(), secondary.len(), mode);
+                match mode {
+                    DualSourceMode::Off => expand_channels(&primary, format.channels),
+                    DualSourceMode::Mix => expand_channels(&capture_source::mix_sources(&primary, &secondary), format.channels),
+                    DualSourceMode::SeparateChannels => capture_source::interleave_sources(&primary, &secondary),
+                }
+            }
+        };
+
+        let remove_spools = || {
+            for path in std::iter::once(primary).chain(secondary) {
+                let _ = std::fs::remove_file(path);
+            }
+        };
+        if samples.is_empty() {
+            remove_spools();
+            return Err(AppError::Recording {
+                message: "No audio data recorded".to_string(),
+            });
+        }
+
+        Self::save_samples_to_file(&samples, format, output_path)?;
+        remove_spools();
+        log::info!("CPAL recording completed: {} samples", samples.len());
+        Ok(())
+    }
+
+    /// 1本の入力を停止まで録音し、デバイスのサンプルレートと `channels` 個のチャンネルでスプールへ書く
+    fn capture_samples(
+        stream: StreamSource,
+        channels: u16,
+        spool: &SpoolTarget,
+        is_recording: Arc<Mutex<bool>>,
+        meters: CaptureMeters,
+    ) -> AppResult<()> {
+        match stream {
+            StreamSource::Process(command) => Self::capture_process(command, spool, is_recording, meters),
+            StreamSource::Device { loopback, input } => {
+                Self::capture_device(loopback, input.as_deref(), channels, spool, is_recording, meters)
+            }
+        }
+    }
+
+    /// 入力元とシステム音声をそれぞれモノラルのスプールへ同時に録音する
+    ///
+    /// 進捗と音量は入力元のものを表示する。片方が録音できなかった場合は、もう片方だけで保存する
+    /// （ループバックは何も再生されていないと音声が届かない）。
+    fn capture_dual(
+        primary: StreamSource,
+        secondary: StreamSource,
+        primary_spool: &SpoolTarget,
+        system_spool: &SpoolTarget,
+        is_recording: Arc<Mutex<bool>>,
+        meters: CaptureMeters,
+    ) -> AppResult<()> {
+        let secondary_thread = {
+            let is_recording = is_recording.clone();
+            let system_spool = system_spool.clone();
+            let meters = meters.detached();
+            thread::spawn(move || Self::capture_samples(secondary, 1, &system_spool, is_recording, meters))
+        };
+        let primary = Self::capture_samples(primary, 1, primary_spool, is_recording, meters);
+        let secondary = secondary_thread.join().map_err(|_| AppError::Recording {
+            message: "System audio capture thread panicked".to_string(),
+        })?;
+
+        match (primary, secondary) {
+            (Err(e), Err(_)) => Err(e),
+            (Ok(()), Err(e)) => {
+                log::warn!("⚠️ System audio was not captured, saving the microphone only: {}", e);
+                Ok(())
+            }
+            (Err(e), Ok(())) => {
+                log::warn!("⚠️ Microphone was not captured, saving system audio only: {}", e);
+                Ok(())
+            }
+            (Ok(()), Ok(())) => Ok(()),
+        }
+    }
+
+    /// 外部プロセス（アプリ単位の録音など）が書き出す音声を録音する
+    fn capture_process(
+        command: Command,
+        spool: &SpoolTarget,
+        is_recording: Arc<Mutex<bool>>,
+        meters: CaptureMeters,
+    ) -> AppResult<()> {
+        meters.capture_sample_rate.store(PROCESS_CAPTURE_SAMPLE_RATE, Ordering::Relaxed);
+        let mut spool = spool.create(PROCESS_CAPTURE_SAMPLE_RATE, 1)?;
+        let mut denoise = DenoiseStage::new(meters.noise_suppression.clone(), PROCESS_CAPTURE_SAMPLE_RATE, 1);
+        let CaptureMeters { captured_frames, input_level, paused, feed, .. } = meters;
+        let mut write = |samples: Vec<f32>| {
+            Self::feed_samples(feed.as_ref(), &samples, 1, PROCESS_CAPTURE_SAMPLE_RATE);
+            spool.append(&samples)
+        };
+        let paused_for_reader = paused.clone();
+        let mut capture = ProcessCapture::spawn(command, move |chunk| {
+            if paused_for_reader.load(Ordering::Relaxed) {
+                return;
+            }
+            captured_frames.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+            let peak = chunk.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+            input_level.store(peak.to_bits(), Ordering::Relaxed);
+        })?;
+
+        while is_recording.lock().map(|guard| *guard).unwrap_or(false) {
+            thread::sleep(Duration::from_millis(100));
+            let samples = capture.take_samples();
+            if !paused.load(Ordering::Relaxed) {
+                write(denoise.process(samples))?;
+            }
+            if capture.has_exited() {
+                log::warn!("Capture process exited before recording was stopped");
+                break;
+            }
+        }
+
+        let rest = capture.finish()?;
+        if !paused.load(Ordering::Relaxed) {
+            write(denoise.process(rest))?;
+        }
+        write(denoise.finish())?;
+        spool.finish()
+    }
+
+    /// `loopback` なら既定の出力デバイスに流れる音声を録音する（WASAPI のループバック）
+    fn capture_device(
+        loopback: bool,
+        input: Option<&str>,
+        channels: u16,
+        spool: &SpoolTarget,
+        is_recording: Arc<Mutex<bool>>,
+        meters: CaptureMeters,
+    ) -> AppResult<()> {
+        let (device, config) = Self::open_device(loopback, input, channels)?;
+        Self::capture_stream(device, config, loopback, input, channels, spool, is_recording, meters)
+    }
+
+    /// 既定の入力デバイス（`loopback` なら既定の出力デバイス、`input` があればその名前の入力デバイス）と録音に使う設定
+    fn open_device(loopback: bool, input: Option<&str>, channels: u16) -> AppResult<(cpal::Device, StreamConfig)> {
         let host = cpal::default_host();
         log::info!("Got CPAL host");
-        
-        let device = host.default_input_device()
-            .ok_or_else(|| AppError::Recording {
-                message: "No default input device available".to_string(),
-            })?;
+
+        if loopback {
+            let device = host.default_output_device()
+                .ok_or_else(|| AppError::Recording {
+                    message: "No default output device available".to_string(),
+                })?;
+            log::info!("Using loopback of output device: {}", device.name().unwrap_or_else(|_| "Unknown".to_string()));
+
+            // ループバックは出力デバイスの共有モードの形式でしか開けない
+            let config = device.default_output_config()
+                .map_err(|e| AppError::Recording {
+                    message: format!("Failed to get output device config: {}", e),
+                })?;
+            let config = StreamConfig {
+                channels: config.channels(),
+                sample_rate: config.sample_rate(),
+                buffer_size: cpal::BufferSize::Default,
+            };
+            return Ok((device, config));
+        }
+
+        let device = match input {
+            Some(name) => host
+                .input_devices()
+                .map_err(|e| AppError::Recording {
+                    message: format!("Failed to enumerate input devices: {}", e),
+                })?
+                .find(|device| device.name().is_ok_and(|device_name| device_name == name))
+                .ok_or_else(|| AppError::Recording {
+                    message: format!("Input device not found: {}", name),
+                })?,
+            None => host.default_input_device()
+                .ok_or_else(|| AppError::Recording {
+                    message: "No default input device available".to_string(),
+                })?,
+        };
 
         log::info!("Using audio device: {}", device.name().unwrap_or_else(|_| "Unknown".to_string()));
 
@@ -211,13 +977,13 @@ impl AudioCapture {
         let config = if let Some(config_range) = available_configs.first() {
             // macOSのデフォルト設定（44.1kHz）を使用し、後でダウンサンプリング
             let sample_rate = config_range.max_sample_rate(); // 通常44100Hz
-            let channels = std::cmp::min(config_range.channels(), CHANNELS);
+            let stream_channels = std::cmp::min(config_range.channels(), channels);
             
-            log::info!("Selected config: channels={}, sample_rate={} (will downsample to {}Hz)", 
-                      channels, sample_rate.0, SAMPLE_RATE);
+            log::info!("Selected config: channels={}, sample_rate={} (resampled when saved)", 
+                      stream_channels, sample_rate.0);
             
             StreamConfig {
-                channels,
+                channels: stream_channels,
                 sample_rate,
                 buffer_size: cpal::BufferSize::Fixed(1024), // 固定バッファサイズ
             }
@@ -227,42 +993,180 @@ impl AudioCapture {
             });
         };
 
-        // 録音データ用のバッファ
+        Ok((device, config))
+    }
+
+    /// 入力ストリームを開いて停止まで録音し、最初のデバイスのサンプルレートと `channels` 個のチャンネルでスプールへ書く
+    ///
+    /// 録音中にデバイスが外れたら通知し、既定のデバイス（名前で指定したデバイスはそのデバイス）を
+    /// 開き直して同じスプールへ書き続ける。
+    #[allow(clippy::too_many_arguments)]
+    fn capture_stream(
+        device: cpal::Device,
+        config: StreamConfig,
+        loopback: bool,
+        input: Option<&str>,
+        channels: u16,
+        spool: &SpoolTarget,
+        is_recording: Arc<Mutex<bool>>,
+        meters: CaptureMeters,
+    ) -> AppResult<()> {
+        // コールバックから受け取ったサンプル（録音ループが定期的にスプールへ移す）
         let recorded_samples = Arc::new(Mutex::new(Vec::<f32>::new()));
+        // エラーのコールバックがデバイスの取り外しを伝える
+        let device_lost = Arc::new(AtomicBool::new(false));
+
+        let spool_rate = config.sample_rate.0;
+        let output_channels = channels.max(1);
+        let mut spool = spool.create(spool_rate, output_channels)?;
+        let mut denoise = DenoiseStage::new(meters.noise_suppression.clone(), spool_rate, output_channels);
+        meters.capture_sample_rate.store(spool_rate, Ordering::Relaxed);
+
+        let mut device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+        let mut stream_rate = spool_rate;
+        let mut stream = Some(Self::open_stream(
+            &device,
+            &config,
+            output_channels,
+            &recorded_samples,
+            &is_recording,
+            &meters,
+            &device_lost,
+        )?);
+        let mut last_retry = Instant::now();
+
+        log::info!("Audio stream started, beginning recording loop");
+
+        let feed = meters.feed.clone();
+        let mut write = |samples: Vec<f32>| {
+            Self::feed_samples(feed.as_ref(), &samples, output_channels, spool_rate);
+            spool.append(&samples)
+        };
+
+        let take_recorded = |stream_rate: u32| {
+            let samples = recorded_samples
+                .lock()
+                .map(|mut guard| std::mem::take(&mut *guard))
+                .unwrap_or_default();
+            // 開き直したデバイスのサンプルレートが違えば、最初のデバイスのレートに揃える
+            if stream_rate == spool_rate {
+                samples
+            } else {
+                resample_interleaved(&samples, output_channels as usize, stream_rate, spool_rate)
+            }
+        };
+
+        // 録音が停止されるまで、溜まったサンプルをスプールへ書き続ける
+        loop {
+            thread::sleep(std::time::Duration::from_millis(100));
+            write(denoise.process(take_recorded(stream_rate)))?;
+
+            let is_recording_status = {
+                let guard = is_recording.lock().unwrap();
+                *guard
+            };
+            
+            if !is_recording_status {
+                break;
+            }
+
+            if device_lost.swap(false, Ordering::Relaxed) {
+                if let Some(lost) = stream.take() {
+                    drop(lost);
+                    write(denoise.process(take_recorded(stream_rate)))?;
+                    log::warn!("🔌 Audio device disconnected: {}", device_name);
+                    let _ = meters.device_events.send(CaptureDeviceEvent::Disconnected { device: device_name.clone() });
+                    last_retry = Instant::now();
+                }
+            }
+
+            // デバイスが戻るか、OS が別の既定のデバイスを選ぶまで開き直す
+            if stream.is_none() && last_retry.elapsed() >= device_fallback::DEVICE_RETRY_INTERVAL {
+                last_retry = Instant::now();
+                let reopened = Self::open_device(loopback, input, channels).and_then(|(device, config)| {
+                    let stream = Self::open_stream(
+                        &device,
+                        &config,
+                        output_channels,
+                        &recorded_samples,
+                        &is_recording,
+                        &meters,
+                        &device_lost,
+                    )?;
+                    Ok((device, config, stream))
+                });
+                match reopened {
+                    Ok((device, config, reopened)) => {
+                        device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+                        stream_rate = config.sample_rate.0;
+                        stream = Some(reopened);
+                        log::info!("🎙️ Recording resumed on audio device: {}", device_name);
+                        let _ = meters.device_events.send(CaptureDeviceEvent::Reconnected { device: device_name.clone() });
+                    }
+                    Err(e) => log::debug!("Waiting for an audio device: {}", e),
+                }
+            }
+        }
+
+        // ストリームを停止し、残りを書き出す
+        drop(stream);
+        write(denoise.process(take_recorded(stream_rate)))?;
+        write(denoise.finish())?;
+        spool.finish()
+    }
+
+    /// 入力ストリームを作って開始する
+    ///
+    /// 受け取った音声は `output_channels` 個のチャンネルにして入力ゲインをかけ、`recorded_samples` に溜める。
+    /// 進捗はスプールのサンプルレートでのフレーム数で数える。
+    fn open_stream(
+        device: &cpal::Device,
+        config: &StreamConfig,
+        output_channels: u16,
+        recorded_samples: &Arc<Mutex<Vec<f32>>>,
+        is_recording: &Arc<Mutex<bool>>,
+        meters: &CaptureMeters,
+        device_lost: &Arc<AtomicBool>,
+    ) -> AppResult<cpal::Stream> {
         let recorded_samples_clone = recorded_samples.clone();
         let is_recording_for_callback = is_recording.clone();
+        let device_lost = device_lost.clone();
 
         log::info!("Creating audio stream with config: channels={}, sample_rate={}", config.channels, config.sample_rate.0);
 
         // 音声ストリームを作成
-        let actual_sample_rate = config.sample_rate.0;
+        let stream_rate = config.sample_rate.0.max(1) as u64;
+        let spool_rate = match meters.capture_sample_rate.load(Ordering::Relaxed) {
+            0 => stream_rate,
+            rate => rate as u64,
+        };
+        let stream_channels = config.channels.max(1) as u64;
+        let CaptureMeters { captured_frames, input_level, paused, input_gain, .. } = meters.clone();
+        let mut gain_stage = GainStage::new(input_gain.read().map(|settings| *settings).unwrap_or_default());
         let stream = device.build_input_stream(
-            &config,
+            config,
             move |data: &[f32], _: &cpal::InputCallbackInfo| {
                 let is_recording_status = match is_recording_for_callback.lock() {
                     Ok(guard) => *guard,
                     Err(_) => false,
                 };
                 
-                if is_recording_status {
+                if is_recording_status && !paused.load(Ordering::Relaxed) {
+                    let frames = data.len() as u64 / stream_channels;
+                    captured_frames.fetch_add(frames * spool_rate / stream_rate, Ordering::Relaxed);
+                    let peak = data.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+                    input_level.store(peak.to_bits(), Ordering::Relaxed);
                     match recorded_samples_clone.lock() {
                         Ok(mut samples) => {
-                            for &sample in data {
-                                // 音声レベルチェックとゲイン調整
-                                let processed_sample = if sample.abs() > 0.0001 {
-                                    // 適度な増幅（過度な増幅を避ける）
-                                    (sample * 2.0).clamp(-0.95, 0.95)
-                                } else {
-                                    sample
-                                };
-                                samples.push(processed_sample);
-                            }
-                            
-                            // 44.1kHzで録音されている場合の進捗ログ
-                            if samples.len() % actual_sample_rate as usize == 0 {
-                                let seconds = samples.len() / actual_sample_rate as usize;
-                                log::info!("Recording progress: {}s ({} samples)", seconds, samples.len());
+                            // モノラルで保存するなら複数チャンネル（ループバックのステレオなど）を混ぜ、
+                            // ステレオで保存するなら先頭の2チャンネルを使う（モノラルの入力は両方に同じ音）
+                            let mut frames = convert_channels(data, stream_channels as u16, output_channels);
+                            // 設定の入力ゲイン（または自動ゲイン）をかける
+                            if let Ok(settings) = input_gain.read() {
+                                gain_stage.update(*settings);
                             }
+                            gain_stage.process(&mut frames);
+                            samples.extend_from_slice(&frames);
                         }
                         Err(e) => {
                             log::error!("Failed to lock samples buffer: {}", e);
@@ -272,6 +1176,9 @@ impl AudioCapture {
             },
             move |err| {
                 log::error!("Audio stream error: {}", err);
+                if device_fallback::is_device_lost(&err) {
+                    device_lost.store(true, Ordering::Relaxed);
+                }
             },
             None,
         ).map_err(|e| AppError::Recording {
@@ -284,68 +1191,23 @@ impl AudioCapture {
         stream.play().map_err(|e| AppError::Recording {
             message: format!("Failed to start audio stream: {}", e),
         })?;
+        Ok(stream)
+    }
 
-        log::info!("Audio stream started, beginning recording loop");
-
-        // ここで開始成功を通知（ただし、既にスレッド関数の戻り値で通知済みなので、このタイミングでの通知は不要）
-
-        // 録音が停止されるまで待機
-        loop {
-            thread::sleep(std::time::Duration::from_millis(100));
-            
-            let is_recording_status = {
-                let guard = is_recording.lock().unwrap();
-                *guard
-            };
-            
-            if !is_recording_status {
-                break;
-            }
-        }
-
-        // ストリームを停止
-        drop(stream);
-
-        // 録音データをファイルに保存
-        let samples = {
-            let guard = recorded_samples.lock().unwrap();
-            guard.clone()
+    /// スプールへ書くサンプル（`channels` 個のチャンネルが交互に並ぶ）を 16kHz モノラルにしてチャンクの配信に回す
+    fn feed_samples(feed: Option<&CaptureFeed>, samples: &[f32], channels: u16, sample_rate: u32) {
+        let Some(feed) = feed else {
+            return;
         };
-
         if samples.is_empty() {
-            return Err(AppError::Recording {
-                message: "No audio data recorded".to_string(),
-            });
+            return;
         }
-
-        let original_sample_count = samples.len();
-        
-        // 44.1kHzから16kHzにダウンサンプリング
-        let downsampled_samples = if config.sample_rate.0 != SAMPLE_RATE {
-            log::info!("Downsampling from {}Hz to {}Hz", config.sample_rate.0, SAMPLE_RATE);
-            Self::downsample(&samples, config.sample_rate.0, SAMPLE_RATE)
-        } else {
-            samples
-        };
-        
-        log::info!("Saving {} downsampled samples", downsampled_samples.len());
-        Self::save_samples_to_file(&downsampled_samples, &output_path)?;
-
-        // ファイル作成確認
-        if output_path.exists() {
-            let file_size = std::fs::metadata(&output_path).map(|m| m.len()).unwrap_or(0);
-            log::info!("CPAL recording completed: {} original samples saved to {:?}, file size: {} bytes", original_sample_count, output_path, file_size);
-        } else {
-            log::error!("CPAL recording failed: file not created at {:?}", output_path);
-            return Err(AppError::Recording {
-                message: format!("Output file was not created: {:?}", output_path),
-            });
-        }
-
-        Ok(())
+        let mono = convert_channels(samples, channels, 1);
+        feed.push(&Self::downsample(&mono, sample_rate, FEED_SAMPLE_RATE));
     }
 
-    fn save_samples_to_file(samples: &[f32], output_path: &Path) -> AppResult<()> {
+    /// `format.channels` が 2 なら `samples` は左右交互に並んだフレーム
+    fn save_samples_to_file(samples: &[f32], format: AudioFormat, output_path: &Path) -> AppResult<()> {
         log::info!("Saving {} samples to file: {:?}", samples.len(), output_path);
         
         // 親ディレクトリが存在することを確認
@@ -360,9 +1222,9 @@ impl AudioCapture {
         }
 
         let spec = WavSpec {
-            channels: CHANNELS,
-            sample_rate: SAMPLE_RATE,
-            bits_per_sample: 16,
+            channels: format.channels,
+            sample_rate: format.sample_rate,
+            bits_per_sample: format.bits_per_sample,
             sample_format: hound::SampleFormat::Int,
         };
 
@@ -376,13 +1238,18 @@ impl AudioCapture {
                 message: format!("Failed to create WAV writer: {}", e),
             })?;
 
-        // f32 サンプルを i16 に変換してファイルに書き込み
+        // f32 サンプルを整数（16bit または 24bit）に変換してファイルに書き込み
+        let max = ((1i32 << (format.bits_per_sample - 1)) - 1) as f32;
         for &sample in samples {
-            let i16_sample = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
-            writer.write_sample(i16_sample)
-                .map_err(|e| AppError::Recording {
-                    message: format!("Failed to write audio sample: {}", e),
-                })?;
+            let int_sample = (sample.clamp(-1.0, 1.0) * max) as i32;
+            let written = if format.bits_per_sample == 16 {
+                writer.write_sample(int_sample as i16)
+            } else {
+                writer.write_sample(int_sample)
+            };
+            written.map_err(|e| AppError::Recording {
+                message: format!("Failed to write audio sample: {}", e),
+            })?;
         }
 
         writer.finalize()
@@ -450,10 +1317,34 @@ impl AudioCapture {
     }
 }
 
-// 利用可能なオーディオデバイスを取得
-pub fn get_audio_devices() -> AppResult<Vec<String>> {
+fn microphone(name: String) -> AudioDevice {
+    AudioDevice {
+        name,
+        source_type: AudioSourceType::Microphone,
+        capture_source: CaptureSource::Microphone,
+    }
+}
+
+// 利用可能なオーディオデバイスを取得（マイク・システム音声・アプリの種類付き）
+pub fn get_audio_devices() -> AppResult<Vec<AudioDevice>> {
+    if demo_mode::is_enabled() {
+        return Ok(vec![microphone(demo_mode::DEMO_AUDIO_DEVICE.to_string())]);
+    }
+
+    // Linux で PipeWire が動いていれば、モニターやアプリのストリームも選べる
+    if cfg!(target_os = "linux") && pipewire::is_available() {
+        match pipewire::list_devices() {
+            Ok(nodes) => {
+                let mut devices = vec![microphone("Default Microphone".to_string())];
+                devices.extend(nodes);
+                return Ok(devices);
+            }
+            Err(e) => log::warn!("⚠️ Failed to enumerate PipeWire nodes, falling back to CPAL: {}", e),
+        }
+    }
+
     let host = cpal::default_host();
-    let mut device_names = Vec::new();
+    let mut devices = Vec::new();
     
     // 入力デバイスを列挙
     let input_devices = host.input_devices()
@@ -461,15 +1352,30 @@ pub fn get_audio_devices() -> AppResult<Vec<String>> {
             message: format!("Failed to enumerate input devices: {}", e),
         })?;
 
+    // 名前で指定して録音できるよう、既定以外のマイクも選べる
     for device in input_devices {
         if let Ok(name) = device.name() {
-            device_names.push(name);
+            devices.push(AudioDevice {
+                capture_source: CaptureSource::InputDevice { name: name.clone() },
+                ..microphone(name)
+            });
         }
     }
 
-    if device_names.is_empty() {
-        device_names.push("Default Microphone".to_string());
+    if devices.is_empty() {
+        devices.push(microphone("Default Microphone".to_string()));
+    }
+
+    // Windows は既定の出力デバイスをループバックで録音できる
+    if CaptureSource::SystemAudio.is_loopback() {
+        if let Some(name) = host.default_output_device().and_then(|device| device.name().ok()) {
+            devices.push(AudioDevice {
+                name: format!("{} (loopback)", name),
+                source_type: AudioSourceType::SystemAudio,
+                capture_source: CaptureSource::SystemAudio,
+            });
+        }
     }
 
-    Ok(device_names)
+    Ok(devices)
 }
\ No newline at end of file
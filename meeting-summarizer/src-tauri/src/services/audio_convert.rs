@@ -0,0 +1,471 @@
+//! Whisper入力用の音声変換（symphonia でデコード → rubato で16kHzへリサンプリング）
+//!
+//! mp3/m4a/flac/ogg などを Rust 側で 16kHz モノラル 16bit WAV に変換するため、
+//! ユーザーのPython環境に ffmpeg や librosa が無くても書き起こしできる。
+
+use super::flac_encoder::FlacEncoder;
+use super::recording_encoder;
+use crate::errors::{AppError, AppResult};
+use hound::{SampleFormat, WavSpec, WavWriter};
+use rubato::{FftFixedIn, Resampler};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+/// Whisperが想定するサンプルレート
+pub const WHISPER_SAMPLE_RATE: u32 = 16_000;
+/// リサンプラーへ一度に渡す入力フレーム数
+const RESAMPLE_CHUNK_FRAMES: usize = 4096;
+/// `transcode` が書き出せる形式
+pub const TRANSCODE_FORMATS: &[&str] = &["wav", "flac", "whisper"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvertedAudio {
+    pub path: PathBuf,
+    pub source_sample_rate: u32,
+    pub source_channels: u16,
+    pub duration_secs: f64,
+}
+
+/// 既に16kHzモノラルのPCM WAVであれば変換不要
+pub fn is_whisper_ready(path: &Path) -> bool {
+    match hound::WavReader::open(path) {
+        Ok(reader) => {
+            let spec = reader.spec();
+            spec.sample_rate == WHISPER_SAMPLE_RATE
+                && spec.channels == 1
+                && spec.sample_format == SampleFormat::Int
+                && spec.bits_per_sample == 16
+        }
+        Err(_) => false,
+    }
+}
+
+/// 入力ファイルをデコードし、16kHzモノラル16bit WAVとして `output` に書き出す
+///
+/// デコードとリサンプリングはパケット単位で行い、ファイル全体をメモリに展開しない。
+pub fn convert_for_whisper(input: &Path, output: &Path) -> AppResult<ConvertedAudio> {
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: WHISPER_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(output, spec).map_err(|e| conversion_error(output, e))?;
+
+    // サンプルレートは最初にデコードしたパケットから確定させる
+    let mut resampler: Option<MonoResampler> = None;
+    let (source_sample_rate, source_channels) = decode_packets(input, |samples, rate, channels| {
+        if resampler.is_none() {
+            resampler = Some(MonoResampler::new(rate)?);
+        }
+        let mono: Vec<f32> = samples
+            .chunks(channels)
+            .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+            .collect();
+        match resampler.as_mut() {
+            Some(resampler) => resampler.push(&mono, &mut writer),
+            None => Ok(()),
+        }
+    })?;
+
+    let resampler = resampler.ok_or_else(|| AppError::AudioConversion {
+        message: format!("No decodable audio in {:?}", input),
+    })?;
+    let written_frames = resampler.finish(&mut writer)?;
+    writer.finalize().map_err(|e| conversion_error(output, e))?;
+
+    let duration_secs = written_frames as f64 / WHISPER_SAMPLE_RATE as f64;
+    log::info!(
+        "🔄 Converted {:?} ({} Hz, {} ch) → 16kHz mono WAV ({:.1}s)",
+        input,
+        source_sample_rate,
+        source_channels,
+        duration_secs
+    );
+
+    Ok(ConvertedAudio {
+        path: output.to_path_buf(),
+        source_sample_rate,
+        source_channels,
+        duration_secs,
+    })
+}
+
+/// 入力ファイルを `format` の形式で `output` に書き出す
+///
+/// `wav`・`flac` は元のサンプルレートとチャンネル数を保った16bit、`whisper` は16kHzモノラルWAV。
+/// Opus などのエンコーダーは組み込んでいないため未対応として扱う。
+pub fn transcode(input: &Path, output: &Path, format: &str) -> AppResult<ConvertedAudio> {
+    match format {
+        "whisper" => return convert_for_whisper(input, output),
+        "wav" | "flac" => {}
+        other => {
+            return Err(AppError::AudioConversion {
+                message: format!(
+                    "Unsupported target format: {} (supported: {})",
+                    other,
+                    TRANSCODE_FORMATS.join(", ")
+                ),
+            })
+        }
+    }
+
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // 出力の形式は最初にデコードしたパケットのサンプルレートとチャンネル数で決まる
+    let mut wav: Option<WavWriter<std::io::BufWriter<File>>> = None;
+    let mut flac: Option<FlacEncoder<std::io::BufWriter<File>>> = None;
+    let mut frames: u64 = 0;
+    let (source_sample_rate, source_channels) = decode_packets(input, |samples, rate, channels| {
+        // 16bit の入力がそのまま同じ値に戻るよう 32768 倍して丸める
+        let pcm: Vec<i16> = samples
+            .iter()
+            .map(|sample| (sample * 32_768.0).round().clamp(i16::MIN as f32, i16::MAX as f32) as i16)
+            .collect();
+        frames += (pcm.len() / channels) as u64;
+
+        if format == "flac" {
+            if flac.is_none() {
+                let file = std::io::BufWriter::new(File::create(output)?);
+                flac = Some(FlacEncoder::new(file, rate, channels as u16)?);
+            }
+            if let Some(encoder) = flac.as_mut() {
+                encoder.write_interleaved(&pcm)?;
+            }
+            return Ok(());
+        }
+
+        if wav.is_none() {
+            let spec = WavSpec {
+                channels: channels as u16,
+                sample_rate: rate,
+                bits_per_sample: 16,
+                sample_format: SampleFormat::Int,
+            };
+            wav = Some(WavWriter::create(output, spec).map_err(|e| conversion_error(output, e))?);
+        }
+        if let Some(writer) = wav.as_mut() {
+            for sample in pcm {
+                writer.write_sample(sample).map_err(|e| conversion_error(output, e))?;
+            }
+        }
+        Ok(())
+    })?;
+
+    match (wav, flac) {
+        (Some(writer), _) => writer.finalize().map_err(|e| conversion_error(output, e))?,
+        (_, Some(encoder)) => {
+            encoder.finish()?;
+        }
+        _ => {
+            return Err(AppError::AudioConversion {
+                message: format!("No decodable audio in {:?}", input),
+            })
+        }
+    }
+
+    let duration_secs = frames as f64 / source_sample_rate.max(1) as f64;
+    log::info!(
+        "🔄 Converted {:?} ({} Hz, {} ch) → {} ({:.1}s)",
+        input,
+        source_sample_rate,
+        source_channels,
+        format,
+        duration_secs
+    );
+
+    Ok(ConvertedAudio {
+        path: output.to_path_buf(),
+        source_sample_rate,
+        source_channels,
+        duration_secs,
+    })
+}
+
+/// 入力ファイルをパケット単位でデコードし、チャンネルが交互に並んだサンプルを `on_samples` に渡す
+///
+/// 最初にデコードできたパケットのサンプルレートとチャンネル数を返す。
+fn decode_packets(
+    input: &Path,
+    mut on_samples: impl FnMut(&[f32], u32, usize) -> AppResult<()>,
+) -> AppResult<(u32, u16)> {
+    let file = File::open(input)?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(extension) = input.extension().and_then(|ext| ext.to_str()) {
+        hint.with_extension(extension);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(&hint, stream, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| conversion_error(input, e))?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| AppError::AudioConversion {
+            message: format!("No audio track found in {:?}", input),
+        })?;
+    let track_id = track.id;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| conversion_error(input, e))?;
+
+    let mut source: Option<(u32, u16)> = None;
+    let mut sample_buffer: Option<SampleBuffer<f32>> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(conversion_error(input, e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // 破損パケットは読み飛ばす
+            Err(SymphoniaError::DecodeError(e)) => {
+                log::warn!("⚠️ Skipping undecodable packet in {:?}: {}", input, e);
+                continue;
+            }
+            Err(e) => return Err(conversion_error(input, e)),
+        };
+
+        let audio_spec = *decoded.spec();
+        let channels = audio_spec.channels.count().max(1);
+        let (rate, _) = *source.get_or_insert((audio_spec.rate, channels as u16));
+
+        if sample_buffer
+            .as_ref()
+            .is_some_and(|buffer| buffer.capacity() < decoded.capacity() * channels)
+        {
+            sample_buffer = None;
+        }
+        let buffer = sample_buffer
+            .get_or_insert_with(|| SampleBuffer::<f32>::new(decoded.capacity() as u64, audio_spec));
+        buffer.copy_interleaved_ref(decoded);
+
+        on_samples(buffer.samples(), rate, channels)?;
+    }
+
+    Ok(source.unwrap_or((0, 0)))
+}
+
+/// 入力ファイルのチャンネルを分け、それぞれ16kHzモノラル16bit WAVとして `work_dir` に書き出す
+///
+/// 出力は `<name>-ch1.wav`・`<name>-ch2.wav` … の順（チャンネルの順）。
+pub fn split_channels_for_whisper(input: &Path, work_dir: &Path, name: &str) -> AppResult<Vec<PathBuf>> {
+    std::fs::create_dir_all(work_dir)?;
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: WHISPER_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let paths_for = |channels: usize| -> Vec<PathBuf> {
+        (1..=channels)
+            .map(|channel| work_dir.join(format!("{}-ch{}.wav", name, channel)))
+            .collect()
+    };
+
+    // チャンネル数は最初にデコードしたパケットから確定させる
+    let mut outputs: Vec<(MonoResampler, WavWriter<std::io::BufWriter<File>>)> = Vec::new();
+    let (_, source_channels) = decode_packets(input, |samples, rate, channels| {
+        if outputs.is_empty() {
+            for path in paths_for(channels) {
+                let writer = WavWriter::create(&path, spec).map_err(|e| conversion_error(&path, e))?;
+                outputs.push((MonoResampler::new(rate)?, writer));
+            }
+        }
+        for (channel, (resampler, writer)) in outputs.iter_mut().enumerate() {
+            let mono: Vec<f32> = samples.iter().skip(channel).step_by(channels).copied().collect();
+            resampler.push(&mono, writer)?;
+        }
+        Ok(())
+    })?;
+
+    if outputs.is_empty() {
+        return Err(AppError::AudioConversion {
+            message: format!("No decodable audio in {:?}", input),
+        });
+    }
+    for (resampler, mut writer) in outputs {
+        resampler.finish(&mut writer)?;
+        writer.finalize().map_err(|e| conversion_error(input, e))?;
+    }
+    log::info!("🔀 Split {:?} into {} channel(s)", input, source_channels);
+    Ok(paths_for(source_channels as usize))
+}
+
+/// 書き起こし用に `work_dir` 以下へ変換する。変換不要なら元のパスをそのまま返す
+pub fn prepare_for_whisper(input: &Path, work_dir: &Path, name: &str) -> AppResult<PathBuf> {
+    if is_whisper_ready(input) {
+        return Ok(input.to_path_buf());
+    }
+
+    let output = work_dir.join(format!("{}.wav", name));
+    if let Err(e) = convert_for_whisper(input, &output) {
+        // Opus など symphonia でデコードできない形式は ffmpeg があれば変換する
+        if !recording_encoder::ffmpeg_available() {
+            return Err(e);
+        }
+        log::info!("🔄 Built-in decoder failed ({}), converting with ffmpeg: {:?}", e, input);
+        std::fs::create_dir_all(work_dir)?;
+        let sample_rate = WHISPER_SAMPLE_RATE.to_string();
+        recording_encoder::run_ffmpeg(input, &output, &["-ac", "1", "-ar", &sample_rate, "-c:a", "pcm_s16le"])?;
+    }
+    Ok(output)
+}
+
+/// モノラル信号を16kHzへ変換しながらWAVへ書き込む
+struct MonoResampler {
+    /// 入力が既に16kHzの場合は None（そのまま書き込む）
+    resampler: Option<FftFixedIn<f32>>,
+    pending: Vec<f32>,
+    input_frames: u64,
+    output_frames: u64,
+    /// リサンプラーの遅延分として先頭から捨てる残りフレーム数
+    skip_frames: usize,
+    ratio: f64,
+}
+
+impl MonoResampler {
+    fn new(source_sample_rate: u32) -> AppResult<Self> {
+        let resampler = if source_sample_rate == WHISPER_SAMPLE_RATE {
+            None
+        } else {
+            Some(
+                FftFixedIn::<f32>::new(
+                    source_sample_rate as usize,
+                    WHISPER_SAMPLE_RATE as usize,
+                    RESAMPLE_CHUNK_FRAMES,
+                    2,
+                    1,
+                )
+                .map_err(|e| AppError::AudioConversion {
+                    message: format!("Failed to create resampler: {}", e),
+                })?,
+            )
+        };
+        let skip_frames = resampler.as_ref().map(|r| r.output_delay()).unwrap_or(0);
+
+        Ok(Self {
+            resampler,
+            pending: Vec::new(),
+            input_frames: 0,
+            output_frames: 0,
+            skip_frames,
+            ratio: WHISPER_SAMPLE_RATE as f64 / source_sample_rate as f64,
+        })
+    }
+
+    fn push(&mut self, samples: &[f32], writer: &mut WavWriter<std::io::BufWriter<File>>) -> AppResult<()> {
+        self.input_frames += samples.len() as u64;
+
+        let Some(resampler) = self.resampler.as_mut() else {
+            return self.write(samples.to_vec(), writer);
+        };
+
+        self.pending.extend_from_slice(samples);
+        let mut outputs = Vec::new();
+        while self.pending.len() >= resampler.input_frames_next() {
+            let needed = resampler.input_frames_next();
+            let mut resampled = resampler
+                .process(&[&self.pending[..needed]], None)
+                .map_err(resample_error)?;
+            self.pending.drain(..needed);
+            outputs.push(resampled.remove(0));
+        }
+
+        for output in outputs {
+            self.write(output, writer)?;
+        }
+        Ok(())
+    }
+
+    /// 残りのサンプルとリサンプラー内部の遅延分を書き出し、総出力フレーム数を返す
+    fn finish(mut self, writer: &mut WavWriter<std::io::BufWriter<File>>) -> AppResult<u64> {
+        if let Some(mut resampler) = self.resampler.take() {
+            let expected = (self.input_frames as f64 * self.ratio).round() as u64;
+            let mut outputs = Vec::new();
+
+            if !self.pending.is_empty() {
+                let mut resampled = resampler
+                    .process_partial(Some(&[&self.pending[..]]), None)
+                    .map_err(resample_error)?;
+                outputs.push(resampled.remove(0));
+            }
+
+            // 遅延分を押し出す
+            let mut flushed = outputs.iter().map(|o| o.len() as u64).sum::<u64>() + self.output_frames;
+            while flushed < expected + self.skip_frames as u64 {
+                let mut resampled = resampler
+                    .process_partial::<&[f32]>(None, None)
+                    .map_err(resample_error)?;
+                let output = resampled.remove(0);
+                if output.is_empty() {
+                    break;
+                }
+                flushed += output.len() as u64;
+                outputs.push(output);
+            }
+
+            for mut output in outputs {
+                let remaining = expected.saturating_sub(self.output_frames) as usize + self.skip_frames;
+                output.truncate(remaining);
+                self.write(output, writer)?;
+            }
+        }
+
+        Ok(self.output_frames)
+    }
+
+    fn write(&mut self, mut samples: Vec<f32>, writer: &mut WavWriter<std::io::BufWriter<File>>) -> AppResult<()> {
+        if self.skip_frames > 0 {
+            let skip = self.skip_frames.min(samples.len());
+            samples.drain(..skip);
+            self.skip_frames -= skip;
+        }
+
+        for sample in &samples {
+            let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            writer.write_sample(value).map_err(|e| AppError::AudioConversion {
+                message: format!("Failed to write WAV sample: {}", e),
+            })?;
+        }
+        self.output_frames += samples.len() as u64;
+        Ok(())
+    }
+}
+
+fn conversion_error(path: &Path, error: impl std::fmt::Display) -> AppError {
+    AppError::AudioConversion {
+        message: format!("{:?}: {}", path, error),
+    }
+}
+
+fn resample_error(error: rubato::ResampleError) -> AppError {
+    AppError::AudioConversion {
+        message: format!("Resampling failed: {}", error),
+    }
+}
@@ -0,0 +1,216 @@
+//! 録音の切り出しと範囲の削除
+//!
+//! 会議の前後の雑談や途中の休憩を取り除けるよう、WAV の録音を書き換える。最初に編集するときに
+//! 元のファイルを `<録音ディレクトリ>/backups/` に残すので、何度編集しても元の録音に戻せる。
+//! 書き換えたあとは録音の長さとサイズを更新し、消えた範囲のマーカーを除いて残りをずらす。
+//! 書き起こしは音声と時刻が合わなくなるため、指定があれば削除する。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::Recording;
+use crate::services::recording_segments;
+use hound::{SampleFormat, WavReader, WavWriter};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 元の録音を残すディレクトリ（録音ディレクトリからの相対パス）
+pub const BACKUP_DIR: &str = "backups";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioEdit {
+    /// `start_ms`〜`end_ms` だけを残す
+    Trim,
+    /// `start_ms`〜`end_ms` を取り除いて前後をつなぐ
+    DeleteRange,
+}
+
+/// 編集の結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditedRecording {
+    pub recording: Recording,
+    /// 編集する前の録音
+    pub backup_path: String,
+    pub duration_ms: i64,
+    /// 削除した書き起こしの数
+    pub removed_transcriptions: usize,
+}
+
+/// 元の録音の保存先
+pub fn backup_path(recordings_dir: &Path, audio_path: &Path) -> PathBuf {
+    let filename = audio_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    recordings_dir.join(BACKUP_DIR).join(filename)
+}
+
+/// 編集したあとに残る範囲（ミリ秒）
+pub fn kept_ranges(edit: AudioEdit, start_ms: i64, end_ms: i64, total_ms: i64) -> AppResult<Vec<(i64, i64)>> {
+    if start_ms < 0 || start_ms >= end_ms || end_ms > total_ms {
+        return Err(AppError::ValidationError {
+            message: format!("Invalid range {}ms - {}ms for a {}ms recording", start_ms, end_ms, total_ms),
+        });
+    }
+    let ranges: Vec<(i64, i64)> = match edit {
+        AudioEdit::Trim => vec![(start_ms, end_ms)],
+        AudioEdit::DeleteRange => [(0, start_ms), (end_ms, total_ms)]
+            .into_iter()
+            .filter(|(start, end)| start < end)
+            .collect(),
+    };
+    if ranges.is_empty() {
+        return Err(AppError::ValidationError {
+            message: "The whole recording would be removed".to_string(),
+        });
+    }
+    Ok(ranges)
+}
+
+/// 編集したあとの時刻（取り除いた範囲にあれば None）
+pub fn shifted_offset(edit: AudioEdit, start_ms: i64, end_ms: i64, offset_ms: i64) -> Option<i64> {
+    match edit {
+        AudioEdit::Trim if (start_ms..=end_ms).contains(&offset_ms) => Some(offset_ms - start_ms),
+        AudioEdit::Trim => None,
+        AudioEdit::DeleteRange if offset_ms < start_ms => Some(offset_ms),
+        AudioEdit::DeleteRange if offset_ms >= end_ms => Some(offset_ms - (end_ms - start_ms)),
+        AudioEdit::DeleteRange => None,
+    }
+}
+
+/// WAV から `ranges` の範囲だけを残して書き換え、書き換えたあとの長さ（ミリ秒）を返す
+///
+/// 書き終えてから元のファイルと入れ替えるので、途中で失敗しても録音は壊れない。
+pub fn rewrite_wav(path: &Path, ranges: &[(i64, i64)]) -> AppResult<i64> {
+    let reader = WavReader::open(path).map_err(|e| AppError::ValidationError {
+        message: format!("Only WAV recordings can be edited ({:?}: {})", path, e),
+    })?;
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as u64;
+    let to_frame = |ms: i64| ms.max(0) as u64 * spec.sample_rate as u64 / 1000;
+    let frame_ranges: Vec<(u64, u64)> = ranges.iter().map(|(start, end)| (to_frame(*start), to_frame(*end))).collect();
+    let keep = |index: usize| {
+        let frame = index as u64 / channels;
+        frame_ranges.iter().any(|(start, end)| (*start..*end).contains(&frame))
+    };
+
+    let partial = path.with_extension("wav.part");
+    let mut writer = WavWriter::create(&partial, spec).map_err(|e| edit_error(&partial, e))?;
+    let mut written: u64 = 0;
+    match spec.sample_format {
+        SampleFormat::Float => {
+            for (index, sample) in reader.into_samples::<f32>().enumerate() {
+                let sample = sample.map_err(|e| edit_error(path, e))?;
+                if keep(index) {
+                    writer.write_sample(sample).map_err(|e| edit_error(&partial, e))?;
+                    written += 1;
+                }
+            }
+        }
+        SampleFormat::Int => {
+            for (index, sample) in reader.into_samples::<i32>().enumerate() {
+                let sample = sample.map_err(|e| edit_error(path, e))?;
+                if keep(index) {
+                    writer.write_sample(sample).map_err(|e| edit_error(&partial, e))?;
+                    written += 1;
+                }
+            }
+        }
+    }
+    writer.finalize().map_err(|e| edit_error(&partial, e))?;
+    fs::rename(&partial, path)?;
+
+    Ok((written / channels * 1000 / spec.sample_rate.max(1) as u64) as i64)
+}
+
+/// 録音を編集し、データベースの長さ・サイズ・マーカーを合わせる
+pub async fn edit_recording(
+    database: &Database,
+    recordings_dir: &Path,
+    recording_id: &str,
+    edit: AudioEdit,
+    start_ms: i64,
+    end_ms: i64,
+    invalidate_transcriptions: bool,
+) -> AppResult<EditedRecording> {
+    let mut recording = database
+        .get_recording(recording_id)
+        .await?
+        .ok_or_else(|| AppError::FileNotFound {
+            path: recording_id.to_string(),
+        })?;
+    if !database.get_recording_segments(recording_id).await?.is_empty() {
+        return Err(AppError::InvalidOperation {
+            message: "Recordings split into segments cannot be edited".to_string(),
+        });
+    }
+
+    let path = PathBuf::from(&recording.file_path);
+    let total_ms = recording_segments::wav_duration_ms(&path).ok_or_else(|| AppError::ValidationError {
+        message: format!("Only WAV recordings can be edited: {:?}", path),
+    })?;
+    let ranges = kept_ranges(edit, start_ms, end_ms, total_ms)?;
+
+    // 何度編集しても、最初の録音を残しておく
+    let backup = backup_path(recordings_dir, &path);
+    if !backup.exists() {
+        fs::create_dir_all(backup.parent().unwrap_or(recordings_dir))?;
+        fs::copy(&path, &backup)?;
+    }
+
+    let path_for_task = path.clone();
+    let duration_ms = tokio::task::spawn_blocking(move || rewrite_wav(&path_for_task, &ranges))
+        .await
+        .map_err(|e| AppError::InvalidOperation {
+            message: format!("Edit task failed: {}", e),
+        })??;
+
+    recording.duration = Some((duration_ms + 500) / 1000);
+    recording.file_size = Some(fs::metadata(&path)?.len() as i64);
+    database.update_recording(&recording).await?;
+
+    for marker in database.get_markers(recording_id).await? {
+        match shifted_offset(edit, start_ms, end_ms, marker.offset_ms) {
+            Some(offset_ms) if offset_ms != marker.offset_ms => {
+                database.update_marker_offset(&marker.id, offset_ms).await?;
+            }
+            Some(_) => {}
+            None => {
+                database.delete_marker(&marker.id).await?;
+            }
+        }
+    }
+
+    let mut removed_transcriptions = 0;
+    if invalidate_transcriptions {
+        for transcription in database.get_transcriptions_by_recording(recording_id).await? {
+            if database.delete_transcription(&transcription.id).await? {
+                removed_transcriptions += 1;
+            }
+        }
+    }
+
+    log::info!(
+        "✂️ Edited recording {} ({:?} {}ms - {}ms): {}ms → {}ms",
+        recording_id,
+        edit,
+        start_ms,
+        end_ms,
+        total_ms,
+        duration_ms
+    );
+
+    Ok(EditedRecording {
+        recording,
+        backup_path: backup.to_string_lossy().to_string(),
+        duration_ms,
+        removed_transcriptions,
+    })
+}
+
+fn edit_error(path: &Path, error: impl std::fmt::Display) -> AppError {
+    AppError::InvalidOperation {
+        message: format!("Failed to edit {:?}: {}", path, error),
+    }
+}
@@ -0,0 +1,134 @@
+//! 音声ファイルの長さ・サンプルレート・チャンネル数の読み取り
+//!
+//! 録音の停止時と取り込み時に呼び、`Recording` の `duration`・`sample_rate`・`channels` を埋める。
+//! WAV はヘッダーだけを読む。それ以外は symphonia でコンテナを開き、長さが書かれていなければ
+//! パケットの長さを足し合わせる（デコードはしない）。
+
+use crate::errors::{AppError, AppResult};
+use crate::models::Recording;
+use hound::WavReader;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use symphonia::core::codecs::CODEC_TYPE_NULL;
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioInfo {
+    pub duration_ms: i64,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl AudioInfo {
+    /// 秒単位の長さ（四捨五入）
+    pub fn duration_secs(&self) -> i64 {
+        (self.duration_ms + 500) / 1000
+    }
+}
+
+/// 音声ファイルの長さ・サンプルレート・チャンネル数を読み取る
+pub fn probe(path: &Path) -> AppResult<AudioInfo> {
+    if let Ok(reader) = WavReader::open(path) {
+        let spec = reader.spec();
+        return Ok(AudioInfo {
+            duration_ms: reader.duration() as i64 * 1000 / spec.sample_rate.max(1) as i64,
+            sample_rate: spec.sample_rate,
+            channels: spec.channels,
+        });
+    }
+    probe_container(path)
+}
+
+/// 区間に分けた録音をまとめて読み取る（長さは合計、サンプルレートとチャンネル数は先頭の区間）
+pub fn probe_files(paths: &[PathBuf]) -> AppResult<AudioInfo> {
+    let mut infos = paths.iter().map(|path| probe(path));
+    let mut total = infos.next().ok_or_else(|| AppError::ValidationError {
+        message: "No audio files to probe".to_string(),
+    })??;
+    for info in infos {
+        total.duration_ms += info?.duration_ms;
+    }
+    Ok(total)
+}
+
+/// 読み取った値を録音に書き込む。読み取れなければ警告だけ出して元の値を残す
+pub fn fill_recording(recording: &mut Recording, paths: &[PathBuf]) {
+    match probe_files(paths) {
+        Ok(info) => {
+            recording.duration = Some(info.duration_secs());
+            recording.sample_rate = Some(info.sample_rate as i32);
+            recording.channels = Some(info.channels as i32);
+        }
+        Err(e) => log::warn!("⚠️ Failed to read audio info of {:?}: {}", paths, e),
+    }
+}
+
+fn probe_container(path: &Path) -> AppResult<AudioInfo> {
+    let file = File::open(path)?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|ext| ext.to_str()) {
+        hint.with_extension(extension);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(&hint, stream, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| probe_error(path, e))?;
+    let mut format = probed.format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| AppError::AudioConversion {
+            message: format!("No audio track found in {:?}", path),
+        })?;
+    let track_id = track.id;
+    let params = track.codec_params.clone();
+
+    let sample_rate = params.sample_rate.ok_or_else(|| AppError::AudioConversion {
+        message: format!("Unknown sample rate in {:?}", path),
+    })?;
+    let channels = params.channels.map(|channels| channels.count() as u16).unwrap_or(1);
+
+    // コンテナに長さが無ければ（ヘッダーの無い mp3 など）パケットの長さを足す
+    let frames = match params.n_frames {
+        Some(frames) => frames,
+        None => {
+            let mut frames = 0;
+            loop {
+                match format.next_packet() {
+                    Ok(packet) if packet.track_id() == track_id => frames += packet.dur,
+                    Ok(_) => {}
+                    Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                    Err(SymphoniaError::ResetRequired) => break,
+                    Err(e) => return Err(probe_error(path, e)),
+                }
+            }
+            frames
+        }
+    };
+    let duration_ms = match params.time_base {
+        Some(time_base) => {
+            let time = time_base.calc_time(frames);
+            (time.seconds as f64 * 1000.0 + time.frac * 1000.0).round() as i64
+        }
+        None => (frames * 1000 / sample_rate.max(1) as u64) as i64,
+    };
+
+    Ok(AudioInfo {
+        duration_ms,
+        sample_rate,
+        channels,
+    })
+}
+
+fn probe_error(path: &Path, error: impl std::fmt::Display) -> AppError {
+    AppError::AudioConversion {
+        message: format!("Failed to read audio info of {:?}: {}", path, error),
+    }
+}
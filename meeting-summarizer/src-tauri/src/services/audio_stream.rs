@@ -0,0 +1,155 @@
+//! 大きな音声ファイルをメモリに一括展開せずに扱うためのストリーミング読み込み
+//!
+//! - `AudioChunkReader`: WAVをフレーム単位のチャンクで順次デコード
+//! - `streaming_file_part`: HTTPアップロード用にファイルをストリームとして送信
+//! - `waveform_peaks`: チャンク読み込みで波形表示用のピーク値を算出
+
+use crate::errors::{AppError, AppResult};
+use hound::{SampleFormat, WavReader, WavSpec};
+use reqwest::multipart;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use tokio_util::io::ReaderStream;
+
+/// 1回の読み込みで扱う既定フレーム数（16kHzで約4秒）
+pub const DEFAULT_CHUNK_FRAMES: usize = 65_536;
+
+/// WAVファイルをチャンク単位でモノラルf32サンプルとして読み出す
+pub struct AudioChunkReader {
+    reader: WavReader<BufReader<File>>,
+    spec: WavSpec,
+    frames_read: u64,
+}
+
+impl AudioChunkReader {
+    pub fn open(path: &Path) -> AppResult<Self> {
+        let reader = WavReader::open(path).map_err(|e| AppError::ValidationError {
+            message: format!("Failed to open WAV file {:?}: {}", path, e),
+        })?;
+        let spec = reader.spec();
+
+        Ok(Self {
+            reader,
+            spec,
+            frames_read: 0,
+        })
+    }
+
+    pub fn spec(&self) -> WavSpec {
+        self.spec
+    }
+
+    /// 総フレーム数（チャンネルあたりのサンプル数）
+    pub fn total_frames(&self) -> u64 {
+        self.reader.duration() as u64
+    }
+
+    pub fn duration_secs(&self) -> f64 {
+        self.total_frames() as f64 / self.spec.sample_rate as f64
+    }
+
+    pub fn frames_read(&self) -> u64 {
+        self.frames_read
+    }
+
+    /// 最大 `max_frames` フレームを読み込み、チャンネルを平均したモノラルサンプルを返す
+    pub fn next_chunk(&mut self, max_frames: usize) -> AppResult<Option<Vec<f32>>> {
+        let channels = self.spec.channels.max(1) as usize;
+        let mut chunk = Vec::with_capacity(max_frames);
+        let mut frame_sum = 0.0f32;
+        let mut channel_index = 0;
+
+        let scale = match self.spec.sample_format {
+            SampleFormat::Float => 1.0,
+            SampleFormat::Int => 1.0 / (1_i64 << (self.spec.bits_per_sample - 1)) as f32,
+        };
+
+        while chunk.len() < max_frames {
+            let sample = match self.spec.sample_format {
+                SampleFormat::Float => self.reader.samples::<f32>().next(),
+                SampleFormat::Int => self
+                    .reader
+                    .samples::<i32>()
+                    .next()
+                    .map(|s| s.map(|v| v as f32 * scale)),
+            };
+
+            let Some(sample) = sample else {
+                break;
+            };
+            let sample = sample.map_err(|e| AppError::ValidationError {
+                message: format!("Failed to decode WAV sample: {}", e),
+            })?;
+
+            frame_sum += sample;
+            channel_index += 1;
+            if channel_index == channels {
+                chunk.push(frame_sum / channels as f32);
+                frame_sum = 0.0;
+                channel_index = 0;
+            }
+        }
+
+        if chunk.is_empty() {
+            return Ok(None);
+        }
+
+        self.frames_read += chunk.len() as u64;
+        Ok(Some(chunk))
+    }
+}
+
+/// 拡張子からアップロード時のMIMEタイプを推定
+pub fn mime_for_path(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .as_deref()
+    {
+        Some("mp3") => "audio/mpeg",
+        Some("m4a") => "audio/mp4",
+        Some("mp4") => "video/mp4",
+        Some("flac") => "audio/flac",
+        Some("ogg") => "audio/ogg",
+        _ => "audio/wav",
+    }
+}
+
+/// ファイル全体を読み込まずにマルチパートのファイルパートを作成
+pub async fn streaming_file_part(path: &Path) -> AppResult<multipart::Part> {
+    let file = tokio::fs::File::open(path).await?;
+    let length = file.metadata().await?.len();
+    let filename = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("audio.wav")
+        .to_string();
+
+    let body = reqwest::Body::wrap_stream(ReaderStream::new(file));
+    Ok(multipart::Part::stream_with_length(body, length)
+        .file_name(filename)
+        .mime_str(mime_for_path(path))?)
+}
+
+/// 波形表示用に `buckets` 個の区間ごとのピーク値（0.0〜1.0）を算出
+pub fn waveform_peaks(path: &Path, buckets: usize) -> AppResult<Vec<f32>> {
+    let mut reader = AudioChunkReader::open(path)?;
+    let buckets = buckets.max(1);
+    let total_frames = reader.total_frames().max(1);
+    let frames_per_bucket = (total_frames as f64 / buckets as f64).ceil().max(1.0) as u64;
+
+    let mut peaks = vec![0.0f32; buckets];
+    let mut frame_index: u64 = 0;
+
+    while let Some(chunk) = reader.next_chunk(DEFAULT_CHUNK_FRAMES)? {
+        for sample in chunk {
+            let bucket = ((frame_index / frames_per_bucket) as usize).min(buckets - 1);
+            peaks[bucket] = peaks[bucket].max(sample.abs().min(1.0));
+            frame_index += 1;
+        }
+    }
+
+    Ok(peaks)
+}
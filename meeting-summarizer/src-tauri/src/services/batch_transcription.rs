@@ -0,0 +1,210 @@
+//! 複数録音の一括書き起こし（ワーカープールによる並列実行）
+//!
+//! 同時実行数はCPUコア数とWhisperモデルサイズから決める既定値か、設定値を使う。
+//! 各ファイルの状態変化はコールバックで通知する。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::services::{corrections, recording_segments, transcription_language, WhisperService};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+/// 同時実行数の上限（設定値もこの値で頭打ち）
+pub const MAX_BATCH_WORKERS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchItemStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// ファイル単位の進捗
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchItemProgress {
+    pub batch_id: String,
+    pub recording_id: String,
+    pub status: BatchItemStatus,
+    pub transcription_id: Option<String>,
+    pub error: Option<String>,
+    /// 完了（成功・失敗）したファイル数
+    pub finished: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchTranscriptionResult {
+    pub batch_id: String,
+    pub workers: usize,
+    pub items: Vec<BatchItemProgress>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+/// モデルサイズとCPUコア数から既定のワーカー数を決める
+///
+/// 大きいモデルはそれ自体が複数スレッドを使うため、並列数を抑える。
+pub fn default_worker_count(model_size: &str) -> usize {
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+
+    let cores_per_worker = match model_size {
+        "tiny" => 2,
+        "base" => 2,
+        "small" => 4,
+        _ => cores,
+    };
+
+    (cores / cores_per_worker.max(1)).clamp(1, MAX_BATCH_WORKERS)
+}
+
+/// 設定値（未指定なら既定値）を有効なワーカー数に丸める
+pub fn resolve_worker_count(configured: Option<usize>, model_size: &str) -> usize {
+    configured
+        .filter(|workers| *workers > 0)
+        .unwrap_or_else(|| default_worker_count(model_size))
+        .min(MAX_BATCH_WORKERS)
+}
+
+/// 最大 `workers` 件ずつ `task` を並列実行する
+///
+/// `task` は成功時に書き起こしIDを返す。結果は入力順に並ぶ。
+pub async fn run_batch<F, Fut, P>(
+    recording_ids: Vec<String>,
+    workers: usize,
+    task: F,
+    on_progress: P,
+) -> BatchTranscriptionResult
+where
+    F: Fn(String) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = AppResult<String>> + Send + 'static,
+    P: Fn(&BatchItemProgress) + Send + Sync + 'static,
+{
+    let batch_id = Uuid::new_v4().to_string();
+    let workers = workers.clamp(1, MAX_BATCH_WORKERS);
+    let total = recording_ids.len();
+    let task = Arc::new(task);
+    let on_progress = Arc::new(on_progress);
+    let semaphore = Arc::new(Semaphore::new(workers));
+    let finished = Arc::new(AtomicUsize::new(0));
+
+    log::info!("📚 Batch {} started: {} files with {} workers", batch_id, total, workers);
+
+    let progress = |recording_id: &str, status: BatchItemStatus, finished: usize| BatchItemProgress {
+        batch_id: batch_id.clone(),
+        recording_id: recording_id.to_string(),
+        status,
+        transcription_id: None,
+        error: None,
+        finished,
+        total,
+    };
+
+    for recording_id in &recording_ids {
+        on_progress(&progress(recording_id, BatchItemStatus::Queued, 0));
+    }
+
+    let mut join_set = JoinSet::new();
+    for (index, recording_id) in recording_ids.into_iter().enumerate() {
+        let task = task.clone();
+        let on_progress = on_progress.clone();
+        let semaphore = semaphore.clone();
+        let finished = finished.clone();
+        let mut item = progress(&recording_id, BatchItemStatus::Running, 0);
+
+        join_set.spawn(async move {
+            // セマフォは閉じないため取得に失敗しない
+            let _permit = semaphore.acquire_owned().await.ok();
+
+            item.finished = finished.load(Ordering::SeqCst);
+            on_progress(&item);
+
+            let outcome = task(recording_id).await;
+            item.finished = finished.fetch_add(1, Ordering::SeqCst) + 1;
+            match outcome {
+                Ok(transcription_id) => {
+                    item.status = BatchItemStatus::Completed;
+                    item.transcription_id = Some(transcription_id);
+                }
+                Err(e) => {
+                    log::error!("❌ Batch transcription failed for {}: {}", item.recording_id, e);
+                    item.status = BatchItemStatus::Failed;
+                    item.error = Some(e.to_string());
+                }
+            }
+            on_progress(&item);
+
+            (index, item)
+        });
+    }
+
+    let mut items: Vec<Option<BatchItemProgress>> = vec![None; total];
+    while let Some(joined) = join_set.join_next().await {
+        match joined {
+            Ok((index, item)) => items[index] = Some(item),
+            Err(e) => log::error!("❌ Batch worker panicked: {}", e),
+        }
+    }
+
+    let items: Vec<BatchItemProgress> = items.into_iter().flatten().collect();
+    let succeeded = items.iter().filter(|item| item.status == BatchItemStatus::Completed).count();
+    let failed = total - succeeded;
+
+    log::info!("✅ Batch {} finished: {} succeeded, {} failed", batch_id, succeeded, failed);
+
+    BatchTranscriptionResult {
+        batch_id,
+        workers,
+        items,
+        succeeded,
+        failed,
+    }
+}
+
+/// 録音を並列に書き起こし、結果をデータベースに保存する
+pub async fn transcribe_recordings<P>(
+    db: Arc<Database>,
+    whisper_service: Arc<WhisperService>,
+    recording_ids: Vec<String>,
+    language: Option<String>,
+    workers: usize,
+    on_progress: P,
+) -> AppResult<BatchTranscriptionResult>
+where
+    P: Fn(&BatchItemProgress) + Send + Sync + 'static,
+{
+    if !whisper_service.is_initialized().await {
+        whisper_service.initialize().await?;
+    }
+
+    let task = move |recording_id: String| {
+        let db = db.clone();
+        let whisper_service = whisper_service.clone();
+        let language = language.clone();
+        async move {
+            let recording = db.get_recording(&recording_id).await?.ok_or_else(|| AppError::ValidationError {
+                message: format!("Recording with id {} not found", recording_id),
+            })?;
+
+            let language = transcription_language::resolve_language(&db, &recording, language).await?;
+            let audio_files = recording_segments::audio_files(&db, &recording).await?;
+            let mut transcription = whisper_service
+                .transcribe_audio_files(&audio_files, recording.id.clone(), language)
+                .await?;
+            corrections::apply_corrections(&db, &mut transcription).await?;
+            db.create_transcription(&transcription).await?;
+
+            Ok(transcription.id)
+        }
+    };
+
+    Ok(run_batch(recording_ids, workers, task, on_progress).await)
+}
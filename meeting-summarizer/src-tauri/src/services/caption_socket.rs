@@ -0,0 +1,216 @@
+//! ライブ字幕のWebSocket配信
+//!
+//! 発表を録音しながら、OBS のブラウザソースや別画面に字幕を表示できるよう、
+//! `LiveCaptionHub` の字幕イベントをローカルの WebSocket で配信する。
+//!
+//! - `GET /` でOBS向けの透過オーバーレイページを返す
+//! - `GET /ws` に接続すると、表示中の字幕を送った後、`live-caption` と同じ形式の
+//!   `CaptionEvent` をJSONのテキストメッセージで送り続ける
+//!
+//! 字幕の内容を外部へ出さないよう、ループバックアドレスでのみ待ち受ける。
+
+use crate::errors::{AppError, AppResult};
+use crate::services::{CaptionEvent, LiveCaptionHub};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::{Html, Response};
+use axum::routing::get;
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::{broadcast, oneshot};
+use tokio::task::JoinHandle;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptionSocketStatus {
+    pub running: bool,
+    /// WebSocket のURL（例: `ws://127.0.0.1:8767/ws`）
+    pub websocket_url: Option<String>,
+    /// OBS のブラウザソースに指定するオーバーレイのURL
+    pub overlay_url: Option<String>,
+}
+
+/// ライブ字幕のWebSocketサーバー
+#[derive(Default)]
+pub struct CaptionSocketServer {
+    address: Option<SocketAddr>,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl CaptionSocketServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn start(&mut self, captions: Arc<LiveCaptionHub>, port: u16) -> AppResult<SocketAddr> {
+        if self.is_running() {
+            return Err(AppError::InvalidOperation {
+                message: "Caption WebSocket server is already running".to_string(),
+            });
+        }
+
+        let listener = tokio::net::TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], port))).await?;
+        let address = listener.local_addr()?;
+
+        let router = Router::new()
+            .route("/", get(overlay))
+            .route("/ws", get(upgrade))
+            .with_state(captions);
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let handle = tokio::spawn(async move {
+            let result = axum::serve(listener, router)
+                .with_graceful_shutdown(async {
+                    let _ = shutdown_rx.await;
+                })
+                .await;
+
+            if let Err(e) = result {
+                log::error!("❌ Caption WebSocket server terminated with error: {}", e);
+            }
+        });
+
+        self.address = Some(address);
+        self.shutdown_tx = Some(shutdown_tx);
+        self.handle = Some(handle);
+
+        log::info!("💬 Caption WebSocket server listening on {}", address);
+        Ok(address)
+    }
+
+    pub async fn stop(&mut self) -> AppResult<()> {
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(());
+        }
+
+        if let Some(handle) = self.handle.take() {
+            handle.await.map_err(|e| AppError::InvalidOperation {
+                message: format!("Failed to stop caption WebSocket server: {}", e),
+            })?;
+        }
+
+        self.address = None;
+        log::info!("🛑 Caption WebSocket server stopped");
+        Ok(())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.handle.as_ref().is_some_and(|handle| !handle.is_finished())
+    }
+
+    pub fn get_status(&self) -> CaptionSocketStatus {
+        let address = self.address.filter(|_| self.is_running());
+        CaptionSocketStatus {
+            running: address.is_some(),
+            websocket_url: address.map(|address| format!("ws://{}/ws", address)),
+            overlay_url: address.map(|address| format!("http://{}/", address)),
+        }
+    }
+}
+
+async fn overlay() -> Html<&'static str> {
+    Html(OVERLAY_PAGE)
+}
+
+async fn upgrade(State(captions): State<Arc<LiveCaptionHub>>, ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |socket| stream_captions(captions, socket))
+}
+
+/// 接続中のクライアントへ字幕を送り続ける
+async fn stream_captions(captions: Arc<LiveCaptionHub>, mut socket: WebSocket) {
+    // 取りこぼしが無いよう、表示中の字幕を送る前に購読しておく
+    let mut receiver = captions.subscribe();
+    for event in captions.captions() {
+        if send_caption(&mut socket, &event).await.is_err() {
+            return;
+        }
+    }
+
+    loop {
+        tokio::select! {
+            received = receiver.recv() => match received {
+                Ok(event) => {
+                    if send_caption(&mut socket, &event).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    log::warn!("⚠️ Caption WebSocket client lagged, skipped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            // クライアントからのメッセージは使わない（切断の検知のみ）
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+async fn send_caption(socket: &mut WebSocket, event: &CaptionEvent) -> Result<(), axum::Error> {
+    let payload = serde_json::to_string(event).map_err(axum::Error::new)?;
+    socket.send(Message::Text(payload)).await
+}
+
+/// OBS のブラウザソース向けの字幕オーバーレイ（背景は透過、直近の字幕を下部に表示）
+const OVERLAY_PAGE: &str = r#"<!doctype html>
+<html lang="ja">
+<head>
+<meta charset="utf-8">
+<title>Meeting Summarizer 字幕</title>
+<style>
+html, body { margin: 0; background: transparent; overflow: hidden; }
+#captions { position: fixed; left: 5%; right: 5%; bottom: 5%; text-align: center; }
+.caption { display: inline-block; margin: 0.15em 0; padding: 0.2em 0.5em; border-radius: 0.2em;
+  background: rgba(0, 0, 0, 0.65); color: #fff; font: 600 36px sans-serif; }
+.caption.partial { opacity: 0.75; }
+</style>
+</head>
+<body>
+<div id="captions"></div>
+<script>
+const MAX_LINES = 2;
+const captions = new Map();
+let sessionId = null;
+
+function render() {
+  const latest = [...captions.values()]
+    .sort((a, b) => a.start_ms - b.start_ms)
+    .slice(-MAX_LINES);
+  const container = document.getElementById('captions');
+  container.replaceChildren(...latest.map((caption) => {
+    const line = document.createElement('div');
+    const span = document.createElement('span');
+    span.className = caption.is_final ? 'caption' : 'caption partial';
+    span.textContent = caption.text;
+    line.appendChild(span);
+    return line;
+  }));
+}
+
+function connect() {
+  const socket = new WebSocket(`ws://${location.host}/ws`);
+  socket.onmessage = (message) => {
+    const caption = JSON.parse(message.data);
+    if (caption.session_id !== sessionId) {
+      sessionId = caption.session_id;
+      captions.clear();
+    }
+    if (caption.removed) {
+      captions.delete(caption.caption_id);
+    } else {
+      captions.set(caption.caption_id, caption);
+    }
+    render();
+  };
+  socket.onclose = () => setTimeout(connect, 2000);
+}
+
+connect();
+</script>
+</body>
+</html>
+"#;
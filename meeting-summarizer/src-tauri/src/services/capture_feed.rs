@@ -0,0 +1,153 @@
+//! 録音中の音声のチャンク配信（ライブ書き起こし・ライブ字幕の土台）
+//!
+//! 録音スレッドは入力元の音声を 16kHz モノラルにしてリングバッファに溜め、設定した長さ（5〜10秒）が
+//! 溜まるごとにチャンクとして切り出して配信する。受け取る側は `subscribe` したチャンネルから、録音を
+//! 止めずに少しずつ処理できる。チャンクの時刻は録音ファイル上の位置（一時停止していた時間は含まない）。
+//!
+//! 同時録音では入力元（マイクなど）の音声だけを配信する。
+
+use crate::errors::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+/// 配信するサンプルのサンプルレート（Whisper と同じ）
+pub const FEED_SAMPLE_RATE: u32 = 16_000;
+
+/// チャンクの長さ（秒）の範囲と既定値
+pub const MIN_CHUNK_SECS: u32 = 5;
+pub const MAX_CHUNK_SECS: u32 = 10;
+pub const DEFAULT_CHUNK_SECS: u32 = 5;
+
+/// 受け取る側が遅れた場合に溜めておけるチャンクの数（超えると古いものから捨てられる）
+const CHANNEL_CAPACITY: usize = 16;
+
+/// 録音中に切り出した音声（16kHz モノラル）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioChunk {
+    /// 録音内の通し番号（0 から）
+    pub index: u64,
+    /// 録音の先頭からの位置
+    pub start_ms: i64,
+    pub samples: Vec<f32>,
+    /// 停止時に残りをまとめたチャンク（指定の長さより短いことがある）
+    pub is_last: bool,
+}
+
+impl AudioChunk {
+    pub fn duration_ms(&self) -> i64 {
+        self.samples.len() as i64 * 1000 / FEED_SAMPLE_RATE as i64
+    }
+
+    pub fn end_ms(&self) -> i64 {
+        self.start_ms + self.duration_ms()
+    }
+}
+
+/// 録音スレッドが書き込み、受け取る側がチャンクを購読するリングバッファ
+#[derive(Clone)]
+pub struct CaptureFeed {
+    /// まだチャンクにしていないサンプル
+    buffer: Arc<Mutex<VecDeque<f32>>>,
+    chunk_frames: Arc<AtomicUsize>,
+    /// これまでに配信したサンプル数とチャンク数
+    emitted_frames: Arc<AtomicU64>,
+    emitted_chunks: Arc<AtomicU64>,
+    sender: broadcast::Sender<AudioChunk>,
+}
+
+impl Default for CaptureFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CaptureFeed {
+    pub fn new() -> Self {
+        Self {
+            buffer: Arc::new(Mutex::new(VecDeque::new())),
+            chunk_frames: Arc::new(AtomicUsize::new(chunk_frames(DEFAULT_CHUNK_SECS))),
+            emitted_frames: Arc::new(AtomicU64::new(0)),
+            emitted_chunks: Arc::new(AtomicU64::new(0)),
+            sender: broadcast::channel(CHANNEL_CAPACITY).0,
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<AudioChunk> {
+        self.sender.subscribe()
+    }
+
+    pub fn chunk_secs(&self) -> u32 {
+        (self.chunk_frames.load(Ordering::Relaxed) / FEED_SAMPLE_RATE as usize) as u32
+    }
+
+    /// チャンクの長さを変える（録音中なら次のチャンクから反映）
+    pub fn set_chunk_secs(&self, secs: u32) -> AppResult<()> {
+        if !(MIN_CHUNK_SECS..=MAX_CHUNK_SECS).contains(&secs) {
+            return Err(AppError::ValidationError {
+                message: format!("Chunk length must be between {} and {} seconds", MIN_CHUNK_SECS, MAX_CHUNK_SECS),
+            });
+        }
+        self.chunk_frames.store(chunk_frames(secs), Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// 録音の開始時に、前の録音の残りと番号を消す
+    pub fn reset(&self) {
+        if let Ok(mut buffer) = self.buffer.lock() {
+            buffer.clear();
+        }
+        self.emitted_frames.store(0, Ordering::Relaxed);
+        self.emitted_chunks.store(0, Ordering::Relaxed);
+    }
+
+    /// 16kHz モノラルのサンプルを溜め、チャンクの長さに達した分を配信する
+    pub fn push(&self, samples: &[f32]) {
+        if samples.is_empty() {
+            return;
+        }
+        let chunk_frames = self.chunk_frames.load(Ordering::Relaxed).max(1);
+        let ready: Vec<Vec<f32>> = match self.buffer.lock() {
+            Ok(mut buffer) => {
+                buffer.extend(samples);
+                let mut ready = Vec::new();
+                while buffer.len() >= chunk_frames {
+                    ready.push(buffer.drain(..chunk_frames).collect());
+                }
+                ready
+            }
+            Err(_) => return,
+        };
+        for samples in ready {
+            self.emit(samples, false);
+        }
+    }
+
+    /// 録音の停止時に、チャンクの長さに満たない残りを最後のチャンクとして配信する
+    pub fn flush(&self) {
+        let rest: Vec<f32> = match self.buffer.lock() {
+            Ok(mut buffer) => buffer.drain(..).collect(),
+            Err(_) => return,
+        };
+        self.emit(rest, true);
+    }
+
+    fn emit(&self, samples: Vec<f32>, is_last: bool) {
+        let frames = samples.len() as u64;
+        let start_frames = self.emitted_frames.fetch_add(frames, Ordering::Relaxed);
+        let chunk = AudioChunk {
+            index: self.emitted_chunks.fetch_add(1, Ordering::Relaxed),
+            start_ms: (start_frames * 1000 / FEED_SAMPLE_RATE as u64) as i64,
+            samples,
+            is_last,
+        };
+        // 購読している人がいなければ捨てる
+        let _ = self.sender.send(chunk);
+    }
+}
+
+fn chunk_frames(secs: u32) -> usize {
+    secs as usize * FEED_SAMPLE_RATE as usize
+}
@@ -0,0 +1,525 @@
+//! 録音の入力元
+//!
+//! 既定のマイクのほか、会議アプリの音声だけを録音できる。アプリ単位の録音は OS ごとの仕組みを
+//! 使う外部プロセスに任せ、標準出力に書き出された音声（f32 リトルエンディアン・モノラル）を
+//! `ProcessCapture` で読み取る。
+//!
+//! - macOS 13 以降: ScreenCaptureKit で指定したアプリの音声だけを取り込む（画面収録の許可が必要）。
+//!   ヘルパーは `helpers/app-audio-capture` の Swift をビルド時にコンパイルしてアプリに同梱する
+//! - Windows: WASAPI のループバックで、既定の出力デバイスに流れる音声（会議の相手の声など）を取り込む。
+//!   Windows 10 2004 以降はプロセス単位のループバックで、指定したアプリの音声だけも取り込める。
+//!   ヘルパーは `helpers/app-audio-capture` の C# をビルド時にコンパイルしてアプリに同梱する
+//! - Linux: PipeWire の `pw-record` で、出力デバイスのモニターや特定のアプリのストリームを取り込む
+//!
+//! オンライン会議では自分の声（マイク）と相手の声（システム音声）の両方を書き起こせるよう、
+//! 2つの入力を別々に録音して1つに混ぜるか、左右のチャンネルに分けて保存できる（`DualSourceMode`）。
+
+use crate::errors::{AppError, AppResult};
+use crate::services::pipewire;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// 外部プロセスに要求するサンプルレート（保存時に 16kHz へ変換する）
+pub const PROCESS_CAPTURE_SAMPLE_RATE: u32 = 48_000;
+
+/// アプリ単位の録音に使うヘルパーのサイドカー名（`helpers/app-audio-capture` を build.rs でビルドする。
+/// macOS は Swift、Windows は C# で書いている）
+pub const APPLICATION_CAPTURE_HELPER: &str = "app-audio-capture";
+
+/// 外部プロセスのエラー出力のうち、録音失敗時のメッセージに含める末尾の長さ
+const STDERR_TAIL_BYTES: usize = 4096;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CaptureSource {
+    /// 既定の入力デバイス
+    #[default]
+    Microphone,
+    /// 名前で指定した入力デバイス（既定以外のマイク）
+    InputDevice { name: String },
+    /// 特定のアプリの音声のみ（macOS は ScreenCaptureKit、Windows はプロセス単位のループバック）
+    ///
+    /// Windows でも macOS のバンドル ID で指定し、対応するプロセス名に読み替える。
+    Application { bundle_id: String },
+    /// 既定の出力デバイスに流れるシステム音声（Windows は WASAPI ループバック、Linux は PipeWire のモニター）
+    SystemAudio,
+    /// PipeWire のノード（名前かシリアル番号）。`monitor` なら出力デバイスのモニターを録音する
+    #[serde(rename = "pipewire_node")]
+    PipeWireNode {
+        node: String,
+        #[serde(default)]
+        monitor: bool,
+    },
+}
+
+/// 録音できる入力の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioSourceType {
+    Microphone,
+    SystemAudio,
+    Application,
+}
+
+/// マイクとシステム音声の同時録音
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DualSourceMode {
+    /// 入力元だけを録音する
+    #[default]
+    Off,
+    /// システム音声を入力元に混ぜてモノラルで保存する
+    Mix,
+    /// 入力元を左、システム音声を右のチャンネルに保存する
+    SeparateChannels,
+}
+
+impl DualSourceMode {
+    /// 保存する WAV のチャンネル数（`requested` は音質で指定されたチャンネル数）
+    pub fn channels(&self, requested: u16) -> u16 {
+        match self {
+            Self::Off | Self::Mix => requested,
+            Self::SeparateChannels => 2,
+        }
+    }
+
+    /// 入力元と組み合わせられるか（システム音声を2回録音しない）
+    pub fn validate_with(&self, source: &CaptureSource) -> AppResult<()> {
+        if *self == Self::Off {
+            return Ok(());
+        }
+        match source {
+            CaptureSource::SystemAudio | CaptureSource::PipeWireNode { monitor: true, .. } => {
+                Err(AppError::ValidationError {
+                    message: "Dual-source recording needs a microphone as the capture source".to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 2つの入力を足し合わせる（短い方の後ろは無音として扱う）
+pub fn mix_sources(primary: &[f32], secondary: &[f32]) -> Vec<f32> {
+    (0..primary.len().max(secondary.len()))
+        .map(|index| {
+            let sample = primary.get(index).copied().unwrap_or(0.0) + secondary.get(index).copied().unwrap_or(0.0);
+            sample.clamp(-1.0, 1.0)
+        })
+        .collect()
+}
+
+/// 2つの入力を左右のチャンネルに交互に並べる（短い方の後ろは無音として扱う）
+pub fn interleave_sources(left: &[f32], right: &[f32]) -> Vec<f32> {
+    (0..left.len().max(right.len()))
+        .flat_map(|index| [left.get(index).copied().unwrap_or(0.0), right.get(index).copied().unwrap_or(0.0)])
+        .collect()
+}
+
+/// 入力の一覧の1件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioDevice {
+    pub name: String,
+    pub source_type: AudioSourceType,
+    /// このデバイスを録音するときの入力元
+    pub capture_source: CaptureSource,
+}
+
+/// アプリ単位で録音できるアプリ
+///
+/// macOS・Windows では対応している会議アプリ、Linux では PipeWire で音声を再生中のアプリのストリーム。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureApplication {
+    /// macOS のバンドル ID（Linux では PipeWire ノードのシリアル番号）
+    pub bundle_id: String,
+    pub name: String,
+    /// いま起動しているか（`capturable_applications` で調べたときだけ）
+    #[serde(default)]
+    pub running: bool,
+    /// このアプリを録音するときの入力元
+    #[serde(default)]
+    pub capture_source: CaptureSource,
+}
+
+struct SupportedApplication {
+    bundle_id: &'static str,
+    name: &'static str,
+    /// macOS の実行ファイル名
+    macos_process: &'static str,
+    /// Windows のプロセス名（`.exe` を除く）
+    windows_process: &'static str,
+}
+
+const SUPPORTED_APPLICATIONS: &[SupportedApplication] = &[
+    SupportedApplication {
+        bundle_id: "us.zoom.xos",
+        name: "Zoom",
+        macos_process: "zoom.us",
+        windows_process: "Zoom",
+    },
+    SupportedApplication {
+        bundle_id: "com.microsoft.teams2",
+        name: "Microsoft Teams",
+        macos_process: "MSTeams",
+        windows_process: "ms-teams",
+    },
+    SupportedApplication {
+        bundle_id: "com.microsoft.teams",
+        name: "Microsoft Teams (classic)",
+        macos_process: "Microsoft Teams",
+        windows_process: "Teams",
+    },
+];
+
+fn find_application(bundle_id: &str) -> Option<&'static SupportedApplication> {
+    SUPPORTED_APPLICATIONS.iter().find(|app| app.bundle_id == bundle_id)
+}
+
+/// アプリ単位で録音できる会議アプリの一覧
+pub fn supported_applications() -> Vec<CaptureApplication> {
+    running_applications(&[])
+}
+
+/// プロセス名の一覧から、対応している会議アプリを起動しているものから順に並べる
+///
+/// プロセス名は macOS・Windows のどちらの名前でもよい（大文字小文字と `.exe` は区別しない）。
+pub fn running_applications(process_names: &[String]) -> Vec<CaptureApplication> {
+    let normalized: Vec<String> = process_names
+        .iter()
+        .map(|name| {
+            let name = name.trim().to_lowercase();
+            name.strip_suffix(".exe").map(str::to_string).unwrap_or(name)
+        })
+        .collect();
+    let mut applications: Vec<CaptureApplication> = SUPPORTED_APPLICATIONS
+        .iter()
+        .map(|app| CaptureApplication {
+            bundle_id: app.bundle_id.to_string(),
+            name: app.name.to_string(),
+            running: normalized.iter().any(|name| {
+                name == &app.macos_process.to_lowercase() || name == &app.windows_process.to_lowercase()
+            }),
+            capture_source: CaptureSource::Application {
+                bundle_id: app.bundle_id.to_string(),
+            },
+        })
+        .collect();
+    applications.sort_by_key(|app| !app.running);
+    applications
+}
+
+/// `ps -axco comm=`（macOS）や `tasklist /fo csv /nh`（Windows）の出力からプロセス名を取り出す
+pub fn parse_process_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let name = match line.strip_prefix('"') {
+                // CSV は先頭の列がイメージ名
+                Some(rest) => rest.split('"').next().unwrap_or_default(),
+                None => line,
+            };
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// PipeWire のノードのうち、音声を再生中のアプリのストリーム
+pub fn pipewire_applications(devices: Vec<AudioDevice>) -> Vec<CaptureApplication> {
+    devices
+        .into_iter()
+        .filter(|device| device.source_type == AudioSourceType::Application)
+        .filter_map(|device| match &device.capture_source {
+            CaptureSource::PipeWireNode { node, .. } => Some(CaptureApplication {
+                bundle_id: node.clone(),
+                name: device.name,
+                running: true,
+                capture_source: device.capture_source.clone(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// この環境でアプリ単位に録音できるアプリを、起動しているかどうか付きで列挙する
+///
+/// Linux では PipeWire で音声を再生中のアプリを返す。アプリ単位の録音ができない環境では空を返す。
+pub fn capturable_applications() -> AppResult<Vec<CaptureApplication>> {
+    if cfg!(target_os = "linux") {
+        if !pipewire::is_available() {
+            return Ok(Vec::new());
+        }
+        return pipewire::list_devices().map(pipewire_applications);
+    }
+
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("ps");
+        command.arg("-axco").arg("comm=");
+        command
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("tasklist");
+        command.args(["/fo", "csv", "/nh"]);
+        command
+    } else {
+        return Ok(Vec::new());
+    };
+
+    let output = command.output().map_err(|e| AppError::Recording {
+        message: format!("Failed to list running applications: {}", e),
+    })?;
+    if !output.status.success() {
+        return Err(AppError::Recording {
+            message: format!(
+                "Failed to list running applications: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        });
+    }
+    let process_names = parse_process_list(&String::from_utf8_lossy(&output.stdout));
+    Ok(running_applications(&process_names))
+}
+
+impl CaptureSource {
+    pub fn validate(&self) -> AppResult<()> {
+        match self {
+            Self::Microphone | Self::SystemAudio => Ok(()),
+            Self::InputDevice { name } if name.trim().is_empty() => Err(AppError::ValidationError {
+                message: "Input device name must not be empty".to_string(),
+            }),
+            Self::InputDevice { .. } => Ok(()),
+            Self::PipeWireNode { node, .. } if node.trim().is_empty() => Err(AppError::ValidationError {
+                message: "PipeWire node must not be empty".to_string(),
+            }),
+            Self::PipeWireNode { .. } => Ok(()),
+            Self::Application { bundle_id } => {
+                if find_application(bundle_id).is_some() {
+                    Ok(())
+                } else {
+                    Err(AppError::ValidationError {
+                        message: format!("Application audio capture is not supported for: {}", bundle_id),
+                    })
+                }
+            }
+        }
+    }
+
+    /// この環境で録音できるか（録音開始時に確認する）
+    pub fn ensure_supported(&self) -> AppResult<()> {
+        self.validate()?;
+        match self {
+            Self::Microphone | Self::InputDevice { .. } => Ok(()),
+            Self::Application { .. } if cfg!(any(target_os = "macos", target_os = "windows")) => Ok(()),
+            Self::Application { .. } => Err(AppError::InvalidOperation {
+                message: "Application audio capture requires macOS 13 or Windows 10 (2004) or later".to_string(),
+            }),
+            Self::SystemAudio if cfg!(any(target_os = "windows", target_os = "linux")) => Ok(()),
+            Self::SystemAudio => Err(AppError::InvalidOperation {
+                message: "System audio capture is not supported on this platform".to_string(),
+            }),
+            Self::PipeWireNode { .. } if cfg!(target_os = "linux") => Ok(()),
+            Self::PipeWireNode { .. } => Err(AppError::InvalidOperation {
+                message: "PipeWire capture is only available on Linux".to_string(),
+            }),
+        }
+    }
+
+    /// 画面やトラックの一覧に表示する名前
+    pub fn label(&self) -> String {
+        match self {
+            Self::Microphone => "Default Microphone".to_string(),
+            Self::InputDevice { name } => name.clone(),
+            Self::SystemAudio => "System Audio".to_string(),
+            Self::Application { bundle_id } => find_application(bundle_id)
+                .map(|app| app.name.to_string())
+                .unwrap_or_else(|| bundle_id.clone()),
+            Self::PipeWireNode { node, .. } => node.clone(),
+        }
+    }
+
+    /// 出力デバイスをループバックで録音する入力元か（Windows の WASAPI）
+    pub fn is_loopback(&self) -> bool {
+        matches!(self, Self::SystemAudio) && cfg!(target_os = "windows")
+    }
+
+    /// 外部プロセスで録音する入力元なら、そのコマンド
+    pub fn process_command(&self) -> AppResult<Option<Command>> {
+        match self {
+            Self::Microphone | Self::InputDevice { .. } => Ok(None),
+            Self::SystemAudio if cfg!(target_os = "linux") => Ok(Some(pipewire::record_command(None, true))),
+            Self::SystemAudio => Ok(None),
+            Self::Application { bundle_id } => application_capture_command(bundle_id).map(Some),
+            Self::PipeWireNode { node, monitor } => Ok(Some(pipewire::record_command(Some(node), *monitor))),
+        }
+    }
+}
+
+fn application_capture_command(bundle_id: &str) -> AppResult<Command> {
+    if cfg!(target_os = "windows") {
+        let app = find_application(bundle_id).ok_or_else(|| AppError::ValidationError {
+            message: format!("Application audio capture is not supported for: {}", bundle_id),
+        })?;
+        let mut command = Command::new(sidecar_path(APPLICATION_CAPTURE_HELPER)?);
+        command
+            .arg(app.windows_process)
+            .arg(PROCESS_CAPTURE_SAMPLE_RATE.to_string());
+        return Ok(command);
+    }
+
+    let mut command = Command::new(sidecar_path(APPLICATION_CAPTURE_HELPER)?);
+    command.arg(bundle_id).arg(PROCESS_CAPTURE_SAMPLE_RATE.to_string());
+    Ok(command)
+}
+
+/// アプリに同梱したヘルパー（Tauri のサイドカー）の実行ファイル
+///
+/// サイドカーはアプリの実行ファイルと同じフォルダに置かれる（`tauri dev` でも同じ）。
+fn sidecar_path(name: &str) -> AppResult<PathBuf> {
+    let path = std::env::current_exe()?.with_file_name(format!("{}{}", name, std::env::consts::EXE_SUFFIX));
+    if !path.is_file() {
+        return Err(AppError::Recording {
+            message: format!("Capture helper is missing from the app bundle: {:?}", path),
+        });
+    }
+    Ok(path)
+}
+
+/// 外部プロセスの標準出力から音声を読み取る
+pub struct ProcessCapture {
+    child: Child,
+    samples: Arc<Mutex<Vec<f32>>>,
+    reader: Option<JoinHandle<()>>,
+    /// エラー出力の末尾（パイプが詰まってプロセスが止まらないよう、別スレッドで読み続ける）
+    stderr: Arc<Mutex<String>>,
+    stderr_reader: Option<JoinHandle<()>>,
+    /// `take_samples` で取り出したサンプル数
+    taken: u64,
+}
+
+impl ProcessCapture {
+    /// `on_chunk` は読み取ったサンプルごとに呼ばれる（進捗・音量の更新用）
+    pub fn spawn<F>(mut command: Command, mut on_chunk: F) -> AppResult<Self>
+    where
+        F: FnMut(&[f32]) + Send + 'static,
+    {
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| AppError::Recording {
+                message: format!("Failed to start capture process: {}", e),
+            })?;
+
+        let mut stdout = child.stdout.take().ok_or_else(|| AppError::Recording {
+            message: "Capture process has no output".to_string(),
+        })?;
+        let stderr = Arc::new(Mutex::new(String::new()));
+        let stderr_reader = child.stderr.take().map(|pipe| {
+            let stderr = stderr.clone();
+            thread::spawn(move || {
+                for line in BufReader::new(pipe).lines() {
+                    let Ok(line) = line else {
+                        break;
+                    };
+                    log::info!("🎙️ Capture process: {}", line);
+                    if let Ok(mut stderr) = stderr.lock() {
+                        stderr.push_str(&line);
+                        stderr.push('\n');
+                        if stderr.len() > STDERR_TAIL_BYTES {
+                            let cut = stderr.len() - STDERR_TAIL_BYTES;
+                            let cut = (cut..stderr.len()).find(|index| stderr.is_char_boundary(*index)).unwrap_or(0);
+                            stderr.drain(..cut);
+                        }
+                    }
+                }
+            })
+        });
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let reader = {
+            let samples = samples.clone();
+            thread::spawn(move || {
+                let mut buffer = [0u8; 8192];
+                let mut pending = Vec::new();
+                loop {
+                    let read = match stdout.read(&mut buffer) {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => read,
+                    };
+                    pending.extend_from_slice(&buffer[..read]);
+
+                    // 4バイトに満たない端数は次の読み取りに回す
+                    let complete = pending.len() / 4 * 4;
+                    let chunk: Vec<f32> = pending[..complete]
+                        .chunks_exact(4)
+                        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                        .collect();
+                    pending.drain(..complete);
+
+                    if chunk.is_empty() {
+                        continue;
+                    }
+                    on_chunk(&chunk);
+                    if let Ok(mut samples) = samples.lock() {
+                        samples.extend_from_slice(&chunk);
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            child,
+            samples,
+            reader: Some(reader),
+            stderr,
+            stderr_reader,
+            taken: 0,
+        })
+    }
+
+    /// ここまでに読み取ったサンプルを取り出す（録音中に少しずつファイルへ書くため）
+    pub fn take_samples(&mut self) -> Vec<f32> {
+        let samples = self
+            .samples
+            .lock()
+            .map(|mut samples| std::mem::take(&mut *samples))
+            .unwrap_or_default();
+        self.taken += samples.len() as u64;
+        samples
+    }
+
+    /// プロセスが終了していれば true（アプリが見つからない等で先に終わった場合）
+    pub fn has_exited(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(Some(_)))
+    }
+
+    /// プロセスを止め、まだ取り出していないサンプルを返す
+    pub fn finish(mut self) -> AppResult<Vec<f32>> {
+        if !self.has_exited() {
+            let _ = self.child.kill();
+        }
+        let status = self.child.wait()?;
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+        if let Some(reader) = self.stderr_reader.take() {
+            let _ = reader.join();
+        }
+
+        let samples = self
+            .samples
+            .lock()
+            .map(|mut samples| std::mem::take(&mut *samples))
+            .unwrap_or_default();
+        if samples.is_empty() && self.taken == 0 {
+            let stderr = self.stderr.lock().map(|stderr| stderr.clone()).unwrap_or_default();
+            return Err(AppError::Recording {
+                message: format!("Capture process produced no audio ({}): {}", status, stderr.trim()),
+            });
+        }
+        Ok(samples)
+    }
+}
@@ -0,0 +1,277 @@
+//! ステレオの通話録音のチャンネルごとの書き起こし
+//!
+//! 通話録音アプリの多くは、自分の声を左、相手の声を右のように話者ごとにチャンネルを分けて保存する。
+//! まとめてモノラルにすると同時に話した部分が混ざり、誰の発言かも分からなくなるため、チャンネルを
+//! 分けて発話区間を検出し、区間ごとに書き起こしてから開始時刻の順に並べ、チャンネルの話者名を付ける。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Transcription, TranscriptSegment, TranscriptionStatus};
+use crate::services::audio_convert::{self, WHISPER_SAMPLE_RATE};
+use crate::services::voice_activity::{self, SpeechRegion};
+use crate::services::whisper_local::SILENT_TRANSCRIPTION;
+use crate::services::{corrections, recording_segments, speaker_analytics, transcription_language, WhisperService};
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// 同じチャンネルの発話の間がこれより短く、間に他のチャンネルの発話が無ければ1つの発言にまとめる
+const MERGE_GAP_MS: i64 = 1_500;
+
+/// 話者名を指定できるチャンネルの数の上限
+pub const MAX_CHANNEL_SPEAKERS: usize = 8;
+
+/// 1つのチャンネルの発言の区間
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelTurn {
+    /// チャンネル（0 から）
+    pub channel: usize,
+    pub start_ms: i64,
+    pub end_ms: i64,
+}
+
+/// チャンネルの話者名（指定が無ければ「Speaker 1」のように番号を付ける）
+pub fn speaker_label(speakers: &[String], channel: usize) -> String {
+    speakers
+        .get(channel)
+        .map(|speaker| speaker.trim())
+        .filter(|speaker| !speaker.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("Speaker {}", channel + 1))
+}
+
+pub fn validate_speakers(speakers: &[String]) -> AppResult<()> {
+    if speakers.len() > MAX_CHANNEL_SPEAKERS {
+        return Err(AppError::ValidationError {
+            message: format!("At most {} channel speakers can be named", MAX_CHANNEL_SPEAKERS),
+        });
+    }
+    Ok(())
+}
+
+/// チャンネルごとの発話区間を、開始時刻の順に並んだ発言にまとめる
+pub fn channel_turns(regions: &[Vec<SpeechRegion>]) -> Vec<ChannelTurn> {
+    let mut turns: Vec<ChannelTurn> = regions
+        .iter()
+        .enumerate()
+        .flat_map(|(channel, regions)| {
+            regions.iter().map(move |region| ChannelTurn {
+                channel,
+                start_ms: region.start_ms,
+                end_ms: region.end_ms,
+            })
+        })
+        .collect();
+    turns.sort_by_key(|turn| (turn.start_ms, turn.channel));
+
+    let mut merged: Vec<ChannelTurn> = Vec::with_capacity(turns.len());
+    for turn in turns {
+        match merged.last_mut() {
+            Some(last) if last.channel == turn.channel && turn.start_ms - last.end_ms < MERGE_GAP_MS => {
+                last.end_ms = last.end_ms.max(turn.end_ms);
+            }
+            _ => merged.push(turn),
+        }
+    }
+    merged
+}
+
+/// 16kHzモノラル WAV から発言の区間を切り出して `output` に書き出す
+fn write_clip(channel_path: &Path, turn: &ChannelTurn, output: &Path) -> AppResult<()> {
+    let mut reader = WavReader::open(channel_path).map_err(|e| clip_error(channel_path, e))?;
+    let start = (turn.start_ms * WHISPER_SAMPLE_RATE as i64 / 1000) as u32;
+    let len = ((turn.end_ms - turn.start_ms) * WHISPER_SAMPLE_RATE as i64 / 1000) as usize;
+    reader.seek(start.min(reader.duration()))?;
+
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: WHISPER_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(output, spec).map_err(|e| clip_error(output, e))?;
+    for sample in reader.samples::<i16>().take(len) {
+        let sample = sample.map_err(|e| clip_error(channel_path, e))?;
+        writer.write_sample(sample).map_err(|e| clip_error(output, e))?;
+    }
+    writer.finalize().map_err(|e| clip_error(output, e))
+}
+
+/// 1つの音声ファイルのチャンネルを分け、発言ごとに書き起こす（時刻は `offset_ms` だけずらす）
+async fn transcribe_file(
+    database: &Database,
+    whisper_service: &WhisperService,
+    audio_path: &Path,
+    recording_id: &str,
+    language: Option<String>,
+    offset_ms: i64,
+) -> AppResult<(Vec<(ChannelTurn, Transcription)>, i64)> {
+    let work_dir = whisper_service.recordings_dir().join("converted");
+    let name = format!("channels-{}", Uuid::new_v4());
+    let input = audio_path.to_path_buf();
+    let task_dir = work_dir.clone();
+    let (channel_paths, turns, duration_ms) = tokio::task::spawn_blocking(move || -> AppResult<_> {
+        let channel_paths = audio_convert::split_channels_for_whisper(&input, &task_dir, &name)?;
+        if channel_paths.len() < 2 {
+            remove_files(&channel_paths);
+            return Err(AppError::ValidationError {
+                message: format!("{:?} is not a multi-channel recording", input),
+            });
+        }
+        let detected = channel_paths
+            .iter()
+            .map(|path| voice_activity::detect_speech_in_file(path, &task_dir))
+            .collect::<AppResult<Vec<_>>>();
+        let detected = match detected {
+            Ok(detected) => detected,
+            Err(e) => {
+                remove_files(&channel_paths);
+                return Err(e);
+            }
+        };
+        let duration_ms = detected.iter().map(|(_, channel_ms)| *channel_ms).max().unwrap_or(0);
+        let regions: Vec<Vec<SpeechRegion>> = detected.into_iter().map(|(regions, _)| regions).collect();
+        Ok((channel_paths, channel_turns(&regions), duration_ms))
+    })
+    .await
+    .map_err(|e| AppError::AudioConversion {
+        message: format!("Channel split task failed: {}", e),
+    })??;
+
+    let result = transcribe_turns(database, whisper_service, &channel_paths, &turns, recording_id, language, &work_dir).await;
+    remove_files(&channel_paths);
+
+    let mut transcribed = result?;
+    for (turn, _) in transcribed.iter_mut() {
+        turn.start_ms += offset_ms;
+        turn.end_ms += offset_ms;
+    }
+    Ok((transcribed, duration_ms))
+}
+
+async fn transcribe_turns(
+    database: &Database,
+    whisper_service: &WhisperService,
+    channel_paths: &[PathBuf],
+    turns: &[ChannelTurn],
+    recording_id: &str,
+    language: Option<String>,
+    work_dir: &Path,
+) -> AppResult<Vec<(ChannelTurn, Transcription)>> {
+    let mut transcribed = Vec::with_capacity(turns.len());
+    for (index, turn) in turns.iter().enumerate() {
+        log::info!("🔀 発言 {}/{} を書き起こし中 (チャンネル {})", index + 1, turns.len(), turn.channel + 1);
+        let clip = work_dir.join(format!("{}-turn{:04}.wav", recording_id, index + 1));
+        let task_clip = clip.clone();
+        let channel_path = channel_paths[turn.channel].clone();
+        let task_turn = *turn;
+        tokio::task::spawn_blocking(move || write_clip(&channel_path, &task_turn, &task_clip))
+            .await
+            .map_err(|e| AppError::AudioConversion {
+                message: format!("Clip task failed: {}", e),
+            })??;
+
+        let result = whisper_service
+            .transcribe_audio_file(&clip, recording_id.to_string(), language.clone())
+            .await;
+        let _ = std::fs::remove_file(&clip);
+        let mut transcription = result?;
+        if transcription.text.trim().is_empty() || transcription.text == SILENT_TRANSCRIPTION {
+            continue;
+        }
+        corrections::apply_corrections(database, &mut transcription).await?;
+        transcribed.push((*turn, transcription));
+    }
+    Ok(transcribed)
+}
+
+/// 録音をチャンネルごとに書き起こし、話者名を付けて時刻の順に並べた書き起こしとして保存する
+///
+/// `speakers` はチャンネルの順の話者名（足りない分は番号）。
+pub async fn transcribe_by_channel(
+    database: &Database,
+    whisper_service: &WhisperService,
+    recording_id: &str,
+    language: Option<String>,
+    speakers: &[String],
+) -> AppResult<Transcription> {
+    validate_speakers(speakers)?;
+    let recording = database
+        .get_recording(recording_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording with id {} not found", recording_id),
+        })?;
+    if !whisper_service.is_initialized().await {
+        whisper_service.initialize().await?;
+    }
+    let language = transcription_language::resolve_language(database, &recording, language).await?;
+    let start_time = std::time::Instant::now();
+
+    // 分けた録音は区間ごとに処理し、前の区間の長さだけ時刻をずらす
+    let mut transcribed = Vec::new();
+    let mut offset_ms = 0;
+    for audio_path in recording_segments::audio_files(database, &recording).await? {
+        let (mut turns, duration_ms) =
+            transcribe_file(database, whisper_service, &audio_path, recording_id, language.clone(), offset_ms).await?;
+        transcribed.append(&mut turns);
+        offset_ms += duration_ms;
+    }
+
+    let text = transcribed
+        .iter()
+        .map(|(turn, transcription)| format!("{}: {}", speaker_label(speakers, turn.channel), transcription.text.trim()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let model_used = transcribed.iter().find_map(|(_, transcription)| transcription.model_used.clone());
+    let transcription = Transcription::new(
+        recording.id.clone(),
+        text,
+        language.unwrap_or_else(|| "ja".to_string()),
+    )
+    .with_confidence(Some(0.95))
+    .with_processing_time(Some(start_time.elapsed().as_millis() as u64))
+    .with_model_used(model_used)
+    .with_status(TranscriptionStatus::Completed);
+
+    let segments: Vec<TranscriptSegment> = transcribed
+        .iter()
+        .enumerate()
+        .map(|(index, (turn, turn_transcription))| {
+            let mut segment = TranscriptSegment::new(
+                transcription.id.clone(),
+                index as i32,
+                turn.start_ms,
+                turn.end_ms,
+                turn_transcription.text.trim().to_string(),
+            );
+            segment.speaker = Some(speaker_label(speakers, turn.channel));
+            segment
+        })
+        .collect();
+
+    database.create_transcription(&transcription).await?;
+    database.create_transcript_segments(&segments).await?;
+    let stats = speaker_analytics::compute_talk_stats(&recording.id, &segments);
+    database.replace_speaker_stats(&recording.id, &stats).await?;
+
+    log::info!(
+        "✅ Transcribed recording {} by channel: {} turn(s)",
+        recording.id,
+        segments.len()
+    );
+    Ok(transcription)
+}
+
+fn remove_files(paths: &[PathBuf]) {
+    for path in paths {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+fn clip_error(path: &Path, error: hound::Error) -> AppError {
+    AppError::AudioConversion {
+        message: format!("Failed to cut turn from {:?}: {}", path, error),
+    }
+}
@@ -0,0 +1,175 @@
+//! チャプターマーカーの検証と書き出し
+//!
+//! トピック分割または手動入力で登録したチャプターを、Markdown の目次・
+//! WebVTT のチャプタートラック・MP3 の ID3 チャプター（CHAP/CTOC）として出力する。
+
+use crate::errors::{AppError, AppResult};
+use crate::models::Chapter;
+use id3::frame::{Chapter as Id3Chapter, TableOfContents};
+use id3::{Frame, Tag, TagLike, Version};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const MAX_TITLE_LENGTH: usize = 200;
+const TOC_ELEMENT_ID: &str = "toc";
+
+/// トピック分割結果などからまとめて登録する際の入力
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChapterDraft {
+    pub title: String,
+    pub start_ms: i64,
+    pub end_ms: Option<i64>,
+}
+
+pub fn validate_chapter(chapter: &Chapter) -> AppResult<()> {
+    let title = chapter.title.trim();
+    if title.is_empty() || title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(AppError::ValidationError {
+            message: format!("Chapter title must be 1-{} characters", MAX_TITLE_LENGTH),
+        });
+    }
+    if chapter.start_ms < 0 {
+        return Err(AppError::ValidationError {
+            message: "Chapter start must not be negative".to_string(),
+        });
+    }
+    if chapter.end_ms.is_some_and(|end| end <= chapter.start_ms) {
+        return Err(AppError::ValidationError {
+            message: "Chapter end must be after its start".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// 各チャプターの (開始, 終了) を解決する
+///
+/// 終了が未指定なら次のチャプターの開始、最後のチャプターは録音の長さまでとする。
+pub fn resolve_ranges(chapters: &[Chapter], total_ms: Option<i64>) -> Vec<(i64, i64)> {
+    chapters
+        .iter()
+        .enumerate()
+        .map(|(index, chapter)| {
+            let next_start = chapters.get(index + 1).map(|next| next.start_ms);
+            let end = chapter
+                .end_ms
+                .or(next_start)
+                .or(total_ms)
+                .unwrap_or(chapter.start_ms)
+                .max(chapter.start_ms);
+            (chapter.start_ms, end)
+        })
+        .collect()
+}
+
+/// Markdown の目次（`HH:MM:SS タイトル` のリスト）
+pub fn render_markdown_toc(chapters: &[Chapter]) -> String {
+    let mut result = String::from("## Chapters\n\n");
+    for (index, chapter) in chapters.iter().enumerate() {
+        result.push_str(&format!(
+            "{}. [{}] {}\n",
+            index + 1,
+            format_timestamp(chapter.start_ms),
+            chapter.title.trim()
+        ));
+    }
+    result
+}
+
+/// WebVTT のチャプタートラック（`<track kind="chapters">` 用）
+pub fn render_vtt_chapters(chapters: &[Chapter], total_ms: Option<i64>) -> String {
+    let mut result = String::from("WEBVTT\n");
+    for (index, (chapter, (start, end))) in chapters.iter().zip(resolve_ranges(chapters, total_ms)).enumerate() {
+        result.push_str(&format!(
+            "\nchapter-{}\n{} --> {}\n{}\n",
+            index + 1,
+            format_vtt_timestamp(start),
+            format_vtt_timestamp(end),
+            // 空行はキューの終端になるため1行にまとめる
+            chapter.title.split_whitespace().collect::<Vec<_>>().join(" ")
+        ));
+    }
+    result
+}
+
+/// MP3 ファイルに ID3v2.4 のチャプター（CHAP）と目次（CTOC）を書き込む
+///
+/// 既存のチャプターと目次は置き換え、それ以外のタグは保持する。
+pub fn write_mp3_chapters(
+    path: &Path,
+    title: Option<&str>,
+    chapters: &[Chapter],
+    total_ms: Option<i64>,
+) -> AppResult<()> {
+    if !path.exists() {
+        return Err(AppError::FileNotFound {
+            path: path.display().to_string(),
+        });
+    }
+
+    let mut tag = match Tag::read_from_path(path) {
+        Ok(tag) => tag,
+        Err(e) if matches!(e.kind, id3::ErrorKind::NoTag) => Tag::new(),
+        Err(e) => return Err(id3_error(e)),
+    };
+
+    tag.remove_all_chapters();
+    tag.remove_all_tables_of_contents();
+    if let Some(title) = title {
+        tag.set_title(title);
+    }
+
+    let mut element_ids = Vec::with_capacity(chapters.len());
+    for (index, (chapter, (start, end))) in chapters.iter().zip(resolve_ranges(chapters, total_ms)).enumerate() {
+        let element_id = format!("chp{}", index + 1);
+        tag.add_frame(Id3Chapter {
+            element_id: element_id.clone(),
+            start_time: clamp_to_u32(start),
+            end_time: clamp_to_u32(end),
+            // バイトオフセットは使用しない
+            start_offset: u32::MAX,
+            end_offset: u32::MAX,
+            frames: vec![Frame::text("TIT2", chapter.title.trim())],
+        });
+        element_ids.push(element_id);
+    }
+
+    if !element_ids.is_empty() {
+        tag.add_frame(TableOfContents {
+            element_id: TOC_ELEMENT_ID.to_string(),
+            top_level: true,
+            ordered: true,
+            elements: element_ids,
+            frames: Vec::new(),
+        });
+    }
+
+    tag.write_to_path(path, Version::Id3v24).map_err(id3_error)?;
+    log::info!("📑 Wrote {} chapters to {}", chapters.len(), path.display());
+    Ok(())
+}
+
+/// `HH:MM:SS`
+pub fn format_timestamp(ms: i64) -> String {
+    let total_seconds = ms.max(0) / 1000;
+    format!(
+        "{:02}:{:02}:{:02}",
+        total_seconds / 3600,
+        (total_seconds % 3600) / 60,
+        total_seconds % 60
+    )
+}
+
+/// `HH:MM:SS.mmm`
+fn format_vtt_timestamp(ms: i64) -> String {
+    format!("{}.{:03}", format_timestamp(ms), ms.max(0) % 1000)
+}
+
+fn clamp_to_u32(ms: i64) -> u32 {
+    ms.clamp(0, u32::MAX as i64) as u32
+}
+
+fn id3_error(error: id3::Error) -> AppError {
+    AppError::InvalidOperation {
+        message: format!("Failed to write MP3 chapter metadata: {}", error),
+    }
+}
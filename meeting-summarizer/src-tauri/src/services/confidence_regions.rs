@@ -0,0 +1,92 @@
+//! 信頼度の低い書き起こし区間の抽出
+//!
+//! 閾値を下回るセグメントを、近接するもの同士でまとめて時刻付きの区間として返す。
+//! UI で音声と照らし合わせて確認すべき箇所をハイライトするために使う。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::TranscriptSegment;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_CONFIDENCE_THRESHOLD: f32 = 0.6;
+
+/// この間隔以内で続く低信頼度セグメントは1つの区間にまとめる
+pub const DEFAULT_MERGE_GAP_MS: i64 = 1_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LowConfidenceRegion {
+    pub transcription_id: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+    pub min_confidence: f32,
+    pub average_confidence: f32,
+    pub segment_ids: Vec<String>,
+}
+
+/// 閾値未満のセグメントを区間にまとめる（信頼度が無いセグメントは対象外）
+pub fn find_regions(segments: &[TranscriptSegment], threshold: f32, merge_gap_ms: i64) -> Vec<LowConfidenceRegion> {
+    let mut regions: Vec<LowConfidenceRegion> = Vec::new();
+    // 平均計算用に区間ごとの信頼度の合計を保持する
+    let mut sums: Vec<f32> = Vec::new();
+    let mut previous_index: Option<i32> = None;
+
+    for segment in segments {
+        let Some(confidence) = segment.confidence.filter(|&confidence| confidence < threshold) else {
+            previous_index = None;
+            continue;
+        };
+
+        let continues = previous_index.is_some_and(|index| index + 1 == segment.segment_index)
+            && regions.last().is_some_and(|region| segment.start_ms - region.end_ms <= merge_gap_ms);
+        match (continues, regions.last_mut(), sums.last_mut()) {
+            (true, Some(region), Some(sum)) => {
+                region.end_ms = region.end_ms.max(segment.end_ms);
+                region.text.push(' ');
+                region.text.push_str(segment.text.trim());
+                region.min_confidence = region.min_confidence.min(confidence);
+                region.segment_ids.push(segment.id.clone());
+                *sum += confidence;
+                region.average_confidence = *sum / region.segment_ids.len() as f32;
+            }
+            _ => {
+                regions.push(LowConfidenceRegion {
+                    transcription_id: segment.transcription_id.clone(),
+                    start_ms: segment.start_ms,
+                    end_ms: segment.end_ms,
+                    text: segment.text.trim().to_string(),
+                    min_confidence: confidence,
+                    average_confidence: confidence,
+                    segment_ids: vec![segment.id.clone()],
+                });
+                sums.push(confidence);
+            }
+        }
+        previous_index = Some(segment.segment_index);
+    }
+
+    regions
+}
+
+pub async fn get_low_confidence_regions(
+    database: &Database,
+    transcription_id: &str,
+    threshold: Option<f32>,
+) -> AppResult<Vec<LowConfidenceRegion>> {
+    let threshold = threshold.unwrap_or(DEFAULT_CONFIDENCE_THRESHOLD);
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(AppError::ValidationError {
+            message: format!("Confidence threshold must be between 0 and 1: {}", threshold),
+        });
+    }
+
+    database
+        .get_transcription(transcription_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Transcription with id {} not found", transcription_id),
+        })?;
+
+    let segments = database.get_segments_by_transcription(transcription_id).await?;
+    Ok(find_regions(&segments, threshold, DEFAULT_MERGE_GAP_MS))
+}
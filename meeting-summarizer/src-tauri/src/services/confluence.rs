@@ -0,0 +1,170 @@
+//! Confluence への議事録公開（REST API + ストレージ形式）
+//!
+//! 要約を指定スペース（親ページの下）に新しいページとして作成する。重要ポイントはパネルマクロ、
+//! アクションアイテムはタスクリストとして書き出すため、Confluence 上でそのままチェックできる。
+//! API トークンは OS のキーチェーンに保存する。
+
+use crate::errors::{AppError, AppResult};
+use crate::models::Summary;
+use crate::services::app_settings::ConfluenceSettings;
+use crate::services::credentials;
+use crate::services::html_export::escape_html;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+const API_TOKEN_KEY: &str = "confluence-api-token";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfluencePage {
+    pub page_id: String,
+    pub title: String,
+    pub url: String,
+}
+
+pub struct ConfluenceService {
+    client: Client,
+    settings: ConfluenceSettings,
+    api_token: String,
+}
+
+impl ConfluenceService {
+    pub fn new(settings: ConfluenceSettings, api_token: String) -> Self {
+        Self {
+            client: Client::new(),
+            settings,
+            api_token,
+        }
+    }
+
+    /// 設定とキーチェーンに保存済みのトークンから生成
+    pub fn from_settings(settings: &ConfluenceSettings) -> AppResult<Self> {
+        settings.validate()?;
+        let api_token = credentials::load_secret(API_TOKEN_KEY)?.ok_or_else(|| AppError::Integration {
+            message: "Confluence API token is not configured".to_string(),
+        })?;
+        Ok(Self::new(settings.clone(), api_token))
+    }
+
+    pub fn save_api_token(api_token: &str) -> AppResult<()> {
+        if api_token.trim().is_empty() {
+            return Err(AppError::ValidationError {
+                message: "Confluence API token must not be empty".to_string(),
+            });
+        }
+        credentials::save_secret(API_TOKEN_KEY, api_token.trim())
+    }
+
+    pub fn is_configured() -> AppResult<bool> {
+        Ok(credentials::load_secret(API_TOKEN_KEY)?.is_some())
+    }
+
+    pub fn clear_api_token() -> AppResult<()> {
+        credentials::delete_secret(API_TOKEN_KEY)
+    }
+
+    /// 要約を新しいページとして公開
+    pub async fn publish_summary(&self, title: &str, summary: &Summary) -> AppResult<ConfluencePage> {
+        let base_url = self.settings.base_url.trim_end_matches('/');
+
+        let mut payload = json!({
+            "type": "page",
+            "title": title,
+            "space": { "key": self.settings.space_key },
+            "body": {
+                "storage": {
+                    "value": build_storage_body(summary),
+                    "representation": "storage",
+                }
+            }
+        });
+        if let Some(parent_page_id) = self.settings.parent_page_id.as_deref().filter(|id| !id.trim().is_empty()) {
+            payload["ancestors"] = json!([{ "id": parent_page_id }]);
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/rest/api/content", base_url))
+            .basic_auth(&self.settings.username, Some(&self.api_token))
+            .json(&payload)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(AppError::Integration {
+                message: format!(
+                    "Confluence returned {}: {}",
+                    response.status(),
+                    response.text().await.unwrap_or_default()
+                ),
+            });
+        }
+
+        let created: serde_json::Value = response.json().await?;
+        let page_id = created
+            .get("id")
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| AppError::Integration {
+                message: "Confluence response did not include page id".to_string(),
+            })?
+            .to_string();
+        let url = match (
+            created.pointer("/_links/base").and_then(|v| v.as_str()),
+            created.pointer("/_links/webui").and_then(|v| v.as_str()),
+        ) {
+            (Some(base), Some(webui)) => format!("{}{}", base, webui),
+            _ => format!("{}/pages/viewpage.action?pageId={}", base_url, page_id),
+        };
+
+        log::info!("📘 Published summary {} to Confluence page {}", summary.id, page_id);
+
+        Ok(ConfluencePage {
+            page_id,
+            title: title.to_string(),
+            url,
+        })
+    }
+}
+
+/// ページ本文（ストレージ形式の XHTML）を組み立てる
+pub fn build_storage_body(summary: &Summary) -> String {
+    let mut body = format!(
+        r#"<ac:structured-macro ac:name="info"><ac:rich-text-body><p>{} · {}</p></ac:rich-text-body></ac:structured-macro>"#,
+        summary.created_at.format("%Y-%m-%d %H:%M"),
+        escape_html(&summary.model_used)
+    );
+
+    body.push_str("<h2>Summary</h2>");
+    for paragraph in summary.summary_text.lines().filter(|line| !line.trim().is_empty()) {
+        body.push_str(&format!("<p>{}</p>", escape_html(paragraph.trim())));
+    }
+
+    if !summary.key_points.is_empty() {
+        let items: String = summary
+            .key_points
+            .iter()
+            .map(|point| format!("<li>{}</li>", escape_html(point)))
+            .collect();
+        body.push_str(&format!(
+            r#"<ac:structured-macro ac:name="panel"><ac:parameter ac:name="title">Key Points</ac:parameter><ac:rich-text-body><ul>{}</ul></ac:rich-text-body></ac:structured-macro>"#,
+            items
+        ));
+    }
+
+    if !summary.action_items.is_empty() {
+        let tasks: String = summary
+            .action_items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                format!(
+                    "<ac:task><ac:task-id>{}</ac:task-id><ac:task-status>incomplete</ac:task-status><ac:task-body>{}</ac:task-body></ac:task>",
+                    index + 1,
+                    escape_html(item)
+                )
+            })
+            .collect();
+        body.push_str(&format!("<h2>Action Items</h2><ac:task-list>{}</ac:task-list>", tasks));
+    }
+
+    body
+}
@@ -0,0 +1,366 @@
+//! 外部ツールからの操作用ローカルサーバー
+//!
+//! Stream Deck のボタンやショートカットアプリ、スクリプトから、画面を操作せずに録音の開始・停止や
+//! 書き起こしを実行できるようにする。Stream Deck の「Website」アクションや `curl` から
+//! そのまま呼べるよう、すべての操作を GET / POST のどちらでも受け付ける。
+//!
+//! - `/status` 録音中かどうか
+//! - `/recording/start` `/recording/stop` `/recording/toggle` 録音の開始・停止
+//!   （停止時に `?transcribe=true` を付けると書き起こしをジョブキューに登録する）
+//! - `/recording/marker` 録音中の位置にマーカーを付ける（`?label=` で名前を指定）
+//! - `/transcribe/latest` 最新の録音の書き起こしを登録する
+//!
+//! ループバックアドレスでのみ待ち受け、`X-Control-Token` ヘッダーのトークンが一致しない要求は
+//! 401 で拒否する。ヘッダーを付けられない「Website」アクションのため、GET に限り `?token=` でも受け付ける。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Job, JobPayload};
+use crate::services::api_server::ApiError;
+use crate::services::phone_mic::generate_token;
+use crate::services::{credentials, JobQueue, RecordingService};
+use axum::extract::{Query, Request, State};
+use axum::http::{HeaderMap, Method, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use subtle::ConstantTimeEq;
+use std::sync::Arc;
+use tokio::sync::{broadcast, oneshot};
+use tokio::task::JoinHandle;
+
+pub const CONTROL_EVENT: &str = "external-control";
+
+/// トークンを渡すヘッダー
+pub const CONTROL_TOKEN_HEADER: &str = "x-control-token";
+
+/// 渡されたトークンが一致するか（比較にかかる時間から推測されないよう定数時間で比べる）
+pub fn token_matches(provided: Option<&str>, expected: &str) -> bool {
+    provided.is_some_and(|provided| bool::from(provided.as_bytes().ct_eq(expected.as_bytes())))
+}
+
+/// キーチェーンに保存するトークンのキー
+const CONTROL_TOKEN_KEY: &str = "control-server-token";
+
+/// 外部から実行された操作（画面の表示を合わせるために通知する）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ControlEvent {
+    RecordingStarted { session_id: String },
+    RecordingStopped { recording_id: String },
+    TranscriptionQueued { recording_id: String, job_id: String },
+    MarkerAdded { marker_id: String },
+}
+
+/// 各操作の応答
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ControlResult {
+    /// 操作後に録音中かどうか
+    pub recording: bool,
+    pub session_id: Option<String>,
+    pub recording_id: Option<String>,
+    pub job_id: Option<String>,
+    pub marker_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlServerStatus {
+    pub running: bool,
+    pub url: Option<String>,
+}
+
+/// 保存済みのトークン（無ければ生成して保存する）
+pub fn load_or_create_token() -> AppResult<String> {
+    if let Some(token) = credentials::load_secret(CONTROL_TOKEN_KEY)? {
+        return Ok(token);
+    }
+    regenerate_token()
+}
+
+/// トークンを作り直す（以前のトークンを設定した外部ツールは使えなくなる）
+pub fn regenerate_token() -> AppResult<String> {
+    let token = generate_token();
+    credentials::save_secret(CONTROL_TOKEN_KEY, &token)?;
+    Ok(token)
+}
+
+#[derive(Clone)]
+struct ControlState {
+    db: Arc<Database>,
+    recording_service: Arc<RecordingService>,
+    job_queue: Arc<JobQueue>,
+    token: String,
+    events: broadcast::Sender<ControlEvent>,
+}
+
+impl ControlState {
+    fn notify(&self, event: ControlEvent) {
+        // 画面が購読していなくても操作は成功させる
+        let _ = self.events.send(event);
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ControlQuery {
+    token: Option<String>,
+    #[serde(default)]
+    transcribe: bool,
+    label: Option<String>,
+    language: Option<String>,
+}
+
+/// 外部操作用のローカルサーバー
+pub struct ControlServer {
+    address: Option<SocketAddr>,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+    events: broadcast::Sender<ControlEvent>,
+}
+
+impl Default for ControlServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ControlServer {
+    pub fn new() -> Self {
+        Self {
+            address: None,
+            shutdown_tx: None,
+            handle: None,
+            events: broadcast::channel(16).0,
+        }
+    }
+
+    /// 外部から実行された操作を購読する
+    pub fn subscribe(&self) -> broadcast::Receiver<ControlEvent> {
+        self.events.subscribe()
+    }
+
+    pub async fn start(
+        &mut self,
+        db: Arc<Database>,
+        recording_service: Arc<RecordingService>,
+        job_queue: Arc<JobQueue>,
+        token: String,
+        port: u16,
+    ) -> AppResult<SocketAddr> {
+        if self.is_running() {
+            return Err(AppError::InvalidOperation {
+                message: "Control server is already running".to_string(),
+            });
+        }
+        if token.trim().is_empty() {
+            return Err(AppError::ValidationError {
+                message: "Control token must not be empty".to_string(),
+            });
+        }
+
+        let listener = tokio::net::TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], port))).await?;
+        let address = listener.local_addr()?;
+
+        let state = ControlState {
+            db,
+            recording_service,
+            job_queue,
+            token,
+            events: self.events.clone(),
+        };
+        let router = Router::new()
+            .route("/status", get(status).post(status))
+            .route("/recording/start", get(start_recording).post(start_recording))
+            .route("/recording/stop", get(stop_recording).post(stop_recording))
+            .route("/recording/toggle", get(toggle_recording).post(toggle_recording))
+            .route("/recording/marker", get(add_marker).post(add_marker))
+            .route("/transcribe/latest", get(transcribe_latest).post(transcribe_latest))
+            .layer(middleware::from_fn_with_state(state.clone(), require_token))
+            .with_state(state);
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let handle = tokio::spawn(async move {
+            let result = axum::serve(listener, router)
+                .with_graceful_shutdown(async {
+                    let _ = shutdown_rx.await;
+                })
+                .await;
+
+            if let Err(e) = result {
+                log::error!("❌ Control server terminated with error: {}", e);
+            }
+        });
+
+        self.address = Some(address);
+        self.shutdown_tx = Some(shutdown_tx);
+        self.handle = Some(handle);
+
+        log::info!("🎛️ Control server listening on {}", address);
+        Ok(address)
+    }
+
+    pub async fn stop(&mut self) -> AppResult<()> {
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(());
+        }
+
+        if let Some(handle) = self.handle.take() {
+            handle.await.map_err(|e| AppError::InvalidOperation {
+                message: format!("Failed to stop control server: {}", e),
+            })?;
+        }
+
+        self.address = None;
+        log::info!("🛑 Control server stopped");
+        Ok(())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.handle.as_ref().is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// 待ち受け中のアドレス（停止中は None）
+    pub fn address(&self) -> Option<SocketAddr> {
+        self.address.filter(|_| self.is_running())
+    }
+
+    pub fn get_status(&self) -> ControlServerStatus {
+        let address = self.address();
+        ControlServerStatus {
+            running: address.is_some(),
+            url: address.map(|address| format!("http://{}", address)),
+        }
+    }
+}
+
+async fn require_token(
+    State(state): State<ControlState>,
+    Query(query): Query<ControlQuery>,
+    headers: HeaderMap,
+    request: Request,
+    next: Next,
+) -> Response {
+    let header = headers.get(CONTROL_TOKEN_HEADER).and_then(|value| value.to_str().ok());
+    // ヘッダーを付けられない Stream Deck の「Website」アクション向けに、GET だけはクエリでも受け付ける
+    let provided = match header {
+        Some(header) => Some(header),
+        None if request.method() == Method::GET => query.token.as_deref(),
+        None => None,
+    };
+    if !token_matches(provided, &state.token) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    next.run(request).await
+}
+
+type ControlResponse = Result<Json<ControlResult>, ApiError>;
+
+async fn current_result(state: &ControlState) -> ControlResult {
+    let session_id = state.recording_service.current_progress().await.map(|progress| progress.session_id);
+    ControlResult {
+        recording: session_id.is_some(),
+        session_id,
+        ..ControlResult::default()
+    }
+}
+
+async fn status(State(state): State<ControlState>) -> ControlResponse {
+    Ok(Json(current_result(&state).await))
+}
+
+async fn start_recording(State(state): State<ControlState>) -> ControlResponse {
+    if state.recording_service.current_progress().await.is_some() {
+        return Err(AppError::InvalidOperation {
+            message: "Recording is already in progress".to_string(),
+        }
+        .into());
+    }
+
+    let session_id = state.recording_service.start_recording().await?;
+    log::info!("🎛️ Recording started by external control: {}", session_id);
+    state.notify(ControlEvent::RecordingStarted { session_id: session_id.clone() });
+    Ok(Json(ControlResult {
+        recording: true,
+        session_id: Some(session_id),
+        ..ControlResult::default()
+    }))
+}
+
+async fn stop_recording(State(state): State<ControlState>, Query(query): Query<ControlQuery>) -> ControlResponse {
+    if state.recording_service.current_progress().await.is_none() {
+        return Err(AppError::InvalidOperation {
+            message: "No recording in progress".to_string(),
+        }
+        .into());
+    }
+
+    let recording = state.recording_service.stop_recording().await?;
+    log::info!("🎛️ Recording stopped by external control: {}", recording.id);
+    state.notify(ControlEvent::RecordingStopped { recording_id: recording.id.clone() });
+
+    let job_id = if query.transcribe {
+        Some(enqueue_transcription(&state, &recording.id, query.language).await?)
+    } else {
+        None
+    };
+    Ok(Json(ControlResult {
+        recording: false,
+        recording_id: Some(recording.id),
+        job_id,
+        ..ControlResult::default()
+    }))
+}
+
+async fn toggle_recording(State(state): State<ControlState>, query: Query<ControlQuery>) -> ControlResponse {
+    if state.recording_service.current_progress().await.is_some() {
+        stop_recording(State(state), query).await
+    } else {
+        start_recording(State(state)).await
+    }
+}
+
+async fn add_marker(State(state): State<ControlState>, Query(query): Query<ControlQuery>) -> ControlResponse {
+    if state.recording_service.current_progress().await.is_none() {
+        return Err(AppError::InvalidOperation {
+            message: "No recording in progress".to_string(),
+        }
+        .into());
+    }
+
+    let marker = state.recording_service.add_marker(query.label).await?;
+    state.notify(ControlEvent::MarkerAdded { marker_id: marker.id.clone() });
+    Ok(Json(ControlResult {
+        marker_id: Some(marker.id),
+        ..current_result(&state).await
+    }))
+}
+
+async fn transcribe_latest(State(state): State<ControlState>, Query(query): Query<ControlQuery>) -> ControlResponse {
+    let latest = state.db.get_recordings(false).await?.into_iter().next().ok_or_else(|| AppError::FileNotFound {
+        path: "recordings/latest".to_string(),
+    })?;
+
+    let job_id = enqueue_transcription(&state, &latest.id, query.language).await?;
+    Ok(Json(ControlResult {
+        recording_id: Some(latest.id),
+        job_id: Some(job_id),
+        ..current_result(&state).await
+    }))
+}
+
+async fn enqueue_transcription(state: &ControlState, recording_id: &str, language: Option<String>) -> AppResult<String> {
+    let job = state
+        .job_queue
+        .enqueue(Job::new(JobPayload::Transcription {
+            recording_id: recording_id.to_string(),
+            language,
+        }))
+        .await?;
+    state.notify(ControlEvent::TranscriptionQueued {
+        recording_id: recording_id.to_string(),
+        job_id: job.id.clone(),
+    });
+    Ok(job.id)
+}
@@ -0,0 +1,81 @@
+//! 誤認識語の補正辞書
+//!
+//! 「クバネテス」→「Kubernetes」のような対応をユーザーが登録し、書き起こし直後に自動で置き換える。
+//! エントリごとに置き換え回数を記録し、何が補正されているかを確認できるようにする。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{CorrectionEntry, Transcription};
+
+/// 補正を適用したテキストと、エントリIDごとの置き換え回数
+pub fn apply(text: &str, entries: &[CorrectionEntry]) -> (String, Vec<(String, i64)>) {
+    // 長い表記を優先し、置き換え後の文字列は再度置き換えない（1パスで走査）
+    let mut candidates: Vec<&CorrectionEntry> = entries
+        .iter()
+        .filter(|entry| entry.enabled && !entry.wrong.is_empty())
+        .collect();
+    candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.wrong.len()));
+
+    let mut counts = vec![0i64; candidates.len()];
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        match candidates.iter().position(|entry| rest.starts_with(&entry.wrong)) {
+            Some(index) => {
+                result.push_str(&candidates[index].correct);
+                rest = &rest[candidates[index].wrong.len()..];
+                counts[index] += 1;
+            }
+            None => {
+                result.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    let hits = candidates
+        .iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .map(|(entry, count)| (entry.id.clone(), count))
+        .collect();
+    (result, hits)
+}
+
+/// 書き起こしの本文とセグメントに補正辞書を適用して置き換え回数を記録する（保存前に呼び出す）
+pub async fn apply_corrections(database: &Database, transcription: &mut Transcription) -> AppResult<i64> {
+    let entries = database.get_correction_entries().await?;
+    if entries.is_empty() {
+        return Ok(0);
+    }
+
+    let (text, hits) = apply(&transcription.text, &entries);
+    if hits.is_empty() {
+        return Ok(0);
+    }
+
+    transcription.text = text;
+    // セグメントは本文と同じ発言なので置き換えるだけで、回数は本文の分だけを記録する
+    for segment in &mut transcription.segments {
+        segment.text = apply(&segment.text, &entries).0;
+    }
+    database.record_correction_hits(&hits).await?;
+
+    let total = hits.iter().map(|(_, count)| count).sum();
+    log::info!("📝 Applied {} dictionary correction(s) to transcription {}", total, transcription.id);
+    Ok(total)
+}
+
+pub fn validate_entry(wrong: &str, correct: &str) -> AppResult<()> {
+    if wrong.trim().is_empty() {
+        return Err(AppError::ValidationError {
+            message: "Misrecognized term must not be empty".to_string(),
+        });
+    }
+    if wrong == correct {
+        return Err(AppError::ValidationError {
+            message: "Correct term must differ from the misrecognized term".to_string(),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! 外部連携の認証情報をOSのキーチェーン（Keychain / Credential Manager / keyutils）に保存する
+//!
+//! キーはワークスペースごとに分ける。既定のワークスペースは従来どおりのキーをそのまま使い、
+//! それ以外のワークスペースはキーの前にワークスペースIDを付ける。
+
+use crate::errors::{AppError, AppResult};
+use crate::services::workspaces::DEFAULT_WORKSPACE_ID;
+use keyring::Entry;
+use std::sync::RwLock;
+
+const SERVICE_NAME: &str = "com.kenshiroebisu.meeting-summarizer";
+
+/// 読み書きに使うワークスペース（None なら既定のワークスペース）
+static WORKSPACE: RwLock<Option<String>> = RwLock::new(None);
+
+/// 以降の読み書きに使うワークスペースを選ぶ（起動時に選択中のワークスペースで呼ぶ）
+pub fn set_workspace(workspace_id: &str) {
+    if let Ok(mut workspace) = WORKSPACE.write() {
+        *workspace = (workspace_id != DEFAULT_WORKSPACE_ID).then(|| workspace_id.to_string());
+    }
+}
+
+/// ワークスペースのキーチェーン上のキー
+pub fn workspace_key(workspace_id: &str, key: &str) -> String {
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        key.to_string()
+    } else {
+        format!("workspaces/{}/{}", workspace_id, key)
+    }
+}
+
+fn entry(key: &str) -> AppResult<Entry> {
+    let workspace = WORKSPACE.read().ok().and_then(|workspace| workspace.clone());
+    let key = workspace_key(workspace.as_deref().unwrap_or(DEFAULT_WORKSPACE_ID), key);
+    Entry::new(SERVICE_NAME, &key).map_err(to_app_error)
+}
+
+fn to_app_error(error: keyring::Error) -> AppError {
+    AppError::Integration {
+        message: format!("Credential store error: {}", error),
+    }
+}
+
+/// シークレットを保存
+pub fn save_secret(key: &str, value: &str) -> AppResult<()> {
+    entry(key)?.set_password(value).map_err(to_app_error)
+}
+
+/// シークレットを取得（未保存の場合は None）
+pub fn load_secret(key: &str) -> AppResult<Option<String>> {
+    match entry(key)?.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(to_app_error(e)),
+    }
+}
+
+/// シークレットを削除（未保存でもエラーにしない）
+pub fn delete_secret(key: &str) -> AppResult<()> {
+    match entry(key)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(to_app_error(e)),
+    }
+}
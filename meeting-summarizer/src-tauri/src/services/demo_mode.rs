@@ -0,0 +1,152 @@
+//! デモ（オフライン）モード
+//!
+//! マイク・Python・LLMサーバーが無い環境でも、録音 → 書き起こし → 要約の流れを
+//! 一通り試せるようにする。有効な間は次のように置き換わり、結果は常に同じになる。
+//!
+//! - 録音：マイクの代わりに決まった波形を録音時間分だけ書き出す
+//! - 書き起こし：Whisper を呼ばず、用意した会議の書き起こしを返す
+//! - 要約：`LLMProvider::Demo` が用意した応答を返す
+//!
+//! 設定の `demo_mode` か、環境変数 `MEETING_SUMMARIZER_DEMO=1` で有効になる。
+
+use crate::models::{LLMConfig, LLMProvider, TranscriptSegment, Transcription, TranscriptionStatus};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub const DEMO_MODE_ENV: &str = "MEETING_SUMMARIZER_DEMO";
+
+/// デモモードで使うモデル名（書き起こし・要約の `model_used`）
+pub const DEMO_MODEL_NAME: &str = "demo";
+
+/// デモモードで表示する録音デバイス名
+pub const DEMO_AUDIO_DEVICE: &str = "Demo Microphone";
+
+/// デモの書き起こしで1文に割り当てる長さ
+pub const DEMO_SEGMENT_MS: i64 = 4_000;
+
+static DEMO_MODE: AtomicBool = AtomicBool::new(false);
+
+pub const DEMO_TRANSCRIPT: &str = "それでは定例会議を始めます。今日の議題は新しい会員アプリのリリース日程です。\
+山田さん、開発の状況を教えてください。\
+はい、ログイン画面と決済画面は完成していて、残りは通知機能のテストだけです。来週の水曜日までに終わる見込みです。\
+ありがとうございます。佐藤さん、サポート側の準備はどうでしょうか。\
+問い合わせ対応のマニュアルを作成中です。リリースの前日までに共有します。\
+わかりました。では、リリース日は再来週の月曜日で確定とします。\
+山田さんは通知機能のテスト結果を水曜日に報告してください。\
+佐藤さんはマニュアルをリリース前日までにチームへ共有してください。\
+以上で今日の会議を終わります。";
+
+const DEMO_SUMMARY_RESPONSE: &str = "## 要約
+新しい会員アプリのリリース日程を確認する定例会議が行われた。開発はログイン画面と決済画面が完成しており、残りは通知機能のテストのみである。サポート側は問い合わせ対応のマニュアルを作成中である。リリース日は再来週の月曜日で確定した。
+
+## 重要ポイント
+- リリース日は再来週の月曜日で確定
+- ログイン画面と決済画面は完成済み
+- 通知機能のテストは来週水曜日までに完了見込み
+- 問い合わせ対応マニュアルを作成中
+
+## アクションアイテム
+- 山田さん：通知機能のテスト結果を水曜日に報告する
+- 佐藤さん：マニュアルをリリース前日までにチームへ共有する
+";
+
+const DEMO_ROLLING_SUMMARY_RESPONSE: &str = "- 新しい会員アプリのリリース日程について議論中
+- 開発は通知機能のテストを残すのみ
+- サポートのマニュアルはリリース前日までに共有予定";
+
+const DEMO_DIGEST_RESPONSE: &str = "## 概要
+期間中は新しい会員アプリのリリースに向けた準備が中心だった。開発は通知機能のテストを残すのみとなり、リリース日が確定した。
+
+## テーマ
+- 会員アプリのリリース準備
+- サポート体制の整備
+
+## 決定事項
+- リリース日は再来週の月曜日（定例会議）
+";
+
+const DEMO_PERSON_NAMES_RESPONSE: &str = "山田\n佐藤";
+
+pub fn set_enabled(enabled: bool) {
+    DEMO_MODE.store(enabled, Ordering::Relaxed);
+    log::info!("🧪 Demo mode {}", if enabled { "enabled" } else { "disabled" });
+}
+
+pub fn is_enabled() -> bool {
+    DEMO_MODE.load(Ordering::Relaxed)
+}
+
+/// 環境変数でデモモードが指定されているか
+pub fn requested_by_env() -> bool {
+    std::env::var(DEMO_MODE_ENV)
+        .map(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+        .unwrap_or(false)
+}
+
+/// 録音の代わりに書き出す波形（サンプル番号だけで決まる、話し声に近い抑揚のある音）
+pub fn demo_sample(index: u64, sample_rate: u32) -> f32 {
+    let time = index as f32 / sample_rate as f32;
+    // 0.25秒ごとに音の高さを変え、1秒のうち最後の0.2秒は無音にする
+    let syllable = (time * 4.0) as u32 % 4;
+    let frequency = 180.0 + 40.0 * syllable as f32;
+    if time.fract() >= 0.8 {
+        return 0.0;
+    }
+    0.3 * (2.0 * std::f32::consts::PI * frequency * time).sin()
+}
+
+/// 用意した会議の書き起こし（1文ずつ時刻付きのセグメントも付ける）
+pub fn demo_transcription(recording_id: String, language: Option<String>) -> Transcription {
+    let mut transcription = Transcription::new(
+        recording_id,
+        DEMO_TRANSCRIPT.to_string(),
+        language.unwrap_or_else(|| "ja".to_string()),
+    )
+    .with_confidence(Some(0.95))
+    .with_processing_time(Some(0))
+    .with_model_used(Some(DEMO_MODEL_NAME.to_string()))
+    .with_status(TranscriptionStatus::Completed);
+    transcription.segments = demo_segments(&transcription.id);
+    transcription
+}
+
+fn demo_segments(transcription_id: &str) -> Vec<TranscriptSegment> {
+    DEMO_TRANSCRIPT
+        .split_inclusive('。')
+        .enumerate()
+        .map(|(index, sentence)| {
+            let start_ms = index as i64 * DEMO_SEGMENT_MS;
+            let mut segment = TranscriptSegment::new(
+                transcription_id.to_string(),
+                index as i32,
+                start_ms,
+                start_ms + DEMO_SEGMENT_MS,
+                sentence.to_string(),
+            );
+            segment.confidence = Some(0.95);
+            segment
+        })
+        .collect()
+}
+
+/// デモ用のLLM設定（通信は発生しない）
+pub fn llm_config() -> LLMConfig {
+    LLMConfig {
+        provider: LLMProvider::Demo,
+        base_url: "http://localhost".to_string(),
+        model_name: DEMO_MODEL_NAME.to_string(),
+        ..LLMConfig::default()
+    }
+}
+
+/// プロンプトの種類に応じた決まった応答（`LLMProvider::Demo` の生成結果）
+pub fn llm_response(prompt: &str) -> String {
+    if prompt.contains("---書き起こしテキスト---") {
+        DEMO_SUMMARY_RESPONSE.to_string()
+    } else if prompt.contains("---会議一覧---") {
+        DEMO_DIGEST_RESPONSE.to_string()
+    } else if prompt.contains("人名") {
+        DEMO_PERSON_NAMES_RESPONSE.to_string()
+    } else {
+        DEMO_ROLLING_SUMMARY_RESPONSE.to_string()
+    }
+}
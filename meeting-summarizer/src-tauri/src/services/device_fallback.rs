@@ -0,0 +1,37 @@
+//! 録音中の入力デバイスの抜き差し
+//!
+//! USB マイクなどが録音中に外れるとストリームがエラーになるだけで、録音が止まったことに
+//! 気付けない。録音スレッドはデバイスが使えなくなったらストリームを閉じて通知し、一定間隔で
+//! 既定のデバイス（外れたあとに OS が選んだ内蔵マイクなど）を開き直して同じ録音を続ける。
+//! 開けるデバイスが無い間は、デバイスが戻るまで音声を取り込まずに待つ。
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+pub const CAPTURE_DEVICE_EVENT: &str = "capture-device-changed";
+
+/// デバイスが外れたあと、既定のデバイスを開き直す間隔
+pub const DEVICE_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 録音中の入力デバイスの変化の通知
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CaptureDeviceEvent {
+    /// 録音中のデバイスが使えなくなった（デバイスが戻るまで音声は取り込まれない）
+    Disconnected { device: String },
+    /// 既定のデバイスで録音を再開した
+    Reconnected { device: String },
+}
+
+impl CaptureDeviceEvent {
+    pub fn device(&self) -> &str {
+        match self {
+            Self::Disconnected { device } | Self::Reconnected { device } => device,
+        }
+    }
+}
+
+/// ストリームのエラーがデバイスの取り外しによるものか
+pub fn is_device_lost(error: &cpal::StreamError) -> bool {
+    matches!(error, cpal::StreamError::DeviceNotAvailable)
+}
@@ -0,0 +1,250 @@
+//! 複数の会議をまとめたダイジェスト（週報など）
+//!
+//! 各会議の最新の要約を材料に、LLM でテーマと決定事項をまとめる。会議が多く一度に渡せない場合は、
+//! 入力の上限に収まるまとまりごとに途中集約（map）してから、その結果をまとめる（reduce）。
+//! 未完了のアクションアイテムは LLM に任せず、対応状況の記録から取り出す。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Recording, RecordingQuery, SortBy, SortOrder, Summary};
+use crate::services::action_items::{self, ActionItemQuery};
+use crate::services::LLMService;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 1回の LLM 呼び出しに渡す会議要約の文字数の上限
+pub const MAX_DIGEST_INPUT_CHARS: usize = 12_000;
+
+/// ダイジェストの対象
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DigestScope {
+    Recordings { recording_ids: Vec<String> },
+    DateRange { from: DateTime<Utc>, to: DateTime<Utc> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestMeeting {
+    pub recording_id: String,
+    pub title: String,
+    pub meeting_date: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingDigest {
+    pub overview: String,
+    pub themes: Vec<String>,
+    pub decisions: Vec<String>,
+    pub open_action_items: Vec<String>,
+    /// ダイジェストに含めた会議（開催順）
+    pub meetings: Vec<DigestMeeting>,
+    /// 要約が無いため含めなかった録音
+    pub skipped_recording_ids: Vec<String>,
+    pub model_used: String,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// ダイジェストを Markdown にする（ファイル保存・メール本文用。空の項目は省く）
+pub fn render_markdown(digest: &MeetingDigest, title: &str) -> String {
+    let mut text = format!("# {}\n\n## 概要\n{}\n", title, digest.overview.trim());
+    for (heading, items) in [
+        ("テーマ", &digest.themes),
+        ("決定事項", &digest.decisions),
+        ("未完了のアクションアイテム", &digest.open_action_items),
+    ] {
+        if items.is_empty() {
+            continue;
+        }
+        text.push_str(&format!("\n## {}\n", heading));
+        for item in items {
+            text.push_str(&format!("- {}\n", item));
+        }
+    }
+    text.push_str("\n## 対象の会議\n");
+    for meeting in &digest.meetings {
+        text.push_str(&format!("- {} {}\n", meeting.meeting_date.format("%Y-%m-%d"), meeting.title));
+    }
+    text
+}
+
+/// 要約1件をダイジェストの材料となるテキストにする
+pub fn format_meeting(meeting: &DigestMeeting, summary: &Summary) -> String {
+    let mut text = format!("### {}（{}）\n{}\n", meeting.title, meeting.meeting_date.format("%Y-%m-%d"), summary.summary_text.trim());
+    for point in &summary.key_points {
+        text.push_str(&format!("- {}\n", point));
+    }
+    if !summary.action_items.is_empty() {
+        text.push_str("アクションアイテム:\n");
+        for item in &summary.action_items {
+            text.push_str(&format!("- {}\n", item));
+        }
+    }
+    text
+}
+
+/// 入力の上限に収まるまとまりに分ける（1件で上限を超える場合はそれだけで1つにする）
+pub fn batch_by_chars(texts: Vec<String>, max_chars: usize) -> Vec<Vec<String>> {
+    let mut batches: Vec<Vec<String>> = Vec::new();
+    let mut current_chars = 0;
+    for text in texts {
+        let chars = text.chars().count();
+        match batches.last_mut() {
+            Some(batch) if current_chars + chars <= max_chars => {
+                current_chars += chars;
+                batch.push(text);
+            }
+            _ => {
+                current_chars = chars;
+                batches.push(vec![text]);
+            }
+        }
+    }
+    batches
+}
+
+/// LLM の応答から概要・テーマ・決定事項を取り出す（見出しが無ければ全体を概要とする）
+pub fn parse_digest_response(response: &str) -> (String, Vec<String>, Vec<String>) {
+    let mut overview = Vec::new();
+    let mut themes = Vec::new();
+    let mut decisions = Vec::new();
+    let mut section = "";
+
+    for line in response.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        if let Some(heading) = line.strip_prefix("## ") {
+            section = match heading.trim() {
+                "概要" => "overview",
+                "テーマ" => "themes",
+                "決定事項" => "decisions",
+                _ => "",
+            };
+            continue;
+        }
+
+        let item = line.trim_start_matches("- ").trim_start_matches("・").trim();
+        match section {
+            "overview" => overview.push(line.to_string()),
+            "themes" if !item.starts_with('（') => themes.push(item.to_string()),
+            "decisions" if !item.starts_with('（') => decisions.push(item.to_string()),
+            _ => {}
+        }
+    }
+
+    if overview.is_empty() && themes.is_empty() && decisions.is_empty() {
+        return (response.trim().to_string(), themes, decisions);
+    }
+    (overview.join("\n"), themes, decisions)
+}
+
+/// 対象の録音（開催順）
+async fn recordings_in_scope(database: &Database, scope: &DigestScope) -> AppResult<Vec<Recording>> {
+    let mut recordings = match scope {
+        DigestScope::Recordings { recording_ids } => {
+            let mut recordings = Vec::with_capacity(recording_ids.len());
+            for id in recording_ids {
+                let recording = database.get_recording(id).await?.ok_or_else(|| AppError::ValidationError {
+                    message: format!("Recording with id {} not found", id),
+                })?;
+                recordings.push(recording);
+            }
+            recordings
+        }
+        DigestScope::DateRange { from, to } => {
+            if from > to {
+                return Err(AppError::ValidationError {
+                    message: "Digest start date must be before the end date".to_string(),
+                });
+            }
+            let query = RecordingQuery {
+                date_from: Some(*from),
+                date_to: Some(*to),
+                sort_by: SortBy::CreatedAt,
+                sort_order: SortOrder::Asc,
+                ..RecordingQuery::default()
+            };
+            database.search_recordings(&query).await?
+        }
+    };
+    recordings.sort_by_key(|recording| recording.created_at);
+    recordings.dedup_by(|a, b| a.id == b.id);
+    Ok(recordings)
+}
+
+/// 複数の会議をまとめたダイジェストを作る
+pub async fn generate_digest(database: &Database, llm: &LLMService, scope: &DigestScope) -> AppResult<MeetingDigest> {
+    let recordings = recordings_in_scope(database, scope).await?;
+
+    // 録音ごとの最新の要約（同じ録音に複数の書き起こしがあれば新しい方）
+    let mut latest: HashMap<String, Summary> = HashMap::new();
+    for (summary, recording_id, _) in database.get_latest_summaries_with_recordings().await? {
+        match latest.get(&recording_id) {
+            Some(existing) if existing.created_at >= summary.created_at => {}
+            _ => {
+                latest.insert(recording_id, summary);
+            }
+        }
+    }
+
+    let mut meetings = Vec::new();
+    let mut meeting_texts = Vec::new();
+    let mut skipped_recording_ids = Vec::new();
+    for recording in &recordings {
+        let Some(summary) = latest.get(&recording.id) else {
+            skipped_recording_ids.push(recording.id.clone());
+            continue;
+        };
+        let meeting = DigestMeeting {
+            recording_id: recording.id.clone(),
+            title: recording.title.clone().unwrap_or_else(|| recording.filename.clone()),
+            meeting_date: recording.created_at,
+        };
+        meeting_texts.push(format_meeting(&meeting, summary));
+        meetings.push(meeting);
+    }
+
+    if meetings.is_empty() {
+        return Err(AppError::ValidationError {
+            message: "No summarized meetings found for the digest".to_string(),
+        });
+    }
+
+    // 上限を超える場合は途中集約を繰り返して1回で渡せる量にする
+    let mut inputs = meeting_texts;
+    while inputs.len() > 1 && inputs.iter().map(|text| text.chars().count()).sum::<usize>() > MAX_DIGEST_INPUT_CHARS {
+        let count = inputs.len();
+        let batches = batch_by_chars(inputs, MAX_DIGEST_INPUT_CHARS);
+        let mut partials = Vec::with_capacity(batches.len());
+        for batch in batches {
+            partials.push(llm.summarize_digest(&batch.join("\n"), true).await?);
+        }
+        inputs = partials;
+        // 1件ずつでも上限を超えてまとめられない場合は、そのまま最終集約に渡す
+        if inputs.len() == count {
+            break;
+        }
+    }
+
+    let response = llm.summarize_digest(&inputs.join("\n"), false).await?;
+    let (overview, themes, decisions) = parse_digest_response(&response);
+
+    let recording_ids: Vec<&str> = meetings.iter().map(|meeting| meeting.recording_id.as_str()).collect();
+    let open_action_items = action_items::list_action_items(database, &ActionItemQuery::default())
+        .await?
+        .into_iter()
+        .filter(|item| recording_ids.contains(&item.recording_id.as_str()))
+        .map(|item| item.text)
+        .collect();
+
+    log::info!("📰 Digest generated from {} meeting(s) ({} skipped)", meetings.len(), skipped_recording_ids.len());
+
+    Ok(MeetingDigest {
+        overview,
+        themes,
+        decisions,
+        open_action_items,
+        meetings,
+        skipped_recording_ids,
+        model_used: llm.get_config().model_name.clone(),
+        generated_at: Utc::now(),
+    })
+}
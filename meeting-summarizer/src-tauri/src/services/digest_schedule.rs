@@ -0,0 +1,227 @@
+//! 定期ダイジェスト
+//!
+//! 設定した曜日（毎週）または日（毎月）の時刻になると、直前の1週間・1か月の会議からダイジェストを作り、
+//! ファイル・メール・Webhook のいずれかで届ける。届けた回の予定時刻を設定に残し、同じ回を二重に作らない。
+//! アプリを起動していなかった間の回は、次に起動したときに直近の1回だけ作る。
+
+use crate::errors::{AppError, AppResult};
+use crate::models::LLMConfig;
+use crate::services::credentials;
+use crate::services::digest::{self, DigestScope, MeetingDigest};
+use crate::services::{DigestDelivery, DigestFrequency, DigestScheduleSettings, Summarizer};
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate, TimeZone, Utc};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+
+/// 予定時刻になったかを確認する間隔（秒）
+pub const DIGEST_SCHEDULE_CHECK_INTERVAL_SECS: u64 = 60;
+
+/// 定期ダイジェストを作ったときに送るイベント
+pub const SCHEDULED_DIGEST_EVENT: &str = "scheduled-digest";
+
+const SMTP_PASSWORD_KEY: &str = "digest-smtp-password";
+
+/// 定期ダイジェスト1回分の結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledDigestRun {
+    pub period_from: DateTime<Utc>,
+    pub period_to: DateTime<Utc>,
+    pub meeting_count: usize,
+    /// 届け先（保存したファイルのパス・宛先・URL）
+    pub delivered_to: Option<String>,
+    pub error: Option<String>,
+}
+
+/// ローカル時刻をタイムゾーン付きにする（夏時間の切り替えで存在しない時刻は1時間後にずらす）
+fn local_time<Tz: TimeZone>(tz: &Tz, date: NaiveDate, hour: u32, minute: u32) -> Option<DateTime<Tz>> {
+    let naive = date.and_hms_opt(hour, minute, 0)?;
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| tz.from_local_datetime(&(naive + Duration::hours(1))).earliest())
+}
+
+/// `now` 以前で最も新しい予定時刻
+pub fn latest_slot<Tz: TimeZone>(frequency: &DigestFrequency, now: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+    let tz = now.timezone();
+    let today = now.date_naive();
+    match *frequency {
+        DigestFrequency::Weekly { weekday, hour, minute } => {
+            let days_back = (today.weekday().num_days_from_monday() + 7 - weekday % 7) % 7;
+            let date = today - Duration::days(days_back as i64);
+            let slot = local_time(&tz, date, hour, minute)?;
+            if slot <= *now {
+                Some(slot)
+            } else {
+                local_time(&tz, date - Duration::days(7), hour, minute)
+            }
+        }
+        DigestFrequency::Monthly { day, hour, minute } => {
+            let date = NaiveDate::from_ymd_opt(today.year(), today.month(), day)?;
+            let slot = local_time(&tz, date, hour, minute)?;
+            if slot <= *now {
+                Some(slot)
+            } else {
+                local_time(&tz, date.checked_sub_months(Months::new(1))?, hour, minute)
+            }
+        }
+    }
+}
+
+/// 予定時刻までの対象期間の開始（毎週なら7日前、毎月なら1か月前）
+pub fn period_start<Tz: TimeZone>(frequency: &DigestFrequency, slot: &DateTime<Tz>) -> DateTime<Tz> {
+    match frequency {
+        DigestFrequency::Weekly { .. } => slot.clone() - Duration::days(7),
+        DigestFrequency::Monthly { .. } => slot
+            .clone()
+            .checked_sub_months(Months::new(1))
+            .unwrap_or_else(|| slot.clone() - Duration::days(30)),
+    }
+}
+
+/// まだ作っていない回があれば、その対象期間を返す
+pub fn due_period<Tz: TimeZone>(
+    schedule: &DigestScheduleSettings,
+    now: &DateTime<Tz>,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    if !schedule.enabled || schedule.delivery.is_none() {
+        return None;
+    }
+    let slot = latest_slot(&schedule.frequency, now)?;
+    if schedule.last_run_at.is_some_and(|last_run| last_run >= slot) {
+        return None;
+    }
+    let from = period_start(&schedule.frequency, &slot);
+    Some((from.with_timezone(&Utc), slot.with_timezone(&Utc)))
+}
+
+/// ダイジェストの見出し（期間はローカルの日付で表示）
+pub fn digest_title(from: &DateTime<Utc>, to: &DateTime<Utc>) -> String {
+    format!(
+        "会議ダイジェスト {}〜{}",
+        from.with_timezone(&Local).format("%Y-%m-%d"),
+        to.with_timezone(&Local).format("%Y-%m-%d")
+    )
+}
+
+pub fn save_smtp_password(password: &str) -> AppResult<()> {
+    credentials::save_secret(SMTP_PASSWORD_KEY, password)
+}
+
+pub fn clear_smtp_password() -> AppResult<()> {
+    credentials::delete_secret(SMTP_PASSWORD_KEY)
+}
+
+/// 設定した届け先へダイジェストを送り、届け先を返す
+pub async fn deliver(
+    digest: &MeetingDigest,
+    title: &str,
+    period: (DateTime<Utc>, DateTime<Utc>),
+    delivery: &DigestDelivery,
+) -> AppResult<String> {
+    delivery.validate()?;
+    let markdown = digest::render_markdown(digest, title);
+
+    match delivery {
+        DigestDelivery::File { directory } => {
+            let directory = PathBuf::from(directory.trim());
+            tokio::fs::create_dir_all(&directory).await?;
+            let path = directory.join(format!("digest_{}.md", period.1.with_timezone(&Local).format("%Y%m%d")));
+            tokio::fs::write(&path, markdown).await?;
+            Ok(path.to_string_lossy().to_string())
+        }
+        DigestDelivery::Email { smtp_host, smtp_port, username, from, to } => {
+            let mut builder = Message::builder().from(parse_mailbox(from)?).subject(title);
+            for recipient in to {
+                builder = builder.to(parse_mailbox(recipient)?);
+            }
+            let message = builder.body(markdown).map_err(|e| AppError::Integration {
+                message: format!("Failed to build digest email: {}", e),
+            })?;
+
+            // 465 は接続時から TLS、それ以外は STARTTLS
+            let transport = if *smtp_port == 465 {
+                AsyncSmtpTransport::<Tokio1Executor>::relay(smtp_host.trim())
+            } else {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(smtp_host.trim())
+            }
+            .map_err(|e| AppError::Integration {
+                message: format!("Invalid SMTP server {}: {}", smtp_host, e),
+            })?
+            .port(*smtp_port);
+            let transport = match username.as_deref().filter(|name| !name.trim().is_empty()) {
+                Some(username) => {
+                    let password = credentials::load_secret(SMTP_PASSWORD_KEY)?.unwrap_or_default();
+                    transport.credentials(Credentials::new(username.trim().to_string(), password))
+                }
+                None => transport,
+            };
+
+            transport.build().send(message).await.map_err(|e| AppError::Integration {
+                message: format!("Failed to send digest email: {}", e),
+            })?;
+            Ok(to.join(", "))
+        }
+        DigestDelivery::Webhook { url } => {
+            let payload = json!({
+                "title": title,
+                "period_from": period.0,
+                "period_to": period.1,
+                "markdown": markdown,
+                "digest": digest,
+            });
+            let response = reqwest::Client::new().post(url.trim()).json(&payload).send().await?;
+            if !response.status().is_success() {
+                return Err(AppError::Integration {
+                    message: format!("Digest webhook returned {}", response.status()),
+                });
+            }
+            Ok(url.trim().to_string())
+        }
+    }
+}
+
+fn parse_mailbox(address: &str) -> AppResult<Mailbox> {
+    address.trim().parse().map_err(|e| AppError::ValidationError {
+        message: format!("Invalid email address {}: {}", address, e),
+    })
+}
+
+/// 期間のダイジェストを作って届ける（失敗しても結果に理由を入れて返す）
+pub async fn run_scheduled_digest(
+    summarizer: &Summarizer,
+    model_config: Option<LLMConfig>,
+    delivery: &DigestDelivery,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> ScheduledDigestRun {
+    let mut run = ScheduledDigestRun {
+        period_from: from,
+        period_to: to,
+        meeting_count: 0,
+        delivered_to: None,
+        error: None,
+    };
+
+    let result = async {
+        let digest = summarizer.generate_digest(model_config, &DigestScope::DateRange { from, to }).await?;
+        run.meeting_count = digest.meetings.len();
+        deliver(&digest, &digest_title(&from, &to), (from, to), delivery).await
+    }
+    .await;
+
+    match result {
+        Ok(destination) => {
+            log::info!("📬 Scheduled digest delivered to {}", destination);
+            run.delivered_to = Some(destination);
+        }
+        Err(e) => {
+            log::warn!("⚠️ Scheduled digest failed: {}", e);
+            run.error = Some(e.to_string());
+        }
+    }
+    run
+}
@@ -0,0 +1,100 @@
+//! 録音前と録音中のディスクの空き容量の確認
+//!
+//! 空き容量が設定した下限を下回っていれば録音を始めない。録音中は録音スレッドが一定間隔で
+//! 確認し、下限の2倍を下回ったら警告、下限を下回ったら録音を止めて、そこまでの音声を保存する
+//! （WAV の書き出し中に容量が尽きて録音全体を失わないようにする）。
+
+use crate::errors::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const LOW_DISK_SPACE_EVENT: &str = "low-disk-space";
+
+/// 録音中に確認する間隔
+pub const DISK_CHECK_INTERVAL_MS: u64 = 2_000;
+
+/// 下限の何倍を下回ったら警告するか
+const WARNING_FACTOR: u64 = 2;
+
+/// 空き容量の警告・録音の停止の通知
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LowDiskSpace {
+    pub free_bytes: u64,
+    pub min_free_bytes: u64,
+    /// 下限を下回って録音を停止した
+    pub stopped: bool,
+    /// 停止して保存した録音（停止時のみ）
+    pub recording_id: Option<String>,
+}
+
+/// 空き容量の確認結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiskSpaceStatus {
+    Ok,
+    Low,
+    Exhausted,
+}
+
+pub fn status(free_bytes: u64, min_free_bytes: u64) -> DiskSpaceStatus {
+    if min_free_bytes == 0 {
+        DiskSpaceStatus::Ok
+    } else if free_bytes < min_free_bytes {
+        DiskSpaceStatus::Exhausted
+    } else if free_bytes < min_free_bytes.saturating_mul(WARNING_FACTOR) {
+        DiskSpaceStatus::Low
+    } else {
+        DiskSpaceStatus::Ok
+    }
+}
+
+/// `path` があるディスクの空き容量（利用者が書き込める分）
+#[cfg(unix)]
+pub fn free_space(path: &Path) -> AppResult<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).map_err(|_| AppError::InvalidPath {
+        message: format!("Path contains NUL: {:?}", path),
+    })?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path は NUL 終端の文字列で、stat は書き込み可能な構造体
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    // ブロック数の型は OS によって異なる（macOS は u32）
+    #[allow(clippy::unnecessary_cast)]
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+/// `path` があるディスクの空き容量（利用者が書き込める分）
+#[cfg(windows)]
+pub fn free_space(path: &Path) -> AppResult<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+    let mut available = 0u64;
+    // SAFETY: wide は NUL 終端の UTF-16 文字列
+    if unsafe { GetDiskFreeSpaceExW(wide.as_ptr(), &mut available, std::ptr::null_mut(), std::ptr::null_mut()) } == 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(available)
+}
+
+/// 空き容量が下限以上あることを確認する（`min_free_bytes` が 0 なら確認しない）
+pub fn ensure_free_space(path: &Path, min_free_bytes: u64) -> AppResult<()> {
+    if min_free_bytes == 0 {
+        return Ok(());
+    }
+    let free_bytes = free_space(path)?;
+    if status(free_bytes, min_free_bytes) == DiskSpaceStatus::Exhausted {
+        return Err(AppError::Recording {
+            message: format!(
+                "Not enough free disk space to record: {} MB free, {} MB required",
+                free_bytes / (1024 * 1024),
+                min_free_bytes / (1024 * 1024)
+            ),
+        });
+    }
+    Ok(())
+}
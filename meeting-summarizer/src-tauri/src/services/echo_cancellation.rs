@@ -0,0 +1,191 @@
+//! 同時録音のエコーキャンセル
+//!
+//! スピーカーで会議の音声を流しながらマイクとシステム音声を同時録音すると、相手の声が
+//! システム音声とマイク（スピーカーから拾った音）の両方に入り、2回聞こえる。システム音声を
+//! 参照信号にして、マイクに回り込んだ分を適応フィルターで推定して差し引く。
+//!
+//! フィルターは分割したブロックごとに周波数領域で更新する NLMS（PBFDAF）。マイクと
+//! システム音声は別のスレッドで録音するため、スプールを WAV にまとめる時にかける。
+
+use realfft::num_complex::Complex;
+use realfft::{ComplexToReal, RealFftPlanner, RealToComplex};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// 推定するエコーの長さ（スピーカーからマイクまでの遅れと残響）
+const TAIL_MS: u32 = 300;
+
+/// 学習の速さ（分割数で割って使う）
+const STEP_SIZE: f32 = 1.0;
+
+/// 相手と同時に話している間も、学習を完全には止めない
+const MIN_ADAPTATION: f32 = 0.01;
+
+/// 参照信号の周波数ごとのパワーと、残ったエコーの推定をならす係数
+const POWER_SMOOTHING: f32 = 0.9;
+
+/// この大きさに満たない参照信号（何も再生されていない）では学習しない
+const SILENT_REFERENCE_POWER: f32 = 1e-8;
+
+/// サンプルレートに合ったブロックの長さ（約16ms 以上の2のべき乗）
+pub fn block_len(sample_rate: u32) -> usize {
+    ((sample_rate / 64).max(64) as usize).next_power_of_two()
+}
+
+/// マイクの音声から、参照信号（システム音声）が回り込んだ分を取り除く
+pub struct EchoCanceller {
+    block_len: usize,
+    forward: Arc<dyn RealToComplex<f32>>,
+    inverse: Arc<dyn ComplexToReal<f32>>,
+    /// 分割したフィルター（周波数領域）
+    weights: Vec<Vec<Complex<f32>>>,
+    /// 直近のブロックの参照信号のスペクトル（新しい順）
+    history: VecDeque<Vec<Complex<f32>>>,
+    /// 1つ前のブロックの参照信号
+    previous: Vec<f32>,
+    power: Vec<f32>,
+    /// 差し引いたあとの音声と推定したエコーの相関（ならした値）
+    leak_correlation: f32,
+    /// 推定したエコーのパワー（ならした値）
+    echo_power: f32,
+    blocks: usize,
+}
+
+impl EchoCanceller {
+    pub fn new(sample_rate: u32) -> Self {
+        let block_len = block_len(sample_rate);
+        let partitions = ((sample_rate as usize * TAIL_MS as usize / 1000).div_ceil(block_len)).max(1);
+        let mut planner = RealFftPlanner::<f32>::new();
+        let bins = block_len + 1;
+        Self {
+            block_len,
+            forward: planner.plan_fft_forward(block_len * 2),
+            inverse: planner.plan_fft_inverse(block_len * 2),
+            weights: vec![vec![Complex::new(0.0, 0.0); bins]; partitions],
+            history: VecDeque::with_capacity(partitions),
+            previous: vec![0.0; block_len],
+            power: vec![0.0; bins],
+            leak_correlation: 0.0,
+            echo_power: 0.0,
+            blocks: 0,
+        }
+    }
+
+    /// 1ブロック分を処理する（`mic` と `reference` はどちらも `block_len` の長さ）
+    fn process_block(&mut self, mic: &[f32], reference: &[f32]) -> Vec<f32> {
+        let n = self.block_len;
+        let partitions = self.weights.len();
+
+        let mut input: Vec<f32> = self.previous.iter().chain(reference).copied().collect();
+        self.previous.copy_from_slice(reference);
+        let mut spectrum = self.forward.make_output_vec();
+        if self.forward.process(&mut input, &mut spectrum).is_err() {
+            return mic.to_vec();
+        }
+        for (power, value) in self.power.iter_mut().zip(&spectrum) {
+            *power = POWER_SMOOTHING * *power + (1.0 - POWER_SMOOTHING) * value.norm_sqr();
+        }
+        if self.history.len() == partitions {
+            self.history.pop_back();
+        }
+        self.history.push_front(spectrum);
+
+        // エコーを推定して差し引く
+        let mut echo_spectrum = vec![Complex::new(0.0, 0.0); n + 1];
+        for (weights, past) in self.weights.iter().zip(&self.history) {
+            for ((echo, weight), value) in echo_spectrum.iter_mut().zip(weights).zip(past) {
+                *echo += weight * value;
+            }
+        }
+        let echo = self.to_time(&mut echo_spectrum);
+        let error: Vec<f32> = mic.iter().zip(&echo[n..]).map(|(mic, echo)| mic - echo).collect();
+
+        let reference_power = reference.iter().map(|sample| sample * sample).sum::<f32>() / n as f32;
+        if reference_power > SILENT_REFERENCE_POWER {
+            self.adapt(&echo[n..], &error);
+        }
+        self.blocks += 1;
+        error
+    }
+
+    fn adapt(&mut self, echo: &[f32], error: &[f32]) {
+        let n = self.block_len;
+        let partitions = self.weights.len();
+
+        // 差し引いたあとに残ったエコーを、推定したエコーとの相関から見積もる。残りの大半がエコーなら
+        // 速く学習し、こちらの声（相手と同時に話している間）や収束後の誤差が大半なら学習を遅くする
+        let correlation: f32 = echo.iter().zip(error).map(|(echo, error)| echo * error).sum();
+        let echo_power: f32 = echo.iter().map(|sample| sample * sample).sum();
+        let error_power: f32 = error.iter().map(|sample| sample * sample).sum();
+        self.leak_correlation = POWER_SMOOTHING * self.leak_correlation + (1.0 - POWER_SMOOTHING) * correlation;
+        self.echo_power = POWER_SMOOTHING * self.echo_power + (1.0 - POWER_SMOOTHING) * echo_power;
+        let adaptation = if self.echo_power <= f32::EPSILON || error_power <= 0.0 {
+            // まだ何も推定していない
+            1.0
+        } else {
+            let leak = self.leak_correlation.abs() / self.echo_power;
+            (leak * leak * echo_power / error_power).clamp(MIN_ADAPTATION, 1.0)
+        };
+        let step = STEP_SIZE * adaptation / partitions as f32;
+
+        let mut padded: Vec<f32> = std::iter::repeat_n(0.0, n).chain(error.iter().copied()).collect();
+        let mut error_spectrum = self.forward.make_output_vec();
+        if self.forward.process(&mut padded, &mut error_spectrum).is_err() {
+            return;
+        }
+        let floor = self.power.iter().sum::<f32>() / self.power.len() as f32 * 1e-3 + f32::EPSILON;
+        for (weights, past) in self.weights.iter_mut().zip(&self.history) {
+            for (((weight, value), error), power) in weights.iter_mut().zip(past).zip(&error_spectrum).zip(&self.power) {
+                *weight += value.conj() * error * (step / (power + floor));
+            }
+        }
+
+        // フィルターの後ろ半分（巡回畳み込みになる部分）を、ブロックごとに1つずつ消す
+        let index = self.blocks % partitions;
+        let mut weights = std::mem::take(&mut self.weights[index]);
+        let mut taps = self.to_time(&mut weights);
+        taps[n..].iter_mut().for_each(|tap| *tap = 0.0);
+        let mut constrained = self.forward.make_output_vec();
+        if self.forward.process(&mut taps, &mut constrained).is_err() {
+            constrained = vec![Complex::new(0.0, 0.0); n + 1];
+        }
+        self.weights[index] = constrained;
+    }
+
+    /// スペクトルを時間領域に戻す（2ブロック分）
+    fn to_time(&self, spectrum: &mut [Complex<f32>]) -> Vec<f32> {
+        // 直流とナイキスト周波数の虚部は 0 でなければならない
+        if let Some(first) = spectrum.first_mut() {
+            first.im = 0.0;
+        }
+        if let Some(last) = spectrum.last_mut() {
+            last.im = 0.0;
+        }
+        let mut output = self.inverse.make_output_vec();
+        if self.inverse.process(spectrum, &mut output).is_err() {
+            return vec![0.0; self.block_len * 2];
+        }
+        let scale = 1.0 / output.len() as f32;
+        output.iter_mut().for_each(|sample| *sample *= scale);
+        output
+    }
+}
+
+/// マイクの音声 `mic` から、同時に録音したシステム音声 `reference` の回り込みを取り除く
+///
+/// どちらも同じサンプルレートのモノラル。戻り値は `mic` と同じ長さ。
+pub fn cancel_echo(mic: &[f32], reference: &[f32], sample_rate: u32) -> Vec<f32> {
+    let mut canceller = EchoCanceller::new(sample_rate);
+    let n = canceller.block_len;
+    let mut output = Vec::with_capacity(mic.len() + n);
+    let block = |samples: &[f32], start: usize| -> Vec<f32> {
+        let mut block: Vec<f32> = samples.iter().skip(start).take(n).copied().collect();
+        block.resize(n, 0.0);
+        block
+    };
+    for start in (0..mic.len()).step_by(n) {
+        output.extend(canceller.process_block(&block(mic, start), &block(reference, start)));
+    }
+    output.truncate(mic.len());
+    output
+}
@@ -0,0 +1,239 @@
+//! 書き起こし環境の診断
+//!
+//! Python・pip・openai-whisper・ffmpeg・ダウンロード済みモデル・キャッシュ/録音ディレクトリの書き込み権限を
+//! 順に確認し、問題があれば具体的な対処方法を添えて返す。問い合わせの多くは Python 環境の不備が原因のため、
+//! ユーザー自身で原因を切り分けられるようにする。
+
+use crate::services::WhisperService;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tokio::process::Command as TokioCommand;
+
+/// openai-whisper が対応する最低の Python バージョン
+pub const MIN_PYTHON_VERSION: (u32, u32) = (3, 8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// 問題がある場合の対処方法
+    pub suggestion: Option<String>,
+}
+
+impl EnvironmentCheck {
+    fn ok(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            suggestion: None,
+        }
+    }
+
+    fn problem(name: &str, status: CheckStatus, detail: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+            suggestion: Some(suggestion.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentReport {
+    /// エラーが1つも無ければ true（警告は含めない）
+    pub healthy: bool,
+    pub checks: Vec<EnvironmentCheck>,
+}
+
+impl EnvironmentReport {
+    pub fn new(checks: Vec<EnvironmentCheck>) -> Self {
+        Self {
+            healthy: checks.iter().all(|check| check.status != CheckStatus::Error),
+            checks,
+        }
+    }
+}
+
+/// "Python 3.11.4" → (3, 11, 4)
+pub fn parse_python_version(output: &str) -> Option<(u32, u32, u32)> {
+    let version = output.trim().strip_prefix("Python ")?;
+    let mut parts = version.split(|c: char| !c.is_ascii_digit());
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next().and_then(|patch| patch.parse().ok()).unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+pub fn check_python_version(python: &str, output: Option<&str>) -> EnvironmentCheck {
+    let Some(output) = output else {
+        return EnvironmentCheck::problem(
+            "python",
+            CheckStatus::Error,
+            format!("`{} --version` could not be run", python),
+            "Install Python 3.8 or later (https://www.python.org/downloads/) and make sure `python3` is on PATH",
+        );
+    };
+    match parse_python_version(output) {
+        Some((major, minor, patch)) if (major, minor) >= MIN_PYTHON_VERSION => {
+            EnvironmentCheck::ok("python", format!("Python {}.{}.{} ({})", major, minor, patch, python))
+        }
+        Some((major, minor, patch)) => EnvironmentCheck::problem(
+            "python",
+            CheckStatus::Error,
+            format!("Python {}.{}.{} is too old ({})", major, minor, patch, python),
+            format!(
+                "openai-whisper needs Python {}.{} or later; install a newer Python and restart the app",
+                MIN_PYTHON_VERSION.0, MIN_PYTHON_VERSION.1
+            ),
+        ),
+        None => EnvironmentCheck::problem(
+            "python",
+            CheckStatus::Warning,
+            format!("Unrecognized version output: {}", output.trim()),
+            "Check that the configured Python is a standard CPython installation",
+        ),
+    }
+}
+
+/// ダウンロード済みのモデル（キャッシュディレクトリの *.pt）と、使用中のモデルの有無
+pub fn check_models(cache_dir: &Path, model_size: &str) -> EnvironmentCheck {
+    let mut models: Vec<String> = std::fs::read_dir(cache_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "pt"))
+                .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    models.sort();
+
+    if models.iter().any(|model| model == model_size) {
+        EnvironmentCheck::ok("models", format!("Downloaded: {}", models.join(", ")))
+    } else {
+        let detail = if models.is_empty() {
+            format!("No models downloaded in {:?}", cache_dir)
+        } else {
+            format!("Model '{}' is not downloaded (available: {})", model_size, models.join(", "))
+        };
+        EnvironmentCheck::problem(
+            "models",
+            CheckStatus::Warning,
+            detail,
+            format!(
+                "Download the '{}' model from the model settings, or it will be downloaded on first transcription",
+                model_size
+            ),
+        )
+    }
+}
+
+/// ディレクトリを作成して一時ファイルを書き込めるか確認する
+pub fn check_writable(name: &str, dir: &Path) -> EnvironmentCheck {
+    let probe = dir.join(".write-test");
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&probe, b"ok"))
+        .and_then(|_| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => EnvironmentCheck::ok(name, format!("{:?} is writable", dir)),
+        Err(e) => EnvironmentCheck::problem(
+            name,
+            CheckStatus::Error,
+            format!("Cannot write to {:?}: {}", dir, e),
+            format!("Fix the permissions of {:?} (or its parent) so the current user can write to it", dir),
+        ),
+    }
+}
+
+/// コマンドを実行し、成功した場合の出力（stdout が空なら stderr）を返す
+async fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = TokioCommand::new(program).args(args).output().await.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if stdout.is_empty() {
+        Some(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    } else {
+        Some(stdout)
+    }
+}
+
+/// 書き起こし環境を一通り確認する
+pub async fn check_transcription_environment(whisper_service: &WhisperService) -> EnvironmentReport {
+    let python = whisper_service.python_command();
+    let mut checks = Vec::new();
+
+    let python_version = run(&python, &["--version"]).await;
+    let python_ok = python_version.is_some();
+    checks.push(check_python_version(&python, python_version.as_deref()));
+
+    if python_ok {
+        checks.push(match run(&python, &["-m", "pip", "--version"]).await {
+            Some(version) => EnvironmentCheck::ok("pip", version),
+            None => EnvironmentCheck::problem(
+                "pip",
+                CheckStatus::Error,
+                format!("pip is not available for {}", python),
+                format!("Run `{} -m ensurepip --upgrade`", python),
+            ),
+        });
+
+        checks.push(
+            match run(&python, &["-c", "import whisper; print(getattr(whisper, '__version__', 'unknown'))"]).await {
+                Some(version) => EnvironmentCheck::ok("openai-whisper", format!("openai-whisper {}", version)),
+                None => EnvironmentCheck::problem(
+                    "openai-whisper",
+                    CheckStatus::Error,
+                    "openai-whisper cannot be imported",
+                    format!("Run `{} -m pip install -U openai-whisper`", python),
+                ),
+            },
+        );
+    }
+
+    // 音声は Rust 側で WAV に変換するため ffmpeg は必須ではない
+    checks.push(match run("ffmpeg", &["-version"]).await {
+        Some(version) => EnvironmentCheck::ok("ffmpeg", version.lines().next().unwrap_or_default().to_string()),
+        None => EnvironmentCheck::problem(
+            "ffmpeg",
+            CheckStatus::Warning,
+            "ffmpeg was not found on PATH",
+            "Optional: install ffmpeg (e.g. `brew install ffmpeg` or `sudo apt install ffmpeg`) for formats the built-in converter cannot decode",
+        ),
+    });
+
+    let cache_dir = whisper_service.whisper_cache_dir();
+    checks.push(check_models(&cache_dir, &whisper_service.get_current_model_size()));
+    checks.push(check_writable("model_cache", &cache_dir));
+    checks.push(check_writable("recordings_dir", &whisper_service.recordings_dir().join("converted")));
+
+    let report = EnvironmentReport::new(checks);
+    if report.healthy {
+        log::info!("🩺 Transcription environment check passed");
+    } else {
+        log::warn!(
+            "🩺 Transcription environment has problems: {}",
+            report
+                .checks
+                .iter()
+                .filter(|check| check.status == CheckStatus::Error)
+                .map(|check| check.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    report
+}
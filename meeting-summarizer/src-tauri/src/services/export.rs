@@ -0,0 +1,167 @@
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Chapter, Recording, Transcription, TranscriptSegment};
+use crate::services::{chapters, html_export, ical};
+
+/// 録音データを指定形式（json / text / markdown / html / vtt / ics）の文字列として書き出す
+///
+/// `vtt` はチャプタートラック（WebVTT chapters）を出力する。`html` は音声を含まない
+/// （音声の埋め込みは `html_export::export_recording_html` を使う）。
+pub async fn export_recording(database: &Database, recording_id: &str, format: &str) -> AppResult<String> {
+    let recording = database
+        .get_recording(recording_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording with id {} not found", recording_id),
+        })?;
+
+    let transcriptions = database.get_transcriptions_by_recording(recording_id).await?;
+    let recording_chapters = database.get_chapters_by_recording(recording_id).await?;
+
+    match format {
+        "json" => {
+            let export_data = serde_json::json!({
+                "recording": recording,
+                "transcriptions": transcriptions,
+                "chapters": recording_chapters,
+                "exported_at": chrono::Utc::now().to_rfc3339(),
+            });
+            Ok(serde_json::to_string_pretty(&export_data)?)
+        }
+        "text" => Ok(render_text(&recording, &transcriptions)),
+        "markdown" | "md" => {
+            let mut segments = Vec::with_capacity(transcriptions.len());
+            for transcription in &transcriptions {
+                segments.push(database.get_segments_by_transcription(&transcription.id).await?);
+            }
+            Ok(render_markdown(&recording, &transcriptions, &segments, &recording_chapters))
+        }
+        "html" => html_export::export_recording_html(database, recording_id, false).await,
+        "vtt" => Ok(chapters::render_vtt_chapters(
+            &recording_chapters,
+            recording.duration.map(|seconds| seconds * 1000),
+        )),
+        "ics" => {
+            let mut summaries = Vec::new();
+            for transcription in &transcriptions {
+                summaries.extend(database.get_summaries_by_transcription(&transcription.id).await?);
+            }
+            let title = recording.title.as_deref().unwrap_or(&recording.filename);
+            Ok(ical::render_action_items(&summaries, Some(title)))
+        }
+        _ => Err(AppError::ValidationError {
+            message: format!("Unsupported export format: {}", format),
+        }),
+    }
+}
+
+/// 単一要約のアクションアイテムを iCalendar 形式で書き出す
+pub async fn export_summary_ical(database: &Database, summary_id: &str) -> AppResult<String> {
+    let summary = database
+        .get_summary(summary_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Summary with id {} not found", summary_id),
+        })?;
+
+    Ok(ical::render_action_items(std::slice::from_ref(&summary), None))
+}
+
+fn render_markdown(
+    recording: &Recording,
+    transcriptions: &[Transcription],
+    segments: &[Vec<TranscriptSegment>],
+    recording_chapters: &[Chapter],
+) -> String {
+    let mut result = format!(
+        "# {}\n\n",
+        recording.title.as_deref().unwrap_or(&recording.filename)
+    );
+    result.push_str(&format!("- Created: {}\n", recording.created_at.format("%Y-%m-%d %H:%M:%S")));
+    if let Some(category) = &recording.category {
+        result.push_str(&format!("- Category: {}\n", category));
+    }
+    if !recording.tags.is_empty() {
+        result.push_str(&format!("- Tags: {}\n", recording.tags.join(", ")));
+    }
+    if let Some(duration) = recording.duration {
+        result.push_str(&format!("- Duration: {}\n", chapters::format_timestamp(duration * 1000)));
+    }
+    if let Some(description) = &recording.description {
+        result.push_str(&format!("\n{}\n", description));
+    }
+
+    if !recording_chapters.is_empty() {
+        result.push('\n');
+        result.push_str(&chapters::render_markdown_toc(recording_chapters));
+    }
+
+    for (index, transcription) in transcriptions.iter().enumerate() {
+        result.push_str(&format!("\n## Transcription ({})\n\n", transcription.language));
+
+        let transcript_segments = segments.get(index).map(Vec::as_slice).unwrap_or_default();
+        if recording_chapters.is_empty() || transcript_segments.is_empty() {
+            result.push_str(&transcription.text);
+            result.push('\n');
+            continue;
+        }
+
+        // セグメントを開始時刻で各チャプターに振り分ける
+        let mut current_chapter = None;
+        for segment in transcript_segments {
+            let chapter_index = recording_chapters
+                .iter()
+                .rposition(|chapter| chapter.start_ms <= segment.start_ms);
+            if chapter_index != current_chapter {
+                if let Some(chapter) = chapter_index.map(|i| &recording_chapters[i]) {
+                    result.push_str(&format!(
+                        "\n### [{}] {}\n\n",
+                        chapters::format_timestamp(chapter.start_ms),
+                        chapter.title.trim()
+                    ));
+                }
+                current_chapter = chapter_index;
+            }
+            match &segment.speaker {
+                Some(speaker) => result.push_str(&format!("**{}**: {}\n\n", speaker, segment.text.trim())),
+                None => result.push_str(&format!("{}\n\n", segment.text.trim())),
+            }
+        }
+    }
+
+    result
+}
+
+fn render_text(recording: &Recording, transcriptions: &[Transcription]) -> String {
+    let mut result = String::new();
+    result.push_str(&format!("=== Recording: {} ===\n", recording.filename));
+    result.push_str(&format!("Created: {}\n", recording.created_at.format("%Y-%m-%d %H:%M:%S")));
+
+    if let Some(title) = &recording.title {
+        result.push_str(&format!("Title: {}\n", title));
+    }
+    if let Some(description) = &recording.description {
+        result.push_str(&format!("Description: {}\n", description));
+    }
+    if let Some(category) = &recording.category {
+        result.push_str(&format!("Category: {}\n", category));
+    }
+    if !recording.tags.is_empty() {
+        result.push_str(&format!("Tags: {}\n", recording.tags.join(", ")));
+    }
+    if let Some(duration) = recording.duration {
+        result.push_str(&format!("Duration: {}s\n", duration));
+    }
+
+    result.push_str("\n=== Transcriptions ===\n");
+    for transcription in transcriptions {
+        result.push_str(&format!("\n--- {} (Confidence: {:.2}) ---\n",
+            transcription.language,
+            transcription.confidence.unwrap_or(0.0)
+        ));
+        result.push_str(&transcription.text);
+        result.push('\n');
+    }
+
+    result
+}
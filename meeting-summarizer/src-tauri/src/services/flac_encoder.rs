@@ -0,0 +1,280 @@
+//! 16bit PCM 用の最小限の FLAC エンコーダー
+//!
+//! 固定予測（0〜4次）と Rice 符号だけを使う可逆圧縮。音声の WAV をおおむね半分程度に縮められ、
+//! 外部ライブラリ無しで書き出せる。デコードは symphonia の FLAC デコーダーで行う。
+
+use crate::errors::{AppError, AppResult};
+use std::io::{Seek, SeekFrom, Write};
+
+/// 1フレームあたりのサンプル数（チャンネルあたり）
+pub const FLAC_BLOCK_SIZE: usize = 4096;
+pub const FLAC_BITS_PER_SAMPLE: u32 = 16;
+/// 固定予測の最大次数
+const MAX_FIXED_ORDER: usize = 4;
+/// Rice パラメーターの最大値（4bit、15 はエスケープ用）
+const MAX_RICE_PARAMETER: u32 = 14;
+
+pub struct FlacEncoder<W: Write + Seek> {
+    writer: W,
+    sample_rate: u32,
+    channels: usize,
+    /// チャンネルごとのバッファ
+    pending: Vec<Vec<i32>>,
+    frame_number: u64,
+    total_samples: u64,
+}
+
+impl<W: Write + Seek> FlacEncoder<W> {
+    pub fn new(mut writer: W, sample_rate: u32, channels: u16) -> AppResult<Self> {
+        if !(1..=8).contains(&channels) || sample_rate == 0 || sample_rate >= 1 << 20 {
+            return Err(AppError::AudioConversion {
+                message: format!("Unsupported FLAC stream: {} Hz, {} channel(s)", sample_rate, channels),
+            });
+        }
+
+        writer.write_all(b"fLaC")?;
+        // STREAMINFO（総サンプル数は finish で書き直す）
+        writer.write_all(&streaminfo(sample_rate, channels as usize, 0))?;
+
+        Ok(Self {
+            writer,
+            sample_rate,
+            channels: channels as usize,
+            pending: vec![Vec::with_capacity(FLAC_BLOCK_SIZE); channels as usize],
+            frame_number: 0,
+            total_samples: 0,
+        })
+    }
+
+    /// チャンネルが交互に並んだ 16bit サンプルを追加する
+    pub fn write_interleaved(&mut self, samples: &[i16]) -> AppResult<()> {
+        for frame in samples.chunks(self.channels) {
+            for (channel, sample) in frame.iter().enumerate() {
+                self.pending[channel].push(*sample as i32);
+            }
+            if self.pending[0].len() == FLAC_BLOCK_SIZE {
+                self.flush_block()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 残りを書き出して STREAMINFO を確定させ、総サンプル数（チャンネルあたり）を返す
+    pub fn finish(mut self) -> AppResult<u64> {
+        if !self.pending[0].is_empty() {
+            self.flush_block()?;
+        }
+        self.writer.seek(SeekFrom::Start(4))?;
+        self.writer
+            .write_all(&streaminfo(self.sample_rate, self.channels, self.total_samples))?;
+        self.writer.seek(SeekFrom::End(0))?;
+        self.writer.flush()?;
+        Ok(self.total_samples)
+    }
+
+    fn flush_block(&mut self) -> AppResult<()> {
+        let block_size = self.pending[0].len();
+        let mut bits = BitWriter::default();
+
+        // フレームヘッダー
+        bits.write(0b1111_1111_1111_1000, 16); // 同期コード + 固定ブロックサイズ
+        bits.write(0b0111, 4); // ブロックサイズはヘッダー末尾に16bitで格納
+        bits.write(0b0000, 4); // サンプルレートは STREAMINFO を参照
+        bits.write(self.channels as u64 - 1, 4); // 各チャンネル独立
+        bits.write(0b100, 3); // 16bit
+        bits.write(0, 1);
+        write_utf8_number(&mut bits, self.frame_number);
+        bits.write(block_size as u64 - 1, 16);
+        let crc = crc8(bits.bytes());
+        bits.write(crc as u64, 8);
+
+        for channel in &self.pending {
+            write_subframe(&mut bits, channel);
+        }
+        bits.align();
+        let crc = crc16(bits.bytes());
+        bits.write(crc as u64, 16);
+
+        self.writer.write_all(bits.bytes())?;
+        self.total_samples += block_size as u64;
+        self.frame_number += 1;
+        for channel in &mut self.pending {
+            channel.clear();
+        }
+        Ok(())
+    }
+}
+
+fn streaminfo(sample_rate: u32, channels: usize, total_samples: u64) -> Vec<u8> {
+    let mut bits = BitWriter::default();
+    // 最後のメタデータブロック、種類 0（STREAMINFO）、長さ 34
+    bits.write(1, 1);
+    bits.write(0, 7);
+    bits.write(34, 24);
+    bits.write(FLAC_BLOCK_SIZE as u64, 16);
+    bits.write(FLAC_BLOCK_SIZE as u64, 16);
+    bits.write(0, 24); // 最小フレームサイズ（不明）
+    bits.write(0, 24); // 最大フレームサイズ（不明）
+    bits.write(sample_rate as u64, 20);
+    bits.write(channels as u64 - 1, 3);
+    bits.write(FLAC_BITS_PER_SAMPLE as u64 - 1, 5);
+    bits.write(total_samples, 36);
+    // MD5（0 は未計算）
+    for _ in 0..16 {
+        bits.write(0, 8);
+    }
+    bits.into_bytes()
+}
+
+fn write_subframe(bits: &mut BitWriter, samples: &[i32]) {
+    if samples.iter().all(|sample| *sample == samples[0]) {
+        bits.write(0b0000_0000, 8); // CONSTANT
+        bits.write_signed(samples[0] as i64, FLAC_BITS_PER_SAMPLE);
+        return;
+    }
+
+    // 残差の絶対値の合計が最小になる次数を選ぶ
+    let max_order = MAX_FIXED_ORDER.min(samples.len().saturating_sub(1));
+    let (order, residuals) = (0..=max_order)
+        .map(|order| (order, fixed_residuals(samples, order)))
+        .min_by_key(|(_, residuals)| residuals.iter().map(|r| r.unsigned_abs()).sum::<u64>())
+        .unwrap_or_else(|| (0, samples.iter().map(|s| *s as i64).collect()));
+
+    bits.write(0b0001_0000 | ((order as u64) << 1), 8); // FIXED（wasted bits なし）
+    for sample in &samples[..order] {
+        bits.write_signed(*sample as i64, FLAC_BITS_PER_SAMPLE);
+    }
+
+    let parameter = rice_parameter(&residuals);
+    bits.write(0b00, 2); // 4bit の Rice パラメーター
+    bits.write(0, 4); // パーティション次数 0
+    bits.write(parameter as u64, 4);
+    for residual in residuals {
+        let folded = ((residual << 1) ^ (residual >> 63)) as u64;
+        bits.write_unary(folded >> parameter);
+        bits.write(folded & ((1 << parameter) - 1), parameter);
+    }
+}
+
+/// 固定予測の残差（先頭 `order` サンプルはウォームアップとしてそのまま書くため含めない）
+fn fixed_residuals(samples: &[i32], order: usize) -> Vec<i64> {
+    let s = |i: usize| samples[i] as i64;
+    (order..samples.len())
+        .map(|i| match order {
+            0 => s(i),
+            1 => s(i) - s(i - 1),
+            2 => s(i) - 2 * s(i - 1) + s(i - 2),
+            3 => s(i) - 3 * s(i - 1) + 3 * s(i - 2) - s(i - 3),
+            _ => s(i) - 4 * s(i - 1) + 6 * s(i - 2) - 4 * s(i - 3) + s(i - 4),
+        })
+        .collect()
+}
+
+fn rice_parameter(residuals: &[i64]) -> u32 {
+    if residuals.is_empty() {
+        return 0;
+    }
+    let mean = residuals.iter().map(|r| r.unsigned_abs()).sum::<u64>() / residuals.len() as u64;
+    let mut parameter = 0;
+    while parameter < MAX_RICE_PARAMETER && (1u64 << (parameter + 1)) <= mean.max(1) {
+        parameter += 1;
+    }
+    parameter
+}
+
+/// フレーム番号の可変長表現（UTF-8 と同じ形式）
+fn write_utf8_number(bits: &mut BitWriter, value: u64) {
+    if value < 0x80 {
+        bits.write(value, 8);
+        return;
+    }
+    let mut continuation = Vec::new();
+    let mut rest = value;
+    let mut first_bits = 6;
+    loop {
+        continuation.push(0x80 | (rest & 0x3F));
+        rest >>= 6;
+        first_bits -= 1;
+        if rest < (1 << first_bits) {
+            break;
+        }
+    }
+    let length = continuation.len() + 1;
+    let prefix = (0xFF00u64 >> length) & 0xFF;
+    bits.write(prefix | rest, 8);
+    for byte in continuation.into_iter().rev() {
+        bits.write(byte, 8);
+    }
+}
+
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |mut crc, byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        }
+        crc
+    })
+}
+
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |mut crc, byte| {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x8005 } else { crc << 1 };
+        }
+        crc
+    })
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    current: u8,
+    filled: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u64, count: u32) {
+        for shift in (0..count).rev() {
+            self.push_bit((value >> shift) & 1 == 1);
+        }
+    }
+
+    fn write_signed(&mut self, value: i64, count: u32) {
+        self.write(value as u64 & ((1u64 << count) - 1), count);
+    }
+
+    fn write_unary(&mut self, zeros: u64) {
+        for _ in 0..zeros {
+            self.push_bit(false);
+        }
+        self.push_bit(true);
+    }
+
+    fn push_bit(&mut self, bit: bool) {
+        self.current = (self.current << 1) | bit as u8;
+        self.filled += 1;
+        if self.filled == 8 {
+            self.bytes.push(self.current);
+            self.current = 0;
+            self.filled = 0;
+        }
+    }
+
+    fn align(&mut self) {
+        while self.filled != 0 {
+            self.push_bit(false);
+        }
+    }
+
+    /// バイト境界まで書き込んだ部分
+    fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn into_bytes(mut self) -> Vec<u8> {
+        self.align();
+        self.bytes
+    }
+}
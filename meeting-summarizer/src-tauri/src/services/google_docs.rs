@@ -0,0 +1,288 @@
+//! Google Docs への議事録エクスポート
+//!
+//! OAuth 2.0 デバイスフローで認可し、取得したトークンはキーチェーンに保存する。
+
+use crate::errors::{AppError, AppResult};
+use crate::models::Summary;
+use crate::services::credentials;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+const DEVICE_CODE_URL: &str = "https://oauth2.googleapis.com/device/code";
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const DOCS_API_URL: &str = "https://docs.googleapis.com/v1/documents";
+const SCOPES: &str = "https://www.googleapis.com/auth/documents https://www.googleapis.com/auth/drive.file";
+const TOKEN_KEY: &str = "google-docs-token";
+const DEVICE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// デバイスフロー開始時にユーザーへ提示する情報
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_url: String,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredToken {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+    expires_in: Option<i64>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoogleDocument {
+    pub document_id: String,
+    pub url: String,
+}
+
+pub struct GoogleDocsService {
+    client: Client,
+    client_id: String,
+    client_secret: Option<String>,
+}
+
+impl GoogleDocsService {
+    pub fn new(client_id: String, client_secret: Option<String>) -> Self {
+        Self {
+            client: Client::new(),
+            client_id,
+            client_secret,
+        }
+    }
+
+    /// 保存済みトークンがあるか
+    pub fn is_authorized() -> AppResult<bool> {
+        Ok(credentials::load_secret(TOKEN_KEY)?.is_some())
+    }
+
+    /// 保存済みトークンを削除
+    pub fn disconnect() -> AppResult<()> {
+        credentials::delete_secret(TOKEN_KEY)
+    }
+
+    /// デバイスフローを開始
+    pub async fn start_device_authorization(&self) -> AppResult<DeviceAuthorization> {
+        let response = self
+            .client
+            .post(DEVICE_CODE_URL)
+            .form(&[("client_id", self.client_id.as_str()), ("scope", SCOPES)])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Integration {
+                message: format!("Google device authorization failed: {}", response.text().await.unwrap_or_default()),
+            });
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// ユーザーが認可するまでトークンエンドポイントをポーリングし、取得したトークンを保存
+    pub async fn complete_device_authorization(&self, authorization: &DeviceAuthorization) -> AppResult<()> {
+        let mut interval = authorization.interval.max(1);
+        let deadline = Utc::now() + Duration::seconds(authorization.expires_in as i64);
+
+        while Utc::now() < deadline {
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+
+            let mut form = vec![
+                ("client_id", self.client_id.as_str()),
+                ("device_code", authorization.device_code.as_str()),
+                ("grant_type", DEVICE_GRANT_TYPE),
+            ];
+            if let Some(secret) = &self.client_secret {
+                form.push(("client_secret", secret.as_str()));
+            }
+
+            let token: TokenResponse = self.client.post(TOKEN_URL).form(&form).send().await?.json().await?;
+
+            match token.error.as_deref() {
+                None => {
+                    self.store_token(token, None)?;
+                    log::info!("✅ Google Docs authorization completed");
+                    return Ok(());
+                }
+                Some("authorization_pending") => continue,
+                Some("slow_down") => interval += 5,
+                Some(error) => {
+                    return Err(AppError::Integration {
+                        message: format!(
+                            "Google authorization failed: {} {}",
+                            error,
+                            token.error_description.unwrap_or_default()
+                        ),
+                    });
+                }
+            }
+        }
+
+        Err(AppError::Integration {
+            message: "Google authorization timed out".to_string(),
+        })
+    }
+
+    /// 要約から新しいGoogleドキュメントを作成
+    pub async fn create_document(&self, title: &str, summary: &Summary) -> AppResult<GoogleDocument> {
+        let access_token = self.access_token().await?;
+
+        let response = self
+            .client
+            .post(DOCS_API_URL)
+            .bearer_auth(&access_token)
+            .json(&json!({ "title": title }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(AppError::Integration {
+                message: format!("Failed to create Google Doc: {}", response.text().await.unwrap_or_default()),
+            });
+        }
+
+        let created: serde_json::Value = response.json().await?;
+        let document_id = created
+            .get("documentId")
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| AppError::Integration {
+                message: "Google Docs response did not include documentId".to_string(),
+            })?
+            .to_string();
+
+        let response = self
+            .client
+            .post(format!("{}/{}:batchUpdate", DOCS_API_URL, document_id))
+            .bearer_auth(&access_token)
+            .json(&json!({ "requests": build_document_requests(title, summary) }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(AppError::Integration {
+                message: format!("Failed to write Google Doc content: {}", response.text().await.unwrap_or_default()),
+            });
+        }
+
+        log::info!("📄 Created Google Doc {} for summary {}", document_id, summary.id);
+
+        Ok(GoogleDocument {
+            url: format!("https://docs.google.com/document/d/{}/edit", document_id),
+            document_id,
+        })
+    }
+
+    /// 有効なアクセストークンを取得（期限切れならリフレッシュ）
+    async fn access_token(&self) -> AppResult<String> {
+        let stored = credentials::load_secret(TOKEN_KEY)?.ok_or_else(|| AppError::Integration {
+            message: "Google Docs is not connected".to_string(),
+        })?;
+        let stored: StoredToken = serde_json::from_str(&stored)?;
+
+        if stored.expires_at > Utc::now() + Duration::seconds(60) {
+            return Ok(stored.access_token);
+        }
+
+        let refresh_token = stored.refresh_token.clone().ok_or_else(|| AppError::Integration {
+            message: "Google Docs token expired; please reconnect".to_string(),
+        })?;
+
+        let mut form = vec![
+            ("client_id", self.client_id.as_str()),
+            ("refresh_token", refresh_token.as_str()),
+            ("grant_type", "refresh_token"),
+        ];
+        if let Some(secret) = &self.client_secret {
+            form.push(("client_secret", secret.as_str()));
+        }
+
+        let token: TokenResponse = self.client.post(TOKEN_URL).form(&form).send().await?.json().await?;
+        if let Some(error) = token.error {
+            return Err(AppError::Integration {
+                message: format!("Failed to refresh Google token: {}", error),
+            });
+        }
+
+        self.store_token(token, Some(refresh_token))
+    }
+
+    fn store_token(&self, token: TokenResponse, previous_refresh_token: Option<String>) -> AppResult<String> {
+        let access_token = token.access_token.ok_or_else(|| AppError::Integration {
+            message: "Google token response did not include access_token".to_string(),
+        })?;
+
+        let stored = StoredToken {
+            access_token: access_token.clone(),
+            // リフレッシュ時は refresh_token が返らないため以前の値を引き継ぐ
+            refresh_token: token.refresh_token.or(previous_refresh_token),
+            expires_at: Utc::now() + Duration::seconds(token.expires_in.unwrap_or(3600)),
+        };
+        credentials::save_secret(TOKEN_KEY, &serde_json::to_string(&stored)?)?;
+
+        Ok(access_token)
+    }
+}
+
+/// Docs API の batchUpdate リクエストを組み立てる（見出しスタイル付き）
+pub fn build_document_requests(title: &str, summary: &Summary) -> Vec<serde_json::Value> {
+    let mut sections: Vec<(String, Option<&str>)> = vec![(title.to_string(), Some("TITLE"))];
+    sections.push(("Summary".to_string(), Some("HEADING_1")));
+    sections.push((summary.summary_text.clone(), None));
+
+    if !summary.key_points.is_empty() {
+        sections.push(("Key Points".to_string(), Some("HEADING_1")));
+        for point in &summary.key_points {
+            sections.push((format!("• {}", point), None));
+        }
+    }
+
+    if !summary.action_items.is_empty() {
+        sections.push(("Action Items".to_string(), Some("HEADING_1")));
+        for item in &summary.action_items {
+            sections.push((format!("☐ {}", item), None));
+        }
+    }
+
+    let mut text = String::new();
+    let mut style_requests = Vec::new();
+    // Docs API のインデックスは本文先頭が1、UTF-16コード単位で数える
+    let mut index = 1;
+
+    for (paragraph, style) in sections {
+        let paragraph = format!("{}\n", paragraph);
+        let length = paragraph.encode_utf16().count();
+
+        if let Some(style) = style {
+            style_requests.push(json!({
+                "updateParagraphStyle": {
+                    "range": { "startIndex": index, "endIndex": index + length },
+                    "paragraphStyle": { "namedStyleType": style },
+                    "fields": "namedStyleType"
+                }
+            }));
+        }
+
+        text.push_str(&paragraph);
+        index += length;
+    }
+
+    let mut requests = vec![json!({
+        "insertText": {
+            "location": { "index": 1 },
+            "text": text
+        }
+    })];
+    requests.extend(style_requests);
+    requests
+}
@@ -0,0 +1,488 @@
+//! ローカルgRPC APIサーバー（tonic）
+//!
+//! REST API と同じサービス群を使い、一覧・検索・書き起こし・要約を提供する。
+//! 書き起こしと要約はサーバーストリーミングで進捗を返す。
+//!
+//! 外部操作用サーバーと同じトークンを `x-control-token` メタデータで渡さない呼び出しは
+//! UNAUTHENTICATED で拒否する。ループバック以外のアドレスで待ち受けるのは、トークンが
+//! 設定されている場合に限る。
+
+use crate::errors::{AppError, AppResult};
+use crate::models::{self, LLMConfig, LLMProvider, RecordingQuery, SortBy, SortOrder, SummaryStatus, TranscriptionStatus};
+use crate::services::control_server::CONTROL_TOKEN_HEADER;
+use crate::services::summarizer::SummaryRoute;
+use crate::services::{corrections, recording_segments, transcription_language, ApiServerState, LLMService};
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status};
+
+pub mod proto {
+    tonic::include_proto!("meeting_summarizer.v1");
+}
+
+use proto::meeting_summarizer_server::{MeetingSummarizer, MeetingSummarizerServer};
+
+pub use crate::services::api_server::ApiServerStatus as GrpcServerStatus;
+
+/// ローカルgRPCサーバー（既定では127.0.0.1のみにバインド）
+pub struct GrpcServer {
+    address: Option<SocketAddr>,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+    state: Option<ApiServerState>,
+}
+
+impl GrpcServer {
+    pub fn new() -> Self {
+        Self {
+            address: None,
+            shutdown_tx: None,
+            handle: None,
+            state: None,
+        }
+    }
+
+    /// サーバーを起動
+    pub async fn start(&mut self, state: ApiServerState, bind_address: &str, port: u16) -> AppResult<SocketAddr> {
+        if self.is_running() {
+            return Err(AppError::InvalidOperation {
+                message: "gRPC server is already running".to_string(),
+            });
+        }
+
+        let ip: IpAddr = bind_address.parse().map_err(|_| AppError::ValidationError {
+            message: format!("Invalid bind address: {}", bind_address),
+        })?;
+        if state.token.trim().is_empty() {
+            return Err(AppError::ValidationError {
+                message: if ip.is_loopback() {
+                    "gRPC token must not be empty".to_string()
+                } else {
+                    format!("Refusing to expose the gRPC server on {} without a token", ip)
+                },
+            });
+        }
+        if !ip.is_loopback() {
+            log::warn!("⚠️ gRPC server is exposed on the local network: {}", ip);
+        }
+
+        let listener = tokio::net::TcpListener::bind(SocketAddr::new(ip, port)).await?;
+        let address = listener.local_addr()?;
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let token = state.token.clone();
+        let service = MeetingSummarizerServer::with_interceptor(GrpcService { state: state.clone() }, move |request| {
+            check_token(request, &token)
+        });
+
+        let handle = tokio::spawn(async move {
+            let result = tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async {
+                    let _ = shutdown_rx.await;
+                })
+                .await;
+
+            if let Err(e) = result {
+                log::error!("❌ gRPC server terminated with error: {}", e);
+            }
+        });
+
+        self.address = Some(address);
+        self.shutdown_tx = Some(shutdown_tx);
+        self.handle = Some(handle);
+        self.state = Some(state);
+
+        log::info!("📡 Local gRPC server listening on {}", address);
+        Ok(address)
+    }
+
+    /// サーバーを停止
+    pub async fn stop(&mut self) -> AppResult<()> {
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(());
+        }
+
+        if let Some(handle) = self.handle.take() {
+            handle.await.map_err(|e| AppError::InvalidOperation {
+                message: format!("Failed to stop gRPC server: {}", e),
+            })?;
+        }
+
+        self.address = None;
+        log::info!("🛑 Local gRPC server stopped");
+        Ok(())
+    }
+
+    /// トークンを作り直した後、起動中なら新しいトークンで開き直す
+    pub async fn restart_with_token(&mut self, token: String) -> AppResult<()> {
+        let (Some(address), Some(state)) = (self.address.filter(|_| self.is_running()), self.state.clone()) else {
+            return Ok(());
+        };
+        self.stop().await?;
+        self.start(ApiServerState { token, ..state }, &address.ip().to_string(), address.port())
+            .await?;
+        Ok(())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.handle.as_ref().is_some_and(|handle| !handle.is_finished())
+    }
+
+    pub fn get_status(&self) -> GrpcServerStatus {
+        let running = self.is_running();
+        GrpcServerStatus {
+            running,
+            address: if running { self.address.map(|addr| addr.to_string()) } else { None },
+        }
+    }
+}
+
+impl Default for GrpcServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct GrpcService {
+    state: ApiServerState,
+}
+
+fn check_token(request: Request<()>, token: &str) -> Result<Request<()>, Status> {
+    let provided = request.metadata().get(CONTROL_TOKEN_HEADER).and_then(|value| value.to_str().ok());
+    if provided != Some(token) {
+        return Err(Status::unauthenticated("Missing or invalid token"));
+    }
+    Ok(request)
+}
+
+type ProgressStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl MeetingSummarizer for GrpcService {
+    async fn list_recordings(
+        &self,
+        _request: Request<proto::ListRecordingsRequest>,
+    ) -> Result<Response<proto::ListRecordingsResponse>, Status> {
+        let database = &self.state.db;
+        let recordings = database.get_recordings(false).await.map_err(to_status)?;
+        Ok(Response::new(proto::ListRecordingsResponse {
+            recordings: recordings.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn search_recordings(
+        &self,
+        request: Request<proto::SearchRecordingsRequest>,
+    ) -> Result<Response<proto::ListRecordingsResponse>, Status> {
+        let request = request.into_inner();
+        let query = RecordingQuery {
+            search_text: request.search_text,
+            category: request.category,
+            tags: request.tags,
+            date_from: None,
+            date_to: None,
+            min_duration: None,
+            max_duration: None,
+            min_silence_ratio: None,
+            max_silence_ratio: None,
+            category_path: None,
+            tag_paths: Vec::new(),
+            color_labels: Vec::new(),
+            include_archived: false,
+            has_transcription: None,
+            has_summary: None,
+            transcription_statuses: Vec::new(),
+            condition: None,
+            limit: Some(request.limit.unwrap_or(50)),
+            offset: Some(request.offset.unwrap_or(0)),
+            sort_by: SortBy::CreatedAt,
+            sort_order: SortOrder::Desc,
+        };
+
+        let database = &self.state.db;
+        let recordings = database.search_recordings(&query).await.map_err(to_status)?;
+        Ok(Response::new(proto::ListRecordingsResponse {
+            recordings: recordings.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    type TranscribeStream = ProgressStream<proto::TranscribeProgress>;
+
+    async fn transcribe(
+        &self,
+        request: Request<proto::TranscribeRequest>,
+    ) -> Result<Response<Self::TranscribeStream>, Status> {
+        let request = request.into_inner();
+        log::info!("📡 gRPC transcription requested for recording: {}", request.recording_id);
+
+        let recording = self
+            .state
+            .recording_service
+            .get_recording(&request.recording_id)
+            .await
+            .map_err(to_status)?
+            .ok_or_else(|| Status::not_found(format!("Recording {} not found", request.recording_id)))?;
+
+        let state = self.state.clone();
+        let (tx, rx) = mpsc::channel(8);
+
+        tokio::spawn(async move {
+            let progress = |stage: &str, message: &str, progress: f32| proto::TranscribeProgress {
+                stage: stage.to_string(),
+                message: message.to_string(),
+                progress,
+                completed: false,
+                transcription: None,
+            };
+
+            if !state.whisper_service.is_initialized().await {
+                let _ = tx.send(Ok(progress("initializing", "Initializing Whisper", 0.1))).await;
+                if let Err(e) = state.whisper_service.initialize().await {
+                    let _ = tx.send(Err(to_status(e))).await;
+                    return;
+                }
+            }
+
+            let language = match transcription_language::resolve_language(&state.db, &recording, request.language).await {
+                Ok(language) => language,
+                Err(e) => {
+                    let _ = tx.send(Err(to_status(e))).await;
+                    return;
+                }
+            };
+
+            let audio_files = match recording_segments::audio_files(&state.db, &recording).await {
+                Ok(audio_files) => audio_files,
+                Err(e) => {
+                    let _ = tx.send(Err(to_status(e))).await;
+                    return;
+                }
+            };
+
+            let _ = tx.send(Ok(progress("transcribing", "Transcribing audio", 0.3))).await;
+            let result = state
+                .whisper_service
+                .transcribe_audio_files(&audio_files, recording.id.clone(), language)
+                .await;
+
+            let mut transcription = match result {
+                Ok(transcription) => transcription,
+                Err(e) => {
+                    let _ = tx.send(Err(to_status(e))).await;
+                    return;
+                }
+            };
+
+            let _ = tx.send(Ok(progress("saving", "Saving transcription", 0.9))).await;
+            if let Err(e) = corrections::apply_corrections(&state.db, &mut transcription).await {
+                let _ = tx.send(Err(to_status(e))).await;
+                return;
+            }
+            if let Err(e) = state.db.create_transcription(&transcription).await {
+                let _ = tx.send(Err(to_status(e))).await;
+                return;
+            }
+
+            let _ = tx
+                .send(Ok(proto::TranscribeProgress {
+                    stage: "completed".to_string(),
+                    message: "Transcription completed".to_string(),
+                    progress: 1.0,
+                    completed: true,
+                    transcription: Some(transcription.into()),
+                }))
+                .await;
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx)) as Self::TranscribeStream))
+    }
+
+    type SummarizeStream = ProgressStream<proto::SummarizeProgress>;
+
+    async fn summarize(
+        &self,
+        request: Request<proto::SummarizeRequest>,
+    ) -> Result<Response<Self::SummarizeStream>, Status> {
+        let request = request.into_inner();
+        log::info!("📡 gRPC summarization requested for transcription: {}", request.transcription_id);
+
+        let transcription = self
+            .state
+            .db
+            .get_transcription(&request.transcription_id)
+            .await
+            .map_err(to_status)?
+            .ok_or_else(|| Status::not_found(format!("Transcription {} not found", request.transcription_id)))?;
+
+        // 指定が無ければ画面からの要約と同じく自動切り替えで選んだモデルを使う
+        let model_config = if request.provider.is_none() && request.base_url.is_none() && request.model_name.is_none() {
+            None
+        } else {
+            let defaults = LLMConfig::default();
+            let provider = match request.provider.as_deref() {
+                Some(provider) => provider.parse::<LLMProvider>().map_err(Status::invalid_argument)?,
+                None => defaults.provider.clone(),
+            };
+            Some(LLMConfig {
+                provider,
+                base_url: request.base_url.unwrap_or(defaults.base_url.clone()),
+                model_name: request.model_name.unwrap_or(defaults.model_name.clone()),
+                ..defaults
+            })
+        };
+
+        let state = self.state.clone();
+        let (tx, rx) = mpsc::channel(8);
+
+        tokio::spawn(async move {
+            let progress = |stage: &str, message: String, progress: f32| proto::SummarizeProgress {
+                stage: stage.to_string(),
+                message,
+                progress,
+                completed: false,
+                summary: None,
+            };
+
+            let route = state.summarizer.route(model_config, &transcription.text).await;
+            let context = state.summarizer.context(&transcription.id, None).await;
+            // 同時要約は先に成功した方を使うので個別には確認しない
+            if let SummaryRoute::Single(config) = &route {
+                let llm_service = LLMService::new(config.clone());
+                let _ = tx.send(Ok(progress("connecting", format!("Connecting to {}", config.base_url), 0.1))).await;
+                match llm_service.check_connection().await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        let _ = tx
+                            .send(Err(Status::unavailable(format!("LLM server is not reachable: {}", config.base_url))))
+                            .await;
+                        return;
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(to_status(e))).await;
+                        return;
+                    }
+                }
+            }
+
+            let _ = tx.send(Ok(progress("summarizing", format!("Summarizing with {}", route.model_label()), 0.3))).await;
+            let summary = match state
+                .summarizer
+                .summarize_via(&route, &transcription.id, &transcription.text, &context)
+                .await
+            {
+                Ok(summary) => summary,
+                Err(e) => {
+                    let _ = tx.send(Err(to_status(e))).await;
+                    return;
+                }
+            };
+
+            if let SummaryStatus::Failed(error) = &summary.status {
+                let _ = tx.send(Err(Status::internal(error.clone()))).await;
+                return;
+            }
+
+            let _ = tx.send(Ok(progress("saving", "Saving summary".to_string(), 0.9))).await;
+            if let Err(e) = state.db.create_summary(&summary).await {
+                let _ = tx.send(Err(to_status(e))).await;
+                return;
+            }
+
+            let _ = tx
+                .send(Ok(proto::SummarizeProgress {
+                    stage: "completed".to_string(),
+                    message: "Summary completed".to_string(),
+                    progress: 1.0,
+                    completed: true,
+                    summary: Some(summary.into()),
+                }))
+                .await;
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx)) as Self::SummarizeStream))
+    }
+}
+
+fn to_status(error: AppError) -> Status {
+    let code = match &error {
+        AppError::FileNotFound { .. } => tonic::Code::NotFound,
+        AppError::ValidationError { .. } | AppError::InvalidPath { .. } => tonic::Code::InvalidArgument,
+        AppError::PermissionDenied { .. } => tonic::Code::PermissionDenied,
+        AppError::InvalidOperation { .. } => tonic::Code::FailedPrecondition,
+        AppError::TranscriptionInProgress { .. } => tonic::Code::AlreadyExists,
+        AppError::LLMConnectionError { .. } => tonic::Code::Unavailable,
+        _ => tonic::Code::Internal,
+    };
+    let message: String = error.into();
+    Status::new(code, message)
+}
+
+fn status_string(status: &TranscriptionStatus) -> String {
+    match status {
+        TranscriptionStatus::Pending => "pending".to_string(),
+        TranscriptionStatus::Processing => "processing".to_string(),
+        TranscriptionStatus::Completed => "completed".to_string(),
+        TranscriptionStatus::Failed(error) => format!("failed:{}", error),
+    }
+}
+
+impl From<models::Recording> for proto::Recording {
+    fn from(recording: models::Recording) -> Self {
+        Self {
+            id: recording.id,
+            filename: recording.filename,
+            file_path: recording.file_path,
+            title: recording.title,
+            description: recording.description,
+            category: recording.category,
+            tags: recording.tags,
+            duration_seconds: recording.duration,
+            file_size: recording.file_size,
+            created_at: recording.created_at.to_rfc3339(),
+            updated_at: recording.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<models::Transcription> for proto::Transcription {
+    fn from(transcription: models::Transcription) -> Self {
+        Self {
+            status: status_string(&transcription.status),
+            id: transcription.id,
+            recording_id: transcription.recording_id,
+            text: transcription.text,
+            language: transcription.language,
+            confidence: transcription.confidence,
+            processing_time_ms: transcription.processing_time_ms,
+            created_at: transcription.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<models::Summary> for proto::Summary {
+    fn from(summary: models::Summary) -> Self {
+        let status = match &summary.status {
+            SummaryStatus::Pending => "pending".to_string(),
+            SummaryStatus::Processing => "processing".to_string(),
+            SummaryStatus::Completed => "completed".to_string(),
+            SummaryStatus::Failed(error) => format!("failed:{}", error),
+        };
+
+        Self {
+            status,
+            id: summary.id,
+            transcription_id: summary.transcription_id,
+            summary_text: summary.summary_text,
+            key_points: summary.key_points,
+            action_items: summary.action_items,
+            model_used: summary.model_used,
+            processing_time_ms: summary.processing_time_ms,
+            created_at: summary.created_at.to_rfc3339(),
+        }
+    }
+}
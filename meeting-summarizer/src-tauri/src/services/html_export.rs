@@ -0,0 +1,278 @@
+//! 単体で開ける HTML 形式の書き起こし（読み上げ追従用）
+//!
+//! 要約・チャプター・タイムスタンプ付きの書き起こしを1ファイルにまとめる。音声を埋め込んだ場合は
+//! 文をクリックするとその位置から再生し、再生中の文をハイライトする。時刻付きのセグメントが無い
+//! 書き起こしは、録音の長さを文字数で按分した目安の時刻（`~` 付き）で出力する。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Chapter, Recording, Summary, TranscriptSegment, Transcription};
+use crate::services::chapters::format_timestamp;
+use base64::Engine;
+use std::path::Path;
+
+/// 埋め込む音声
+pub struct EmbeddedAudio {
+    pub mime_type: &'static str,
+    pub data: Vec<u8>,
+}
+
+/// 録音の最新の書き起こし・要約から HTML を生成する
+pub async fn export_recording_html(database: &Database, recording_id: &str, include_audio: bool) -> AppResult<String> {
+    let recording = database
+        .get_recording(recording_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording with id {} not found", recording_id),
+        })?;
+
+    let transcription = database
+        .get_transcriptions_by_recording(recording_id)
+        .await?
+        .into_iter()
+        .next();
+    let (segments, summary) = match &transcription {
+        Some(transcription) => (
+            database.get_segments_by_transcription(&transcription.id).await?,
+            database
+                .get_summaries_by_transcription(&transcription.id)
+                .await?
+                .into_iter()
+                .next(),
+        ),
+        None => (Vec::new(), None),
+    };
+    let chapters = database.get_chapters_by_recording(recording_id).await?;
+
+    let audio = if include_audio {
+        let path = Path::new(&recording.file_path);
+        Some(EmbeddedAudio {
+            mime_type: audio_mime_type(path),
+            data: tokio::fs::read(path).await?,
+        })
+    } else {
+        None
+    };
+
+    Ok(render_html(
+        &recording,
+        transcription.as_ref(),
+        &segments,
+        summary.as_ref(),
+        &chapters,
+        audio.as_ref(),
+    ))
+}
+
+pub fn render_html(
+    recording: &Recording,
+    transcription: Option<&Transcription>,
+    segments: &[TranscriptSegment],
+    summary: Option<&Summary>,
+    chapters: &[Chapter],
+    audio: Option<&EmbeddedAudio>,
+) -> String {
+    let title = escape_html(recording.title.as_deref().unwrap_or(&recording.filename));
+    let mut body = format!("<header>\n<h1>{}</h1>\n", title);
+    body.push_str(&format!(
+        "<p class=\"meta\">{}",
+        recording.created_at.format("%Y-%m-%d %H:%M")
+    ));
+    if let Some(duration) = recording.duration {
+        body.push_str(&format!(" · {}", format_timestamp(duration * 1000)));
+    }
+    body.push_str("</p>\n</header>\n");
+
+    if let Some(audio) = audio {
+        body.push_str(&format!(
+            "<audio id=\"player\" controls preload=\"metadata\" src=\"data:{};base64,{}\"></audio>\n",
+            audio.mime_type,
+            base64::engine::general_purpose::STANDARD.encode(&audio.data)
+        ));
+    }
+
+    if let Some(summary) = summary {
+        body.push_str("<section class=\"summary\">\n<h2>Summary</h2>\n");
+        body.push_str(&format!("<p>{}</p>\n", escape_html(&summary.summary_text)));
+        push_list(&mut body, "Key points", &summary.key_points);
+        push_list(&mut body, "Action items", &summary.action_items);
+        body.push_str("</section>\n");
+    }
+
+    if !chapters.is_empty() {
+        body.push_str("<nav class=\"chapters\">\n<h2>Chapters</h2>\n<ol>\n");
+        for chapter in chapters {
+            body.push_str(&format!(
+                "<li><a href=\"#\" class=\"seek\" data-start=\"{}\">[{}] {}</a></li>\n",
+                seconds(chapter.start_ms),
+                format_timestamp(chapter.start_ms),
+                escape_html(chapter.title.trim())
+            ));
+        }
+        body.push_str("</ol>\n</nav>\n");
+    }
+
+    body.push_str("<section class=\"transcript\">\n<h2>Transcript</h2>\n");
+    let text = transcription.map(|t| t.text.as_str()).unwrap_or_default();
+    let duration_ms = recording.duration.unwrap_or_default() * 1000;
+    if !segments.is_empty() {
+        push_segments(&mut body, segments, false);
+    } else if duration_ms > 0 && !text.trim().is_empty() {
+        // セグメントが無い場合は録音の長さを文字数で按分した目安の時刻で出力
+        push_segments(&mut body, &estimated_segments(text, duration_ms), true);
+    } else {
+        // 録音の長さも分からない場合は時刻なしで段落ごとに出力
+        for paragraph in text.split("\n\n") {
+            let paragraph = paragraph.trim();
+            if !paragraph.is_empty() {
+                body.push_str(&format!("<p>{}</p>\n", escape_html(paragraph)));
+            }
+        }
+    }
+    body.push_str("</section>\n");
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"{lang}\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<style>{style}</style>\n</head>\n<body>\n{body}<script>{script}</script>\n</body>\n</html>\n",
+        lang = escape_html(transcription.map(|t| t.language.as_str()).unwrap_or("ja")),
+        title = title,
+        style = STYLE,
+        body = body,
+        script = SCRIPT,
+    )
+}
+
+fn push_segments(body: &mut String, segments: &[TranscriptSegment], estimated: bool) {
+    for segment in segments {
+        body.push_str(&format!(
+            "<p class=\"seg seek\" data-start=\"{}\" data-end=\"{}\"><span class=\"ts\">{}{}</span>",
+            seconds(segment.start_ms),
+            seconds(segment.end_ms),
+            if estimated { "~" } else { "" },
+            format_timestamp(segment.start_ms)
+        ));
+        if let Some(speaker) = &segment.speaker {
+            body.push_str(&format!("<span class=\"speaker\">{}</span>", escape_html(speaker)));
+        }
+        body.push_str(&format!("{}</p>\n", escape_html(segment.text.trim())));
+    }
+}
+
+/// 時刻の無い書き起こしを文に分け、録音の長さを文字数で按分した目安の時刻を付ける
+pub fn estimated_segments(text: &str, duration_ms: i64) -> Vec<TranscriptSegment> {
+    let sentences = split_sentences(text);
+    let total = sentences.iter().map(|sentence| sentence.chars().count()).sum::<usize>().max(1) as i64;
+    let mut consumed = 0;
+    sentences
+        .into_iter()
+        .enumerate()
+        .map(|(index, sentence)| {
+            let start_ms = duration_ms * consumed / total;
+            consumed += sentence.chars().count() as i64;
+            TranscriptSegment::new(String::new(), index as i32, start_ms, duration_ms * consumed / total, sentence)
+        })
+        .collect()
+}
+
+fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        current.push(c);
+        let ends = match c {
+            '。' | '！' | '？' | '\n' => true,
+            '.' | '!' | '?' => chars.peek().is_none_or(|next| next.is_whitespace()),
+            _ => false,
+        };
+        if ends {
+            let sentence = current.trim();
+            if !sentence.is_empty() {
+                sentences.push(sentence.to_string());
+            }
+            current.clear();
+        }
+    }
+    let rest = current.trim();
+    if !rest.is_empty() {
+        sentences.push(rest.to_string());
+    }
+    sentences
+}
+
+fn push_list(body: &mut String, heading: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    body.push_str(&format!("<h3>{}</h3>\n<ul>\n", heading));
+    for item in items {
+        body.push_str(&format!("<li>{}</li>\n", escape_html(item)));
+    }
+    body.push_str("</ul>\n");
+}
+
+fn seconds(ms: i64) -> String {
+    format!("{:.3}", ms.max(0) as f64 / 1000.0)
+}
+
+fn audio_mime_type(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .as_deref()
+    {
+        Some("mp3") => "audio/mpeg",
+        Some("m4a") | Some("mp4") | Some("aac") => "audio/mp4",
+        Some("ogg") | Some("opus") => "audio/ogg",
+        Some("flac") => "audio/flac",
+        Some("webm") => "audio/webm",
+        _ => "audio/wav",
+    }
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Hiragino Sans", "Noto Sans JP", sans-serif; max-width: 860px; margin: 2rem auto; padding: 0 1rem; line-height: 1.7; color: #222; }
+.meta { color: #666; }
+audio { position: sticky; top: 0; width: 100%; background: #fff; padding: .5rem 0; }
+.summary { background: #f6f8fa; border-radius: 8px; padding: .5rem 1rem; }
+.seek { cursor: pointer; }
+.seg { margin: .25rem 0; padding: .15rem .4rem; border-radius: 4px; }
+.seg:hover { background: #eef3ff; }
+.seg.active { background: #fff3c4; }
+.ts { color: #888; font-size: .85em; margin-right: .6em; font-variant-numeric: tabular-nums; }
+.speaker { font-weight: bold; margin-right: .4em; }
+"#;
+
+const SCRIPT: &str = r#"
+(function () {
+  var player = document.getElementById('player');
+  var segments = Array.prototype.slice.call(document.querySelectorAll('.seg'));
+  document.querySelectorAll('.seek').forEach(function (el) {
+    el.addEventListener('click', function (event) {
+      event.preventDefault();
+      if (!player) return;
+      player.currentTime = parseFloat(el.dataset.start);
+      player.play();
+    });
+  });
+  if (!player) return;
+  player.addEventListener('timeupdate', function () {
+    var t = player.currentTime;
+    segments.forEach(function (el) {
+      var active = t >= parseFloat(el.dataset.start) && t < parseFloat(el.dataset.end);
+      if (active && !el.classList.contains('active')) el.scrollIntoView({ block: 'nearest', behavior: 'smooth' });
+      el.classList.toggle('active', active);
+    });
+  });
+})();
+"#;
@@ -0,0 +1,117 @@
+//! バックエンドから返すメッセージの多言語化
+//!
+//! メッセージは `src/locales/<locale>.json` のカタログから引き、`{name}` 形式の
+//! プレースホルダーを引数で置き換える。ログ出力は対象外（英語/日本語のまま）。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::{OnceLock, RwLock};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    Ja,
+    En,
+}
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::Ja => "ja",
+            Locale::En => "en",
+        }
+    }
+
+    pub fn all() -> &'static [Locale] {
+        &[Locale::Ja, Locale::En]
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // "ja-JP" や "en_US" のような地域付きの指定も受け付ける
+        match s.split(['-', '_']).next().unwrap_or_default().to_lowercase().as_str() {
+            "ja" => Ok(Locale::Ja),
+            "en" => Ok(Locale::En),
+            _ => Err(format!("Unsupported locale: {}", s)),
+        }
+    }
+}
+
+static CURRENT_LOCALE: RwLock<Locale> = RwLock::new(Locale::Ja);
+static CATALOGS: OnceLock<HashMap<Locale, HashMap<String, String>>> = OnceLock::new();
+
+fn catalogs() -> &'static HashMap<Locale, HashMap<String, String>> {
+    CATALOGS.get_or_init(|| {
+        let parse = |source: &str| -> HashMap<String, String> {
+            serde_json::from_str(source).expect("Invalid message catalog")
+        };
+
+        HashMap::from([
+            (Locale::Ja, parse(include_str!("../locales/ja.json"))),
+            (Locale::En, parse(include_str!("../locales/en.json"))),
+        ])
+    })
+}
+
+pub fn set_locale(locale: Locale) {
+    if let Ok(mut current) = CURRENT_LOCALE.write() {
+        *current = locale;
+    }
+    log::info!("🌐 Locale set to {}", locale.as_str());
+}
+
+pub fn current_locale() -> Locale {
+    CURRENT_LOCALE.read().map(|locale| *locale).unwrap_or_default()
+}
+
+/// 現在のロケールでメッセージを取得
+pub fn t(key: &str) -> String {
+    tr(key, &[])
+}
+
+/// 現在のロケールでメッセージを取得し、プレースホルダーを置き換える
+pub fn tr(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    tr_in(current_locale(), key, args)
+}
+
+/// 指定ロケールでメッセージを取得。未登録のキーは英語、それも無ければキー自体を返す
+pub fn tr_in(locale: Locale, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let catalogs = catalogs();
+    let template = catalogs
+        .get(&locale)
+        .and_then(|catalog| catalog.get(key))
+        .or_else(|| catalogs.get(&Locale::En).and_then(|catalog| catalog.get(key)));
+
+    let Some(template) = template else {
+        log::warn!("⚠️ Missing message catalog entry: {}", key);
+        return key.to_string();
+    };
+
+    args.iter().fold(template.clone(), |message, (name, value)| {
+        message.replace(&format!("{{{}}}", name), &value.to_string())
+    })
+}
+
+/// 全ロケールで同じキーが揃っているか検査し、欠けているキーを返す
+pub fn missing_keys() -> Vec<(Locale, String)> {
+    let catalogs = catalogs();
+    let mut all_keys: Vec<&String> = catalogs.values().flat_map(|catalog| catalog.keys()).collect();
+    all_keys.sort();
+    all_keys.dedup();
+
+    let mut missing = Vec::new();
+    for locale in Locale::all() {
+        for key in &all_keys {
+            if !catalogs.get(locale).is_some_and(|catalog| catalog.contains_key(*key)) {
+                missing.push((*locale, (*key).clone()));
+            }
+        }
+    }
+    missing
+}
@@ -0,0 +1,115 @@
+//! アクションアイテムの iCalendar (.ics) 出力
+//!
+//! 各アクションアイテムを VTODO として出力し、本文から期限日を読み取れた場合は
+//! DUE を設定したうえでフォローアップ用の終日 VEVENT も追加する。
+
+use crate::models::Summary;
+use chrono::{DateTime, NaiveDate, Utc};
+
+const PRODUCT_ID: &str = "-//kenshiroebisu//Meeting Summarizer//JA";
+
+/// 要約群のアクションアイテムから iCalendar 文字列を生成
+pub fn render_action_items(summaries: &[Summary], meeting_title: Option<&str>) -> String {
+    let now = format_datetime(&Utc::now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODUCT_ID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+    ];
+
+    for summary in summaries {
+        for (index, item) in summary.action_items.iter().enumerate() {
+            let uid = format!("{}-{}@meeting-summarizer", summary.id, index);
+            let description = meeting_title
+                .map(|title| format!("Meeting: {}", title))
+                .unwrap_or_else(|| format!("Summary: {}", summary.id));
+            let due_date = extract_due_date(item);
+
+            lines.push("BEGIN:VTODO".to_string());
+            lines.push(format!("UID:todo-{}", uid));
+            lines.push(format!("DTSTAMP:{}", now));
+            lines.push(format!("CREATED:{}", format_datetime(&summary.created_at)));
+            lines.push(format!("SUMMARY:{}", escape_text(item)));
+            lines.push(format!("DESCRIPTION:{}", escape_text(&description)));
+            if let Some(date) = due_date {
+                lines.push(format!("DUE;VALUE=DATE:{}", date.format("%Y%m%d")));
+            }
+            lines.push("STATUS:NEEDS-ACTION".to_string());
+            lines.push("END:VTODO".to_string());
+
+            if let Some(date) = due_date {
+                lines.push("BEGIN:VEVENT".to_string());
+                lines.push(format!("UID:event-{}", uid));
+                lines.push(format!("DTSTAMP:{}", now));
+                lines.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+                if let Some(next_day) = date.succ_opt() {
+                    lines.push(format!("DTEND;VALUE=DATE:{}", next_day.format("%Y%m%d")));
+                }
+                lines.push(format!("SUMMARY:{}", escape_text(&format!("Follow-up: {}", item))));
+                lines.push(format!("DESCRIPTION:{}", escape_text(&description)));
+                lines.push("TRANSP:TRANSPARENT".to_string());
+                lines.push("END:VEVENT".to_string());
+            }
+        }
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+/// アクションアイテム本文から期限日を抽出（2024-05-10 / 2024/5/10 / 2024年5月10日）
+pub fn extract_due_date(text: &str) -> Option<NaiveDate> {
+    let normalized: String = text
+        .chars()
+        .map(|c| match c {
+            '年' | '月' | '/' => '-',
+            '０'..='９' => char::from_digit(c as u32 - '０' as u32, 10).unwrap_or(c),
+            _ => c,
+        })
+        .collect();
+
+    normalized
+        .split(|c: char| !(c.is_ascii_digit() || c == '-'))
+        .map(|token| token.trim_matches('-'))
+        .filter(|token| token.len() >= 8)
+        .find_map(|token| NaiveDate::parse_from_str(token, "%Y-%m-%d").ok())
+}
+
+fn format_datetime(datetime: &DateTime<Utc>) -> String {
+    datetime.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// RFC 5545 のTEXT値エスケープ
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// 75オクテットを超える行を折り返す（マルチバイト文字の途中では切らない）
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut line_len = 0;
+
+    for c in line.chars() {
+        let char_len = c.len_utf8();
+        if line_len + char_len > 75 {
+            folded.push_str("\r\n ");
+            line_len = 1;
+        }
+        folded.push(c);
+        line_len += char_len;
+    }
+
+    folded
+}
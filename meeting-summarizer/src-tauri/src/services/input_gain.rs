@@ -0,0 +1,68 @@
+//! 録音時の入力ゲイン
+//!
+//! 固定ゲインのほか、入力のピークが目標の大きさに近づくようゲインを少しずつ変える自動ゲイン
+//! （AGC）を選べる。大きな音に対してはすぐに下げ、小さな音に対してはゆっくり上げるので、
+//! 話者の声が大きくなっても割れにくい。
+
+use crate::services::app_settings::InputGainSettings;
+
+/// 自動ゲインで目指すピークの大きさ
+pub const AUTO_GAIN_TARGET_PEAK: f32 = 0.5;
+
+/// 書き出すサンプルの上限（これを超える分は切り詰める）
+pub const OUTPUT_LIMIT: f32 = 0.95;
+
+/// ほぼ無音とみなすピーク（自動ゲインはこれ以下ではゲインを上げない）
+const NOISE_FLOOR: f32 = 0.0001;
+
+/// ゲインを上げるとき・下げるときの追従の速さ（1回の入力ごと）
+const RISE_RATE: f32 = 0.02;
+const FALL_RATE: f32 = 0.5;
+
+/// 入力ごとにゲインをかける（自動ゲインの現在値を保持する）
+#[derive(Debug, Clone)]
+pub struct GainStage {
+    settings: InputGainSettings,
+    current_gain: f32,
+}
+
+impl GainStage {
+    pub fn new(settings: InputGainSettings) -> Self {
+        Self {
+            settings,
+            current_gain: settings.gain,
+        }
+    }
+
+    /// 設定を差し替える（自動ゲインの現在値は引き継ぐ）
+    pub fn update(&mut self, settings: InputGainSettings) {
+        if !settings.auto_gain {
+            self.current_gain = settings.gain;
+        }
+        self.settings = settings;
+    }
+
+    /// 現在のゲイン
+    pub fn gain(&self) -> f32 {
+        self.current_gain
+    }
+
+    /// `samples` にゲインをかけ、`OUTPUT_LIMIT` で切り詰める
+    pub fn process(&mut self, samples: &mut [f32]) {
+        if self.settings.auto_gain {
+            let peak = samples.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+            if peak > NOISE_FLOOR {
+                let desired = (AUTO_GAIN_TARGET_PEAK / peak).clamp(InputGainSettings::MIN_GAIN, InputGainSettings::MAX_GAIN);
+                let rate = if desired < self.current_gain { FALL_RATE } else { RISE_RATE };
+                self.current_gain += (desired - self.current_gain) * rate;
+            }
+        }
+
+        for sample in samples.iter_mut() {
+            // ほぼ無音はそのまま（ノイズを持ち上げない）
+            if sample.abs() > NOISE_FLOOR {
+                *sample = (*sample * self.current_gain).clamp(-OUTPUT_LIMIT, OUTPUT_LIMIT);
+            }
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! 永続化されたバックグラウンドジョブキュー
+//!
+//! ジョブは jobs テーブルに保存され、ワーカーが優先度順に取り出して実行する。
+//! アプリ終了時に実行中だったジョブは次回起動時に再投入される。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Job, JobPayload, JobStatus, LLMConfig, Transcription, TranscriptionStatus};
+use crate::services::{channel_separation, corrections, export, recording_segments, transcription_language, Summarizer, WhisperService};
+use chrono::{Duration, Utc};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{Mutex, Notify};
+use tokio::task::{AbortHandle, JoinHandle};
+
+/// 新規ジョブがない場合のポーリング間隔
+const IDLE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+/// リトライ時のバックオフ基準（秒）。attempts回目は BASE * 2^(attempts-1)
+const RETRY_BACKOFF_BASE_SECS: i64 = 10;
+
+pub struct JobQueue {
+    db: Arc<Database>,
+    whisper_service: Arc<WhisperService>,
+    summarizer: Arc<Summarizer>,
+    notify: Arc<Notify>,
+    running: Arc<Mutex<HashMap<String, AbortHandle>>>,
+    workers: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl JobQueue {
+    pub fn new(db: Arc<Database>, whisper_service: Arc<WhisperService>, summarizer: Arc<Summarizer>) -> Self {
+        Self {
+            db,
+            whisper_service,
+            summarizer,
+            notify: Arc::new(Notify::new()),
+            running: Arc::new(Mutex::new(HashMap::new())),
+            workers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 中断ジョブを再投入し、ワーカーを起動
+    pub async fn start(self: &Arc<Self>, worker_count: usize) -> AppResult<()> {
+        let requeued = self.db.requeue_interrupted_jobs().await?;
+        if requeued > 0 {
+            log::info!("🔁 Requeued {} interrupted jobs", requeued);
+        }
+
+        let mut workers = self.workers.lock().await;
+        for worker_id in 0..worker_count.max(1) {
+            let queue = self.clone();
+            workers.push(tokio::spawn(async move {
+                queue.worker_loop(worker_id).await;
+            }));
+        }
+
+        log::info!("🧵 Job queue started with {} workers", workers.len());
+        Ok(())
+    }
+
+    /// ジョブを登録
+    ///
+    /// 同じ録音の書き起こしジョブが待機中・実行中なら、新規登録せず既存のジョブを返す。
+    pub async fn enqueue(&self, job: Job) -> AppResult<Job> {
+        if let JobPayload::Transcription { recording_id, .. } = &job.payload {
+            if let Some(existing) = self.active_transcription_job(recording_id).await? {
+                log::info!("⏳ Transcription for {} already queued as job {}", recording_id, existing.id);
+                return Ok(existing);
+            }
+        }
+
+        self.db.create_job(&job).await?;
+        log::info!("📥 Enqueued {} job {} (priority {})", job.payload.kind(), job.id, job.priority);
+        self.notify.notify_one();
+        Ok(job)
+    }
+
+    pub async fn list_jobs(&self, status: Option<JobStatus>) -> AppResult<Vec<Job>> {
+        self.db.list_jobs(status.as_ref()).await
+    }
+
+    pub async fn get_job(&self, id: &str) -> AppResult<Option<Job>> {
+        self.db.get_job(id).await
+    }
+
+    /// 失敗・キャンセル済みのジョブを再実行
+    pub async fn retry_job(&self, id: &str) -> AppResult<Job> {
+        let mut job = self.require_job(id).await?;
+
+        if !matches!(job.status, JobStatus::Failed | JobStatus::Cancelled) {
+            return Err(AppError::InvalidOperation {
+                message: format!("Job {} cannot be retried while {}", id, job.status.as_str()),
+            });
+        }
+
+        job.status = JobStatus::Pending;
+        job.attempts = 0;
+        job.last_error = None;
+        job.run_after = Utc::now();
+        self.db.update_job(&job).await?;
+        self.notify.notify_one();
+
+        log::info!("🔁 Retrying job {}", id);
+        Ok(job)
+    }
+
+    /// 待機中または実行中のジョブをキャンセル
+    pub async fn cancel_job(&self, id: &str) -> AppResult<Job> {
+        let mut job = self.require_job(id).await?;
+
+        if !matches!(job.status, JobStatus::Pending | JobStatus::Running) {
+            return Err(AppError::InvalidOperation {
+                message: format!("Job {} is already {}", id, job.status.as_str()),
+            });
+        }
+
+        if let Some(handle) = self.running.lock().await.remove(id) {
+            handle.abort();
+        }
+
+        job.status = JobStatus::Cancelled;
+        self.db.update_job(&job).await?;
+
+        log::info!("🚫 Cancelled job {}", id);
+        Ok(job)
+    }
+
+    async fn active_transcription_job(&self, recording_id: &str) -> AppResult<Option<Job>> {
+        for status in [JobStatus::Running, JobStatus::Pending] {
+            let existing = self.db.list_jobs(Some(&status)).await?.into_iter().find(|job| {
+                matches!(&job.payload, JobPayload::Transcription { recording_id: id, .. } if id == recording_id)
+            });
+            if existing.is_some() {
+                return Ok(existing);
+            }
+        }
+        Ok(None)
+    }
+
+    async fn require_job(&self, id: &str) -> AppResult<Job> {
+        self.db.get_job(id).await?.ok_or_else(|| AppError::ValidationError {
+            message: format!("Job with id {} not found", id),
+        })
+    }
+
+    async fn worker_loop(self: Arc<Self>, worker_id: usize) {
+        loop {
+            let job = match self.db.claim_next_job().await {
+                Ok(Some(job)) => job,
+                Ok(None) => {
+                    let _ = tokio::time::timeout(IDLE_POLL_INTERVAL, self.notify.notified()).await;
+                    continue;
+                }
+                Err(e) => {
+                    log::error!("❌ Worker {} failed to fetch job: {}", worker_id, e);
+                    tokio::time::sleep(IDLE_POLL_INTERVAL).await;
+                    continue;
+                }
+            };
+
+            log::info!("▶️ Worker {} running {} job {} (attempt {})", worker_id, job.payload.kind(), job.id, job.attempts);
+            self.run_job(job).await;
+        }
+    }
+
+    async fn run_job(&self, mut job: Job) {
+        let task = {
+            let db = self.db.clone();
+            let whisper_service = self.whisper_service.clone();
+            let summarizer = self.summarizer.clone();
+            let payload = job.payload.clone();
+            tokio::spawn(async move { execute(&db, &whisper_service, &summarizer, payload).await })
+        };
+        self.running.lock().await.insert(job.id.clone(), task.abort_handle());
+
+        let outcome = task.await;
+        self.running.lock().await.remove(&job.id);
+
+        match outcome {
+            // cancel_job で中断された場合はステータス更新済み
+            Err(e) if e.is_cancelled() => return,
+            Ok(Ok(result)) => {
+                job.status = JobStatus::Completed;
+                job.result = Some(result);
+                job.last_error = None;
+                log::info!("✅ Job {} completed", job.id);
+            }
+            Ok(Err(e)) => self.record_failure(&mut job, e.to_string()),
+            Err(e) => self.record_failure(&mut job, format!("Job panicked: {}", e)),
+        }
+
+        // 実行中にキャンセルされていた場合は上書きしない
+        if let Ok(Some(current)) = self.db.get_job(&job.id).await {
+            if current.status == JobStatus::Cancelled {
+                return;
+            }
+        }
+
+        if let Err(e) = self.db.update_job(&job).await {
+            log::error!("❌ Failed to update job {}: {}", job.id, e);
+        }
+    }
+
+    fn record_failure(&self, job: &mut Job, error: String) {
+        job.last_error = Some(error.clone());
+
+        if job.attempts < job.max_attempts {
+            let backoff = RETRY_BACKOFF_BASE_SECS * 2_i64.pow((job.attempts - 1).max(0) as u32);
+            job.status = JobStatus::Pending;
+            job.run_after = Utc::now() + Duration::seconds(backoff);
+            log::warn!("⚠️ Job {} failed (attempt {}/{}), retrying in {}s: {}", job.id, job.attempts, job.max_attempts, backoff, error);
+        } else {
+            job.status = JobStatus::Failed;
+            log::error!("❌ Job {} failed permanently: {}", job.id, error);
+        }
+    }
+}
+
+/// ジョブ種別ごとの処理を実行し、結果をJSONで返す
+async fn execute(
+    db: &Database,
+    whisper_service: &WhisperService,
+    summarizer: &Summarizer,
+    payload: JobPayload,
+) -> AppResult<serde_json::Value> {
+    match payload {
+        JobPayload::Transcription { recording_id, language } => {
+            let transcription = transcribe(db, whisper_service, &recording_id, language).await?;
+            Ok(json!({ "transcription_id": transcription.id }))
+        }
+        JobPayload::Summarization { transcription_id, model_config } => {
+            let summary_id = summarize(db, summarizer, &transcription_id, model_config).await?;
+            Ok(json!({ "summary_id": summary_id }))
+        }
+        JobPayload::ModelDownload { model_name } => {
+            whisper_service.download_specific_model(&model_name).await?;
+            Ok(json!({ "model_name": model_name }))
+        }
+        JobPayload::Export { recording_id, format, output_path } => {
+            let content = export::export_recording(db, &recording_id, &format).await?;
+            tokio::fs::write(&output_path, content).await?;
+            Ok(json!({ "output_path": output_path }))
+        }
+        JobPayload::TranscribeAndSummarize { recording_id, language, model_config } => {
+            // 要約だけ失敗してリトライした場合は、既存の書き起こしを使い回す
+            let existing = db
+                .get_transcriptions_by_recording(&recording_id)
+                .await?
+                .into_iter()
+                .find(|transcription| matches!(transcription.status, TranscriptionStatus::Completed));
+            let transcription = match existing {
+                Some(transcription) => transcription,
+                None => transcribe(db, whisper_service, &recording_id, language).await?,
+            };
+            let summary_id = summarize(db, summarizer, &transcription.id, model_config).await?;
+            Ok(json!({ "transcription_id": transcription.id, "summary_id": summary_id }))
+        }
+        JobPayload::ChannelTranscription { recording_id, language, speakers, summarize: should_summarize, model_config } => {
+            // 要約だけ失敗してリトライした場合は、既存の書き起こしを使い回す
+            let existing = db
+                .get_transcriptions_by_recording(&recording_id)
+                .await?
+                .into_iter()
+                .find(|transcription| matches!(transcription.status, TranscriptionStatus::Completed));
+            let transcription = match existing {
+                Some(transcription) => transcription,
+                None => channel_separation::transcribe_by_channel(db, whisper_service, &recording_id, language, &speakers).await?,
+            };
+            if !should_summarize {
+                return Ok(json!({ "transcription_id": transcription.id }));
+            }
+            let summary_id = summarize(db, summarizer, &transcription.id, model_config).await?;
+            Ok(json!({ "transcription_id": transcription.id, "summary_id": summary_id }))
+        }
+    }
+}
+
+async fn transcribe(
+    db: &Database,
+    whisper_service: &WhisperService,
+    recording_id: &str,
+    language: Option<String>,
+) -> AppResult<Transcription> {
+    let recording = db.get_recording(recording_id).await?.ok_or_else(|| AppError::ValidationError {
+        message: format!("Recording with id {} not found", recording_id),
+    })?;
+
+    if !whisper_service.is_initialized().await {
+        whisper_service.initialize().await?;
+    }
+
+    let language = transcription_language::resolve_language(db, &recording, language).await?;
+    let audio_files = recording_segments::audio_files(db, &recording).await?;
+    let mut transcription = whisper_service
+        .transcribe_audio_files(&audio_files, recording.id.clone(), language)
+        .await?;
+    corrections::apply_corrections(db, &mut transcription).await?;
+    db.create_transcription(&transcription).await?;
+
+    Ok(transcription)
+}
+
+async fn summarize(
+    db: &Database,
+    summarizer: &Summarizer,
+    transcription_id: &str,
+    model_config: Option<LLMConfig>,
+) -> AppResult<String> {
+    let transcription = db.get_transcription(transcription_id).await?.ok_or_else(|| AppError::ValidationError {
+        message: format!("Transcription with id {} not found", transcription_id),
+    })?;
+
+    // 画面から要約した場合と同じく、会議中のメモとマーカー付近の発言も渡す
+    let summary = summarizer
+        .summarize(&transcription.id, &transcription.text, model_config, None)
+        .await?;
+
+    if let crate::models::SummaryStatus::Failed(error) = &summary.status {
+        return Err(AppError::LLMError { message: error.clone() });
+    }
+    db.create_summary(&summary).await?;
+
+    Ok(summary.id)
+}
@@ -0,0 +1,141 @@
+//! キーワード監視
+//!
+//! 登録した監視ワード（「予算」「訴訟」、顧客名など）が新しい書き起こしに現れたら、録音にタグを付けて
+//! 検出記録を残し、"keyword-alert" イベントで通知する。多数の会議を軽く見張る用途を想定している。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{KeywordAlert, Transcription, WatchWord};
+use chrono::Utc;
+use uuid::Uuid;
+
+pub const KEYWORD_ALERT_EVENT: &str = "keyword-alert";
+
+/// スニペットとして一致箇所の前後に含める文字数
+const SNIPPET_CONTEXT_CHARS: usize = 30;
+
+/// テキスト中の一致箇所の文字位置（大文字小文字は区別しない。英数字のみの語は単語単位で一致）
+pub fn keyword_positions(text: &str, word: &str) -> Vec<usize> {
+    let needle: Vec<char> = word.trim().chars().map(fold_case).collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let whole_word = needle.iter().all(|c| c.is_ascii_alphanumeric() || *c == ' ');
+    let chars: Vec<char> = text.chars().collect();
+    let is_word_char = |index: usize| chars.get(index).is_some_and(|c| c.is_ascii_alphanumeric());
+
+    let mut positions = Vec::new();
+    let mut index = 0;
+    while index + needle.len() <= chars.len() {
+        let end = index + needle.len();
+        let matched = chars[index..end].iter().zip(&needle).all(|(&c, &n)| fold_case(c) == n)
+            && !(whole_word && ((index > 0 && is_word_char(index - 1)) || is_word_char(end)));
+        if matched {
+            positions.push(index);
+            index = end;
+        } else {
+            index += 1;
+        }
+    }
+    positions
+}
+
+/// テキスト中の一致回数と最初の一致箇所の前後
+pub fn find_keyword(text: &str, word: &str) -> Option<(i64, String)> {
+    let needle_len = word.trim().chars().count();
+    let chars: Vec<char> = text.chars().collect();
+    let positions = keyword_positions(text, word);
+    let occurrences = positions.len() as i64;
+
+    positions.first().map(|&start| {
+        let from = start.saturating_sub(SNIPPET_CONTEXT_CHARS);
+        let to = (start + needle_len + SNIPPET_CONTEXT_CHARS).min(chars.len());
+        let mut snippet: String = chars[from..to].iter().collect::<String>().trim().to_string();
+        if from > 0 {
+            snippet.insert(0, '…');
+        }
+        if to < chars.len() {
+            snippet.push('…');
+        }
+        (occurrences, snippet)
+    })
+}
+
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// 書き起こしを有効な監視ワードで走査し、新しく検出したものを保存して録音にタグを付ける
+pub async fn scan_transcription(database: &Database, transcription: &Transcription) -> AppResult<Vec<KeywordAlert>> {
+    if transcription.text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let watch_words: Vec<WatchWord> = database
+        .get_watch_words()
+        .await?
+        .into_iter()
+        .filter(|watch_word| watch_word.enabled)
+        .collect();
+    if watch_words.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut alerts = Vec::new();
+    let mut tags = Vec::new();
+    for watch_word in &watch_words {
+        if let Some((occurrences, snippet)) = find_keyword(&transcription.text, &watch_word.word) {
+            alerts.push(KeywordAlert {
+                id: Uuid::new_v4().to_string(),
+                watch_word_id: watch_word.id.clone(),
+                word: watch_word.word.clone(),
+                recording_id: transcription.recording_id.clone(),
+                transcription_id: transcription.id.clone(),
+                occurrences,
+                snippet,
+                acknowledged: false,
+                created_at: Utc::now(),
+            });
+            tags.push(watch_word.recording_tag());
+        }
+    }
+    if alerts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let inserted = database.create_keyword_alerts(&alerts).await?;
+    database.add_recording_tags(&transcription.recording_id, &tags).await?;
+
+    if !inserted.is_empty() {
+        log::info!(
+            "🔔 Watch words found in recording {}: {}",
+            transcription.recording_id,
+            inserted.iter().map(|alert| alert.word.as_str()).collect::<Vec<_>>().join(", ")
+        );
+    }
+    Ok(inserted)
+}
+
+/// 録音の既存の書き起こしを再走査する（監視ワードを追加した後など）
+pub async fn rescan_recording(database: &Database, recording_id: &str) -> AppResult<Vec<KeywordAlert>> {
+    database
+        .get_recording(recording_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording with id {} not found", recording_id),
+        })?;
+
+    let mut alerts = Vec::new();
+    for transcription in database.get_transcriptions_by_recording(recording_id).await? {
+        alerts.extend(scan_transcription(database, &transcription).await?);
+    }
+    Ok(alerts)
+}
+
+pub fn validate_watch_word(word: &str) -> AppResult<()> {
+    if word.trim().is_empty() {
+        return Err(AppError::ValidationError {
+            message: "Watch word must not be empty".to_string(),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,157 @@
+//! キーワードの出現タイムライン
+//!
+//! 書き起こしのセグメントからキーワードが出現した時刻を求め、録音の長さを等分した区間ごとの出現数
+//! （密度）とあわせて返す。UI はこれを使って、話題がいつ議論されたかをタイムライン表示する。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Recording, TranscriptSegment, TranscriptionStatus};
+use crate::services::keyword_alerts::keyword_positions;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 密度を求める区間数の既定値
+pub const DEFAULT_TIMELINE_BUCKETS: usize = 50;
+const MAX_TIMELINE_BUCKETS: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeywordOccurrence {
+    /// 録音開始からの時刻（セグメント内の文字位置から按分）
+    pub offset_ms: i64,
+    pub segment_index: i32,
+    pub speaker: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeywordTimeline {
+    pub recording_id: String,
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub duration_ms: i64,
+    pub occurrences: Vec<KeywordOccurrence>,
+    /// 録音を等分した区間ごとの出現数
+    pub density: Vec<u32>,
+}
+
+/// セグメント中のキーワードの出現時刻
+pub fn occurrences_in_segments(segments: &[TranscriptSegment], keyword: &str) -> Vec<KeywordOccurrence> {
+    let mut occurrences: Vec<KeywordOccurrence> = segments
+        .iter()
+        .flat_map(|segment| {
+            let length = segment.text.chars().count().max(1) as i64;
+            let span = (segment.end_ms - segment.start_ms).max(0);
+            keyword_positions(&segment.text, keyword)
+                .into_iter()
+                .map(move |position| KeywordOccurrence {
+                    offset_ms: segment.start_ms + span * position as i64 / length,
+                    segment_index: segment.segment_index,
+                    speaker: segment.speaker.clone(),
+                })
+        })
+        .collect();
+    occurrences.sort_by_key(|occurrence| occurrence.offset_ms);
+    occurrences
+}
+
+/// 区間ごとの出現数
+pub fn density(occurrences: &[KeywordOccurrence], duration_ms: i64, buckets: usize) -> Vec<u32> {
+    let buckets = buckets.clamp(1, MAX_TIMELINE_BUCKETS);
+    let mut counts = vec![0; buckets];
+    if duration_ms <= 0 {
+        return counts;
+    }
+    for occurrence in occurrences {
+        let index = (occurrence.offset_ms.max(0) as i128 * buckets as i128 / duration_ms as i128) as usize;
+        counts[index.min(buckets - 1)] += 1;
+    }
+    counts
+}
+
+async fn timeline_for(
+    database: &Database,
+    recording: &Recording,
+    keyword: &str,
+    buckets: usize,
+) -> AppResult<Option<KeywordTimeline>> {
+    // 最新の書き起こしを使う（失敗したものは除く）
+    let transcription = database
+        .get_transcriptions_by_recording(&recording.id)
+        .await?
+        .into_iter()
+        .find(|transcription| !matches!(transcription.status, TranscriptionStatus::Failed(_)));
+    let Some(transcription) = transcription else {
+        return Ok(None);
+    };
+
+    let segments = database.get_segments_by_transcription(&transcription.id).await?;
+    let occurrences = occurrences_in_segments(&segments, keyword);
+    let duration_ms = recording
+        .duration
+        .map(|secs| secs * 1_000)
+        .unwrap_or_default()
+        .max(segments.iter().map(|segment| segment.end_ms).max().unwrap_or_default());
+
+    Ok(Some(KeywordTimeline {
+        recording_id: recording.id.clone(),
+        title: recording.title.clone(),
+        created_at: recording.created_at,
+        duration_ms,
+        density: density(&occurrences, duration_ms, buckets),
+        occurrences,
+    }))
+}
+
+fn validate_keyword(keyword: &str) -> AppResult<()> {
+    if keyword.trim().is_empty() {
+        return Err(AppError::ValidationError {
+            message: "Keyword must not be empty".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// 1つの録音でのキーワードのタイムライン（書き起こしが無ければ None）
+pub async fn recording_timeline(
+    database: &Database,
+    recording_id: &str,
+    keyword: &str,
+    buckets: usize,
+) -> AppResult<Option<KeywordTimeline>> {
+    validate_keyword(keyword)?;
+    let recording = database
+        .get_recording(recording_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording with id {} not found", recording_id),
+        })?;
+    timeline_for(database, &recording, keyword, buckets).await
+}
+
+/// 期間内の録音のうちキーワードが出現したもののタイムライン（古い順）
+pub async fn timelines_in_range(
+    database: &Database,
+    keyword: &str,
+    date_from: Option<DateTime<Utc>>,
+    date_to: Option<DateTime<Utc>>,
+    buckets: usize,
+) -> AppResult<Vec<KeywordTimeline>> {
+    validate_keyword(keyword)?;
+    let mut recordings: Vec<Recording> = database
+        .get_all_recordings()
+        .await?
+        .into_iter()
+        .filter(|recording| date_from.is_none_or(|from| recording.created_at >= from))
+        .filter(|recording| date_to.is_none_or(|to| recording.created_at <= to))
+        .collect();
+    recordings.sort_by_key(|recording| recording.created_at);
+
+    let mut timelines = Vec::new();
+    for recording in &recordings {
+        if let Some(timeline) = timeline_for(database, recording, keyword, buckets).await? {
+            if !timeline.occurrences.is_empty() {
+                timelines.push(timeline);
+            }
+        }
+    }
+    Ok(timelines)
+}
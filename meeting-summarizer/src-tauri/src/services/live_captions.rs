@@ -0,0 +1,277 @@
+//! ライブ字幕イベント
+//!
+//! ライブ書き起こしの仮説（セグメント単位、確定前は何度でも更新される）を字幕サイズの
+//! チャンクに分割し、専用イベント `live-caption` で配信する。チャンクIDは
+//! 「セッション・セグメント番号・チャンク番号」から決まるため、書き起こしが訂正されると
+//! 同じIDのイベント（revision が増加）が届き、フローティング字幕ウィンドウは表示中の
+//! テキストを置き換えるだけでよい。
+
+use crate::errors::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+pub const LIVE_CAPTION_EVENT: &str = "live-caption";
+
+/// 1チャンクの最大文字数（字幕2行分程度）
+pub const DEFAULT_MAX_CAPTION_CHARS: usize = 64;
+
+const CHANNEL_CAPACITY: usize = 256;
+
+/// ライブ書き起こしから届くセグメントの仮説
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptionHypothesis {
+    /// セッション内で一意なセグメント番号（訂正時も同じ番号で送る）
+    pub segment_index: u64,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+    pub is_final: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptionEvent {
+    pub session_id: String,
+    /// 訂正後も変わらないチャンクID
+    pub caption_id: String,
+    pub segment_index: u64,
+    pub revision: u32,
+    pub text: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub is_final: bool,
+    /// 訂正でチャンク数が減った場合、不要になったチャンクは removed として通知する
+    pub removed: bool,
+}
+
+#[derive(Default)]
+struct SegmentState {
+    revision: u32,
+    chunks: Vec<CaptionEvent>,
+}
+
+/// 1回のライブ書き起こしに対応する字幕の状態
+pub struct LiveCaptionSession {
+    session_id: String,
+    max_chars: usize,
+    segments: BTreeMap<u64, SegmentState>,
+}
+
+impl LiveCaptionSession {
+    pub fn new(session_id: String) -> Self {
+        Self {
+            session_id,
+            max_chars: DEFAULT_MAX_CAPTION_CHARS,
+            segments: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars.max(1);
+        self
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// 仮説を反映し、変化したチャンクのイベントを返す
+    pub fn apply(&mut self, hypothesis: &CaptionHypothesis) -> Vec<CaptionEvent> {
+        let texts = split_caption_text(&hypothesis.text, self.max_chars);
+        let timings = distribute_timings(&texts, hypothesis.start_ms, hypothesis.end_ms);
+        let state = self.segments.entry(hypothesis.segment_index).or_default();
+        let revision = state.revision + 1;
+
+        let mut events = Vec::new();
+        let mut chunks = Vec::with_capacity(texts.len());
+        for (index, (text, (start_ms, end_ms))) in texts.into_iter().zip(timings).enumerate() {
+            let chunk = CaptionEvent {
+                session_id: self.session_id.clone(),
+                caption_id: format!("{}-{}-{}", self.session_id, hypothesis.segment_index, index),
+                segment_index: hypothesis.segment_index,
+                revision,
+                text,
+                start_ms,
+                end_ms,
+                is_final: hypothesis.is_final,
+                removed: false,
+            };
+
+            let unchanged = state.chunks.get(index).is_some_and(|previous| {
+                previous.text == chunk.text
+                    && previous.start_ms == chunk.start_ms
+                    && previous.end_ms == chunk.end_ms
+                    && previous.is_final == chunk.is_final
+            });
+            if unchanged {
+                chunks.push(state.chunks[index].clone());
+            } else {
+                events.push(chunk.clone());
+                chunks.push(chunk);
+            }
+        }
+
+        for stale in state.chunks.iter().skip(chunks.len()) {
+            events.push(CaptionEvent {
+                revision,
+                removed: true,
+                ..stale.clone()
+            });
+        }
+
+        if !events.is_empty() {
+            state.revision = revision;
+        }
+        state.chunks = chunks;
+        events
+    }
+
+    /// 現在表示すべき字幕（新しく開いたウィンドウの初期表示用）
+    pub fn captions(&self) -> Vec<CaptionEvent> {
+        self.segments
+            .values()
+            .flat_map(|state| state.chunks.iter().cloned())
+            .collect()
+    }
+}
+
+/// ライブ字幕の配信ハブ。購読者（Tauriイベント転送など）へブロードキャストする
+pub struct LiveCaptionHub {
+    session: RwLock<Option<LiveCaptionSession>>,
+    sender: broadcast::Sender<CaptionEvent>,
+}
+
+impl Default for LiveCaptionHub {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            session: RwLock::new(None),
+            sender,
+        }
+    }
+}
+
+impl LiveCaptionHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<CaptionEvent> {
+        self.sender.subscribe()
+    }
+
+    /// 新しいセッションを開始してIDを返す（前のセッションは破棄）
+    pub fn start_session(&self) -> String {
+        let session_id = Uuid::new_v4().to_string();
+        if let Ok(mut session) = self.session.write() {
+            *session = Some(LiveCaptionSession::new(session_id.clone()));
+        }
+        log::info!("💬 Live caption session started: {}", session_id);
+        session_id
+    }
+
+    pub fn end_session(&self) -> Option<String> {
+        let ended = self.session.write().ok()?.take()?;
+        log::info!("💬 Live caption session ended: {}", ended.session_id());
+        Some(ended.session_id)
+    }
+
+    pub fn current_session_id(&self) -> Option<String> {
+        self.session
+            .read()
+            .ok()?
+            .as_ref()
+            .map(|session| session.session_id.clone())
+    }
+
+    /// 仮説を反映して変化分を配信する
+    pub fn publish(&self, hypothesis: &CaptionHypothesis) -> AppResult<Vec<CaptionEvent>> {
+        let events = {
+            let mut session = self.session.write().map_err(|_| AppError::InvalidOperation {
+                message: "Live caption state is poisoned".to_string(),
+            })?;
+            let session = session.as_mut().ok_or_else(|| AppError::InvalidOperation {
+                message: "No live caption session is active".to_string(),
+            })?;
+            session.apply(hypothesis)
+        };
+
+        for event in &events {
+            // 購読者がいない場合の送信エラーは無視する
+            let _ = self.sender.send(event.clone());
+        }
+        Ok(events)
+    }
+
+    pub fn captions(&self) -> Vec<CaptionEvent> {
+        self.session
+            .read()
+            .ok()
+            .and_then(|session| session.as_ref().map(LiveCaptionSession::captions))
+            .unwrap_or_default()
+    }
+}
+
+/// 字幕サイズに分割する。句読点・空白で区切れる位置を優先し、無ければ文字数で切る
+pub fn split_caption_text(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let chars: Vec<char> = normalized.chars().collect();
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let remaining = chars.len() - start;
+        let cut = if remaining <= max_chars {
+            remaining
+        } else {
+            chars[start..start + max_chars]
+                .iter()
+                .rposition(|c| is_break_char(*c))
+                .map(|position| position + 1)
+                // 極端に短いチャンクになる区切りは使わない
+                .filter(|&position| position > max_chars / 3)
+                .unwrap_or(max_chars)
+        };
+
+        let chunk: String = chars[start..start + cut].iter().collect();
+        let chunk = chunk.trim();
+        if !chunk.is_empty() {
+            chunks.push(chunk.to_string());
+        }
+        start += cut;
+    }
+    chunks
+}
+
+fn is_break_char(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '、' | '。' | '，' | '．' | '！' | '？' | ',' | '.' | '!' | '?' | ';' | ':')
+}
+
+/// セグメントの時間範囲を文字数に比例して各チャンクへ割り当てる
+fn distribute_timings(chunks: &[String], start_ms: i64, end_ms: i64) -> Vec<(i64, i64)> {
+    let end_ms = end_ms.max(start_ms);
+    let total_chars: usize = chunks.iter().map(|chunk| chunk.chars().count()).sum();
+    if total_chars == 0 {
+        return vec![(start_ms, end_ms); chunks.len()];
+    }
+
+    let duration = (end_ms - start_ms) as f64;
+    let mut consumed = 0usize;
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(index, chunk)| {
+            let chunk_start = start_ms + (duration * consumed as f64 / total_chars as f64).round() as i64;
+            consumed += chunk.chars().count();
+            let chunk_end = if index + 1 == chunks.len() {
+                end_ms
+            } else {
+                start_ms + (duration * consumed as f64 / total_chars as f64).round() as i64
+            };
+            (chunk_start, chunk_end)
+        })
+        .collect()
+}
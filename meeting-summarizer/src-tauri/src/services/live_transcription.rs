@@ -0,0 +1,516 @@
+//! 録音中のリアルタイム書き起こし
+//!
+//! 録音スレッドが配信する音声のチャンク（`CaptureFeed`）を受け取り、新しく届いたチャンクだけを
+//! 書き起こして途中経過（partial）に継ぎ足して配信する。発言の後に短い無音が続くか、発言が長く
+//! なりすぎたところで発言全体を一度だけ書き起こし直して確定（final）し、次の発言に進む。補正辞書は
+//! 途中経過にも適用するが、置き換え回数は確定した発言の分だけ記録する。whisper.cpp を使う場合は
+//! 読み込んだモデルを使い回し、音声をファイルに書き出さずに書き起こす。
+//!
+//! 確定した発言は録音の停止後に `finish` で録音の書き起こしとして保存する。書き起こしが追いつかず
+//! 飛ばしたチャンクがあった場合は、抜けのある書き起こしを保存せず録音全体を書き起こし直す。ライブ字幕の
+//! セッションが開いていれば、同じ内容を字幕の仮説としても配信する。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Transcription, TranscriptSegment, TranscriptionStatus};
+use crate::services::capture_feed::{AudioChunk, FEED_SAMPLE_RATE};
+use crate::services::live_captions::{CaptionHypothesis, LiveCaptionHub};
+use crate::services::whisper_local::SILENT_TRANSCRIPTION;
+use crate::services::{corrections, recording_segments, transcription_language, WhisperService};
+use hound::{SampleFormat, WavSpec, WavWriter};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+pub const LIVE_TRANSCRIPT_EVENT: &str = "live-transcript";
+pub const LIVE_TRANSCRIPTION_SAVED_EVENT: &str = "live-transcription-saved";
+
+/// 1つの発言として書き起こし直す音声の上限（超えたら確定する）
+const MAX_SEGMENT_MS: i64 = 20_000;
+
+/// 末尾がこの長さだけ無音なら発言の区切りとみなす
+const TRAILING_SILENCE_MS: i64 = 500;
+
+/// これより小さい RMS は無音とみなす
+const SILENCE_RMS: f32 = 0.01;
+
+/// 停止後、最後のチャンクの書き起こしを待つ時間の上限
+const FINISH_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 配信する書き起こしの途中経過（同じ `segment_index` のものは後から届いた方で置き換える）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiveTranscriptSegment {
+    pub session_id: String,
+    pub segment_index: u64,
+    /// 録音の先頭からの位置
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+    pub is_final: bool,
+}
+
+/// 発言の現在の範囲と書き起こす音声
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveWindow {
+    pub segment_index: u64,
+    /// 発言の先頭
+    pub start_ms: i64,
+    pub end_ms: i64,
+    /// 確定前は新しく届いたチャンクだけ、確定時は発言全体
+    pub samples: Vec<f32>,
+    pub is_final: bool,
+}
+
+/// 届いたチャンクを発言ごとにまとめる
+#[derive(Default)]
+pub struct LiveSegmenter {
+    segment_index: u64,
+    start_ms: i64,
+    pending: Vec<f32>,
+}
+
+impl LiveSegmenter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// チャンクを加え、いま書き起こすべき音声を返す
+    pub fn push(&mut self, chunk: &AudioChunk) -> LiveWindow {
+        if self.pending.is_empty() {
+            self.start_ms = chunk.start_ms;
+        }
+        self.pending.extend_from_slice(&chunk.samples);
+
+        let end_ms = self.start_ms + duration_ms(self.pending.len());
+        let is_final =
+            chunk.is_last || end_ms - self.start_ms >= MAX_SEGMENT_MS || ends_with_silence(&chunk.samples);
+        let window = LiveWindow {
+            segment_index: self.segment_index,
+            start_ms: self.start_ms,
+            end_ms,
+            samples: if is_final {
+                std::mem::take(&mut self.pending)
+            } else {
+                chunk.samples.clone()
+            },
+            is_final,
+        };
+        if is_final {
+            self.segment_index += 1;
+        }
+        window
+    }
+}
+
+fn duration_ms(frames: usize) -> i64 {
+    frames as i64 * 1000 / FEED_SAMPLE_RATE as i64
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+fn ends_with_silence(samples: &[f32]) -> bool {
+    let tail = (TRAILING_SILENCE_MS * FEED_SAMPLE_RATE as i64 / 1000) as usize;
+    samples.len() >= tail && rms(&samples[samples.len() - tail..]) < SILENCE_RMS
+}
+
+/// 書き起こす録音
+pub struct LiveSource {
+    /// 録音のセッションID（`RecordingService::current_session_id`）
+    pub recording_session_id: String,
+    /// 録音中の音声のチャンク（`RecordingService::subscribe_audio_chunks`）
+    pub chunks: broadcast::Receiver<AudioChunk>,
+}
+
+#[derive(Default)]
+struct LiveState {
+    session_id: Option<String>,
+    /// 書き起こしている録音のセッションID（別の録音に保存しないよう `finish` で確かめる）
+    recording_session_id: Option<String>,
+    language: Option<String>,
+    model: Option<String>,
+    segments: BTreeMap<u64, LiveTranscriptSegment>,
+    /// 書き起こしが追いつかず飛ばしたチャンクの数
+    skipped_chunks: u64,
+    whisper_service: Option<Arc<WhisperService>>,
+}
+
+/// 録音中の書き起こしを1つだけ実行する
+#[derive(Default)]
+pub struct LiveTranscriber {
+    state: Mutex<LiveState>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl LiveTranscriber {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `source` の録音を書き起こし始めてセッションIDを返す（前のセッションは破棄）
+    ///
+    /// `on_segment` は途中経過・確定のたびに呼ばれる。`captions` にセッションが開いていれば字幕にも流す。
+    pub fn start<F>(
+        self: &Arc<Self>,
+        source: LiveSource,
+        database: Arc<Database>,
+        whisper_service: Arc<WhisperService>,
+        captions: Option<Arc<LiveCaptionHub>>,
+        language: Option<String>,
+        on_segment: F,
+    ) -> String
+    where
+        F: Fn(&LiveTranscriptSegment) + Send + 'static,
+    {
+        self.stop();
+
+        let LiveSource {
+            recording_session_id,
+            mut chunks,
+        } = source;
+        let session_id = Uuid::new_v4().to_string();
+        if let Ok(mut state) = self.state.lock() {
+            *state = LiveState {
+                session_id: Some(session_id.clone()),
+                recording_session_id: Some(recording_session_id),
+                language: language.clone(),
+                model: Some(whisper_service.get_current_model_size()),
+                segments: BTreeMap::new(),
+                skipped_chunks: 0,
+                whisper_service: Some(whisper_service.clone()),
+            };
+        }
+        log::info!("🎧 Live transcription started: {}", session_id);
+
+        let transcriber = Arc::clone(self);
+        let task_session_id = session_id.clone();
+        let handle = tokio::spawn(async move {
+            let mut segmenter = LiveSegmenter::new();
+            // 確定していない発言のこれまでの書き起こし
+            let mut partial_text = String::new();
+            loop {
+                let chunk = match chunks.recv().await {
+                    Ok(chunk) => chunk,
+                    Err(RecvError::Lagged(skipped)) => {
+                        log::warn!("⚠️ Live transcription lagged, skipped {} audio chunks", skipped);
+                        transcriber.record_skipped(&task_session_id, skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                let window = segmenter.push(&chunk);
+                let result = transcribe_window(
+                    &database,
+                    &whisper_service,
+                    &task_session_id,
+                    &window,
+                    language.clone(),
+                )
+                .await;
+                let text = match result {
+                    Ok(Some(text)) if window.is_final => Some(text),
+                    Ok(Some(text)) => {
+                        append_partial(&mut partial_text, &text, language.as_deref());
+                        Some(partial_text.clone())
+                    }
+                    Ok(None) => None,
+                    Err(e) => {
+                        log::warn!("⚠️ Live transcription of chunk {} failed: {}", chunk.index, e);
+                        None
+                    }
+                };
+                if window.is_final {
+                    partial_text.clear();
+                }
+                if let Some(text) = text {
+                    let segment = LiveTranscriptSegment {
+                        session_id: task_session_id.clone(),
+                        segment_index: window.segment_index,
+                        start_ms: window.start_ms,
+                        end_ms: window.end_ms,
+                        text,
+                        is_final: window.is_final,
+                    };
+                    transcriber.record(&segment);
+                    let open_captions = captions.as_ref().filter(|hub| hub.current_session_id().is_some());
+                    if let Some(hub) = open_captions {
+                        let _ = hub.publish(&CaptionHypothesis {
+                            segment_index: segment.segment_index,
+                            start_ms: segment.start_ms,
+                            end_ms: segment.end_ms,
+                            text: segment.text.clone(),
+                            is_final: segment.is_final,
+                        });
+                    }
+                    on_segment(&segment);
+                }
+                if chunk.is_last {
+                    break;
+                }
+            }
+            log::info!("🎧 Live transcription reached the end of the recording: {}", task_session_id);
+        });
+        if let Ok(mut task) = self.task.lock() {
+            *task = Some(handle);
+        }
+        session_id
+    }
+
+    fn record(&self, segment: &LiveTranscriptSegment) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.session_id.as_deref() == Some(segment.session_id.as_str()) {
+            state.segments.insert(segment.segment_index, segment.clone());
+        }
+    }
+
+    fn record_skipped(&self, session_id: &str, skipped: u64) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.session_id.as_deref() == Some(session_id) {
+            state.skipped_chunks += skipped;
+        }
+    }
+
+    /// 保存せずに止める
+    pub fn stop(&self) -> bool {
+        let handle = self.task.lock().ok().and_then(|mut task| task.take());
+        if let Ok(mut state) = self.state.lock() {
+            *state = LiveState::default();
+        }
+        match handle {
+            Some(handle) => {
+                handle.abort();
+                log::info!("🎧 Live transcription stopped");
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn session_id(&self) -> Option<String> {
+        self.state.lock().ok()?.session_id.clone()
+    }
+
+    /// これまでの書き起こし（途中参加・リロード時の表示用）
+    pub fn segments(&self) -> Vec<LiveTranscriptSegment> {
+        self.state
+            .lock()
+            .map(|state| state.segments.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// 録音の停止後に呼び、最後のチャンクまで書き起こしてから確定した発言を録音の書き起こしとして保存する
+    ///
+    /// 録音セッション `recording_session_id` を書き起こしていないか、確定した発言が無ければ何も保存しない。
+    /// 飛ばしたチャンクがあれば、確定した発言の代わりに録音全体を書き起こして保存する。
+    pub async fn finish(
+        &self,
+        database: &Database,
+        recording_session_id: &str,
+        recording_id: &str,
+    ) -> AppResult<Option<Transcription>> {
+        let belongs_to_recording = self
+            .state
+            .lock()
+            .map(|state| state.recording_session_id.as_deref() == Some(recording_session_id))
+            .unwrap_or(false);
+        if !belongs_to_recording {
+            return Ok(None);
+        }
+
+        let handle = self.task.lock().ok().and_then(|mut task| task.take());
+        if let Some(mut handle) = handle {
+            if tokio::time::timeout(FINISH_TIMEOUT, &mut handle).await.is_err() {
+                handle.abort();
+                log::warn!("⚠️ Live transcription did not reach the end of the recording in time");
+            }
+        }
+        let state = match self.state.lock() {
+            Ok(mut state) => std::mem::take(&mut *state),
+            Err(_) => return Ok(None),
+        };
+        if state.session_id.is_none() {
+            return Ok(None);
+        }
+        if state.skipped_chunks > 0 {
+            if let Some(whisper_service) = &state.whisper_service {
+                log::warn!(
+                    "⚠️ Live transcription skipped {} audio chunks, transcribing recording {} again",
+                    state.skipped_chunks,
+                    recording_id
+                );
+                return transcribe_recording(database, whisper_service, recording_id, state.language)
+                    .await
+                    .map(Some);
+            }
+        }
+
+        let finals: Vec<LiveTranscriptSegment> =
+            state.segments.into_values().filter(|segment| segment.is_final).collect();
+        if finals.is_empty() {
+            log::info!("🎧 Live transcription produced no final segments for recording {}", recording_id);
+            return Ok(None);
+        }
+        database
+            .get_recording(recording_id)
+            .await?
+            .ok_or_else(|| AppError::ValidationError {
+                message: format!("Recording with id {} not found", recording_id),
+            })?;
+
+        let text = finals.iter().map(|segment| segment.text.as_str()).collect::<Vec<_>>().join("\n");
+        let transcription = Transcription::new(
+            recording_id.to_string(),
+            text,
+            state.language.unwrap_or_else(|| "ja".to_string()),
+        )
+        .with_model_used(state.model.map(|model| format!("{} (live)", model)))
+        .with_status(TranscriptionStatus::Completed);
+        let segments: Vec<TranscriptSegment> = finals
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                TranscriptSegment::new(
+                    transcription.id.clone(),
+                    index as i32,
+                    segment.start_ms,
+                    segment.end_ms,
+                    segment.text.clone(),
+                )
+            })
+            .collect();
+
+        database.create_transcription(&transcription).await?;
+        database.create_transcript_segments(&segments).await?;
+        log::info!(
+            "✅ Saved live transcription of recording {}: {} segment(s)",
+            recording_id,
+            segments.len()
+        );
+        Ok(Some(transcription))
+    }
+}
+
+/// 抜けのない書き起こしにするため、保存した録音全体を書き起こす
+async fn transcribe_recording(
+    database: &Database,
+    whisper_service: &WhisperService,
+    recording_id: &str,
+    language: Option<String>,
+) -> AppResult<Transcription> {
+    let recording = database
+        .get_recording(recording_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording with id {} not found", recording_id),
+        })?;
+    let language = transcription_language::resolve_language(database, &recording, language).await?;
+    let audio_files = recording_segments::audio_files(database, &recording).await?;
+    let mut transcription = whisper_service
+        .transcribe_audio_files(&audio_files, recording.id.clone(), language)
+        .await?;
+    corrections::apply_corrections(database, &mut transcription).await?;
+    database.create_transcription(&transcription).await?;
+    log::info!("✅ Saved full transcription of recording {} after live transcription lagged", recording_id);
+    Ok(transcription)
+}
+
+/// 音声を書き起こす（無音や認識できなかった場合は None）
+async fn transcribe_window(
+    database: &Database,
+    whisper_service: &WhisperService,
+    session_id: &str,
+    window: &LiveWindow,
+    language: Option<String>,
+) -> AppResult<Option<String>> {
+    if rms(&window.samples) < SILENCE_RMS {
+        return Ok(None);
+    }
+
+    let text = match whisper_service
+        .transcribe_live_samples(window.samples.clone(), language.as_deref())
+        .await?
+    {
+        Some(text) => text,
+        None => transcribe_clip(whisper_service, session_id, window, language).await?,
+    };
+    let text = text.trim();
+    if text.is_empty() || text == SILENT_TRANSCRIPTION {
+        return Ok(None);
+    }
+
+    // 途中経過は確定時に書き起こし直すので、置き換え回数は確定した発言の分だけ記録する
+    let entries = database.get_correction_entries().await?;
+    let (text, hits) = corrections::apply(text, &entries);
+    if window.is_final && !hits.is_empty() {
+        database.record_correction_hits(&hits).await?;
+    }
+    Ok(Some(text))
+}
+
+/// 音声ファイルに書き出して書き起こす（whisper.cpp 以外の場合）
+async fn transcribe_clip(
+    whisper_service: &WhisperService,
+    session_id: &str,
+    window: &LiveWindow,
+    language: Option<String>,
+) -> AppResult<String> {
+    let work_dir = whisper_service.recordings_dir().join("converted");
+    let clip = work_dir.join(format!("live-{}-{:04}.wav", session_id, window.segment_index));
+    let task_clip = clip.clone();
+    let samples = window.samples.clone();
+    tokio::task::spawn_blocking(move || write_clip(&task_clip, &samples))
+        .await
+        .map_err(|e| AppError::AudioConversion {
+            message: format!("Clip task failed: {}", e),
+        })??;
+
+    let result = whisper_service
+        .transcribe_audio_file(&clip, format!("live-{}", session_id), language)
+        .await;
+    let _ = std::fs::remove_file(&clip);
+    Ok(result?.text)
+}
+
+/// 途中経過にチャンクの書き起こしを継ぎ足す（日本語・中国語以外は空白を挟む）
+fn append_partial(partial: &mut String, text: &str, language: Option<&str>) {
+    if !partial.is_empty() && !matches!(language.unwrap_or("ja"), "ja" | "zh") {
+        partial.push(' ');
+    }
+    partial.push_str(text);
+}
+
+fn write_clip(path: &Path, samples: &[f32]) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: FEED_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let clip_error = |e: hound::Error| AppError::AudioConversion {
+        message: format!("Failed to write live clip {:?}: {}", path, e),
+    };
+    let mut writer = WavWriter::create(path, spec).map_err(clip_error)?;
+    for sample in samples {
+        writer
+            .write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+            .map_err(clip_error)?;
+    }
+    writer.finalize().map_err(clip_error)
+}
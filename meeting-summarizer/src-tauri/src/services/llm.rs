@@ -1,10 +1,23 @@
 use crate::errors::{AppError, AppResult};
-use crate::models::{LLMConfig, LLMProvider, Summary, SummaryStatus};
-use reqwest::Client;
+use crate::models::{LLMAuth, LLMConfig, LLMProvider, MeetingNote, Summary};
+use crate::services::{demo_mode, meeting_notes};
+use crate::services::recording_markers::{self, MarkerExcerpt};
+use base64::Engine;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use reqwest::{Client, StatusCode, Url};
 use serde_json::{json, Value};
 use std::time::{Duration, Instant};
 use tokio::time::timeout;
 
+/// 書き起こしと一緒にLLMへ渡す補足情報
+#[derive(Debug, Clone, Default)]
+pub struct SummaryContext {
+    /// 会議中のメモ
+    pub notes: Vec<MeetingNote>,
+    /// 録音中にマーカーを付けた場面の発言
+    pub marker_excerpts: Vec<MarkerExcerpt>,
+}
+
 pub struct LLMService {
     config: LLMConfig,
     client: Client,
@@ -12,15 +25,101 @@ pub struct LLMService {
 
 impl LLMService {
     pub fn new(config: LLMConfig) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_seconds))
-            .build()
-            .expect("Failed to create HTTP client");
+        let client = Self::build_client(&config).unwrap_or_else(|e| {
+            // CA証明書を読めない場合は既定の証明書ストアで検証する（検証自体は省略しない）
+            log::error!("❌ Invalid LLM connection settings, using defaults: {}", e);
+            Client::builder()
+                .timeout(Duration::from_secs(config.timeout_seconds))
+                .build()
+                .expect("Failed to create HTTP client")
+        });
 
         Self { config, client }
     }
 
+    /// 接続設定を検証してから作成する（設定の誤りをエラーとして返す）
+    pub fn try_new(config: LLMConfig) -> AppResult<Self> {
+        Self::validate_connection(&config)?;
+        let client = Self::build_client(&config)?;
+        Ok(Self { config, client })
+    }
+
+    /// base_url と認証・CA証明書の組み合わせを検証する
+    ///
+    /// 認証情報は localhost 以外へ平文の HTTP で送らない。
+    pub fn validate_connection(config: &LLMConfig) -> AppResult<()> {
+        let url = Url::parse(&config.base_url).map_err(|e| AppError::LLMConfigError {
+            message: format!("Invalid base URL {}: {}", config.base_url, e),
+        })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(AppError::LLMConfigError {
+                message: format!("Unsupported URL scheme: {}", url.scheme()),
+            });
+        }
+
+        let is_local = matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+        if url.scheme() == "http" && !is_local {
+            if config.connection.auth.is_some() {
+                return Err(AppError::LLMConfigError {
+                    message: format!("Credentials for {} must be sent over HTTPS", config.base_url),
+                });
+            }
+            if config.connection.ca_cert_path.is_some() {
+                return Err(AppError::LLMConfigError {
+                    message: "A CA certificate requires an https:// base URL".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn build_client(config: &LLMConfig) -> AppResult<Client> {
+        let mut builder = Client::builder().timeout(Duration::from_secs(config.timeout_seconds));
+
+        if let Some(ca_cert_path) = &config.connection.ca_cert_path {
+            let pem = std::fs::read(ca_cert_path).map_err(|e| AppError::LLMConfigError {
+                message: format!("Failed to read CA certificate {}: {}", ca_cert_path, e),
+            })?;
+            let certificate = reqwest::Certificate::from_pem(&pem).map_err(|e| AppError::LLMConfigError {
+                message: format!("Invalid CA certificate {}: {}", ca_cert_path, e),
+            })?;
+            builder = builder.add_root_certificate(certificate);
+        }
+
+        if let Some(auth) = &config.connection.auth {
+            let value = match auth {
+                LLMAuth::Bearer { token } => format!("Bearer {}", token.trim()),
+                LLMAuth::Basic { username, password } => format!(
+                    "Basic {}",
+                    base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, password))
+                ),
+            };
+            let mut value = HeaderValue::from_str(&value).map_err(|_| AppError::LLMConfigError {
+                message: "Authorization header contains invalid characters".to_string(),
+            })?;
+            value.set_sensitive(true);
+            let mut headers = HeaderMap::new();
+            headers.insert(AUTHORIZATION, value);
+            builder = builder.default_headers(headers);
+        }
+
+        builder.build().map_err(|e| AppError::LLMConfigError {
+            message: format!("Failed to create HTTP client: {}", e),
+        })
+    }
+
     pub async fn summarize_text(&self, transcription_text: &str, transcription_id: String) -> AppResult<Summary> {
+        self.summarize_text_with_context(transcription_text, &SummaryContext::default(), transcription_id)
+            .await
+    }
+
+    /// メモやマーカー付近の発言を書き起こしと一緒に渡して要約する（重要なメモは重要ポイントに必ず含める）
+    pub async fn summarize_text_with_context(
+        &self,
+        transcription_text: &str,
+        context: &SummaryContext,
+        transcription_id: String,
+    ) -> AppResult<Summary> {
         let start_time = Instant::now();
         
         log::info!("🤖 Starting LLM summarization with {} model", self.config.model_name);
@@ -30,23 +129,18 @@ impl LLMService {
             .set_processing();
 
         // Generate prompt for Japanese summarization
-        let prompt = self.create_japanese_summary_prompt(transcription_text);
+        let prompt = self.create_japanese_summary_prompt(transcription_text, context);
         
         // Call LLM based on provider
-        let llm_response = match self.config.provider {
-            LLMProvider::Ollama => self.call_ollama(&prompt).await,
-            LLMProvider::OpenAI => self.call_openai_compatible(&prompt).await,
-            LLMProvider::GPT4All => self.call_gpt4all(&prompt).await,
-            LLMProvider::LMStudio => self.call_lmstudio(&prompt).await,
-            LLMProvider::Custom => self.call_custom_api(&prompt).await,
-        };
+        let llm_response = self.generate(&prompt).await;
 
         match llm_response {
             Ok(response_text) => {
                 let processing_time = start_time.elapsed().as_millis() as u64;
                 
                 // Parse structured response
-                let (summary_text, key_points, action_items) = self.parse_summary_response(&response_text);
+                let (summary_text, mut key_points, action_items) = self.parse_summary_response(&response_text);
+                meeting_notes::ensure_important_notes(&mut key_points, &context.notes);
                 
                 summary = summary
                     .with_content(summary_text, key_points, action_items)
@@ -62,7 +156,154 @@ impl LLMService {
         }
     }
 
-    fn create_japanese_summary_prompt(&self, text: &str) -> String {
+    /// 会議中の途中経過要約：前回までの要約に新しい発言を反映した要約を返す
+    pub async fn summarize_incremental(&self, previous_summary: Option<&str>, new_text: &str) -> AppResult<String> {
+        log::info!("🤖 Updating rolling summary with {} model", self.config.model_name);
+
+        let prompt = self.create_rolling_summary_prompt(previous_summary, new_text);
+        let response = self.generate(&prompt).await?;
+        Ok(response.trim().to_string())
+    }
+
+    /// 複数の会議の要約からダイジェストを作る（`partial` は件数が多い場合の途中集約で、箇条書きのみを返す）
+    pub async fn summarize_digest(&self, meetings: &str, partial: bool) -> AppResult<String> {
+        log::info!("🤖 Generating {} digest with {} model", if partial { "partial" } else { "final" }, self.config.model_name);
+
+        let prompt = self.create_digest_prompt(meetings, partial);
+        let response = self.generate(&prompt).await?;
+        Ok(response.trim().to_string())
+    }
+
+    /// 書き起こし中に登場する人名を抽出する（匿名化用）
+    pub async fn extract_person_names(&self, text: &str) -> AppResult<Vec<String>> {
+        log::info!("🤖 Extracting person names with {} model", self.config.model_name);
+
+        let prompt = self.create_person_names_prompt(text);
+        let response = self.generate(&prompt).await?;
+        Ok(Self::parse_name_list(&response))
+    }
+
+    async fn generate(&self, prompt: &str) -> AppResult<String> {
+        match self.config.provider {
+            LLMProvider::Ollama => self.call_ollama(prompt).await,
+            LLMProvider::OpenAI => self.call_openai_compatible(prompt).await,
+            LLMProvider::GPT4All => self.call_gpt4all(prompt).await,
+            LLMProvider::LMStudio => self.call_lmstudio(prompt).await,
+            LLMProvider::Custom => self.call_custom_api(prompt).await,
+            LLMProvider::Demo => Ok(demo_mode::llm_response(prompt)),
+        }
+    }
+
+    fn create_rolling_summary_prompt(&self, previous_summary: Option<&str>, new_text: &str) -> String {
+        format!(
+            r#"以下は進行中の会議の書き起こしです。途中から参加した人が状況を把握できるよう、これまでの要約に新しい発言の内容を反映した最新の要約を日本語で作成してください。
+
+- 5-8個程度の箇条書きで、議論の流れ・決定事項・未解決の論点を含める
+- 前回の要約の内容は必要に応じて短く統合してよい
+- 要約本文のみを出力する
+
+---これまでの要約---
+{previous}
+---新しい発言---
+{text}
+---"#,
+            previous = previous_summary.unwrap_or("（まだありません）"),
+            text = new_text
+        )
+    }
+
+    fn create_digest_prompt(&self, meetings: &str, partial: bool) -> String {
+        let instructions = if partial {
+            r#"以下は複数の会議の要約です。後でさらに他の会議とまとめるため、話題・決定事項を落とさずに日本語の箇条書きで短くまとめてください。
+決定事項には、どの会議で決まったかを括弧書きで含めてください。箇条書きのみを出力してください。"#
+        } else {
+            r#"以下は期間中の複数の会議の要約です。全体を通したダイジェストを、以下の形式で日本語で作成してください：
+
+## 概要
+（期間全体の動きを3-5文で簡潔にまとめてください）
+
+## テーマ
+- （複数の会議にまたがる主な話題を箇条書きで）
+
+## 決定事項
+- （決まったことを箇条書きで。どの会議で決まったか分かれば含める）"#
+        };
+
+        format!(
+            r#"{instructions}
+
+---会議一覧---
+{meetings}
+---"#,
+            instructions = instructions,
+            meetings = meetings
+        )
+    }
+
+    fn create_person_names_prompt(&self, text: &str) -> String {
+        format!(
+            r#"以下のテキストに登場する人名（姓・名・フルネーム・ニックネーム）をすべて抜き出してください。
+
+- 1行に1つずつ、テキスト中の表記のまま出力する
+- 「さん」「様」などの敬称は含めない
+- 会社名・製品名・地名は含めない
+- 人名が無い場合は「なし」とだけ出力する
+
+---テキスト---
+{text}
+---"#,
+            text = text
+        )
+    }
+
+    fn parse_name_list(response: &str) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for line in response.lines() {
+            let name = line
+                .trim()
+                .trim_start_matches("- ")
+                .trim_start_matches("・")
+                .trim()
+                .trim_end_matches("さん")
+                .trim_end_matches("様")
+                .trim();
+            if name.is_empty() || name == "なし" || name.starts_with("---") {
+                continue;
+            }
+            if !names.iter().any(|existing| existing == name) {
+                names.push(name.to_string());
+            }
+        }
+        names
+    }
+
+    fn create_japanese_summary_prompt(&self, text: &str, context: &SummaryContext) -> String {
+        let notes = &context.notes;
+        let mut notes_section = if notes.is_empty() {
+            String::new()
+        } else {
+            format!(
+                r#"
+---参加者のメモ---
+{notes}
+---
+参加者が会議中に入力したメモです。内容を必ず要約に反映し、★の付いたメモは重要ポイントに含めてください。
+"#,
+                notes = meeting_notes::format_notes_for_prompt(notes)
+            )
+        };
+        if !context.marker_excerpts.is_empty() {
+            notes_section.push_str(&format!(
+                r#"
+---マーカー付近の発言---
+{excerpts}
+---
+参加者が録音中にマーカーを付けた場面の発言です。重要ポイントはこれらの場面を優先して抽出してください。
+"#,
+                excerpts = recording_markers::format_excerpts_for_prompt(&context.marker_excerpts)
+            ));
+        }
+
         format!(
             r#"以下は会議や音声から書き起こしたテキストです。このテキストを分析して、以下の形式で日本語で要約してください：
 
@@ -79,9 +320,10 @@ impl LLMService {
 
 ---書き起こしテキスト---
 {text}
----
+---{notes_section}
 上記のテキストを分析して、指定された形式で要約を作成してください。"#,
-            text = text
+            text = text,
+            notes_section = notes_section
         )
     }
 
@@ -257,6 +499,7 @@ impl LLMService {
     pub async fn check_connection(&self) -> AppResult<bool> {
         match self.config.provider {
             LLMProvider::Ollama => self.check_ollama_connection().await,
+            LLMProvider::Demo => Ok(true),
             _ => self.check_generic_connection().await,
         }
     }
@@ -268,7 +511,7 @@ impl LLMService {
             Duration::from_secs(5), // Short timeout for connection check
             self.client.get(&url).send()
         ).await {
-            Ok(Ok(response)) => Ok(response.status().is_success()),
+            Ok(Ok(response)) => Self::connection_status(&url, response.status()),
             _ => Ok(false),
         }
     }
@@ -280,21 +523,47 @@ impl LLMService {
             Duration::from_secs(5),
             self.client.get(&url).send()
         ).await {
-            Ok(Ok(response)) => Ok(response.status().is_success()),
+            Ok(Ok(response)) => Self::connection_status(&url, response.status()),
             _ => Ok(false),
         }
     }
 
+    /// 認証で拒否された場合は「つながらない」と区別できるようエラーにする
+    fn connection_status(url: &str, status: StatusCode) -> AppResult<bool> {
+        if matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) {
+            return Err(AppError::LLMConfigError {
+                message: format!("Authentication rejected by {} ({})", url, status),
+            });
+        }
+        Ok(status.is_success())
+    }
+
+    /// 読み込み済みモデルのメモリ使用量（MB）。Ollama の /api/ps のみ対応し、他のプロバイダーは None
+    pub async fn loaded_model_memory_mb(&self) -> AppResult<Option<u64>> {
+        if !matches!(self.config.provider, LLMProvider::Ollama) {
+            return Ok(None);
+        }
+
+        let url = format!("{}/api/ps", self.config.base_url);
+        let response: Value = self.client.get(&url).send().await?.json().await?;
+        let size = response["models"]
+            .as_array()
+            .and_then(|models| {
+                models.iter().find(|model| {
+                    model["name"].as_str() == Some(self.config.model_name.as_str())
+                        || model["model"].as_str() == Some(self.config.model_name.as_str())
+                })
+            })
+            .and_then(|model| model["size"].as_u64());
+        Ok(size.map(|bytes| bytes / (1024 * 1024)))
+    }
+
     pub fn get_config(&self) -> &LLMConfig {
         &self.config
     }
 
     pub fn update_config(&mut self, new_config: LLMConfig) {
-        self.config = new_config;
-        // Recreate client with new timeout
-        self.client = Client::builder()
-            .timeout(Duration::from_secs(self.config.timeout_seconds))
-            .build()
-            .expect("Failed to recreate HTTP client");
+        // Recreate client with new timeout and connection settings
+        *self = Self::new(new_config);
     }
 }
\ No newline at end of file
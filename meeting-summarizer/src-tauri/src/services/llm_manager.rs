@@ -1,5 +1,5 @@
 use crate::errors::{AppError, AppResult};
-use crate::models::{LLMConfig, LLMProvider};
+use crate::models::{LLMConfig, LLMConnection, LLMProvider};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -329,16 +329,13 @@ impl LLMModelManager {
     }
 
     /// モデルに対応するConfigを生成
-    fn create_config_for_model(&self, model_id: &str) -> AppResult<LLMConfig> {
-        let parts: Vec<&str> = model_id.split(':').collect();
-        if parts.len() != 2 {
+    pub fn create_config_for_model(&self, model_id: &str) -> AppResult<LLMConfig> {
+        // Ollama のモデル名はタグに ':' を含む（"ollama:llama3.2:3b"）ため、最初の ':' でのみ分割する
+        let Some((provider_str, model_name)) = model_id.split_once(':').filter(|(_, name)| !name.is_empty()) else {
             return Err(AppError::LLMConfigError { 
                 message: format!("Invalid model ID format: {}", model_id) 
             });
-        }
-        
-        let provider_str = parts[0];
-        let model_name = parts[1];
+        };
         
         let provider = match provider_str {
             "ollama" => LLMProvider::Ollama,
@@ -365,6 +362,7 @@ impl LLMModelManager {
             temperature: 0.7,
             max_tokens: 2048,
             timeout_seconds: 120,
+            connection: LLMConnection::default(),
         })
     }
 
@@ -460,6 +458,50 @@ impl LLMModelManager {
         }
     }
 
+    /// 要約の処理時間（秒）を推定する。ベンチマーク済みなら実測の速度を使い、無ければモデルサイズから推定する
+    pub fn estimate_processing_time(&self, model_id: &str, text_length: u32) -> f64 {
+        // テキスト長からトークン数を推定（1トークン ≈ 4文字）
+        let estimated_tokens = text_length as f64 / 4.0;
+
+        let benchmarked_speed = self
+            .benchmarks_cache
+            .get(model_id)
+            .and_then(|benchmark| benchmark.inference_speed)
+            .filter(|speed| speed.is_finite() && *speed > 0.0);
+        let tokens_per_second = match benchmarked_speed {
+            Some(speed) => {
+                log::debug!("⏱️ Using benchmarked speed for {}: {:.2} tokens/sec", model_id, speed);
+                speed
+            }
+            None => Self::heuristic_tokens_per_second(model_id),
+        };
+
+        estimated_tokens / tokens_per_second
+    }
+
+    /// モデルサイズに基づく処理速度の推定（ベンチマークが無い場合）
+    fn heuristic_tokens_per_second(model_id: &str) -> f64 {
+        let model_name = model_id.split_once(':').map(|(_, name)| name).unwrap_or("");
+        if model_name.contains("1b") {
+            50.0 // 高速
+        } else if model_name.contains("3b") {
+            30.0 // 中速
+        } else if model_name.contains("7b") {
+            15.0 // 標準
+        } else if model_name.contains("13b") {
+            8.0 // やや低速
+        } else if model_name.contains("70b") {
+            2.0 // 低速
+        } else {
+            20.0 // デフォルト
+        }
+    }
+
+    /// ベンチマーク結果をキャッシュに登録する（保存済みの結果を読み込んだ場合など）
+    pub fn insert_benchmark(&mut self, benchmark: ModelBenchmark) {
+        self.benchmarks_cache.insert(benchmark.model_id.clone(), benchmark);
+    }
+
     /// キャッシュされたモデル情報を取得
     pub fn get_cached_models(&self) -> Vec<&ModelInfo> {
         self.models_cache.values().collect()
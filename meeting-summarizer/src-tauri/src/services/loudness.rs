@@ -0,0 +1,261 @@
+//! 書き起こし用のコピー（16kHz モノラルへの変換と音量の正規化）
+//!
+//! m4a・mp3・48kHz ステレオなどをそのまま Whisper に渡すと、書き起こしのたびに変換が必要になり、
+//! 録音ごとに声の大きさがばらつくと小さい声の会議で認識が落ちる。録音の停止時と取り込み時に
+//! 16kHz モノラル WAV に変換し、正規化が有効なら ITU-R BS.1770 の方法でラウドネス（LUFS）を
+//! 測って目標の大きさにそろえたうえで `<録音ディレクトリ>/normalized/` に保存する。書き起こしは
+//! 元のファイルではなくこのコピーを使う。元の録音はそのまま残す。
+
+use crate::errors::{AppError, AppResult};
+use crate::services::audio_convert::{self, WHISPER_SAMPLE_RATE};
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 書き起こし用のコピーを置くディレクトリ（録音ディレクトリからの相対パス）
+pub const NORMALIZED_DIR: &str = "normalized";
+
+/// 無音に近い録音を大きくしすぎないためのゲインの上限
+const MAX_GAIN_DB: f32 = 30.0;
+
+/// 正規化したあとのピークの上限（-1 dBFS）
+const PEAK_CEILING: f32 = 0.891;
+
+/// ゲーティングに使うブロックの長さと間隔（400ms のブロックを 100ms ずつずらす）
+const BLOCK_STEP_MS: u32 = 100;
+const STEPS_PER_BLOCK: usize = 4;
+
+/// この大きさに満たないブロックは無音として測定から除く
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+/// 全体の大きさからこれだけ小さいブロックも除く（話していない間の背景ノイズ）
+const RELATIVE_GATE_LU: f64 = -10.0;
+
+/// 正規化の結果
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LoudnessSummary {
+    /// 正規化する前のラウドネス（無音なら None）
+    pub input_lufs: Option<f32>,
+    /// かけたゲイン（dB）
+    pub gain_db: f32,
+}
+
+/// 書き起こし用のコピーの保存先
+pub fn normalized_path(recordings_dir: &Path, audio_path: &Path) -> PathBuf {
+    let stem = audio_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    recordings_dir.join(NORMALIZED_DIR).join(format!("{}.wav", stem))
+}
+
+/// 2次の IIR フィルター（K 特性の各段）
+#[derive(Debug, Clone, Copy)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    state: [f64; 2],
+}
+
+impl Biquad {
+    fn process(&mut self, input: f64) -> f64 {
+        let output = self.b[0] * input + self.state[0];
+        self.state[0] = self.b[1] * input - self.a[0] * output + self.state[1];
+        self.state[1] = self.b[2] * input - self.a[1] * output;
+        output
+    }
+}
+
+/// K 特性（高域のシェルフと低域カット）の係数を任意のサンプルレート向けに求める
+fn k_weighting(sample_rate: u32) -> [Biquad; 2] {
+    let rate = sample_rate as f64;
+
+    let k = (std::f64::consts::PI * 1_681.974_450_955_533 / rate).tan();
+    let q = 0.707_175_236_955_419_6;
+    let vh = 10f64.powf(3.999_843_853_973_347 / 20.0);
+    let vb = vh.powf(0.499_666_774_154_541_6);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad {
+        b: [(vh + vb * k / q + k * k) / a0, 2.0 * (k * k - vh) / a0, (vh - vb * k / q + k * k) / a0],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        state: [0.0; 2],
+    };
+
+    let k = (std::f64::consts::PI * 38.135_470_876_024_44 / rate).tan();
+    let q = 0.500_327_037_323_877_3;
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad {
+        b: [1.0, -2.0, 1.0],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        state: [0.0; 2],
+    };
+
+    [shelf, high_pass]
+}
+
+fn to_lufs(mean_square: f64) -> f64 {
+    -0.691 + 10.0 * mean_square.log10()
+}
+
+/// モノラル音声のラウドネスを少しずつ測る
+pub struct LoudnessMeter {
+    filters: [Biquad; 2],
+    step_len: usize,
+    /// 100ms ごとの二乗和
+    steps: Vec<f64>,
+    current: f64,
+    current_len: usize,
+    peak: f32,
+}
+
+impl LoudnessMeter {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            filters: k_weighting(sample_rate),
+            step_len: (sample_rate * BLOCK_STEP_MS / 1000).max(1) as usize,
+            steps: Vec::new(),
+            current: 0.0,
+            current_len: 0,
+            peak: 0.0,
+        }
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        for &sample in samples {
+            self.peak = self.peak.max(sample.abs());
+            let weighted = self
+                .filters
+                .iter_mut()
+                .fold(sample as f64, |value, filter| filter.process(value));
+            self.current += weighted * weighted;
+            self.current_len += 1;
+            if self.current_len == self.step_len {
+                self.steps.push(self.current);
+                self.current = 0.0;
+                self.current_len = 0;
+            }
+        }
+    }
+
+    /// これまでのピーク（絶対値）
+    pub fn peak(&self) -> f32 {
+        self.peak
+    }
+
+    /// ゲーティングしたラウドネス（LUFS）。400ms に満たない・無音の場合は None
+    pub fn integrated(&self) -> Option<f32> {
+        let block_len = (self.step_len * STEPS_PER_BLOCK) as f64;
+        let blocks: Vec<f64> = self
+            .steps
+            .windows(STEPS_PER_BLOCK)
+            .map(|window| window.iter().sum::<f64>() / block_len)
+            .filter(|mean_square| *mean_square > 0.0 && to_lufs(*mean_square) > ABSOLUTE_GATE_LUFS)
+            .collect();
+        if blocks.is_empty() {
+            return None;
+        }
+
+        let relative_gate = to_lufs(blocks.iter().sum::<f64>() / blocks.len() as f64) + RELATIVE_GATE_LU;
+        let gated: Vec<f64> = blocks.into_iter().filter(|block| to_lufs(*block) > relative_gate).collect();
+        if gated.is_empty() {
+            return None;
+        }
+        Some(to_lufs(gated.iter().sum::<f64>() / gated.len() as f64) as f32)
+    }
+}
+
+/// 目標の大きさにそろえるゲイン（倍率）。ピークが上限を超えず、上げすぎないように抑える
+pub fn normalization_gain(input_lufs: Option<f32>, peak: f32, target_lufs: f32) -> f32 {
+    let Some(input_lufs) = input_lufs else {
+        return 1.0;
+    };
+    let gain_db = (target_lufs - input_lufs).min(MAX_GAIN_DB);
+    let mut gain = 10f32.powf(gain_db / 20.0);
+    if peak > 0.0 {
+        gain = gain.min(PEAK_CEILING / peak);
+    }
+    gain
+}
+
+/// `input` を16kHzモノラルに変換し、ラウドネスを `target_lufs` にそろえて（None なら変換だけ）`output` に書き出す
+///
+/// 書き起こしが途中のファイルを読まないよう、書き終えてから `output` へ移す。
+pub fn normalize_for_transcription(input: &Path, output: &Path, target_lufs: Option<f32>) -> AppResult<LoudnessSummary> {
+    let work_dir = output.parent().map(Path::to_path_buf).unwrap_or_default();
+    fs::create_dir_all(&work_dir)?;
+    let name = output
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let converted_name = format!("{}.converted", name);
+    let source = audio_convert::prepare_for_whisper(input, &work_dir, &converted_name)?;
+
+    let result = write_normalized(&source, output, target_lufs);
+    if source != input {
+        let _ = fs::remove_file(&source);
+    }
+    result
+}
+
+fn write_normalized(source: &Path, output: &Path, target_lufs: Option<f32>) -> AppResult<LoudnessSummary> {
+    let (input_lufs, gain) = match target_lufs {
+        Some(target_lufs) => {
+            let mut meter = LoudnessMeter::new(WHISPER_SAMPLE_RATE);
+            for_each_chunk(source, |samples| meter.push(samples))?;
+            let input_lufs = meter.integrated();
+            (input_lufs, normalization_gain(input_lufs, meter.peak(), target_lufs))
+        }
+        None => (None, 1.0),
+    };
+
+    let partial = output.with_extension("wav.part");
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: WHISPER_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(&partial, spec).map_err(|e| normalize_error(&partial, e))?;
+    let mut write_result = Ok(());
+    for_each_chunk(source, |samples| {
+        for sample in samples {
+            if write_result.is_err() {
+                return;
+            }
+            let value = ((sample * gain).clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            write_result = writer.write_sample(value);
+        }
+    })?;
+    write_result.map_err(|e| normalize_error(&partial, e))?;
+    writer.finalize().map_err(|e| normalize_error(&partial, e))?;
+    fs::rename(&partial, output)?;
+
+    Ok(LoudnessSummary {
+        input_lufs,
+        gain_db: 20.0 * gain.log10(),
+    })
+}
+
+/// 16kHzモノラル16bit WAV を少しずつ読み、-1.0〜1.0 のサンプルを渡す
+fn for_each_chunk(path: &Path, mut on_samples: impl FnMut(&[f32])) -> AppResult<()> {
+    const CHUNK: usize = 16_000;
+    let mut reader = WavReader::open(path).map_err(|e| normalize_error(path, e))?;
+    let mut chunk = Vec::with_capacity(CHUNK);
+    for sample in reader.samples::<i16>() {
+        chunk.push(sample.map_err(|e| normalize_error(path, e))? as f32 / 32_768.0);
+        if chunk.len() == CHUNK {
+            on_samples(&chunk);
+            chunk.clear();
+        }
+    }
+    if !chunk.is_empty() {
+        on_samples(&chunk);
+    }
+    Ok(())
+}
+
+fn normalize_error(path: &Path, error: impl std::fmt::Display) -> AppError {
+    AppError::AudioConversion {
+        message: format!("Failed to normalize {:?}: {}", path, error),
+    }
+}
@@ -0,0 +1,375 @@
+//! Zoom / Teams のクラウド録画フォルダ取り込み
+//!
+//! ダウンロードした録画フォルダ（音声 + チャット + メタデータJSON）を解析し、
+//! 1つの録音として参加者・添付ファイルと合わせて登録する。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Attachment, Participant, Recording};
+use crate::services::audio_probe;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MeetingSource {
+    Zoom,
+    Teams,
+}
+
+impl MeetingSource {
+    fn tag(&self) -> &'static str {
+        match self {
+            MeetingSource::Zoom => "zoom",
+            MeetingSource::Teams => "teams",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedMeeting {
+    pub source: MeetingSource,
+    pub recording: Recording,
+    pub participants: Vec<Participant>,
+    pub attachments: Vec<Attachment>,
+}
+
+/// メタデータJSONから読み取った会議情報
+#[derive(Debug, Default)]
+struct MeetingMetadata {
+    title: Option<String>,
+    start_time: Option<DateTime<Utc>>,
+    duration: Option<i64>, // seconds
+    participants: Vec<ParticipantInfo>,
+}
+
+#[derive(Debug)]
+struct ParticipantInfo {
+    name: String,
+    email: Option<String>,
+    join_time: Option<DateTime<Utc>>,
+    leave_time: Option<DateTime<Utc>>,
+}
+
+/// フォルダ内のファイルを種類ごとに分類した結果
+#[derive(Debug, Default)]
+struct FolderContents {
+    audio: Vec<PathBuf>,
+    video: Vec<PathBuf>,
+    chat: Vec<PathBuf>,
+    captions: Vec<PathBuf>,
+    metadata: Vec<(PathBuf, Value)>,
+}
+
+/// 録画フォルダを取り込み、録音・参加者・添付ファイルをデータベースに登録
+pub async fn import_meeting_folder(
+    database: &Database,
+    folder: &Path,
+    recordings_dir: &Path,
+) -> AppResult<ImportedMeeting> {
+    if !folder.is_dir() {
+        return Err(AppError::FileNotFound {
+            path: folder.to_string_lossy().to_string(),
+        });
+    }
+
+    let contents = scan_folder(folder)?;
+    let source = detect_source(folder, &contents).ok_or_else(|| AppError::ValidationError {
+        message: "Folder does not look like a Zoom or Teams recording".to_string(),
+    })?;
+
+    log::info!("📥 Importing {:?} meeting from {:?}", source, folder);
+
+    let audio_source = select_audio_file(&contents).ok_or_else(|| AppError::ValidationError {
+        message: "No audio or video file found in meeting folder".to_string(),
+    })?;
+
+    let metadata = contents
+        .metadata
+        .iter()
+        .map(|(_, value)| match source {
+            MeetingSource::Zoom => parse_zoom_metadata(value),
+            MeetingSource::Teams => parse_teams_metadata(value),
+        })
+        .fold(MeetingMetadata::default(), merge_metadata);
+
+    // 音声ファイルを録音ディレクトリにコピー
+    fs::create_dir_all(recordings_dir)?;
+    let extension = audio_source
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("m4a")
+        .to_lowercase();
+    let mut recording = Recording::new(String::new(), String::new());
+    let filename = format!("import_{}_{}.{}", source.tag(), recording.id, extension);
+    let audio_path = recordings_dir.join(&filename);
+    fs::copy(&audio_source, &audio_path)?;
+
+    recording.filename = filename;
+    recording.file_path = audio_path.to_string_lossy().to_string();
+    recording.file_size = Some(fs::metadata(&audio_path)?.len() as i64);
+    recording.title = metadata.title.clone().or_else(|| {
+        folder
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    });
+    recording.duration = metadata.duration;
+    audio_probe::fill_recording(&mut recording, std::slice::from_ref(&audio_path));
+    recording.tags = vec![source.tag().to_string()];
+    if let Some(start_time) = metadata.start_time {
+        recording.created_at = start_time;
+    }
+
+    database.create_recording(&recording).await?;
+
+    // 参加者を登録
+    let mut participants = Vec::new();
+    for info in metadata.participants {
+        let mut participant = Participant::new(recording.id.clone(), info.name);
+        participant.email = info.email;
+        participant.join_time = info.join_time;
+        participant.leave_time = info.leave_time;
+        database.create_participant(&participant).await?;
+        participants.push(participant);
+    }
+
+    // チャット・字幕・メタデータを添付ファイルとして保存
+    let attachments_dir = recordings_dir.join("attachments").join(&recording.id);
+    let attachment_files = contents
+        .chat
+        .iter()
+        .map(|path| ("chat", path))
+        .chain(contents.captions.iter().map(|path| ("caption", path)))
+        .chain(contents.metadata.iter().map(|(path, _)| ("metadata", path)));
+
+    let mut attachments = Vec::new();
+    for (kind, path) in attachment_files {
+        fs::create_dir_all(&attachments_dir)?;
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let destination = attachments_dir.join(&filename);
+        fs::copy(path, &destination)?;
+
+        let attachment = Attachment::new(
+            recording.id.clone(),
+            kind.to_string(),
+            filename,
+            destination.to_string_lossy().to_string(),
+        );
+        database.create_attachment(&attachment).await?;
+        attachments.push(attachment);
+    }
+
+    log::info!(
+        "✅ Imported meeting {} ({} participants, {} attachments)",
+        recording.id,
+        participants.len(),
+        attachments.len()
+    );
+
+    Ok(ImportedMeeting {
+        source,
+        recording,
+        participants,
+        attachments,
+    })
+}
+
+fn scan_folder(folder: &Path) -> AppResult<FolderContents> {
+    let mut contents = FolderContents::default();
+
+    for entry in fs::read_dir(folder)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .unwrap_or_default();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        match extension.as_str() {
+            "m4a" | "mp3" | "wav" | "ogg" | "flac" => contents.audio.push(path),
+            "mp4" => contents.video.push(path),
+            "vtt" => contents.captions.push(path),
+            "txt" if name.contains("chat") => contents.chat.push(path),
+            "txt" if name.contains("caption") || name.contains("transcript") => contents.captions.push(path),
+            "json" if name.contains("chat") => contents.chat.push(path),
+            "json" => {
+                let value: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+                contents.metadata.push((path, value));
+            }
+            _ => {}
+        }
+    }
+
+    // 取り込み順を安定させる
+    contents.audio.sort();
+    contents.video.sort();
+    contents.chat.sort();
+    contents.captions.sort();
+    contents.metadata.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(contents)
+}
+
+fn detect_source(folder: &Path, contents: &FolderContents) -> Option<MeetingSource> {
+    for (_, value) in &contents.metadata {
+        if value.get("topic").is_some() || value.get("recording_files").is_some() || value.get("uuid").is_some() {
+            return Some(MeetingSource::Zoom);
+        }
+        if value.get("subject").is_some()
+            || value.get("startDateTime").is_some()
+            || value.get("attendanceRecords").is_some()
+        {
+            return Some(MeetingSource::Teams);
+        }
+    }
+
+    // メタデータがない場合はファイル名で判定
+    let names: Vec<String> = contents
+        .audio
+        .iter()
+        .chain(contents.video.iter())
+        .filter_map(|path| path.file_name().map(|name| name.to_string_lossy().to_lowercase()))
+        .collect();
+
+    if names.iter().any(|name| name.starts_with("audio_only") || name.starts_with("gmt") || name.contains("zoom")) {
+        return Some(MeetingSource::Zoom);
+    }
+
+    let folder_name = folder
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if folder_name.contains("teams") || names.iter().any(|name| name.contains("meeting recording") || name.contains("teams")) {
+        return Some(MeetingSource::Teams);
+    }
+
+    None
+}
+
+/// 音声のみのファイルを優先し、なければ動画ファイルを使用する
+fn select_audio_file(contents: &FolderContents) -> Option<PathBuf> {
+    contents
+        .audio
+        .iter()
+        .find(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().to_lowercase().starts_with("audio_only"))
+                .unwrap_or(false)
+        })
+        .or_else(|| contents.audio.first())
+        .or_else(|| contents.video.first())
+        .cloned()
+}
+
+fn merge_metadata(mut acc: MeetingMetadata, next: MeetingMetadata) -> MeetingMetadata {
+    acc.title = acc.title.or(next.title);
+    acc.start_time = acc.start_time.or(next.start_time);
+    acc.duration = acc.duration.or(next.duration);
+    if acc.participants.is_empty() {
+        acc.participants = next.participants;
+    }
+    acc
+}
+
+fn parse_zoom_metadata(value: &Value) -> MeetingMetadata {
+    let participants = value
+        .get("participants")
+        .and_then(|p| p.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|p| {
+                    Some(ParticipantInfo {
+                        name: p.get("name").and_then(|n| n.as_str())?.to_string(),
+                        email: string_field(p, "user_email").or_else(|| string_field(p, "email")),
+                        join_time: datetime_field(p, "join_time"),
+                        leave_time: datetime_field(p, "leave_time"),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    MeetingMetadata {
+        title: string_field(value, "topic"),
+        start_time: datetime_field(value, "start_time"),
+        // Zoomの duration は分単位
+        duration: value.get("duration").and_then(|d| d.as_i64()).map(|minutes| minutes * 60),
+        participants,
+    }
+}
+
+fn parse_teams_metadata(value: &Value) -> MeetingMetadata {
+    let start_time = datetime_field(value, "startDateTime");
+    let end_time = datetime_field(value, "endDateTime");
+
+    let records = value
+        .get("attendanceRecords")
+        .or_else(|| value.get("participants"))
+        .and_then(|p| p.as_array());
+
+    let participants = records
+        .map(|list| {
+            list.iter()
+                .filter_map(|p| {
+                    let name = p
+                        .get("identity")
+                        .and_then(|identity| string_field(identity, "displayName"))
+                        .or_else(|| string_field(p, "displayName"))?;
+                    let interval = p
+                        .get("attendanceIntervals")
+                        .and_then(|i| i.as_array())
+                        .and_then(|i| i.first());
+
+                    Some(ParticipantInfo {
+                        name,
+                        email: string_field(p, "emailAddress").or_else(|| string_field(p, "email")),
+                        join_time: interval
+                            .and_then(|i| datetime_field(i, "joinDateTime"))
+                            .or_else(|| datetime_field(p, "joinDateTime")),
+                        leave_time: interval
+                            .and_then(|i| datetime_field(i, "leaveDateTime"))
+                            .or_else(|| datetime_field(p, "leaveDateTime")),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    MeetingMetadata {
+        title: string_field(value, "subject").or_else(|| string_field(value, "meetingTitle")),
+        start_time,
+        duration: start_time
+            .zip(end_time)
+            .map(|(start, end)| end.signed_duration_since(start).num_seconds()),
+        participants,
+    }
+}
+
+fn string_field(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+}
+
+fn datetime_field(value: &Value, key: &str) -> Option<DateTime<Utc>> {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
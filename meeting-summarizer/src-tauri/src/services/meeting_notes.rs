@@ -0,0 +1,75 @@
+//! 会議中のメモ
+//!
+//! 録音中に入力したメモを録音に紐付けて保存し、要約時に書き起こしと一緒にLLMへ渡す。
+//! 重要と印を付けたメモは、LLMの出力に含まれていなければ重要ポイントに追加して必ず残す。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::MeetingNote;
+
+/// メモ1件の最大文字数
+pub const MAX_NOTE_CHARS: usize = 2_000;
+
+/// プロンプトに埋め込むメモの一覧（重要なメモには ★ を付け、入力者が分かれば末尾に添える）
+pub fn format_notes_for_prompt(notes: &[MeetingNote]) -> String {
+    notes
+        .iter()
+        .map(|note| {
+            let time = note
+                .offset_ms
+                .map(|ms| format!("[{:02}:{:02}] ", ms / 60_000, (ms / 1_000) % 60))
+                .unwrap_or_default();
+            let mark = if note.important { "★ " } else { "" };
+            let author = note
+                .author
+                .as_deref()
+                .map(|author| format!("（{}）", author))
+                .unwrap_or_default();
+            format!("- {}{}{}{}", time, mark, note.text.trim(), author)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 重要なメモのうち重要ポイントに含まれていないものを追加する
+pub fn ensure_important_notes(key_points: &mut Vec<String>, notes: &[MeetingNote]) {
+    let normalize = |text: &str| text.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+
+    for note in notes.iter().filter(|note| note.important) {
+        let text = note.text.trim();
+        let normalized = normalize(text);
+        if normalized.is_empty() {
+            continue;
+        }
+        if !key_points.iter().any(|point| normalize(point).contains(&normalized)) {
+            key_points.push(text.to_string());
+        }
+    }
+}
+
+/// 書き起こしの元になった録音のメモ
+pub async fn notes_for_transcription(database: &Database, transcription_id: &str) -> AppResult<Vec<MeetingNote>> {
+    let transcription = database
+        .get_transcription(transcription_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Transcription with id {} not found", transcription_id),
+        })?;
+    database.get_meeting_notes(&transcription.recording_id).await
+}
+
+/// 空でなく長すぎないメモ本文
+pub fn validate_note_text(text: &str) -> AppResult<String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(AppError::ValidationError {
+            message: "Note must not be empty".to_string(),
+        });
+    }
+    if text.chars().count() > MAX_NOTE_CHARS {
+        return Err(AppError::ValidationError {
+            message: format!("Note is longer than {} characters", MAX_NOTE_CHARS),
+        });
+    }
+    Ok(text.to_string())
+}
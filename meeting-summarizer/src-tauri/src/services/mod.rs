@@ -1,23 +1,138 @@
 // pub mod audio_capture;  // 実際の音声キャプチャ（Send+Sync問題のため一時無効化）
 pub mod audio_capture_mock;
 pub mod audio_capture_cpal;    // CPAL音声キャプチャ実装
+pub mod capture_feed;
+pub mod capture_source;
+pub mod pipewire;
 pub mod recording;
+pub mod recording_progress;
+pub mod recording_quality;
+pub mod recording_encoder;
+pub mod recording_spool;
+pub mod recording_tracks;
+pub mod recording_markers;
+pub mod storage_quota;
+pub mod audio_stream;
+pub mod audio_convert;
+pub mod audio_probe;
+pub mod flac_encoder;
+pub mod recording_conversion;
+pub mod voice_activity;
+pub mod job_queue;
+pub mod batch_transcription;
+pub mod transcription_lock;
+pub mod corrections;
+pub mod transcription_language;
+pub mod transcription_estimate;
+pub mod confidence_regions;
+pub mod transcript_edits;
+pub mod speaker_analytics;
+pub mod speaker_tracks;
+pub mod channel_separation;
+pub mod live_captions;
+pub mod live_transcription;
+pub mod demo_mode;
+pub mod caption_socket;
+pub mod phone_mic;
+pub mod voice_memo;
+pub mod control_server;
 
 // ローカルWhisper実装（Python whisperライブラリ使用）
 pub mod whisper;
+pub mod workspaces;
 pub mod whisper_local;
+pub mod whisper_acceleration;
+pub mod whisper_cpp;
+pub mod whisper_download_progress;
 pub mod whisper_mock;
+pub mod environment_doctor;
 
 // LLM統合サービス
 pub mod llm;
 pub mod llm_manager;
+pub mod summarization_status;
+pub mod rolling_summary;
+pub mod summary_review;
+pub mod summarizer;
+pub mod meeting_notes;
+pub mod model_comparison;
 pub mod model_settings;
+pub mod model_selection;
+pub mod provider_race;
+pub mod ollama_pool;
 pub mod model_downloader;
 
+// アプリ設定・外部連携
+pub mod app_settings;
+pub mod i18n;
+pub mod api_server;
+pub mod grpc_server;
+pub mod export;
+pub mod html_export;
+pub mod share_bundle;
+pub mod pdf_export;
+pub mod redaction;
+pub mod anonymize;
+pub mod digest;
+pub mod digest_schedule;
+pub mod chapters;
+pub mod ical;
+pub mod action_items;
+pub mod keyword_alerts;
+pub mod keyword_timeline;
+pub mod taxonomy;
+pub mod meeting_import;
+pub mod watched_folders;
+pub mod transcript_import;
+pub mod credentials;
+pub mod google_docs;
+pub mod teams;
+pub mod confluence;
+pub mod tray;
+pub mod disk_space;
+pub mod input_gain;
+pub mod device_fallback;
+pub mod recording_segments;
+pub mod noise_suppression;
+pub mod sleep_inhibitor;
+pub mod echo_cancellation;
+pub mod loudness;
+pub mod playback;
+pub mod audio_edit;
+
 pub use audio_capture_cpal::AudioCapture;
-pub use recording::RecordingService;
+pub use capture_feed::{AudioChunk, CaptureFeed};
+pub use capture_source::{AudioDevice, AudioSourceType, CaptureApplication, CaptureSource, DualSourceMode};
+pub use recording::{RecordingService, StoppedRecording};
+pub use recording_quality::{AudioFormat, RecordingQuality};
+pub use recording_encoder::RecordingFormat;
+pub use job_queue::JobQueue;
+pub use transcription_lock::{InFlightTranscription, TranscriptionLocks};
+pub use live_captions::{CaptionEvent, CaptionHypothesis, LiveCaptionHub};
+pub use live_transcription::{LiveTranscriber, LiveTranscriptSegment};
+pub use caption_socket::{CaptionSocketServer, CaptionSocketStatus};
+pub use batch_transcription::{BatchItemProgress, BatchItemStatus, BatchTranscriptionResult};
 pub use whisper_local::WhisperService;
 pub use llm::LLMService;
+pub use summarizer::Summarizer;
+pub use workspaces::{Workspace, WorkspaceManager};
+pub use summarization_status::{SummarizationStatus, SummarizationStatusRegistry};
+pub use rolling_summary::{RollingSummarizer, RollingSummary};
 pub use llm_manager::{LLMModelManager, ModelInfo, ModelBenchmark, ModelCapabilities};
-pub use model_settings::{ModelSettings, ModelPreference, PerformancePriority, ModelSettingsManager};
+pub use model_settings::{ModelSettings, ModelPreference, PerformancePriority, ModelSettingsManager, ProviderRace};
+pub use ollama_pool::{OllamaHostStatus, OllamaPool};
 pub use model_downloader::{ModelDownloader, DownloadableModel, SystemCompatibility, DownloadProgress, DownloadStatus};
+pub use app_settings::{AppSettings, ApiServerSettings, AppSettingsManager, BatchTranscriptionSettings, CaptionSocketSettings, ConfluenceSettings, RecordingProfile, ControlServerSettings, DigestDelivery, DigestFrequency, DigestScheduleSettings, GoogleDocsSettings, GrpcServerSettings, PhoneMicSettings, StorageSettings, UserProfile, VoiceMemoSettings, WatchedFolderRule, WhisperAccelerationSettings, WhisperBackendSettings, SilenceTrimSettings, InputGainSettings, NoiseSuppressionSettings, LoudnessSettings, SleepPreventionSettings};
+pub use api_server::{ApiServer, ApiServerState, ApiServerStatus};
+pub use grpc_server::{GrpcServer, GrpcServerStatus};
+pub use phone_mic::{PhoneMicEvent, PhoneMicServer, PhoneMicStatus};
+pub use control_server::{ControlEvent, ControlResult, ControlServer, ControlServerStatus};
+pub use meeting_import::{ImportedMeeting, MeetingSource};
+pub use transcript_import::{ImportedTranscript, TranscriptFormat};
+pub use chapters::ChapterDraft;
+pub use redaction::{RedactedExport, RedactionOptions};
+pub use anonymize::{AnonymizeOptions, AnonymizedExport};
+pub use digest::{DigestScope, MeetingDigest};
+pub use i18n::Locale;
+pub use tray::{TrayAction, TrayRecordingState, TrayState};
+pub use playback::{PlaybackService, PlaybackStatus};
@@ -0,0 +1,128 @@
+//! 複数モデルでの要約の比較
+//!
+//! 同じ書き起こしを 2〜3 個のモデルで同時に要約し、結果と処理時間・速度・メモリ使用量を並べて返す。
+//! 実際の会議で比べて既定のモデルを選べるようにするためのもの。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{LLMConfig, Summary, SummaryStatus};
+use crate::services::{summary_review, LLMService};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+pub const MIN_COMPARISON_MODELS: usize = 2;
+pub const MAX_COMPARISON_MODELS: usize = 3;
+
+/// モデル1つ分の比較結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelComparisonResult {
+    pub config: LLMConfig,
+    /// 成功した場合の要約
+    pub summary: Option<Summary>,
+    pub error: Option<String>,
+    pub processing_time_ms: u64,
+    /// 出力トークン数の概算（4文字 = 1トークン）
+    pub output_tokens: usize,
+    pub tokens_per_second: Option<f64>,
+    /// 要約後に読み込まれていたモデルのメモリ使用量（取得できる場合のみ）
+    pub memory_mb: Option<u64>,
+}
+
+pub fn validate_configs(configs: &[LLMConfig]) -> AppResult<()> {
+    if !(MIN_COMPARISON_MODELS..=MAX_COMPARISON_MODELS).contains(&configs.len()) {
+        return Err(AppError::ValidationError {
+            message: format!(
+                "Select {} to {} models to compare (got {})",
+                MIN_COMPARISON_MODELS,
+                MAX_COMPARISON_MODELS,
+                configs.len()
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// 書き起こしを指定したモデルで同時に要約する（`save` なら成功した要約を保存する）
+pub async fn compare_models(
+    database: &Database,
+    transcription_id: &str,
+    configs: Vec<LLMConfig>,
+    save: bool,
+) -> AppResult<Vec<ModelComparisonResult>> {
+    validate_configs(&configs)?;
+    if save {
+        summary_review::ensure_can_regenerate(database, transcription_id).await?;
+    }
+    let transcription = database
+        .get_transcription(transcription_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Transcription with id {} not found", transcription_id),
+        })?;
+
+    log::info!("⚖️ Comparing {} models on transcription {}", configs.len(), transcription_id);
+    let handles: Vec<_> = configs
+        .into_iter()
+        .map(|config| {
+            let text = transcription.text.clone();
+            let transcription_id = transcription.id.clone();
+            tokio::spawn(async move { summarize_with(config, &text, transcription_id).await })
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        let result = handle.await.map_err(|e| AppError::LLMConnectionError {
+            message: format!("Comparison task failed: {}", e),
+        })?;
+        if save {
+            if let Some(summary) = &result.summary {
+                database.create_summary(summary).await?;
+            }
+        }
+        results.push(result);
+    }
+    Ok(results)
+}
+
+async fn summarize_with(config: LLMConfig, text: &str, transcription_id: String) -> ModelComparisonResult {
+    let llm = LLMService::new(config.clone());
+    let start = Instant::now();
+    let outcome = llm.summarize_text(text, transcription_id).await;
+    let processing_time_ms = start.elapsed().as_millis() as u64;
+
+    let (summary, error) = match outcome {
+        Ok(summary) => match &summary.status {
+            SummaryStatus::Failed(error) => (None, Some(error.clone())),
+            _ => (Some(summary), None),
+        },
+        Err(error) => (None, Some(error.to_string())),
+    };
+
+    let output_tokens = summary.as_ref().map(estimate_output_tokens).unwrap_or(0);
+    let tokens_per_second = (summary.is_some() && processing_time_ms > 0)
+        .then(|| output_tokens as f64 / (processing_time_ms as f64 / 1000.0));
+    // メモリ使用量は参考値なので、取得に失敗しても比較結果は返す
+    let memory_mb = if summary.is_some() {
+        llm.loaded_model_memory_mb().await.unwrap_or(None)
+    } else {
+        None
+    };
+
+    ModelComparisonResult {
+        config,
+        summary,
+        error,
+        processing_time_ms,
+        output_tokens,
+        tokens_per_second,
+        memory_mb,
+    }
+}
+
+fn estimate_output_tokens(summary: &Summary) -> usize {
+    let chars = summary.summary_text.chars().count()
+        + summary.key_points.iter().map(|point| point.chars().count()).sum::<usize>()
+        + summary.action_items.iter().map(|item| item.chars().count()).sum::<usize>();
+    chars / 4
+}
@@ -0,0 +1,278 @@
+//! 要約時のモデル自動選択（`auto_switch_enabled`）
+//!
+//! 書き起こしの長さ・空きメモリ・検出できたプロバイダーのモデル・性能優先度から要約に使うモデルを選び、
+//! 選んだ理由と一緒に返す。要約のたびに検出し直すので、停止しているプロバイダーのモデルは選ばれない。
+
+use crate::errors::AppResult;
+use crate::models::LLMConfig;
+use crate::services::{LLMModelManager, ModelBenchmark, ModelInfo, ModelSettings, ModelSettingsManager, PerformancePriority};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use tokio::sync::Mutex;
+
+/// プロンプトと出力のために書き起こし以外に確保するトークン数
+const PROMPT_OVERHEAD_TOKENS: u64 = 1024;
+/// バランス重視のときに目安にするパラメーター数（B）
+const BALANCED_PARAMETERS_B: f64 = 4.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoSelection {
+    pub model_id: String,
+    pub config: LLMConfig,
+    pub reason: String,
+}
+
+/// 書き起こしの文字数からトークン数を概算する（1トークン ≈ 4文字）
+pub fn estimate_tokens(text_chars: usize) -> u64 {
+    (text_chars as u64).div_ceil(4)
+}
+
+/// 候補からモデルを選ぶ。使えるモデルが無ければ None
+pub fn select_model(
+    settings: &ModelSettings,
+    models: &[ModelInfo],
+    benchmarks: &[ModelBenchmark],
+    text_chars: usize,
+    available_memory_mb: Option<u64>,
+) -> Option<(String, String)> {
+    let candidates: Vec<&ModelInfo> = models
+        .iter()
+        .filter(|model| model.is_available)
+        .filter(|model| settings.model_preferences.get(&model.id).is_none_or(|pref| pref.enabled))
+        .collect();
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let mut notes = Vec::new();
+    let required_tokens = estimate_tokens(text_chars) + PROMPT_OVERHEAD_TOKENS;
+    let mut fitting: Vec<&ModelInfo> = candidates
+        .iter()
+        .copied()
+        .filter(|model| model.context_length.is_none_or(|length| length as u64 >= required_tokens))
+        .collect();
+    if fitting.is_empty() {
+        // どれも収まらない場合はコンテキストの最も長いモデルに任せる
+        let longest = candidates.iter().filter_map(|model| model.context_length).max();
+        fitting = candidates
+            .iter()
+            .copied()
+            .filter(|model| model.context_length == longest)
+            .collect();
+        notes.push(format!("no model fits ~{} tokens, using the longest context", required_tokens));
+    } else {
+        notes.push(format!("~{} tokens", required_tokens));
+    }
+
+    if let Some(available) = available_memory_mb {
+        let within_memory: Vec<&ModelInfo> = fitting
+            .iter()
+            .copied()
+            .filter(|model| model.memory_required.is_none_or(|required| required <= available))
+            .collect();
+        if within_memory.is_empty() {
+            let smallest = fitting.iter().filter_map(|model| model.memory_required).min();
+            fitting.retain(|model| model.memory_required == smallest);
+            notes.push(format!("no model fits in {} MB free memory, using the smallest", available));
+        } else {
+            fitting = within_memory;
+            notes.push(format!("{} MB free memory", available));
+        }
+    }
+
+    let benchmark = |model: &ModelInfo| benchmarks.iter().find(|benchmark| benchmark.model_id == model.id);
+    let preference = |model: &ModelInfo| {
+        settings
+            .model_preferences
+            .get(&model.id)
+            .map(|pref| pref.priority)
+            .unwrap_or(0)
+    };
+    let compare = |a: &&ModelInfo, b: &&ModelInfo| -> Ordering {
+        let primary = match settings.performance_priority {
+            PerformancePriority::Speed => compare_option(
+                benchmark(a).and_then(|bench| bench.inference_speed),
+                benchmark(b).and_then(|bench| bench.inference_speed),
+            )
+            .then_with(|| compare_option(parameters_b(b), parameters_b(a))),
+            PerformancePriority::Quality => compare_option(
+                benchmark(a).and_then(|bench| bench.quality_score.map(f64::from)),
+                benchmark(b).and_then(|bench| bench.quality_score.map(f64::from)),
+            )
+            .then_with(|| compare_option(parameters_b(a), parameters_b(b))),
+            PerformancePriority::Memory => compare_option(
+                b.memory_required.map(|mb| mb as f64),
+                a.memory_required.map(|mb| mb as f64),
+            ),
+            PerformancePriority::Balance => compare_option(
+                parameters_b(b).map(|params| (params / BALANCED_PARAMETERS_B).ln().abs()),
+                parameters_b(a).map(|params| (params / BALANCED_PARAMETERS_B).ln().abs()),
+            ),
+        };
+        primary
+            .then_with(|| preference(a).cmp(&preference(b)))
+            .then_with(|| b.id.cmp(&a.id))
+    };
+
+    let chosen = fitting.iter().copied().max_by(compare)?;
+    let reason = format!(
+        "{} priority; {}; chosen from {} available model(s)",
+        priority_label(&settings.performance_priority),
+        notes.join(", "),
+        candidates.len()
+    );
+    Some((chosen.id.clone(), reason))
+}
+
+/// 大きい方を Greater とする（値が無いものは最小扱い）
+fn compare_option(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// "7B" → 7.0
+fn parameters_b(model: &ModelInfo) -> Option<f64> {
+    model.parameter_count.as_deref()?.trim_end_matches(['B', 'b']).parse().ok()
+}
+
+fn priority_label(priority: &PerformancePriority) -> &'static str {
+    match priority {
+        PerformancePriority::Speed => "speed",
+        PerformancePriority::Quality => "quality",
+        PerformancePriority::Balance => "balance",
+        PerformancePriority::Memory => "memory",
+    }
+}
+
+/// テキスト長・用途・速度重視度から推奨するモデル（検出前の候補。推奨順）
+pub fn heuristic_recommendations(use_case: &str, text_length: u32, memory_limit: u32, speed_priority: f32) -> Vec<String> {
+    let mut recommendations = Vec::new();
+
+    // テキスト長に基づく推奨
+    if text_length < 1000 {
+        // 短いテキスト - 高速モデル推奨
+        if speed_priority > 0.7 {
+            recommendations.extend(vec![
+                "ollama:llama3.2:1b".to_string(),
+                "gpt4all:orca-mini".to_string(),
+            ]);
+        }
+        recommendations.push("ollama:llama3.2:3b".to_string());
+    } else if text_length < 10000 {
+        // 中程度のテキスト - バランス型推奨
+        recommendations.extend(vec![
+            "ollama:llama3.2:3b".to_string(),
+            "ollama:mistral:7b".to_string(),
+        ]);
+
+        if speed_priority < 0.5 && memory_limit >= 16000 {
+            recommendations.push("ollama:llama3.2:7b".to_string());
+        }
+    } else {
+        // 長いテキスト - 高品質モデル推奨
+        if memory_limit >= 16000 {
+            recommendations.push("ollama:llama3.2:7b".to_string());
+        }
+        recommendations.extend(vec![
+            "ollama:llama3.2:3b".to_string(),
+            "ollama:mistral:7b".to_string(),
+        ]);
+    }
+
+    // 用途別フィルタ
+    match use_case {
+        "japanese" => {
+            // 日本語対応を優先
+            recommendations.retain(|model| model.contains("llama") || model.contains("mistral"));
+        }
+        "code" => {
+            // コード関連を優先
+            recommendations.insert(0, "ollama:codellama:7b".to_string());
+        }
+        _ => {}
+    }
+
+    recommendations.dedup();
+    recommendations
+}
+
+/// 推奨候補を実際に利用できるモデルに絞り、ユーザーの優先度順に並べる（最大5個）
+///
+/// 候補に無いモデルでも、利用可能でメモリに収まるものは候補の後ろに加える。設定で無効にしたモデルは除く。
+pub fn rank_recommendations(
+    candidates: &[String],
+    installed: &[ModelInfo],
+    settings: &ModelSettings,
+    memory_limit_mb: u64,
+) -> Vec<String> {
+    let enabled = |id: &str| settings.model_preferences.get(id).is_none_or(|pref| pref.enabled);
+    let available: Vec<&ModelInfo> = installed
+        .iter()
+        .filter(|model| model.is_available && enabled(&model.id))
+        .collect();
+
+    let mut ranked: Vec<&str> = candidates
+        .iter()
+        .filter(|candidate| available.iter().any(|model| &model.id == *candidate))
+        .map(String::as_str)
+        .collect();
+    for model in &available {
+        let fits = model.memory_required.is_none_or(|required| required <= memory_limit_mb);
+        if fits && !ranked.contains(&model.id.as_str()) {
+            ranked.push(&model.id);
+        }
+    }
+
+    // 優先度の高い順（同じ優先度なら推奨順を保つ）
+    let priority = |id: &str| settings.model_preferences.get(id).map(|pref| pref.priority).unwrap_or(0);
+    ranked.sort_by_key(|id| std::cmp::Reverse(priority(id)));
+    ranked.truncate(5);
+    ranked.into_iter().map(str::to_string).collect()
+}
+
+/// 空きメモリ（MB）。取得できない環境では None
+pub fn available_memory_mb() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemAvailable:"))
+        .and_then(|value| value.trim().trim_end_matches("kB").trim().parse::<u64>().ok())
+        .map(|kb| kb / 1024)
+}
+
+/// 自動切り替えが有効なら、利用可能なモデルを検出して要約に使う設定を決める
+pub async fn auto_select(
+    settings_manager: &Mutex<ModelSettingsManager>,
+    model_manager: &Mutex<LLMModelManager>,
+    text_chars: usize,
+) -> AppResult<Option<AutoSelection>> {
+    let settings = settings_manager.lock().await.get_settings().clone();
+    if !settings.auto_switch_enabled {
+        return Ok(None);
+    }
+
+    let mut manager = model_manager.lock().await;
+    let models = manager.discover_available_models().await?;
+    let benchmarks: Vec<ModelBenchmark> = manager.get_cached_benchmarks().into_iter().cloned().collect();
+    let Some((model_id, reason)) = select_model(&settings, &models, &benchmarks, text_chars, available_memory_mb())
+    else {
+        log::warn!("⚠️ Auto-switch enabled but no available model was found; using the default config");
+        return Ok(None);
+    };
+
+    let config = match settings
+        .model_preferences
+        .get(&model_id)
+        .and_then(|pref| pref.custom_config.clone())
+    {
+        Some(config) => config,
+        None => manager.create_config_for_model(&model_id)?,
+    };
+
+    log::info!("🔀 Auto-selected model {}: {}", model_id, reason);
+    Ok(Some(AutoSelection { model_id, config, reason }))
+}
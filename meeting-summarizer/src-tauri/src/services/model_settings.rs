@@ -12,6 +12,19 @@ pub struct ModelSettings {
     pub use_case_defaults: HashMap<String, String>, // use_case -> model_id
     pub auto_switch_enabled: bool,
     pub performance_priority: PerformancePriority,
+    /// 設定されていれば、要約を2つのプロバイダーへ同時に送り先に成功した方を使う
+    #[serde(default)]
+    pub provider_race: Option<ProviderRace>,
+    /// Ollamaの要約リクエストを振り分けるホスト（空ならモデル設定の base_url のみ）
+    #[serde(default)]
+    pub ollama_hosts: Vec<String>,
+}
+
+/// 同時に要約を依頼する2つのプロバイダー（不安定なローカルサーバーとクラウドの組み合わせなど）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderRace {
+    pub primary: LLMConfig,
+    pub secondary: LLMConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +58,8 @@ impl Default for ModelSettings {
             use_case_defaults,
             auto_switch_enabled: false,
             performance_priority: PerformancePriority::Balance,
+            provider_race: None,
+            ollama_hosts: Vec::new(),
         }
     }
 }
@@ -172,6 +187,20 @@ impl ModelSettings {
                 errors.push(format!("Invalid priority for model '{}': {} (must be 1-10)", model_id, preference.priority));
             }
         }
+
+        // Ollamaホストの検証
+        for host in &self.ollama_hosts {
+            if !host.starts_with("http://") && !host.starts_with("https://") {
+                errors.push(format!("Invalid Ollama host URL: {}", host));
+            }
+        }
+
+        // 同時要約の検証
+        if let Some(race) = &self.provider_race {
+            if race.primary.base_url == race.secondary.base_url && race.primary.model_name == race.secondary.model_name {
+                errors.push("Provider race needs two different endpoints or models".to_string());
+            }
+        }
         
         errors
     }
@@ -201,6 +230,12 @@ impl ModelSettings {
         // 設定項目を更新
         self.auto_switch_enabled = other.auto_switch_enabled;
         self.performance_priority = other.performance_priority;
+        if other.provider_race.is_some() {
+            self.provider_race = other.provider_race;
+        }
+        if !other.ollama_hosts.is_empty() {
+            self.ollama_hosts = other.ollama_hosts;
+        }
     }
 }
 
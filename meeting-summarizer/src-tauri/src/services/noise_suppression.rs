@@ -0,0 +1,233 @@
+//! 録音時のノイズ除去
+//!
+//! 会議室の空調やファンのようなノイズを、録音スレッドでスプールへ書く前に取り除く。
+//! 除去には RNNoise の Rust 実装（`nnnoiseless`）を使う。モデルは 48kHz の10msごとの
+//! フレームを前提にしているため、それ以外のサンプルレートは 48kHz に変換して通し、元に戻す。
+//!
+//! フレームと変換の分だけ遅れて出力するため、録音の最後に `flush` で残りを書き出す。
+
+use nnnoiseless::{DenoiseState, FRAME_SIZE};
+use rubato::{FftFixedInOut, Resampler};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// モデルが前提とするサンプルレート
+const MODEL_SAMPLE_RATE: usize = 48_000;
+
+/// モデルは 16bit PCM の値の範囲で受け渡す
+const PCM_SCALE: f32 = 32_768.0;
+
+/// 48kHz 以外の録音を変換する単位
+const RESAMPLE_CHUNK_MS: usize = 10;
+
+/// 録音の終わりに、遅延の分より余分に押し出すフレーム数（変換の1回分に満たない入力の分）
+const FLUSH_EXTRA_FRAMES: usize = 8;
+
+/// 1チャンネル分のノイズ除去
+pub struct NoiseSuppressor {
+    state: Box<DenoiseState<'static>>,
+    /// 48kHz への変換と元のサンプルレートへの変換（48kHz の録音では None）
+    resamplers: Option<(FftFixedInOut<f32>, FftFixedInOut<f32>)>,
+    /// 各段の入力のうち、1回分に満たずまだ通していないもの
+    pending: Vec<f32>,
+    model_pending: Vec<f32>,
+    denoised_pending: Vec<f32>,
+    /// 出力の先頭の、入力より前の区間（捨てる）
+    skip: usize,
+    input_count: u64,
+    output_count: u64,
+}
+
+impl NoiseSuppressor {
+    pub fn new(sample_rate: u32) -> Self {
+        let sample_rate = sample_rate.max(1) as usize;
+        let resamplers = (sample_rate != MODEL_SAMPLE_RATE)
+            .then(|| {
+                let chunk = (sample_rate * RESAMPLE_CHUNK_MS / 1000).max(1);
+                let up = FftFixedInOut::new(sample_rate, MODEL_SAMPLE_RATE, chunk, 1).ok()?;
+                let down = FftFixedInOut::new(MODEL_SAMPLE_RATE, sample_rate, up.output_frames_next(), 1).ok()?;
+                Some((up, down))
+            })
+            .flatten();
+        // モデルは1フレーム、変換はそれぞれの遅延の分だけ遅れる
+        let skip = match &resamplers {
+            Some((up, down)) => {
+                (up.output_delay() + FRAME_SIZE) * sample_rate / MODEL_SAMPLE_RATE + down.output_delay()
+            }
+            None => FRAME_SIZE,
+        };
+        Self {
+            state: DenoiseState::new(),
+            resamplers,
+            pending: Vec::new(),
+            model_pending: Vec::new(),
+            denoised_pending: Vec::new(),
+            skip,
+            input_count: 0,
+            output_count: 0,
+        }
+    }
+
+    /// 入力を渡し、処理が済んだ分を返す（出力は入力より遅れる）
+    pub fn process(&mut self, input: &[f32]) -> Vec<f32> {
+        self.input_count += input.len() as u64;
+        let output = self.run(input);
+        self.emit(output)
+    }
+
+    /// 残りの入力を処理して返す（入力と同じ長さになるまで無音で押し出す）
+    pub fn flush(&mut self) -> Vec<f32> {
+        let remaining = (self.input_count - self.output_count) as usize;
+        let mut output = Vec::with_capacity(remaining);
+        let silence = vec![0.0; FRAME_SIZE];
+        // 遅延の分を押し出すには足りる回数だけ回し、それでも足りなければ無音で埋める
+        let rounds = (remaining + self.skip) / FRAME_SIZE + FLUSH_EXTRA_FRAMES;
+        for _ in 0..rounds {
+            if output.len() >= remaining {
+                break;
+            }
+            let produced = self.run(&silence);
+            output.extend(self.emit(produced));
+        }
+        output.resize(remaining, 0.0);
+        self.output_count = self.input_count;
+        output
+    }
+
+    fn emit(&mut self, mut output: Vec<f32>) -> Vec<f32> {
+        let skipped = self.skip.min(output.len());
+        self.skip -= skipped;
+        output.drain(..skipped);
+        self.output_count += output.len() as u64;
+        output
+    }
+
+    /// 入力を 48kHz に変換してフレームごとにモデルへ通し、元のサンプルレートに戻す
+    fn run(&mut self, input: &[f32]) -> Vec<f32> {
+        match self.resamplers.as_mut() {
+            Some((up, _)) => {
+                self.pending.extend_from_slice(input);
+                while self.pending.len() >= up.input_frames_next() {
+                    let frames = up.input_frames_next();
+                    if let Ok(mut resampled) = up.process(&[&self.pending[..frames]], None) {
+                        self.model_pending.append(&mut resampled[0]);
+                    }
+                    self.pending.drain(..frames);
+                }
+            }
+            None => self.model_pending.extend_from_slice(input),
+        }
+
+        let mut denoised = Vec::with_capacity(self.model_pending.len());
+        let mut frame = [0.0; FRAME_SIZE];
+        let mut output = [0.0; FRAME_SIZE];
+        while self.model_pending.len() >= FRAME_SIZE {
+            for (scaled, sample) in frame.iter_mut().zip(self.model_pending.drain(..FRAME_SIZE)) {
+                *scaled = sample * PCM_SCALE;
+            }
+            self.state.process_frame(&mut output, &frame);
+            denoised.extend(output.iter().map(|sample| sample / PCM_SCALE));
+        }
+
+        match self.resamplers.as_mut() {
+            Some((_, down)) => {
+                self.denoised_pending.extend(denoised);
+                let mut output = Vec::new();
+                while self.denoised_pending.len() >= down.input_frames_next() {
+                    let frames = down.input_frames_next();
+                    if let Ok(mut resampled) = down.process(&[&self.denoised_pending[..frames]], None) {
+                        output.append(&mut resampled[0]);
+                    }
+                    self.denoised_pending.drain(..frames);
+                }
+                output
+            }
+            None => denoised,
+        }
+    }
+}
+
+/// チャンネルが交互に並んだ音声に、チャンネルごとのノイズ除去をかける
+pub struct ChannelDenoiser {
+    suppressors: Vec<NoiseSuppressor>,
+}
+
+impl ChannelDenoiser {
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        Self {
+            suppressors: (0..channels.max(1)).map(|_| NoiseSuppressor::new(sample_rate)).collect(),
+        }
+    }
+
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        self.each_channel(|suppressor, channel| suppressor.process(channel), samples)
+    }
+
+    pub fn flush(&mut self) -> Vec<f32> {
+        self.each_channel(|suppressor, _| suppressor.flush(), &[])
+    }
+
+    fn each_channel(&mut self, mut run: impl FnMut(&mut NoiseSuppressor, &[f32]) -> Vec<f32>, samples: &[f32]) -> Vec<f32> {
+        let channels = self.suppressors.len();
+        if channels == 1 {
+            return run(&mut self.suppressors[0], samples);
+        }
+        // 各チャンネルは同じ数のサンプルを受け取るので、出力の長さもそろう
+        let outputs: Vec<Vec<f32>> = self
+            .suppressors
+            .iter_mut()
+            .enumerate()
+            .map(|(index, suppressor)| {
+                let channel: Vec<f32> = samples.iter().skip(index).step_by(channels).copied().collect();
+                run(suppressor, &channel)
+            })
+            .collect();
+        let frames = outputs.iter().map(Vec::len).min().unwrap_or(0);
+        (0..frames)
+            .flat_map(|frame| outputs.iter().map(move |output| output[frame]))
+            .collect()
+    }
+}
+
+/// 録音スレッドでかけるノイズ除去（録音中に有効・無効を切り替えてもすぐに従う）
+pub struct DenoiseStage {
+    enabled: Arc<AtomicBool>,
+    sample_rate: u32,
+    channels: u16,
+    denoiser: Option<ChannelDenoiser>,
+}
+
+impl DenoiseStage {
+    pub fn new(enabled: Arc<AtomicBool>, sample_rate: u32, channels: u16) -> Self {
+        Self {
+            enabled,
+            sample_rate,
+            channels,
+            denoiser: None,
+        }
+    }
+
+    pub fn process(&mut self, samples: Vec<f32>) -> Vec<f32> {
+        if self.enabled.load(Ordering::Relaxed) {
+            let (sample_rate, channels) = (self.sample_rate, self.channels);
+            return self
+                .denoiser
+                .get_or_insert_with(|| ChannelDenoiser::new(sample_rate, channels))
+                .process(&samples);
+        }
+        // 無効にされたら、処理中の分を書き出してからそのまま通す
+        match self.denoiser.take() {
+            Some(mut denoiser) => {
+                let mut output = denoiser.flush();
+                output.extend(samples);
+                output
+            }
+            None => samples,
+        }
+    }
+
+    /// 録音の終わりに、処理中の分を書き出す
+    pub fn finish(&mut self) -> Vec<f32> {
+        self.denoiser.take().map(|mut denoiser| denoiser.flush()).unwrap_or_default()
+    }
+}
@@ -0,0 +1,182 @@
+//! 複数のOllamaホストへの要約リクエストの振り分け
+//!
+//! デスクトップと自宅サーバーのように複数のOllamaを設定した場合、実行中のリクエストが少ない
+//! ホストから順に使い、失敗したら次のホストへ切り替える。直近で失敗したホストは
+//! `UNHEALTHY_COOLDOWN` の間は後回しにする（他が全て失敗した場合は最後に試す）。
+
+use crate::errors::{AppError, AppResult};
+use crate::models::{LLMConfig, Summary, SummaryStatus};
+use crate::services::llm::SummaryContext;
+use crate::services::LLMService;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 失敗したホストを後回しにする期間
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Default)]
+struct HostState {
+    in_flight: usize,
+    failed_at: Option<Instant>,
+    completed: u64,
+    failures: u64,
+}
+
+impl HostState {
+    fn is_healthy(&self) -> bool {
+        self.failed_at.is_none_or(|failed_at| failed_at.elapsed() >= UNHEALTHY_COOLDOWN)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaHostStatus {
+    pub base_url: String,
+    pub in_flight: usize,
+    pub healthy: bool,
+    pub completed: u64,
+    pub failures: u64,
+}
+
+/// 実行中のリクエスト数を数えるためのリース（破棄時に解放される）
+pub struct HostLease {
+    hosts: Arc<Mutex<HashMap<String, HostState>>>,
+    base_url: String,
+}
+
+impl Drop for HostLease {
+    fn drop(&mut self) {
+        if let Some(state) = self.hosts.lock().unwrap().get_mut(&self.base_url) {
+            state.in_flight = state.in_flight.saturating_sub(1);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct OllamaPool {
+    hosts: Arc<Mutex<HashMap<String, HostState>>>,
+}
+
+/// 設定されたホストのURLを揃える（空白・末尾のスラッシュを除き、重複を除く）
+pub fn normalize_hosts(hosts: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for host in hosts {
+        let host = host.trim().trim_end_matches('/').to_string();
+        if !host.is_empty() && !normalized.contains(&host) {
+            normalized.push(host);
+        }
+    }
+    normalized
+}
+
+impl OllamaPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 試す順番：正常なホスト → 実行中の少ないホスト → 設定順
+    pub fn candidates(&self, hosts: &[String]) -> Vec<String> {
+        let states = self.hosts.lock().unwrap();
+        let mut ranked: Vec<(bool, usize, usize, String)> = normalize_hosts(hosts)
+            .into_iter()
+            .enumerate()
+            .map(|(index, host)| {
+                let (healthy, in_flight) = states
+                    .get(&host)
+                    .map(|state| (state.is_healthy(), state.in_flight))
+                    .unwrap_or((true, 0));
+                (!healthy, in_flight, index, host)
+            })
+            .collect();
+        ranked.sort();
+        ranked.into_iter().map(|(_, _, _, host)| host).collect()
+    }
+
+    /// ホストの実行中リクエストを1つ増やす
+    pub fn lease(&self, base_url: &str) -> HostLease {
+        self.hosts.lock().unwrap().entry(base_url.to_string()).or_default().in_flight += 1;
+        HostLease {
+            hosts: self.hosts.clone(),
+            base_url: base_url.to_string(),
+        }
+    }
+
+    pub fn record_result(&self, base_url: &str, success: bool) {
+        let mut states = self.hosts.lock().unwrap();
+        let state = states.entry(base_url.to_string()).or_default();
+        if success {
+            state.completed += 1;
+            state.failed_at = None;
+        } else {
+            state.failures += 1;
+            state.failed_at = Some(Instant::now());
+        }
+    }
+
+    pub fn status(&self, hosts: &[String]) -> Vec<OllamaHostStatus> {
+        let states = self.hosts.lock().unwrap();
+        normalize_hosts(hosts)
+            .into_iter()
+            .map(|host| {
+                let state = states.get(&host);
+                OllamaHostStatus {
+                    in_flight: state.map(|state| state.in_flight).unwrap_or(0),
+                    healthy: state.map(HostState::is_healthy).unwrap_or(true),
+                    completed: state.map(|state| state.completed).unwrap_or(0),
+                    failures: state.map(|state| state.failures).unwrap_or(0),
+                    base_url: host,
+                }
+            })
+            .collect()
+    }
+
+    /// 各ホストへ接続を確認し、結果を反映した状態を返す
+    pub async fn check_hosts(&self, hosts: &[String], config: &LLMConfig) -> Vec<OllamaHostStatus> {
+        for host in normalize_hosts(hosts) {
+            let service = LLMService::new(LLMConfig { base_url: host.clone(), ..config.clone() });
+            let reachable = service.check_connection().await.unwrap_or(false);
+            if reachable {
+                self.hosts.lock().unwrap().entry(host).or_default().failed_at = None;
+            } else {
+                self.record_result(&host, false);
+            }
+        }
+        self.status(hosts)
+    }
+
+    /// 空いているホストから順に要約を依頼し、失敗したら次のホストへ切り替える
+    pub async fn summarize(
+        &self,
+        hosts: &[String],
+        config: &LLMConfig,
+        transcription_text: &str,
+        context: &SummaryContext,
+        transcription_id: String,
+    ) -> AppResult<Summary> {
+        let mut last_failure = None;
+
+        for host in self.candidates(hosts) {
+            let _lease = self.lease(&host);
+            let service = LLMService::new(LLMConfig { base_url: host.clone(), ..config.clone() });
+            let summary = service
+                .summarize_text_with_context(transcription_text, context, transcription_id.clone())
+                .await?;
+
+            if let SummaryStatus::Failed(error) = &summary.status {
+                log::warn!("⚠️ Ollama host {} failed, trying the next host: {}", host, error);
+                self.record_result(&host, false);
+                last_failure = Some(summary);
+                continue;
+            }
+
+            log::info!("🖧 Summary generated on Ollama host {}", host);
+            self.record_result(&host, true);
+            return Ok(summary);
+        }
+
+        last_failure.ok_or_else(|| AppError::LLMError {
+            message: "No Ollama hosts configured".to_string(),
+        })
+    }
+}
@@ -0,0 +1,346 @@
+//! 要約の PDF 出力（任意でパスワード保護）
+//!
+//! 日本語は Adobe-Japan1 の CID フォント（HeiseiKakuGo-W5、非埋め込み）で描画し、
+//! 閲覧側のフォントで表示させる。パスワードを指定した場合は AES-256（PDF 2.0 / V5）で暗号化し、
+//! オーナーパスワードが無い閲覧者には印刷のみ許可する。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::Summary;
+use chrono::{DateTime, Utc};
+use lopdf::content::{Content, Operation};
+use lopdf::encryption::crypt_filters::{Aes256CryptFilter, CryptFilter};
+use lopdf::{dictionary, Document, EncryptionState, EncryptionVersion, Object, Permissions, Stream, StringFormat};
+use rand::distr::Alphanumeric;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+const PAGE_WIDTH: f32 = 595.0; // A4
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+const BODY_SIZE: f32 = 10.5;
+const HEADING_SIZE: f32 = 13.0;
+const TITLE_SIZE: f32 = 17.0;
+const LINE_SPACING: f32 = 1.6;
+const FONT_NAME: &str = "HeiseiKakuGo-W5";
+
+/// オーナーパスワードが未指定のときに作る値の長さ
+const GENERATED_OWNER_PASSWORD_LEN: usize = 32;
+
+/// PDF のパスワード設定。どちらも未指定なら暗号化しない
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PdfProtection {
+    /// 閲覧時に要求するパスワード
+    pub user_password: Option<String>,
+    /// 権限（編集・コピー）を解除するためのパスワード。未指定ならランダムな値にして誰にも渡さない
+    pub owner_password: Option<String>,
+}
+
+impl PdfProtection {
+    pub fn is_enabled(&self) -> bool {
+        non_empty(&self.user_password).is_some() || non_empty(&self.owner_password).is_some()
+    }
+}
+
+/// 要約を PDF として `output_path` に書き出す
+pub async fn export_summary_pdf(
+    database: &Database,
+    summary_id: &str,
+    output_path: &Path,
+    protection: &PdfProtection,
+) -> AppResult<()> {
+    let summary = database
+        .get_summary(summary_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Summary with id {} not found", summary_id),
+        })?;
+
+    let mut title = None;
+    if let Some(transcription) = database.get_transcription(&summary.transcription_id).await? {
+        if let Some(recording) = database.get_recording(&transcription.recording_id).await? {
+            title = Some(recording.title.unwrap_or(recording.filename));
+        }
+    }
+
+    let bytes = render_summary_pdf(title.as_deref().unwrap_or("Meeting minutes"), &summary, protection)?;
+    tokio::fs::write(output_path, bytes).await?;
+    log::info!(
+        "📄 Exported summary {} to PDF{}",
+        summary_id,
+        if protection.is_enabled() { " (password protected)" } else { "" }
+    );
+    Ok(())
+}
+
+pub fn render_summary_pdf(title: &str, summary: &Summary, protection: &PdfProtection) -> AppResult<Vec<u8>> {
+    let mut layout = Layout::default();
+    layout.paragraph(title, TITLE_SIZE);
+    layout.paragraph(&format_meeting_date(&summary.created_at), BODY_SIZE);
+    layout.gap();
+
+    layout.paragraph("要約", HEADING_SIZE);
+    layout.paragraph(&summary.summary_text, BODY_SIZE);
+    for (heading, items) in [("重要ポイント", &summary.key_points), ("アクションアイテム", &summary.action_items)] {
+        if items.is_empty() {
+            continue;
+        }
+        layout.gap();
+        layout.paragraph(heading, HEADING_SIZE);
+        for item in items {
+            layout.paragraph(&format!("・{}", item), BODY_SIZE);
+        }
+    }
+
+    let mut document = build_document(layout.finish());
+    if protection.is_enabled() {
+        encrypt(&mut document, protection)?;
+    }
+
+    let mut bytes = Vec::new();
+    document.save_to(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// 1行分のテキスト（ページ内の y 座標とフォントサイズ）
+struct Line {
+    text: String,
+    y: f32,
+    size: f32,
+}
+
+#[derive(Default)]
+struct Layout {
+    pages: Vec<Vec<Line>>,
+    cursor: f32,
+}
+
+impl Layout {
+    fn paragraph(&mut self, text: &str, size: f32) {
+        for source_line in text.lines() {
+            for line in wrap_text(source_line, PAGE_WIDTH - MARGIN * 2.0, size) {
+                self.line(line, size);
+            }
+        }
+    }
+
+    fn line(&mut self, text: String, size: f32) {
+        let height = size * LINE_SPACING;
+        if self.pages.is_empty() || self.cursor - height < MARGIN {
+            self.pages.push(Vec::new());
+            self.cursor = PAGE_HEIGHT - MARGIN;
+        }
+        self.cursor -= height;
+        if let Some(page) = self.pages.last_mut() {
+            page.push(Line {
+                text,
+                y: self.cursor,
+                size,
+            });
+        }
+    }
+
+    fn gap(&mut self) {
+        self.cursor -= BODY_SIZE;
+    }
+
+    fn finish(mut self) -> Vec<Vec<Line>> {
+        if self.pages.is_empty() {
+            self.pages.push(Vec::new());
+        }
+        self.pages
+    }
+}
+
+/// 文字幅（ASCII は半角、それ以外は全角として扱う）で折り返す
+fn wrap_text(text: &str, max_width: f32, size: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut width = 0.0;
+
+    for c in text.chars() {
+        let advance = char_width(c) * size;
+        if width + advance > max_width && !current.is_empty() {
+            // 英単語の途中で折り返さないよう、直前の空白で区切れる場合はそこで改行する
+            let split_at = if c.is_ascii_alphanumeric() {
+                current.rfind(' ').filter(|&i| i > 0)
+            } else {
+                None
+            };
+            match split_at {
+                Some(index) => {
+                    let rest = current.split_off(index + 1);
+                    lines.push(current.trim_end().to_string());
+                    width = rest.chars().map(|c| char_width(c) * size).sum();
+                    current = rest;
+                }
+                None => {
+                    lines.push(std::mem::take(&mut current));
+                    width = 0.0;
+                }
+            }
+        }
+        current.push(c);
+        width += advance;
+    }
+
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn char_width(c: char) -> f32 {
+    if c.is_ascii() { 0.5 } else { 1.0 }
+}
+
+/// UniJIS-UCS2-HW-H 用の UCS-2（ビッグエンディアン）文字列。BMP 外の文字は〓に置き換える
+fn encode_ucs2(text: &str) -> Vec<u8> {
+    text.chars()
+        .flat_map(|c| {
+            let code = u16::try_from(c as u32).unwrap_or(0x3013);
+            code.to_be_bytes()
+        })
+        .collect()
+}
+
+fn build_document(pages: Vec<Vec<Line>>) -> Document {
+    // 暗号化に使う AES-256（V5 / R6）は PDF 2.0 で定められている
+    let mut document = Document::with_version("2.0");
+    let pages_id = document.new_object_id();
+
+    let descriptor_id = document.add_object(dictionary! {
+        "Type" => "FontDescriptor",
+        "FontName" => FONT_NAME,
+        "Flags" => 4,
+        "FontBBox" => vec![(-92).into(), (-250).into(), 1010.into(), 922.into()],
+        "ItalicAngle" => 0,
+        "Ascent" => 880,
+        "Descent" => -120,
+        "CapHeight" => 700,
+        "StemV" => 80,
+    });
+    let cid_font_id = document.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "CIDFontType0",
+        "BaseFont" => FONT_NAME,
+        "CIDSystemInfo" => dictionary! {
+            "Registry" => Object::string_literal("Adobe"),
+            "Ordering" => Object::string_literal("Japan1"),
+            "Supplement" => 2,
+        },
+        "FontDescriptor" => descriptor_id,
+        "DW" => 1000,
+        // 半角文字（CID 231-389）の幅
+        "W" => vec![231.into(), 389.into(), 500.into()],
+    });
+    let font_id = document.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type0",
+        "BaseFont" => FONT_NAME,
+        "Encoding" => "UniJIS-UCS2-HW-H",
+        "DescendantFonts" => vec![cid_font_id.into()],
+    });
+    let resources_id = document.add_object(dictionary! {
+        "Font" => dictionary! { "F1" => font_id },
+    });
+
+    let mut page_ids = Vec::with_capacity(pages.len());
+    for lines in pages {
+        let mut operations = Vec::with_capacity(lines.len() * 4);
+        for line in lines {
+            operations.push(Operation::new("BT", vec![]));
+            operations.push(Operation::new("Tf", vec!["F1".into(), line.size.into()]));
+            operations.push(Operation::new("Td", vec![MARGIN.into(), line.y.into()]));
+            operations.push(Operation::new(
+                "Tj",
+                vec![Object::String(encode_ucs2(&line.text), StringFormat::Hexadecimal)],
+            ));
+            operations.push(Operation::new("ET", vec![]));
+        }
+        let content = Content { operations };
+        let content_id = document.add_object(Stream::new(
+            dictionary! {},
+            content.encode().unwrap_or_default(),
+        ));
+        let page_id = document.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+        });
+        page_ids.push(page_id.into());
+    }
+
+    let page_count = page_ids.len() as i64;
+    document.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => page_ids,
+            "Count" => page_count,
+            "Resources" => resources_id,
+            "MediaBox" => vec![0.into(), 0.into(), PAGE_WIDTH.into(), PAGE_HEIGHT.into()],
+        }),
+    );
+    let catalog_id = document.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    });
+    document.trailer.set("Root", catalog_id);
+    document.compress();
+    document
+}
+
+fn encrypt(document: &mut Document, protection: &PdfProtection) -> AppResult<()> {
+    let user_password = non_empty(&protection.user_password).unwrap_or("");
+    // 閲覧パスワードと同じにすると閲覧者が権限を解除できてしまうため、未指定なら推測できない値にする
+    let generated_owner_password: String;
+    let owner_password = match non_empty(&protection.owner_password) {
+        Some(owner_password) => owner_password,
+        None => {
+            generated_owner_password = rand::rng()
+                .sample_iter(Alphanumeric)
+                .take(GENERATED_OWNER_PASSWORD_LEN)
+                .map(char::from)
+                .collect();
+            &generated_owner_password
+        }
+    };
+
+    let mut file_encryption_key = [0u8; 32];
+    rand::rng().fill(&mut file_encryption_key);
+    let crypt_filter: Arc<dyn CryptFilter> = Arc::new(Aes256CryptFilter);
+
+    let state = EncryptionState::try_from(EncryptionVersion::V5 {
+        encrypt_metadata: true,
+        crypt_filters: BTreeMap::from([(b"StdCF".to_vec(), crypt_filter)]),
+        file_encryption_key: &file_encryption_key,
+        stream_filter: b"StdCF".to_vec(),
+        string_filter: b"StdCF".to_vec(),
+        owner_password,
+        user_password,
+        permissions: Permissions::PRINTABLE
+            | Permissions::PRINTABLE_IN_HIGH_QUALITY
+            | Permissions::COPYABLE_FOR_ACCESSIBILITY,
+    })
+    .map_err(pdf_error)?;
+
+    document.encrypt(&state).map_err(pdf_error)
+}
+
+fn format_meeting_date(date: &DateTime<Utc>) -> String {
+    date.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string()
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().filter(|value| !value.is_empty())
+}
+
+fn pdf_error(error: lopdf::Error) -> AppError {
+    AppError::InvalidOperation {
+        message: format!("Failed to write PDF: {}", error),
+    }
+}
@@ -0,0 +1,640 @@
+//! スマートフォンをマイクとして使うLAN音声取り込み
+//!
+//! 会議室でノートPCのマイクが遠い場合に、スマートフォンのブラウザからマイク音声を
+//! WebSocket で送ってもらう。録音中に接続した場合は、その録音の別のトラックとして保存し、
+//! 録音していなければ別の録音として保存する。
+//!
+//! - `GET /?token=...` で録音用のページを返し、ページは `/ws?token=...` に接続する
+//! - 最初のテキストメッセージで形式（`{"sample_rate":48000,"channels":1}`）を送り、
+//!   以降はバイナリメッセージで 16bit リトルエンディアンの PCM を送る
+//! - 接続が閉じた時点でWAVを確定する。録音中に接続した場合は、録音の開始から接続までを
+//!   無音で埋めて時刻を揃え、録音が止まったらトラックとして紐付ける（先に録音が止まれば、
+//!   その時点で接続を閉じる）
+//!
+//! ブラウザは HTTPS のページでしかマイクを使わせないため、起動のたびに自己署名証明書を
+//! 作って HTTPS で待ち受ける（スマートフォンでは最初に証明書の警告を許可する）。
+//! LAN に公開するため、起動のたびに作るペアリング用のトークンがURLと一致しない接続は拒否する。
+//! 録音と同じく、空き容量が下限を下回ったら受け取りをやめてそこまでを保存する。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Recording, RecordingTrack};
+use crate::services::disk_space;
+use crate::services::recording::{RecordingService, StoppedRecording};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use axum_server::tls_rustls::RustlsConfig;
+use chrono::Utc;
+use hound::{SampleFormat, WavSpec, WavWriter};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::BufWriter;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+pub const PHONE_MIC_EVENT: &str = "phone-mic";
+
+/// 取り込んだ録音に付けるタグ
+pub const PHONE_MIC_TAG: &str = "phone-mic";
+
+/// 録音のトラックにしたときのデバイス名
+pub const PHONE_TRACK_NAME: &str = "Phone";
+
+/// 停止時に送信中の接続を待つ時間
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+
+/// 先頭の無音をまとめて書くサンプル数
+const PAD_CHUNK_SAMPLES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PhoneMicEvent {
+    Connected { session_id: String },
+    /// `track` は録音中に接続してその録音のトラックにした場合だけ入る
+    Finished {
+        session_id: String,
+        recording: Box<Recording>,
+        track: Option<RecordingTrack>,
+    },
+    Failed { session_id: String, message: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhoneMicStatus {
+    pub running: bool,
+    /// スマートフォンで開くURL（ペアリング用のトークン付き）
+    pub url: Option<String>,
+}
+
+/// ブラウザから送られる音声の形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamFormat {
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl StreamFormat {
+    pub fn validate(&self) -> AppResult<()> {
+        if !(8_000..=96_000).contains(&self.sample_rate) || !(1..=2).contains(&self.channels) {
+            return Err(AppError::ValidationError {
+                message: format!("Unsupported stream format: {} Hz, {} channels", self.sample_rate, self.channels),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// 1回の接続で受け取った音声をWAVに書き出す
+pub struct PhoneMicSession {
+    id: String,
+    path: PathBuf,
+    format: StreamFormat,
+    writer: WavWriter<BufWriter<File>>,
+    samples_written: u64,
+    /// 先頭に埋めた無音のサンプル数
+    padding_samples: u64,
+    /// メッセージの境界で分かれたサンプルの前半
+    pending_byte: Option<u8>,
+}
+
+impl PhoneMicSession {
+    pub fn create(recordings_dir: &Path, format: StreamFormat) -> AppResult<Self> {
+        format.validate()?;
+        std::fs::create_dir_all(recordings_dir)?;
+
+        let filename = format!("phone_{}.wav", Utc::now().format("%Y%m%d_%H%M%S_%3f"));
+        let path = recordings_dir.join(filename);
+        let spec = WavSpec {
+            channels: format.channels,
+            sample_rate: format.sample_rate,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        let writer = WavWriter::create(&path, spec).map_err(wav_error)?;
+
+        Ok(Self {
+            id: Uuid::new_v4().to_string(),
+            path,
+            format,
+            writer,
+            samples_written: 0,
+            padding_samples: 0,
+            pending_byte: None,
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// 先頭に `duration_ms` の無音を書く（録音の途中から加わったトラックの時刻を揃える）
+    ///
+    /// 長い録音では量が多いため、非同期の処理からは `spawn_blocking` で呼ぶ。
+    pub fn pad_silence(&mut self, duration_ms: u64) -> AppResult<()> {
+        let frames = duration_ms * self.format.sample_rate as u64 / 1000;
+        let mut remaining = frames * self.format.channels as u64;
+        let zeros = vec![0i16; PAD_CHUNK_SAMPLES];
+        while remaining > 0 {
+            let chunk = remaining.min(PAD_CHUNK_SAMPLES as u64) as usize;
+            let mut writer = self.writer.get_i16_writer(chunk as u32);
+            for &sample in &zeros[..chunk] {
+                writer.write_sample(sample);
+            }
+            writer.flush().map_err(wav_error)?;
+            self.samples_written += chunk as u64;
+            remaining -= chunk as u64;
+        }
+        self.padding_samples = self.samples_written;
+        Ok(())
+    }
+
+    /// 16bit リトルエンディアンの PCM を追記する
+    pub fn push_pcm(&mut self, bytes: &[u8]) -> AppResult<()> {
+        let mut bytes = bytes.iter().copied();
+        if let Some(low) = self.pending_byte.take() {
+            match bytes.next() {
+                Some(high) => self.write_sample(i16::from_le_bytes([low, high]))?,
+                None => {
+                    self.pending_byte = Some(low);
+                    return Ok(());
+                }
+            }
+        }
+
+        loop {
+            match (bytes.next(), bytes.next()) {
+                (Some(low), Some(high)) => self.write_sample(i16::from_le_bytes([low, high]))?,
+                (Some(low), None) => {
+                    self.pending_byte = Some(low);
+                    break;
+                }
+                _ => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_sample(&mut self, sample: i16) -> AppResult<()> {
+        self.writer.write_sample(sample).map_err(wav_error)?;
+        self.samples_written += 1;
+        Ok(())
+    }
+
+    /// 書き出した音声の長さ（秒、先頭の無音を含む）
+    pub fn duration_secs(&self) -> f64 {
+        let frames = self.samples_written / self.format.channels as u64;
+        frames as f64 / self.format.sample_rate as f64
+    }
+
+    /// WAVを確定し、録音として登録する内容を返す（音声が無ければファイルを消してエラー）
+    pub fn finish(self) -> AppResult<Recording> {
+        let duration = self.duration_secs();
+        self.writer.finalize().map_err(wav_error)?;
+
+        if self.samples_written == self.padding_samples {
+            let _ = std::fs::remove_file(&self.path);
+            return Err(AppError::ValidationError {
+                message: "No audio was received from the phone".to_string(),
+            });
+        }
+
+        let file_size = std::fs::metadata(&self.path)?.len() as i64;
+        let filename = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        Ok(Recording::new(filename, self.path.to_string_lossy().to_string())
+            .with_duration(duration.round() as i64)
+            .with_file_size(file_size)
+            .with_audio_info(self.format.sample_rate as i32, self.format.channels as i32)
+            .with_tags(vec![PHONE_MIC_TAG.to_string()]))
+    }
+}
+
+fn wav_error(error: hound::Error) -> AppError {
+    AppError::AudioConversion {
+        message: format!("Failed to write phone audio: {}", error),
+    }
+}
+
+/// スマートフォンから見たこのPCのLAN上のアドレス（経路が無ければ None）
+pub fn lan_address() -> Option<IpAddr> {
+    // UDP の connect はパケットを送らず、送信元アドレスの決定だけを行う
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:80").ok()?;
+    socket.local_addr().ok().map(|address| address.ip()).filter(|ip| !ip.is_unspecified())
+}
+
+/// ペアリング用のトークン（推測されにくいランダムな値）
+pub fn generate_token() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// このPCのアドレス向けの自己署名証明書で TLS の設定を作る
+pub fn self_signed_tls_config(lan_address: Option<IpAddr>) -> AppResult<RustlsConfig> {
+    let tls_error = |e: &dyn std::fmt::Display| AppError::InvalidOperation {
+        message: format!("Failed to set up HTTPS for the phone microphone: {}", e),
+    };
+
+    let mut names = vec!["localhost".to_string(), "127.0.0.1".to_string()];
+    names.extend(lan_address.map(|ip| ip.to_string()));
+    let certified = rcgen::generate_simple_self_signed(names).map_err(|e| tls_error(&e))?;
+    let certificate = CertificateDer::from(certified.cert.der().to_vec());
+    let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(certified.key_pair.serialize_der()));
+
+    let config = rustls::ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| tls_error(&e))?
+        .with_no_client_auth()
+        .with_single_cert(vec![certificate], key)
+        .map_err(|e| tls_error(&e))?;
+    Ok(RustlsConfig::from_config(Arc::new(config)))
+}
+
+#[derive(Clone)]
+struct PhoneMicState {
+    db: Arc<Database>,
+    recording_service: Arc<RecordingService>,
+    token: String,
+    events: broadcast::Sender<PhoneMicEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenQuery {
+    token: Option<String>,
+}
+
+/// LAN向けの音声取り込みサーバー
+pub struct PhoneMicServer {
+    address: Option<SocketAddr>,
+    token: Option<String>,
+    server: Option<axum_server::Handle>,
+    handle: Option<JoinHandle<()>>,
+    events: broadcast::Sender<PhoneMicEvent>,
+}
+
+impl Default for PhoneMicServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PhoneMicServer {
+    pub fn new() -> Self {
+        Self {
+            address: None,
+            token: None,
+            server: None,
+            handle: None,
+            events: broadcast::channel(16).0,
+        }
+    }
+
+    /// 接続・取り込み完了のイベントを購読する
+    pub fn subscribe(&self) -> broadcast::Receiver<PhoneMicEvent> {
+        self.events.subscribe()
+    }
+
+    /// すべてのインターフェースで HTTPS で待ち受ける（スマートフォンから接続するため）
+    pub async fn start(&mut self, db: Arc<Database>, recording_service: Arc<RecordingService>, port: u16) -> AppResult<SocketAddr> {
+        if self.is_running() {
+            return Err(AppError::InvalidOperation {
+                message: "Phone microphone server is already running".to_string(),
+            });
+        }
+
+        let listener = std::net::TcpListener::bind(SocketAddr::from(([0, 0, 0, 0], port)))?;
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+        let tls = self_signed_tls_config(lan_address())?;
+        let token = generate_token();
+
+        let state = PhoneMicState {
+            db,
+            recording_service,
+            token: token.clone(),
+            events: self.events.clone(),
+        };
+        let router = Router::new()
+            .route("/", get(index))
+            .route("/ws", get(upgrade))
+            .with_state(state);
+
+        let server = axum_server::Handle::new();
+        let server_handle = server.clone();
+        let handle = tokio::spawn(async move {
+            let result = axum_server::from_tcp_rustls(listener, tls)
+                .handle(server_handle)
+                .serve(router.into_make_service())
+                .await;
+
+            if let Err(e) = result {
+                log::error!("❌ Phone microphone server terminated with error: {}", e);
+            }
+        });
+
+        self.address = Some(address);
+        self.token = Some(token);
+        self.server = Some(server);
+        self.handle = Some(handle);
+
+        log::info!("📱 Phone microphone server listening on port {} (HTTPS)", address.port());
+        Ok(address)
+    }
+
+    pub async fn stop(&mut self) -> AppResult<()> {
+        if let Some(server) = self.server.take() {
+            server.graceful_shutdown(Some(SHUTDOWN_GRACE));
+        }
+
+        if let Some(handle) = self.handle.take() {
+            handle.await.map_err(|e| AppError::InvalidOperation {
+                message: format!("Failed to stop phone microphone server: {}", e),
+            })?;
+        }
+
+        self.address = None;
+        self.token = None;
+        log::info!("🛑 Phone microphone server stopped");
+        Ok(())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.handle.as_ref().is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// `lan_host` はスマートフォンから見たこのPCのアドレス
+    pub fn get_status(&self, lan_host: &str) -> PhoneMicStatus {
+        let running = self.is_running();
+        let url = match (&self.address, &self.token) {
+            (Some(address), Some(token)) if running => {
+                Some(format!("https://{}:{}/?token={}", lan_host, address.port(), token))
+            }
+            _ => None,
+        };
+        PhoneMicStatus { running, url }
+    }
+}
+
+fn authorized(state: &PhoneMicState, query: &TokenQuery) -> bool {
+    query.token.as_deref() == Some(state.token.as_str())
+}
+
+async fn index(State(state): State<PhoneMicState>, Query(query): Query<TokenQuery>) -> Response {
+    if !authorized(&state, &query) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    Html(CAPTURE_PAGE).into_response()
+}
+
+async fn upgrade(
+    State(state): State<PhoneMicState>,
+    Query(query): Query<TokenQuery>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    if !authorized(&state, &query) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    ws.on_upgrade(move |socket| ingest(state, socket))
+}
+
+/// 接続時に録音中だったセッション（止まったらその録音のトラックにする）
+struct ActiveRecording {
+    session_id: String,
+    stopped: broadcast::Receiver<StoppedRecording>,
+}
+
+impl ActiveRecording {
+    /// 録音が止まって保存されるまで待つ
+    async fn stopped(&mut self) -> Option<Recording> {
+        loop {
+            match self.stopped.recv().await {
+                Ok(stopped) if stopped.session_id == self.session_id => return Some(stopped.recording),
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+/// 形式を受け取って書き出しを始める（録音中なら録音の開始からの無音を先に書く）
+async fn open_session(state: &PhoneMicState, text: &str, min_free_bytes: u64) -> AppResult<(PhoneMicSession, Option<ActiveRecording>)> {
+    let format: StreamFormat = serde_json::from_str(text)?;
+    let recordings_dir = state.recording_service.recordings_dir();
+    disk_space::ensure_free_space(recordings_dir, min_free_bytes)?;
+    let mut session = PhoneMicSession::create(recordings_dir, format)?;
+
+    // 停止を見逃さないよう、進捗を読む前に購読する
+    let stopped = state.recording_service.subscribe_stopped_recordings();
+    let Some(progress) = state.recording_service.current_progress().await else {
+        return Ok((session, None));
+    };
+    let session = tokio::task::spawn_blocking(move || session.pad_silence(progress.elapsed_ms).map(|_| session))
+        .await
+        .map_err(|e| AppError::InvalidOperation {
+            message: format!("Silence padding task failed: {}", e),
+        })??;
+    Ok((session, Some(ActiveRecording { session_id: progress.session_id, stopped })))
+}
+
+/// 1つの接続の音声を受け取り、閉じたら録音（またはそのトラック）として登録する
+async fn ingest(state: PhoneMicState, mut socket: WebSocket) {
+    let min_free_bytes = state.recording_service.min_free_bytes().await;
+    let disk_check_interval = Duration::from_millis(disk_space::DISK_CHECK_INTERVAL_MS);
+    let mut last_disk_check = Instant::now();
+    let mut session: Option<PhoneMicSession> = None;
+    let mut active: Option<ActiveRecording> = None;
+    let mut stopped_recording: Option<Recording> = None;
+
+    loop {
+        let message = tokio::select! {
+            message = socket.recv() => message,
+            Some(recording) = async {
+                match active.as_mut() {
+                    Some(active) => active.stopped().await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                log::info!("📱 Recording stopped; closing the phone connection");
+                stopped_recording = Some(recording);
+                let _ = socket.send(Message::Close(None)).await;
+                break;
+            }
+        };
+        let Some(message) = message else { break };
+
+        let result = match message {
+            Ok(Message::Text(text)) if session.is_none() => open_session(&state, &text, min_free_bytes).await.map(|(created, recording)| {
+                log::info!("📱 Phone connected ({} Hz, {} ch)", created.format.sample_rate, created.format.channels);
+                let _ = state.events.send(PhoneMicEvent::Connected { session_id: created.id().to_string() });
+                session = Some(created);
+                active = recording;
+            }),
+            Ok(Message::Binary(bytes)) => match session.as_mut() {
+                Some(session) => session.push_pcm(&bytes),
+                None => Err(AppError::ValidationError {
+                    message: "Stream format must be sent before audio".to_string(),
+                }),
+            },
+            Ok(Message::Close(_)) => break,
+            Ok(_) => Ok(()),
+            Err(e) => {
+                log::warn!("⚠️ Phone connection lost: {}", e);
+                break;
+            }
+        };
+
+        if let Err(e) = result {
+            log::error!("❌ Phone audio rejected: {}", e);
+            if let Some(session) = session.take() {
+                let session_id = session.id().to_string();
+                let _ = session.finish();
+                let _ = state.events.send(PhoneMicEvent::Failed { session_id, message: e.to_string() });
+            }
+            return;
+        }
+
+        // 空き容量が足りなくなったら、そこまでを保存して接続を閉じる
+        if session.is_some() && last_disk_check.elapsed() >= disk_check_interval {
+            last_disk_check = Instant::now();
+            if let Err(e) = disk_space::ensure_free_space(state.recording_service.recordings_dir(), min_free_bytes) {
+                log::warn!("⚠️ Stopping phone audio: {}", e);
+                let _ = socket.send(Message::Close(None)).await;
+                break;
+            }
+        }
+    }
+
+    let Some(session) = session else { return };
+    // 録音が止まるのを待つ間にサーバーを止めても保存できるよう、接続とは別のタスクで待つ
+    tokio::spawn(finish_session(state, session, stopped_recording, active));
+}
+
+/// 録音中に接続した場合は録音が止まって保存されるのを待ち、取り込んだ音声を登録する
+async fn finish_session(
+    state: PhoneMicState,
+    session: PhoneMicSession,
+    stopped_recording: Option<Recording>,
+    mut active: Option<ActiveRecording>,
+) {
+    let session_id = session.id().to_string();
+    let recording = match (stopped_recording, active.as_mut()) {
+        (Some(recording), _) => Some(recording),
+        (None, Some(active)) => active.stopped().await,
+        (None, None) => None,
+    };
+    let event = match save(&state, session, recording).await {
+        Ok((recording, track)) => {
+            log::info!("✅ Phone recording saved: {} ({}s)", recording.id, recording.duration.unwrap_or(0));
+            PhoneMicEvent::Finished { session_id, recording: Box::new(recording), track }
+        }
+        Err(e) => {
+            log::error!("❌ Failed to save phone recording: {}", e);
+            PhoneMicEvent::Failed { session_id, message: e.to_string() }
+        }
+    };
+    let _ = state.events.send(event);
+}
+
+/// 録音のトラックとして紐付けるか、別の録音として登録する
+async fn save(
+    state: &PhoneMicState,
+    session: PhoneMicSession,
+    recording: Option<Recording>,
+) -> AppResult<(Recording, Option<RecordingTrack>)> {
+    let phone_recording = session.finish()?;
+    match recording {
+        Some(recording) => {
+            let track = state
+                .recording_service
+                .attach_track(&recording, Path::new(&phone_recording.file_path), PHONE_TRACK_NAME)
+                .await?;
+            Ok((recording, Some(track)))
+        }
+        None => {
+            state.db.create_recording(&phone_recording).await?;
+            Ok((phone_recording, None))
+        }
+    }
+}
+
+/// スマートフォンで開く録音ページ（マイク音声を 16bit PCM に変換して送る）
+const CAPTURE_PAGE: &str = r#"<!doctype html>
+<html lang="ja">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Meeting Summarizer マイク</title>
+<style>
+body { font-family: sans-serif; text-align: center; padding: 2em; }
+button { font-size: 1.5em; padding: 0.8em 2em; }
+</style>
+</head>
+<body>
+<h1>🎙️ マイク</h1>
+<p id="status">準備完了</p>
+<button id="toggle">録音開始</button>
+<script>
+let socket, context, stream, processor;
+const status = document.getElementById('status');
+const button = document.getElementById('toggle');
+
+async function start() {
+  stream = await navigator.mediaDevices.getUserMedia({ audio: { echoCancellation: true, noiseSuppression: true } });
+  context = new AudioContext();
+  const source = context.createMediaStreamSource(stream);
+  processor = context.createScriptProcessor(4096, 1, 1);
+  const protocol = location.protocol === 'https:' ? 'wss:' : 'ws:';
+  socket = new WebSocket(protocol + '//' + location.host + '/ws' + location.search);
+  socket.binaryType = 'arraybuffer';
+  socket.onopen = () => {
+    socket.send(JSON.stringify({ sample_rate: context.sampleRate, channels: 1 }));
+    status.textContent = '送信中';
+  };
+  socket.onclose = () => { status.textContent = '停止しました'; };
+  processor.onaudioprocess = (event) => {
+    if (socket.readyState !== WebSocket.OPEN) return;
+    const input = event.inputBuffer.getChannelData(0);
+    const pcm = new Int16Array(input.length);
+    for (let i = 0; i < input.length; i++) {
+      const s = Math.max(-1, Math.min(1, input[i]));
+      pcm[i] = s < 0 ? s * 0x8000 : s * 0x7fff;
+    }
+    socket.send(pcm.buffer);
+  };
+  source.connect(processor);
+  processor.connect(context.destination);
+  button.textContent = '録音停止';
+}
+
+function stop() {
+  processor.disconnect();
+  stream.getTracks().forEach((track) => track.stop());
+  context.close();
+  socket.close();
+  button.textContent = '録音開始';
+}
+
+button.onclick = () => {
+  if (socket && socket.readyState === WebSocket.OPEN) {
+    stop();
+  } else {
+    start().catch((error) => { status.textContent = 'マイクを使用できません: ' + error; });
+  }
+};
+</script>
+</body>
+</html>
+"#;
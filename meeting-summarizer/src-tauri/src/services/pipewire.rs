@@ -0,0 +1,108 @@
+//! Linux の PipeWire での録音
+//!
+//! `pw-dump` でノードを列挙し、`pw-record` で録音した音声を標準出力から受け取る。
+//!
+//! - `Audio/Source` マイクなどの入力デバイス
+//! - `Audio/Sink` 出力デバイス（モニターを録音するとシステム音声になる）
+//! - `Stream/Output/Audio` 音声を再生中のアプリ（会議アプリだけを録音できる）
+
+use crate::errors::{AppError, AppResult};
+use crate::services::capture_source::{AudioDevice, AudioSourceType, CaptureSource, PROCESS_CAPTURE_SAMPLE_RATE};
+use serde_json::Value;
+use std::process::Command;
+
+/// PipeWire のツールが使えるか
+pub fn is_available() -> bool {
+    Command::new("pw-record")
+        .arg("--version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// `pw-dump` の出力から録音できるノードを取り出す
+pub fn parse_pw_dump(json: &str) -> AppResult<Vec<AudioDevice>> {
+    let objects: Vec<Value> = serde_json::from_str(json)?;
+
+    let mut devices = Vec::new();
+    for object in &objects {
+        if object["type"] != "PipeWire:Interface:Node" {
+            continue;
+        }
+        let props = &object["info"]["props"];
+        let text = |key: &str| props[key].as_str().map(str::to_string).filter(|value| !value.is_empty());
+        let Some(node_name) = text("node.name") else {
+            continue;
+        };
+        let description = text("node.description").unwrap_or_else(|| node_name.clone());
+
+        let device = match props["media.class"].as_str() {
+            Some("Audio/Source") => AudioDevice {
+                name: description,
+                source_type: AudioSourceType::Microphone,
+                capture_source: CaptureSource::PipeWireNode { node: node_name, monitor: false },
+            },
+            Some("Audio/Sink") => AudioDevice {
+                name: format!("{} (monitor)", description),
+                source_type: AudioSourceType::SystemAudio,
+                capture_source: CaptureSource::PipeWireNode { node: node_name, monitor: true },
+            },
+            Some("Stream/Output/Audio") => {
+                // アプリのストリームは名前が重複しやすいので、シリアル番号で指定する
+                let target = props["object.serial"]
+                    .as_u64()
+                    .or_else(|| object["id"].as_u64())
+                    .map(|serial| serial.to_string())
+                    .unwrap_or(node_name);
+                let application = text("application.name").unwrap_or(description);
+                let name = match text("media.name") {
+                    Some(media) if media != application => format!("{}: {}", application, media),
+                    _ => application,
+                };
+                AudioDevice {
+                    name,
+                    source_type: AudioSourceType::Application,
+                    capture_source: CaptureSource::PipeWireNode { node: target, monitor: false },
+                }
+            }
+            _ => continue,
+        };
+        devices.push(device);
+    }
+    Ok(devices)
+}
+
+/// PipeWire のノードを列挙する
+pub fn list_devices() -> AppResult<Vec<AudioDevice>> {
+    let output = Command::new("pw-dump").output().map_err(|e| AppError::Recording {
+        message: format!("Failed to run pw-dump: {}", e),
+    })?;
+    if !output.status.success() {
+        return Err(AppError::Recording {
+            message: format!("pw-dump failed: {}", String::from_utf8_lossy(&output.stderr).trim()),
+        });
+    }
+    parse_pw_dump(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// 標準出力へモノラル f32 で書き出す `pw-record`
+///
+/// `target` が None なら既定のデバイス。`monitor` なら出力デバイスに流れる音声を録音する。
+pub fn record_command(target: Option<&str>, monitor: bool) -> Command {
+    let mut command = Command::new("pw-record");
+    command
+        .arg("--format")
+        .arg("f32")
+        .arg("--rate")
+        .arg(PROCESS_CAPTURE_SAMPLE_RATE.to_string())
+        .arg("--channels")
+        .arg("1");
+    if monitor {
+        command.arg("-P").arg("{ stream.capture.sink = true }");
+    }
+    if let Some(target) = target {
+        command.arg("--target").arg(target);
+    }
+    command.arg("-");
+    command
+}
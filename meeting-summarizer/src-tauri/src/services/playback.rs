@@ -0,0 +1,647 @@
+//! 録音の再生
+//!
+//! 画面で書き起こしのタイムスタンプに合わせて音声を聞けるよう、録音を既定の出力デバイスで
+//! 再生する。再生は専用のスレッドが出力ストリームを持ち、symphonia で少しずつデコードした
+//! 音声を出力デバイスの形式に変換して渡す。分けて保存した録音は区間を順につないで1本の録音と
+//! して扱う。再生位置は一定間隔で通知する。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::Recording;
+use crate::services::recording_segments;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::StreamConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{Decoder, DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::{FormatOptions, FormatReader, SeekMode, SeekTo};
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use symphonia::core::units::{Time, TimeBase};
+use tokio::sync::broadcast;
+
+pub const PLAYBACK_EVENT: &str = "playback-position";
+
+/// 再生位置を通知する間隔
+pub const POSITION_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 出力ストリームへ渡すために先にデコードしておく長さ
+const BUFFER_MS: u64 = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackState {
+    Stopped,
+    Playing,
+    Paused,
+}
+
+/// 再生の状態（`PLAYBACK_EVENT` で通知する）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaybackStatus {
+    pub recording_id: Option<String>,
+    pub state: PlaybackState,
+    /// 録音の先頭からの再生位置（分けた録音は全区間を通した位置）
+    pub position_ms: i64,
+    pub duration_ms: i64,
+}
+
+impl Default for PlaybackStatus {
+    fn default() -> Self {
+        Self {
+            recording_id: None,
+            state: PlaybackState::Stopped,
+            position_ms: 0,
+            duration_ms: 0,
+        }
+    }
+}
+
+/// 再生する音声ファイルと、録音の先頭からの開始位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaybackTrack {
+    pub path: PathBuf,
+    pub offset_ms: i64,
+}
+
+/// 録音を再生するファイルと、録音全体の長さ（ミリ秒）
+pub async fn recording_tracks(database: &Database, recording: &Recording) -> AppResult<(Vec<PlaybackTrack>, i64)> {
+    let segments = database.get_recording_segments(&recording.id).await?;
+    if let Some(last) = segments.last() {
+        let duration_ms = last.offset_ms + last.duration_ms;
+        let tracks = segments
+            .into_iter()
+            .map(|segment| PlaybackTrack {
+                path: PathBuf::from(segment.file_path),
+                offset_ms: segment.offset_ms,
+            })
+            .collect();
+        return Ok((tracks, duration_ms));
+    }
+
+    let path = PathBuf::from(&recording.file_path);
+    let duration_ms = recording_segments::wav_duration_ms(&path)
+        .or(recording.duration.map(|seconds| seconds * 1000))
+        .unwrap_or(0);
+    Ok((vec![PlaybackTrack { path, offset_ms: 0 }], duration_ms))
+}
+
+/// 1つの音声ファイルを少しずつデコードする（チャンネルが交互に並んだサンプルを返す）
+pub struct PlaybackDecoder {
+    format: Box<dyn FormatReader>,
+    decoder: Box<dyn Decoder>,
+    track_id: u32,
+    time_base: Option<TimeBase>,
+    sample_rate: u32,
+    channels: u16,
+    /// シーク後、目的の位置より前にあるため捨てるフレーム数
+    skip_frames: u64,
+}
+
+impl PlaybackDecoder {
+    pub fn open(path: &Path) -> AppResult<Self> {
+        let file = File::open(path).map_err(|_| AppError::FileNotFound {
+            path: path.to_string_lossy().to_string(),
+        })?;
+        let stream = MediaSourceStream::new(Box::new(file), Default::default());
+        let mut hint = Hint::new();
+        if let Some(extension) = path.extension().and_then(|ext| ext.to_str()) {
+            hint.with_extension(extension);
+        }
+
+        let probed = symphonia::default::get_probe()
+            .format(&hint, stream, &FormatOptions::default(), &MetadataOptions::default())
+            .map_err(|e| playback_error(path, e))?;
+        let format = probed.format;
+        let track = format
+            .tracks()
+            .iter()
+            .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+            .ok_or_else(|| AppError::Playback {
+                message: format!("No audio track found in {:?}", path),
+            })?;
+        let params = track.codec_params.clone();
+        let decoder = symphonia::default::get_codecs()
+            .make(&params, &DecoderOptions::default())
+            .map_err(|e| playback_error(path, e))?;
+
+        Ok(Self {
+            track_id: track.id,
+            time_base: params.time_base,
+            sample_rate: params.sample_rate.unwrap_or(0),
+            channels: params.channels.map(|channels| channels.count() as u16).unwrap_or(0),
+            format,
+            decoder,
+            skip_frames: 0,
+        })
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// ファイルの先頭から `position_ms` の位置へ移る
+    pub fn seek(&mut self, position_ms: i64) -> AppResult<()> {
+        let seconds = position_ms.max(0) as f64 / 1000.0;
+        let seeked = self
+            .format
+            .seek(
+                SeekMode::Accurate,
+                SeekTo::Time {
+                    time: Time::from(seconds),
+                    track_id: Some(self.track_id),
+                },
+            )
+            .map_err(|e| AppError::Playback {
+                message: format!("Seek failed: {}", e),
+            })?;
+        self.decoder.reset();
+
+        // パケットの境界までしか戻れない形式では、目的の位置までのサンプルを捨てる
+        let ahead = seeked.required_ts.saturating_sub(seeked.actual_ts);
+        self.skip_frames = match self.time_base {
+            Some(time_base) if self.sample_rate > 0 => {
+                ahead * self.sample_rate as u64 * time_base.numer as u64 / time_base.denom.max(1) as u64
+            }
+            _ => ahead,
+        };
+        Ok(())
+    }
+
+    /// 次のサンプル（ファイルの終わりなら None）
+    pub fn next_samples(&mut self) -> AppResult<Option<Vec<f32>>> {
+        loop {
+            let packet = match self.format.next_packet() {
+                Ok(packet) => packet,
+                Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(SymphoniaError::ResetRequired) => return Ok(None),
+                Err(e) => {
+                    return Err(AppError::Playback {
+                        message: format!("Failed to read audio: {}", e),
+                    })
+                }
+            };
+            if packet.track_id() != self.track_id {
+                continue;
+            }
+
+            let decoded = match self.decoder.decode(&packet) {
+                Ok(decoded) => decoded,
+                // 破損パケットは読み飛ばす
+                Err(SymphoniaError::DecodeError(_)) => continue,
+                Err(e) => {
+                    return Err(AppError::Playback {
+                        message: format!("Failed to decode audio: {}", e),
+                    })
+                }
+            };
+            let spec = *decoded.spec();
+            self.sample_rate = spec.rate;
+            self.channels = spec.channels.count().max(1) as u16;
+            let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+            buffer.copy_interleaved_ref(decoded);
+
+            let mut samples = buffer.samples().to_vec();
+            if self.skip_frames > 0 {
+                let frames = (samples.len() / self.channels as usize) as u64;
+                let skip = self.skip_frames.min(frames);
+                samples.drain(..skip as usize * self.channels as usize);
+                self.skip_frames -= skip;
+            }
+            if !samples.is_empty() {
+                return Ok(Some(samples));
+            }
+        }
+    }
+}
+
+/// 出力デバイスの形式への変換（チャンネル数の変換と線形補間によるリサンプリング）
+pub struct OutputConverter {
+    from_rate: u32,
+    from_channels: u16,
+    to_rate: u32,
+    to_channels: u16,
+    /// 直前の入力の最後のフレーム（出力のチャンネル数）
+    previous: Option<Vec<f32>>,
+    /// 次に出力する位置（`previous` を 0 とした入力フレームの位置）
+    position: f64,
+}
+
+impl OutputConverter {
+    pub fn new(from_rate: u32, from_channels: u16, to_rate: u32, to_channels: u16) -> Self {
+        Self {
+            from_rate: from_rate.max(1),
+            from_channels: from_channels.max(1),
+            to_rate: to_rate.max(1),
+            to_channels: to_channels.max(1),
+            previous: None,
+            position: 0.0,
+        }
+    }
+
+    /// シークしたときなど、前の入力とつながらない場合に呼ぶ
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.position = 0.0;
+    }
+
+    pub fn convert(&mut self, samples: &[f32]) -> Vec<f32> {
+        let to_channels = self.to_channels as usize;
+        let mapped = samples.chunks(self.from_channels as usize).map(|frame| map_channels(frame, to_channels));
+        if self.from_rate == self.to_rate {
+            return mapped.flatten().collect();
+        }
+        let mut frames: Vec<Vec<f32>> = self.previous.take().into_iter().chain(mapped).collect();
+        if frames.is_empty() {
+            return Vec::new();
+        }
+
+        let step = self.from_rate as f64 / self.to_rate as f64;
+        let mut output = Vec::new();
+        while self.position + 1.0 < frames.len() as f64 {
+            let index = self.position as usize;
+            let fraction = (self.position - index as f64) as f32;
+            for (current, next) in frames[index].iter().zip(&frames[index + 1]) {
+                output.push(current + (next - current) * fraction);
+            }
+            self.position += step;
+        }
+        self.position -= (frames.len() - 1) as f64;
+        self.previous = frames.pop();
+        output
+    }
+}
+
+fn map_channels(frame: &[f32], to_channels: usize) -> Vec<f32> {
+    if frame.len() == to_channels {
+        return frame.to_vec();
+    }
+    if to_channels == 1 {
+        return vec![frame.iter().sum::<f32>() / frame.len().max(1) as f32];
+    }
+    // モノラルは全チャンネルに、それ以外は足りないチャンネルを先頭から繰り返して割り当てる
+    (0..to_channels).map(|channel| frame[channel % frame.len()]).collect()
+}
+
+/// 区間をつないで1本の録音として読む
+pub struct PlaybackSource {
+    tracks: Vec<PlaybackTrack>,
+    current: usize,
+    decoder: PlaybackDecoder,
+    converter: OutputConverter,
+    output_rate: u32,
+    output_channels: u16,
+}
+
+impl PlaybackSource {
+    pub fn open(tracks: Vec<PlaybackTrack>, output_rate: u32, output_channels: u16) -> AppResult<Self> {
+        let first = tracks.first().ok_or_else(|| AppError::Playback {
+            message: "No audio file to play".to_string(),
+        })?;
+        let decoder = PlaybackDecoder::open(&first.path)?;
+        let converter = OutputConverter::new(decoder.sample_rate(), decoder.channels(), output_rate, output_channels);
+        Ok(Self {
+            tracks,
+            current: 0,
+            decoder,
+            converter,
+            output_rate,
+            output_channels,
+        })
+    }
+
+    /// 録音の先頭から `position_ms` の位置へ移る
+    pub fn seek(&mut self, position_ms: i64) -> AppResult<()> {
+        let index = self
+            .tracks
+            .iter()
+            .rposition(|track| track.offset_ms <= position_ms)
+            .unwrap_or(0);
+        self.open_track(index)?;
+        self.decoder.seek(position_ms - self.tracks[index].offset_ms)
+    }
+
+    /// 出力デバイスの形式のサンプル（録音の終わりなら None）
+    pub fn read(&mut self) -> AppResult<Option<Vec<f32>>> {
+        loop {
+            if let Some(samples) = self.decoder.next_samples()? {
+                let output = self.converter.convert(&samples);
+                if !output.is_empty() {
+                    return Ok(Some(output));
+                }
+                continue;
+            }
+            if self.current + 1 >= self.tracks.len() {
+                return Ok(None);
+            }
+            self.open_track(self.current + 1)?;
+        }
+    }
+
+    fn open_track(&mut self, index: usize) -> AppResult<()> {
+        if index != self.current {
+            self.decoder = PlaybackDecoder::open(&self.tracks[index].path)?;
+            self.current = index;
+        }
+        self.converter = OutputConverter::new(
+            self.decoder.sample_rate(),
+            self.decoder.channels(),
+            self.output_rate,
+            self.output_channels,
+        );
+        Ok(())
+    }
+}
+
+enum PlaybackCommand {
+    Pause,
+    Resume,
+    Seek(i64),
+    Stop,
+}
+
+/// 録音の再生を管理する（同時に再生するのは1つの録音だけ）
+pub struct PlaybackService {
+    commands: Mutex<Option<mpsc::Sender<PlaybackCommand>>>,
+    status: Arc<RwLock<PlaybackStatus>>,
+    events: broadcast::Sender<PlaybackStatus>,
+}
+
+impl Default for PlaybackService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlaybackService {
+    pub fn new() -> Self {
+        Self {
+            commands: Mutex::new(None),
+            status: Arc::new(RwLock::new(PlaybackStatus::default())),
+            events: broadcast::channel(64).0,
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<PlaybackStatus> {
+        self.events.subscribe()
+    }
+
+    pub fn status(&self) -> PlaybackStatus {
+        self.status.read().map(|status| status.clone()).unwrap_or_default()
+    }
+
+    /// 録音を `start_ms` から再生する（再生中の録音は止める）
+    pub fn play(&self, recording_id: &str, tracks: Vec<PlaybackTrack>, duration_ms: i64, start_ms: i64) -> AppResult<()> {
+        self.stop();
+
+        let (sender, receiver) = mpsc::channel();
+        let (ready_sender, ready_receiver) = mpsc::channel();
+        let status = self.status.clone();
+        let events = self.events.clone();
+        let recording_id = recording_id.to_string();
+        thread::spawn(move || {
+            run_playback(recording_id, tracks, duration_ms, start_ms, receiver, ready_sender, status, events);
+        });
+
+        // 出力デバイスを開けたかどうかを待ってから返す
+        ready_receiver.recv().map_err(|_| AppError::Playback {
+            message: "Playback thread exited unexpectedly".to_string(),
+        })??;
+        if let Ok(mut commands) = self.commands.lock() {
+            *commands = Some(sender);
+        }
+        Ok(())
+    }
+
+    pub fn pause(&self) -> AppResult<()> {
+        self.send(PlaybackCommand::Pause)
+    }
+
+    pub fn resume(&self) -> AppResult<()> {
+        self.send(PlaybackCommand::Resume)
+    }
+
+    /// 録音の先頭から `position_ms` の位置へ移る（一時停止中は一時停止のまま）
+    pub fn seek(&self, position_ms: i64) -> AppResult<()> {
+        if position_ms < 0 {
+            return Err(AppError::ValidationError {
+                message: "Position must not be negative".to_string(),
+            });
+        }
+        self.send(PlaybackCommand::Seek(position_ms))
+    }
+
+    pub fn stop(&self) {
+        if let Some(sender) = self.commands.lock().ok().and_then(|mut commands| commands.take()) {
+            let _ = sender.send(PlaybackCommand::Stop);
+        }
+    }
+
+    fn send(&self, command: PlaybackCommand) -> AppResult<()> {
+        let commands = self.commands.lock().map_err(|_| AppError::Playback {
+            message: "Playback state is unavailable".to_string(),
+        })?;
+        let sent = commands.as_ref().map(|sender| sender.send(command).is_ok()).unwrap_or(false);
+        if !sent {
+            return Err(AppError::InvalidOperation {
+                message: "Nothing is playing".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// 再生スレッド。出力ストリームはこのスレッドで作って持ち続ける
+#[allow(clippy::too_many_arguments)]
+fn run_playback(
+    recording_id: String,
+    tracks: Vec<PlaybackTrack>,
+    duration_ms: i64,
+    start_ms: i64,
+    commands: mpsc::Receiver<PlaybackCommand>,
+    ready: mpsc::Sender<AppResult<()>>,
+    status: Arc<RwLock<PlaybackStatus>>,
+    events: broadcast::Sender<PlaybackStatus>,
+) {
+    let buffer: Arc<Mutex<VecDeque<f32>>> = Arc::new(Mutex::new(VecDeque::new()));
+    let played_frames = Arc::new(AtomicU64::new(0));
+    let paused = Arc::new(AtomicBool::new(false));
+
+    let opened = open_output(&buffer, &played_frames, &paused).and_then(|(stream, config)| {
+        let mut source = PlaybackSource::open(tracks, config.sample_rate.0, config.channels)?;
+        source.seek(start_ms)?;
+        Ok((stream, config, source))
+    });
+    let (stream, config, mut source) = match opened {
+        Ok(opened) => opened,
+        Err(e) => {
+            let _ = ready.send(Err(e));
+            return;
+        }
+    };
+    let _ = ready.send(Ok(()));
+    log::info!("▶️ Playing recording {} from {}ms", recording_id, start_ms);
+
+    let output_rate = config.sample_rate.0.max(1) as u64;
+    let output_channels = config.channels.max(1) as usize;
+    let buffer_samples = (output_rate * BUFFER_MS / 1000) as usize * output_channels;
+    let mut base_ms = start_ms;
+    let mut finished = false;
+    let mut last_notified: Option<Instant> = None;
+
+    // 長さが分からない録音（0）では位置を切り詰めない
+    let clamp = |position_ms: i64| if duration_ms > 0 { position_ms.min(duration_ms) } else { position_ms };
+    let position = |base_ms: i64| clamp(base_ms + (played_frames.load(Ordering::Relaxed) * 1000 / output_rate) as i64);
+    let publish = |state: PlaybackState, position_ms: i64| {
+        let current = PlaybackStatus {
+            recording_id: Some(recording_id.clone()),
+            state,
+            position_ms,
+            duration_ms,
+        };
+        if let Ok(mut status) = status.write() {
+            *status = current.clone();
+        }
+        let _ = events.send(current);
+    };
+    publish(PlaybackState::Playing, start_ms);
+
+    loop {
+        match commands.recv_timeout(Duration::from_millis(20)) {
+            Ok(PlaybackCommand::Pause) => {
+                paused.store(true, Ordering::Relaxed);
+                let _ = stream.pause();
+                publish(PlaybackState::Paused, position(base_ms));
+            }
+            Ok(PlaybackCommand::Resume) => {
+                paused.store(false, Ordering::Relaxed);
+                let _ = stream.play();
+                publish(PlaybackState::Playing, position(base_ms));
+            }
+            Ok(PlaybackCommand::Seek(position_ms)) => {
+                let position_ms = clamp(position_ms);
+                if let Err(e) = source.seek(position_ms) {
+                    log::warn!("⚠️ Failed to seek playback: {}", e);
+                    continue;
+                }
+                if let Ok(mut buffer) = buffer.lock() {
+                    buffer.clear();
+                }
+                played_frames.store(0, Ordering::Relaxed);
+                base_ms = position_ms;
+                finished = false;
+                let state = if paused.load(Ordering::Relaxed) { PlaybackState::Paused } else { PlaybackState::Playing };
+                publish(state, position_ms);
+            }
+            Ok(PlaybackCommand::Stop) | Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+        }
+
+        // 出力ストリームが読む分を先にデコードしておく
+        while !finished && buffer.lock().map(|buffer| buffer.len() < buffer_samples).unwrap_or(false) {
+            match source.read() {
+                Ok(Some(samples)) => {
+                    if let Ok(mut buffer) = buffer.lock() {
+                        buffer.extend(samples);
+                    }
+                }
+                Ok(None) => finished = true,
+                Err(e) => {
+                    log::warn!("⚠️ Playback stopped: {}", e);
+                    finished = true;
+                }
+            }
+        }
+
+        if finished && buffer.lock().map(|buffer| buffer.is_empty()).unwrap_or(true) {
+            log::info!("⏹️ Playback of {} finished", recording_id);
+            break;
+        }
+        if !paused.load(Ordering::Relaxed) && last_notified.is_none_or(|notified| notified.elapsed() >= POSITION_INTERVAL) {
+            last_notified = Some(Instant::now());
+            publish(PlaybackState::Playing, position(base_ms));
+        }
+    }
+
+    // 出力ストリームは止めてから通知する
+    let _ = stream.pause();
+    let position_ms = if finished && duration_ms > 0 { duration_ms } else { position(base_ms) };
+    publish(PlaybackState::Stopped, position_ms);
+}
+
+/// 既定の出力デバイスを開き、`buffer` のサンプルを流すストリームを作る
+fn open_output(
+    buffer: &Arc<Mutex<VecDeque<f32>>>,
+    played_frames: &Arc<AtomicU64>,
+    paused: &Arc<AtomicBool>,
+) -> AppResult<(cpal::Stream, StreamConfig)> {
+    let host = cpal::default_host();
+    let device = host.default_output_device().ok_or_else(|| AppError::Playback {
+        message: "No default output device available".to_string(),
+    })?;
+    let supported = device.default_output_config().map_err(|e| AppError::Playback {
+        message: format!("Failed to get output device config: {}", e),
+    })?;
+    let config = StreamConfig {
+        channels: supported.channels(),
+        sample_rate: supported.sample_rate(),
+        buffer_size: cpal::BufferSize::Default,
+    };
+    log::info!(
+        "🔈 Using output device: {} ({} Hz, {} ch)",
+        device.name().unwrap_or_else(|_| "Unknown".to_string()),
+        config.sample_rate.0,
+        config.channels
+    );
+
+    let channels = config.channels.max(1) as usize;
+    let buffer = buffer.clone();
+    let played_frames = played_frames.clone();
+    let paused = paused.clone();
+    let stream = device
+        .build_output_stream(
+            &config,
+            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                let mut written = 0;
+                if !paused.load(Ordering::Relaxed) {
+                    if let Ok(mut buffer) = buffer.lock() {
+                        let available = data.len().min(buffer.len());
+                        for (slot, sample) in data.iter_mut().zip(buffer.drain(..available)) {
+                            *slot = sample;
+                            written += 1;
+                        }
+                    }
+                }
+                data[written..].fill(0.0);
+                played_frames.fetch_add((written / channels) as u64, Ordering::Relaxed);
+            },
+            |err| log::error!("Playback stream error: {}", err),
+            None,
+        )
+        .map_err(|e| AppError::Playback {
+            message: format!("Failed to build output stream: {}", e),
+        })?;
+    stream.play().map_err(|e| AppError::Playback {
+        message: format!("Failed to start output stream: {}", e),
+    })?;
+    Ok((stream, config))
+}
+
+fn playback_error(path: &Path, error: impl std::fmt::Display) -> AppError {
+    AppError::Playback {
+        message: format!("{:?}: {}", path, error),
+    }
+}
@@ -0,0 +1,68 @@
+//! 2つのプロバイダーで同時に要約し、先に成功した方を使う
+//!
+//! 不安定なローカルサーバーの裏にクラウドを控えておく用途を想定している。
+//! 勝った方が決まった時点で負けた方の Future は破棄され、HTTPリクエストも中断される。
+
+use crate::errors::AppResult;
+use crate::models::{Summary, SummaryStatus};
+use crate::services::llm::SummaryContext;
+use crate::services::{LLMService, ProviderRace};
+use std::future::Future;
+
+fn is_success(result: &AppResult<Summary>) -> bool {
+    matches!(result, Ok(summary) if !matches!(summary.status, SummaryStatus::Failed(_)))
+}
+
+/// 先に成功した結果を返す。片方が失敗した場合はもう片方の結果（失敗を含む）を待って返す
+pub async fn first_successful<A, B>(first: A, second: B) -> AppResult<Summary>
+where
+    A: Future<Output = AppResult<Summary>>,
+    B: Future<Output = AppResult<Summary>>,
+{
+    tokio::pin!(first);
+    tokio::pin!(second);
+
+    tokio::select! {
+        result = &mut first => {
+            if is_success(&result) {
+                return result;
+            }
+            log::warn!("⚠️ Primary provider failed in race, waiting for the secondary");
+            second.await
+        }
+        result = &mut second => {
+            if is_success(&result) {
+                return result;
+            }
+            log::warn!("⚠️ Secondary provider failed in race, waiting for the primary");
+            first.await
+        }
+    }
+}
+
+/// 設定した2つのプロバイダーへ同時に要約を依頼する
+pub async fn race_summaries(
+    race: &ProviderRace,
+    transcription_text: &str,
+    context: &SummaryContext,
+    transcription_id: String,
+) -> AppResult<Summary> {
+    log::info!(
+        "🏁 Racing summarization between {} and {}",
+        race.primary.model_name,
+        race.secondary.model_name
+    );
+
+    let primary = LLMService::new(race.primary.clone());
+    let secondary = LLMService::new(race.secondary.clone());
+    let summary = first_successful(
+        primary.summarize_text_with_context(transcription_text, context, transcription_id.clone()),
+        secondary.summarize_text_with_context(transcription_text, context, transcription_id),
+    )
+    .await?;
+
+    if !matches!(summary.status, SummaryStatus::Failed(_)) {
+        log::info!("🏆 Race won by {}", summary.model_used);
+    }
+    Ok(summary)
+}
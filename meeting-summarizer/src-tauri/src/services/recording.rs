@@ -1,18 +1,100 @@
 use crate::database::Database;
 use crate::errors::{AppError, AppResult};
-use crate::models::{Recording, RecordingSession};
+use crate::models::{MeetingNote, Recording, RecordingListItem, RecordingMarker, RecordingSegment, RecordingSession, RecordingTrack};
 use crate::services::audio_capture_cpal::AudioCapture;
+use crate::services::audio_convert;
+use crate::services::audio_probe;
+use crate::services::audio_edit::{self, AudioEdit, EditedRecording};
+use crate::services::capture_feed::{AudioChunk, CaptureFeed};
+use crate::services::capture_source::{AudioDevice, CaptureSource, DualSourceMode};
+use crate::services::device_fallback::CaptureDeviceEvent;
+use crate::services::disk_space::{self, LowDiskSpace};
+use crate::services::loudness::{self, LoudnessSummary};
+use crate::services::meeting_import::{self, ImportedMeeting};
+use crate::services::meeting_notes;
+use crate::services::recording_markers;
+use crate::services::recording_segments;
+use crate::services::recording_spool;
+use crate::services::recording_tracks;
+use crate::services::recording_progress::{RecordingProgress, HEARTBEAT_INTERVAL_MS};
+use crate::services::recording_encoder;
+use crate::services::recording_quality::RecordingQuality;
+use crate::services::sleep_inhibitor::SleepInhibitor;
+use crate::services::{InputGainSettings, LoudnessSettings, NoiseSuppressionSettings, RecordingProfile, SleepPreventionSettings};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{broadcast, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// 停止して保存した録音（どの経路で止めても `subscribe_stopped_recordings` に届く）
+#[derive(Debug, Clone)]
+pub struct StoppedRecording {
+    /// 録音中のセッションID（`start_recording` の戻り値）
+    pub session_id: String,
+    pub recording: Recording,
+}
+
+/// 録音の保存中に動かしたファイル（保存に失敗したら一時ファイルの名前に戻す）
+#[derive(Default)]
+struct PendingFiles {
+    /// (一時ファイル, 移した先)
+    moved: Vec<(PathBuf, PathBuf)>,
+    /// (変換元の WAV, 変換したファイル)
+    encoded: Vec<(PathBuf, PathBuf)>,
+}
+
+impl PendingFiles {
+    fn move_file(&mut self, from: &Path, to: &Path) -> AppResult<()> {
+        fs::rename(from, to)?;
+        self.moved.push((from.to_path_buf(), to.to_path_buf()));
+        Ok(())
+    }
+
+    /// 保存できたので、変換元の WAV を消す
+    fn commit(self) {
+        for (wav, _) in self.encoded {
+            if let Err(e) = fs::remove_file(&wav) {
+                log::warn!("⚠️ Failed to remove {:?} after encoding: {}", wav, e);
+            }
+        }
+    }
+
+    /// 保存できなかったので、変換したファイルを消して一時ファイルを元の名前に戻す
+    fn roll_back(self) {
+        for (_, output) in self.encoded {
+            let _ = fs::remove_file(output);
+        }
+        for (temp, moved) in self.moved.into_iter().rev() {
+            if let Err(e) = fs::rename(&moved, &temp) {
+                log::error!("❌ Failed to move {:?} back to {:?}: {}", moved, temp, e);
+            }
+        }
+    }
+}
+
 pub struct RecordingService {
     db: Arc<Database>,
     recordings_dir: PathBuf,
     current_session: Arc<Mutex<Option<RecordingSession>>>,
     audio_capture: Arc<Mutex<AudioCapture>>,
+    progress_sender: broadcast::Sender<RecordingProgress>,
+    /// 録音中に入力されたメモ（停止時に録音へ紐付けて保存）
+    session_notes: Arc<Mutex<Vec<MeetingNote>>>,
+    /// 録音中に付けたマーカー（停止時に録音へ紐付けて保存）
+    session_markers: Arc<Mutex<Vec<RecordingMarker>>>,
+    low_disk_sender: broadcast::Sender<LowDiskSpace>,
+    device_events: broadcast::Sender<CaptureDeviceEvent>,
+    stopped_sender: broadcast::Sender<StoppedRecording>,
+    /// 録音中の音声のチャンク配信
+    capture_feed: CaptureFeed,
+    /// 書き起こし用のコピーの音量の正規化
+    loudness: Arc<std::sync::RwLock<LoudnessSettings>>,
+    sleep_prevention: Arc<std::sync::RwLock<SleepPreventionSettings>>,
+    /// 録音中にスリープを止めている間だけ入る
+    sleep_inhibitor: Arc<std::sync::Mutex<Option<SleepInhibitor>>>,
+    /// 録音の開始・停止と起動時の復元を同時に行わないためのロック
+    lifecycle: Arc<Mutex<()>>,
 }
 
 impl RecordingService {
@@ -24,16 +106,38 @@ impl RecordingService {
 
         // オーディオキャプチャを初期化
         let audio_capture = AudioCapture::new()?;
+        let low_disk_sender = audio_capture.low_disk_sender();
+        let device_events = audio_capture.device_event_sender();
+        let capture_feed = audio_capture.capture_feed();
 
         Ok(Self {
             db,
             recordings_dir,
             current_session: Arc::new(Mutex::new(None)),
             audio_capture: Arc::new(Mutex::new(audio_capture)),
+            progress_sender: broadcast::channel(64).0,
+            session_notes: Arc::new(Mutex::new(Vec::new())),
+            session_markers: Arc::new(Mutex::new(Vec::new())),
+            low_disk_sender,
+            device_events,
+            stopped_sender: broadcast::channel(16).0,
+            capture_feed,
+            loudness: Arc::new(std::sync::RwLock::new(LoudnessSettings::default())),
+            sleep_prevention: Arc::new(std::sync::RwLock::new(SleepPreventionSettings::default())),
+            sleep_inhibitor: Arc::new(std::sync::Mutex::new(None)),
+            lifecycle: Arc::new(Mutex::new(())),
         })
     }
 
     pub async fn start_recording(&self) -> AppResult<String> {
+        self.start_recording_with_quality(None).await
+    }
+
+    /// 録音を開始する（`quality` を指定すると今回の録音だけ設定の音質の代わりに使う）
+    pub async fn start_recording_with_quality(&self, quality: Option<RecordingQuality>) -> AppResult<String> {
+        // 起動時の復元が終わるまで始めない
+        let _lifecycle = self.lifecycle.lock().await;
+
         // セッション状態をチェック
         {
             let current_session = self.current_session.lock().await;
@@ -53,14 +157,22 @@ impl RecordingService {
             fs::create_dir_all(&self.recordings_dir)?;
         }
 
+        // 空き容量が足りなければ始めない（録音中も録音スレッドが確認する）
+        let min_free_bytes = self.audio_capture.lock().await.min_free_bytes();
+        disk_space::ensure_free_space(&self.recordings_dir, min_free_bytes)?;
+
         // 一時ファイル名を生成
-        let timestamp = SystemTime::now()
+        let mut timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map_err(|_| AppError::InvalidOperation { 
                 message: "Failed to get system time".to_string() 
             })?
             .as_secs();
 
+        // 保存に失敗して復元を待っている一時ファイルとは重ならない名前にする
+        while self.recordings_dir.join(format!("recording_temp_{}.wav", timestamp)).exists() {
+            timestamp += 1;
+        }
         let temp_filename = format!("recording_temp_{}.wav", timestamp);
         let temp_file_path = self.recordings_dir.join(&temp_filename);
 
@@ -75,21 +187,439 @@ impl RecordingService {
         // 実際の音声録音を開始
         {
             let mut audio_capture = self.audio_capture.lock().await;
-            audio_capture.start_recording(&temp_file_path).await?;
+            let quality = quality.unwrap_or_else(|| audio_capture.quality());
+            audio_capture.start_recording_with_quality(&temp_file_path, quality).await?;
         } // Mutexガードがここでdropされる
 
         log::info!("Audio capture started successfully");
+        if self.sleep_prevention().enabled {
+            self.inhibit_sleep().await;
+        }
 
         // セッションを設定
         {
             let mut current_session = self.current_session.lock().await;
             *current_session = Some(session);
         }
+        self.session_notes.lock().await.clear();
+        self.session_markers.lock().await.clear();
+
+        self.spawn_progress_heartbeat(session_id.clone());
 
         Ok(session_id)
     }
 
+    /// 録音中にメモを追加する（録音開始からの経過時間を記録）
+    pub async fn add_live_note(&self, text: &str, important: bool, author: Option<String>) -> AppResult<MeetingNote> {
+        let text = meeting_notes::validate_note_text(text)?;
+        let session = self.current_session.lock().await.clone().ok_or_else(|| AppError::Recording {
+            message: "No active recording session".to_string(),
+        })?;
+
+        // 録音IDは停止時に確定するため、それまではセッションIDを入れておく
+        let mut note = MeetingNote::new(session.id.clone(), text).with_author(author);
+        note.offset_ms = Some(
+            chrono::Utc::now()
+                .signed_duration_since(session.start_time)
+                .num_milliseconds()
+                .max(0),
+        );
+        note.important = important;
+
+        self.session_notes.lock().await.push(note.clone());
+        Ok(note)
+    }
+
+    /// 録音中に入力されたメモ
+    pub async fn live_notes(&self) -> Vec<MeetingNote> {
+        self.session_notes.lock().await.clone()
+    }
+
+    /// 録音中にマーカーを付ける（録音開始からの経過時間を記録）
+    pub async fn add_marker(&self, label: Option<String>) -> AppResult<RecordingMarker> {
+        let session = self.current_session.lock().await.clone().ok_or_else(|| AppError::Recording {
+            message: "No active recording session".to_string(),
+        })?;
+
+        // 一時停止していた時間は音声に含まれないので除く
+        let paused_ms = self.audio_capture.lock().await.paused_duration().as_millis() as i64;
+        let offset_ms = (chrono::Utc::now()
+            .signed_duration_since(session.start_time)
+            .num_milliseconds()
+            - paused_ms)
+            .max(0);
+        // 録音IDは停止時に確定するため、それまではセッションIDを入れておく
+        let marker = RecordingMarker::new(session.id.clone(), offset_ms, recording_markers::normalize_label(label));
+
+        self.session_markers.lock().await.push(marker.clone());
+        log::info!("🔖 Marker added at {} ms", offset_ms);
+        Ok(marker)
+    }
+
+    /// 録音中に付けたマーカー
+    pub async fn live_markers(&self) -> Vec<RecordingMarker> {
+        self.session_markers.lock().await.clone()
+    }
+
+    /// 録音を一時停止する（再開するまでの音声は保存しない）
+    pub async fn pause_recording(&self) -> AppResult<()> {
+        if self.current_session.lock().await.is_none() {
+            return Err(AppError::Recording {
+                message: "No active recording session".to_string(),
+            });
+        }
+        self.audio_capture.lock().await.pause()?;
+        log::info!("⏸️ Recording paused");
+        Ok(())
+    }
+
+    pub async fn resume_recording(&self) -> AppResult<()> {
+        if self.current_session.lock().await.is_none() {
+            return Err(AppError::Recording {
+                message: "No active recording session".to_string(),
+            });
+        }
+        self.audio_capture.lock().await.resume();
+        log::info!("▶️ Recording resumed");
+        Ok(())
+    }
+
+    pub async fn is_paused(&self) -> bool {
+        self.audio_capture.lock().await.is_paused()
+    }
+
+    pub async fn input_gain(&self) -> InputGainSettings {
+        self.audio_capture.lock().await.input_gain()
+    }
+
+    /// 入力ゲインを設定する（録音中でもすぐに反映）
+    pub async fn set_input_gain(&self, settings: InputGainSettings) {
+        self.audio_capture.lock().await.set_input_gain(settings);
+    }
+
+    pub async fn noise_suppression(&self) -> NoiseSuppressionSettings {
+        NoiseSuppressionSettings {
+            enabled: self.audio_capture.lock().await.noise_suppression(),
+        }
+    }
+
+    /// ノイズ除去を切り替える（録音中でもすぐに反映）
+    pub async fn set_noise_suppression(&self, settings: NoiseSuppressionSettings) {
+        self.audio_capture.lock().await.set_noise_suppression(settings.enabled);
+    }
+
+    pub fn loudness(&self) -> LoudnessSettings {
+        self.loudness.read().map(|settings| *settings).unwrap_or_default()
+    }
+
+    /// 音量の正規化を設定する（次の録音・取り込みから反映）
+    pub fn set_loudness(&self, settings: LoudnessSettings) {
+        if let Ok(mut current) = self.loudness.write() {
+            *current = settings;
+        }
+    }
+
+    /// 録音中の音声を 16kHz モノラルのチャンクで受け取る（停止時に残りが `is_last` のチャンクで届く）
+    pub fn subscribe_audio_chunks(&self) -> broadcast::Receiver<AudioChunk> {
+        self.capture_feed.subscribe()
+    }
+
+    pub fn audio_chunk_secs(&self) -> u32 {
+        self.capture_feed.chunk_secs()
+    }
+
+    /// チャンクの長さ（秒）を設定する（録音中でも次のチャンクから反映）
+    pub fn set_audio_chunk_secs(&self, secs: u32) -> AppResult<()> {
+        self.capture_feed.set_chunk_secs(secs)
+    }
+
+    pub fn sleep_prevention(&self) -> SleepPreventionSettings {
+        self.sleep_prevention.read().map(|settings| *settings).unwrap_or_default()
+    }
+
+    /// 録音中のスリープ防止を切り替える（録音中でもすぐに反映）
+    pub async fn set_sleep_prevention(&self, settings: SleepPreventionSettings) {
+        if let Ok(mut current) = self.sleep_prevention.write() {
+            *current = settings;
+        }
+        let recording = self.current_session.lock().await.is_some();
+        if settings.enabled && recording {
+            self.inhibit_sleep().await;
+        } else {
+            self.allow_sleep();
+        }
+    }
+
+    /// 今スリープを止めているか
+    pub fn sleep_inhibited(&self) -> bool {
+        self.sleep_inhibitor
+            .lock()
+            .map(|inhibitor| inhibitor.is_some())
+            .unwrap_or(false)
+    }
+
+    /// スリープを止める。止められなくても録音は続ける
+    async fn inhibit_sleep(&self) {
+        if self.sleep_inhibited() {
+            return;
+        }
+        match SleepInhibitor::acquire("Recording a meeting").await {
+            Ok(acquired) => {
+                // 待っている間に無効にされたか、別の経路で止めていたら、取ったほうは drop して解除する
+                if !self.sleep_prevention().enabled {
+                    return;
+                }
+                if let Ok(mut inhibitor) = self.sleep_inhibitor.lock() {
+                    inhibitor.get_or_insert(acquired);
+                }
+            }
+            Err(e) => log::warn!("⚠️ Failed to prevent system sleep: {}", e),
+        }
+    }
+
+    fn allow_sleep(&self) {
+        if let Ok(mut inhibitor) = self.sleep_inhibitor.lock() {
+            inhibitor.take();
+        }
+    }
+
+    /// 録音の音声ファイル（分けた録音は区間ごと）を書き起こし用の16kHzモノラルWAVに変換したコピーを作る
+    ///
+    /// 正規化が有効なら音量もそろえる。無効で、元から16kHzモノラルのWAVならコピーは作らない。
+    pub async fn prepare_transcription_copies(&self, recording: &Recording) -> AppResult<Vec<LoudnessSummary>> {
+        let settings = self.loudness();
+        let target_lufs = settings.enabled.then_some(settings.target_lufs);
+        let files = recording_segments::audio_files(&self.db, recording).await?;
+        let recordings_dir = self.recordings_dir.clone();
+        let summaries = tokio::task::spawn_blocking(move || {
+            files
+                .iter()
+                .filter(|file| target_lufs.is_some() || !audio_convert::is_whisper_ready(file))
+                .map(|file| {
+                    let output = loudness::normalized_path(&recordings_dir, file);
+                    loudness::normalize_for_transcription(file, &output, target_lufs)
+                })
+                .collect::<AppResult<Vec<_>>>()
+        })
+        .await
+        .map_err(|e| AppError::AudioConversion {
+            message: format!("Conversion task failed: {}", e),
+        })??;
+        for summary in &summaries {
+            log::info!(
+                "🔄 Prepared transcription copy of {}: {:?} LUFS, {:.1} dB gain",
+                recording.id,
+                summary.input_lufs,
+                summary.gain_db
+            );
+        }
+        Ok(summaries)
+    }
+
+    /// 変換がうまくいかなくても録音・取り込みは失敗にせず、書き起こしの時に変換する
+    pub async fn prepare_for_transcription(&self, recording: &Recording) {
+        if let Err(e) = self.prepare_transcription_copies(recording).await {
+            log::warn!("⚠️ Failed to prepare transcription copy of {}: {}", recording.id, e);
+        }
+    }
+
+    /// 直近の入力のピーク音量（録音中でなければ 0）
+    pub async fn input_level(&self) -> f32 {
+        self.audio_capture.lock().await.input_level()
+    }
+
+    /// 録音の入力元
+    pub async fn capture_source(&self) -> CaptureSource {
+        self.audio_capture.lock().await.source().clone()
+    }
+
+    /// 録音の入力元を切り替える（録音中は変更できない）
+    pub async fn set_capture_source(&self, source: CaptureSource) -> AppResult<()> {
+        source.validate()?;
+        if self.current_session.lock().await.is_some() {
+            return Err(AppError::InvalidOperation {
+                message: "Cannot change the capture source while recording".to_string(),
+            });
+        }
+        self.audio_capture.lock().await.set_source(source);
+        Ok(())
+    }
+
+    /// システム音声の同時録音の設定
+    pub async fn dual_source(&self) -> DualSourceMode {
+        self.audio_capture.lock().await.dual_source()
+    }
+
+    /// 同時録音のエコーキャンセルの設定
+    pub async fn echo_cancellation(&self) -> bool {
+        self.audio_capture.lock().await.echo_cancellation()
+    }
+
+    /// 入力元と同時録音の設定をまとめて切り替える（録音中は変更できない）
+    pub async fn apply_recording_profile(&self, profile: &RecordingProfile) -> AppResult<()> {
+        profile.dual_source.validate_with(&profile.source)?;
+        if profile.echo_cancellation && profile.dual_source == DualSourceMode::Off {
+            return Err(AppError::ValidationError {
+                message: "Echo cancellation needs system audio recorded alongside the microphone".to_string(),
+            });
+        }
+        recording_tracks::validate_extra_tracks(&profile.source, &profile.extra_tracks)?;
+        let max_segment = recording_segments::max_segment_duration(profile.max_segment_minutes)?;
+        self.set_capture_source(profile.source.clone()).await?;
+        let mut audio_capture = self.audio_capture.lock().await;
+        audio_capture.set_dual_source(profile.dual_source);
+        audio_capture.set_echo_cancellation(profile.echo_cancellation);
+        audio_capture.set_extra_tracks(profile.extra_tracks.clone());
+        audio_capture.set_quality(profile.quality);
+        audio_capture.set_encoding(profile.format);
+        audio_capture.set_max_segment_duration(max_segment);
+        Ok(())
+    }
+
+    /// 設定の音質
+    pub async fn quality(&self) -> RecordingQuality {
+        self.audio_capture.lock().await.quality()
+    }
+
+    /// 録音ファイルの保存先
+    pub fn recordings_dir(&self) -> &Path {
+        &self.recordings_dir
+    }
+
+    /// 録音中の空き容量の警告と、空き容量不足による停止を購読する
+    ///
+    /// 停止の通知を受けたら `stop_recording` を呼んで録音を保存する。
+    pub fn subscribe_low_disk_space(&self) -> broadcast::Receiver<LowDiskSpace> {
+        self.low_disk_sender.subscribe()
+    }
+
+    /// 停止して保存した録音を受け取る（画面・トレイ・自動停止など、止めた経路を問わない）
+    pub fn subscribe_stopped_recordings(&self) -> broadcast::Receiver<StoppedRecording> {
+        self.stopped_sender.subscribe()
+    }
+
+    /// 前回の起動中に落ちて残った録音の一時ファイルを録音として登録する（起動時に呼ぶ）
+    ///
+    /// 復元が終わるまで録音の開始・停止を待たせ、録音中のセッションの一時ファイルには触れない。
+    pub async fn recover_orphaned_recordings(&self) -> AppResult<Vec<Recording>> {
+        let _lifecycle = self.lifecycle.lock().await;
+        let active = self
+            .current_session
+            .lock()
+            .await
+            .as_ref()
+            .map(|session| PathBuf::from(&session.temp_file_path));
+        recording_spool::recover_orphaned_recordings(&self.db, &self.recordings_dir, active.as_deref()).await
+    }
+
+    /// 録音中のセッションID
+    pub async fn current_session_id(&self) -> Option<String> {
+        self.current_session.lock().await.as_ref().map(|session| session.id.clone())
+    }
+
+    /// 録音に必要な空き容量（0 なら確認しない）
+    pub async fn min_free_bytes(&self) -> u64 {
+        self.audio_capture.lock().await.min_free_bytes()
+    }
+
+    /// 別に録った WAV（スマートフォンのマイクなど）を保存済みの録音の次のトラックとして移す
+    pub async fn attach_track(&self, recording: &Recording, path: &Path, device_name: &str) -> AppResult<RecordingTrack> {
+        let index = self
+            .db
+            .get_recording_tracks(&recording.id)
+            .await?
+            .iter()
+            .map(|track| track.track_index)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let track_path = recording_tracks::track_path(&Path::new(&recording.file_path).with_extension("wav"), index as usize);
+        fs::rename(path, &track_path)?;
+
+        let track = RecordingTrack::new(
+            recording.id.clone(),
+            index,
+            device_name.to_string(),
+            track_path.to_string_lossy().to_string(),
+            recording_segments::wav_duration_ms(&track_path).unwrap_or(0),
+        )
+        .with_file_size(fs::metadata(&track_path)?.len() as i64);
+        self.db.create_recording_track(&track).await?;
+        log::info!("🎙️ Attached track {} from {}", index, device_name);
+        Ok(track)
+    }
+
+    /// 録音に必要な空き容量（None なら確認しない）
+    pub async fn set_min_free_space(&self, min_free_bytes: Option<u64>) {
+        self.audio_capture.lock().await.set_min_free_bytes(min_free_bytes);
+    }
+
+    /// 録音中の入力デバイスの取り外しと、既定のデバイスでの再開を購読する
+    pub fn subscribe_device_changes(&self) -> broadcast::Receiver<CaptureDeviceEvent> {
+        self.device_events.subscribe()
+    }
+
+    /// 録音の進捗イベントを購読する
+    pub fn subscribe_progress(&self) -> broadcast::Receiver<RecordingProgress> {
+        self.progress_sender.subscribe()
+    }
+
+    /// 現在の録音の進捗（録音中でなければ None）
+    pub async fn current_progress(&self) -> Option<RecordingProgress> {
+        let session_id = self.current_session.lock().await.as_ref()?.id.clone();
+        let audio_capture = self.audio_capture.lock().await;
+        Some(Self::progress_of(&session_id, &audio_capture))
+    }
+
+    fn progress_of(session_id: &str, audio_capture: &AudioCapture) -> RecordingProgress {
+        let format = audio_capture.output_format();
+        RecordingProgress::new(
+            session_id.to_string(),
+            audio_capture.get_recording_duration().as_millis() as u64,
+            audio_capture.recorded_sample_count(),
+            format.sample_rate,
+            format.channels,
+        )
+        .with_bits_per_sample(format.bits_per_sample)
+        .with_paused(audio_capture.is_paused())
+    }
+
+    /// セッションが終わるまで一定間隔で進捗を配信する
+    fn spawn_progress_heartbeat(&self, session_id: String) {
+        let current_session = self.current_session.clone();
+        let audio_capture = self.audio_capture.clone();
+        let sender = self.progress_sender.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_millis(HEARTBEAT_INTERVAL_MS));
+            loop {
+                interval.tick().await;
+
+                let active = current_session
+                    .lock()
+                    .await
+                    .as_ref()
+                    .is_some_and(|session| session.id == session_id);
+                if !active {
+                    break;
+                }
+
+                // 停止処理中はキャプチャがロックされているので、その回は送らない
+                let Ok(audio_capture) = audio_capture.try_lock() else {
+                    continue;
+                };
+                if !audio_capture.is_recording() {
+                    continue;
+                }
+                // 購読者がいなくても録音は続ける
+                let _ = sender.send(Self::progress_of(&session_id, &audio_capture));
+            }
+            log::info!("⏱️ Recording heartbeat stopped for session {}", session_id);
+        });
+    }
+
     pub async fn stop_recording(&self) -> AppResult<Recording> {
+        let _lifecycle = self.lifecycle.lock().await;
         log::info!("Stopping recording");
         // current_sessionをlogに出力
         log::info!("Current session: {:?}", self.current_session);
@@ -103,11 +633,66 @@ impl RecordingService {
         };
 
         // 実際の音声録音を停止
-        {
+        let stopped = {
             let mut audio_capture = self.audio_capture.lock().await;
-            audio_capture.stop_recording().await?;
-        } // Mutexガードがここでdropされる
+            let stopped = audio_capture.stop_recording().await;
+            self.allow_sleep();
+            stopped.map(|_| (audio_capture.paused_duration().as_secs() as i64, audio_capture.extra_tracks().to_vec()))
+        }; // Mutexガードがここでdropされる
+
+        // キャプチャは止まったので、この後の保存に失敗しても次の録音を始められるようセッションを外す
+        self.current_session.lock().await.take();
+        let notes = std::mem::take(&mut *self.session_notes.lock().await);
+        let markers = std::mem::take(&mut *self.session_markers.lock().await);
+        let (paused_secs, extra_tracks) = stopped?;
+
+        let mut files = PendingFiles::default();
+        let mut created = None;
+        let saved = self
+            .save_stopped_recording(&session, paused_secs, &extra_tracks, notes, markers, &mut files, &mut created)
+            .await;
+        let recording = match saved {
+            Ok(recording) => {
+                files.commit();
+                recording
+            }
+            Err(e) => {
+                // 作りかけの行とファイルを片付け、一時 WAV は次の起動時の復元に任せる
+                if let Some(recording_id) = created {
+                    if let Err(delete_error) = self.db.delete_recording(&recording_id).await {
+                        log::warn!("⚠️ Failed to remove the partially saved recording {}: {}", recording_id, delete_error);
+                    }
+                }
+                files.roll_back();
+                log::error!("❌ Failed to save the recording, leaving it for recovery: {}", e);
+                return Err(e);
+            }
+        };
+
+        self.prepare_for_transcription(&recording).await;
+        let _ = self.stopped_sender.send(StoppedRecording {
+            session_id: session.id.clone(),
+            recording: recording.clone(),
+        });
 
+        Ok(recording)
+    }
+
+    /// 止めた録音を保存する
+    ///
+    /// 一時 WAV は `files` に記録しながら移し、行を作ったら `created` に入れる。
+    /// 失敗したら呼び出し元が行を消して `files` を戻す。
+    #[allow(clippy::too_many_arguments)]
+    async fn save_stopped_recording(
+        &self,
+        session: &RecordingSession,
+        paused_secs: i64,
+        extra_tracks: &[CaptureSource],
+        notes: Vec<MeetingNote>,
+        markers: Vec<RecordingMarker>,
+        files: &mut PendingFiles,
+        created: &mut Option<String>,
+    ) -> AppResult<Recording> {
         // 一時ファイルの存在確認
         let temp_path = std::path::Path::new(&session.temp_file_path);
         log::info!("Checking temp file existence: {:?}", temp_path);
@@ -126,10 +711,12 @@ impl RecordingService {
             log::warn!("Temp file is empty: {:?}", temp_path);
         }
 
-        // 録音時間を計算（秒）
-        let duration = chrono::Utc::now()
+        // 録音時間を計算（秒。一時停止していた時間を除く）
+        let duration = (chrono::Utc::now()
             .signed_duration_since(session.start_time)
-            .num_seconds();
+            .num_seconds()
+            - paused_secs)
+            .max(0);
 
         // 一時ファイルを最終的な場所に移動（セッションIDを含めて一意化）
         let final_filename = format!(
@@ -137,42 +724,187 @@ impl RecordingService {
             session.start_time.format("%Y%m%d_%H%M%S"),
             session.id
         );
-        let final_path = self.recordings_dir.join(&final_filename);
-
-        log::info!("Moving temp file from {:?} to {:?}", temp_path, final_path);
-        fs::rename(&session.temp_file_path, &final_path)?;
+        let wav_path = self.recordings_dir.join(&final_filename);
+
+        // 区間ごとに分けた録音は、2つ目以降のファイルも同じ名前に `_part002` などを付けて移す
+        let mut parts = Vec::new();
+        for (index, temp_part) in recording_segments::existing_segments(temp_path).into_iter().enumerate() {
+            let part_path = recording_segments::segment_path(&wav_path, index);
+            log::info!("Moving temp file from {:?} to {:?}", temp_part, part_path);
+            files.move_file(&temp_part, &part_path)?;
+            let duration_ms = recording_segments::wav_duration_ms(&part_path).unwrap_or(0);
+            parts.push((part_path, duration_ms));
+        }
 
-        // ファイルサイズを取得
-        let file_size = fs::metadata(&final_path)?.len() as i64;
+        // 設定に応じて FLAC・Opus・MP3 に変換する（WAV は保存が終わるまで残す）
+        let encoding = self.audio_capture.lock().await.encoding();
+        let wav_parts: Vec<PathBuf> = parts.iter().map(|(path, _)| path.clone()).collect();
+        let encoded = tokio::task::spawn_blocking(move || {
+            wav_parts
+                .into_iter()
+                .map(|path| {
+                    let output = recording_encoder::encode_copy_or_keep(&path, encoding);
+                    (path, output)
+                })
+                .collect::<Vec<_>>()
+        })
+        .await
+        .map_err(|e| AppError::Recording {
+            message: format!("Encoding task failed: {}", e),
+        })?;
+        for (part, (wav, output)) in parts.iter_mut().zip(encoded) {
+            if output != wav {
+                part.0 = output.clone();
+                files.encoded.push((wav, output));
+            }
+        }
+        let final_path = parts.first().map(|(path, _)| path.clone()).unwrap_or_else(|| wav_path.clone());
+        let final_filename = final_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or(final_filename);
+
+        // ファイルサイズを取得（分けた録音は全区間の合計）
+        let mut file_size = 0;
+        for (path, _) in &parts {
+            file_size += fs::metadata(path)?.len() as i64;
+        }
 
-        // Recording オブジェクトを作成
-        let recording = Recording::new(
+        // Recording オブジェクトを作成（長さ・サンプルレート・チャンネル数はファイルから読み取る）
+        let mut recording = Recording::new(
             final_filename,
             final_path.to_string_lossy().to_string(),
         )
         .with_duration(duration)
         .with_file_size(file_size);
+        let paths: Vec<PathBuf> = parts.iter().map(|(path, _)| path.clone()).collect();
+        audio_probe::fill_recording(&mut recording, &paths);
 
         // データベースに保存
         self.db.create_recording(&recording).await?;
+        *created = Some(recording.id.clone());
+
+        // 分けたファイルを録音に紐付ける
+        if parts.len() > 1 {
+            let mut offset_ms = 0;
+            for (index, (path, duration_ms)) in parts.iter().enumerate() {
+                let segment = RecordingSegment::new(
+                    recording.id.clone(),
+                    index as i32,
+                    path.to_string_lossy().to_string(),
+                    offset_ms,
+                    *duration_ms,
+                )
+                .with_file_size(fs::metadata(path)?.len() as i64);
+                self.db.create_recording_segment(&segment).await?;
+                offset_ms += duration_ms;
+            }
+            log::info!("✂️ Recording saved in {} segments", parts.len());
+        }
+
+        // 別の入力デバイスから録音したトラックも同じ名前に `_track01` などを付けて移す（トラックは WAV のまま）
+        for (index, temp_track) in recording_tracks::existing_tracks(temp_path, extra_tracks.len()) {
+            let track_path = recording_tracks::track_path(&wav_path, index);
+            files.move_file(&temp_track, &track_path)?;
+            let device_name = extra_tracks.get(index - 1).map(CaptureSource::label).unwrap_or_default();
+            let track = RecordingTrack::new(
+                recording.id.clone(),
+                index as i32,
+                device_name,
+                track_path.to_string_lossy().to_string(),
+                recording_segments::wav_duration_ms(&track_path).unwrap_or(0),
+            )
+            .with_file_size(fs::metadata(&track_path)?.len() as i64);
+            self.db.create_recording_track(&track).await?;
+            log::info!("🎙️ Saved track {} from {}", index, track.device_name);
+        }
+
+        // 録音中に入力されたメモを録音に紐付ける
+        for mut note in notes {
+            note.recording_id = recording.id.clone();
+            self.db.create_meeting_note(&note).await?;
+        }
+
+        for mut marker in markers {
+            marker.recording_id = recording.id.clone();
+            self.db.create_marker(&marker).await?;
+        }
+
+        Ok(recording)
+    }
+
+    /// 録音を停止し、録音一覧には登録せず差し替え用スニペットとして保存する
+    pub async fn stop_snippet_recording(&self) -> AppResult<PathBuf> {
+        let session = {
+            let current_session = self.current_session.lock().await;
+            current_session.clone().ok_or_else(|| AppError::Recording {
+                message: "No active recording session".to_string(),
+            })?
+        };
+
+        {
+            let mut audio_capture = self.audio_capture.lock().await;
+            let stopped = audio_capture.stop_recording().await;
+            self.allow_sleep();
+            stopped?;
+        }
+
+        let temp_path = Path::new(&session.temp_file_path);
+        if !temp_path.exists() {
+            return Err(AppError::Recording {
+                message: format!("Temp file not found: {}", session.temp_file_path),
+            });
+        }
+
+        self.session_notes.lock().await.clear();
+        self.session_markers.lock().await.clear();
+
+        let snippets_dir = self.recordings_dir.join("snippets");
+        fs::create_dir_all(&snippets_dir)?;
+        let snippet_path = snippets_dir.join(format!(
+            "snippet_{}_{}.wav",
+            session.start_time.format("%Y%m%d_%H%M%S"),
+            session.id
+        ));
+        fs::rename(temp_path, &snippet_path)?;
 
-        // ここまで成功したら、セッションをクリア
         {
             let mut current_session = self.current_session.lock().await;
             *current_session = None;
         }
 
-        Ok(recording)
+        log::info!("🎙️ Saved replacement snippet: {:?}", snippet_path);
+        Ok(snippet_path)
     }
 
+    /// アーカイブ済みを除いた録音一覧
     pub async fn get_recordings(&self) -> AppResult<Vec<Recording>> {
-        self.db.get_all_recordings().await
+        self.db.get_recordings(false).await
+    }
+
+    pub async fn get_recordings_including_archived(&self) -> AppResult<Vec<Recording>> {
+        self.db.get_recordings(true).await
+    }
+
+    /// 書き起こし・要約の状態付きの録音一覧
+    pub async fn get_recordings_with_status(&self, include_archived: bool) -> AppResult<Vec<RecordingListItem>> {
+        self.db.get_recordings_with_status(include_archived).await
     }
 
     pub async fn get_recording(&self, id: &str) -> AppResult<Option<Recording>> {
         self.db.get_recording(id).await
     }
 
+    /// 区間ごとに分けて保存した録音のファイル（分けていない録音は空）
+    pub async fn get_recording_segments(&self, id: &str) -> AppResult<Vec<RecordingSegment>> {
+        self.db.get_recording_segments(id).await
+    }
+
+    /// 別の入力デバイスから録音したトラック
+    pub async fn get_recording_tracks(&self, id: &str) -> AppResult<Vec<RecordingTrack>> {
+        self.db.get_recording_tracks(id).await
+    }
+
     pub async fn delete_recording(&self, id: &str) -> AppResult<bool> {
         // データベースから録音情報を取得
         if let Some(recording) = self.db.get_recording(id).await? {
@@ -181,6 +913,36 @@ impl RecordingService {
             if file_path.exists() {
                 fs::remove_file(file_path)?;
             }
+            for segment in self.db.get_recording_segments(id).await? {
+                let segment_path = Path::new(&segment.file_path);
+                if segment_path.exists() {
+                    fs::remove_file(segment_path)?;
+                }
+            }
+            for track in self.db.get_recording_tracks(id).await? {
+                let track_path = Path::new(&track.file_path);
+                if track_path.exists() {
+                    fs::remove_file(track_path)?;
+                }
+            }
+
+            // 取り込み時の添付ファイルを削除
+            let attachments_dir = self.recordings_dir.join("attachments").join(id);
+            if attachments_dir.exists() {
+                fs::remove_dir_all(&attachments_dir)?;
+            }
+
+            // 編集前の録音と、書き起こし用のコピーを削除
+            let backup = audio_edit::backup_path(&self.recordings_dir, file_path);
+            if backup.exists() {
+                fs::remove_file(backup)?;
+            }
+            for audio_file in recording_segments::audio_files(&self.db, &recording).await? {
+                let normalized = loudness::normalized_path(&self.recordings_dir, &audio_file);
+                if normalized.exists() {
+                    fs::remove_file(normalized)?;
+                }
+            }
             
             // データベースから削除
             self.db.delete_recording(id).await
@@ -189,6 +951,40 @@ impl RecordingService {
         }
     }
 
+    /// 録音を切り出す・範囲を削除する（書き起こし用のコピーも作り直す）
+    pub async fn edit_recording(
+        &self,
+        id: &str,
+        edit: AudioEdit,
+        start_ms: i64,
+        end_ms: i64,
+        invalidate_transcriptions: bool,
+    ) -> AppResult<EditedRecording> {
+        let edited = audio_edit::edit_recording(
+            &self.db,
+            &self.recordings_dir,
+            id,
+            edit,
+            start_ms,
+            end_ms,
+            invalidate_transcriptions,
+        )
+        .await?;
+        let normalized = loudness::normalized_path(&self.recordings_dir, Path::new(&edited.recording.file_path));
+        if normalized.exists() {
+            fs::remove_file(normalized)?;
+        }
+        self.prepare_for_transcription(&edited.recording).await;
+        Ok(edited)
+    }
+
+    /// Zoom / Teams の録画フォルダを取り込む
+    pub async fn import_meeting_folder(&self, folder: &Path) -> AppResult<ImportedMeeting> {
+        let imported = meeting_import::import_meeting_folder(&self.db, folder, &self.recordings_dir).await?;
+        self.prepare_for_transcription(&imported.recording).await;
+        Ok(imported)
+    }
+
     pub fn is_recording(&self) -> bool {
         // セッション状態とオーディオキャプチャ状態の両方をチェック
         let session_active = self.current_session.try_lock()
@@ -220,7 +1016,7 @@ impl RecordingService {
     }
 
     // オーディオデバイス情報を取得
-    pub fn get_audio_devices(&self) -> AppResult<Vec<String>> {
+    pub fn get_audio_devices(&self) -> AppResult<Vec<AudioDevice>> {
         crate::services::audio_capture_cpal::get_audio_devices()
     }
 }
\ No newline at end of file
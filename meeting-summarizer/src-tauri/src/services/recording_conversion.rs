@@ -0,0 +1,118 @@
+//! 保存済み録音の形式変換
+//!
+//! 録音ファイルを Rust のデコード・エンコード処理で別形式に変換して元ファイルの隣に書き出す。
+//! `replace_original` を指定すると録音の参照先を変換後のファイルに切り替え、元ファイルを削除する。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::services::audio_convert::{self, ConvertedAudio, WHISPER_SAMPLE_RATE};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingConversion {
+    pub recording_id: String,
+    pub target_format: String,
+    pub output_path: String,
+    pub file_size: i64,
+    pub duration_secs: f64,
+    /// 録音の参照先を変換後のファイルに切り替えたか
+    pub replaced_original: bool,
+}
+
+/// 変換後のファイルのパス（元と同じパスになる場合は `_converted` を付ける）
+pub fn output_path_for(input: &Path, target_format: &str) -> PathBuf {
+    let stem = input
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "recording".to_string());
+    let (suffix, extension) = match target_format {
+        "whisper" => ("_16k", "wav"),
+        other => ("", other),
+    };
+
+    let output = input.with_file_name(format!("{}{}.{}", stem, suffix, extension));
+    if output == input {
+        input.with_file_name(format!("{}_converted.{}", stem, extension))
+    } else {
+        output
+    }
+}
+
+/// 録音を `target_format`（wav / flac / whisper）に変換する
+pub async fn convert_recording(
+    database: &Database,
+    recording_id: &str,
+    target_format: &str,
+    replace_original: bool,
+) -> AppResult<RecordingConversion> {
+    let target_format = target_format.trim().to_lowercase();
+    if !audio_convert::TRANSCODE_FORMATS.contains(&target_format.as_str()) {
+        return Err(AppError::ValidationError {
+            message: format!(
+                "Unsupported target format: {} (supported: {})",
+                target_format,
+                audio_convert::TRANSCODE_FORMATS.join(", ")
+            ),
+        });
+    }
+
+    let mut recording = database
+        .get_recording(recording_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording with id {} not found", recording_id),
+        })?;
+
+    let input = PathBuf::from(&recording.file_path);
+    if !input.exists() {
+        return Err(AppError::FileNotFound {
+            path: recording.file_path.clone(),
+        });
+    }
+    let output = output_path_for(&input, &target_format);
+
+    let (task_input, task_output, task_format) = (input.clone(), output.clone(), target_format.clone());
+    let converted: ConvertedAudio =
+        tokio::task::spawn_blocking(move || audio_convert::transcode(&task_input, &task_output, &task_format))
+            .await
+            .map_err(|e| AppError::AudioConversion {
+                message: format!("Conversion task failed: {}", e),
+            })??;
+
+    let file_size = std::fs::metadata(&output)?.len() as i64;
+
+    if replace_original {
+        recording.filename = output
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| recording.filename.clone());
+        recording.file_path = output.to_string_lossy().to_string();
+        recording.file_size = Some(file_size);
+        if target_format == "whisper" {
+            recording.sample_rate = Some(WHISPER_SAMPLE_RATE as i32);
+            recording.channels = Some(1);
+        }
+        database.update_recording(&recording).await?;
+
+        if let Err(e) = std::fs::remove_file(&input) {
+            log::warn!("⚠️ Failed to remove original recording file {:?}: {}", input, e);
+        }
+    }
+
+    log::info!(
+        "🔄 Converted recording {} to {} ({} bytes)",
+        recording_id,
+        target_format,
+        file_size
+    );
+
+    Ok(RecordingConversion {
+        recording_id: recording_id.to_string(),
+        target_format,
+        output_path: output.to_string_lossy().to_string(),
+        file_size,
+        duration_secs: converted.duration_secs,
+        replaced_original: replace_original,
+    })
+}
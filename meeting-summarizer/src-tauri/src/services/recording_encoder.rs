@@ -0,0 +1,154 @@
+//! 録音の保存形式
+//!
+//! 1時間の会議を WAV で残すと数百MBになるため、録音の停止後に FLAC・Opus・MP3 へ変換して保存できる。
+//! FLAC は組み込みのエンコーダー（`flac_encoder`）で書き出す。Opus と MP3 は ffmpeg に任せるため、
+//! ffmpeg が無い環境では選択肢に出さず、設定もできない（設定後に ffmpeg を消した場合は WAV のまま残す）。
+//!
+//! Opus は symphonia でデコードできないので、書き起こし時は ffmpeg で 16kHz モノラル WAV に戻す
+//! （`audio_convert::prepare_for_whisper`）。
+
+use crate::errors::{AppError, AppResult};
+use crate::services::audio_convert;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordingFormat {
+    #[default]
+    Wav,
+    /// 可逆圧縮（16bit）
+    Flac,
+    /// 音声向けの非可逆圧縮（ffmpeg が必要）
+    Opus,
+    /// 非可逆圧縮（ffmpeg が必要）
+    Mp3,
+}
+
+impl RecordingFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Flac => "flac",
+            Self::Opus => "opus",
+            Self::Mp3 => "mp3",
+        }
+    }
+
+    /// 変換に ffmpeg が必要か
+    pub fn requires_ffmpeg(&self) -> bool {
+        matches!(self, Self::Opus | Self::Mp3)
+    }
+
+    /// ffmpeg のエンコーダーの引数
+    fn ffmpeg_codec_args(&self) -> &'static [&'static str] {
+        match self {
+            Self::Opus => &["-c:a", "libopus", "-b:a", "48k"],
+            Self::Mp3 => &["-c:a", "libmp3lame", "-q:a", "4"],
+            Self::Wav | Self::Flac => &[],
+        }
+    }
+}
+
+/// ffmpeg が使えるか
+pub fn ffmpeg_available() -> bool {
+    Command::new("ffmpeg")
+        .arg("-version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// ffmpeg で `input` を変換する（`args` は入力と出力の間に入れる引数）
+pub fn run_ffmpeg(input: &Path, output: &Path, args: &[&str]) -> AppResult<()> {
+    let result = Command::new("ffmpeg")
+        .arg("-y")
+        .arg("-loglevel")
+        .arg("error")
+        .arg("-i")
+        .arg(input)
+        .args(args)
+        .arg(output)
+        .output()
+        .map_err(|e| AppError::AudioConversion {
+            message: format!("Failed to run ffmpeg: {}", e),
+        })?;
+    if !result.status.success() {
+        return Err(AppError::AudioConversion {
+            message: format!("ffmpeg failed: {}", String::from_utf8_lossy(&result.stderr).trim()),
+        });
+    }
+    Ok(())
+}
+
+/// 保存した WAV を `format` へ変換したファイルを隣に書き出し、そのパスを返す（WAV は残す）
+pub fn encode_copy(wav_path: &Path, format: RecordingFormat) -> AppResult<PathBuf> {
+    if format == RecordingFormat::Wav {
+        return Ok(wav_path.to_path_buf());
+    }
+    let output = wav_path.with_extension(format.extension());
+
+    let result = if format.requires_ffmpeg() {
+        run_ffmpeg(wav_path, &output, format.ffmpeg_codec_args())
+    } else {
+        audio_convert::transcode(wav_path, &output, format.extension()).map(|_| ())
+    };
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&output);
+        return Err(e);
+    }
+
+    log::info!("🗜️ Encoded recording as {}: {:?}", format.extension(), output);
+    Ok(output)
+}
+
+/// 保存した WAV を `format` へ変換し、成功したら WAV を削除して変換後のパスを返す
+pub fn encode_recording(wav_path: &Path, format: RecordingFormat) -> AppResult<PathBuf> {
+    let output = encode_copy(wav_path, format)?;
+    if output != wav_path {
+        std::fs::remove_file(wav_path)?;
+    }
+    Ok(output)
+}
+
+/// `encode_copy` が失敗した場合は WAV のパスを返す（録音を失わないため）
+pub fn encode_copy_or_keep(wav_path: &Path, format: RecordingFormat) -> PathBuf {
+    match encode_copy(wav_path, format) {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!("⚠️ Keeping the recording as WAV, {} encoding failed: {}", format.extension(), e);
+            wav_path.to_path_buf()
+        }
+    }
+}
+
+/// `encode_recording` が失敗した場合は WAV のまま残す（録音を失わないため）
+pub fn encode_or_keep(wav_path: &Path, format: RecordingFormat) -> PathBuf {
+    match encode_recording(wav_path, format) {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!("⚠️ Keeping the recording as WAV, {} encoding failed: {}", format.extension(), e);
+            wav_path.to_path_buf()
+        }
+    }
+}
+
+/// この環境で保存できる形式（ffmpeg が無ければ Opus・MP3 は含めない）
+pub fn format_options() -> Vec<RecordingFormat> {
+    let ffmpeg = ffmpeg_available();
+    [RecordingFormat::Wav, RecordingFormat::Flac, RecordingFormat::Opus, RecordingFormat::Mp3]
+        .into_iter()
+        .filter(|format| !format.requires_ffmpeg() || ffmpeg)
+        .collect()
+}
+
+/// この環境で `format` に保存できることを確かめる
+pub fn ensure_supported(format: RecordingFormat) -> AppResult<()> {
+    if format.requires_ffmpeg() && !ffmpeg_available() {
+        return Err(AppError::ValidationError {
+            message: format!("Saving recordings as {} requires ffmpeg", format.extension()),
+        });
+    }
+    Ok(())
+}
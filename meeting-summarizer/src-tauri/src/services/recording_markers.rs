@@ -0,0 +1,144 @@
+//! 録音中のマーカー（ブックマーク）
+//!
+//! 録音中に付けたマーカーは録音開始からの経過時間とともに保存し、波形表示に使う。
+//! 要約時にはマーカー付近の発言を抜き出してLLMに渡し、重要ポイント抽出の手がかりにする。
+//! 書き起こしの表示では、発言の間にマーカーを時刻順に並べる。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{RecordingMarker, TranscriptSegment};
+use serde::{Deserialize, Serialize};
+
+/// マーカーの前後に含める発言の範囲
+pub const MARKER_CONTEXT_MS: i64 = 15_000;
+
+/// ラベルの最大文字数
+pub const MAX_MARKER_LABEL_CHARS: usize = 200;
+
+/// マーカーを付けた場面の発言
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarkerExcerpt {
+    pub offset_ms: i64,
+    pub label: Option<String>,
+    pub text: String,
+}
+
+/// 書き起こしの表示に並べる項目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TimelineItem {
+    Segment(TranscriptSegment),
+    Marker(RecordingMarker),
+}
+
+impl TimelineItem {
+    /// 録音開始からの時刻
+    pub fn offset_ms(&self) -> i64 {
+        match self {
+            Self::Segment(segment) => segment.start_ms,
+            Self::Marker(marker) => marker.offset_ms,
+        }
+    }
+}
+
+/// ラベルを整える（空なら None、長すぎる場合は切り詰める）
+pub fn normalize_label(label: Option<String>) -> Option<String> {
+    let label = label?.trim().to_string();
+    if label.is_empty() {
+        return None;
+    }
+    Some(label.chars().take(MAX_MARKER_LABEL_CHARS).collect())
+}
+
+/// 各マーカーの前後 `context_ms` に重なるセグメントの発言をまとめる（発言が無いマーカーは除く）
+pub fn marker_excerpts(
+    markers: &[RecordingMarker],
+    segments: &[TranscriptSegment],
+    context_ms: i64,
+) -> Vec<MarkerExcerpt> {
+    markers
+        .iter()
+        .filter_map(|marker| {
+            let from = marker.offset_ms - context_ms;
+            let to = marker.offset_ms + context_ms;
+            let text = segments
+                .iter()
+                .filter(|segment| segment.end_ms > from && segment.start_ms < to)
+                .map(|segment| segment.text.trim())
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            (!text.is_empty()).then(|| MarkerExcerpt {
+                offset_ms: marker.offset_ms,
+                label: marker.label.clone(),
+                text,
+            })
+        })
+        .collect()
+}
+
+/// 発言とマーカーを時刻順に並べる（マーカーはその時刻より後に始まる最初の発言の前に入る）
+pub fn transcript_timeline(segments: &[TranscriptSegment], markers: &[RecordingMarker]) -> Vec<TimelineItem> {
+    let mut segments = segments.to_vec();
+    segments.sort_by_key(|segment| (segment.start_ms, segment.segment_index));
+    let mut markers = markers.to_vec();
+    markers.sort_by_key(|marker| marker.offset_ms);
+
+    let mut markers = markers.into_iter().peekable();
+    let mut timeline = Vec::with_capacity(segments.len() + markers.len());
+    for segment in segments {
+        while let Some(marker) = markers.next_if(|marker| marker.offset_ms <= segment.start_ms) {
+            timeline.push(TimelineItem::Marker(marker));
+        }
+        timeline.push(TimelineItem::Segment(segment));
+    }
+    timeline.extend(markers.map(TimelineItem::Marker));
+    timeline
+}
+
+/// 書き起こしの発言と、書き起こし元の録音に付いたマーカー
+pub async fn timeline_for_transcription(database: &Database, transcription_id: &str) -> AppResult<Vec<TimelineItem>> {
+    let transcription = database
+        .get_transcription(transcription_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Transcription with id {} not found", transcription_id),
+        })?;
+    let markers = database.get_markers(&transcription.recording_id).await?;
+    let segments = database.get_segments_by_transcription(transcription_id).await?;
+    Ok(transcript_timeline(&segments, &markers))
+}
+
+/// 書き起こし元の録音に付いたマーカー付近の発言
+pub async fn excerpts_for_transcription(database: &Database, transcription_id: &str) -> AppResult<Vec<MarkerExcerpt>> {
+    let transcription = database
+        .get_transcription(transcription_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Transcription with id {} not found", transcription_id),
+        })?;
+    let markers = database.get_markers(&transcription.recording_id).await?;
+    if markers.is_empty() {
+        return Ok(Vec::new());
+    }
+    let segments = database.get_segments_by_transcription(transcription_id).await?;
+    Ok(marker_excerpts(&markers, &segments, MARKER_CONTEXT_MS))
+}
+
+/// プロンプトに埋め込むマーカー付近の発言の一覧
+pub fn format_excerpts_for_prompt(excerpts: &[MarkerExcerpt]) -> String {
+    excerpts
+        .iter()
+        .map(|excerpt| {
+            let label = excerpt.label.as_deref().map(|label| format!("{}: ", label)).unwrap_or_default();
+            format!(
+                "- [{:02}:{:02}] {}{}",
+                excerpt.offset_ms / 60_000,
+                (excerpt.offset_ms / 1_000) % 60,
+                label,
+                excerpt.text
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
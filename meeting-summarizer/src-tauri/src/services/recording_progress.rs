@@ -0,0 +1,72 @@
+//! 録音中の進捗ハートビート
+//!
+//! 録音中は一定間隔で経過時間・サンプル数・ファイルサイズを `recording-progress` イベントで配信する。
+//! UI はフロントエンドのストップウォッチではなくこの値を表示するため、実際の録音とずれない。
+
+use serde::{Deserialize, Serialize};
+
+pub const RECORDING_PROGRESS_EVENT: &str = "recording-progress";
+
+/// ハートビートの間隔
+pub const HEARTBEAT_INTERVAL_MS: u64 = 1_000;
+
+/// WAV ヘッダーのサイズ（hound が書き出す PCM の場合）
+const WAV_HEADER_BYTES: u64 = 44;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordingProgress {
+    pub session_id: String,
+    pub elapsed_ms: u64,
+    /// 保存されるWAVに換算したサンプル数（チャンネルあたり）
+    pub sample_count: u64,
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// 現時点で停止した場合のファイルサイズ
+    pub file_size_bytes: u64,
+    /// 一時停止中か
+    #[serde(default)]
+    pub paused: bool,
+}
+
+impl RecordingProgress {
+    pub fn new(session_id: String, elapsed_ms: u64, sample_count: u64, sample_rate: u32, channels: u16) -> Self {
+        Self {
+            session_id,
+            elapsed_ms,
+            sample_count,
+            sample_rate,
+            channels,
+            file_size_bytes: wav_file_size(sample_count, channels),
+            paused: false,
+        }
+    }
+
+    pub fn with_paused(mut self, paused: bool) -> Self {
+        self.paused = paused;
+        self
+    }
+
+    /// 16bit 以外で保存する場合のファイルサイズに直す
+    pub fn with_bits_per_sample(mut self, bits_per_sample: u16) -> Self {
+        self.file_size_bytes = pcm_wav_file_size(self.sample_count, self.channels, bits_per_sample);
+        self
+    }
+
+    /// 録音済みの音声の長さ（経過時間ではなくサンプル数から求める）
+    pub fn recorded_ms(&self) -> u64 {
+        if self.sample_rate == 0 {
+            return 0;
+        }
+        self.sample_count * 1_000 / self.sample_rate as u64
+    }
+}
+
+/// 16bit PCM WAV のファイルサイズ
+pub fn wav_file_size(sample_count: u64, channels: u16) -> u64 {
+    pcm_wav_file_size(sample_count, channels, 16)
+}
+
+/// 量子化ビット数を指定した PCM WAV のファイルサイズ
+pub fn pcm_wav_file_size(sample_count: u64, channels: u16, bits_per_sample: u16) -> u64 {
+    WAV_HEADER_BYTES + sample_count * channels as u64 * (bits_per_sample / 8) as u64
+}
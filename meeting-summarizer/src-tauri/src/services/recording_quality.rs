@@ -0,0 +1,76 @@
+//! 録音の音質
+//!
+//! 既定は書き起こしに十分な 16kHz モノラル 16bit。音楽や議事録として残したい会議では
+//! 48kHz ステレオなどの高音質で保存できる。書き起こしの前に `audio_convert` で
+//! 16kHz モノラルへ変換するため、どの音質でも Whisper の処理は変わらない。
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingQuality {
+    /// 16kHz モノラル 16bit（書き起こし向け）
+    #[default]
+    Voice,
+    /// 44.1kHz モノラル 16bit
+    Standard,
+    /// 48kHz ステレオ 24bit
+    High,
+}
+
+/// 保存する WAV の形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioFormat {
+    pub sample_rate: u32,
+    pub bits_per_sample: u16,
+    pub channels: u16,
+}
+
+/// 画面に表示する音質の選択肢
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingQualityPreset {
+    pub quality: RecordingQuality,
+    #[serde(flatten)]
+    pub format: AudioFormat,
+}
+
+impl RecordingQuality {
+    pub const ALL: [RecordingQuality; 3] = [Self::Voice, Self::Standard, Self::High];
+
+    pub fn format(&self) -> AudioFormat {
+        match self {
+            Self::Voice => AudioFormat {
+                sample_rate: 16_000,
+                bits_per_sample: 16,
+                channels: 1,
+            },
+            Self::Standard => AudioFormat {
+                sample_rate: 44_100,
+                bits_per_sample: 16,
+                channels: 1,
+            },
+            Self::High => AudioFormat {
+                sample_rate: 48_000,
+                bits_per_sample: 24,
+                channels: 2,
+            },
+        }
+    }
+}
+
+impl AudioFormat {
+    /// 1秒あたりのバイト数（容量の見積もり用）
+    pub fn bytes_per_second(&self) -> u64 {
+        self.sample_rate as u64 * self.channels as u64 * (self.bits_per_sample / 8) as u64
+    }
+}
+
+pub fn presets() -> Vec<RecordingQualityPreset> {
+    RecordingQuality::ALL
+        .iter()
+        .map(|quality| RecordingQualityPreset {
+            quality: *quality,
+            format: quality.format(),
+        })
+        .collect()
+}
@@ -0,0 +1,95 @@
+//! 長い会議の録音の分割
+//!
+//! 何時間も続く録音を1つの WAV にすると、停止時のまとめ直しでメモリを使い切ったり、
+//! 書き起こしのファイルサイズの上限を超えたりする。区間の長さを設定すると、録音スレッドは
+//! その長さごとに次のファイル（`<録音>_part002.wav` など）へ切り替える。分けたファイルは
+//! `recording_segments` で1つの録音に紐付け、書き起こしは区間ごとに実行してつなげる。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Recording, Transcription};
+use hound::WavReader;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// 区間の長さの下限（分）
+pub const MIN_SEGMENT_MINUTES: u32 = 1;
+
+/// 設定の区間の長さ（None なら分けない）
+pub fn max_segment_duration(max_segment_minutes: Option<u32>) -> AppResult<Option<Duration>> {
+    match max_segment_minutes {
+        None => Ok(None),
+        Some(minutes) if minutes < MIN_SEGMENT_MINUTES => Err(AppError::ValidationError {
+            message: format!("Segment duration must be at least {} minute", MIN_SEGMENT_MINUTES),
+        }),
+        Some(minutes) => Ok(Some(Duration::from_secs(minutes as u64 * 60))),
+    }
+}
+
+/// `index` 番目（0 から）の区間のファイル（先頭の区間は `output` そのもの）
+pub fn segment_path(output: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        return output.to_path_buf();
+    }
+    let stem = output.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let name = match output.extension() {
+        Some(extension) => format!("{}_part{:03}.{}", stem, index + 1, extension.to_string_lossy()),
+        None => format!("{}_part{:03}", stem, index + 1),
+    };
+    output.with_file_name(name)
+}
+
+/// 書き出された区間のファイル（先頭から続いている分だけ）
+pub fn existing_segments(output: &Path) -> Vec<PathBuf> {
+    (0..)
+        .map(|index| segment_path(output, index))
+        .take_while(|path| path.exists())
+        .collect()
+}
+
+/// WAV の長さ（ミリ秒）
+pub fn wav_duration_ms(path: &Path) -> Option<i64> {
+    let reader = WavReader::open(path).ok()?;
+    let spec = reader.spec();
+    Some(reader.duration() as i64 * 1000 / spec.sample_rate.max(1) as i64)
+}
+
+/// 録音の音声ファイル（分けた録音は区間の順、そうでなければ `file_path` だけ）
+pub async fn audio_files(database: &Database, recording: &Recording) -> AppResult<Vec<PathBuf>> {
+    let segments = database.get_recording_segments(&recording.id).await?;
+    if segments.is_empty() {
+        return Ok(vec![PathBuf::from(&recording.file_path)]);
+    }
+    Ok(segments.into_iter().map(|segment| PathBuf::from(segment.file_path)).collect())
+}
+
+/// 区間ごとの書き起こしを1つにつなげる（ID などは先頭の区間のものを使う。タイムスタンプ付きの区間は
+/// 録音の先頭からの時刻にずらしてから渡す）
+pub fn merge_transcriptions(parts: Vec<Transcription>) -> Option<Transcription> {
+    let mut parts = parts.into_iter();
+    let mut merged = parts.next()?;
+    let mut confidences: Vec<f32> = merged.confidence.into_iter().collect();
+    for part in parts {
+        let text = part.text.trim();
+        if !text.is_empty() {
+            if !merged.text.trim().is_empty() {
+                merged.text.push('\n');
+            }
+            merged.text.push_str(text);
+        }
+        merged.processing_time_ms = match (merged.processing_time_ms, part.processing_time_ms) {
+            (Some(total), Some(time)) => Some(total + time),
+            (total, time) => total.or(time),
+        };
+        confidences.extend(part.confidence);
+        merged.segments.extend(part.segments);
+    }
+    if !confidences.is_empty() {
+        merged.confidence = Some(confidences.iter().sum::<f32>() / confidences.len() as f32);
+    }
+    for (index, segment) in merged.segments.iter_mut().enumerate() {
+        segment.transcription_id = merged.id.clone();
+        segment.segment_index = index as i32;
+    }
+    Some(merged)
+}
@@ -0,0 +1,492 @@
+//! 録音中の一時ファイル（スプール）とクラッシュ後の復元
+//!
+//! 録音中の音声はメモリに溜めず、入力ごとの一時 WAV（32bit float、デバイスのサンプルレート）に
+//! 追記し、1秒ごとにヘッダーを書き直す。アプリが落ちても直前までの音声がファイルに残るため、
+//! 次の起動時に `recover_orphaned_recordings` で通常の録音として登録し直す（録音中のものには触れない）。
+//!
+//! - `recording_temp_<時刻>.primary.part` 入力元（マイクなど）
+//! - `recording_temp_<時刻>.system.part` 同時録音のシステム音声
+//!
+//! 区間の長さを設定した録音は、区間ごとに `recording_temp_<時刻>_part002.primary.part` のように
+//! 別のスプールへ切り替える。追加のトラックは `recording_temp_<時刻>_track01.primary.part` に書く。
+//! 復元時は区間とトラックをまとめて1つの録音にし、`recording_segments`・`recording_tracks` で紐付ける。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Recording, RecordingSegment, RecordingTrack};
+use crate::services::capture_source::DualSourceMode;
+use crate::services::audio_probe;
+use crate::services::recording_quality::AudioFormat;
+use crate::services::recording_segments;
+use crate::services::recording_tracks;
+use crate::services::AudioCapture;
+use chrono::DateTime;
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub const RECORDINGS_RECOVERED_EVENT: &str = "recordings-recovered";
+
+/// ヘッダーを書き直す間隔（落ちたときに失うのは最大でこの時間分）
+pub const SPOOL_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+const PRIMARY_SPOOL_EXTENSION: &str = "primary.part";
+const SYSTEM_SPOOL_EXTENSION: &str = "system.part";
+
+/// 番号とスプールのベース名
+type NumberedBases = Vec<(usize, String)>;
+
+/// 入力元のスプール
+pub fn primary_spool_path(output: &Path) -> PathBuf {
+    output.with_extension(PRIMARY_SPOOL_EXTENSION)
+}
+
+/// 同時録音のシステム音声のスプール
+pub fn system_spool_path(output: &Path) -> PathBuf {
+    output.with_extension(SYSTEM_SPOOL_EXTENSION)
+}
+
+/// 録音中の音声を追記する一時 WAV
+pub struct SpoolWriter {
+    writer: WavWriter<BufWriter<File>>,
+    last_flush: Instant,
+}
+
+impl SpoolWriter {
+    pub fn create(path: &Path, sample_rate: u32, channels: u16) -> AppResult<Self> {
+        let spec = WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 32,
+            sample_format: SampleFormat::Float,
+        };
+        let writer = WavWriter::create(path, spec).map_err(|e| spool_error(path, e))?;
+        Ok(Self {
+            writer,
+            last_flush: Instant::now(),
+        })
+    }
+
+    /// サンプルを追記する（一定間隔でヘッダーを書き直し、そこまでを読める状態にする）
+    pub fn append(&mut self, samples: &[f32]) -> AppResult<()> {
+        for sample in samples {
+            self.writer.write_sample(*sample).map_err(|e| AppError::Recording {
+                message: format!("Failed to write recording spool: {}", e),
+            })?;
+        }
+        if self.last_flush.elapsed() >= SPOOL_FLUSH_INTERVAL {
+            self.writer.flush().map_err(|e| AppError::Recording {
+                message: format!("Failed to flush recording spool: {}", e),
+            })?;
+            self.last_flush = Instant::now();
+        }
+        Ok(())
+    }
+
+    pub fn finish(self) -> AppResult<()> {
+        self.writer.finalize().map_err(|e| AppError::Recording {
+            message: format!("Failed to finalize recording spool: {}", e),
+        })
+    }
+}
+
+/// 録音スレッドが書くスプール（入力元かシステム音声か、と区間の長さ）
+#[derive(Clone)]
+pub struct SpoolTarget {
+    /// 録音の出力先（先頭の区間）
+    pub output: PathBuf,
+    spool_path: fn(&Path) -> PathBuf,
+    /// 区間の長さ（None なら分けない）
+    pub max_segment: Option<Duration>,
+}
+
+impl SpoolTarget {
+    /// 入力元のスプール
+    pub fn new(output: &Path, max_segment: Option<Duration>) -> Self {
+        Self {
+            output: output.to_path_buf(),
+            spool_path: primary_spool_path,
+            max_segment,
+        }
+    }
+
+    /// 同じ録音のシステム音声のスプール
+    pub fn system(&self) -> Self {
+        Self {
+            spool_path: system_spool_path,
+            ..self.clone()
+        }
+    }
+
+    /// `index` 番目の区間のスプール
+    pub fn spool_path(&self, index: usize) -> PathBuf {
+        (self.spool_path)(&recording_segments::segment_path(&self.output, index))
+    }
+
+    pub fn create(&self, sample_rate: u32, channels: u16) -> AppResult<SegmentedSpool> {
+        let max_frames = self
+            .max_segment
+            .map(|duration| (duration.as_secs_f64() * sample_rate as f64) as u64)
+            .filter(|frames| *frames > 0);
+        Ok(SegmentedSpool {
+            writer: SpoolWriter::create(&self.spool_path(0), sample_rate, channels)?,
+            target: self.clone(),
+            sample_rate,
+            channels: channels.max(1),
+            max_frames,
+            index: 0,
+            frames: 0,
+        })
+    }
+
+    /// 書いたスプールをすべて削除する（録音に失敗した場合）
+    pub fn remove_all(&self) {
+        for index in 0.. {
+            let path = self.spool_path(index);
+            if !path.exists() {
+                break;
+            }
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// 区間の長さに達したら次の区間のスプールへ切り替えて追記する
+pub struct SegmentedSpool {
+    writer: SpoolWriter,
+    target: SpoolTarget,
+    sample_rate: u32,
+    channels: u16,
+    max_frames: Option<u64>,
+    /// 書いている区間
+    index: usize,
+    /// 書いている区間のフレーム数
+    frames: u64,
+}
+
+impl SegmentedSpool {
+    pub fn append(&mut self, samples: &[f32]) -> AppResult<()> {
+        let channels = self.channels as usize;
+        let mut rest = samples;
+        if let Some(max_frames) = self.max_frames {
+            loop {
+                let room = max_frames.saturating_sub(self.frames) as usize * channels;
+                if rest.len() <= room {
+                    break;
+                }
+                let (head, tail) = rest.split_at(room);
+                self.writer.append(head)?;
+                self.roll_over()?;
+                rest = tail;
+            }
+        }
+        self.frames += (rest.len() / channels) as u64;
+        self.writer.append(rest)
+    }
+
+    fn roll_over(&mut self) -> AppResult<()> {
+        self.index += 1;
+        let next = SpoolWriter::create(&self.target.spool_path(self.index), self.sample_rate, self.channels)?;
+        std::mem::replace(&mut self.writer, next).finish()?;
+        self.frames = 0;
+        log::info!("✂️ Recording rolled over to segment {}", self.index + 1);
+        Ok(())
+    }
+
+    pub fn finish(self) -> AppResult<()> {
+        self.writer.finish()
+    }
+}
+
+/// スプールの中身（チャンネルが交互に並んだサンプル）
+#[derive(Debug, Clone)]
+pub struct SpoolAudio {
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+/// スプールを読み込む（最後にヘッダーを書き直した位置まで）
+pub fn read_spool(path: &Path) -> AppResult<SpoolAudio> {
+    let mut reader = WavReader::open(path).map_err(|e| spool_error(path, e))?;
+    let spec = reader.spec();
+    let samples = reader
+        .samples::<f32>()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| spool_error(path, e))?;
+    Ok(SpoolAudio {
+        samples,
+        sample_rate: spec.sample_rate,
+        channels: spec.channels,
+    })
+}
+
+fn spool_error(path: &Path, e: hound::Error) -> AppError {
+    AppError::Recording {
+        message: format!("Failed to read or write recording spool {:?}: {}", path, e),
+    }
+}
+
+/// 復元した録音
+#[derive(Debug, Clone)]
+pub struct RecoveredFile {
+    /// 先頭の区間（分けていなければ録音そのもの）
+    pub path: PathBuf,
+    /// 全区間の長さの合計
+    pub duration_secs: i64,
+    /// 区間ごとに分けた録音の各区間とその長さ（ミリ秒。分けていなければ空）
+    pub segments: Vec<(PathBuf, i64)>,
+    /// 別の入力デバイスから録音したトラック（番号は 1 から）
+    pub tracks: Vec<(usize, PathBuf)>,
+}
+
+/// `recording_temp_<時刻>_track01` を録音の名前とトラックの番号（1 から）に分ける
+fn split_track(base: &str) -> Option<(&str, usize)> {
+    let (stem, number) = base.rsplit_once("_track")?;
+    let number = number.parse::<usize>().ok().filter(|number| *number >= 1)?;
+    Some((stem, number))
+}
+
+/// `recording_temp_<時刻>_part002` を録音の名前と区間の番号（0 から）に分ける
+fn split_part(base: &str) -> (&str, usize) {
+    match base.rsplit_once("_part").map(|(stem, number)| (stem, number.parse::<usize>())) {
+        Some((stem, Ok(number))) if number >= 2 => (stem, number - 1),
+        _ => (base, 0),
+    }
+}
+
+/// 復元した録音の保存先（`recording_temp_<UNIX 時刻>` から録音開始時刻を取り出す）
+///
+/// 分けた録音の2つ目以降の区間は、この名前に `_part002` などを付けて保存する。
+fn recovered_path(recordings_dir: &Path, base: &str) -> PathBuf {
+    let started = base
+        .strip_prefix("recording_temp_")
+        .and_then(|secs| secs.parse::<i64>().ok())
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        .map(|time| time.format("%Y%m%d_%H%M%S").to_string())
+        .unwrap_or_else(|| base.to_string());
+    let mut path = recordings_dir.join(format!("recording_{}_recovered.wav", started));
+    let mut suffix = 2;
+    while path.exists() {
+        path = recordings_dir.join(format!("recording_{}_recovered_{}.wav", started, suffix));
+        suffix += 1;
+    }
+    path
+}
+
+/// 録音中の一時ファイル `active`（区間・トラックのスプールを含む）のものか
+fn belongs_to_active(base: &str, active: Option<&Path>) -> bool {
+    let Some(stem) = active.and_then(|path| path.file_stem()).map(|stem| stem.to_string_lossy()) else {
+        return false;
+    };
+    base.strip_prefix(stem.as_ref())
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('_'))
+}
+
+/// 残っている一時ファイルを通常の WAV にまとめる（ファイル操作のみ。登録は呼び出し側で行う）
+///
+/// 録音中の一時ファイル `active` のものは残し、中身の無いスプールや一時ファイルは削除する。
+pub fn recover_files(recordings_dir: &Path, active: Option<&Path>) -> AppResult<Vec<RecoveredFile>> {
+    if !recordings_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut bases: Vec<String> = Vec::new();
+    for entry in std::fs::read_dir(recordings_dir)? {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        let base = name
+            .strip_suffix(&format!(".{}", PRIMARY_SPOOL_EXTENSION))
+            .or_else(|| name.strip_suffix(&format!(".{}", SYSTEM_SPOOL_EXTENSION)))
+            .or_else(|| name.strip_prefix("recording_temp_").and_then(|_| name.strip_suffix(".wav")));
+        if let Some(base) = base.filter(|base| !belongs_to_active(base, active)) {
+            if !bases.iter().any(|known| known == base) {
+                bases.push(base.to_string());
+            }
+        }
+    }
+
+    // 区間・トラックは `_part002`・`_track01` などを除いた名前で1つの録音にまとめる
+    let mut recordings: BTreeMap<String, (NumberedBases, NumberedBases)> = BTreeMap::new();
+    for base in bases {
+        match split_track(&base) {
+            Some((name, index)) => recordings.entry(name.to_string()).or_default().1.push((index, base.clone())),
+            None => {
+                let (name, index) = split_part(&base);
+                recordings.entry(name.to_string()).or_default().0.push((index, base.clone()));
+            }
+        }
+    }
+
+    let mut recovered = Vec::new();
+    for (name, (mut parts, mut track_bases)) in recordings {
+        parts.sort();
+        track_bases.sort();
+        let output = recovered_path(recordings_dir, &name);
+        let mut segments = Vec::new();
+        for (_, base) in parts {
+            // 中身の無い区間があっても、残りの区間は続けて番号を振る
+            let part_output = recording_segments::segment_path(&output, segments.len());
+            if recover_spool(recordings_dir, &base, &part_output)? {
+                let duration_ms = recording_segments::wav_duration_ms(&part_output).unwrap_or(0);
+                segments.push((part_output, duration_ms));
+            }
+        }
+
+        let mut tracks = Vec::new();
+        for (index, base) in track_bases {
+            let track_output = recording_tracks::track_path(&output, index);
+            if recover_spool(recordings_dir, &base, &track_output)? {
+                tracks.push((index, track_output));
+            }
+        }
+        // 入力元の音声が残っていなければ、最初のトラックを録音の音声にする
+        if segments.is_empty() && !tracks.is_empty() {
+            let (_, track) = tracks.remove(0);
+            std::fs::rename(&track, &output)?;
+            let duration_ms = recording_segments::wav_duration_ms(&output).unwrap_or(0);
+            segments.push((output.clone(), duration_ms));
+        }
+        let Some((path, _)) = segments.first().cloned() else {
+            continue;
+        };
+
+        log::info!(
+            "🩹 Recovered interrupted recording: {:?} ({} segment(s), {} extra track(s))",
+            path,
+            segments.len(),
+            tracks.len()
+        );
+        recovered.push(RecoveredFile {
+            path,
+            duration_secs: segments.iter().map(|(_, duration_ms)| duration_ms).sum::<i64>() / 1000,
+            segments: if segments.len() > 1 { segments } else { Vec::new() },
+            tracks,
+        });
+    }
+    Ok(recovered)
+}
+
+/// 1つの一時ファイル（区間）を `output` の WAV にまとめる（中身が無ければ削除して false）
+fn recover_spool(recordings_dir: &Path, base: &str, output: &Path) -> AppResult<bool> {
+    let temp_wav = recordings_dir.join(format!("{}.wav", base));
+    let primary = primary_spool_path(&temp_wav);
+    let system = system_spool_path(&temp_wav);
+
+    if primary.exists() || system.exists() {
+        // 入力元のスプールの形式で書き出す（同時録音だった場合はモノラルに混ぜる）
+        let spec = [&primary, &system]
+            .iter()
+            .find_map(|path| WavReader::open(path).ok().map(|reader| reader.spec()));
+        let Some(spec) = spec else {
+            log::warn!("⚠️ Discarding unreadable recording spool: {}", base);
+            let _ = std::fs::remove_file(&primary);
+            let _ = std::fs::remove_file(&system);
+            let _ = std::fs::remove_file(&temp_wav);
+            return Ok(false);
+        };
+        let dual = primary.exists() && system.exists();
+        let format = AudioFormat {
+            sample_rate: spec.sample_rate,
+            bits_per_sample: 16,
+            channels: if dual { 1 } else { spec.channels },
+        };
+        let primary_path = if primary.exists() { primary.clone() } else { system.clone() };
+        let system_path = dual.then_some(system.as_path());
+        if let Err(e) = AudioCapture::finalize_spools(&primary_path, system_path, DualSourceMode::Mix, false, format, output) {
+            log::warn!("⚠️ Discarding recording spool without audio ({}): {}", base, e);
+            let _ = std::fs::remove_file(&primary);
+            let _ = std::fs::remove_file(&system);
+            let _ = std::fs::remove_file(&temp_wav);
+            return Ok(false);
+        }
+        let _ = std::fs::remove_file(&temp_wav);
+        return Ok(true);
+    }
+
+    // スプールをまとめた後、登録前に落ちた場合は一時 WAV が完成している
+    match recording_segments::wav_duration_ms(&temp_wav) {
+        Some(_) if std::fs::metadata(&temp_wav)?.len() > 44 => {
+            std::fs::rename(&temp_wav, output)?;
+            Ok(true)
+        }
+        _ => {
+            std::fs::remove_file(&temp_wav)?;
+            Ok(false)
+        }
+    }
+}
+
+/// 録音中にアプリが終了して残った一時ファイルを録音として登録する
+///
+/// 録音の開始と重ならないよう、起動時は `RecordingService::recover_orphaned_recordings` から呼ぶ。
+pub async fn recover_orphaned_recordings(
+    database: &Database,
+    recordings_dir: &Path,
+    active: Option<&Path>,
+) -> AppResult<Vec<Recording>> {
+    let dir = recordings_dir.to_path_buf();
+    let active = active.map(Path::to_path_buf);
+    let files = tokio::task::spawn_blocking(move || recover_files(&dir, active.as_deref()))
+        .await
+        .map_err(|e| AppError::Recording {
+            message: format!("Recording recovery task failed: {}", e),
+        })??;
+
+    let mut recordings = Vec::new();
+    for file in files {
+        let filename = file
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let paths: Vec<PathBuf> = if file.segments.is_empty() {
+            vec![file.path.clone()]
+        } else {
+            file.segments.iter().map(|(path, _)| path.clone()).collect()
+        };
+        // 分けた録音は全区間の合計
+        let mut file_size = 0;
+        for path in &paths {
+            file_size += std::fs::metadata(path)?.len() as i64;
+        }
+        let mut recording = Recording::new(filename, file.path.to_string_lossy().to_string())
+            .with_title("Recovered recording".to_string())
+            .with_duration(file.duration_secs)
+            .with_file_size(file_size);
+        audio_probe::fill_recording(&mut recording, &paths);
+        database.create_recording(&recording).await?;
+
+        // 分けたファイルを録音に紐付ける
+        let mut offset_ms = 0;
+        for (index, (path, duration_ms)) in file.segments.iter().enumerate() {
+            let segment = RecordingSegment::new(
+                recording.id.clone(),
+                index as i32,
+                path.to_string_lossy().to_string(),
+                offset_ms,
+                *duration_ms,
+            )
+            .with_file_size(std::fs::metadata(path)?.len() as i64);
+            database.create_recording_segment(&segment).await?;
+            offset_ms += duration_ms;
+        }
+
+        // 別の入力デバイスのトラックも紐付ける（デバイス名は残っていないので番号で呼ぶ）
+        for (index, path) in &file.tracks {
+            let track = RecordingTrack::new(
+                recording.id.clone(),
+                *index as i32,
+                format!("Track {}", index),
+                path.to_string_lossy().to_string(),
+                recording_segments::wav_duration_ms(path).unwrap_or(0),
+            )
+            .with_file_size(std::fs::metadata(path)?.len() as i64);
+            database.create_recording_track(&track).await?;
+        }
+        recordings.push(recording);
+    }
+    Ok(recordings)
+}
@@ -0,0 +1,49 @@
+//! 複数の入力デバイスからの同時録音（マルチトラック）
+//!
+//! 会議室に置いた2本目のマイクなど、入力元のほかに選んだデバイスを同じ録音の別のトラックとして
+//! 録音する。トラックは混ぜずに `<録音>_track01.wav` のようなモノラル WAV に保存し、
+//! `recording_tracks` で録音に紐付ける。話者分離や話者ごとの書き起こしで、その人に近いマイクの
+//! 音声を使える。
+
+use crate::errors::{AppError, AppResult};
+use crate::services::capture_source::CaptureSource;
+use std::path::{Path, PathBuf};
+
+/// 追加できるトラックの数の上限
+pub const MAX_EXTRA_TRACKS: usize = 4;
+
+/// `index` 番目（1 から）のトラックのファイル
+pub fn track_path(output: &Path, index: usize) -> PathBuf {
+    let stem = output.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let name = match output.extension() {
+        Some(extension) => format!("{}_track{:02}.{}", stem, index, extension.to_string_lossy()),
+        None => format!("{}_track{:02}", stem, index),
+    };
+    output.with_file_name(name)
+}
+
+/// 書き出されたトラックのファイル（録音できなかったトラックは含まない）
+pub fn existing_tracks(output: &Path, count: usize) -> Vec<(usize, PathBuf)> {
+    (1..=count)
+        .map(|index| (index, track_path(output, index)))
+        .filter(|(_, path)| path.exists())
+        .collect()
+}
+
+/// 追加のトラックを確かめる（入力元や他のトラックと同じ入力は録音しない）
+pub fn validate_extra_tracks(source: &CaptureSource, tracks: &[CaptureSource]) -> AppResult<()> {
+    if tracks.len() > MAX_EXTRA_TRACKS {
+        return Err(AppError::ValidationError {
+            message: format!("At most {} extra tracks can be recorded", MAX_EXTRA_TRACKS),
+        });
+    }
+    for (index, track) in tracks.iter().enumerate() {
+        track.validate()?;
+        if track == source || tracks[..index].contains(track) {
+            return Err(AppError::ValidationError {
+                message: format!("{} is already being recorded", track.label()),
+            });
+        }
+    }
+    Ok(())
+}
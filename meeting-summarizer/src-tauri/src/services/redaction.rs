@@ -0,0 +1,319 @@
+//! 書き出し前の個人情報マスキング
+//!
+//! メールアドレス・電話番号（任意で LLM が検出した人名）を `[EMAIL_1]` のようなプレースホルダーに
+//! 置き換える。対応表は録音ごとにローカル DB にだけ保存し、以降の書き出しでも同じ値には同じ
+//! プレースホルダーを使う。元の書き起こし・要約は変更しない。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{PiiKind, RedactionEntry, Summary};
+use crate::services::{export, LLMService};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// マスキング対象
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionOptions {
+    pub emails: bool,
+    pub phone_numbers: bool,
+    /// LLM で人名を検出する（LLM が使えない場合は登録済みの人名のみ置き換える）
+    pub person_names: bool,
+}
+
+impl Default for RedactionOptions {
+    fn default() -> Self {
+        Self {
+            emails: true,
+            phone_numbers: true,
+            person_names: false,
+        }
+    }
+}
+
+/// 匿名化した書き出し結果と、その録音の対応表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactedExport {
+    pub content: String,
+    pub redacted_count: usize,
+    pub mappings: Vec<RedactionEntry>,
+}
+
+/// 録音1件分のプレースホルダー割り当て
+pub struct Redactor {
+    recording_id: String,
+    entries: Vec<RedactionEntry>,
+    saved_count: usize,
+    redacted_count: usize,
+}
+
+impl Redactor {
+    pub fn new(recording_id: &str, existing: Vec<RedactionEntry>) -> Self {
+        let saved_count = existing.len();
+        Self {
+            recording_id: recording_id.to_string(),
+            entries: existing,
+            saved_count,
+            redacted_count: 0,
+        }
+    }
+
+    /// 人名を登録する（1文字の名前は誤置換が多いため対象外）
+    pub fn add_person_names(&mut self, names: &[String]) {
+        for name in names {
+            let name = name.trim();
+            if name.chars().count() >= 2 {
+                self.placeholder_for(PiiKind::PersonName, name);
+            }
+        }
+    }
+
+    pub fn redact(&mut self, text: &str, options: &RedactionOptions) -> String {
+        let mut matches: Vec<(Range<usize>, PiiKind)> = Vec::new();
+        if options.emails {
+            matches.extend(find_emails(text).into_iter().map(|range| (range, PiiKind::Email)));
+        }
+        if options.phone_numbers {
+            matches.extend(find_phone_numbers(text).into_iter().map(|range| (range, PiiKind::PhoneNumber)));
+        }
+        if options.person_names {
+            matches.extend(self.find_person_names(text).into_iter().map(|range| (range, PiiKind::PersonName)));
+        }
+
+        // 重なった場合は先に始まる（同じ位置なら長い）方を優先する
+        matches.sort_by(|(a, _), (b, _)| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+
+        let mut result = String::with_capacity(text.len());
+        let mut cursor = 0;
+        for (range, kind) in matches {
+            if range.start < cursor {
+                continue;
+            }
+            result.push_str(&text[cursor..range.start]);
+            result.push_str(&self.placeholder_for(kind, &text[range.clone()]));
+            self.redacted_count += 1;
+            cursor = range.end;
+        }
+        result.push_str(&text[cursor..]);
+        result
+    }
+
+    pub fn entries(&self) -> &[RedactionEntry] {
+        &self.entries
+    }
+
+    /// このインスタンスで新しく割り当てた対応
+    pub fn new_entries(&self) -> &[RedactionEntry] {
+        &self.entries[self.saved_count..]
+    }
+
+    pub fn redacted_count(&self) -> usize {
+        self.redacted_count
+    }
+
+    fn placeholder_for(&mut self, kind: PiiKind, original: &str) -> String {
+        if let Some(entry) = self.entries.iter().find(|entry| entry.original == original) {
+            return entry.placeholder.clone();
+        }
+
+        let number = self.entries.iter().filter(|entry| entry.kind == kind).count() + 1;
+        let placeholder = format!("[{}_{}]", kind.placeholder_prefix(), number);
+        self.entries.push(RedactionEntry {
+            recording_id: self.recording_id.clone(),
+            placeholder: placeholder.clone(),
+            original: original.to_string(),
+            kind,
+            created_at: Utc::now(),
+        });
+        placeholder
+    }
+
+    fn find_person_names(&self, text: &str) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        for entry in self.entries.iter().filter(|entry| entry.kind == PiiKind::PersonName) {
+            let name = entry.original.as_str();
+            let ascii_word = name.chars().all(|c| c.is_ascii_alphanumeric() || c == ' ');
+            for (start, _) in text.match_indices(name) {
+                let end = start + name.len();
+                // 英字の名前は単語の途中に一致させない（"Al" が "Also" に一致しないように）
+                if ascii_word
+                    && (text[..start].chars().next_back().is_some_and(|c| c.is_ascii_alphanumeric())
+                        || text[end..].chars().next().is_some_and(|c| c.is_ascii_alphanumeric()))
+                {
+                    continue;
+                }
+                ranges.push(start..end);
+            }
+        }
+        ranges
+    }
+}
+
+/// プレースホルダーを元の値に戻す
+pub fn restore(text: &str, entries: &[RedactionEntry]) -> String {
+    entries
+        .iter()
+        .fold(text.to_string(), |result, entry| result.replace(&entry.placeholder, &entry.original))
+}
+
+pub fn find_emails(text: &str) -> Vec<Range<usize>> {
+    let is_local = |c: char| c.is_ascii_alphanumeric() || "._%+-".contains(c);
+    let is_domain = |c: char| c.is_ascii_alphanumeric() || c == '.' || c == '-';
+
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for (at, _) in text.match_indices('@') {
+        if ranges.last().is_some_and(|last| at < last.end) {
+            continue;
+        }
+
+        let local_len: usize = text[..at]
+            .chars()
+            .rev()
+            .take_while(|&c| is_local(c))
+            .map(char::len_utf8)
+            .sum();
+        let start = at - local_len;
+        let start = start + text[start..at].len() - text[start..at].trim_start_matches('.').len();
+
+        let domain_len: usize = text[at + 1..].chars().take_while(|&c| is_domain(c)).map(char::len_utf8).sum();
+        let domain = text[at + 1..at + 1 + domain_len].trim_end_matches(['.', '-']);
+        let tld = domain.rsplit('.').next().unwrap_or_default();
+
+        if start < at && domain.contains('.') && tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()) {
+            ranges.push(start..at + 1 + domain.len());
+        }
+    }
+    ranges
+}
+
+/// 電話番号（0 または + で始まる 10〜15 桁。日付や時刻には一致しない）
+pub fn find_phone_numbers(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut iter = text.char_indices().peekable();
+
+    while let Some((start, c)) = iter.next() {
+        let at_boundary = !previous.is_some_and(|p| p.is_ascii_alphanumeric() || "+-./:".contains(p));
+        previous = Some(c);
+        if !at_boundary || !(c == '+' || c == '0' || c == '(') {
+            continue;
+        }
+
+        // 国際表記（+81 90 ...）のみ空白区切りを許す
+        let international = c == '+';
+        let mut end = start + c.len_utf8();
+        let mut digits = usize::from(c.is_ascii_digit());
+        let mut last_digit_end = if c.is_ascii_digit() { end } else { start };
+        while let Some(&(index, next)) = iter.peek() {
+            let allowed = next.is_ascii_digit() || "-()".contains(next) || (international && next == ' ');
+            if !allowed {
+                break;
+            }
+            iter.next();
+            previous = Some(next);
+            end = index + next.len_utf8();
+            if next.is_ascii_digit() {
+                digits += 1;
+                last_digit_end = end;
+            }
+        }
+
+        let followed_by_word = text[end..].chars().next().is_some_and(|n| n.is_ascii_alphanumeric());
+        let body = &text[start..last_digit_end];
+        let starts_like_phone = international || body.trim_start_matches('(').starts_with('0');
+        if (10..=15).contains(&digits) && starts_like_phone && !followed_by_word {
+            ranges.push(start..last_digit_end);
+        }
+    }
+    ranges
+}
+
+/// 録音を指定形式で書き出し、個人情報を置き換えた結果を返す
+pub async fn export_recording_redacted(
+    database: &Database,
+    llm: Option<&LLMService>,
+    recording_id: &str,
+    format: &str,
+    options: &RedactionOptions,
+) -> AppResult<RedactedExport> {
+    let content = export::export_recording(database, recording_id, format).await?;
+
+    let mut redactor = load_redactor(database, recording_id).await?;
+    if options.person_names {
+        if let Some(llm) = llm {
+            for transcription in database.get_transcriptions_by_recording(recording_id).await? {
+                detect_person_names(llm, &mut redactor, &transcription.text).await?;
+            }
+        }
+    }
+
+    let content = redactor.redact(&content, options);
+    save_redactor(database, &redactor).await?;
+    log::info!(
+        "🕶️ Redacted {} item(s) in {} export of recording {}",
+        redactor.redacted_count(),
+        format,
+        recording_id
+    );
+
+    Ok(RedactedExport {
+        content,
+        redacted_count: redactor.redacted_count(),
+        mappings: redactor.entries().to_vec(),
+    })
+}
+
+/// 要約の本文・重要ポイント・アクションアイテムを置き換えたコピーを返す（DB の要約は変更しない）
+pub async fn redact_summary(
+    database: &Database,
+    llm: Option<&LLMService>,
+    summary_id: &str,
+    options: &RedactionOptions,
+) -> AppResult<Summary> {
+    let mut summary = database
+        .get_summary(summary_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Summary with id {} not found", summary_id),
+        })?;
+    let transcription = database
+        .get_transcription(&summary.transcription_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Transcription with id {} not found", summary.transcription_id),
+        })?;
+
+    let mut redactor = load_redactor(database, &transcription.recording_id).await?;
+    if options.person_names {
+        if let Some(llm) = llm {
+            detect_person_names(llm, &mut redactor, &transcription.text).await?;
+        }
+    }
+
+    summary.summary_text = redactor.redact(&summary.summary_text, options);
+    summary.key_points = summary.key_points.iter().map(|point| redactor.redact(point, options)).collect();
+    summary.action_items = summary.action_items.iter().map(|item| redactor.redact(item, options)).collect();
+    save_redactor(database, &redactor).await?;
+    Ok(summary)
+}
+
+async fn load_redactor(database: &Database, recording_id: &str) -> AppResult<Redactor> {
+    let existing = database.get_redaction_mappings(recording_id).await?;
+    Ok(Redactor::new(recording_id, existing))
+}
+
+async fn save_redactor(database: &Database, redactor: &Redactor) -> AppResult<()> {
+    if !redactor.new_entries().is_empty() {
+        database.save_redaction_mappings(redactor.new_entries()).await?;
+    }
+    Ok(())
+}
+
+/// 人名の検出に失敗した場合は書き出し自体を失敗させる（人名が残ったまま共有されないように）
+async fn detect_person_names(llm: &LLMService, redactor: &mut Redactor, text: &str) -> AppResult<()> {
+    if !text.trim().is_empty() {
+        let names = llm.extract_person_names(text).await?;
+        redactor.add_person_names(&names);
+    }
+    Ok(())
+}
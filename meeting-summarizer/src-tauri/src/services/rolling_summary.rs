@@ -0,0 +1,236 @@
+//! 会議中の途中経過要約（ローリングサマリー）
+//!
+//! ライブ字幕の確定済みテキストを蓄積し、一定間隔で「前回までの要約 + 未要約の発言」を
+//! LLM に渡して要約を更新する。1回に渡す量はトークン予算で制限し、収まらない発言は
+//! 次回以降に持ち越すため、長い会議でも取りこぼしなく追いつける。
+
+use crate::errors::AppResult;
+use crate::services::live_captions::CaptionEvent;
+use crate::services::LLMService;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+pub const ROLLING_SUMMARY_EVENT: &str = "rolling-summary";
+pub const DEFAULT_INTERVAL_SECONDS: u64 = 120;
+pub const DEFAULT_TOKEN_BUDGET: usize = 3000;
+const MIN_INTERVAL_SECONDS: u64 = 15;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollingSummary {
+    pub session_id: String,
+    pub summary: String,
+    pub revision: u32,
+    /// 要約に反映済みの発言の終了時刻
+    pub covered_until_ms: i64,
+    /// まだ要約に反映していない発言があるか
+    pub has_pending: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 字幕チャンクの並び順キー（セグメント番号, 開始時刻）
+type PieceKey = (u64, i64);
+
+#[derive(Default)]
+struct RollingState {
+    session_id: Option<String>,
+    pieces: BTreeMap<String, (PieceKey, i64, String)>,
+    cursor: Option<PieceKey>,
+    summary: Option<RollingSummary>,
+}
+
+impl RollingState {
+    fn pending(&self) -> Vec<(PieceKey, i64, &str)> {
+        let mut pending: Vec<_> = self
+            .pieces
+            .values()
+            .filter(|(key, _, _)| self.cursor.is_none_or(|cursor| *key > cursor))
+            .map(|(key, end_ms, text)| (*key, *end_ms, text.as_str()))
+            .collect();
+        pending.sort_by_key(|(key, _, _)| *key);
+        pending
+    }
+}
+
+/// 1回の更新で LLM に渡す内容
+struct RollingInput {
+    session_id: String,
+    previous_summary: Option<String>,
+    new_text: String,
+    cursor: PieceKey,
+    covered_until_ms: i64,
+}
+
+#[derive(Default)]
+pub struct RollingSummarizer {
+    state: Mutex<RollingState>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl RollingSummarizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ライブ字幕のイベントを取り込む（確定済みのみ。要約済み部分の訂正は反映しない）
+    pub fn record_caption(&self, event: &CaptionEvent) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+
+        if state.session_id.as_deref() != Some(event.session_id.as_str()) {
+            *state = RollingState {
+                session_id: Some(event.session_id.clone()),
+                ..RollingState::default()
+            };
+        }
+
+        if event.removed {
+            state.pieces.remove(&event.caption_id);
+        } else if event.is_final {
+            state.pieces.insert(
+                event.caption_id.clone(),
+                ((event.segment_index, event.start_ms), event.end_ms, event.text.clone()),
+            );
+        }
+    }
+
+    pub fn current(&self) -> Option<RollingSummary> {
+        self.state.lock().ok()?.summary.clone()
+    }
+
+    /// 未要約の発言があれば要約を更新する。`summarize` には（前回の要約, 新しい発言）を渡す
+    pub async fn update_with<F, Fut>(&self, token_budget: usize, summarize: F) -> AppResult<Option<RollingSummary>>
+    where
+        F: FnOnce(Option<String>, String) -> Fut,
+        Fut: Future<Output = AppResult<String>>,
+    {
+        let Some(input) = self.next_input(token_budget) else {
+            return Ok(None);
+        };
+
+        let summary_text = summarize(input.previous_summary, input.new_text).await?;
+
+        let Ok(mut state) = self.state.lock() else {
+            return Ok(None);
+        };
+        // 要約中に別のセッションへ切り替わった場合は結果を捨てる
+        if state.session_id.as_deref() != Some(input.session_id.as_str()) {
+            return Ok(None);
+        }
+        state.cursor = Some(input.cursor);
+        let revision = state.summary.as_ref().map_or(0, |summary| summary.revision) + 1;
+        let summary = RollingSummary {
+            session_id: input.session_id,
+            summary: summary_text,
+            revision,
+            covered_until_ms: input.covered_until_ms,
+            has_pending: !state.pending().is_empty(),
+            updated_at: Utc::now(),
+        };
+        state.summary = Some(summary.clone());
+        Ok(Some(summary))
+    }
+
+    fn next_input(&self, token_budget: usize) -> Option<RollingInput> {
+        let state = self.state.lock().ok()?;
+        let session_id = state.session_id.clone()?;
+        let pending = state.pending();
+        if pending.is_empty() {
+            return None;
+        }
+
+        let previous_summary = state.summary.as_ref().map(|summary| summary.summary.clone());
+        let mut remaining = token_budget.saturating_sub(previous_summary.as_deref().map_or(0, estimate_tokens));
+
+        // 古い発言から予算に収まる分だけ取り出す（最低1チャンクは含める）
+        let mut texts = Vec::new();
+        let mut cursor = None;
+        let mut covered_until_ms = 0;
+        for (key, end_ms, text) in pending {
+            let tokens = estimate_tokens(text);
+            if !texts.is_empty() && tokens > remaining {
+                break;
+            }
+            remaining = remaining.saturating_sub(tokens);
+            texts.push(text);
+            cursor = Some(key);
+            covered_until_ms = end_ms;
+        }
+
+        Some(RollingInput {
+            session_id,
+            previous_summary,
+            new_text: texts.join("\n"),
+            cursor: cursor?,
+            covered_until_ms,
+        })
+    }
+
+    /// 定期更新を開始する（実行中なら置き換える）
+    pub fn start<F>(self: &Arc<Self>, llm_service: LLMService, interval_seconds: u64, token_budget: usize, on_update: F)
+    where
+        F: Fn(&RollingSummary) + Send + 'static,
+    {
+        self.stop();
+
+        let interval = Duration::from_secs(interval_seconds.max(MIN_INTERVAL_SECONDS));
+        let summarizer = Arc::clone(self);
+        log::info!("📝 Rolling summary started (every {}s, budget {} tokens)", interval.as_secs(), token_budget);
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // 最初の tick は即時に完了するため読み捨てる
+            ticker.tick().await;
+            let llm_service = &llm_service;
+            loop {
+                ticker.tick().await;
+                let result = summarizer
+                    .update_with(token_budget, |previous, new_text| async move {
+                        llm_service.summarize_incremental(previous.as_deref(), &new_text).await
+                    })
+                    .await;
+                match result {
+                    Ok(Some(summary)) => on_update(&summary),
+                    Ok(None) => {}
+                    Err(e) => log::warn!("⚠️ Rolling summary update failed: {}", e),
+                }
+            }
+        });
+
+        if let Ok(mut task) = self.task.lock() {
+            *task = Some(handle);
+        }
+    }
+
+    pub fn stop(&self) -> bool {
+        let handle = self.task.lock().ok().and_then(|mut task| task.take());
+        match handle {
+            Some(handle) => {
+                handle.abort();
+                log::info!("📝 Rolling summary stopped");
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.task
+            .lock()
+            .map(|task| task.as_ref().is_some_and(|handle| !handle.is_finished()))
+            .unwrap_or(false)
+    }
+}
+
+/// おおよそのトークン数（ASCIIは4文字で1トークン、それ以外は1文字1トークンとして数える）
+pub fn estimate_tokens(text: &str) -> usize {
+    let (ascii, other) = text
+        .chars()
+        .fold((0usize, 0usize), |(ascii, other), c| if c.is_ascii() { (ascii + 1, other) } else { (ascii, other + 1) });
+    ascii.div_ceil(4) + other
+}
@@ -0,0 +1,178 @@
+//! メール送付用の暗号化共有パッケージ
+//!
+//! 要約・書き起こし（任意で音声）を AES-256 で暗号化した ZIP にまとめる。
+//! 7-Zip や macOS の Keka など一般的な解凍ソフトで開ける形式を選んでいる。
+//! パスワードは書き込み時にのみ使用し、設定やログ・データベースには一切保存しない。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::Summary;
+use crate::services::export;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{AesMode, CompressionMethod, ZipWriter};
+
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShareBundle {
+    pub path: String,
+    pub files: Vec<String>,
+    pub size_bytes: u64,
+}
+
+/// 録音1件分の共有パッケージを `output_path` に作成する
+pub async fn create_share_bundle(
+    database: &Database,
+    recording_id: &str,
+    output_path: &Path,
+    password: &str,
+    include_audio: bool,
+) -> AppResult<ShareBundle> {
+    validate_password(password)?;
+
+    let recording = database
+        .get_recording(recording_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording with id {} not found", recording_id),
+        })?;
+
+    let mut summaries = Vec::new();
+    for transcription in database.get_transcriptions_by_recording(recording_id).await? {
+        if let Some(summary) = database.get_summaries_by_transcription(&transcription.id).await?.into_iter().next() {
+            summaries.push(summary);
+        }
+    }
+
+    let title = recording.title.clone().unwrap_or_else(|| recording.filename.clone());
+    let mut entries: Vec<(String, BundleSource)> = vec![
+        ("minutes.md".to_string(), BundleSource::Bytes(render_minutes(&title, &summaries).into_bytes())),
+        (
+            "transcript.md".to_string(),
+            BundleSource::Bytes(export::export_recording(database, recording_id, "markdown").await?.into_bytes()),
+        ),
+        (
+            "transcript.html".to_string(),
+            BundleSource::Bytes(export::export_recording(database, recording_id, "html").await?.into_bytes()),
+        ),
+    ];
+
+    if include_audio {
+        let audio_path = PathBuf::from(&recording.file_path);
+        if !audio_path.exists() {
+            return Err(AppError::FileNotFound {
+                path: recording.file_path.clone(),
+            });
+        }
+        let file_name = audio_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| recording.filename.clone());
+        entries.push((format!("audio/{}", file_name), BundleSource::File(audio_path)));
+    }
+
+    let output_path = output_path.to_path_buf();
+    let password = password.to_string();
+    tokio::task::spawn_blocking(move || write_encrypted_zip(&output_path, &password, entries))
+        .await
+        .map_err(|e| AppError::InvalidOperation {
+            message: format!("Share bundle task failed: {}", e),
+        })?
+}
+
+pub fn validate_password(password: &str) -> AppResult<()> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(AppError::ValidationError {
+            message: format!("Password must be at least {} characters", MIN_PASSWORD_LENGTH),
+        });
+    }
+    Ok(())
+}
+
+enum BundleSource {
+    Bytes(Vec<u8>),
+    File(PathBuf),
+}
+
+fn write_encrypted_zip(output_path: &Path, password: &str, entries: Vec<(String, BundleSource)>) -> AppResult<ShareBundle> {
+    // 途中で失敗した不完全なファイルを残さないよう一時ファイルに書いてから置き換える
+    let partial_path = output_path.with_extension("zip.part");
+    let result = (|| -> AppResult<Vec<String>> {
+        let mut writer = ZipWriter::new(File::create(&partial_path)?);
+        let mut files = Vec::with_capacity(entries.len());
+
+        for (name, source) in entries {
+            // 音声は圧縮しても小さくならないため無圧縮で格納する
+            let method = match source {
+                BundleSource::Bytes(_) => CompressionMethod::Deflated,
+                BundleSource::File(_) => CompressionMethod::Stored,
+            };
+            let options = SimpleFileOptions::default()
+                .compression_method(method)
+                .large_file(matches!(source, BundleSource::File(_)))
+                .with_aes_encryption(AesMode::Aes256, password);
+
+            writer.start_file(name.as_str(), options).map_err(zip_error)?;
+            match source {
+                BundleSource::Bytes(bytes) => writer.write_all(&bytes)?,
+                BundleSource::File(path) => {
+                    std::io::copy(&mut File::open(&path)?, &mut writer)?;
+                }
+            }
+            files.push(name);
+        }
+
+        writer.finish().map_err(zip_error)?;
+        Ok(files)
+    })();
+
+    let files = match result {
+        Ok(files) => files,
+        Err(e) => {
+            let _ = std::fs::remove_file(&partial_path);
+            return Err(e);
+        }
+    };
+    std::fs::rename(&partial_path, output_path)?;
+
+    let size_bytes = std::fs::metadata(output_path)?.len();
+    log::info!("🔐 Created encrypted share bundle: {} ({} files, {} bytes)", output_path.display(), files.len(), size_bytes);
+    Ok(ShareBundle {
+        path: output_path.to_string_lossy().to_string(),
+        files,
+        size_bytes,
+    })
+}
+
+fn render_minutes(title: &str, summaries: &[Summary]) -> String {
+    let mut result = format!("# {}\n", title);
+    if summaries.is_empty() {
+        result.push_str("\n(No summary)\n");
+    }
+    for summary in summaries {
+        result.push_str(&format!("\n## Summary\n\n{}\n", summary.summary_text));
+        if !summary.key_points.is_empty() {
+            result.push_str("\n## Key points\n\n");
+            for point in &summary.key_points {
+                result.push_str(&format!("- {}\n", point));
+            }
+        }
+        if !summary.action_items.is_empty() {
+            result.push_str("\n## Action items\n\n");
+            for item in &summary.action_items {
+                result.push_str(&format!("- [ ] {}\n", item));
+            }
+        }
+    }
+    result
+}
+
+fn zip_error(error: zip::result::ZipError) -> AppError {
+    AppError::InvalidOperation {
+        message: format!("Failed to write share bundle: {}", error),
+    }
+}
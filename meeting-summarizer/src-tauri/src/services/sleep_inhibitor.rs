@@ -0,0 +1,123 @@
+//! 録音中のスリープ防止
+//!
+//! 長い会議の途中でノートPCがスリープすると、そこで録音が途切れる。録音を始めたらスリープを止め、
+//! 停止したら元に戻す。OS ごとに次の仕組みを使う。
+//!
+//! - macOS: `caffeinate -i -w <アプリのPID>`
+//! - Windows: `SetThreadExecutionState`（専用のスレッドが録音の間だけ保持する）
+//! - Linux: `systemd-inhibit --what=sleep:idle` で `tail --pid=<アプリのPID>` を動かす
+//!
+//! macOS と Linux は外部プロセスが止めている間だけ有効で、アプリが落ちても PID の監視で自動的に解除される。
+
+use crate::errors::AppResult;
+use std::process::Command;
+#[cfg(not(windows))]
+use {
+    crate::errors::AppError,
+    std::process::{Child, Stdio},
+};
+
+const APP_NAME: &str = "Meeting Summarizer";
+
+/// スリープを止める外部プロセスのコマンド（Windows などプロセスを使わない環境では None）
+pub fn inhibit_command(reason: &str) -> Option<Command> {
+    let pid = std::process::id().to_string();
+    if cfg!(target_os = "macos") {
+        let mut command = Command::new("caffeinate");
+        command.args(["-i", "-w", &pid]);
+        Some(command)
+    } else if cfg!(target_os = "linux") {
+        let mut command = Command::new("systemd-inhibit");
+        command
+            .arg("--what=sleep:idle")
+            .arg(format!("--who={}", APP_NAME))
+            .arg(format!("--why={}", reason))
+            .arg("--mode=block")
+            .args(["tail", &format!("--pid={}", pid), "-f", "/dev/null"]);
+        Some(command)
+    } else {
+        None
+    }
+}
+
+enum Inhibition {
+    #[cfg(not(windows))]
+    Process(Child),
+    #[cfg(windows)]
+    Thread(std::sync::mpsc::Sender<()>),
+}
+
+/// スリープを止めている間だけ持つ（drop すると解除する）
+pub struct SleepInhibitor {
+    inhibition: Inhibition,
+}
+
+impl SleepInhibitor {
+    pub async fn acquire(reason: &str) -> AppResult<Self> {
+        #[cfg(windows)]
+        let inhibition = {
+            let _ = reason;
+            Inhibition::Thread(hold_execution_state())
+        };
+        #[cfg(not(windows))]
+        let inhibition = Inhibition::Process(spawn_inhibitor(reason).await?);
+
+        log::info!("☕ Preventing system sleep while recording");
+        Ok(Self { inhibition })
+    }
+}
+
+#[cfg(not(windows))]
+async fn spawn_inhibitor(reason: &str) -> AppResult<Child> {
+    let mut command = inhibit_command(reason).ok_or_else(|| AppError::InvalidOperation {
+        message: "Preventing sleep is not supported on this platform".to_string(),
+    })?;
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| AppError::InvalidOperation {
+            message: format!("Failed to prevent sleep: {}", e),
+        })?;
+    // 権限が無いなどですぐに終了した場合は止められていない（待つ間もランタイムのスレッドは塞がない）
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    if let Ok(Some(status)) = child.try_wait() {
+        return Err(AppError::InvalidOperation {
+            message: format!("Failed to prevent sleep: inhibitor exited with {}", status),
+        });
+    }
+    Ok(child)
+}
+
+impl Drop for SleepInhibitor {
+    fn drop(&mut self) {
+        match &mut self.inhibition {
+            #[cfg(not(windows))]
+            Inhibition::Process(child) => {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+            #[cfg(windows)]
+            Inhibition::Thread(release) => {
+                let _ = release.send(());
+            }
+        }
+        log::info!("☕ Allowing system sleep again");
+    }
+}
+
+/// `SetThreadExecutionState` はスレッドごとの設定なので、解除されるまで専用のスレッドで保持する
+#[cfg(windows)]
+fn hold_execution_state() -> std::sync::mpsc::Sender<()> {
+    use windows_sys::Win32::System::Power::{SetThreadExecutionState, ES_CONTINUOUS, ES_SYSTEM_REQUIRED};
+
+    let (release, released) = std::sync::mpsc::channel::<()>();
+    std::thread::spawn(move || {
+        // SAFETY: 引数はフラグだけで、呼び出したスレッドの状態を変えるだけ
+        unsafe { SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED) };
+        let _ = released.recv();
+        unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+    });
+    release
+}
@@ -0,0 +1,260 @@
+//! 話者分離済みセグメントからの会議分析
+//!
+//! 話者ごとの発話時間・発話回数（ターン数）・最長の連続発話を録音単位で計算して保存し、
+//! 人ごとに会議を横断して集計できるようにする。話者の組ごとの発話の重なり・割り込みや、
+//! 話者ごとのフィラー（「えー」「あのー」、um など）の回数も数える。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{SpeakerTalkStats, TranscriptSegment};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 既定で数えるフィラー
+pub const DEFAULT_FILLER_WORDS: &[&str] = &[
+    "えーっと", "えーと", "えー", "あのー", "あのう", "うーん", "まあ", "なんか", "um", "uh", "like", "you know",
+];
+
+/// 話者ごとのフィラーの回数（`speaker` が None なら話者不明の発言）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FillerWordStats {
+    pub speaker: Option<String>,
+    pub total: i64,
+    /// フィラーごとの回数
+    pub counts: BTreeMap<String, i64>,
+    /// 発話1分あたりの回数（セグメントの時間が分かる場合のみ）
+    pub per_minute: Option<f64>,
+}
+
+/// 話者の組ごとの重なり（`speaker` が話している途中に `other_speaker` が話し始めた回数）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeakerOverlap {
+    /// 先に話していた話者
+    pub speaker: String,
+    /// 途中から話し始めた話者
+    pub other_speaker: String,
+    pub overlap_count: i64,
+    /// 重なりのうち、先の話者が相手より先に話し終えたもの（相づちではなく発言を遮った）
+    pub interruption_count: i64,
+    pub overlap_ms: i64,
+}
+
+/// 話者ごとの発話量を計算する（話者が付いていないセグメントはターンの区切りとして扱う）
+pub fn compute_talk_stats(recording_id: &str, segments: &[TranscriptSegment]) -> Vec<SpeakerTalkStats> {
+    let mut by_speaker: BTreeMap<&str, SpeakerTalkStats> = BTreeMap::new();
+    // 現在のターンの話者と開始時刻
+    let mut current_turn: Option<(&str, i64)> = None;
+
+    for segment in segments {
+        let Some(speaker) = segment.speaker.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
+            current_turn = None;
+            continue;
+        };
+
+        let stats = by_speaker.entry(speaker).or_insert_with(|| SpeakerTalkStats {
+            recording_id: recording_id.to_string(),
+            speaker: speaker.to_string(),
+            talk_time_ms: 0,
+            turn_count: 0,
+            longest_monologue_ms: 0,
+            talk_share: 0.0,
+        });
+        stats.talk_time_ms += (segment.end_ms - segment.start_ms).max(0);
+
+        let turn_start = match current_turn {
+            Some((current, start)) if current == speaker => start,
+            _ => {
+                stats.turn_count += 1;
+                segment.start_ms
+            }
+        };
+        stats.longest_monologue_ms = stats.longest_monologue_ms.max(segment.end_ms - turn_start);
+        current_turn = Some((speaker, turn_start));
+    }
+
+    let total: i64 = by_speaker.values().map(|stats| stats.talk_time_ms).sum();
+    let mut stats: Vec<SpeakerTalkStats> = by_speaker
+        .into_values()
+        .map(|mut stats| {
+            stats.talk_share = if total > 0 { stats.talk_time_ms as f64 / total as f64 } else { 0.0 };
+            stats
+        })
+        .collect();
+    stats.sort_by(|a, b| b.talk_time_ms.cmp(&a.talk_time_ms).then_with(|| a.speaker.cmp(&b.speaker)));
+    stats
+}
+
+/// 話者の組ごとの発話の重なりと割り込みを数える（回数の多い順）
+pub fn compute_overlaps(segments: &[TranscriptSegment]) -> Vec<SpeakerOverlap> {
+    let mut spoken: Vec<(&str, &TranscriptSegment)> = segments
+        .iter()
+        .filter_map(|segment| {
+            let speaker = segment.speaker.as_deref().map(str::trim).filter(|s| !s.is_empty())?;
+            Some((speaker, segment))
+        })
+        .collect();
+    spoken.sort_by_key(|(_, segment)| (segment.start_ms, segment.end_ms));
+
+    let mut by_pair: BTreeMap<(&str, &str), SpeakerOverlap> = BTreeMap::new();
+    for (index, (speaker, segment)) in spoken.iter().enumerate() {
+        for (other_speaker, other) in &spoken[index + 1..] {
+            if other.start_ms >= segment.end_ms {
+                break;
+            }
+            if other_speaker == speaker || other.start_ms <= segment.start_ms {
+                continue;
+            }
+
+            let overlap = by_pair.entry((speaker, other_speaker)).or_insert_with(|| SpeakerOverlap {
+                speaker: speaker.to_string(),
+                other_speaker: other_speaker.to_string(),
+                overlap_count: 0,
+                interruption_count: 0,
+                overlap_ms: 0,
+            });
+            overlap.overlap_count += 1;
+            overlap.overlap_ms += segment.end_ms.min(other.end_ms) - other.start_ms;
+            if segment.end_ms < other.end_ms {
+                overlap.interruption_count += 1;
+            }
+        }
+    }
+
+    let mut overlaps: Vec<SpeakerOverlap> = by_pair.into_values().collect();
+    overlaps.sort_by(|a, b| {
+        b.interruption_count
+            .cmp(&a.interruption_count)
+            .then(b.overlap_count.cmp(&a.overlap_count))
+    });
+    overlaps
+}
+
+/// テキスト中のフィラーを数える（長い表記を優先して1パスで走査。英字は単語単位・大文字小文字を区別しない）
+pub fn count_filler_words(text: &str, filler_words: &[String]) -> BTreeMap<String, i64> {
+    let mut fillers: Vec<Vec<char>> = filler_words
+        .iter()
+        .map(|word| word.trim().to_lowercase().chars().collect::<Vec<char>>())
+        .filter(|word| !word.is_empty())
+        .collect();
+    fillers.sort_by_key(|filler| std::cmp::Reverse(filler.len()));
+    fillers.dedup();
+
+    let chars: Vec<char> = text.to_lowercase().chars().collect();
+    let is_word_char = |index: usize| chars.get(index).is_some_and(|c| c.is_ascii_alphanumeric());
+
+    let mut counts = BTreeMap::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let found = fillers.iter().find(|filler| {
+            let end = index + filler.len();
+            let ascii = filler.iter().all(|c| c.is_ascii_alphanumeric() || *c == ' ');
+            chars.get(index..end) == Some(filler.as_slice())
+                && !(ascii && ((index > 0 && is_word_char(index - 1)) || is_word_char(end)))
+        });
+        match found {
+            Some(filler) => {
+                *counts.entry(filler.iter().collect::<String>()).or_insert(0) += 1;
+                index += filler.len();
+            }
+            None => index += 1,
+        }
+    }
+    counts
+}
+
+/// 話者ごとにフィラーを数える（回数の多い順）
+pub fn compute_filler_stats(segments: &[TranscriptSegment], filler_words: &[String]) -> Vec<FillerWordStats> {
+    let mut by_speaker: BTreeMap<Option<&str>, (BTreeMap<String, i64>, i64)> = BTreeMap::new();
+    for segment in segments {
+        let speaker = segment.speaker.as_deref().map(str::trim).filter(|s| !s.is_empty());
+        let (counts, talk_time_ms) = by_speaker.entry(speaker).or_default();
+        for (word, count) in count_filler_words(&segment.text, filler_words) {
+            *counts.entry(word).or_insert(0) += count;
+        }
+        *talk_time_ms += (segment.end_ms - segment.start_ms).max(0);
+    }
+
+    let mut stats: Vec<FillerWordStats> = by_speaker
+        .into_iter()
+        .map(|(speaker, (counts, talk_time_ms))| {
+            let total = counts.values().sum();
+            FillerWordStats {
+                speaker: speaker.map(str::to_string),
+                total,
+                counts,
+                per_minute: (talk_time_ms > 0).then(|| total as f64 / (talk_time_ms as f64 / 60_000.0)),
+            }
+        })
+        .collect();
+    stats.sort_by_key(|stat| std::cmp::Reverse(stat.total));
+    stats
+}
+
+/// 録音の最新の書き起こしからフィラーを数える（セグメントが無ければ本文全体を話者不明として数える）
+pub async fn filler_word_stats(
+    database: &Database,
+    recording_id: &str,
+    filler_words: Option<Vec<String>>,
+) -> AppResult<Vec<FillerWordStats>> {
+    let filler_words =
+        filler_words.unwrap_or_else(|| DEFAULT_FILLER_WORDS.iter().map(|word| word.to_string()).collect());
+
+    database
+        .get_recording(recording_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording with id {} not found", recording_id),
+        })?;
+    let transcription = database
+        .get_transcriptions_by_recording(recording_id)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording {} has no transcription", recording_id),
+        })?;
+
+    let segments = database.get_segments_by_transcription(&transcription.id).await?;
+    if !segments.is_empty() {
+        return Ok(compute_filler_stats(&segments, &filler_words));
+    }
+
+    let counts = count_filler_words(&transcription.text, &filler_words);
+    Ok(vec![FillerWordStats {
+        speaker: None,
+        total: counts.values().sum(),
+        counts,
+        per_minute: None,
+    }])
+}
+
+/// 録音の最新の話者付き書き起こしから発話量を計算して保存する
+pub async fn analyze_recording(database: &Database, recording_id: &str) -> AppResult<Vec<SpeakerTalkStats>> {
+    let segments = diarized_segments(database, recording_id).await?;
+    let stats = compute_talk_stats(recording_id, &segments);
+    database.replace_speaker_stats(recording_id, &stats).await?;
+
+    log::info!("📊 Computed talk time for {} speaker(s) in recording {}", stats.len(), recording_id);
+    Ok(stats)
+}
+
+/// 話者が付いたセグメントを持つ最新の書き起こしのセグメント
+pub async fn diarized_segments(database: &Database, recording_id: &str) -> AppResult<Vec<TranscriptSegment>> {
+    database
+        .get_recording(recording_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording with id {} not found", recording_id),
+        })?;
+
+    for transcription in database.get_transcriptions_by_recording(recording_id).await? {
+        let segments = database.get_segments_by_transcription(&transcription.id).await?;
+        if segments.iter().any(|segment| segment.speaker.is_some()) {
+            return Ok(segments);
+        }
+    }
+
+    Err(AppError::ValidationError {
+        message: format!("Recording {} has no speaker-labelled transcript segments", recording_id),
+    })
+}
@@ -0,0 +1,172 @@
+//! 話者ごとの音声トラック書き出し
+//!
+//! 話者分離済みのセグメントをもとに、話者ごとにその人の発言区間だけを残して他を無音にした WAV を
+//! 書き出す。全トラックの長さは元の録音と同じなので、編集ソフトで重ねればそのまま揃う。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::services::audio_convert;
+use crate::services::audio_stream::{AudioChunkReader, DEFAULT_CHUNK_FRAMES};
+use crate::models::TranscriptSegment;
+use crate::services::speaker_analytics;
+use hound::{SampleFormat, WavSpec, WavWriter};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerTrack {
+    pub speaker: String,
+    pub path: PathBuf,
+    pub segment_count: usize,
+    pub talk_time_ms: i64,
+}
+
+/// 話者ごとの発言区間（ミリ秒、開始順）
+pub type SpeakerRanges = BTreeMap<String, Vec<(i64, i64)>>;
+
+/// 音声ファイルから話者ごとのトラックを `output_dir` に書き出す（出力は元と同じサンプルレートのモノラル 16bit）
+pub fn render_speaker_tracks(
+    audio_path: &Path,
+    ranges: &SpeakerRanges,
+    output_dir: &Path,
+    base_name: &str,
+) -> AppResult<Vec<SpeakerTrack>> {
+    std::fs::create_dir_all(output_dir)?;
+
+    // WAV 以外は一度 16kHz モノラル WAV に変換してから扱う
+    let input = if AudioChunkReader::open(audio_path).is_ok() {
+        audio_path.to_path_buf()
+    } else {
+        audio_convert::prepare_for_whisper(audio_path, output_dir, &format!("{}-source", base_name))?
+    };
+    let result = write_tracks(&input, ranges, output_dir, base_name);
+    if input != audio_path {
+        let _ = std::fs::remove_file(&input);
+    }
+    result
+}
+
+fn write_tracks(input: &Path, ranges: &SpeakerRanges, output_dir: &Path, base_name: &str) -> AppResult<Vec<SpeakerTrack>> {
+    let mut reader = AudioChunkReader::open(input)?;
+    let sample_rate = reader.spec().sample_rate;
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+
+    let mut tracks = Vec::new();
+    let mut writers = Vec::new();
+    for (index, (speaker, speaker_ranges)) in ranges.iter().enumerate() {
+        let path = output_dir.join(format!("{}-{:02}-{}.wav", base_name, index + 1, file_name_part(speaker)));
+        let writer = WavWriter::create(&path, spec).map_err(|e| track_error(&path, e))?;
+        writers.push((writer, speaker_ranges.as_slice(), 0usize));
+        tracks.push(SpeakerTrack {
+            speaker: speaker.clone(),
+            path,
+            segment_count: speaker_ranges.len(),
+            talk_time_ms: speaker_ranges.iter().map(|(start, end)| (end - start).max(0)).sum(),
+        });
+    }
+
+    let mut frame_index: u64 = 0;
+    while let Some(chunk) = reader.next_chunk(DEFAULT_CHUNK_FRAMES)? {
+        for sample in chunk {
+            let time_ms = (frame_index * 1000 / sample_rate as u64) as i64;
+            let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            for (writer, speaker_ranges, cursor) in writers.iter_mut() {
+                // 区間は開始順なので、過ぎた区間はカーソルを進めて読み飛ばす
+                while *cursor < speaker_ranges.len() && speaker_ranges[*cursor].1 <= time_ms {
+                    *cursor += 1;
+                }
+                let audible = speaker_ranges
+                    .get(*cursor)
+                    .is_some_and(|(start, _)| *start <= time_ms);
+                writer
+                    .write_sample(if audible { value } else { 0 })
+                    .map_err(|e| track_error(input, e))?;
+            }
+            frame_index += 1;
+        }
+    }
+
+    for (writer, _, _) in writers {
+        writer.finalize().map_err(|e| track_error(input, e))?;
+    }
+    Ok(tracks)
+}
+
+/// セグメントを話者ごとの区間にまとめる（重なった区間・接した区間は結合する）
+pub fn speaker_ranges(segments: &[TranscriptSegment]) -> SpeakerRanges {
+    let mut ranges: SpeakerRanges = BTreeMap::new();
+    for segment in segments {
+        let Some(speaker) = segment.speaker.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
+            continue;
+        };
+        ranges
+            .entry(speaker.to_string())
+            .or_default()
+            .push((segment.start_ms, segment.end_ms));
+    }
+
+    for speaker_ranges in ranges.values_mut() {
+        speaker_ranges.sort();
+        let mut merged: Vec<(i64, i64)> = Vec::with_capacity(speaker_ranges.len());
+        for &(start, end) in speaker_ranges.iter() {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        *speaker_ranges = merged;
+    }
+    ranges
+}
+
+/// 録音の話者ごとのトラックを書き出す
+pub async fn export_speaker_tracks(
+    database: &Database,
+    recording_id: &str,
+    output_dir: &Path,
+) -> AppResult<Vec<SpeakerTrack>> {
+    let recording = database
+        .get_recording(recording_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording with id {} not found", recording_id),
+        })?;
+    let segments = speaker_analytics::diarized_segments(database, recording_id).await?;
+    let ranges = speaker_ranges(&segments);
+
+    let audio_path = PathBuf::from(&recording.file_path);
+    let output_dir = output_dir.to_path_buf();
+    let base_name = Path::new(&recording.filename)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| recording.id.clone());
+
+    let tracks = tokio::task::spawn_blocking(move || render_speaker_tracks(&audio_path, &ranges, &output_dir, &base_name))
+        .await
+        .map_err(|e| AppError::AudioConversion {
+            message: format!("Speaker track task failed: {}", e),
+        })??;
+
+    log::info!("🎚️ Exported {} speaker track(s) for recording {}", tracks.len(), recording_id);
+    Ok(tracks)
+}
+
+/// ファイル名に使えない文字を置き換える
+fn file_name_part(speaker: &str) -> String {
+    speaker
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+fn track_error(path: &Path, error: hound::Error) -> AppError {
+    AppError::AudioConversion {
+        message: format!("Failed to write speaker track {:?}: {}", path, error),
+    }
+}
@@ -0,0 +1,166 @@
+//! 録音ディレクトリの容量上限と警告
+//!
+//! 設定した上限に対する使用率がしきい値（既定 80% / 95%）を超えたら `storage-quota-warning`
+//! イベントで通知する。あわせて、アーカイブまたは削除すると最も容量を空けられる古い録音を
+//! サイズの大きい順に提示する。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::services::app_settings::StorageSettings;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+
+pub const STORAGE_WARNING_EVENT: &str = "storage-quota-warning";
+
+/// 定期チェックの間隔
+pub const STORAGE_CHECK_INTERVAL_SECS: u64 = 300;
+
+/// 容量を空ける候補として提示する最大件数
+const MAX_RECLAIM_CANDIDATES: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageUsage {
+    pub used_bytes: u64,
+    pub quota_bytes: Option<u64>,
+    /// 上限に対する使用率（上限未設定なら None）
+    pub usage_ratio: Option<f64>,
+    /// 超えている最も高いしきい値
+    pub crossed_threshold: Option<f64>,
+}
+
+/// 容量を空ける候補の録音
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReclaimCandidate {
+    pub recording_id: String,
+    pub title: Option<String>,
+    pub filename: String,
+    pub file_size: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageReport {
+    pub usage: StorageUsage,
+    pub candidates: Vec<ReclaimCandidate>,
+    /// 候補をすべてアーカイブまたは削除した場合に空く容量
+    pub reclaimable_bytes: u64,
+}
+
+/// ディレクトリ以下のファイルサイズの合計（シンボリックリンクはたどらない）
+pub fn directory_size(dir: &Path) -> AppResult<u64> {
+    if !dir.exists() {
+        return Ok(0);
+    }
+
+    let mut total = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            total += directory_size(&entry.path())?;
+        } else if file_type.is_file() {
+            total += entry.metadata()?.len();
+        }
+    }
+    Ok(total)
+}
+
+/// 使用率が超えている最も高いしきい値
+pub fn crossed_threshold(usage_ratio: f64, thresholds: &[f64]) -> Option<f64> {
+    thresholds
+        .iter()
+        .copied()
+        .filter(|threshold| usage_ratio >= *threshold)
+        .max_by(|a, b| a.total_cmp(b))
+}
+
+pub fn compute_usage(used_bytes: u64, settings: &StorageSettings) -> StorageUsage {
+    let quota_bytes = settings.quota_bytes.filter(|quota| *quota > 0);
+    let usage_ratio = quota_bytes.map(|quota| used_bytes as f64 / quota as f64);
+
+    StorageUsage {
+        used_bytes,
+        quota_bytes,
+        usage_ratio,
+        crossed_threshold: usage_ratio.and_then(|ratio| crossed_threshold(ratio, &settings.warning_thresholds)),
+    }
+}
+
+/// `min_age_days` より古い録音をファイルサイズの大きい順に返す
+pub async fn reclaim_candidates(
+    database: &Database,
+    min_age_days: i64,
+    now: DateTime<Utc>,
+) -> AppResult<Vec<ReclaimCandidate>> {
+    let cutoff = now - Duration::days(min_age_days);
+
+    let mut candidates: Vec<ReclaimCandidate> = database
+        .get_all_recordings()
+        .await?
+        .into_iter()
+        .filter(|recording| recording.created_at <= cutoff)
+        .filter_map(|recording| {
+            // ファイルが無い録音は空けられる容量が無い
+            let file_size = std::fs::metadata(&recording.file_path).ok()?.len();
+            Some(ReclaimCandidate {
+                recording_id: recording.id,
+                title: recording.title,
+                filename: recording.filename,
+                file_size,
+                created_at: recording.created_at,
+            })
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| b.file_size.cmp(&a.file_size).then(a.created_at.cmp(&b.created_at)));
+    candidates.truncate(MAX_RECLAIM_CANDIDATES);
+    Ok(candidates)
+}
+
+/// 録音ディレクトリの使用量と容量を空ける候補
+pub async fn storage_report(
+    database: &Database,
+    recordings_dir: &Path,
+    settings: &StorageSettings,
+) -> AppResult<StorageReport> {
+    let dir = recordings_dir.to_path_buf();
+    let used_bytes = tokio::task::spawn_blocking(move || directory_size(&dir))
+        .await
+        .map_err(|e| AppError::InvalidOperation {
+            message: format!("Storage scan task failed: {}", e),
+        })??;
+
+    let candidates = reclaim_candidates(database, settings.candidate_min_age_days, Utc::now()).await?;
+    Ok(StorageReport {
+        usage: compute_usage(used_bytes, settings),
+        reclaimable_bytes: candidates.iter().map(|candidate| candidate.file_size).sum(),
+        candidates,
+    })
+}
+
+/// しきい値を新たに超えたときだけ警告を出すための状態
+#[derive(Default)]
+pub struct StorageQuotaMonitor {
+    last_threshold: Mutex<Option<f64>>,
+}
+
+impl StorageQuotaMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 前回より高いしきい値を超えていれば true。使用量が下がったらリセットする
+    pub fn should_warn(&self, usage: &StorageUsage) -> bool {
+        let mut last = self.last_threshold.lock().unwrap_or_else(|e| e.into_inner());
+        let crossed = usage.crossed_threshold;
+        let warn = match (crossed, *last) {
+            (Some(current), Some(previous)) => current > previous,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        *last = crossed;
+        warn
+    }
+}
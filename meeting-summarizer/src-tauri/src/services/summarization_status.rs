@@ -0,0 +1,109 @@
+//! 要約処理の進捗レジストリ
+//!
+//! ストリーミング要約コマンドが段階ごとに状態を記録し、画面リロード後も
+//! 書き起こしIDから進行中・直近の要約の状態を取得できるようにする。
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// 完了・失敗した状態を保持する期間
+const FINISHED_RETENTION_MINUTES: i64 = 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummarizationStatus {
+    pub transcription_id: String,
+    pub stage: String,
+    pub message: String,
+    pub progress: f32,
+    pub summary_id: Option<String>,
+    /// 生成済みテキストの文字数
+    pub partial_text_length: usize,
+    pub completed: bool,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SummarizationStatus {
+    fn new(transcription_id: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            transcription_id: transcription_id.to_string(),
+            stage: "initializing".to_string(),
+            message: String::new(),
+            progress: 0.0,
+            summary_id: None,
+            partial_text_length: 0,
+            completed: false,
+            error: None,
+            started_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.completed || self.error.is_some()
+    }
+}
+
+/// 書き起こしIDをキーにした要約状態のマップ
+#[derive(Default)]
+pub struct SummarizationStatusRegistry {
+    statuses: RwLock<HashMap<String, SummarizationStatus>>,
+}
+
+impl SummarizationStatusRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 新しい要約の開始を記録（同じ書き起こしの前回の状態は置き換える）
+    pub fn start(&self, transcription_id: &str, message: String) -> SummarizationStatus {
+        let status = SummarizationStatus {
+            message,
+            ..SummarizationStatus::new(transcription_id)
+        };
+
+        if let Ok(mut statuses) = self.statuses.write() {
+            Self::prune(&mut statuses);
+            statuses.insert(transcription_id.to_string(), status.clone());
+        }
+        status
+    }
+
+    /// 状態を更新して更新後の値を返す。未登録なら開始扱いで登録する
+    pub fn update<F>(&self, transcription_id: &str, updater: F) -> Option<SummarizationStatus>
+    where
+        F: FnOnce(&mut SummarizationStatus),
+    {
+        let mut statuses = self.statuses.write().ok()?;
+        let status = statuses
+            .entry(transcription_id.to_string())
+            .or_insert_with(|| SummarizationStatus::new(transcription_id));
+
+        updater(status);
+        status.updated_at = Utc::now();
+        Some(status.clone())
+    }
+
+    pub fn get(&self, transcription_id: &str) -> Option<SummarizationStatus> {
+        self.statuses.read().ok()?.get(transcription_id).cloned()
+    }
+
+    /// 進行中の要約のみ
+    pub fn active(&self) -> Vec<SummarizationStatus> {
+        let Ok(statuses) = self.statuses.read() else {
+            return Vec::new();
+        };
+        let mut active: Vec<_> = statuses.values().filter(|s| !s.is_finished()).cloned().collect();
+        active.sort_by_key(|s| s.started_at);
+        active
+    }
+
+    fn prune(statuses: &mut HashMap<String, SummarizationStatus>) {
+        let cutoff = Utc::now() - Duration::minutes(FINISHED_RETENTION_MINUTES);
+        statuses.retain(|_, status| !status.is_finished() || status.updated_at > cutoff);
+    }
+}
@@ -0,0 +1,171 @@
+//! 要約の共通経路
+//!
+//! 画面のコマンド・進捗付きの要約・ジョブキュー・REST/gRPC のどこから要約しても、
+//! ここを通して同じ手順で要約する。明示的な設定が無ければ、同時要約が設定されていれば2つの
+//! プロバイダーで競わせ、そうでなければ自動切り替えで選んだモデルを使う。Ollama を使う場合に
+//! 複数のホストが設定されていれば、負荷に応じて振り分ける。会議中のメモとマーカー付近の発言も
+//! どの経路でも一緒に渡し、承認済みで固定された要約はどの経路からも再生成できない。
+//! 複数の会議のダイジェストも、画面から作る場合と定期配信とで同じくここを通してモデルを選ぶ。
+
+use crate::database::Database;
+use crate::errors::AppResult;
+use crate::models::{LLMConfig, LLMProvider, Summary};
+use crate::services::digest::{self, DigestScope, MeetingDigest};
+use crate::services::llm::SummaryContext;
+use crate::services::{
+    demo_mode, meeting_notes, model_selection, provider_race, recording_markers, summary_review, LLMModelManager,
+    LLMService, ModelSettingsManager, OllamaPool, ProviderRace,
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// 要約の依頼先
+#[derive(Debug, Clone)]
+pub enum SummaryRoute {
+    /// 1つのモデルで要約する
+    Single(LLMConfig),
+    /// 2つのプロバイダーへ同時に依頼し、先に成功した方を使う
+    Race(ProviderRace),
+    /// 複数のOllamaホストのうち空いているものから順に使う
+    OllamaPool { hosts: Vec<String>, config: LLMConfig },
+}
+
+impl SummaryRoute {
+    /// 進捗表示用のモデル名
+    pub fn model_label(&self) -> String {
+        match self {
+            Self::Single(config) | Self::OllamaPool { config, .. } => config.model_name.clone(),
+            Self::Race(race) => format!("{} / {}", race.primary.model_name, race.secondary.model_name),
+        }
+    }
+}
+
+pub struct Summarizer {
+    db: Arc<Database>,
+    settings_manager: Arc<Mutex<ModelSettingsManager>>,
+    model_manager: Arc<Mutex<LLMModelManager>>,
+    ollama_pool: Arc<OllamaPool>,
+}
+
+impl Summarizer {
+    pub fn new(
+        db: Arc<Database>,
+        settings_manager: Arc<Mutex<ModelSettingsManager>>,
+        model_manager: Arc<Mutex<LLMModelManager>>,
+        ollama_pool: Arc<OllamaPool>,
+    ) -> Self {
+        Self {
+            db,
+            settings_manager,
+            model_manager,
+            ollama_pool,
+        }
+    }
+
+    /// 明示的な設定が無ければ、自動切り替えが有効な場合に選んだモデルを使う（無効・失敗時は既定の設定）
+    pub async fn resolve_config(&self, model_config: Option<LLMConfig>, transcription_text: &str) -> LLMConfig {
+        if let Some(config) = model_config {
+            return config;
+        }
+        if demo_mode::is_enabled() {
+            return demo_mode::llm_config();
+        }
+        match model_selection::auto_select(&self.settings_manager, &self.model_manager, transcription_text.chars().count())
+            .await
+        {
+            Ok(Some(selection)) => selection.config,
+            Ok(None) => LLMConfig::default(),
+            Err(e) => {
+                log::warn!("⚠️ Automatic model selection failed, using the default config: {}", e);
+                LLMConfig::default()
+            }
+        }
+    }
+
+    /// 要約の依頼先を決める（明示的な設定があればそれだけを使う）
+    pub async fn route(&self, model_config: Option<LLMConfig>, transcription_text: &str) -> SummaryRoute {
+        let (race, ollama_hosts) = {
+            let manager = self.settings_manager.lock().await;
+            let settings = manager.get_settings();
+            (settings.provider_race.clone(), settings.ollama_hosts.clone())
+        };
+        if let Some(race) = race.filter(|_| model_config.is_none() && !demo_mode::is_enabled()) {
+            return SummaryRoute::Race(race);
+        }
+
+        let config = self.resolve_config(model_config, transcription_text).await;
+        if matches!(config.provider, LLMProvider::Ollama) && !ollama_hosts.is_empty() {
+            return SummaryRoute::OllamaPool { hosts: ollama_hosts, config };
+        }
+        SummaryRoute::Single(config)
+    }
+
+    /// 要約に含める会議中のメモとマーカー付近の発言（取得に失敗した場合はそれ無しで要約する）
+    pub async fn context(&self, transcription_id: &str, include_notes: Option<bool>) -> SummaryContext {
+        if !include_notes.unwrap_or(true) {
+            return SummaryContext::default();
+        }
+        let notes = match meeting_notes::notes_for_transcription(&self.db, transcription_id).await {
+            Ok(notes) => notes,
+            Err(e) => {
+                log::warn!("⚠️ Failed to load meeting notes for transcription {}: {}", transcription_id, e);
+                Vec::new()
+            }
+        };
+        let marker_excerpts = match recording_markers::excerpts_for_transcription(&self.db, transcription_id).await {
+            Ok(excerpts) => excerpts,
+            Err(e) => {
+                log::warn!("⚠️ Failed to load recording markers for transcription {}: {}", transcription_id, e);
+                Vec::new()
+            }
+        };
+        SummaryContext { notes, marker_excerpts }
+    }
+
+    /// 書き起こしを要約する（`include_notes` を省略するとメモも渡す。保存は呼び出し側で行う）
+    pub async fn summarize(
+        &self,
+        transcription_id: &str,
+        transcription_text: &str,
+        model_config: Option<LLMConfig>,
+        include_notes: Option<bool>,
+    ) -> AppResult<Summary> {
+        let route = self.route(model_config, transcription_text).await;
+        let context = self.context(transcription_id, include_notes).await;
+        self.summarize_via(&route, transcription_id, transcription_text, &context).await
+    }
+
+    /// 複数の会議のダイジェストを作る（明示的な設定が無ければ自動切り替えで選んだモデルを使う）
+    pub async fn generate_digest(&self, model_config: Option<LLMConfig>, scope: &DigestScope) -> AppResult<MeetingDigest> {
+        let config = self.resolve_config(model_config, "").await;
+        digest::generate_digest(&self.db, &LLMService::new(config), scope).await
+    }
+
+    /// 決めた依頼先で要約する（進捗を表示する経路向け）
+    pub async fn summarize_via(
+        &self,
+        route: &SummaryRoute,
+        transcription_id: &str,
+        transcription_text: &str,
+        context: &SummaryContext,
+    ) -> AppResult<Summary> {
+        // 承認済みで固定された要約は再生成しない
+        summary_review::ensure_can_regenerate(&self.db, transcription_id).await?;
+
+        match route {
+            SummaryRoute::Single(config) => {
+                LLMService::new(config.clone())
+                    .summarize_text_with_context(transcription_text, context, transcription_id.to_string())
+                    .await
+            }
+            SummaryRoute::Race(race) => {
+                provider_race::race_summaries(race, transcription_text, context, transcription_id.to_string()).await
+            }
+            SummaryRoute::OllamaPool { hosts, config } => {
+                self.ollama_pool
+                    .summarize(hosts, config, transcription_text, context, transcription_id.to_string())
+                    .await
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! 要約の承認ワークフロー
+//!
+//! 議事録を配布前に承認する運用向け。要約は下書き（draft）として作成され、承認（approved）した
+//! 要約は固定（lock）できる。固定中はその書き起こしの要約の再生成・編集・削除ができない。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{ReviewStatus, Summary, SummaryStatus};
+use chrono::Utc;
+
+/// 要約を承認する（`lock` を指定すると同時に固定する）
+pub async fn approve_summary(
+    database: &Database,
+    summary_id: &str,
+    approved_by: Option<String>,
+    lock: bool,
+) -> AppResult<Summary> {
+    let summary = find_summary(database, summary_id).await?;
+    if !matches!(summary.status, SummaryStatus::Completed) {
+        return Err(AppError::InvalidOperation {
+            message: format!("Only completed summaries can be approved (summary {})", summary_id),
+        });
+    }
+
+    let approved_by = approved_by.map(|name| name.trim().to_string()).filter(|name| !name.is_empty());
+    database
+        .set_summary_review(summary_id, ReviewStatus::Approved, approved_by.as_deref(), Some(Utc::now()))
+        .await?;
+    if lock {
+        database.set_summary_locked(summary_id, true).await?;
+    }
+
+    log::info!("✅ Summary {} approved by {:?}", summary_id, approved_by);
+    find_summary(database, summary_id).await
+}
+
+/// 承認を取り消して下書きに戻す（固定も解除される）
+pub async fn reopen_summary(database: &Database, summary_id: &str) -> AppResult<Summary> {
+    find_summary(database, summary_id).await?;
+    database
+        .set_summary_review(summary_id, ReviewStatus::Draft, None, None)
+        .await?;
+
+    log::info!("📝 Summary {} reopened as draft", summary_id);
+    find_summary(database, summary_id).await
+}
+
+/// 承認済みの要約を固定・固定解除する
+pub async fn set_summary_locked(database: &Database, summary_id: &str, locked: bool) -> AppResult<Summary> {
+    let summary = find_summary(database, summary_id).await?;
+    if locked && summary.review_status != ReviewStatus::Approved {
+        return Err(AppError::InvalidOperation {
+            message: format!("Summary {} must be approved before it can be locked", summary_id),
+        });
+    }
+
+    database.set_summary_locked(summary_id, locked).await?;
+    log::info!("🔒 Summary {} lock set to {}", summary_id, locked);
+    find_summary(database, summary_id).await
+}
+
+/// 要約を再生成できるか確認する（LLM を呼ぶ前のチェック用）
+pub async fn ensure_can_regenerate(database: &Database, transcription_id: &str) -> AppResult<()> {
+    if database.has_locked_summary(transcription_id).await? {
+        return Err(AppError::InvalidOperation {
+            message: format!(
+                "The summary for transcription {} is approved and locked; unlock it before regenerating",
+                transcription_id
+            ),
+        });
+    }
+    Ok(())
+}
+
+async fn find_summary(database: &Database, summary_id: &str) -> AppResult<Summary> {
+    database
+        .get_summary(summary_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Summary with id {} not found", summary_id),
+        })
+}
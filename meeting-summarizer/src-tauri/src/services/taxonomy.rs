@@ -0,0 +1,83 @@
+//! 階層化されたカテゴリ・タグ
+//!
+//! カテゴリとタグは `Clients/ACME/Project-X` のように `/` 区切りのパスで階層を表す。
+//! 親ノードの件数・時間は子孫の録音も含めて集計する（1つの録音は各ノードで1回だけ数える）。
+
+use crate::models::{TaxonomyAssignment, TaxonomyNode};
+use std::collections::{BTreeMap, HashSet};
+
+pub const PATH_SEPARATOR: char = '/';
+
+/// 区切りの前後の空白と空の階層を取り除く（例: " Clients / ACME// " → "Clients/ACME"）
+pub fn normalize_path(path: &str) -> String {
+    path.split(PATH_SEPARATOR)
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join(&PATH_SEPARATOR.to_string())
+}
+
+/// 自身を含む祖先のパス（浅い順）
+pub fn ancestors(path: &str) -> Vec<String> {
+    let segments: Vec<&str> = path.split(PATH_SEPARATOR).filter(|s| !s.is_empty()).collect();
+    (1..=segments.len())
+        .map(|depth| segments[..depth].join(&PATH_SEPARATOR.to_string()))
+        .collect()
+}
+
+/// `path` 自身またはその子孫か
+pub fn is_within(candidate: &str, path: &str) -> bool {
+    candidate == path
+        || candidate
+            .strip_prefix(path)
+            .is_some_and(|rest| rest.starts_with(PATH_SEPARATOR))
+}
+
+#[derive(Default)]
+struct NodeAccumulator {
+    direct_count: i64,
+    recordings: HashSet<String>,
+    total_duration: i64,
+}
+
+/// 割り当ての一覧から階層ツリーを組み立てる（名前順）
+pub fn build_tree(assignments: &[TaxonomyAssignment]) -> Vec<TaxonomyNode> {
+    let mut nodes: BTreeMap<String, NodeAccumulator> = BTreeMap::new();
+
+    for assignment in assignments {
+        let path = normalize_path(&assignment.path);
+        if path.is_empty() {
+            continue;
+        }
+        nodes.entry(path.clone()).or_default().direct_count += 1;
+        for ancestor in ancestors(&path) {
+            let node = nodes.entry(ancestor).or_default();
+            if node.recordings.insert(assignment.recording_id.clone()) {
+                node.total_duration += assignment.duration;
+            }
+        }
+    }
+
+    fn children_of(parent: Option<&str>, nodes: &BTreeMap<String, NodeAccumulator>) -> Vec<TaxonomyNode> {
+        nodes
+            .iter()
+            .filter(|(path, _)| match parent {
+                Some(parent) => path
+                    .strip_prefix(parent)
+                    .and_then(|rest| rest.strip_prefix(PATH_SEPARATOR))
+                    .is_some_and(|rest| !rest.contains(PATH_SEPARATOR)),
+                None => !path.contains(PATH_SEPARATOR),
+            })
+            .map(|(path, node)| TaxonomyNode {
+                name: path.rsplit(PATH_SEPARATOR).next().unwrap_or(path).to_string(),
+                path: path.clone(),
+                count: node.direct_count,
+                total_count: node.recordings.len() as i64,
+                total_duration: node.total_duration,
+                children: children_of(Some(path), nodes),
+            })
+            .collect()
+    }
+
+    children_of(None, &nodes)
+}
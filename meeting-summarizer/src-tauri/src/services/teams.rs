@@ -0,0 +1,173 @@
+//! Microsoft Teams チャンネルへの要約投稿（Incoming Webhook + Adaptive Card）
+
+use crate::errors::{AppError, AppResult};
+use crate::models::{Summary, Transcription};
+use crate::services::credentials;
+use reqwest::Client;
+use serde_json::{json, Value};
+
+const WEBHOOK_KEY: &str = "teams-webhook-url";
+/// カード内に展開表示する書き起こしの最大文字数（Teamsのペイロード上限対策）
+const TRANSCRIPT_EXCERPT_CHARS: usize = 2000;
+
+pub struct TeamsService {
+    client: Client,
+    webhook_url: String,
+}
+
+impl TeamsService {
+    pub fn new(webhook_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            webhook_url,
+        }
+    }
+
+    /// キーチェーンに保存済みのWebhook URLから生成
+    pub fn from_stored_webhook() -> AppResult<Self> {
+        let webhook_url = credentials::load_secret(WEBHOOK_KEY)?.ok_or_else(|| AppError::Integration {
+            message: "Teams webhook is not configured".to_string(),
+        })?;
+        Ok(Self::new(webhook_url))
+    }
+
+    /// Webhook URLを検証して保存
+    pub fn save_webhook(webhook_url: &str) -> AppResult<()> {
+        let url = reqwest::Url::parse(webhook_url).map_err(|e| AppError::ValidationError {
+            message: format!("Invalid webhook URL: {}", e),
+        })?;
+        if url.scheme() != "https" {
+            return Err(AppError::ValidationError {
+                message: "Teams webhook URL must use https".to_string(),
+            });
+        }
+        credentials::save_secret(WEBHOOK_KEY, webhook_url)
+    }
+
+    pub fn is_configured() -> AppResult<bool> {
+        Ok(credentials::load_secret(WEBHOOK_KEY)?.is_some())
+    }
+
+    pub fn clear_webhook() -> AppResult<()> {
+        credentials::delete_secret(WEBHOOK_KEY)
+    }
+
+    /// 要約をAdaptive Cardとして投稿
+    pub async fn post_summary(
+        &self,
+        title: &str,
+        summary: &Summary,
+        transcription: Option<&Transcription>,
+        transcript_url: Option<&str>,
+    ) -> AppResult<()> {
+        let payload = json!({
+            "type": "message",
+            "attachments": [{
+                "contentType": "application/vnd.microsoft.card.adaptive",
+                "contentUrl": null,
+                "content": build_adaptive_card(title, summary, transcription, transcript_url),
+            }]
+        });
+
+        let response = self.client.post(&self.webhook_url).json(&payload).send().await?;
+        if !response.status().is_success() {
+            return Err(AppError::Integration {
+                message: format!(
+                    "Teams webhook returned {}: {}",
+                    response.status(),
+                    response.text().await.unwrap_or_default()
+                ),
+            });
+        }
+
+        log::info!("💬 Posted summary {} to Teams", summary.id);
+        Ok(())
+    }
+}
+
+/// 要約・キーポイント・アクションアイテムと、折りたたみ式の書き起こし欄を持つカードを生成
+pub fn build_adaptive_card(
+    title: &str,
+    summary: &Summary,
+    transcription: Option<&Transcription>,
+    transcript_url: Option<&str>,
+) -> Value {
+    let mut body = vec![
+        json!({ "type": "TextBlock", "text": title, "size": "Large", "weight": "Bolder", "wrap": true }),
+        json!({
+            "type": "TextBlock",
+            "text": format!("{} · {}", summary.created_at.format("%Y-%m-%d %H:%M"), summary.model_used),
+            "isSubtle": true,
+            "spacing": "None",
+            "wrap": true
+        }),
+        json!({ "type": "TextBlock", "text": summary.summary_text, "wrap": true }),
+    ];
+
+    if !summary.key_points.is_empty() {
+        body.push(json!({ "type": "TextBlock", "text": "Key Points", "weight": "Bolder", "separator": true }));
+        body.push(json!({ "type": "TextBlock", "text": bullet_list(&summary.key_points), "wrap": true }));
+    }
+
+    if !summary.action_items.is_empty() {
+        body.push(json!({ "type": "TextBlock", "text": "Action Items", "weight": "Bolder", "separator": true }));
+        body.push(json!({ "type": "TextBlock", "text": bullet_list(&summary.action_items), "wrap": true }));
+    }
+
+    let mut actions = Vec::new();
+
+    if transcription.is_some() || transcript_url.is_some() {
+        let mut transcript_items = Vec::new();
+        if let Some(transcription) = transcription {
+            let excerpt: String = transcription.text.chars().take(TRANSCRIPT_EXCERPT_CHARS).collect();
+            let truncated = transcription.text.chars().count() > TRANSCRIPT_EXCERPT_CHARS;
+            transcript_items.push(json!({
+                "type": "TextBlock",
+                "text": if truncated { format!("{}…", excerpt) } else { excerpt },
+                "wrap": true,
+                "size": "Small"
+            }));
+        }
+        if let Some(url) = transcript_url {
+            transcript_items.push(json!({
+                "type": "TextBlock",
+                "text": format!("[Open full transcript]({})", url),
+                "wrap": true
+            }));
+        }
+
+        body.push(json!({
+            "type": "Container",
+            "id": "transcript",
+            "isVisible": false,
+            "separator": true,
+            "items": transcript_items
+        }));
+        actions.push(json!({
+            "type": "Action.ToggleVisibility",
+            "title": "Show transcript",
+            "targetElements": ["transcript"]
+        }));
+    }
+
+    if let Some(url) = transcript_url {
+        actions.push(json!({ "type": "Action.OpenUrl", "title": "Open transcript", "url": url }));
+    }
+
+    json!({
+        "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+        "type": "AdaptiveCard",
+        "version": "1.4",
+        "msteams": { "width": "Full" },
+        "body": body,
+        "actions": actions
+    })
+}
+
+fn bullet_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|item| format!("- {}", item))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
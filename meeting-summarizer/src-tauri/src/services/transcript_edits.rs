@@ -0,0 +1,180 @@
+//! 書き起こしセグメントの修正（録り直し・手入力）
+//!
+//! 聞き取れなかったセグメントについて、短い差し替え音声を録り直してその部分だけを書き起こし、
+//! セグメントと書き起こし本文を置き換える。修正前後の文言は履歴として残す。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{TranscriptEdit, TranscriptEditSource, TranscriptSegment};
+use crate::services::{corrections, WhisperService};
+use chrono::Utc;
+use std::path::Path;
+use uuid::Uuid;
+
+/// 手入力で修正したセグメントの信頼度（確認済みとして扱う）
+const MANUAL_EDIT_CONFIDENCE: f32 = 1.0;
+
+/// 差し替え音声を書き起こしてセグメントを置き換える
+pub async fn rerecord_segment(
+    database: &Database,
+    whisper_service: &WhisperService,
+    segment_id: &str,
+    snippet_path: &Path,
+    language: Option<String>,
+) -> AppResult<TranscriptEdit> {
+    let segment = find_segment(database, segment_id).await?;
+    let transcription = database
+        .get_transcription(&segment.transcription_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Transcription with id {} not found", segment.transcription_id),
+        })?;
+
+    if !whisper_service.is_initialized().await {
+        whisper_service.initialize().await?;
+    }
+
+    // 録音全体ではなくスニペットのみを書き起こす（ロックは録音ではなくセグメント単位）
+    let mut snippet = whisper_service
+        .transcribe_audio_file(
+            snippet_path,
+            format!("snippet-{}", segment.id),
+            Some(language.unwrap_or(transcription.language)),
+        )
+        .await?;
+    corrections::apply_corrections(database, &mut snippet).await?;
+
+    let new_text = snippet.text.trim();
+    if new_text.is_empty() {
+        return Err(AppError::TranscriptionFailed {
+            message: "Replacement snippet produced no text".to_string(),
+        });
+    }
+
+    apply_edit(
+        database,
+        segment,
+        new_text,
+        TranscriptEditSource::Rerecording,
+        Some(snippet_path),
+        snippet.confidence,
+    )
+    .await
+}
+
+/// セグメントの文言を手入力で修正する
+pub async fn edit_segment_text(database: &Database, segment_id: &str, new_text: &str) -> AppResult<TranscriptEdit> {
+    let new_text = new_text.trim();
+    if new_text.is_empty() {
+        return Err(AppError::ValidationError {
+            message: "Segment text must not be empty".to_string(),
+        });
+    }
+
+    let segment = find_segment(database, segment_id).await?;
+    apply_edit(
+        database,
+        segment,
+        new_text,
+        TranscriptEditSource::Manual,
+        None,
+        Some(MANUAL_EDIT_CONFIDENCE),
+    )
+    .await
+}
+
+async fn find_segment(database: &Database, segment_id: &str) -> AppResult<TranscriptSegment> {
+    database
+        .get_segment(segment_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Transcript segment with id {} not found", segment_id),
+        })
+}
+
+async fn apply_edit(
+    database: &Database,
+    segment: TranscriptSegment,
+    new_text: &str,
+    source: TranscriptEditSource,
+    snippet_path: Option<&Path>,
+    confidence: Option<f32>,
+) -> AppResult<TranscriptEdit> {
+    let transcription = database
+        .get_transcription(&segment.transcription_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Transcription with id {} not found", segment.transcription_id),
+        })?;
+    let segments = database.get_segments_by_transcription(&segment.transcription_id).await?;
+    let text = splice_text(&transcription.text, &segments, &segment.id, new_text);
+
+    let edit = TranscriptEdit {
+        id: Uuid::new_v4().to_string(),
+        transcription_id: segment.transcription_id.clone(),
+        segment_id: segment.id.clone(),
+        previous_text: segment.text.clone(),
+        new_text: new_text.to_string(),
+        source,
+        snippet_path: snippet_path.map(|path| path.to_string_lossy().to_string()),
+        created_at: Utc::now(),
+    };
+    database.apply_transcript_edit(&edit, confidence, &text).await?;
+
+    log::info!(
+        "✏️ Replaced segment {} of transcription {} ({})",
+        segment.segment_index,
+        segment.transcription_id,
+        source.as_str()
+    );
+    Ok(edit)
+}
+
+/// 書き起こし本文中の該当セグメントの文言を差し替える
+///
+/// 本文がセグメントから組み立てたもの（取り込み時の形式）ならそのまま組み立て直す。そうでなければ
+/// 前のセグメントを先頭から順に辿った位置より後ろにある最初の一致箇所を置き換え（Whisper の本文は
+/// セグメントを区切らずに連結したもの）、見つからない場合はセグメントから組み立て直す。
+pub fn splice_text(full_text: &str, segments: &[TranscriptSegment], segment_id: &str, new_text: &str) -> String {
+    let Some(position) = segments.iter().position(|segment| segment.id == segment_id) else {
+        return full_text.to_string();
+    };
+
+    let mut edited = segments.to_vec();
+    edited[position].text = new_text.to_string();
+    if full_text == join_segments(segments) {
+        return join_segments(&edited);
+    }
+
+    let previous_text = segments[position].text.trim();
+    if !previous_text.is_empty() {
+        // 先頭から順にセグメントを辿り、同じ文言が前にあっても該当セグメントの位置から探す
+        let search_from = segments[..position].iter().fold(0, |cursor, segment| {
+            let before = segment.text.trim();
+            full_text[cursor..]
+                .find(before)
+                .map_or(cursor, |offset| cursor + offset + before.len())
+        });
+        let found = full_text[search_from..]
+            .find(previous_text)
+            .map(|offset| search_from + offset)
+            .or_else(|| full_text.find(previous_text));
+        if let Some(start) = found {
+            return format!("{}{}{}", &full_text[..start], new_text, &full_text[start + previous_text.len()..]);
+        }
+    }
+
+    join_segments(&edited)
+}
+
+/// セグメントを本文の形式（話者がいれば「話者: 文」）で1行ずつ連結する
+fn join_segments(segments: &[TranscriptSegment]) -> String {
+    segments
+        .iter()
+        .map(|segment| match &segment.speaker {
+            Some(speaker) => format!("{}: {}", speaker, segment.text),
+            None => segment.text.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,462 @@
+//! 外部の書き起こしファイル取り込み
+//!
+//! Otter エクスポート（JSON / TXT）、Whisper JSON（whisper.cpp の `-oj` 出力と
+//! openai-whisper の JSON）、WebVTT を解析し、内部の書き起こし + セグメントに変換する。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Recording, TranscriptSegment, Transcription};
+use crate::services::speaker_analytics;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TranscriptFormat {
+    Otter,
+    WhisperJson,
+    WebVtt,
+}
+
+/// 解析結果（データベース保存前）
+#[derive(Debug, Clone, Default)]
+pub struct ParsedTranscript {
+    pub language: Option<String>,
+    pub segments: Vec<ParsedSegment>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParsedSegment {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub speaker: Option<String>,
+    pub text: String,
+    pub confidence: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedTranscript {
+    pub format: TranscriptFormat,
+    pub recording: Recording,
+    pub transcription: Transcription,
+    pub segments: Vec<TranscriptSegment>,
+}
+
+/// 書き起こしだけを取り込んだ（音声の無い）録音のファイルパス
+///
+/// 実際のファイルは作らない。取り込んだ元ファイルを録音として登録すると、録音の削除・編集で
+/// ユーザーのファイルが消されたり書き換えられたりするため、録音ごとに一意なアプリ内のパスにする。
+pub fn transcript_only_path(recordings_dir: &Path, recording_id: &str) -> PathBuf {
+    recordings_dir.join("transcript_only").join(recording_id)
+}
+
+/// 書き起こしファイルを取り込み、録音に紐付けて保存する
+///
+/// `recording_id` を省略した場合は、音声の無い録音レコードを新規作成する（元ファイルは参照しない）。
+pub async fn import_transcript_file(
+    database: &Database,
+    recordings_dir: &Path,
+    path: &Path,
+    recording_id: Option<&str>,
+    format: Option<TranscriptFormat>,
+) -> AppResult<ImportedTranscript> {
+    if !path.is_file() {
+        return Err(AppError::FileNotFound {
+            path: path.to_string_lossy().to_string(),
+        });
+    }
+
+    let content = fs::read_to_string(path)?;
+    let format = match format {
+        Some(format) => format,
+        None => detect_format(path, &content).ok_or_else(|| AppError::ValidationError {
+            message: format!("Unrecognized transcript format: {}", path.display()),
+        })?,
+    };
+
+    log::info!("📥 Importing {:?} transcript from {:?}", format, path);
+
+    let parsed = parse_transcript(&content, format)?;
+    if parsed.segments.is_empty() {
+        return Err(AppError::ValidationError {
+            message: "Transcript contains no segments".to_string(),
+        });
+    }
+
+    let recording = match recording_id {
+        Some(id) => database.get_recording(id).await?.ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording with id {} not found", id),
+        })?,
+        None => {
+            let filename = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let duration = parsed.segments.iter().map(|s| s.end_ms).max().unwrap_or(0) / 1000;
+            let mut recording = Recording::new(filename.clone(), String::new())
+                .with_title(filename)
+                .with_duration(duration)
+                .add_tag("imported".to_string());
+            recording.file_path = transcript_only_path(recordings_dir, &recording.id)
+                .to_string_lossy()
+                .to_string();
+            database.create_recording(&recording).await?;
+            recording
+        }
+    };
+
+    let text = parsed
+        .segments
+        .iter()
+        .map(|segment| match &segment.speaker {
+            Some(speaker) => format!("{}: {}", speaker, segment.text),
+            None => segment.text.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let confidences: Vec<f32> = parsed.segments.iter().filter_map(|s| s.confidence).collect();
+    let confidence = if confidences.is_empty() {
+        None
+    } else {
+        Some(confidences.iter().sum::<f32>() / confidences.len() as f32)
+    };
+
+    let transcription = Transcription::new(
+        recording.id.clone(),
+        String::new(),
+        parsed.language.clone().unwrap_or_else(|| "unknown".to_string()),
+    )
+    .with_text(text, confidence);
+
+    let segments: Vec<TranscriptSegment> = parsed
+        .segments
+        .into_iter()
+        .enumerate()
+        .map(|(index, parsed)| {
+            let mut segment = TranscriptSegment::new(
+                transcription.id.clone(),
+                index as i32,
+                parsed.start_ms,
+                parsed.end_ms,
+                parsed.text,
+            );
+            segment.speaker = parsed.speaker;
+            segment.confidence = parsed.confidence;
+            segment
+        })
+        .collect();
+
+    database.create_transcription(&transcription).await?;
+    database.create_transcript_segments(&segments).await?;
+
+    // 話者付きの書き起こしなら発話量を集計しておく
+    if segments.iter().any(|segment| segment.speaker.is_some()) {
+        let stats = speaker_analytics::compute_talk_stats(&recording.id, &segments);
+        database.replace_speaker_stats(&recording.id, &stats).await?;
+    }
+
+    log::info!("✅ Imported transcription {} with {} segments", transcription.id, segments.len());
+
+    Ok(ImportedTranscript {
+        format,
+        recording,
+        transcription,
+        segments,
+    })
+}
+
+/// 拡張子と内容から形式を推定
+pub fn detect_format(path: &Path, content: &str) -> Option<TranscriptFormat> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+
+    if extension == "vtt" || content.trim_start_matches('\u{feff}').starts_with("WEBVTT") {
+        return Some(TranscriptFormat::WebVtt);
+    }
+
+    if let Ok(value) = serde_json::from_str::<Value>(content) {
+        if value.get("transcription").is_some() || value.get("segments").is_some() {
+            return Some(TranscriptFormat::WhisperJson);
+        }
+        if value.get("transcripts").is_some() {
+            return Some(TranscriptFormat::Otter);
+        }
+        return None;
+    }
+
+    if extension == "txt" && content.lines().any(|line| parse_otter_header(line).is_some()) {
+        return Some(TranscriptFormat::Otter);
+    }
+
+    None
+}
+
+pub fn parse_transcript(content: &str, format: TranscriptFormat) -> AppResult<ParsedTranscript> {
+    match format {
+        TranscriptFormat::WebVtt => Ok(parse_webvtt(content)),
+        TranscriptFormat::WhisperJson => parse_whisper_json(content),
+        TranscriptFormat::Otter => {
+            if content.trim_start().starts_with('{') {
+                parse_otter_json(content)
+            } else {
+                Ok(parse_otter_text(content))
+            }
+        }
+    }
+}
+
+fn parse_webvtt(content: &str) -> ParsedTranscript {
+    let mut segments = Vec::new();
+    let mut lines = content.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let Some((start, end)) = line.split_once("-->") else {
+            continue;
+        };
+        // "00:00:01.000 --> 00:00:04.000 align:start" のような設定は無視
+        let end = end.split_whitespace().next().unwrap_or("");
+        let (Some(start_ms), Some(end_ms)) = (parse_timestamp(start.trim()), parse_timestamp(end)) else {
+            continue;
+        };
+
+        let mut cue_lines = Vec::new();
+        while let Some(next) = lines.peek() {
+            if next.trim().is_empty() {
+                break;
+            }
+            cue_lines.push(lines.next().unwrap_or_default().trim());
+        }
+
+        let mut speaker = None;
+        let mut text = cue_lines.join(" ");
+
+        // <v Speaker>text</v> 形式（Teams）
+        if let Some(rest) = text.strip_prefix("<v ") {
+            if let Some((name, body)) = rest.split_once('>') {
+                speaker = Some(name.trim().to_string());
+                text = body.replace("</v>", "");
+            }
+        } else if let Some((name, body)) = text.split_once(": ") {
+            // "Speaker: text" 形式（Zoom）
+            if !name.is_empty() && name.chars().count() <= 40 {
+                speaker = Some(name.trim().to_string());
+                text = body.to_string();
+            }
+        }
+
+        let text = text.trim().to_string();
+        if !text.is_empty() {
+            segments.push(ParsedSegment {
+                start_ms,
+                end_ms,
+                speaker,
+                text,
+                confidence: None,
+            });
+        }
+    }
+
+    ParsedTranscript {
+        language: None,
+        segments,
+    }
+}
+
+fn parse_whisper_json(content: &str) -> AppResult<ParsedTranscript> {
+    let value: Value = serde_json::from_str(content)?;
+    let mut segments = Vec::new();
+
+    // whisper.cpp: {"result": {"language": "ja"}, "transcription": [{"offsets": {"from", "to"}, "text"}]}
+    if let Some(items) = value.get("transcription").and_then(|t| t.as_array()) {
+        for item in items {
+            let offsets = item.get("offsets");
+            let start_ms = offsets.and_then(|o| o.get("from")).and_then(|v| v.as_i64());
+            let end_ms = offsets.and_then(|o| o.get("to")).and_then(|v| v.as_i64());
+            let text = item.get("text").and_then(|t| t.as_str()).unwrap_or("").trim();
+
+            if let (Some(start_ms), Some(end_ms)) = (start_ms, end_ms) {
+                if !text.is_empty() {
+                    segments.push(ParsedSegment {
+                        start_ms,
+                        end_ms,
+                        speaker: None,
+                        text: text.to_string(),
+                        confidence: None,
+                    });
+                }
+            }
+        }
+
+        let language = value
+            .get("result")
+            .and_then(|r| r.get("language"))
+            .and_then(|l| l.as_str())
+            .map(|l| l.to_string());
+
+        return Ok(ParsedTranscript { language, segments });
+    }
+
+    // openai-whisper: {"language": "ja", "segments": [{"start", "end", "text", "avg_logprob"}]}
+    if let Some(items) = value.get("segments").and_then(|s| s.as_array()) {
+        for item in items {
+            let start = item.get("start").and_then(|v| v.as_f64());
+            let end = item.get("end").and_then(|v| v.as_f64());
+            let text = item.get("text").and_then(|t| t.as_str()).unwrap_or("").trim();
+
+            if let (Some(start), Some(end)) = (start, end) {
+                if !text.is_empty() {
+                    segments.push(ParsedSegment {
+                        start_ms: (start * 1000.0).round() as i64,
+                        end_ms: (end * 1000.0).round() as i64,
+                        speaker: None,
+                        text: text.to_string(),
+                        confidence: item
+                            .get("avg_logprob")
+                            .and_then(|v| v.as_f64())
+                            .map(|logprob| logprob.exp().clamp(0.0, 1.0) as f32),
+                    });
+                }
+            }
+        }
+
+        let language = value.get("language").and_then(|l| l.as_str()).map(|l| l.to_string());
+        return Ok(ParsedTranscript { language, segments });
+    }
+
+    Err(AppError::ValidationError {
+        message: "Whisper JSON has neither 'transcription' nor 'segments'".to_string(),
+    })
+}
+
+fn parse_otter_json(content: &str) -> AppResult<ParsedTranscript> {
+    let value: Value = serde_json::from_str(content)?;
+    let items = value
+        .get("transcripts")
+        .and_then(|t| t.as_array())
+        .ok_or_else(|| AppError::ValidationError {
+            message: "Otter JSON has no 'transcripts' array".to_string(),
+        })?;
+
+    let segments = items
+        .iter()
+        .filter_map(|item| {
+            let text = item
+                .get("transcript")
+                .or_else(|| item.get("text"))
+                .and_then(|t| t.as_str())?
+                .trim()
+                .to_string();
+            if text.is_empty() {
+                return None;
+            }
+
+            Some(ParsedSegment {
+                start_ms: item.get("start_offset").and_then(|v| v.as_i64()).unwrap_or(0),
+                end_ms: item.get("end_offset").and_then(|v| v.as_i64()).unwrap_or(0),
+                speaker: item
+                    .get("speaker")
+                    .or_else(|| item.get("speaker_name"))
+                    .and_then(|s| s.as_str())
+                    .map(|s| s.to_string()),
+                text,
+                confidence: None,
+            })
+        })
+        .collect();
+
+    Ok(ParsedTranscript {
+        language: value.get("language").and_then(|l| l.as_str()).map(|l| l.to_string()),
+        segments,
+    })
+}
+
+/// Otter のテキストエクスポート（"Speaker 1  0:03" の見出し行 + 本文）
+fn parse_otter_text(content: &str) -> ParsedTranscript {
+    let mut segments: Vec<ParsedSegment> = Vec::new();
+    let mut current: Option<ParsedSegment> = None;
+
+    for line in content.lines() {
+        if let Some((speaker, start_ms)) = parse_otter_header(line) {
+            if let Some(segment) = current.take() {
+                segments.push(segment);
+            }
+            current = Some(ParsedSegment {
+                start_ms,
+                end_ms: start_ms,
+                speaker: Some(speaker),
+                text: String::new(),
+                confidence: None,
+            });
+        } else if let Some(segment) = current.as_mut() {
+            let line = line.trim();
+            if !line.is_empty() {
+                if !segment.text.is_empty() {
+                    segment.text.push(' ');
+                }
+                segment.text.push_str(line);
+            }
+        }
+    }
+    if let Some(segment) = current.take() {
+        segments.push(segment);
+    }
+
+    // 終了時刻は次の発言の開始時刻で補う
+    for i in 0..segments.len() {
+        let next_start = segments.get(i + 1).map(|s| s.start_ms);
+        if let Some(next_start) = next_start {
+            segments[i].end_ms = next_start;
+        }
+    }
+    segments.retain(|segment| !segment.text.is_empty());
+
+    ParsedTranscript {
+        language: None,
+        segments,
+    }
+}
+
+fn parse_otter_header(line: &str) -> Option<(String, i64)> {
+    let line = line.trim_end();
+    let (speaker, timestamp) = line.rsplit_once(char::is_whitespace)?;
+    let speaker = speaker.trim();
+    if speaker.is_empty() || !timestamp.contains(':') {
+        return None;
+    }
+    let start_ms = parse_timestamp(timestamp)?;
+    Some((speaker.to_string(), start_ms))
+}
+
+/// "HH:MM:SS.mmm" / "MM:SS.mmm" / "H:MM:SS,mmm" / "M:SS" をミリ秒に変換
+fn parse_timestamp(value: &str) -> Option<i64> {
+    let value = value.trim().replace(',', ".");
+    let (clock, millis) = match value.split_once('.') {
+        Some((clock, fraction)) => {
+            let digits: String = fraction.chars().take(3).collect();
+            if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            let millis = digits.parse::<i64>().ok()? * 10_i64.pow(3 - digits.len() as u32);
+            (clock.to_string(), millis)
+        }
+        None => (value, 0),
+    };
+
+    let parts: Vec<i64> = clock
+        .split(':')
+        .map(|part| part.parse::<i64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    let seconds = match parts.as_slice() {
+        [h, m, s] => h * 3600 + m * 60 + s,
+        [m, s] => m * 60 + s,
+        _ => return None,
+    };
+
+    Some(seconds * 1000 + millis)
+}
@@ -0,0 +1,133 @@
+//! 書き起こし開始前の所要時間・メモリの見積もり
+//!
+//! 過去の書き起こし実績（モデル別の処理時間 / 音声の長さ）があればそれを使い、
+//! 無ければモデルごとの目安値で見積もる。`small` と `medium` のどちらで実行するかを
+//! 事前に判断できるようにするためのもの。目安値は CPU での値なので、GPU で書き起こす場合は
+//! 計測済みの（無ければデバイスごとの目安の）速度比で割る。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::TranscriptionThroughput;
+use crate::services::model_selection;
+use crate::services::whisper_acceleration::{self, WhisperDevice};
+use crate::services::WhisperAccelerationSettings;
+use serde::{Deserialize, Serialize};
+
+/// Whisperモデルごとの目安（CPU実行時の 処理秒 / 音声秒、必要メモリMB）
+const MODEL_PROFILES: &[(&str, f64, u64)] = &[
+    ("tiny", 0.3, 1024),
+    ("base", 0.4, 1024),
+    ("small", 0.8, 2048),
+    ("medium", 1.6, 5120),
+    ("large", 3.2, 10240),
+];
+
+/// 目安値が無いモデルに使う値
+const FALLBACK_PROFILE: (f64, u64) = (1.6, 5120);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionEstimate {
+    pub model: String,
+    pub audio_seconds: i64,
+    pub estimated_seconds: i64,
+    /// 処理秒 / 音声秒
+    pub realtime_factor: f64,
+    pub estimated_memory_mb: u64,
+    /// 見積もりに使った過去の書き起こし件数（0 なら目安値）
+    pub history_runs: i64,
+    /// 空きメモリが分かる環境で、必要メモリが足りるか
+    pub fits_in_memory: Option<bool>,
+    /// 書き起こしに使うデバイス
+    #[serde(default = "cpu")]
+    pub device: WhisperDevice,
+}
+
+fn cpu() -> WhisperDevice {
+    WhisperDevice::Cpu
+}
+
+impl TranscriptionEstimate {
+    /// GPU で書き起こす場合の見積もりにする（実績がある場合は実測なのでそのまま）
+    pub fn accelerated(mut self, device: WhisperDevice, speedup: f64) -> Self {
+        self.device = device;
+        if self.history_runs == 0 && speedup > 0.0 {
+            self.realtime_factor /= speedup;
+            self.estimated_seconds = (self.audio_seconds.max(0) as f64 * self.realtime_factor).ceil() as i64;
+        }
+        self
+    }
+}
+
+/// 見積もりの対象にする既定のモデル一覧
+pub fn known_models() -> Vec<String> {
+    MODEL_PROFILES.iter().map(|(name, _, _)| name.to_string()).collect()
+}
+
+/// 1モデル分の見積もり。`history` はそのモデルの実績
+pub fn estimate(
+    model: &str,
+    audio_seconds: i64,
+    history: Option<&TranscriptionThroughput>,
+    available_memory_mb: Option<u64>,
+) -> TranscriptionEstimate {
+    let (default_factor, memory_mb) = MODEL_PROFILES
+        .iter()
+        .find(|(name, _, _)| *name == model)
+        .map(|(_, factor, memory)| (*factor, *memory))
+        .unwrap_or(FALLBACK_PROFILE);
+
+    let measured = history.filter(|history| history.runs > 0 && history.audio_seconds > 0);
+    let realtime_factor = match measured {
+        Some(history) => history.processing_ms as f64 / 1000.0 / history.audio_seconds as f64,
+        None => default_factor,
+    };
+
+    TranscriptionEstimate {
+        model: model.to_string(),
+        audio_seconds,
+        estimated_seconds: (audio_seconds.max(0) as f64 * realtime_factor).ceil() as i64,
+        realtime_factor,
+        estimated_memory_mb: memory_mb,
+        history_runs: measured.map(|history| history.runs).unwrap_or(0),
+        fits_in_memory: available_memory_mb.map(|available| available >= memory_mb),
+        device: WhisperDevice::Cpu,
+    }
+}
+
+/// 録音の長さと過去の実績から、指定したモデルそれぞれの CPU での見積もりを返す
+pub async fn estimate_for_recording(
+    db: &Database,
+    recording_id: &str,
+    models: &[String],
+) -> AppResult<Vec<TranscriptionEstimate>> {
+    estimate_for_recording_on(db, recording_id, models, WhisperDevice::Cpu, &WhisperAccelerationSettings::default())
+        .await
+}
+
+/// `device` で書き起こす場合の見積もりを返す（`acceleration` はモデルごとの計測済みの速度比）
+pub async fn estimate_for_recording_on(
+    db: &Database,
+    recording_id: &str,
+    models: &[String],
+    device: WhisperDevice,
+    acceleration: &WhisperAccelerationSettings,
+) -> AppResult<Vec<TranscriptionEstimate>> {
+    let recording = db.get_recording(recording_id).await?.ok_or_else(|| AppError::ValidationError {
+        message: format!("Recording with id {} not found", recording_id),
+    })?;
+    let audio_seconds = recording.duration.filter(|duration| *duration > 0).ok_or_else(|| AppError::ValidationError {
+        message: format!("Duration of recording {} is unknown", recording_id),
+    })?;
+
+    let throughput = db.get_transcription_throughput().await?;
+    let available_memory_mb = model_selection::available_memory_mb();
+
+    Ok(models
+        .iter()
+        .map(|model| {
+            let history = throughput.iter().find(|entry| &entry.model == model);
+            let speedup = whisper_acceleration::expected_speedup(acceleration, device, model);
+            estimate(model, audio_seconds, history, available_memory_mb).accelerated(device, speedup)
+        })
+        .collect())
+}
@@ -0,0 +1,32 @@
+//! 書き起こし言語の決定
+//!
+//! 言語が指定されなかった場合は録音のカテゴリに設定された既定の言語を使う
+//! （例: 「EN customer calls」は常に英語）。階層カテゴリでは最も近い祖先の設定を引き継ぐ。
+//! どれも無ければ Whisper の自動判定に任せる。
+
+use crate::database::Database;
+use crate::errors::AppResult;
+use crate::models::Recording;
+use crate::services::taxonomy;
+
+/// 明示された言語、カテゴリの既定言語の順に決める
+pub async fn resolve_language(
+    database: &Database,
+    recording: &Recording,
+    requested: Option<String>,
+) -> AppResult<Option<String>> {
+    if let Some(language) = requested.filter(|language| !language.trim().is_empty()) {
+        return Ok(Some(language));
+    }
+
+    let Some(category) = recording.category.as_deref().map(str::trim).filter(|c| !c.is_empty()) else {
+        return Ok(None);
+    };
+    for path in taxonomy::ancestors(&taxonomy::normalize_path(category)).iter().rev() {
+        if let Some(language) = database.get_category_language(path).await? {
+            log::info!("🌐 Using default language {} for category {}", language, path);
+            return Ok(Some(language));
+        }
+    }
+    Ok(None)
+}
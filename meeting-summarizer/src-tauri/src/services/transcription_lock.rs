@@ -0,0 +1,94 @@
+//! 録音ごとの書き起こし実行中レジストリ
+//!
+//! 同じ録音に対する書き起こしが重複して起動されないよう、実行中の録音IDとジョブIDを保持する。
+//! ジョブIDは完了後に保存される書き起こしのIDと同じになる。
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InFlightTranscription {
+    pub job_id: String,
+    pub recording_id: String,
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+pub struct TranscriptionLocks {
+    in_flight: Mutex<HashMap<String, InFlightTranscription>>,
+}
+
+impl TranscriptionLocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 録音のロックを取得する。既に実行中なら実行中のジョブ情報を返す
+    pub fn try_acquire(self: &Arc<Self>, recording_id: &str) -> Result<TranscriptionLockGuard, InFlightTranscription> {
+        let mut in_flight = self.lock();
+
+        if let Some(existing) = in_flight.get(recording_id) {
+            return Err(existing.clone());
+        }
+
+        let entry = InFlightTranscription {
+            job_id: Uuid::new_v4().to_string(),
+            recording_id: recording_id.to_string(),
+            started_at: Utc::now(),
+        };
+        in_flight.insert(recording_id.to_string(), entry.clone());
+
+        Ok(TranscriptionLockGuard {
+            locks: self.clone(),
+            recording_id: entry.recording_id,
+            job_id: entry.job_id,
+        })
+    }
+
+    pub fn get(&self, recording_id: &str) -> Option<InFlightTranscription> {
+        self.lock().get(recording_id).cloned()
+    }
+
+    pub fn list(&self) -> Vec<InFlightTranscription> {
+        let mut entries: Vec<_> = self.lock().values().cloned().collect();
+        entries.sort_by_key(|entry| entry.started_at);
+        entries
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, InFlightTranscription>> {
+        // 保持中にパニックしてもマップ自体は壊れないため、poisonは無視する
+        self.in_flight.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// ドロップ時にロックを解放する
+pub struct TranscriptionLockGuard {
+    locks: Arc<TranscriptionLocks>,
+    recording_id: String,
+    job_id: String,
+}
+
+impl TranscriptionLockGuard {
+    pub fn job_id(&self) -> &str {
+        &self.job_id
+    }
+
+    pub fn recording_id(&self) -> &str {
+        &self.recording_id
+    }
+}
+
+impl Drop for TranscriptionLockGuard {
+    fn drop(&mut self) {
+        let mut in_flight = self.locks.lock();
+        if in_flight
+            .get(&self.recording_id)
+            .is_some_and(|entry| entry.job_id == self.job_id)
+        {
+            in_flight.remove(&self.recording_id);
+        }
+    }
+}
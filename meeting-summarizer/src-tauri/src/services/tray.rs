@@ -0,0 +1,144 @@
+//! システムトレイの録音操作
+//!
+//! トレイのメニューから録音の開始・一時停止・再開・停止を行い、アイコンのタイトル（macOS の
+//! メニューバー）とツールチップで録音中であることを示す。メニューの表示と有効・無効は
+//! `RecordingService` の状態から作る `TrayState` で決める。
+
+use crate::services::i18n;
+use crate::services::recording_progress::RecordingProgress;
+use serde::{Deserialize, Serialize};
+
+pub const TRAY_ID: &str = "main";
+
+/// トレイの状態が変わったときに送るイベント
+pub const TRAY_STATE_EVENT: &str = "tray-state";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayRecordingState {
+    #[default]
+    Idle,
+    Recording,
+    Paused,
+}
+
+/// トレイのメニュー項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayAction {
+    Start,
+    Pause,
+    Resume,
+    Stop,
+    ShowWindow,
+    Quit,
+}
+
+impl TrayAction {
+    /// メニューに並べる順
+    pub const ALL: [TrayAction; 6] = [
+        TrayAction::Start,
+        TrayAction::Pause,
+        TrayAction::Resume,
+        TrayAction::Stop,
+        TrayAction::ShowWindow,
+        TrayAction::Quit,
+    ];
+
+    /// メニュー項目の ID
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Start => "tray-start",
+            Self::Pause => "tray-pause",
+            Self::Resume => "tray-resume",
+            Self::Stop => "tray-stop",
+            Self::ShowWindow => "tray-show",
+            Self::Quit => "tray-quit",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.id() == id)
+    }
+
+    pub fn label(&self) -> String {
+        i18n::t(match self {
+            Self::Start => "tray.start",
+            Self::Pause => "tray.pause",
+            Self::Resume => "tray.resume",
+            Self::Stop => "tray.stop",
+            Self::ShowWindow => "tray.show",
+            Self::Quit => "tray.quit",
+        })
+    }
+
+    /// 録音操作の項目か（ウィンドウ表示・終了以外）
+    pub fn is_recording_control(&self) -> bool {
+        matches!(self, Self::Start | Self::Pause | Self::Resume | Self::Stop)
+    }
+}
+
+/// トレイに表示する録音の状態
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrayState {
+    pub recording: TrayRecordingState,
+    pub session_id: Option<String>,
+    /// 一時停止していた時間を除いた経過時間
+    pub elapsed_ms: u64,
+}
+
+impl TrayState {
+    /// 録音中の進捗から作る（録音中でなければ待機中）
+    pub fn from_progress(progress: Option<&RecordingProgress>) -> Self {
+        match progress {
+            None => Self::default(),
+            Some(progress) => Self {
+                recording: if progress.paused { TrayRecordingState::Paused } else { TrayRecordingState::Recording },
+                session_id: Some(progress.session_id.clone()),
+                elapsed_ms: progress.elapsed_ms,
+            },
+        }
+    }
+
+    /// メニュー項目を押せるか
+    pub fn is_enabled(&self, action: TrayAction) -> bool {
+        match action {
+            TrayAction::Start => self.recording == TrayRecordingState::Idle,
+            TrayAction::Pause => self.recording == TrayRecordingState::Recording,
+            TrayAction::Resume => self.recording == TrayRecordingState::Paused,
+            TrayAction::Stop => self.recording != TrayRecordingState::Idle,
+            TrayAction::ShowWindow | TrayAction::Quit => true,
+        }
+    }
+
+    /// アイコンの横に出す表示（待機中は出さない）
+    pub fn title(&self) -> Option<String> {
+        let elapsed = format_elapsed(self.elapsed_ms);
+        match self.recording {
+            TrayRecordingState::Idle => None,
+            TrayRecordingState::Recording => Some(format!("● {}", elapsed)),
+            TrayRecordingState::Paused => Some(format!("❚❚ {}", elapsed)),
+        }
+    }
+
+    pub fn tooltip(&self) -> String {
+        let elapsed = format_elapsed(self.elapsed_ms);
+        let status = match self.recording {
+            TrayRecordingState::Idle => i18n::t("tray.idle"),
+            TrayRecordingState::Recording => i18n::tr("tray.recording", &[("elapsed", &elapsed)]),
+            TrayRecordingState::Paused => i18n::tr("tray.paused", &[("elapsed", &elapsed)]),
+        };
+        format!("meeting-summarizer - {}", status)
+    }
+}
+
+/// `12:34`（1時間以上は `1:02:03`）
+pub fn format_elapsed(elapsed_ms: u64) -> String {
+    let seconds = elapsed_ms / 1_000;
+    let (hours, minutes, seconds) = (seconds / 3_600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
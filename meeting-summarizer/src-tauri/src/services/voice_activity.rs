@@ -0,0 +1,280 @@
+//! 発話区間検出（エネルギーベースの簡易 VAD）
+//!
+//! 16kHz モノラルに変換した音声を 30ms のフレームに区切り、背景ノイズより十分大きいフレームを
+//! 発話とみなす。検出した区間から無音率・平均の間・発話密度を計算して録音ごとに保存する。
+//!
+//! 書き起こしの前には長い無音を取り除く（`trim_silence`）。Whisper の処理時間が減り、
+//! 無音部分で同じ文を繰り返すような誤認識も起きにくくなる。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::SpeechMetrics;
+use crate::services::audio_convert::{self, WHISPER_SAMPLE_RATE};
+use crate::services::SilenceTrimSettings;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// 判定に使うフレームの長さ
+const FRAME_MS: i64 = 30;
+/// これより短い無音は発話の途中（息継ぎ）として扱う
+const MIN_PAUSE_MS: i64 = 300;
+/// これより短い発話区間はノイズとして捨てる
+const MIN_SPEECH_MS: i64 = 120;
+/// 背景ノイズ（フレーム RMS の下位 10%）に対する倍率
+const NOISE_FLOOR_FACTOR: f32 = 3.0;
+/// ほぼ話し続けている録音では下位 10% も発話になるため、しきい値は発話の音量（上位 10%）のこの割合までに抑える
+const SPEECH_LEVEL_FACTOR: f32 = 0.25;
+/// 静かな録音でも無音を発話と誤判定しないための最低しきい値（約 -46 dBFS）
+const MIN_SPEECH_RMS: f32 = 0.005;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpeechRegion {
+    pub start_ms: i64,
+    pub end_ms: i64,
+}
+
+impl SpeechRegion {
+    pub fn duration_ms(&self) -> i64 {
+        self.end_ms - self.start_ms
+    }
+}
+
+/// モノラルのサンプル列から発話区間を検出する
+pub fn detect_speech(samples: &[f32], sample_rate: u32) -> Vec<SpeechRegion> {
+    let frame_len = (sample_rate as i64 * FRAME_MS / 1000).max(1) as usize;
+    let energies: Vec<f32> = samples
+        .chunks(frame_len)
+        .map(|frame| (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt())
+        .collect();
+    if energies.is_empty() {
+        return Vec::new();
+    }
+
+    let mut sorted = energies.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let noise_floor = sorted[sorted.len() / 10];
+    let speech_level = sorted[sorted.len() * 9 / 10];
+    let threshold = (noise_floor * NOISE_FLOOR_FACTOR)
+        .min(speech_level * SPEECH_LEVEL_FACTOR)
+        .max(MIN_SPEECH_RMS);
+
+    let mut regions: Vec<SpeechRegion> = Vec::new();
+    let total_ms = samples.len() as i64 * 1000 / sample_rate as i64;
+    for (index, energy) in energies.iter().enumerate() {
+        if *energy < threshold {
+            continue;
+        }
+        let start_ms = index as i64 * FRAME_MS;
+        let end_ms = (start_ms + FRAME_MS).min(total_ms);
+        match regions.last_mut() {
+            Some(last) if start_ms - last.end_ms < MIN_PAUSE_MS => last.end_ms = end_ms,
+            _ => regions.push(SpeechRegion { start_ms, end_ms }),
+        }
+    }
+
+    regions.retain(|region| region.duration_ms() >= MIN_SPEECH_MS);
+    regions
+}
+
+/// 無音の除去結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilenceTrimSummary {
+    pub original_ms: i64,
+    pub trimmed_ms: i64,
+    pub speech_ms: i64,
+    /// 残した区間（元の音声での位置）
+    #[serde(default)]
+    pub kept_ranges: Vec<SpeechRegion>,
+}
+
+impl SilenceTrimSummary {
+    pub fn removed_ms(&self) -> i64 {
+        self.original_ms - self.trimmed_ms
+    }
+
+    /// 発話が1つも見つからなかった
+    pub fn is_silent(&self) -> bool {
+        self.speech_ms == 0
+    }
+
+    /// 無音を除いた音声での位置を元の音声での位置に戻す
+    pub fn original_ms(&self, trimmed_ms: i64) -> i64 {
+        let mut offset = 0;
+        for range in &self.kept_ranges {
+            if trimmed_ms < offset + range.duration_ms() {
+                return range.start_ms + (trimmed_ms - offset).max(0);
+            }
+            offset += range.duration_ms();
+        }
+        match self.kept_ranges.last() {
+            Some(last) => last.end_ms + (trimmed_ms - offset),
+            None => trimmed_ms,
+        }
+    }
+}
+
+/// 発話区間の前後に `keep_silence_ms` を残し、`min_silence_ms` 以上の無音を取り除いた区間
+pub fn kept_ranges(regions: &[SpeechRegion], total_ms: i64, settings: &SilenceTrimSettings) -> Vec<SpeechRegion> {
+    let keep = settings.keep_silence_ms as i64;
+    let mut ranges: Vec<SpeechRegion> = Vec::new();
+    for region in regions {
+        let start_ms = (region.start_ms - keep).max(0);
+        let end_ms = (region.end_ms + keep).min(total_ms);
+        match ranges.last_mut() {
+            Some(last) if start_ms - last.end_ms < settings.min_silence_ms as i64 => last.end_ms = end_ms,
+            _ => ranges.push(SpeechRegion { start_ms, end_ms }),
+        }
+    }
+    ranges
+}
+
+/// 発話区間だけをつなげたサンプル列を返す
+pub fn trim_silence(
+    samples: &[f32],
+    sample_rate: u32,
+    regions: &[SpeechRegion],
+    settings: &SilenceTrimSettings,
+) -> (Vec<f32>, SilenceTrimSummary) {
+    let to_index = |ms: i64| ((ms * sample_rate as i64 / 1000) as usize).min(samples.len());
+    let original_ms = samples.len() as i64 * 1000 / sample_rate as i64;
+
+    let kept_ranges = kept_ranges(regions, original_ms, settings);
+    let mut trimmed = Vec::new();
+    for range in &kept_ranges {
+        trimmed.extend_from_slice(&samples[to_index(range.start_ms)..to_index(range.end_ms)]);
+    }
+    let summary = SilenceTrimSummary {
+        original_ms,
+        trimmed_ms: trimmed.len() as i64 * 1000 / sample_rate as i64,
+        speech_ms: regions.iter().map(SpeechRegion::duration_ms).sum(),
+        kept_ranges,
+    };
+    (trimmed, summary)
+}
+
+/// 16kHz モノラル WAV の長い無音を取り除いて `output` に書き出す
+///
+/// 取り除く無音が無ければ書き出さずに None を返す。
+pub fn trim_silence_in_file(
+    input: &Path,
+    output: &Path,
+    settings: &SilenceTrimSettings,
+) -> AppResult<Option<SilenceTrimSummary>> {
+    let samples = read_samples(input)?;
+    let regions = detect_speech(&samples, WHISPER_SAMPLE_RATE);
+    let (trimmed, summary) = trim_silence(&samples, WHISPER_SAMPLE_RATE, &regions, settings);
+    if summary.removed_ms() < settings.min_silence_ms as i64 && !summary.is_silent() {
+        return Ok(None);
+    }
+
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: WHISPER_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let write_error = |e: hound::Error| AppError::AudioConversion {
+        message: format!("Failed to write {:?}: {}", output, e),
+    };
+    let mut writer = hound::WavWriter::create(output, spec).map_err(write_error)?;
+    for sample in trimmed {
+        writer
+            .write_sample((sample * i16::MAX as f32) as i16)
+            .map_err(write_error)?;
+    }
+    writer.finalize().map_err(write_error)?;
+    Ok(Some(summary))
+}
+
+/// 発話区間から録音の指標を計算する
+pub fn compute_metrics(recording_id: &str, regions: &[SpeechRegion], duration_ms: i64) -> SpeechMetrics {
+    let speech_ms: i64 = regions.iter().map(SpeechRegion::duration_ms).sum();
+    let pauses: Vec<i64> = regions
+        .windows(2)
+        .map(|pair| pair[1].start_ms - pair[0].end_ms)
+        .collect();
+    let average_pause_ms = if pauses.is_empty() {
+        0
+    } else {
+        pauses.iter().sum::<i64>() / pauses.len() as i64
+    };
+
+    let (silence_ratio, speech_density) = if duration_ms > 0 {
+        (
+            (1.0 - speech_ms as f64 / duration_ms as f64).clamp(0.0, 1.0),
+            regions.len() as f64 / (duration_ms as f64 / 60_000.0),
+        )
+    } else {
+        (0.0, 0.0)
+    };
+
+    SpeechMetrics {
+        recording_id: recording_id.to_string(),
+        duration_ms,
+        speech_ms,
+        silence_ratio,
+        average_pause_ms,
+        pause_count: pauses.len() as i64,
+        speech_density,
+        analyzed_at: Utc::now(),
+    }
+}
+
+/// 音声ファイルを 16kHz モノラルに変換して発話区間と長さ（ミリ秒）を返す
+pub fn detect_speech_in_file(path: &Path, work_dir: &Path) -> AppResult<(Vec<SpeechRegion>, i64)> {
+    let name = format!("vad-{}", Uuid::new_v4());
+    let input = audio_convert::prepare_for_whisper(path, work_dir, &name)?;
+    let samples = read_samples(&input);
+    if input != path {
+        let _ = std::fs::remove_file(&input);
+    }
+    let samples = samples?;
+
+    let duration_ms = samples.len() as i64 * 1000 / WHISPER_SAMPLE_RATE as i64;
+    Ok((detect_speech(&samples, WHISPER_SAMPLE_RATE), duration_ms))
+}
+
+/// 16kHz モノラル 16bit WAV のサンプル列を読み込む
+pub fn read_samples(path: &Path) -> AppResult<Vec<f32>> {
+    let mut reader = hound::WavReader::open(path).map_err(|e| AppError::AudioConversion {
+        message: format!("Failed to read {:?}: {}", path, e),
+    })?;
+    reader
+        .samples::<i16>()
+        .map(|sample| sample.map(|s| s as f32 / i16::MAX as f32))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::AudioConversion {
+            message: format!("Failed to read {:?}: {}", path, e),
+        })
+}
+
+/// 録音の発話区間を解析して指標を保存する（変換した一時ファイルは解析後に削除する）
+pub async fn analyze_recording(database: &Database, recording_id: &str) -> AppResult<SpeechMetrics> {
+    let recording = database
+        .get_recording(recording_id)
+        .await?
+        .ok_or_else(|| AppError::ValidationError {
+            message: format!("Recording with id {} not found", recording_id),
+        })?;
+
+    let path = PathBuf::from(&recording.file_path);
+    let work_dir = std::env::temp_dir().join("meeting-summarizer-vad");
+    let (regions, duration_ms) = tokio::task::spawn_blocking(move || detect_speech_in_file(&path, &work_dir))
+        .await
+        .map_err(|e| AppError::AudioConversion {
+            message: format!("Speech detection task failed: {}", e),
+        })??;
+
+    let metrics = compute_metrics(recording_id, &regions, duration_ms);
+    database.save_speech_metrics(&metrics).await?;
+
+    log::info!(
+        "🔇 Recording {}: {:.0}% silence, average pause {} ms",
+        recording_id,
+        metrics.silence_ratio * 100.0,
+        metrics.average_pause_ms
+    );
+    Ok(metrics)
+}
@@ -0,0 +1,190 @@
+//! ボイスメモ
+//!
+//! 会議の録音とは別の、思いついたことを手早く残すための経路。録音を始め、話し終えて無音が
+//! 続くか停止されたら止め、速いモデルですぐに書き起こす。録音はメモ用のカテゴリに入れ、
+//! 設定があれば日付ごとの Markdown にも時刻付きで追記する。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Recording, Transcription};
+use crate::services::{corrections, RecordingService, VoiceMemoSettings, WhisperService};
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+
+/// 入力の音量を確認する間隔（ミリ秒）
+pub const LEVEL_POLL_INTERVAL_MS: u64 = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceMemo {
+    pub recording: Recording,
+    pub transcription: Transcription,
+    /// 追記した日付ごとの Markdown
+    pub daily_note_path: Option<String>,
+}
+
+/// 話し終えた後の無音を検出する（話し始める前の無音では止めない）
+#[derive(Debug)]
+pub struct SilenceDetector {
+    threshold: f32,
+    timeout: Duration,
+    heard_speech: bool,
+    silent_since: Option<Duration>,
+}
+
+impl SilenceDetector {
+    pub fn new(threshold: f32, timeout: Duration) -> Self {
+        Self {
+            threshold,
+            timeout,
+            heard_speech: false,
+            silent_since: None,
+        }
+    }
+
+    /// 録音開始からの経過時間と音量を渡し、止めるべきなら true を返す
+    pub fn observe(&mut self, level: f32, elapsed: Duration) -> bool {
+        if level >= self.threshold {
+            self.heard_speech = true;
+            self.silent_since = None;
+            return false;
+        }
+        if !self.heard_speech {
+            return false;
+        }
+        let silent_since = *self.silent_since.get_or_insert(elapsed);
+        elapsed.saturating_sub(silent_since) >= self.timeout
+    }
+}
+
+/// 日付ごとの Markdown のパス
+pub fn daily_note_path(directory: &Path, date: NaiveDate) -> PathBuf {
+    directory.join(format!("{}.md", date.format("%Y-%m-%d")))
+}
+
+/// 日付ごとの Markdown にメモを追記する（新しいファイルには日付の見出しを付ける）
+pub async fn append_to_daily_note(directory: &Path, at: DateTime<Local>, text: &str) -> AppResult<PathBuf> {
+    tokio::fs::create_dir_all(directory).await?;
+    let path = daily_note_path(directory, at.date_naive());
+
+    let mut entry = String::new();
+    if !path.exists() {
+        entry.push_str(&format!("# {}\n\n", at.format("%Y-%m-%d")));
+    }
+    entry.push_str(&format!("- {} {}\n", at.format("%H:%M"), text.split_whitespace().collect::<Vec<_>>().join(" ")));
+
+    let mut file = tokio::fs::OpenOptions::new().create(true).append(true).open(&path).await?;
+    file.write_all(entry.as_bytes()).await?;
+    file.flush().await?;
+    Ok(path)
+}
+
+/// ボイスメモの録音状態（同時に1件だけ）
+#[derive(Debug, Default)]
+pub struct VoiceMemoRecorder {
+    active: AtomicBool,
+    stop_requested: AtomicBool,
+}
+
+struct ActiveGuard<'a>(&'a AtomicBool);
+
+impl Drop for ActiveGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+impl VoiceMemoRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// 録音中のメモを止める（録音中でなければ false）
+    pub fn request_stop(&self) -> bool {
+        if !self.is_active() {
+            return false;
+        }
+        self.stop_requested.store(true, Ordering::SeqCst);
+        true
+    }
+
+    /// 無音か停止まで録音し、書き起こして保存する
+    pub async fn capture(
+        &self,
+        database: &Database,
+        recording_service: &RecordingService,
+        whisper_service: &WhisperService,
+        settings: &VoiceMemoSettings,
+    ) -> AppResult<VoiceMemo> {
+        settings.validate()?;
+        if self.active.swap(true, Ordering::SeqCst) {
+            return Err(AppError::InvalidOperation {
+                message: "Voice memo capture is already in progress".to_string(),
+            });
+        }
+        let _guard = ActiveGuard(&self.active);
+        self.stop_requested.store(false, Ordering::SeqCst);
+
+        recording_service.start_recording().await?;
+        log::info!("📝 Voice memo recording started");
+
+        let started = Instant::now();
+        let max_duration = Duration::from_secs(settings.max_duration_secs);
+        let mut detector = SilenceDetector::new(settings.silence_threshold, Duration::from_secs(settings.silence_timeout_secs));
+        let mut interval = tokio::time::interval(Duration::from_millis(LEVEL_POLL_INTERVAL_MS));
+        loop {
+            interval.tick().await;
+            let elapsed = started.elapsed();
+            if self.stop_requested.load(Ordering::SeqCst)
+                || elapsed >= max_duration
+                || detector.observe(recording_service.input_level().await, elapsed)
+            {
+                break;
+            }
+        }
+
+        let mut recording = recording_service.stop_recording().await?;
+        let captured_at = Local::now();
+        recording.title = Some(format!("ボイスメモ {}", captured_at.format("%Y-%m-%d %H:%M")));
+        if !settings.category.trim().is_empty() {
+            recording.category = Some(settings.category.trim().to_string());
+        }
+        database.update_recording(&recording).await?;
+
+        if !whisper_service.is_initialized().await {
+            whisper_service.initialize().await?;
+        }
+        let mut transcription = whisper_service
+            .transcribe_audio_file_with_model(
+                Path::new(&recording.file_path),
+                recording.id.clone(),
+                settings.language.clone(),
+                Some(&settings.model_size),
+            )
+            .await?;
+        corrections::apply_corrections(database, &mut transcription).await?;
+        database.create_transcription(&transcription).await?;
+
+        let daily_note_path = match settings.daily_notes_directory.as_deref().map(str::trim).filter(|dir| !dir.is_empty()) {
+            Some(directory) => {
+                let path = append_to_daily_note(Path::new(directory), captured_at, &transcription.text).await?;
+                Some(path.to_string_lossy().to_string())
+            }
+            None => None,
+        };
+
+        log::info!("📝 Voice memo saved: {} ({} chars)", recording.id, transcription.text.chars().count());
+        Ok(VoiceMemo {
+            recording,
+            transcription,
+            daily_note_path,
+        })
+    }
+}
@@ -0,0 +1,196 @@
+//! 監視フォルダからの自動取り込み
+//!
+//! 設定したフォルダ（Zoom のローカル録画の保存先など）を定期的に走査し、新しい音声ファイルを
+//! 録音ディレクトリにコピーして登録する。フォルダごとのルールに従って書き起こし・要約のジョブも投入する。
+//! 書き込み途中のファイルを拾わないよう、一定時間更新されていないファイルだけを対象にする。
+
+use crate::database::Database;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Job, JobPayload, Recording};
+use crate::services::app_settings::WatchedFolderRule;
+use crate::services::{audio_probe, channel_separation};
+use crate::services::JobQueue;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+pub const WATCHED_IMPORT_EVENT: &str = "watched-folder-import";
+
+/// 走査の間隔
+pub const WATCH_INTERVAL_SECS: u64 = 30;
+
+/// 最終更新からこの時間が経ったファイルを書き込み完了とみなす
+pub const SETTLE_SECS: u64 = 10;
+
+pub const AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "aac", "flac", "ogg"];
+
+/// 自動取り込みした録音
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchedImport {
+    pub folder_id: String,
+    pub source_path: String,
+    pub recording: Recording,
+    /// 投入した書き起こし（・要約）ジョブ
+    pub job_id: Option<String>,
+}
+
+pub fn validate_rule(rule: &WatchedFolderRule) -> AppResult<()> {
+    if !Path::new(&rule.path).is_dir() {
+        return Err(AppError::FileNotFound { path: rule.path.clone() });
+    }
+    if rule.summarize && !rule.transcribe {
+        return Err(AppError::ValidationError {
+            message: "Summarizing requires transcription to be enabled".to_string(),
+        });
+    }
+    channel_separation::validate_speakers(&rule.channel_speakers)
+}
+
+fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// フォルダ直下の書き込みが終わった音声ファイル（更新日時の古い順）
+pub fn settled_audio_files(folder: &Path, now: SystemTime) -> AppResult<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(folder)? {
+        let entry = entry?;
+        let path = entry.path();
+        if !entry.file_type()?.is_file() || !is_audio_file(&path) {
+            continue;
+        }
+        let modified = entry.metadata()?.modified()?;
+        let settled = now
+            .duration_since(modified)
+            .map(|age| age >= Duration::from_secs(SETTLE_SECS))
+            .unwrap_or(false);
+        if settled {
+            files.push((modified, path));
+        }
+    }
+    files.sort();
+    Ok(files.into_iter().map(|(_, path)| path).collect())
+}
+
+/// ファイルを録音ディレクトリにコピーし、ルールのカテゴリ・タグを付けて登録する
+pub async fn import_file(
+    database: &Database,
+    rule: &WatchedFolderRule,
+    source: &Path,
+    recordings_dir: &Path,
+) -> AppResult<Recording> {
+    fs::create_dir_all(recordings_dir)?;
+    let extension = source
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("wav")
+        .to_lowercase();
+
+    let mut recording = Recording::new(String::new(), String::new());
+    let filename = format!("watched_{}.{}", recording.id, extension);
+    let audio_path = recordings_dir.join(&filename);
+    fs::copy(source, &audio_path)?;
+
+    recording.filename = filename;
+    recording.file_path = audio_path.to_string_lossy().to_string();
+    recording.file_size = Some(fs::metadata(&audio_path)?.len() as i64);
+    recording.title = source.file_stem().map(|stem| stem.to_string_lossy().to_string());
+    audio_probe::fill_recording(&mut recording, std::slice::from_ref(&audio_path));
+    recording.category = rule.category.clone();
+    recording.tags = rule.tags.clone();
+    if let Ok(modified) = fs::metadata(source).and_then(|metadata| metadata.modified()) {
+        recording.created_at = DateTime::<Utc>::from(modified);
+    }
+
+    database.create_recording(&recording).await?;
+    database
+        .record_watched_import(&source.to_string_lossy(), &rule.id, &recording.id)
+        .await?;
+    Ok(recording)
+}
+
+/// ルールに従って投入するジョブ
+pub fn job_for_rule(rule: &WatchedFolderRule, recording_id: &str) -> Option<Job> {
+    if !rule.transcribe {
+        return None;
+    }
+    let payload = if rule.separate_channels {
+        JobPayload::ChannelTranscription {
+            recording_id: recording_id.to_string(),
+            language: rule.language.clone(),
+            speakers: rule.channel_speakers.clone(),
+            summarize: rule.summarize,
+            model_config: rule.model_config.clone(),
+        }
+    } else if rule.summarize {
+        JobPayload::TranscribeAndSummarize {
+            recording_id: recording_id.to_string(),
+            language: rule.language.clone(),
+            model_config: rule.model_config.clone(),
+        }
+    } else {
+        JobPayload::Transcription {
+            recording_id: recording_id.to_string(),
+            language: rule.language.clone(),
+        }
+    };
+    Some(Job::new(payload))
+}
+
+/// 1つの監視フォルダを走査して、まだ取り込んでいないファイルを取り込む
+pub async fn scan_folder(
+    database: &Database,
+    job_queue: &JobQueue,
+    rule: &WatchedFolderRule,
+    recordings_dir: &Path,
+) -> AppResult<Vec<WatchedImport>> {
+    let folder = PathBuf::from(&rule.path);
+    if !rule.enabled || !folder.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut imports = Vec::new();
+    for source in settled_audio_files(&folder, SystemTime::now())? {
+        let source_path = source.to_string_lossy().to_string();
+        if database.is_watched_file_imported(&source_path).await? {
+            continue;
+        }
+
+        let recording = import_file(database, rule, &source, recordings_dir).await?;
+        let job_id = match job_for_rule(rule, &recording.id) {
+            Some(job) => Some(job_queue.enqueue(job).await?.id),
+            None => None,
+        };
+        log::info!("👀 Imported {} from watched folder {}", source_path, rule.path);
+
+        imports.push(WatchedImport {
+            folder_id: rule.id.clone(),
+            source_path,
+            recording,
+            job_id,
+        });
+    }
+    Ok(imports)
+}
+
+/// 有効なすべての監視フォルダを走査する（失敗したフォルダはログに残して続行）
+pub async fn scan_all(
+    database: &Database,
+    job_queue: &JobQueue,
+    rules: &[WatchedFolderRule],
+    recordings_dir: &Path,
+) -> Vec<WatchedImport> {
+    let mut imports = Vec::new();
+    for rule in rules.iter().filter(|rule| rule.enabled) {
+        match scan_folder(database, job_queue, rule, recordings_dir).await {
+            Ok(mut folder_imports) => imports.append(&mut folder_imports),
+            Err(e) => log::warn!("⚠️ Failed to scan watched folder {}: {}", rule.path, e),
+        }
+    }
+    imports
+}
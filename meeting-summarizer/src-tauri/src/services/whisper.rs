@@ -3,6 +3,7 @@ use crate::models::{Transcription, TranscriptionStatus};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
+use crate::services::audio_stream;
 use reqwest::multipart;
 use std::fs;
 
@@ -156,16 +157,8 @@ impl WhisperService {
             message: "OpenAI API key is required".to_string(),
         })?;
 
-        // ファイルを読み込み
-        let file_content = fs::read(audio_path)?;
-        let filename = audio_path.file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("audio.wav");
-
-        // マルチパートフォームを作成
-        let file_part = multipart::Part::bytes(file_content)
-            .file_name(filename.to_string())
-            .mime_str("audio/wav")?;
+        // ファイルをストリーミングで送信（全体をメモリに読み込まない）
+        let file_part = audio_stream::streaming_file_part(audio_path).await?;
 
         let mut form = multipart::Form::new()
             .part("file", file_part)
@@ -214,14 +207,7 @@ impl WhisperService {
         language: Option<&str>,
     ) -> AppResult<String> {
         // ローカルWhisperサーバー（whisper.cpp server等）との連携
-        let file_content = fs::read(audio_path)?;
-        let filename = audio_path.file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("audio.wav");
-
-        let file_part = multipart::Part::bytes(file_content)
-            .file_name(filename.to_string())
-            .mime_str("audio/wav")?;
+        let file_part = audio_stream::streaming_file_part(audio_path).await?;
 
         let mut form = multipart::Form::new()
             .part("file", file_part);
@@ -0,0 +1,194 @@
+//! GPU での Whisper の高速化
+//!
+//! Python の whisper では PyTorch のデバイス（Apple Silicon は MPS、NVIDIA の GPU は CUDA）を、
+//! whisper.cpp では GPU 対応のビルドをそのまま使う。medium / large は CPU だけでは
+//! 実用的な速さにならないので、GPU があれば既定で有効にし、同じ音声を CPU と GPU の
+//! 両方で書き起こして測った速度比をモデルごとに保存する。速度比は所要時間の見積もりにも使う。
+
+use crate::services::whisper_cpp::WhisperBackend;
+use crate::services::WhisperAccelerationSettings;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WhisperDevice {
+    Cpu,
+    /// PyTorch の MPS（Metal Performance Shaders）
+    Metal,
+    /// NVIDIA の GPU
+    Cuda,
+}
+
+impl WhisperDevice {
+    /// PyTorch のデバイス名
+    pub fn torch_name(&self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Metal => "mps",
+            Self::Cuda => "cuda",
+        }
+    }
+
+    /// 計測結果が無いときに見込む CPU に対する速度比
+    pub fn default_speedup(&self) -> f64 {
+        match self {
+            Self::Cpu => 1.0,
+            Self::Metal => 3.0,
+            Self::Cuda => 6.0,
+        }
+    }
+}
+
+/// Metal を使える環境か（Apple Silicon の macOS）
+pub fn is_apple_silicon() -> bool {
+    cfg!(all(target_os = "macos", target_arch = "aarch64"))
+}
+
+/// 見つかった GPU
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DetectedGpu {
+    pub device: WhisperDevice,
+    pub name: String,
+}
+
+/// `nvidia-smi --query-gpu=name --format=csv,noheader` の出力から最初の GPU の名前を取り出す
+pub fn parse_nvidia_smi(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+fn detect_cuda() -> Option<String> {
+    let output = Command::new("nvidia-smi")
+        .args(["--query-gpu=name", "--format=csv,noheader"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_nvidia_smi(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// 書き起こしに使える GPU（初回だけ調べて覚えておく）
+pub fn detected_gpu() -> Option<DetectedGpu> {
+    static GPU: OnceLock<Option<DetectedGpu>> = OnceLock::new();
+    GPU.get_or_init(|| {
+        let gpu = if is_apple_silicon() {
+            Some(DetectedGpu {
+                device: WhisperDevice::Metal,
+                name: "Apple Silicon GPU".to_string(),
+            })
+        } else if cfg!(target_os = "macos") {
+            None
+        } else {
+            detect_cuda().map(|name| DetectedGpu {
+                device: WhisperDevice::Cuda,
+                name,
+            })
+        };
+        match &gpu {
+            Some(gpu) => log::info!("⚡ GPU を検出: {} ({:?})", gpu.name, gpu.device),
+            None => log::info!("⚡ GPU が見つからないため Whisper は CPU で実行します"),
+        }
+        gpu
+    })
+    .clone()
+}
+
+/// この環境で使える GPU のデバイス
+pub fn gpu_device() -> Option<WhisperDevice> {
+    detected_gpu().map(|gpu| gpu.device)
+}
+
+/// `device` で書き起こしたときに見込む CPU に対する速度比（計測済みならその値）
+pub fn expected_speedup(settings: &WhisperAccelerationSettings, device: WhisperDevice, model: &str) -> f64 {
+    match device {
+        WhisperDevice::Cpu => 1.0,
+        _ => settings.speedup_for(model).unwrap_or_else(|| device.default_speedup()),
+    }
+}
+
+/// 同じ音声を CPU と Metal で書き起こした計測結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccelerationBenchmark {
+    pub model: String,
+    pub audio_seconds: f64,
+    pub cpu_ms: u64,
+    pub accelerated_ms: u64,
+    pub measured_at: DateTime<Utc>,
+}
+
+impl AccelerationBenchmark {
+    /// CPU に対する速度比（2.0 なら2倍速い）
+    pub fn speedup(&self) -> f64 {
+        self.cpu_ms as f64 / self.accelerated_ms.max(1) as f64
+    }
+}
+
+/// 画面表示用の高速化の状態
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccelerationInfo {
+    /// この環境で GPU を使えるか
+    pub available: bool,
+    pub enabled: bool,
+    /// 書き起こしに使うデバイス
+    pub device: WhisperDevice,
+    pub current_model: String,
+    /// 現在のモデルの計測済みの速度比
+    pub current_speedup: Option<f64>,
+    pub benchmarks: Vec<AccelerationBenchmark>,
+}
+
+/// 設定とサービスの状態から表示用の情報を作る
+pub fn acceleration_info(
+    settings: &WhisperAccelerationSettings,
+    device: WhisperDevice,
+    current_model: &str,
+) -> AccelerationInfo {
+    AccelerationInfo {
+        available: gpu_device().is_some(),
+        enabled: settings.enabled,
+        device,
+        current_model: current_model.to_string(),
+        current_speedup: settings.speedup_for(current_model),
+        benchmarks: settings.benchmarks.clone(),
+    }
+}
+
+/// 書き起こしが GPU で動くかどうかの状態
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccelerationStatus {
+    /// 見つかった GPU（無ければ None）
+    pub gpu: Option<DetectedGpu>,
+    pub enabled: bool,
+    /// 書き起こしに使うデバイス
+    pub device: WhisperDevice,
+    pub backend: WhisperBackend,
+    pub current_model: String,
+    /// 所要時間の見積もりに使う CPU に対する速度比
+    pub expected_speedup: f64,
+    /// `expected_speedup` が計測した値か（false なら目安値）
+    pub measured: bool,
+}
+
+pub fn acceleration_status(
+    settings: &WhisperAccelerationSettings,
+    device: WhisperDevice,
+    backend: WhisperBackend,
+    current_model: &str,
+) -> AccelerationStatus {
+    AccelerationStatus {
+        gpu: detected_gpu(),
+        enabled: settings.enabled,
+        device,
+        backend,
+        current_model: current_model.to_string(),
+        expected_speedup: expected_speedup(settings, device, current_model),
+        measured: device != WhisperDevice::Cpu && settings.speedup_for(current_model).is_some(),
+    }
+}
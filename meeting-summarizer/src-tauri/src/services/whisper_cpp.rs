@@ -0,0 +1,261 @@
+//! whisper.cpp による書き起こし
+//!
+//! Python と pip のパッケージを入れなくても書き起こせるよう、whisper.cpp を whisper-rs で
+//! アプリに組み込み、GGML 形式のモデルで書き起こす。モデルはアプリのデータフォルダの
+//! `models/` に置き、無ければ初期化時にダウンロードする。読み込んだモデルは、モデルと
+//! デバイスが変わらない限り次の書き起こしでも使い回す。
+//!
+//! Metal / CUDA 対応でビルドした場合は GPU を使い、高速化を無効にした場合は CPU で実行する。
+//! 区間ごとの開始・終了時刻と、トークンの確率の平均を信頼度として返す。
+
+use crate::errors::{AppError, AppResult};
+use crate::services::voice_activity;
+use crate::services::whisper_acceleration::WhisperDevice;
+use crate::services::whisper_download_progress::WhisperDownloadProgress;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::broadcast;
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+const MODEL_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WhisperBackend {
+    /// Python の openai-whisper
+    #[default]
+    Python,
+    /// whisper.cpp と GGML モデル
+    WhisperCpp,
+}
+
+/// タイムスタンプ付きの区間
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WhisperSegment {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+    /// 0.0〜1.0（求められなかった場合は None）
+    pub confidence: Option<f32>,
+}
+
+/// 書き起こしの結果（本文と区間）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WhisperOutput {
+    pub text: String,
+    pub segments: Vec<WhisperSegment>,
+}
+
+impl WhisperOutput {
+    /// 区間をつなげて本文にする
+    pub fn from_segments(segments: Vec<WhisperSegment>) -> Self {
+        let text = segments.iter().map(|segment| segment.text.as_str()).collect::<Vec<_>>().join("\n");
+        Self { text, segments }
+    }
+}
+
+/// モデル名に対応する GGML ファイル名
+pub fn ggml_model_file_name(model_size: &str) -> String {
+    match model_size {
+        "large" => "ggml-large-v3.bin".to_string(),
+        other => format!("ggml-{}.bin", other),
+    }
+}
+
+pub fn ggml_model_url(model_size: &str) -> String {
+    format!("{}/{}", MODEL_BASE_URL, ggml_model_file_name(model_size))
+}
+
+/// whisper.cpp の区間を変換する（時刻は 10ms 単位。本文が空の区間は捨てる）
+pub fn build_segment(t0: i64, t1: i64, text: &str, token_probs: &[f32]) -> Option<WhisperSegment> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let confidence = (!token_probs.is_empty())
+        .then(|| (token_probs.iter().sum::<f32>() / token_probs.len() as f32).clamp(0.0, 1.0));
+    Some(WhisperSegment {
+        start_ms: t0 * 10,
+        end_ms: t1 * 10,
+        text: text.to_string(),
+        confidence,
+    })
+}
+
+fn whisper_error(context: &str, error: impl std::fmt::Display) -> AppError {
+    AppError::TranscriptionFailed {
+        message: format!("{}: {}", context, error),
+    }
+}
+
+/// 読み込んだモデルを保持して書き起こす
+#[derive(Default)]
+pub struct WhisperCppEngine {
+    loaded: Mutex<Option<(PathBuf, bool, Arc<WhisperContext>)>>,
+}
+
+impl WhisperCppEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// モデルを読み込む（同じモデルとデバイスなら読み込み済みのものを返す）
+    fn context(&self, model_path: &Path, use_gpu: bool) -> AppResult<Arc<WhisperContext>> {
+        let mut loaded = self.loaded.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((path, gpu, context)) = loaded.as_ref() {
+            if path == model_path && *gpu == use_gpu {
+                return Ok(context.clone());
+            }
+        }
+
+        log::info!("📦 whisper.cpp のモデルを読み込み中: {}", model_path.display());
+        let mut params = WhisperContextParameters::default();
+        params.use_gpu(use_gpu);
+        let context = WhisperContext::new_with_params(&model_path.to_string_lossy(), params)
+            .map(Arc::new)
+            .map_err(|e| whisper_error("Failed to load GGML model", e))?;
+        *loaded = Some((model_path.to_path_buf(), use_gpu, context.clone()));
+        Ok(context)
+    }
+
+    /// 16kHz モノラルのサンプル列を書き起こす（モデルの読み込みと推論で時間がかかるため別スレッドで呼ぶ）
+    pub fn transcribe_samples(
+        &self,
+        model_path: &Path,
+        samples: &[f32],
+        language: &str,
+        device: WhisperDevice,
+    ) -> AppResult<WhisperOutput> {
+        if samples.is_empty() {
+            return Ok(WhisperOutput::default());
+        }
+        let context = self.context(model_path, device != WhisperDevice::Cpu)?;
+        let mut state = context
+            .create_state()
+            .map_err(|e| whisper_error("Failed to create whisper.cpp state", e))?;
+
+        let mut params = FullParams::new(SamplingStrategy::BeamSearch {
+            beam_size: 5,
+            patience: -1.0,
+        });
+        params.set_language(Some(language));
+        params.set_n_threads(std::thread::available_parallelism().map_or(4, |n| n.get()) as i32);
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+
+        state
+            .full(params, samples)
+            .map_err(|e| whisper_error("whisper.cpp transcription failed", e))?;
+
+        let eot = context.token_eot();
+        let segment_count = state
+            .full_n_segments()
+            .map_err(|e| whisper_error("Failed to read whisper.cpp segments", e))?;
+        let mut segments = Vec::new();
+        for index in 0..segment_count {
+            let read = |e| whisper_error("Failed to read whisper.cpp segment", e);
+            let text = state.full_get_segment_text_lossy(index).map_err(read)?;
+            let t0 = state.full_get_segment_t0(index).map_err(read)?;
+            let t1 = state.full_get_segment_t1(index).map_err(read)?;
+            // 特殊トークン（タイムスタンプなど）は信頼度に含めない
+            let mut token_probs = Vec::new();
+            for token in 0..state.full_n_tokens(index).map_err(read)? {
+                if state.full_get_token_id(index, token).map_err(read)? < eot {
+                    token_probs.push(state.full_get_token_prob(index, token).map_err(read)?);
+                }
+            }
+            segments.extend(build_segment(t0, t1, &text, &token_probs));
+        }
+        Ok(WhisperOutput::from_segments(segments))
+    }
+
+    /// 16kHz モノラルの音声をそのまま書き起こす（録音中の音声など、ファイルに書き出さない場合）
+    pub async fn transcribe_pcm(
+        self: &Arc<Self>,
+        model_path: &Path,
+        samples: Vec<f32>,
+        language: &str,
+        device: WhisperDevice,
+    ) -> AppResult<WhisperOutput> {
+        let engine = self.clone();
+        let model_path = model_path.to_path_buf();
+        let language = language.to_string();
+        tokio::task::spawn_blocking(move || engine.transcribe_samples(&model_path, &samples, &language, device))
+            .await
+            .map_err(|e| whisper_error("whisper.cpp task failed", e))?
+    }
+
+    /// whisper.cpp で書き起こす（入力は 16kHz モノラル WAV）
+    pub async fn transcribe(
+        self: &Arc<Self>,
+        model_path: &Path,
+        audio_path: &Path,
+        language: &str,
+        device: WhisperDevice,
+    ) -> AppResult<WhisperOutput> {
+        let engine = self.clone();
+        let model_path = model_path.to_path_buf();
+        let audio_path = audio_path.to_path_buf();
+        let language = language.to_string();
+        tokio::task::spawn_blocking(move || {
+            let samples = voice_activity::read_samples(&audio_path)?;
+            engine.transcribe_samples(&model_path, &samples, &language, device)
+        })
+        .await
+        .map_err(|e| whisper_error("whisper.cpp task failed", e))?
+    }
+}
+
+/// GGML モデルをダウンロードする（一時ファイルに書いてから置き換える）
+pub async fn download_model(
+    models_dir: &Path,
+    model_size: &str,
+    progress: &broadcast::Sender<WhisperDownloadProgress>,
+) -> AppResult<PathBuf> {
+    tokio::fs::create_dir_all(models_dir).await?;
+    let path = models_dir.join(ggml_model_file_name(model_size));
+    let partial = path.with_extension("bin.part");
+
+    log::info!("📥 GGMLモデルをダウンロード中: {}", model_size);
+    let mut response = reqwest::get(ggml_model_url(model_size)).await?.error_for_status()?;
+    let total = response.content_length();
+    let mut file = tokio::fs::File::create(&partial).await?;
+    let mut downloaded = 0u64;
+    let mut last_percent = None;
+
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk).await?;
+        downloaded += chunk.len() as u64;
+        if let Some(total) = total.filter(|total| *total > 0) {
+            let percent = (downloaded * 100 / total) as f32;
+            // 同じ値は送らない
+            if last_percent != Some(percent) {
+                last_percent = Some(percent);
+                let _ = progress.send(WhisperDownloadProgress {
+                    model: model_size.to_string(),
+                    percent,
+                    downloaded: Some(format!("{}M", downloaded / (1024 * 1024))),
+                    total: Some(format!("{}M", total / (1024 * 1024))),
+                    done: false,
+                });
+            }
+        }
+    }
+    file.flush().await?;
+    drop(file);
+    tokio::fs::rename(&partial, &path).await?;
+
+    let _ = progress.send(WhisperDownloadProgress {
+        model: model_size.to_string(),
+        percent: 100.0,
+        downloaded: None,
+        total: None,
+        done: true,
+    });
+    log::info!("📦 GGMLモデルのダウンロード完了: {}", path.display());
+    Ok(path)
+}
@@ -0,0 +1,69 @@
+//! Whisperモデルのダウンロード進捗
+//!
+//! モデルは Python の `whisper.load_model` がダウンロードし、進捗は tqdm の表示として stderr に出る。
+//! その出力を読み取って `whisper-model-download-progress` イベントで配信し、初回の初期化が
+//! 固まったように見えないようにする。
+
+use serde::{Deserialize, Serialize};
+
+pub const WHISPER_DOWNLOAD_PROGRESS_EVENT: &str = "whisper-model-download-progress";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WhisperDownloadProgress {
+    pub model: String,
+    pub percent: f32,
+    /// tqdm の表示そのまま（例: "207M"）
+    pub downloaded: Option<String>,
+    pub total: Option<String>,
+    pub done: bool,
+}
+
+/// tqdm の進捗行（例: ` 45%|████▌     | 207M/461M [00:10<00:12, 20.1MiB/s]`）を解析する
+pub fn parse_progress_line(line: &str) -> Option<(f32, Option<String>, Option<String>)> {
+    let (head, rest) = line.split_once("%|")?;
+    let percent: f32 = head.trim().rsplit(' ').next()?.parse().ok()?;
+
+    let counts = rest
+        .split_once('|')
+        .map(|(_, after_bar)| after_bar.split(" [").next().unwrap_or("").trim())
+        .and_then(|counts| counts.split_once('/'));
+    let (downloaded, total) = match counts {
+        Some((downloaded, total)) => (Some(downloaded.trim().to_string()), Some(total.trim().to_string())),
+        None => (None, None),
+    };
+
+    Some((percent.clamp(0.0, 100.0), downloaded, total))
+}
+
+/// 出力を `\r` / `\n` 区切りの行に分ける（tqdm は同じ行を `\r` で上書きする）
+#[derive(Debug, Default)]
+pub struct ProgressLineSplitter {
+    pending: Vec<u8>,
+}
+
+impl ProgressLineSplitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 読み取った出力を追加し、区切りまで揃った行を返す
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        let mut lines = Vec::new();
+        for &byte in chunk {
+            if byte == b'\r' || byte == b'\n' {
+                if !self.pending.is_empty() {
+                    lines.push(String::from_utf8_lossy(&self.pending).into_owned());
+                    self.pending.clear();
+                }
+            } else {
+                self.pending.push(byte);
+            }
+        }
+        lines
+    }
+
+    /// 区切りの無いまま残った出力
+    pub fn finish(self) -> Option<String> {
+        (!self.pending.is_empty()).then(|| String::from_utf8_lossy(&self.pending).into_owned())
+    }
+}
@@ -1,8 +1,19 @@
 use crate::errors::{AppError, AppResult};
-use crate::models::{Transcription, TranscriptionStatus};
+use crate::models::{TranscriptSegment, Transcription, TranscriptionStatus};
+use crate::services::{audio_convert, demo_mode, loudness, recording_segments};
+use crate::services::transcription_lock::TranscriptionLocks;
+use crate::services::whisper_acceleration::{self, AccelerationBenchmark, WhisperDevice};
+use crate::services::whisper_cpp::{self, WhisperBackend, WhisperCppEngine, WhisperOutput, WhisperSegment};
+use crate::services::voice_activity::{self, SilenceTrimSummary};
+use crate::services::{SilenceTrimSettings, WhisperBackendSettings};
+use crate::services::whisper_download_progress::{self, ProgressLineSplitter, WhisperDownloadProgress};
+use serde::Deserialize;
 use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::io::AsyncReadExt;
+use tokio::sync::{broadcast, Mutex};
 use tokio::process::Command as TokioCommand;
 use std::fs;
 use dirs;
@@ -14,8 +25,22 @@ pub struct WhisperService {
     whisper_command: String,
     initialized: Arc<Mutex<bool>>,
     model_size: String,
+    /// 録音ごとの実行中書き起こし（重複起動防止）
+    locks: Arc<TranscriptionLocks>,
+    download_progress: broadcast::Sender<WhisperDownloadProgress>,
+    /// GPU（Metal / CUDA）があれば使うか
+    acceleration: Arc<AtomicBool>,
+    /// Python と whisper.cpp のどちらで書き起こすか
+    backend: Arc<std::sync::RwLock<WhisperBackendSettings>>,
+    /// 読み込んだ whisper.cpp のモデル
+    whisper_cpp: Arc<WhisperCppEngine>,
+    /// 書き起こし前の無音の除去
+    silence_trim: Arc<std::sync::RwLock<SilenceTrimSettings>>,
 }
 
+/// 発話が無い音声の書き起こし結果
+pub const SILENT_TRANSCRIPTION: &str = "（無音または認識できない音声）";
+
 impl WhisperService {
     pub fn new(model_path: PathBuf, recordings_dir: PathBuf) -> Self {
         // モデルサイズを環境変数で設定可能（デフォルト: base - 品質と速度のバランス）
@@ -36,9 +61,65 @@ impl WhisperService {
             whisper_command,
             initialized: Arc::new(Mutex::new(false)),
             model_size,
+            locks: Arc::new(TranscriptionLocks::new()),
+            download_progress: broadcast::channel(64).0,
+            acceleration: Arc::new(AtomicBool::new(true)),
+            backend: Arc::new(std::sync::RwLock::new(WhisperBackendSettings::default())),
+            whisper_cpp: Arc::new(WhisperCppEngine::new()),
+            silence_trim: Arc::new(std::sync::RwLock::new(SilenceTrimSettings::default())),
+        }
+    }
+
+    pub fn silence_trim(&self) -> SilenceTrimSettings {
+        self.silence_trim.read().map(|settings| settings.clone()).unwrap_or_default()
+    }
+
+    pub fn set_silence_trim(&self, settings: SilenceTrimSettings) {
+        if let Ok(mut current) = self.silence_trim.write() {
+            *current = settings;
+        }
+    }
+
+    pub fn backend(&self) -> WhisperBackendSettings {
+        self.backend.read().map(|backend| backend.clone()).unwrap_or_default()
+    }
+
+    /// 書き起こしの方式を切り替える（次の書き起こしの前に初期化し直す）
+    pub async fn set_backend(&self, backend: WhisperBackendSettings) {
+        if let Ok(mut current) = self.backend.write() {
+            *current = backend;
+        }
+        *self.initialized.lock().await = false;
+    }
+
+    /// whisper.cpp の GGML モデルの置き場所
+    pub fn ggml_model_path(&self, model_size: &str) -> PathBuf {
+        let models_dir = self.model_path.parent().unwrap_or(&self.model_path);
+        models_dir.join(whisper_cpp::ggml_model_file_name(model_size))
+    }
+
+    pub fn acceleration_enabled(&self) -> bool {
+        self.acceleration.load(Ordering::Relaxed)
+    }
+
+    /// 次の書き起こしから反映する
+    pub fn set_acceleration_enabled(&self, enabled: bool) {
+        self.acceleration.store(enabled, Ordering::Relaxed);
+    }
+
+    /// 書き起こしに使うデバイス（GPU が無ければ常に CPU）
+    pub fn device(&self) -> WhisperDevice {
+        match whisper_acceleration::gpu_device() {
+            Some(device) if self.acceleration_enabled() => device,
+            _ => WhisperDevice::Cpu,
         }
     }
 
+    /// モデルのダウンロード進捗を購読する
+    pub fn subscribe_download_progress(&self) -> broadcast::Receiver<WhisperDownloadProgress> {
+        self.download_progress.subscribe()
+    }
+
     pub async fn initialize(&self) -> AppResult<()> {
         let mut initialized = self.initialized.lock().await;
         
@@ -46,6 +127,20 @@ impl WhisperService {
             return Ok(());
         }
 
+        // デモモードでは Python・モデルを使わない
+        if demo_mode::is_enabled() {
+            *initialized = true;
+            log::info!("🧪 デモモードのため Whisper の初期化を省略");
+            return Ok(());
+        }
+
+        if self.backend().backend == WhisperBackend::WhisperCpp {
+            self.initialize_whisper_cpp().await?;
+            *initialized = true;
+            log::info!("✅ whisper.cpp 初期化完了 (モデル: {})", self.model_size);
+            return Ok(());
+        }
+
         log::info!("🔄 ローカルWhisper初期化中...");
 
         // Pythonの存在確認
@@ -70,6 +165,25 @@ impl WhisperService {
         Ok(())
     }
 
+    /// whisper.cpp の GGML モデルが無ければダウンロードする
+    async fn initialize_whisper_cpp(&self) -> AppResult<()> {
+        log::info!("🔄 whisper.cpp 初期化中...");
+        let model_path = self.ggml_model_path(&self.model_size);
+        if !model_path.exists() {
+            let models_dir = model_path.parent().unwrap_or(&model_path).to_path_buf();
+            whisper_cpp::download_model(&models_dir, &self.model_size, &self.download_progress)
+                .await
+                .map_err(|e| AppError::WhisperInit {
+                    message: format!("Failed to download GGML model {}: {}", self.model_size, e),
+                })?;
+        }
+        Ok(())
+    }
+
+    pub fn transcription_locks(&self) -> Arc<TranscriptionLocks> {
+        self.locks.clone()
+    }
+
     pub async fn is_initialized(&self) -> bool {
         let initialized = self.initialized.lock().await;
         *initialized
@@ -81,10 +195,82 @@ impl WhisperService {
         recording_id: String,
         language: Option<String>,
     ) -> AppResult<Transcription> {
+        self.transcribe_audio_file_with_model(audio_path, recording_id, language, None).await
+    }
+
+    /// 区間ごとに分けた録音を順に書き起こし、1つの書き起こしにつなげる
+    pub async fn transcribe_audio_files(
+        &self,
+        audio_paths: &[PathBuf],
+        recording_id: String,
+        language: Option<String>,
+    ) -> AppResult<Transcription> {
+        let mut parts = Vec::with_capacity(audio_paths.len());
+        let mut offset_ms = 0;
+        for (index, audio_path) in audio_paths.iter().enumerate() {
+            if audio_paths.len() > 1 {
+                log::info!("✂️ 区間 {}/{} を書き起こし中", index + 1, audio_paths.len());
+            }
+            let mut part = self.transcribe_audio_file(audio_path, recording_id.clone(), language.clone()).await?;
+            // 2つ目以降の区間の時刻は録音の先頭からの位置にする
+            for segment in &mut part.segments {
+                segment.start_ms += offset_ms;
+                segment.end_ms += offset_ms;
+            }
+            offset_ms = match recording_segments::wav_duration_ms(audio_path) {
+                Some(duration_ms) => offset_ms + duration_ms,
+                None => part.segments.last().map_or(offset_ms, |segment| segment.end_ms),
+            };
+            parts.push(part);
+        }
+        recording_segments::merge_transcriptions(parts).ok_or_else(|| AppError::ValidationError {
+            message: "No audio files to transcribe".to_string(),
+        })
+    }
+
+    /// 録音中の 16kHz の音声をファイルに書かずに書き起こす（whisper.cpp の場合のみ）
+    ///
+    /// 読み込んだモデルをチャンクごとに使い回す。whisper.cpp を使っていなければ None を返すので、
+    /// 呼び出し側で音声ファイルに書き出して `transcribe_audio_file` で書き起こす。
+    pub async fn transcribe_live_samples(&self, samples: Vec<f32>, language: Option<&str>) -> AppResult<Option<String>> {
+        if demo_mode::is_enabled() || self.backend().backend != WhisperBackend::WhisperCpp {
+            return Ok(None);
+        }
+        if !self.is_initialized().await {
+            return Err(AppError::WhisperNotInitialized {
+                message: "Whisper service is not initialized. Call initialize() first.".to_string(),
+            });
+        }
+        let output = self
+            .whisper_cpp
+            .transcribe_pcm(&self.ggml_model_path(&self.model_size), samples, language.unwrap_or("ja"), self.device())
+            .await?;
+        Ok(Some(output.text))
+    }
+
+    /// モデルを指定して書き起こす（None なら設定中のモデル。ボイスメモなど速さを優先する場合に使う）
+    pub async fn transcribe_audio_file_with_model(
+        &self,
+        audio_path: &Path,
+        recording_id: String,
+        language: Option<String>,
+        model_size: Option<&str>,
+    ) -> AppResult<Transcription> {
+        let model_size = match model_size {
+            Some(model_size) => {
+                if !self.get_available_models().await?.iter().any(|model| model == model_size) {
+                    return Err(AppError::ValidationError {
+                        message: format!("Invalid model size: {}", model_size),
+                    });
+                }
+                model_size.to_string()
+            }
+            None => self.model_size.clone(),
+        };
         let start_time = std::time::Instant::now();
         
         // 初期化チェック
-        if !self.is_initialized().await {
+        if !demo_mode::is_enabled() && !self.is_initialized().await {
             return Err(AppError::WhisperNotInitialized {
                 message: "Whisper service is not initialized. Call initialize() first.".to_string(),
             });
@@ -110,6 +296,25 @@ impl WhisperService {
             });
         }
 
+        // 同じ録音の書き起こしが実行中なら、そのジョブIDを返して重複起動しない
+        let lock = self.locks.try_acquire(&recording_id).map_err(|existing| {
+            log::warn!("⏳ 書き起こし実行中のため重複リクエストをスキップ: {} (job {})", existing.recording_id, existing.job_id);
+            AppError::TranscriptionInProgress {
+                recording_id: existing.recording_id,
+                job_id: existing.job_id,
+            }
+        })?;
+
+        if demo_mode::is_enabled() {
+            let mut transcription = demo_mode::demo_transcription(recording_id, language);
+            transcription.id = lock.job_id().to_string();
+            for segment in &mut transcription.segments {
+                segment.transcription_id = transcription.id.clone();
+            }
+            log::info!("🧪 デモモードの書き起こしを返却: {:?}", audio_path);
+            return Ok(transcription);
+        }
+
         log::info!("🎤 ローカル音声書き起こし開始: {:?}", audio_path);
 
         // 出力ファイルパスを生成
@@ -117,24 +322,80 @@ impl WhisperService {
         fs::create_dir_all(&output_dir)?;
         let output_file = output_dir.join(format!("{}.txt", recording_id));
 
-        // whisperコマンドを実行
-        let transcription_text = self.run_whisper_command(
-            audio_path,
-            &output_file,
-            language.as_deref()
-        ).await?;
+        // 録音の停止時・取り込み時に作った書き起こし用のコピーがあればそれを使い、無ければ16kHzモノラルWAVへ変換（ffmpeg不要）
+        let normalized = loudness::normalized_path(&self.recordings_dir, audio_path);
+        let whisper_input = if normalized.exists() {
+            log::info!("🔄 変換済みのコピーで書き起こし: {:?}", normalized);
+            normalized.clone()
+        } else {
+            self.prepare_input(audio_path, &recording_id).await?
+        };
+        // 長い無音を取り除く
+        let trimmed = self.trim_input(&whisper_input, &recording_id).await;
+
+        // whisperコマンドを実行（発話が無ければ実行しない）
+        let result = match &trimmed {
+            Some((_, summary)) if summary.is_silent() => {
+                log::info!("🔇 発話が検出されなかったため書き起こしをスキップ: {:?}", audio_path);
+                Ok(WhisperOutput {
+                    text: SILENT_TRANSCRIPTION.to_string(),
+                    segments: Vec::new(),
+                })
+            }
+            _ => {
+                let input = trimmed.as_ref().map(|(path, _)| path.as_path()).unwrap_or(&whisper_input);
+                self.run_whisper_command(
+                    input,
+                    &output_file,
+                    language.as_deref(),
+                    &model_size,
+                    self.device(),
+                ).await
+            }
+        };
+
+        // 変換した一時ファイルを削除
+        if let Some((path, _)) = &trimmed {
+            let _ = fs::remove_file(path);
+        }
+        if whisper_input != audio_path && whisper_input != normalized {
+            let _ = fs::remove_file(&whisper_input);
+        }
+        let output = result?;
 
         let processing_time = start_time.elapsed().as_millis() as u64;
         
-        // 転写結果を作成
-        let transcription = Transcription::new(
+        // 転写結果を作成（信頼度は区間ごとの信頼度の平均）
+        let mut transcription = Transcription::new(
             recording_id,
-            transcription_text,
+            output.text,
             language.unwrap_or_else(|| "ja".to_string()),
         )
-        .with_confidence(Some(0.95)) // ローカル処理なので高い信頼度を設定
+        .with_confidence(average_confidence(&output.segments))
         .with_processing_time(Some(processing_time))
+        .with_model_used(Some(model_size))
         .with_status(TranscriptionStatus::Completed);
+        // 重複リクエストに返したジョブIDで結果を参照できるようにする
+        transcription.id = lock.job_id().to_string();
+        // 無音を除いて書き起こした場合は、区間の時刻を元の音声での位置に戻す
+        let trim_summary = trimmed.as_ref().map(|(_, summary)| summary);
+        transcription.segments = output
+            .segments
+            .into_iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                let to_original = |ms: i64| trim_summary.map_or(ms, |summary| summary.original_ms(ms));
+                let mut transcript_segment = TranscriptSegment::new(
+                    transcription.id.clone(),
+                    index as i32,
+                    to_original(segment.start_ms),
+                    to_original(segment.end_ms),
+                    segment.text,
+                );
+                transcript_segment.confidence = segment.confidence;
+                transcript_segment
+            })
+            .collect();
 
         log::info!("✅ ローカル書き起こし完了: {} 文字 ({}ms)", 
                   transcription.text.len(), processing_time);
@@ -142,19 +403,140 @@ impl WhisperService {
         Ok(transcription)
     }
 
+    /// 16kHzモノラルWAVへ変換する（変換不要ならそのままのパス）
+    async fn prepare_input(&self, audio_path: &Path, name: &str) -> AppResult<PathBuf> {
+        let work_dir = self.recordings_dir.join("converted");
+        let audio_path = audio_path.to_path_buf();
+        let name = name.to_string();
+        tokio::task::spawn_blocking(move || audio_convert::prepare_for_whisper(&audio_path, &work_dir, &name))
+            .await
+            .map_err(|e| AppError::AudioConversion {
+                message: format!("Conversion task failed: {}", e),
+            })?
+    }
+
+    /// 長い無音を取り除いた入力を作る（無効・取り除く無音が無い・失敗した場合は None）
+    async fn trim_input(&self, input: &Path, name: &str) -> Option<(PathBuf, SilenceTrimSummary)> {
+        let settings = self.silence_trim();
+        if !settings.enabled {
+            return None;
+        }
+        let work_dir = self.recordings_dir.join("converted");
+        let output = work_dir.join(format!("{}-trimmed.wav", name));
+        let input = input.to_path_buf();
+        let task_output = output.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            fs::create_dir_all(&work_dir)?;
+            voice_activity::trim_silence_in_file(&input, &task_output, &settings)
+        })
+        .await;
+
+        match result {
+            Ok(Ok(Some(summary))) => {
+                log::info!(
+                    "✂️ 無音を除去: {}ms → {}ms (発話 {}ms)",
+                    summary.original_ms,
+                    summary.trimmed_ms,
+                    summary.speech_ms
+                );
+                Some((output, summary))
+            }
+            Ok(Ok(None)) => None,
+            Ok(Err(e)) => {
+                log::warn!("⚠️ 無音の除去に失敗したため元の音声で書き起こします: {}", e);
+                None
+            }
+            Err(e) => {
+                log::warn!("⚠️ 無音の除去に失敗したため元の音声で書き起こします: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 同じ音声を CPU と GPU で書き起こし、かかった時間を比べる（GPU がある環境のみ）
+    pub async fn benchmark_acceleration(
+        &self,
+        audio_path: &Path,
+        audio_seconds: f64,
+        model_size: &str,
+    ) -> AppResult<AccelerationBenchmark> {
+        let Some(gpu) = whisper_acceleration::gpu_device() else {
+            return Err(AppError::InvalidOperation {
+                message: "GPU acceleration requires Apple Silicon or an NVIDIA GPU".to_string(),
+            });
+        };
+        if !self.get_available_models().await?.iter().any(|model| model == model_size) {
+            return Err(AppError::ValidationError {
+                message: format!("Invalid model size: {}", model_size),
+            });
+        }
+        if !audio_path.exists() {
+            return Err(AppError::FileNotFound {
+                path: audio_path.to_string_lossy().to_string(),
+            });
+        }
+
+        log::info!("⏱️ Whisper高速化の計測開始: {} ({:.0}秒の音声)", model_size, audio_seconds);
+        let whisper_input = self.prepare_input(audio_path, "acceleration_benchmark").await?;
+        let output_file = self.recordings_dir.join("transcripts").join("acceleration_benchmark.txt");
+
+        let mut elapsed = Vec::new();
+        for device in [WhisperDevice::Cpu, gpu] {
+            let started = std::time::Instant::now();
+            let result = self.run_whisper_command(&whisper_input, &output_file, None, model_size, device).await;
+            if let Err(e) = result {
+                if whisper_input != audio_path {
+                    let _ = fs::remove_file(&whisper_input);
+                }
+                return Err(e);
+            }
+            elapsed.push(started.elapsed().as_millis() as u64);
+        }
+        if whisper_input != audio_path {
+            let _ = fs::remove_file(&whisper_input);
+        }
+
+        let benchmark = AccelerationBenchmark {
+            model: model_size.to_string(),
+            audio_seconds,
+            cpu_ms: elapsed[0],
+            accelerated_ms: elapsed[1],
+            measured_at: chrono::Utc::now(),
+        };
+        log::info!("⏱️ Whisper高速化の計測完了: {} は {:.1}倍", model_size, benchmark.speedup());
+        Ok(benchmark)
+    }
+
     async fn run_whisper_command(
         &self,
         audio_path: &Path,
         output_file: &Path,
         language: Option<&str>,
-    ) -> AppResult<String> {
+        model_size: &str,
+        device: WhisperDevice,
+    ) -> AppResult<WhisperOutput> {
+        if self.backend().backend == WhisperBackend::WhisperCpp {
+            let output = self
+                .whisper_cpp
+                .transcribe(&self.ggml_model_path(model_size), audio_path, language.unwrap_or("ja"), device)
+                .await?;
+            if output.text.is_empty() {
+                log::warn!("whisper.cpp returned empty result");
+                return Ok(WhisperOutput {
+                    text: SILENT_TRANSCRIPTION.to_string(),
+                    segments: Vec::new(),
+                });
+            }
+            return Ok(output);
+        }
+
         // PythonスクリプトとしてWhisperを実行
         let python_cmd = self.python_path.as_ref()
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_else(|| "python3".to_string());
 
         // Pythonスクリプトを作成
-        let script = self.create_whisper_script(audio_path, language).await?;
+        let script = self.create_whisper_script(audio_path, language, model_size, device).await?;
         
         log::debug!("実行Python: {} -c '{}'", python_cmd, script);
 
@@ -188,16 +570,21 @@ impl WhisperService {
         // 空の結果でもエラーにしない（無音の音声ファイルなど）
         if result.is_empty() {
             log::warn!("Whisper returned empty result. stdout: '{}', stderr: '{}'", stdout, stderr);
-            return Ok("（無音または認識できない音声）".to_string());
+            return Ok(WhisperOutput {
+                text: SILENT_TRANSCRIPTION.to_string(),
+                segments: Vec::new(),
+            });
         }
 
-        Ok(result)
+        Ok(parse_script_output(&result))
     }
 
     async fn create_whisper_script(
         &self,
         audio_path: &Path,
         language: Option<&str>,
+        model_size: &str,
+        device: WhisperDevice,
     ) -> AppResult<String> {
         // 日本語の場合は明示的に言語指定と最適化オプションを追加
         let language = language.unwrap_or("ja");
@@ -226,6 +613,7 @@ impl WhisperService {
         let script = format!(
             r#"
 import whisper
+import json
 import sys
 import warnings
 import os
@@ -244,57 +632,89 @@ try:
         print("Audio file is empty", file=sys.stderr)
         sys.exit(1)
     
-    print(f"Loading model: {model_size} (optimized for Japanese)", file=sys.stderr)
-    model = whisper.load_model('{model_size}')
+    device = '{device}'
+    if device == 'mps':
+        import torch
+        if not torch.backends.mps.is_available():
+            print("MPS is not available, falling back to CPU", file=sys.stderr)
+            device = 'cpu'
+    elif device == 'cuda':
+        import torch
+        if not torch.cuda.is_available():
+            print("CUDA is not available, falling back to CPU", file=sys.stderr)
+            device = 'cpu'
+
+    print(f"Loading model: {model_size} on {{device}} (optimized for Japanese)", file=sys.stderr)
+    # MPS では疎テンソルを直接読み込めないため、CPU で読み込んでから移す
+    model = whisper.load_model('{model_size}', device='cpu')
+    if device != 'cpu':
+        model = model.to(device)
     
     print(f"Transcribing file: {{audio_file}} ({{file_size}} bytes) with Japanese optimization", file=sys.stderr)
     
-    # 音声前処理（ノイズ除去とボリューム正規化）
-    try:
-        import librosa
-        # librosaで音声を読み込み、前処理
-        audio_data, sr = librosa.load(audio_file, sr=16000)
-        
-        # 音声品質チェック
-        if len(audio_data) == 0:
-            print("Warning: Empty audio data", file=sys.stderr)
-            sys.exit(1)
-            
-        # RMSベースのボリューム正規化（より保守的）
-        rms = np.sqrt(np.mean(audio_data**2))
-        if rms > 0:
-            # 音声レベルが低すぎる場合の警告
-            if rms < 0.001:
-                print(f"Warning: Very low audio level (RMS: {{rms:.6f}})", file=sys.stderr)
-            target_rms = 0.05  # より保守的なレベル
-            audio_data = audio_data * (target_rms / rms)
-            
-        # 無音部分の除去（より保守的）
-        audio_data, _ = librosa.effects.trim(audio_data, top_db=20)  # より感度良く
-        
-        # 最小音声長チェック
-        min_duration = 0.1  # 0.1秒以上
-        if len(audio_data) / sr < min_duration:
-            print(f"Warning: Audio too short ({{len(audio_data) / sr:.2f}}s)", file=sys.stderr)
-            
-        print(f"Audio preprocessing completed: {{len(audio_data) / sr:.2f}}s, RMS: {{np.sqrt(np.mean(audio_data**2)):.6f}}", file=sys.stderr)
-        
-        # 前処理済み音声でトランスクリプション
-        result = model.transcribe(
-            audio_data,
-            {transcribe_options}
-        )
-    except ImportError:
-        print(f"librosa not available, using direct file processing", file=sys.stderr)
-        # 日本語最適化設定でトランスクリプション実行（ファイル直接）
-        result = model.transcribe(
-            audio_file,
-            {transcribe_options}
-        )
+    # 音量の正規化はRust側で済んでいる
+    # 入力はRust側で16kHzモノラル16bit WAVに変換済みのため、ffmpegを使わず読み込む
+    import wave
+    with wave.open(audio_file, 'rb') as wav_file:
+        frames = wav_file.readframes(wav_file.getnframes())
+    audio_data = np.frombuffer(frames, dtype=np.int16).astype(np.float32) / 32768.0
+
+    # 音声品質チェック
+    if len(audio_data) == 0:
+        print("Warning: Empty audio data", file=sys.stderr)
+        sys.exit(1)
+
+    # 音声レベルが低すぎる場合の警告
+    rms = np.sqrt(np.mean(audio_data**2))
+    if rms < 0.001:
+        print(f"Warning: Very low audio level (RMS: {{rms:.6f}})", file=sys.stderr)
+
+    print(f"Audio loaded: {{len(audio_data) / 16000:.2f}}s, RMS: {{np.sqrt(np.mean(audio_data**2)):.6f}}", file=sys.stderr)
+
+    result = model.transcribe(
+        audio_data,
+        {transcribe_options}
+    )
     
+    def clean_text(text):
+        text = text.strip()
+        # 日本語の場合、後処理で改善
+        if '{language}' != 'ja':
+            return text
+
+        # 日本語特有の後処理
+        import re
+
+        # プロンプトテキストと幻覚パターンの除去
+        hallucination_patterns = [
+            '日本語の音声です：',
+            '以下は日本語の音声です：',
+            '日本語の音声です。',
+            '以下は日本語の音声です。',
+            'お疲れ様でした。',
+            '次回はお楽しみに',
+            'ありがとうございました。',
+            'ご視聴ありがとうございました'
+        ]
+
+        for pattern in hallucination_patterns:
+            # 幻覚パターンの除去
+            while pattern in text:
+                text = text.replace(pattern, '', 1).strip()
+
+        # 不要な空白を削除
+        text = re.sub(r'\s+', ' ', text).strip()
+        # 句読点の正規化
+        text = text.replace('、', '、').replace('。', '。')
+        # 英数字周りのスペース調整
+        text = re.sub(r'([ぁ-んァ-ヶ一-龯])([A-Za-z0-9])', r'\1 \2', text)
+        text = re.sub(r'([A-Za-z0-9])([ぁ-んァ-ヶ一-龯])', r'\1 \2', text)
+        return text
+
     text = result.get('text', '').strip()
-    
-    # デバッグ情報を出力
+
+    # 区間ごとの時刻と平均対数確率（信頼度）を本文と一緒に返す
+    segments = []
     if 'segments' in result:
         total_segments = len(result['segments'])
         print(f"Processed {{total_segments}} audio segments", file=sys.stderr)
@@ -304,6 +724,14 @@ try:
         for segment in result['segments']:
             if 'avg_logprob' in segment and segment['avg_logprob'] < -0.8:
                 low_confidence_segments += 1
+            segment_text = clean_text(segment.get('text', ''))
+            if segment_text:
+                segments.append({{
+                    'start': segment.get('start', 0.0),
+                    'end': segment.get('end', 0.0),
+                    'text': segment_text,
+                    'avg_logprob': segment.get('avg_logprob'),
+                }})
         
         if low_confidence_segments > 0:
             print(f"Warning: {{low_confidence_segments}} segments have low confidence", file=sys.stderr)
@@ -312,43 +740,14 @@ try:
         # 実際の音声が認識できない場合
         print(f"Warning: No text could be transcribed from audio", file=sys.stderr)
         print(f"Audio file size: {{file_size}} bytes", file=sys.stderr)
-        print("音声が認識できませんでした。より明瞭に話すか、マイクの距離を近づけてください。")
+        text = "音声が認識できませんでした。より明瞭に話すか、マイクの距離を近づけてください。"
     else:
-        # 日本語の場合、後処理で改善
-        if '{language}' == 'ja':
-            # 日本語特有の後処理
-            import re
-            
-            # プロンプトテキストと幻覚パターンの除去
-            hallucination_patterns = [
-                '日本語の音声です：',
-                '以下は日本語の音声です：',
-                '日本語の音声です。',
-                '以下は日本語の音声です。',
-                'お疲れ様でした。',
-                '次回はお楽しみに',
-                'ありがとうございました。',
-                'ご視聴ありがとうございました'
-            ]
-            
-            for pattern in hallucination_patterns:
-                # 幻覚パターンの除去
-                while pattern in text:
-                    text = text.replace(pattern, '', 1).strip()
-            
-            # 不要な空白を削除
-            text = re.sub(r'\s+', ' ', text).strip()
-            # 句読点の正規化
-            text = text.replace('、', '、').replace('。', '。')
-            # 英数字周りのスペース調整
-            text = re.sub(r'([ぁ-んァ-ヶ一-龯])([A-Za-z0-9])', r'\1 \2', text)
-            text = re.sub(r'([A-Za-z0-9])([ぁ-んァ-ヶ一-龯])', r'\1 \2', text)
-            
-            # 空の結果になった場合のハンドリング
-            if not text.strip():
-                text = "音声を認識できませんでした。"
-        
-        print(text)
+        text = clean_text(text)
+        # 空の結果になった場合のハンドリング
+        if not text:
+            text = "音声を認識できませんでした。"
+
+    print(json.dumps({{'text': text, 'segments': segments}}, ensure_ascii=False))
         
 except Exception as e:
     print(f"Error: {{e}}", file=sys.stderr)
@@ -357,7 +756,8 @@ except Exception as e:
     sys.exit(1)
 "#,
             audio_path = audio_path.to_string_lossy(),
-            model_size = self.model_size,
+            model_size = model_size,
+            device = device.torch_name(),
             transcribe_options = transcribe_options,
             language = language
         );
@@ -430,7 +830,6 @@ except Exception as e:
         // 必要なライブラリのリスト（音声処理の品質向上のため）
         let packages = vec![
             "openai-whisper",
-            "librosa",
             "soundfile",
             "numpy",
         ];
@@ -453,7 +852,7 @@ except Exception as e:
             if !output.status.success() {
                 let stderr = String::from_utf8_lossy(&output.stderr);
                 log::warn!("Failed to install {}: {}", package, stderr);
-                // librosa等の失敗は致命的ではないため、whisperのみ必須とする
+                // soundfile等の失敗は致命的ではないため、whisperのみ必須とする
                 if package == "openai-whisper" {
                     return Err(AppError::WhisperInit {
                         message: format!("Whisper installation failed: {}", stderr),
@@ -490,26 +889,27 @@ except Exception as e:
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_else(|| "python3".to_string());
 
-        let output = TokioCommand::new(&python_cmd)
-            .arg("-c")
-            .arg(&format!(
-                "import whisper; model = whisper.load_model('{}'); print('Model loaded')",
-                self.model_size
-            ))
-            .output()
-            .await
-            .map_err(|e| AppError::WhisperInit {
-                message: format!("Failed to download model: {}", e),
-            })?;
+        let script = format!(
+            "import whisper; model = whisper.load_model('{}'); print('Model loaded')",
+            self.model_size
+        );
+        let result = self.run_model_download(&python_cmd, &script, &self.model_size).await;
 
         // 一時ファイルを削除
         let _ = fs::remove_file(&temp_audio);
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(AppError::WhisperInit {
-                message: format!("Model download failed: {}", stderr),
-            });
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(stderr)) => {
+                return Err(AppError::WhisperInit {
+                    message: format!("Model download failed: {}", stderr),
+                });
+            }
+            Err(e) => {
+                return Err(AppError::WhisperInit {
+                    message: format!("Failed to download model: {}", e),
+                });
+            }
         }
 
         log::info!("✅ モデルダウンロード完了");
@@ -550,6 +950,22 @@ except Exception as e:
         Ok(dummy_audio)
     }
 
+    /// 書き起こしに使う Python コマンド
+    pub fn python_command(&self) -> String {
+        self.python_path
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| "python3".to_string())
+    }
+
+    pub fn recordings_dir(&self) -> &Path {
+        &self.recordings_dir
+    }
+
+    pub fn whisper_cache_dir(&self) -> PathBuf {
+        self.get_whisper_cache_dir()
+    }
+
     fn get_whisper_cache_dir(&self) -> PathBuf {
         // Whisperのデフォルトキャッシュディレクトリ
         if let Some(home) = dirs::home_dir() {
@@ -686,6 +1102,7 @@ except Exception as e:
         let script = format!(
             r#"
 import whisper
+import json
 import sys
 import warnings
 warnings.filterwarnings("ignore")
@@ -701,17 +1118,14 @@ except Exception as e:
             model_name, model_name, model_name, model_name
         );
 
-        let output = TokioCommand::new(&python_cmd)
-            .arg("-c")
-            .arg(&script)
-            .output()
+        let result = self
+            .run_model_download(&python_cmd, &script, model_name)
             .await
             .map_err(|e| AppError::WhisperInit {
                 message: format!("Failed to download model {}: {}", model_name, e),
             })?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
+        if let Err(stderr) = result {
             return Err(AppError::WhisperInit {
                 message: format!("Model {} download failed: {}", model_name, stderr),
             });
@@ -720,6 +1134,67 @@ except Exception as e:
         Ok(())
     }
 
+    /// モデルを読み込む Python スクリプトを実行し、stderr の tqdm 表示から進捗を配信する
+    ///
+    /// プロセスが失敗した場合は stderr の内容を `Ok(Err(..))` で返す。
+    async fn run_model_download(&self, python_cmd: &str, script: &str, model_name: &str) -> std::io::Result<Result<(), String>> {
+        let mut child = TokioCommand::new(python_cmd)
+            .arg("-c")
+            .arg(script)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+        let mut splitter = ProgressLineSplitter::new();
+        let mut messages = Vec::new();
+        let mut last_percent = None;
+        let mut buffer = [0u8; 4096];
+
+        loop {
+            let read = stderr.read(&mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+            for line in splitter.push(&buffer[..read]) {
+                match whisper_download_progress::parse_progress_line(&line) {
+                    Some((percent, downloaded, total)) => {
+                        // 同じ値の上書き表示は送らない
+                        if last_percent != Some(percent) {
+                            last_percent = Some(percent);
+                            let _ = self.download_progress.send(WhisperDownloadProgress {
+                                model: model_name.to_string(),
+                                percent,
+                                downloaded,
+                                total,
+                                done: false,
+                            });
+                        }
+                    }
+                    None => messages.push(line),
+                }
+            }
+        }
+        messages.extend(splitter.finish());
+
+        let status = child.wait().await?;
+        if !status.success() {
+            return Ok(Err(messages.join("\n")));
+        }
+
+        if last_percent.is_some() {
+            log::info!("📦 モデル {} のダウンロード完了", model_name);
+        }
+        let _ = self.download_progress.send(WhisperDownloadProgress {
+            model: model_name.to_string(),
+            percent: 100.0,
+            downloaded: None,
+            total: None,
+            done: true,
+        });
+        Ok(Ok(()))
+    }
+
     pub async fn get_available_models(&self) -> AppResult<Vec<String>> {
         Ok(vec![
             "tiny".to_string(),
@@ -751,4 +1226,51 @@ except Exception as e:
     pub fn get_current_model_size(&self) -> String {
         self.model_size.clone()
     }
-}
\ No newline at end of file
+}
+/// 区間ごとの信頼度の平均（信頼度の分かる区間が無ければ None）
+pub fn average_confidence(segments: &[WhisperSegment]) -> Option<f32> {
+    let confidences: Vec<f32> = segments.iter().filter_map(|segment| segment.confidence).collect();
+    (!confidences.is_empty()).then(|| confidences.iter().sum::<f32>() / confidences.len() as f32)
+}
+
+#[derive(Deserialize)]
+struct ScriptOutput {
+    text: String,
+    #[serde(default)]
+    segments: Vec<ScriptSegment>,
+}
+
+#[derive(Deserialize)]
+struct ScriptSegment {
+    start: f64,
+    end: f64,
+    text: String,
+    avg_logprob: Option<f64>,
+}
+
+/// Python スクリプトの出力（本文と区間の JSON）を読む（JSON でなければ全体を本文として扱う）
+pub fn parse_script_output(stdout: &str) -> WhisperOutput {
+    let last_line = stdout.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or_default();
+    let Ok(output) = serde_json::from_str::<ScriptOutput>(last_line.trim()) else {
+        return WhisperOutput {
+            text: stdout.trim().to_string(),
+            segments: Vec::new(),
+        };
+    };
+
+    let segments = output
+        .segments
+        .into_iter()
+        .map(|segment| WhisperSegment {
+            start_ms: (segment.start * 1000.0).round() as i64,
+            end_ms: (segment.end * 1000.0).round() as i64,
+            text: segment.text,
+            // 平均対数確率を 0〜1 の確率に直す
+            confidence: segment.avg_logprob.map(|logprob| (logprob.exp() as f32).clamp(0.0, 1.0)),
+        })
+        .collect();
+    WhisperOutput {
+        text: output.text,
+        segments,
+    }
+}
@@ -0,0 +1,183 @@
+//! ワークスペース（クライアントごとに分離したデータ領域）
+//!
+//! ワークスペースごとにデータベース・録音ディレクトリ・設定ファイルを分けて保存する。
+//! 一覧と選択中のワークスペースは `workspaces.json` に保持し、起動時に選択中のワークスペースの
+//! パスで各サービスを初期化する。既定のワークスペースは従来どおりアプリのデータディレクトリ直下を使う。
+//! Whisper モデルは容量が大きく会議データを含まないため、全ワークスペースで共有する。
+//! 外部連携の認証情報（`credentials`、OSのキーチェーン）はワークスペースIDでキーを分けて保存する。
+
+use crate::errors::{AppError, AppResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+pub const DEFAULT_WORKSPACE_ID: &str = "default";
+
+/// ワークスペース名の最大文字数
+const MAX_WORKSPACE_NAME_CHARS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// ワークスペースごとのデータの保存先
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspacePaths {
+    pub root: PathBuf,
+    pub db_path: PathBuf,
+    pub recordings_dir: PathBuf,
+    pub app_settings_path: PathBuf,
+    pub model_settings_path: PathBuf,
+}
+
+impl WorkspacePaths {
+    fn new(root: PathBuf) -> Self {
+        Self {
+            db_path: root.join("recordings.db"),
+            recordings_dir: root.join("recordings"),
+            app_settings_path: root.join("app_settings.json"),
+            model_settings_path: root.join("model_settings.json"),
+            root,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WorkspaceRegistry {
+    active: String,
+    workspaces: Vec<Workspace>,
+}
+
+impl Default for WorkspaceRegistry {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_WORKSPACE_ID.to_string(),
+            workspaces: vec![Workspace {
+                id: DEFAULT_WORKSPACE_ID.to_string(),
+                name: "Default".to_string(),
+                created_at: Utc::now(),
+            }],
+        }
+    }
+}
+
+pub struct WorkspaceManager {
+    app_data_dir: PathBuf,
+    registry: WorkspaceRegistry,
+}
+
+impl WorkspaceManager {
+    /// `workspaces.json` を読み込む（無ければ既定のワークスペースだけの状態で始める）
+    pub fn load(app_data_dir: &Path) -> AppResult<Self> {
+        let registry_path = app_data_dir.join("workspaces.json");
+        let mut registry: WorkspaceRegistry = if registry_path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(&registry_path)?)?
+        } else {
+            WorkspaceRegistry::default()
+        };
+
+        if !registry.workspaces.iter().any(|workspace| workspace.id == DEFAULT_WORKSPACE_ID) {
+            registry.workspaces.insert(0, WorkspaceRegistry::default().workspaces.remove(0));
+        }
+        // 選択中のワークスペースが消えていたら既定に戻す
+        if !registry.workspaces.iter().any(|workspace| workspace.id == registry.active) {
+            log::warn!("⚠️ Active workspace {} not found, falling back to default", registry.active);
+            registry.active = DEFAULT_WORKSPACE_ID.to_string();
+        }
+
+        Ok(Self {
+            app_data_dir: app_data_dir.to_path_buf(),
+            registry,
+        })
+    }
+
+    pub fn list(&self) -> Vec<Workspace> {
+        self.registry.workspaces.clone()
+    }
+
+    pub fn active(&self) -> &Workspace {
+        self.registry
+            .workspaces
+            .iter()
+            .find(|workspace| workspace.id == self.registry.active)
+            .unwrap_or(&self.registry.workspaces[0])
+    }
+
+    pub fn paths(&self, workspace_id: &str) -> AppResult<WorkspacePaths> {
+        self.find(workspace_id)?;
+        Ok(if workspace_id == DEFAULT_WORKSPACE_ID {
+            WorkspacePaths::new(self.app_data_dir.clone())
+        } else {
+            WorkspacePaths::new(self.app_data_dir.join("workspaces").join(workspace_id))
+        })
+    }
+
+    /// 選択中のワークスペースの保存先（ディレクトリが無ければ作成する）
+    pub fn active_paths(&self) -> AppResult<WorkspacePaths> {
+        let paths = self.paths(&self.registry.active)?;
+        std::fs::create_dir_all(&paths.root)?;
+        Ok(paths)
+    }
+
+    pub fn create(&mut self, name: &str) -> AppResult<Workspace> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_WORKSPACE_NAME_CHARS {
+            return Err(AppError::ValidationError {
+                message: format!("Workspace name must be 1-{} characters", MAX_WORKSPACE_NAME_CHARS),
+            });
+        }
+        if self
+            .registry
+            .workspaces
+            .iter()
+            .any(|workspace| workspace.name.to_lowercase() == name.to_lowercase())
+        {
+            return Err(AppError::ValidationError {
+                message: format!("Workspace '{}' already exists", name),
+            });
+        }
+
+        let workspace = Workspace {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            created_at: Utc::now(),
+        };
+        self.registry.workspaces.push(workspace.clone());
+        std::fs::create_dir_all(&self.paths(&workspace.id)?.root)?;
+        self.save()?;
+
+        log::info!("🗂️ Created workspace {} ({})", workspace.name, workspace.id);
+        Ok(workspace)
+    }
+
+    /// 選択中のワークスペースを切り替える（反映は次回起動時）
+    pub fn switch(&mut self, workspace_id: &str) -> AppResult<Workspace> {
+        let workspace = self.find(workspace_id)?.clone();
+        self.registry.active = workspace.id.clone();
+        self.save()?;
+
+        log::info!("🗂️ Switched active workspace to {} ({})", workspace.name, workspace.id);
+        Ok(workspace)
+    }
+
+    fn find(&self, workspace_id: &str) -> AppResult<&Workspace> {
+        self.registry
+            .workspaces
+            .iter()
+            .find(|workspace| workspace.id == workspace_id)
+            .ok_or_else(|| AppError::ValidationError {
+                message: format!("Workspace with id {} not found", workspace_id),
+            })
+    }
+
+    fn save(&self) -> AppResult<()> {
+        std::fs::create_dir_all(&self.app_data_dir)?;
+        let content = serde_json::to_string_pretty(&self.registry)?;
+        std::fs::write(self.app_data_dir.join("workspaces.json"), content)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use chrono::NaiveDate;
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{ActionItemRef, ActionItemStatus, Recording, Summary, Transcription};
+use meeting_summarizer_lib::services::action_items::{
+    self, due_bucket, extract_assignee, ActionItemQuery, DueBucket,
+};
+
+async fn create_meeting(database: &Database, title: &str, action_items: &[&str]) -> Summary {
+    let mut recording = Recording::new(format!("{}.wav", title), format!("/tmp/{}.wav", title));
+    recording.title = Some(title.to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let transcription = Transcription::new(recording.id.clone(), "text".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+
+    let summary = Summary::new(transcription.id.clone(), "llama3".to_string()).with_content(
+        "summary".to_string(),
+        Vec::new(),
+        action_items.iter().map(|item| item.to_string()).collect(),
+    );
+    database.create_summary(&summary).await.unwrap();
+    summary
+}
+
+#[test]
+fn test_assignee_extraction() {
+    assert_eq!(extract_assignee("見積書を送付（担当：田中さん、2024-05-10まで）").as_deref(), Some("田中"));
+    assert_eq!(extract_assignee("議事録を共有 担当者: 佐藤").as_deref(), Some("佐藤"));
+    assert_eq!(extract_assignee("Update the roadmap (Owner: Alice)").as_deref(), Some("Alice"));
+    assert_eq!(extract_assignee("@bob will review the PR").as_deref(), Some("bob"));
+    assert!(extract_assignee("次回の日程を決める").is_none());
+}
+
+#[test]
+fn test_due_buckets() {
+    // 2024-05-08 は水曜日
+    let today = NaiveDate::from_ymd_opt(2024, 5, 8).unwrap();
+    let date = |day| NaiveDate::from_ymd_opt(2024, 5, day);
+    assert_eq!(due_bucket(date(7), today), DueBucket::Overdue);
+    assert_eq!(due_bucket(date(8), today), DueBucket::Today);
+    assert_eq!(due_bucket(date(12), today), DueBucket::ThisWeek);
+    assert_eq!(due_bucket(date(13), today), DueBucket::Later);
+    assert_eq!(due_bucket(None, today), DueBucket::NoDueDate);
+}
+
+#[tokio::test]
+async fn test_items_across_meetings_and_bulk_update() {
+    let database = Database::in_memory().unwrap();
+    let weekly = create_meeting(
+        &database,
+        "weekly",
+        &["資料作成 担当：田中 2024-05-10", "予算確認 担当：佐藤"],
+    )
+    .await;
+    create_meeting(&database, "kickoff", &["環境構築 担当：田中 2024-05-09", "議事録共有"]).await;
+
+    let open = action_items::list_action_items(&database, &ActionItemQuery::default()).await.unwrap();
+    assert_eq!(open.len(), 4);
+    // 期限の近い順、期限なしは最後
+    assert_eq!(open[0].text, "環境構築 担当：田中 2024-05-09");
+    assert!(open[3].due_date.is_none());
+
+    let groups = action_items::group_by_assignee(open);
+    let names: Vec<_> = groups.iter().map(|g| g.assignee.clone()).collect();
+    assert_eq!(names, vec![Some("佐藤".to_string()), Some("田中".to_string()), None]);
+    assert_eq!(groups[1].items.len(), 2);
+
+    let updated = database
+        .set_action_item_statuses(
+            &[
+                ActionItemRef { summary_id: weekly.id.clone(), item_index: 0 },
+                ActionItemRef { summary_id: weekly.id.clone(), item_index: 1 },
+                // 存在しない項目は無視される
+                ActionItemRef { summary_id: weekly.id.clone(), item_index: 9 },
+            ],
+            ActionItemStatus::Done,
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated, 2);
+
+    let tanaka = ActionItemQuery {
+        status: None,
+        assignee: Some("田中".to_string()),
+    };
+    let remaining = action_items::list_action_items(&database, &tanaka).await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].recording_title.as_deref(), Some("kickoff"));
+
+    let done = ActionItemQuery {
+        status: Some(ActionItemStatus::Done),
+        assignee: None,
+    };
+    let done_items = action_items::list_action_items(&database, &done).await.unwrap();
+    assert_eq!(done_items.len(), 2);
+    assert!(done_items.iter().all(|item| item.status_updated_at.is_some()));
+}
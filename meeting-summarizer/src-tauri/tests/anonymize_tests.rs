@@ -0,0 +1,77 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{MeetingNote, Recording, Summary, Transcription, TranscriptSegment};
+use meeting_summarizer_lib::services::anonymize::{self, participant_label, AnonymizeOptions, Anonymizer};
+
+#[test]
+fn test_participant_labels() {
+    assert_eq!(participant_label(0), "Participant A");
+    assert_eq!(participant_label(25), "Participant Z");
+    assert_eq!(participant_label(26), "Participant AA");
+    assert_eq!(participant_label(27), "Participant AB");
+}
+
+#[test]
+fn test_longest_name_wins_and_words_are_respected() {
+    let mut anonymizer = Anonymizer::new();
+    assert_eq!(anonymizer.add_participant("SPEAKER_1").as_deref(), Some("Participant A"));
+    assert_eq!(anonymizer.add_participant("SPEAKER_10").as_deref(), Some("Participant B"));
+    assert_eq!(anonymizer.add_participant("Al").as_deref(), Some("Participant C"));
+    assert_eq!(anonymizer.add_participant(" SPEAKER_1 ").as_deref(), Some("Participant A"));
+    assert_eq!(anonymizer.add_participant("李"), None);
+
+    assert_eq!(
+        anonymizer.anonymize("SPEAKER_10: Also ask Al. SPEAKER_1 agreed with 李さん"),
+        "Participant B: Also ask Participant C. Participant A agreed with 李さん"
+    );
+}
+
+#[tokio::test]
+async fn test_anonymized_export_uses_same_labels_for_transcript_and_summary() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("meeting.wav".to_string(), "/tmp/meeting.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let transcription = Transcription::new(
+        recording.id.clone(),
+        "山田: 来週リリースします。佐藤: マニュアルを用意します。".to_string(),
+        "ja".to_string(),
+    );
+    database.create_transcription(&transcription).await.unwrap();
+    let segments: Vec<TranscriptSegment> = [(0, 1_000, "山田", "来週リリースします。"), (1_000, 2_000, "佐藤", "マニュアルを用意します。")]
+        .iter()
+        .enumerate()
+        .map(|(index, (start, end, speaker, text))| {
+            let mut segment = TranscriptSegment::new(transcription.id.clone(), index as i32, *start, *end, text.to_string());
+            segment.speaker = Some(speaker.to_string());
+            segment
+        })
+        .collect();
+    database.create_transcript_segments(&segments).await.unwrap();
+    let note = MeetingNote::new(recording.id.clone(), "日程を確認".to_string()).with_author(Some("Suzuki".to_string()));
+    database.create_meeting_note(&note).await.unwrap();
+
+    let summary = Summary::new(transcription.id.clone(), "llama3".to_string()).with_content(
+        "山田さんが日程を説明した。".to_string(),
+        vec!["佐藤さんがマニュアルを担当".to_string()],
+        vec!["山田: テスト結果を共有する".to_string()],
+    );
+    database.create_summary(&summary).await.unwrap();
+
+    let export = anonymize::export_recording_anonymized(&database, None, &recording.id, "text", &AnonymizeOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(export.participant_count, 3);
+    assert!(export.content.contains("Participant A: 来週リリースします。Participant B: マニュアルを用意します。"));
+    assert!(!export.content.contains("山田") && !export.content.contains("佐藤"));
+    assert_eq!(export.summaries.len(), 1);
+    assert_eq!(export.summaries[0].summary_text, "Participant Aさんが日程を説明した。");
+    assert_eq!(export.summaries[0].action_items, vec!["Participant A: テスト結果を共有する".to_string()]);
+
+    // 要約単体でも同じラベルになり、元データは変更しない
+    let anonymized = anonymize::anonymize_summary(&database, None, &summary.id, &AnonymizeOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(anonymized.key_points, vec!["Participant Bさんがマニュアルを担当".to_string()]);
+    let stored = database.get_summary(&summary.id).await.unwrap().unwrap();
+    assert_eq!(stored.summary_text, summary.summary_text);
+}
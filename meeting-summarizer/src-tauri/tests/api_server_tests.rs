@@ -0,0 +1,190 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, Summary, Transcription};
+use meeting_summarizer_lib::services::control_server::CONTROL_TOKEN_HEADER;
+use meeting_summarizer_lib::services::{summary_review, ApiServer, ApiServerState, LLMModelManager, ModelSettingsManager, OllamaPool, RecordingService, Summarizer, WhisperService};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::sync::Mutex;
+
+const TOKEN: &str = "test-token";
+
+fn summarizer(database: &Arc<Database>) -> Arc<Summarizer> {
+    // 設定ファイルは読み書きしない（既定の設定で要約する）
+    Arc::new(Summarizer::new(
+        database.clone(),
+        Arc::new(Mutex::new(ModelSettingsManager::new(PathBuf::from("model_settings.json")))),
+        Arc::new(Mutex::new(LLMModelManager::new())),
+        Arc::new(OllamaPool::new()),
+    ))
+}
+
+#[tokio::test]
+async fn test_api_server_serves_recordings() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("api_test.db");
+    let recordings_dir = temp_dir.path().join("recordings");
+
+    let database = Database::new(&db_path).unwrap();
+    let recording = Recording::new("api.wav".to_string(), "/tmp/api.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let database = Arc::new(database);
+    let state = ApiServerState {
+        db: database.clone(),
+        recording_service: Arc::new(
+            RecordingService::new(Arc::new(Database::new(&db_path).unwrap()), recordings_dir.clone()).unwrap(),
+        ),
+        whisper_service: Arc::new(WhisperService::new(temp_dir.path().join("model.bin"), recordings_dir)),
+        summarizer: summarizer(&database),
+        token: TOKEN.to_string(),
+    };
+
+    let mut server = ApiServer::new();
+    let address = server.start(state, 0).await.unwrap();
+    assert!(server.get_status().running);
+
+    let client = reqwest::Client::new();
+
+    // トークンが無い・違う要求は拒否される
+    let response = reqwest::get(format!("http://{}/api/recordings", address)).await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    let response = reqwest::get(format!("http://{}/api/recordings?token=wrong", address)).await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    // 一覧取得
+    let recordings: Vec<Recording> = client
+        .get(format!("http://{}/api/recordings", address))
+        .header(CONTROL_TOKEN_HEADER, TOKEN)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(recordings.len(), 1);
+    assert_eq!(recordings[0].id, recording.id);
+
+    // 存在しないIDは404
+    let response = client
+        .get(format!("http://{}/api/recordings/missing", address))
+        .header(CONTROL_TOKEN_HEADER, TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    // URL に残るクエリのトークンは受け付けない
+    let response = reqwest::get(format!("http://{}/api/recordings?token={}", address, TOKEN)).await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    server.stop().await.unwrap();
+    assert!(!server.get_status().running);
+}
+
+#[tokio::test]
+async fn test_grpc_server_lists_recordings() {
+    use meeting_summarizer_lib::services::grpc_server::proto::meeting_summarizer_client::MeetingSummarizerClient;
+    use meeting_summarizer_lib::services::grpc_server::proto::ListRecordingsRequest;
+    use meeting_summarizer_lib::services::GrpcServer;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("grpc_test.db");
+    let recordings_dir = temp_dir.path().join("recordings");
+
+    let database = Database::new(&db_path).unwrap();
+    let recording = Recording::new("grpc.wav".to_string(), "/tmp/grpc.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let database = Arc::new(database);
+    let state = ApiServerState {
+        db: database.clone(),
+        recording_service: Arc::new(
+            RecordingService::new(Arc::new(Database::new(&db_path).unwrap()), recordings_dir.clone()).unwrap(),
+        ),
+        whisper_service: Arc::new(WhisperService::new(temp_dir.path().join("model.bin"), recordings_dir)),
+        summarizer: summarizer(&database),
+        token: TOKEN.to_string(),
+    };
+
+    let mut server = GrpcServer::new();
+    let address = server.start(state, "127.0.0.1", 0).await.unwrap();
+
+    let mut client = MeetingSummarizerClient::connect(format!("http://{}", address)).await.unwrap();
+
+    // トークンが無い呼び出しは拒否される
+    let status = client.list_recordings(ListRecordingsRequest {}).await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::Unauthenticated);
+
+    let mut request = tonic::Request::new(ListRecordingsRequest {});
+    request.metadata_mut().insert(CONTROL_TOKEN_HEADER, TOKEN.parse().unwrap());
+    let response = client.list_recordings(request).await.unwrap().into_inner();
+    assert_eq!(response.recordings.len(), 1);
+    assert_eq!(response.recordings[0].id, recording.id);
+
+    server.stop().await.unwrap();
+    assert!(!server.get_status().running);
+}
+
+#[tokio::test]
+async fn test_grpc_server_refuses_network_bind_without_token() {
+    use meeting_summarizer_lib::services::GrpcServer;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let recordings_dir = temp_dir.path().join("recordings");
+    let database = Arc::new(Database::new(&temp_dir.path().join("grpc_bind_test.db")).unwrap());
+    let state = ApiServerState {
+        db: database.clone(),
+        recording_service: Arc::new(RecordingService::new(database.clone(), recordings_dir.clone()).unwrap()),
+        whisper_service: Arc::new(WhisperService::new(temp_dir.path().join("model.bin"), recordings_dir)),
+        summarizer: summarizer(&database),
+        token: String::new(),
+    };
+
+    let mut server = GrpcServer::new();
+    assert!(server.start(state, "0.0.0.0", 0).await.is_err());
+    assert!(!server.get_status().running);
+}
+
+#[tokio::test]
+async fn test_api_server_refuses_to_regenerate_locked_summary() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("api_lock_test.db");
+    let recordings_dir = temp_dir.path().join("recordings");
+
+    let database = Arc::new(Database::new(&db_path).unwrap());
+    let recording = Recording::new("lock.wav".to_string(), "/tmp/lock.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+    let summary = Summary::new(transcription.id.clone(), "llama3".to_string()).with_content(
+        "承認済みの要約".to_string(),
+        vec![],
+        vec![],
+    );
+    database.create_summary(&summary).await.unwrap();
+    summary_review::approve_summary(&database, &summary.id, None, true).await.unwrap();
+
+    let state = ApiServerState {
+        db: database.clone(),
+        recording_service: Arc::new(RecordingService::new(database.clone(), recordings_dir.clone()).unwrap()),
+        whisper_service: Arc::new(WhisperService::new(temp_dir.path().join("model.bin"), recordings_dir)),
+        summarizer: summarizer(&database),
+        token: TOKEN.to_string(),
+    };
+
+    let mut server = ApiServer::new();
+    let address = server.start(state, 0).await.unwrap();
+
+    // LLM を呼ぶ前に固定済みとして拒否される
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/api/transcriptions/{}/summarize", address, transcription.id))
+        .header(CONTROL_TOKEN_HEADER, TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+    assert_eq!(database.get_summaries_by_transcription(&transcription.id).await.unwrap().len(), 1);
+
+    server.stop().await.unwrap();
+}
@@ -0,0 +1,56 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, RecordingQuery};
+
+async fn create_recordings(database: &Database, names: &[&str]) -> Vec<String> {
+    let mut ids = Vec::new();
+    for name in names {
+        let mut recording = Recording::new(format!("{}.wav", name), format!("/tmp/archive_{}.wav", name));
+        recording.duration = Some(60);
+        recording.category = Some("Weekly".to_string());
+        database.create_recording(&recording).await.unwrap();
+        ids.push(recording.id);
+    }
+    ids
+}
+
+#[tokio::test]
+async fn test_archived_recordings_are_hidden_by_default() {
+    let database = Database::in_memory().unwrap();
+    let ids = create_recordings(&database, &["a", "b", "c"]).await;
+
+    assert_eq!(database.set_archived(&ids[..2], true).await.unwrap(), 2);
+    // 既にアーカイブ済みのものは件数に含めない
+    assert_eq!(database.set_archived(&ids[..1], true).await.unwrap(), 0);
+
+    let visible = database.get_recordings(false).await.unwrap();
+    assert_eq!(visible.len(), 1);
+    assert_eq!(visible[0].id, ids[2]);
+    assert_eq!(database.get_recordings(true).await.unwrap().len(), 3);
+
+    let searched = database.search_recordings(&RecordingQuery::default()).await.unwrap();
+    assert_eq!(searched.len(), 1);
+    let all = database
+        .search_recordings(&RecordingQuery { include_archived: true, ..Default::default() })
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 3);
+    assert_eq!(all.iter().filter(|recording| recording.archived).count(), 2);
+}
+
+#[tokio::test]
+async fn test_stats_exclude_archived_recordings() {
+    let database = Database::in_memory().unwrap();
+    let ids = create_recordings(&database, &["a", "b"]).await;
+
+    assert_eq!(database.get_recording_stats().await.unwrap().total_count, 2);
+
+    database.set_archived(&ids[..1], true).await.unwrap();
+    let stats = database.get_recording_stats().await.unwrap();
+    assert_eq!(stats.total_count, 1);
+    assert_eq!(stats.total_duration, 60);
+    assert_eq!(stats.recent_count, 1);
+    assert_eq!(stats.categories[0].count, 1);
+
+    database.set_archived(&ids, false).await.unwrap();
+    assert_eq!(database.get_recording_stats().await.unwrap().total_count, 2);
+}
@@ -0,0 +1,76 @@
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use meeting_summarizer_lib::services::audio_convert::{
+    convert_for_whisper, is_whisper_ready, prepare_for_whisper, WHISPER_SAMPLE_RATE,
+};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn write_sine_wav(path: &Path, sample_rate: u32, channels: u16, seconds: f32) {
+    let spec = WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec).unwrap();
+    let frames = (sample_rate as f32 * seconds) as usize;
+    for i in 0..frames {
+        let value = (i as f32 * 440.0 * 2.0 * std::f32::consts::PI / sample_rate as f32).sin() * 0.5;
+        for _ in 0..channels {
+            writer.write_sample((value * i16::MAX as f32) as i16).unwrap();
+        }
+    }
+    writer.finalize().unwrap();
+}
+
+#[test]
+fn test_convert_stereo_44k_to_16k_mono() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let input = temp_dir.path().join("input.wav");
+    let output = temp_dir.path().join("converted").join("output.wav");
+    write_sine_wav(&input, 44_100, 2, 2.0);
+
+    assert!(!is_whisper_ready(&input));
+    let converted = convert_for_whisper(&input, &output).unwrap();
+
+    assert_eq!(converted.source_sample_rate, 44_100);
+    assert_eq!(converted.source_channels, 2);
+    assert!((converted.duration_secs - 2.0).abs() < 0.01, "duration {}", converted.duration_secs);
+
+    let reader = WavReader::open(&output).unwrap();
+    let spec = reader.spec();
+    assert_eq!(spec.sample_rate, WHISPER_SAMPLE_RATE);
+    assert_eq!(spec.channels, 1);
+    assert_eq!(spec.bits_per_sample, 16);
+    assert_eq!(reader.duration(), 32_000);
+
+    // 信号が無音になっていないこと
+    let peak = reader
+        .into_samples::<i16>()
+        .map(|s| s.unwrap().unsigned_abs())
+        .max()
+        .unwrap();
+    assert!(peak > (i16::MAX as u16) / 4, "peak {}", peak);
+    assert!(is_whisper_ready(&output));
+}
+
+#[test]
+fn test_prepare_skips_already_converted_audio() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let input = temp_dir.path().join("ready.wav");
+    write_sine_wav(&input, WHISPER_SAMPLE_RATE, 1, 0.5);
+
+    let prepared = prepare_for_whisper(&input, &temp_dir.path().join("work"), "rec").unwrap();
+    assert_eq!(prepared, input);
+    assert!(!temp_dir.path().join("work").exists());
+}
+
+#[test]
+fn test_convert_rejects_non_audio_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let input = temp_dir.path().join("notes.mp3");
+    std::fs::write(&input, b"this is not audio").unwrap();
+
+    let result = convert_for_whisper(&input, &temp_dir.path().join("out.wav"));
+    assert!(result.is_err());
+}
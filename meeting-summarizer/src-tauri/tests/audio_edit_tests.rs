@@ -0,0 +1,120 @@
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, RecordingMarker, Transcription};
+use meeting_summarizer_lib::services::audio_edit::{edit_recording, kept_ranges, shifted_offset, AudioEdit};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// フレーム番号がそのまま値になる 1kHz の WAV（10秒）
+fn write_numbered(path: &Path, channels: u16) {
+    let spec = WavSpec {
+        channels,
+        sample_rate: 1_000,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec).unwrap();
+    for frame in 0..10_000 {
+        for _ in 0..channels {
+            writer.write_sample(frame as i16).unwrap();
+        }
+    }
+    writer.finalize().unwrap();
+}
+
+fn frames(path: &Path) -> Vec<i16> {
+    let mut reader = WavReader::open(path).unwrap();
+    let channels = reader.spec().channels as usize;
+    let samples: Vec<i16> = reader.samples::<i16>().map(|sample| sample.unwrap()).collect();
+    samples.chunks(channels).map(|frame| frame[0]).collect()
+}
+
+#[test]
+fn test_ranges_and_offsets() {
+    assert_eq!(kept_ranges(AudioEdit::Trim, 1_000, 4_000, 10_000).unwrap(), vec![(1_000, 4_000)]);
+    assert_eq!(
+        kept_ranges(AudioEdit::DeleteRange, 1_000, 4_000, 10_000).unwrap(),
+        vec![(0, 1_000), (4_000, 10_000)]
+    );
+    assert_eq!(kept_ranges(AudioEdit::DeleteRange, 0, 4_000, 10_000).unwrap(), vec![(4_000, 10_000)]);
+    assert!(kept_ranges(AudioEdit::DeleteRange, 0, 10_000, 10_000).is_err());
+    assert!(kept_ranges(AudioEdit::Trim, 4_000, 1_000, 10_000).is_err());
+    assert!(kept_ranges(AudioEdit::Trim, 1_000, 12_000, 10_000).is_err());
+
+    assert_eq!(shifted_offset(AudioEdit::Trim, 1_000, 4_000, 2_500), Some(1_500));
+    assert_eq!(shifted_offset(AudioEdit::Trim, 1_000, 4_000, 5_000), None);
+    assert_eq!(shifted_offset(AudioEdit::DeleteRange, 1_000, 4_000, 500), Some(500));
+    assert_eq!(shifted_offset(AudioEdit::DeleteRange, 1_000, 4_000, 2_000), None);
+    assert_eq!(shifted_offset(AudioEdit::DeleteRange, 1_000, 4_000, 6_000), Some(3_000));
+}
+
+#[tokio::test]
+async fn test_trim_keeps_backup_and_updates_recording() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("recording.wav");
+    write_numbered(&path, 1);
+
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("recording.wav".to_string(), path.to_string_lossy().to_string()).with_duration(10);
+    database.create_recording(&recording).await.unwrap();
+    let inside = RecordingMarker::new(recording.id.clone(), 3_000, Some("決定".to_string()));
+    let outside = RecordingMarker::new(recording.id.clone(), 8_000, None);
+    database.create_marker(&inside).await.unwrap();
+    database.create_marker(&outside).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+
+    let edited = edit_recording(&database, temp_dir.path(), &recording.id, AudioEdit::Trim, 2_000, 6_000, false)
+        .await
+        .unwrap();
+    assert_eq!(edited.duration_ms, 4_000);
+    assert_eq!(edited.removed_transcriptions, 0);
+    let trimmed = frames(&path);
+    assert_eq!((trimmed.len(), trimmed[0]), (4_000, 2_000));
+
+    let stored = database.get_recording(&recording.id).await.unwrap().unwrap();
+    assert_eq!(stored.duration, Some(4));
+    assert_eq!(stored.file_size, Some(std::fs::metadata(&path).unwrap().len() as i64));
+
+    let markers = database.get_markers(&recording.id).await.unwrap();
+    assert_eq!(markers.len(), 1);
+    assert_eq!((markers[0].id.clone(), markers[0].offset_ms), (inside.id.clone(), 1_000));
+
+    // 2回目の編集でも、最初の録音が残る
+    let edited = edit_recording(&database, temp_dir.path(), &recording.id, AudioEdit::DeleteRange, 0, 1_000, true)
+        .await
+        .unwrap();
+    assert_eq!(edited.removed_transcriptions, 1);
+    assert_eq!(frames(&path)[0], 3_000);
+    assert_eq!(frames(Path::new(&edited.backup_path)).len(), 10_000);
+    assert!(database.get_transcriptions_by_recording(&recording.id).await.unwrap().is_empty());
+    // 削除した範囲の直後のマーカーは先頭へ寄る
+    assert_eq!(database.get_markers(&recording.id).await.unwrap()[0].offset_ms, 0);
+}
+
+#[tokio::test]
+async fn test_delete_range_joins_stereo_audio() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("stereo.wav");
+    write_numbered(&path, 2);
+
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("stereo.wav".to_string(), path.to_string_lossy().to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let edited = edit_recording(&database, temp_dir.path(), &recording.id, AudioEdit::DeleteRange, 1_000, 9_000, false)
+        .await
+        .unwrap();
+    assert_eq!(edited.duration_ms, 2_000);
+    let joined = frames(&path);
+    assert_eq!((joined[999], joined[1_000]), (999, 9_000));
+
+    // 範囲外や存在しない録音は書き換えない
+    assert!(edit_recording(&database, temp_dir.path(), &recording.id, AudioEdit::Trim, 0, 5_000, false)
+        .await
+        .is_err());
+    assert!(edit_recording(&database, temp_dir.path(), "missing", AudioEdit::Trim, 0, 1_000, false)
+        .await
+        .is_err());
+    assert_eq!(frames(&path).len(), 2_000);
+}
@@ -0,0 +1,84 @@
+use hound::{SampleFormat, WavSpec, WavWriter};
+use meeting_summarizer_lib::models::Recording;
+use meeting_summarizer_lib::services::audio_convert::transcode;
+use meeting_summarizer_lib::services::audio_probe::{fill_recording, probe, probe_files, AudioInfo};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn write_wav(path: &Path, sample_rate: u32, channels: u16, seconds: f32) {
+    let spec = WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec).unwrap();
+    let frames = (sample_rate as f32 * seconds) as usize;
+    for index in 0..frames {
+        let sample = ((index as f32 * 0.05).sin() * 8_000.0) as i16;
+        for _ in 0..channels {
+            writer.write_sample(sample).unwrap();
+        }
+    }
+    writer.finalize().unwrap();
+}
+
+#[test]
+fn test_probe_wav_header() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("recording.wav");
+    write_wav(&path, 48_000, 2, 1.5);
+
+    let info = probe(&path).unwrap();
+    assert_eq!(
+        info,
+        AudioInfo {
+            duration_ms: 1_500,
+            sample_rate: 48_000,
+            channels: 2,
+        }
+    );
+    assert_eq!(info.duration_secs(), 2);
+}
+
+#[test]
+fn test_probe_other_containers() {
+    let temp_dir = TempDir::new().unwrap();
+    let wav = temp_dir.path().join("source.wav");
+    write_wav(&wav, 44_100, 1, 2.0);
+    let flac = temp_dir.path().join("imported.flac");
+    transcode(&wav, &flac, "flac").unwrap();
+
+    let info = probe(&flac).unwrap();
+    assert_eq!(info.sample_rate, 44_100);
+    assert_eq!(info.channels, 1);
+    assert!((info.duration_ms - 2_000).abs() <= 50, "{:?}", info);
+
+    let text = temp_dir.path().join("notes.txt");
+    std::fs::write(&text, "not audio").unwrap();
+    assert!(probe(&text).is_err());
+}
+
+#[test]
+fn test_fill_recording_from_segments() {
+    let temp_dir = TempDir::new().unwrap();
+    let first = temp_dir.path().join("recording.wav");
+    let second = temp_dir.path().join("recording_part001.wav");
+    write_wav(&first, 16_000, 1, 2.0);
+    write_wav(&second, 16_000, 1, 1.25);
+    let paths = vec![first.clone(), second];
+
+    assert_eq!(probe_files(&paths).unwrap().duration_ms, 3_250);
+
+    let mut recording = Recording::new("recording.wav".to_string(), first.to_string_lossy().to_string());
+    fill_recording(&mut recording, &paths);
+    assert_eq!(recording.duration, Some(3));
+    assert_eq!(recording.sample_rate, Some(16_000));
+    assert_eq!(recording.channels, Some(1));
+
+    // 読み取れないファイルなら元の値を残す
+    let mut recording = Recording::new("missing.wav".to_string(), String::new()).with_duration(42);
+    fill_recording(&mut recording, &[temp_dir.path().join("missing.wav")]);
+    assert_eq!(recording.duration, Some(42));
+    assert_eq!(recording.sample_rate, None);
+}
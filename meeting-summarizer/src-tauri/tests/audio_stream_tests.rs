@@ -0,0 +1,41 @@
+use hound::{SampleFormat, WavSpec, WavWriter};
+use meeting_summarizer_lib::services::audio_stream::{waveform_peaks, AudioChunkReader};
+use tempfile::TempDir;
+
+#[test]
+fn test_chunk_reader_mixes_stereo_to_mono() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("stereo.wav");
+
+    let spec = WavSpec {
+        channels: 2,
+        sample_rate: 16000,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(&path, spec).unwrap();
+    for i in 0..16000 {
+        // 左: 無音、右: 後半のみ最大振幅
+        writer.write_sample(0i16).unwrap();
+        writer.write_sample(if i >= 8000 { i16::MAX } else { 0 }).unwrap();
+    }
+    writer.finalize().unwrap();
+
+    let mut reader = AudioChunkReader::open(&path).unwrap();
+    assert_eq!(reader.total_frames(), 16000);
+    assert!((reader.duration_secs() - 1.0).abs() < f64::EPSILON);
+
+    let mut chunks = 0;
+    let mut frames = 0;
+    while let Some(chunk) = reader.next_chunk(4096).unwrap() {
+        assert!(chunk.len() <= 4096);
+        chunks += 1;
+        frames += chunk.len();
+    }
+    assert_eq!(frames, 16000);
+    assert_eq!(chunks, 4);
+
+    let peaks = waveform_peaks(&path, 2).unwrap();
+    assert_eq!(peaks[0], 0.0);
+    assert!((peaks[1] - 0.5).abs() < 0.01);
+}
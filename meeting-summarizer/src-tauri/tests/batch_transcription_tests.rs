@@ -0,0 +1,78 @@
+use meeting_summarizer_lib::errors::AppError;
+use meeting_summarizer_lib::services::batch_transcription::{
+    default_worker_count, resolve_worker_count, run_batch, MAX_BATCH_WORKERS,
+};
+use meeting_summarizer_lib::services::{BatchItemProgress, BatchItemStatus};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_run_batch_limits_concurrency_and_reports_progress() {
+    let active = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let events: Arc<Mutex<Vec<BatchItemProgress>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let ids: Vec<String> = (0..6).map(|i| format!("rec-{}", i)).collect();
+
+    let task = {
+        let active = active.clone();
+        let peak = peak.clone();
+        move |recording_id: String| {
+            let active = active.clone();
+            let peak = peak.clone();
+            async move {
+                let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(30)).await;
+                active.fetch_sub(1, Ordering::SeqCst);
+
+                if recording_id == "rec-3" {
+                    Err(AppError::TranscriptionFailed {
+                        message: "boom".to_string(),
+                    })
+                } else {
+                    Ok(format!("tx-{}", recording_id))
+                }
+            }
+        }
+    };
+
+    let sink = events.clone();
+    let result = run_batch(ids.clone(), 2, task, move |progress| {
+        sink.lock().unwrap().push(progress.clone());
+    })
+    .await;
+
+    assert_eq!(result.workers, 2);
+    assert!(peak.load(Ordering::SeqCst) <= 2);
+    assert_eq!(result.succeeded, 5);
+    assert_eq!(result.failed, 1);
+
+    // 結果は入力順
+    let result_ids: Vec<&str> = result.items.iter().map(|item| item.recording_id.as_str()).collect();
+    assert_eq!(result_ids, ids.iter().map(String::as_str).collect::<Vec<_>>());
+    assert_eq!(result.items[0].transcription_id.as_deref(), Some("tx-rec-0"));
+    assert_eq!(result.items[3].status, BatchItemStatus::Failed);
+    assert_eq!(result.items[3].error.as_deref(), Some("Transcription failed: boom"));
+
+    let events = events.lock().unwrap();
+    for status in [BatchItemStatus::Queued, BatchItemStatus::Running] {
+        assert_eq!(events.iter().filter(|e| e.status == status).count(), 6);
+    }
+    let last_finished = events.iter().map(|e| e.finished).max().unwrap();
+    assert_eq!(last_finished, 6);
+    assert!(events.iter().all(|e| e.total == 6 && e.batch_id == result.batch_id));
+}
+
+#[test]
+fn test_worker_count_resolution() {
+    assert!(default_worker_count("large") >= 1);
+    assert!(default_worker_count("tiny") >= default_worker_count("large"));
+    assert!(default_worker_count("tiny") <= MAX_BATCH_WORKERS);
+
+    assert_eq!(resolve_worker_count(Some(3), "large"), 3);
+    assert_eq!(resolve_worker_count(Some(100), "tiny"), MAX_BATCH_WORKERS);
+    assert_eq!(resolve_worker_count(Some(0), "medium"), default_worker_count("medium"));
+    assert_eq!(resolve_worker_count(None, "base"), default_worker_count("base"));
+}
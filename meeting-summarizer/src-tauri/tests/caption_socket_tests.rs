@@ -0,0 +1,28 @@
+use meeting_summarizer_lib::services::{CaptionSocketServer, LiveCaptionHub};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_caption_socket_serves_overlay_on_loopback() {
+    let captions = Arc::new(LiveCaptionHub::new());
+    let mut server = CaptionSocketServer::new();
+    assert!(!server.get_status().running);
+
+    let address = server.start(captions.clone(), 0).await.unwrap();
+    assert!(address.ip().is_loopback());
+
+    let status = server.get_status();
+    assert!(status.running);
+    assert_eq!(status.websocket_url, Some(format!("ws://{}/ws", address)));
+
+    // 起動中に再度起動はできない
+    assert!(server.start(captions, 0).await.is_err());
+
+    let page = reqwest::get(status.overlay_url.unwrap()).await.unwrap();
+    assert!(page.status().is_success());
+    assert!(page.text().await.unwrap().contains("/ws"));
+
+    server.stop().await.unwrap();
+    let status = server.get_status();
+    assert!(!status.running);
+    assert!(status.websocket_url.is_none());
+}
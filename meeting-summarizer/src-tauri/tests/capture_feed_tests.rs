@@ -0,0 +1,76 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::services::capture_feed::{CaptureFeed, FEED_SAMPLE_RATE};
+use meeting_summarizer_lib::services::{demo_mode, RecordingService};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn seconds(secs: f32) -> Vec<f32> {
+    vec![0.1; (FEED_SAMPLE_RATE as f32 * secs) as usize]
+}
+
+#[test]
+fn test_chunks_are_emitted_at_the_configured_length() {
+    let feed = CaptureFeed::new();
+    let mut chunks = feed.subscribe();
+    feed.set_chunk_secs(5).unwrap();
+
+    // 録音スレッドからは 100ms ずつ届く
+    for _ in 0..120 {
+        feed.push(&seconds(0.1));
+    }
+    let first = chunks.try_recv().unwrap();
+    let second = chunks.try_recv().unwrap();
+    assert!(chunks.try_recv().is_err());
+    assert_eq!((first.index, first.start_ms, first.end_ms()), (0, 0, 5_000));
+    assert_eq!((second.index, second.start_ms, second.end_ms()), (1, 5_000, 10_000));
+    assert!(!second.is_last);
+
+    feed.flush();
+    let last = chunks.try_recv().unwrap();
+    assert_eq!((last.index, last.start_ms, last.end_ms()), (2, 10_000, 12_000));
+    assert!(last.is_last);
+}
+
+#[test]
+fn test_chunk_length_is_limited() {
+    let feed = CaptureFeed::new();
+    assert!(feed.set_chunk_secs(4).is_err());
+    assert!(feed.set_chunk_secs(11).is_err());
+    feed.set_chunk_secs(10).unwrap();
+    assert_eq!(feed.chunk_secs(), 10);
+}
+
+#[test]
+fn test_reset_starts_a_new_recording() {
+    let feed = CaptureFeed::new();
+    let mut chunks = feed.subscribe();
+    feed.push(&seconds(6.0));
+    feed.reset();
+    feed.push(&seconds(1.0));
+    feed.flush();
+
+    assert_eq!(chunks.try_recv().unwrap().index, 0);
+    let after_reset = chunks.try_recv().unwrap();
+    assert_eq!((after_reset.index, after_reset.start_ms, after_reset.end_ms()), (0, 0, 1_000));
+}
+
+#[tokio::test]
+async fn test_recording_feeds_chunks_while_in_progress() {
+    demo_mode::set_enabled(true);
+    let temp_dir = TempDir::new().unwrap();
+    let database = Arc::new(Database::new(temp_dir.path().join("feed.db")).unwrap());
+    let recording_service = RecordingService::new(database, temp_dir.path().join("recordings")).unwrap();
+    let mut chunks = recording_service.subscribe_audio_chunks();
+
+    recording_service.start_recording().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(1_000)).await;
+    recording_service.stop_recording().await.unwrap();
+
+    // 5秒に満たない録音は停止時に1つのチャンクで届く
+    let chunk = chunks.try_recv().unwrap();
+    assert_eq!((chunk.index, chunk.start_ms), (0, 0));
+    assert!(chunk.is_last);
+    assert!(chunk.duration_ms() >= 500);
+    assert!(chunk.samples.iter().any(|sample| *sample != 0.0));
+}
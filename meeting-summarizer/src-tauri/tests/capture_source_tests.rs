@@ -0,0 +1,213 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::services::capture_source::{
+    capturable_applications, interleave_sources, mix_sources, parse_process_list, running_applications,
+    supported_applications, ProcessCapture,
+};
+use meeting_summarizer_lib::services::pipewire;
+use meeting_summarizer_lib::services::{demo_mode, CaptureSource, DualSourceMode, RecordingProfile, RecordingService};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+#[test]
+fn test_application_source_is_limited_to_meeting_apps() {
+    let zoom = CaptureSource::Application { bundle_id: "us.zoom.xos".to_string() };
+    assert!(zoom.validate().is_ok());
+    assert!(CaptureSource::Application { bundle_id: "com.apple.Music".to_string() }.validate().is_err());
+    assert!(supported_applications().iter().any(|app| app.name == "Zoom"));
+
+    // macOS・Windows 以外ではアプリ単位の録音は開始できない
+    assert_eq!(
+        zoom.ensure_supported().is_ok(),
+        cfg!(any(target_os = "macos", target_os = "windows"))
+    );
+    assert!(CaptureSource::Microphone.ensure_supported().is_ok());
+}
+
+#[test]
+fn test_running_applications_are_listed_first() {
+    // macOS の `ps -axco comm=`
+    let macos = parse_process_list("launchd\nMSTeams\nFinder\n");
+    let applications = running_applications(&macos);
+    assert_eq!(applications[0].bundle_id, "com.microsoft.teams2");
+    assert!(applications[0].running);
+    assert!(applications[1..].iter().all(|app| !app.running));
+
+    // Windows の `tasklist /fo csv /nh`
+    let windows = parse_process_list(
+        "\"System Idle Process\",\"0\",\"Services\",\"0\",\"8 K\"\r\n\"Zoom.exe\",\"4242\",\"Console\",\"1\",\"120,000 K\"\r\n",
+    );
+    assert_eq!(windows, vec!["System Idle Process".to_string(), "Zoom.exe".to_string()]);
+    let applications = running_applications(&windows);
+    assert_eq!(applications[0].name, "Zoom");
+    assert!(applications[0].running);
+
+    assert!(supported_applications().iter().all(|app| !app.running));
+}
+
+#[test]
+fn test_capturable_applications_are_empty_where_unsupported() {
+    if cfg!(any(target_os = "macos", target_os = "windows")) || pipewire::is_available() {
+        return;
+    }
+    assert!(capturable_applications().unwrap().is_empty());
+}
+
+#[test]
+fn test_running_applications_record_the_application() {
+    let applications = running_applications(&["zoom.us".to_string()]);
+    assert_eq!(
+        applications[0].capture_source,
+        CaptureSource::Application { bundle_id: "us.zoom.xos".to_string() }
+    );
+}
+
+/// アプリに同梱する Windows のヘルパーのソース
+const WINDOWS_APPLICATION_CAPTURE_SOURCE: &str = include_str!("../helpers/app-audio-capture/Program.cs");
+
+#[test]
+fn test_windows_helper_uses_verbatim_device_path() {
+    // C# の通常の文字列では `\P` が不正なエスケープになりコンパイルできない
+    assert!(WINDOWS_APPLICATION_CAPTURE_SOURCE.contains(r#"ActivateAudioInterfaceAsync(@"VAD\Process_Loopback""#));
+    assert!(!WINDOWS_APPLICATION_CAPTURE_SOURCE.contains(r#"("VAD\Process_Loopback""#));
+    assert!(WINDOWS_APPLICATION_CAPTURE_SOURCE.contains("static int Main(string[] args)"));
+}
+
+#[test]
+fn test_profile_defaults_to_microphone() {
+    let profile: RecordingProfile = serde_json::from_str("{}").unwrap();
+    assert_eq!(profile.source, CaptureSource::Microphone);
+
+    let json = serde_json::to_value(CaptureSource::Application { bundle_id: "us.zoom.xos".to_string() }).unwrap();
+    assert_eq!(json, serde_json::json!({ "type": "application", "bundle_id": "us.zoom.xos" }));
+}
+
+#[test]
+fn test_process_capture_reads_f32_samples() {
+    // 0.5 と -0.25 を f32 リトルエンディアンで出力する
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(r"printf '\000\000\000\077\000\000\200\276'");
+
+    let chunks = Arc::new(AtomicUsize::new(0));
+    let counter = chunks.clone();
+    let mut capture = ProcessCapture::spawn(command, move |chunk| {
+        counter.fetch_add(chunk.len(), Ordering::SeqCst);
+    })
+    .unwrap();
+    while !capture.has_exited() {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert_eq!(capture.finish().unwrap(), vec![0.5, -0.25]);
+    assert_eq!(chunks.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_process_capture_without_audio_reports_error() {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg("echo 'Application is not running' >&2; exit 2");
+
+    let mut capture = ProcessCapture::spawn(command, |_| {}).unwrap();
+    while !capture.has_exited() {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    let error = capture.finish().unwrap_err().to_string();
+    assert!(error.contains("Application is not running"));
+}
+
+#[test]
+fn test_process_capture_keeps_draining_stderr() {
+    // パイプの容量を超えるエラー出力の後でも音声を読み取れる（読まないとプロセスが書き込みで止まる）
+    let mut command = Command::new("sh");
+    command
+        .arg("-c")
+        .arg(r"i=0; while [ $i -lt 5000 ]; do echo 'capture progress message' >&2; i=$((i+1)); done; printf '\000\000\000\077'");
+
+    let mut capture = ProcessCapture::spawn(command, |_| {}).unwrap();
+    let started = std::time::Instant::now();
+    while !capture.has_exited() {
+        assert!(started.elapsed() < std::time::Duration::from_secs(10), "capture process is blocked on stderr");
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert_eq!(capture.finish().unwrap(), vec![0.5]);
+}
+
+#[tokio::test]
+async fn test_source_cannot_change_while_recording() {
+    demo_mode::set_enabled(true);
+
+    let temp_dir = TempDir::new().unwrap();
+    let database = Arc::new(Database::new(temp_dir.path().join("source.db")).unwrap());
+    let recording_service = RecordingService::new(database, temp_dir.path().join("recordings")).unwrap();
+    let zoom = CaptureSource::Application { bundle_id: "us.zoom.xos".to_string() };
+
+    recording_service.set_capture_source(zoom.clone()).await.unwrap();
+    assert_eq!(recording_service.capture_source().await, zoom);
+    assert!(recording_service
+        .set_capture_source(CaptureSource::Application { bundle_id: "unknown".to_string() })
+        .await
+        .is_err());
+
+    // デモモードでは入力元に関わらず録音できる
+    recording_service.start_recording().await.unwrap();
+    assert!(recording_service.set_capture_source(CaptureSource::Microphone).await.is_err());
+    recording_service.stop_recording().await.unwrap();
+    recording_service.set_capture_source(CaptureSource::Microphone).await.unwrap();
+}
+
+#[test]
+fn test_system_audio_uses_loopback_on_windows() {
+    let source: CaptureSource = serde_json::from_str(r#"{"type": "system_audio"}"#).unwrap();
+    assert_eq!(source, CaptureSource::SystemAudio);
+    assert!(source.validate().is_ok());
+    // Windows は WASAPI ループバック、Linux は PipeWire のモニターを録音する
+    assert_eq!(source.is_loopback(), cfg!(target_os = "windows"));
+    assert_eq!(source.process_command().unwrap().is_some(), cfg!(target_os = "linux"));
+    assert_eq!(
+        source.ensure_supported().is_ok(),
+        cfg!(any(target_os = "windows", target_os = "linux"))
+    );
+
+    assert!(!CaptureSource::Microphone.is_loopback());
+}
+
+#[test]
+fn test_dual_source_mix_and_channels() {
+    assert_eq!(mix_sources(&[0.5, -0.25, 0.75], &[0.25, 0.5]), vec![0.75, 0.25, 0.75]);
+    // 足して範囲を超えた分は切り詰める
+    assert_eq!(mix_sources(&[0.75], &[0.5]), vec![1.0]);
+    assert_eq!(interleave_sources(&[0.1, 0.2], &[0.3]), vec![0.1, 0.3, 0.2, 0.0]);
+
+    assert_eq!(DualSourceMode::Off.channels(1), 1);
+    assert_eq!(DualSourceMode::Mix.channels(2), 2);
+    assert_eq!(DualSourceMode::SeparateChannels.channels(1), 2);
+
+    let profile: RecordingProfile = serde_json::from_str(r#"{"dual_source": "separate_channels"}"#).unwrap();
+    assert_eq!(profile.source, CaptureSource::Microphone);
+    assert_eq!(profile.dual_source, DualSourceMode::SeparateChannels);
+    assert_eq!(RecordingProfile::default().dual_source, DualSourceMode::Off);
+}
+
+#[tokio::test]
+async fn test_dual_source_requires_microphone_source() {
+    let temp_dir = TempDir::new().unwrap();
+    let database = Arc::new(Database::new(temp_dir.path().join("dual.db")).unwrap());
+    let recording_service = RecordingService::new(database, temp_dir.path().join("recordings")).unwrap();
+
+    let system_audio = RecordingProfile {
+        source: CaptureSource::SystemAudio,
+        dual_source: DualSourceMode::Mix,
+        ..RecordingProfile::default()
+    };
+    assert!(recording_service.apply_recording_profile(&system_audio).await.is_err());
+    assert_eq!(recording_service.dual_source().await, DualSourceMode::Off);
+
+    let microphone = RecordingProfile {
+        source: CaptureSource::Microphone,
+        dual_source: DualSourceMode::SeparateChannels,
+        ..RecordingProfile::default()
+    };
+    recording_service.apply_recording_profile(&microphone).await.unwrap();
+    assert_eq!(recording_service.dual_source().await, DualSourceMode::SeparateChannels);
+}
@@ -0,0 +1,152 @@
+use hound::{SampleFormat, WavSpec, WavWriter};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::Recording;
+use meeting_summarizer_lib::services::audio_convert::split_channels_for_whisper;
+use meeting_summarizer_lib::services::channel_separation::{channel_turns, speaker_label, transcribe_by_channel, ChannelTurn};
+use meeting_summarizer_lib::services::voice_activity::SpeechRegion;
+use meeting_summarizer_lib::services::{demo_mode, WhisperService};
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn region(start_ms: i64, end_ms: i64) -> SpeechRegion {
+    SpeechRegion { start_ms, end_ms }
+}
+
+/// 各チャンネルの `(開始秒, 終了秒)` の間だけ音を鳴らしたステレオ WAV を書く
+fn write_call(path: &Path, sample_rate: u32, seconds: f32, talks: [(f32, f32); 2]) {
+    let spec = WavSpec {
+        channels: 2,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec).unwrap();
+    for index in 0..(sample_rate as f32 * seconds) as usize {
+        let time = index as f32 / sample_rate as f32;
+        let tone = (2.0 * std::f32::consts::PI * 300.0 * time).sin() * 0.3;
+        for (start, end) in talks {
+            let value = if (start..end).contains(&time) { tone } else { 0.0 };
+            writer.write_sample((value * i16::MAX as f32) as i16).unwrap();
+        }
+    }
+    writer.finalize().unwrap();
+}
+
+fn rms(path: &Path) -> f32 {
+    let mut reader = hound::WavReader::open(path).unwrap();
+    let samples: Vec<f32> = reader.samples::<i16>().map(|s| s.unwrap() as f32 / 32_768.0).collect();
+    (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+#[test]
+fn test_turns_are_interleaved_by_start_time() {
+    let turns = channel_turns(&[
+        vec![region(0, 1_000), region(1_500, 2_000), region(6_000, 7_000)],
+        vec![region(3_000, 5_000)],
+    ]);
+    assert_eq!(
+        turns,
+        vec![
+            // 同じチャンネルの短い間はまとめる
+            ChannelTurn { channel: 0, start_ms: 0, end_ms: 2_000 },
+            ChannelTurn { channel: 1, start_ms: 3_000, end_ms: 5_000 },
+            ChannelTurn { channel: 0, start_ms: 6_000, end_ms: 7_000 },
+        ]
+    );
+}
+
+#[test]
+fn test_turns_are_not_merged_across_the_other_party() {
+    let turns = channel_turns(&[vec![region(0, 1_000), region(1_400, 2_000)], vec![region(1_100, 1_300)]]);
+    assert_eq!(turns.len(), 3);
+    assert_eq!(turns[1].channel, 1);
+}
+
+#[test]
+fn test_speaker_labels_fall_back_to_numbers() {
+    let speakers = vec!["自分".to_string(), " ".to_string()];
+    assert_eq!(speaker_label(&speakers, 0), "自分");
+    assert_eq!(speaker_label(&speakers, 1), "Speaker 2");
+    assert_eq!(speaker_label(&speakers, 2), "Speaker 3");
+}
+
+#[test]
+fn test_split_channels_writes_16khz_mono_per_channel() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("call.wav");
+    write_call(&input, 48_000, 1.0, [(0.0, 1.0), (2.0, 3.0)]);
+
+    let channels = split_channels_for_whisper(&input, &dir.path().join("work"), "call").unwrap();
+    assert_eq!(channels.len(), 2);
+    for path in &channels {
+        let reader = hound::WavReader::open(path).unwrap();
+        assert_eq!(reader.spec().sample_rate, 16_000);
+        assert_eq!(reader.spec().channels, 1);
+        assert!((reader.duration() as i64 - 16_000).abs() < 160);
+    }
+    assert!(rms(&channels[0]) > 0.1);
+    assert!(rms(&channels[1]) < 0.001);
+}
+
+#[tokio::test]
+async fn test_call_is_transcribed_with_channel_speakers() {
+    demo_mode::set_enabled(true);
+    let dir = TempDir::new().unwrap();
+    let recordings_dir = dir.path().join("recordings");
+    std::fs::create_dir_all(&recordings_dir).unwrap();
+    let database = Arc::new(Database::new(dir.path().join("call.db")).unwrap());
+    let whisper = WhisperService::new(dir.path().join("model.bin"), recordings_dir.clone());
+
+    let audio_path = recordings_dir.join("call.wav");
+    write_call(&audio_path, 16_000, 4.0, [(2.0, 3.0), (0.2, 1.2)]);
+    let recording = Recording::new("call.wav".to_string(), audio_path.to_string_lossy().to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let speakers = vec!["自分".to_string(), "相手".to_string()];
+    let transcription = transcribe_by_channel(&database, &whisper, &recording.id, None, &speakers)
+        .await
+        .unwrap();
+
+    // 相手（右）が先に話している
+    let lines: Vec<&str> = transcription.text.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("相手: "));
+    assert!(lines[1].starts_with("自分: "));
+
+    let segments = database.get_segments_by_transcription(&transcription.id).await.unwrap();
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[0].speaker.as_deref(), Some("相手"));
+    assert!((segments[0].start_ms - 200).abs() <= 60);
+    assert_eq!(segments[1].speaker.as_deref(), Some("自分"));
+    assert!((segments[1].start_ms - 2_000).abs() <= 60);
+
+    // 作業用のファイルは残さない
+    let leftovers = std::fs::read_dir(recordings_dir.join("converted")).unwrap().count();
+    assert_eq!(leftovers, 0);
+}
+
+#[tokio::test]
+async fn test_mono_recording_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    let database = Arc::new(Database::new(dir.path().join("mono.db")).unwrap());
+    let whisper = WhisperService::new(dir.path().join("model.bin"), dir.path().to_path_buf());
+
+    let audio_path = dir.path().join("mono.wav");
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: 16_000,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(&audio_path, spec).unwrap();
+    for _ in 0..16_000 {
+        writer.write_sample(0i16).unwrap();
+    }
+    writer.finalize().unwrap();
+    let recording = Recording::new("mono.wav".to_string(), audio_path.to_string_lossy().to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    demo_mode::set_enabled(true);
+    assert!(transcribe_by_channel(&database, &whisper, &recording.id, None, &[]).await.is_err());
+}
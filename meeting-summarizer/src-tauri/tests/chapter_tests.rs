@@ -0,0 +1,114 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Chapter, ChapterSource, Recording, Transcription, TranscriptSegment};
+use meeting_summarizer_lib::services::{chapters, export};
+use tempfile::TempDir;
+
+async fn setup_recording(database: &Database) -> Recording {
+    let mut recording = Recording::new("standup.wav".to_string(), "/tmp/standup.wav".to_string());
+    recording.title = Some("Daily standup".to_string());
+    recording.duration = Some(600);
+    database.create_recording(&recording).await.unwrap();
+    recording
+}
+
+#[tokio::test]
+async fn test_topic_chapters_replace_only_topic_source() {
+    let database = Database::in_memory().unwrap();
+    let recording = setup_recording(&database).await;
+
+    let manual = Chapter::new(recording.id.clone(), "Q&A".to_string(), 500_000);
+    database.create_chapter(&manual).await.unwrap();
+
+    let topic = |title: &str, start_ms| {
+        Chapter::new(recording.id.clone(), title.to_string(), start_ms).with_source(ChapterSource::Topic)
+    };
+    database
+        .replace_chapters(&recording.id, ChapterSource::Topic, &[topic("Intro", 0), topic("Roadmap", 120_000)])
+        .await
+        .unwrap();
+    database
+        .replace_chapters(&recording.id, ChapterSource::Topic, &[topic("Opening", 0), topic("Budget", 90_000)])
+        .await
+        .unwrap();
+
+    let stored = database.get_chapters_by_recording(&recording.id).await.unwrap();
+    let titles: Vec<_> = stored.iter().map(|c| c.title.as_str()).collect();
+    assert_eq!(titles, vec!["Opening", "Budget", "Q&A"]);
+    assert_eq!(stored[2].source, ChapterSource::Manual);
+
+    database.delete_recording(&recording.id).await.unwrap();
+    assert!(database.get_chapters_by_recording(&recording.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_markdown_and_vtt_exports_include_chapters() {
+    let database = Database::in_memory().unwrap();
+    let recording = setup_recording(&database).await;
+
+    database
+        .create_chapter(&Chapter::new(recording.id.clone(), "Intro".to_string(), 0))
+        .await
+        .unwrap();
+    database
+        .create_chapter(&Chapter::new(recording.id.clone(), "Roadmap".to_string(), 65_500))
+        .await
+        .unwrap();
+
+    let transcription = Transcription::new(recording.id.clone(), "hello roadmap".to_string(), "en".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+    database
+        .create_transcript_segments(&[
+            TranscriptSegment::new(transcription.id.clone(), 0, 0, 5_000, "hello".to_string()),
+            TranscriptSegment::new(transcription.id.clone(), 1, 70_000, 75_000, "roadmap".to_string()),
+        ])
+        .await
+        .unwrap();
+
+    let markdown = export::export_recording(&database, &recording.id, "markdown").await.unwrap();
+    assert!(markdown.contains("## Chapters"));
+    assert!(markdown.contains("2. [00:01:05] Roadmap"));
+    let intro = markdown.find("### [00:00:00] Intro").unwrap();
+    let roadmap = markdown.find("### [00:01:05] Roadmap").unwrap();
+    assert!(intro < markdown.find("hello\n").unwrap());
+    assert!(roadmap < markdown.find("roadmap\n").unwrap());
+
+    let vtt = export::export_recording(&database, &recording.id, "vtt").await.unwrap();
+    assert!(vtt.starts_with("WEBVTT\n"));
+    assert!(vtt.contains("00:00:00.000 --> 00:01:05.500\nIntro"));
+    // 最後のチャプターは録音の長さまで
+    assert!(vtt.contains("00:01:05.500 --> 00:10:00.000\nRoadmap"));
+}
+
+#[test]
+fn test_mp3_chapter_frames_are_written() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("meeting.mp3");
+    std::fs::write(&path, [0xFFu8, 0xFB, 0x90, 0x00]).unwrap();
+
+    let chapter_list = vec![
+        Chapter::new("rec".to_string(), "Intro".to_string(), 0),
+        Chapter::new("rec".to_string(), "Wrap-up".to_string(), 30_000).with_end(45_000),
+    ];
+    chapters::write_mp3_chapters(&path, Some("Standup"), &chapter_list, Some(60_000)).unwrap();
+    // 再書き込みしても重複しない
+    chapters::write_mp3_chapters(&path, Some("Standup"), &chapter_list, Some(60_000)).unwrap();
+
+    use id3::TagLike;
+    let tag = id3::Tag::read_from_path(&path).unwrap();
+    let written: Vec<_> = tag.chapters().map(|c| (c.start_time, c.end_time)).collect();
+    assert_eq!(written, vec![(0, 30_000), (30_000, 45_000)]);
+    assert_eq!(tag.tables_of_contents().count(), 1);
+    assert_eq!(tag.title(), Some("Standup"));
+}
+
+#[test]
+fn test_invalid_chapters_are_rejected() {
+    let negative = Chapter::new("rec".to_string(), "Intro".to_string(), -1);
+    assert!(chapters::validate_chapter(&negative).is_err());
+
+    let reversed = Chapter::new("rec".to_string(), "Intro".to_string(), 10_000).with_end(5_000);
+    assert!(chapters::validate_chapter(&reversed).is_err());
+
+    let untitled = Chapter::new("rec".to_string(), "  ".to_string(), 0);
+    assert!(chapters::validate_chapter(&untitled).is_err());
+}
@@ -0,0 +1,53 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::services::RecordingService;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn write_wav(path: &Path) {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 16_000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(path, spec).unwrap();
+    for index in 0..16_000 {
+        writer.write_sample(((index as f32 * 0.05).sin() * 8_000.0) as i16).unwrap();
+    }
+    writer.finalize().unwrap();
+}
+
+#[tokio::test]
+async fn test_transcribe_save_copies_the_file_into_recordings() {
+    let temp_dir = TempDir::new().unwrap();
+    let data_dir = temp_dir.path().join("data");
+    let source = temp_dir.path().join("standup.wav");
+    write_wav(&source);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_meeting-summarizer-cli"))
+        .env("MEETING_SUMMARIZER_DEMO", "1")
+        .arg("--data-dir")
+        .arg(&data_dir)
+        .args(["transcribe", "--save"])
+        .arg(&source)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let recordings_dir = data_dir.join("recordings");
+    let database = Arc::new(Database::new(data_dir.join("recordings.db")).unwrap());
+    let recordings = database.get_all_recordings().await.unwrap();
+    assert_eq!(recordings.len(), 1);
+    let recording = &recordings[0];
+    assert!(Path::new(&recording.file_path).starts_with(&recordings_dir));
+    assert_eq!(recording.title.as_deref(), Some("standup"));
+    assert_eq!(database.get_transcriptions_by_recording(&recording.id).await.unwrap().len(), 1);
+
+    // アプリから削除しても元のファイルは残る
+    let recording_service = RecordingService::new(database.clone(), recordings_dir).unwrap();
+    assert!(recording_service.delete_recording(&recording.id).await.unwrap());
+    assert!(!Path::new(&recording.file_path).exists());
+    assert!(source.exists());
+}
@@ -0,0 +1,42 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{ColorLabel, Recording, RecordingQuery};
+
+#[test]
+fn test_color_label_round_trip() {
+    assert_eq!(serde_json::to_string(&ColorLabel::Purple).unwrap(), "\"purple\"");
+    assert_eq!("green".parse::<ColorLabel>().unwrap(), ColorLabel::Green);
+    assert!("pink".parse::<ColorLabel>().is_err());
+}
+
+#[tokio::test]
+async fn test_set_and_filter_color_labels() {
+    let database = Database::in_memory().unwrap();
+    let mut ids = Vec::new();
+    for name in ["a", "b", "c"] {
+        let recording = Recording::new(format!("{}.wav", name), format!("/tmp/color_{}.wav", name));
+        database.create_recording(&recording).await.unwrap();
+        ids.push(recording.id);
+    }
+
+    let updated = database.set_color_labels(&ids[..2], Some(ColorLabel::Red)).await.unwrap();
+    assert_eq!(updated, 2);
+    database.set_color_labels(&ids[2..], Some(ColorLabel::Blue)).await.unwrap();
+
+    let red = database
+        .search_recordings(&RecordingQuery { color_labels: vec![ColorLabel::Red], ..Default::default() })
+        .await
+        .unwrap();
+    assert_eq!(red.len(), 2);
+    assert!(red.iter().all(|recording| recording.color_label == Some(ColorLabel::Red)));
+
+    let either = database
+        .search_recordings(&RecordingQuery { color_labels: vec![ColorLabel::Red, ColorLabel::Blue], ..Default::default() })
+        .await
+        .unwrap();
+    assert_eq!(either.len(), 3);
+
+    // ラベルの解除
+    database.set_color_labels(&ids[..1], None).await.unwrap();
+    let cleared = database.get_recording(&ids[0]).await.unwrap().unwrap();
+    assert_eq!(cleared.color_label, None);
+}
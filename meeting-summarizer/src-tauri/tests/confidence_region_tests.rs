@@ -0,0 +1,113 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, Transcription, TranscriptSegment};
+use meeting_summarizer_lib::services::confidence_regions::{self, find_regions};
+use meeting_summarizer_lib::services::whisper_local::{average_confidence, parse_script_output};
+
+fn segment(index: i32, start_ms: i64, end_ms: i64, text: &str, confidence: Option<f32>) -> TranscriptSegment {
+    let mut segment = TranscriptSegment::new("tx-1".to_string(), index, start_ms, end_ms, text.to_string());
+    segment.confidence = confidence;
+    segment
+}
+
+#[test]
+fn test_adjacent_low_segments_are_merged() {
+    let segments = vec![
+        segment(0, 0, 2_000, "はじめます", Some(0.9)),
+        segment(1, 2_000, 4_000, "えーと", Some(0.4)),
+        segment(2, 4_500, 6_000, "クバネテスの", Some(0.5)),
+        segment(3, 6_000, 8_000, "件です", Some(0.8)),
+        segment(4, 8_000, 9_000, "不明", None),
+        segment(5, 9_000, 10_000, "以上", Some(0.3)),
+        // 間隔が空いている場合は別の区間にする
+        segment(6, 15_000, 16_000, "次回", Some(0.2)),
+    ];
+
+    let regions = find_regions(&segments, 0.6, 1_000);
+    assert_eq!(regions.len(), 3);
+
+    assert_eq!((regions[0].start_ms, regions[0].end_ms), (2_000, 6_000));
+    assert_eq!(regions[0].text, "えーと クバネテスの");
+    assert_eq!(regions[0].min_confidence, 0.4);
+    assert!((regions[0].average_confidence - 0.45).abs() < 1e-6);
+    assert_eq!(regions[0].segment_ids.len(), 2);
+
+    assert_eq!((regions[1].start_ms, regions[1].end_ms), (9_000, 10_000));
+    assert_eq!((regions[2].start_ms, regions[2].end_ms), (15_000, 16_000));
+}
+
+#[tokio::test]
+async fn test_low_confidence_regions_for_transcription() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("meeting.wav".to_string(), "/tmp/meeting.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "text".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+
+    let mut low = TranscriptSegment::new(transcription.id.clone(), 0, 0, 1_500, "聞き取りにくい".to_string());
+    low.confidence = Some(0.5);
+    let mut high = TranscriptSegment::new(transcription.id.clone(), 1, 1_500, 3_000, "明瞭".to_string());
+    high.confidence = Some(0.95);
+    database.create_transcript_segments(&[low, high]).await.unwrap();
+
+    let regions = confidence_regions::get_low_confidence_regions(&database, &transcription.id, None).await.unwrap();
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].text, "聞き取りにくい");
+
+    let stricter = confidence_regions::get_low_confidence_regions(&database, &transcription.id, Some(0.99)).await.unwrap();
+    assert_eq!(stricter.len(), 1);
+    assert_eq!(stricter[0].end_ms, 3_000);
+
+    assert!(confidence_regions::get_low_confidence_regions(&database, &transcription.id, Some(1.5)).await.is_err());
+    assert!(confidence_regions::get_low_confidence_regions(&database, "missing", None).await.is_err());
+}
+
+#[tokio::test]
+async fn test_whisper_segments_are_saved_with_the_transcription() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("meeting.wav".to_string(), "/tmp/meeting.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    // Python スクリプトは本文と区間（平均対数確率付き）を JSON で返す
+    let output = parse_script_output(
+        "Loading...\n{\"text\": \"はじめます 聞き取りにくい\", \"segments\": [\
+         {\"start\": 0.0, \"end\": 1.5, \"text\": \"はじめます\", \"avg_logprob\": -0.05},\
+         {\"start\": 1.5, \"end\": 3.2, \"text\": \"聞き取りにくい\", \"avg_logprob\": -1.2}]}\n",
+    );
+    assert_eq!(output.text, "はじめます 聞き取りにくい");
+    assert_eq!(output.segments[1].start_ms, 1_500);
+    let confidence = average_confidence(&output.segments).unwrap();
+    assert!(confidence < 0.95);
+
+    let mut transcription = Transcription::new(recording.id.clone(), output.text, "ja".to_string())
+        .with_confidence(Some(confidence));
+    transcription.segments = output
+        .segments
+        .into_iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            let mut transcript_segment = TranscriptSegment::new(
+                transcription.id.clone(),
+                index as i32,
+                segment.start_ms,
+                segment.end_ms,
+                segment.text,
+            );
+            transcript_segment.confidence = segment.confidence;
+            transcript_segment
+        })
+        .collect();
+    database.create_transcription(&transcription).await.unwrap();
+
+    let saved = database.get_segments_by_transcription(&transcription.id).await.unwrap();
+    assert_eq!(saved.len(), 2);
+    assert_eq!((saved[1].start_ms, saved[1].end_ms), (1_500, 3_200));
+
+    let regions = confidence_regions::get_low_confidence_regions(&database, &transcription.id, None).await.unwrap();
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].text, "聞き取りにくい");
+
+    // JSON でない出力は本文としてそのまま使う
+    let plain = parse_script_output("こんにちは\n");
+    assert_eq!(plain.text, "こんにちは");
+    assert!(plain.segments.is_empty());
+}
@@ -0,0 +1,153 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::JobPayload;
+use meeting_summarizer_lib::services::{demo_mode, ControlResult, ControlServer, JobQueue, LLMModelManager, ModelSettingsManager, OllamaPool, RecordingService, Summarizer, WhisperService};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::sync::Mutex;
+
+fn summarizer(database: &Arc<Database>) -> Arc<Summarizer> {
+    // 設定ファイルは読み書きしない（既定の設定で要約する）
+    Arc::new(Summarizer::new(
+        database.clone(),
+        Arc::new(Mutex::new(ModelSettingsManager::new(PathBuf::from("model_settings.json")))),
+        Arc::new(Mutex::new(LLMModelManager::new())),
+        Arc::new(OllamaPool::new()),
+    ))
+}
+
+const TOKEN: &str = "test-token";
+
+#[tokio::test]
+async fn test_control_server_drives_recording() {
+    demo_mode::set_enabled(true);
+
+    let temp_dir = TempDir::new().unwrap();
+    let recordings_dir = temp_dir.path().join("recordings");
+    let database = Arc::new(Database::new(temp_dir.path().join("control.db")).unwrap());
+    let recording_service = Arc::new(RecordingService::new(database.clone(), recordings_dir.clone()).unwrap());
+    let whisper = Arc::new(WhisperService::new(temp_dir.path().join("model.bin"), recordings_dir));
+    let job_queue = Arc::new(JobQueue::new(database.clone(), whisper, summarizer(&database)));
+
+    let mut server = ControlServer::new();
+    let mut events = server.subscribe();
+    let address = server.start(database.clone(), recording_service.clone(), job_queue.clone(), TOKEN.to_string(), 0).await.unwrap();
+    assert!(server.get_status().running);
+    assert_eq!(address.ip().to_string(), "127.0.0.1");
+
+    let client = reqwest::Client::new();
+    let url = |path: &str| format!("http://{}{}", address, path);
+
+    // トークンが無い・違う要求は拒否する
+    let response = client.post(url("/recording/start")).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    let response = client.get(url("/recording/start?token=wrong")).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    // クエリのトークンは GET だけ
+    let response = client.post(url(&format!("/recording/start?token={}", TOKEN))).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    assert!(!recording_service.is_recording());
+
+    // 録音していなければマーカーは付けられない
+    let response = client.post(url("/recording/marker")).header("X-Control-Token", TOKEN).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+
+    // トグルで開始（Stream Deck の Website アクションと同じ GET）
+    let started: ControlResult = client
+        .get(url(&format!("/recording/toggle?token={}", TOKEN)))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(started.recording);
+    assert!(started.session_id.is_some());
+
+    let response = client.post(url("/recording/start")).header("X-Control-Token", TOKEN).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+
+    let marked: ControlResult = client
+        .post(url("/recording/marker?label=決定事項"))
+        .header("X-Control-Token", TOKEN)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(marked.marker_id.is_some());
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    // 停止と同時に書き起こしを登録する
+    let stopped: ControlResult = client
+        .post(url("/recording/stop?transcribe=true&language=ja"))
+        .header("X-Control-Token", TOKEN)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(!stopped.recording);
+    let recording_id = stopped.recording_id.unwrap();
+    let job = job_queue.get_job(&stopped.job_id.unwrap()).await.unwrap().unwrap();
+    match job.payload {
+        JobPayload::Transcription { recording_id: queued, language } => {
+            assert_eq!(queued, recording_id);
+            assert_eq!(language.as_deref(), Some("ja"));
+        }
+        other => panic!("unexpected payload: {:?}", other),
+    }
+
+    // 最新の録音の書き起こしは、登録済みのジョブを返す
+    let latest: ControlResult = client
+        .get(url(&format!("/transcribe/latest?token={}", TOKEN)))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(latest.recording_id.as_deref(), Some(recording_id.as_str()));
+    assert_eq!(latest.job_id, Some(job.id));
+
+    let status: ControlResult = client
+        .get(url("/status"))
+        .header("X-Control-Token", TOKEN)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(!status.recording);
+
+    let mut kinds = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        kinds.push(serde_json::to_value(event).unwrap()["type"].as_str().unwrap().to_string());
+    }
+    assert_eq!(
+        kinds,
+        ["recording_started", "marker_added", "recording_stopped", "transcription_queued", "transcription_queued"]
+    );
+
+    server.stop().await.unwrap();
+    assert!(!server.get_status().running);
+}
+
+#[tokio::test]
+async fn test_control_server_rejects_empty_token() {
+    let temp_dir = TempDir::new().unwrap();
+    let recordings_dir = temp_dir.path().join("recordings");
+    let database = Arc::new(Database::new(temp_dir.path().join("control.db")).unwrap());
+    let recording_service = Arc::new(RecordingService::new(database.clone(), recordings_dir.clone()).unwrap());
+    let whisper = Arc::new(WhisperService::new(temp_dir.path().join("model.bin"), recordings_dir));
+    let job_queue = Arc::new(JobQueue::new(database.clone(), whisper, summarizer(&database)));
+
+    let mut server = ControlServer::new();
+    assert!(server.start(database, recording_service, job_queue, " ".to_string(), 0).await.is_err());
+    assert!(!server.is_running());
+}
@@ -0,0 +1,54 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{CorrectionEntry, TranscriptSegment, Transcription};
+use meeting_summarizer_lib::services::corrections;
+
+#[test]
+fn test_apply_prefers_longer_terms_in_a_single_pass() {
+    let entries = vec![
+        CorrectionEntry::new("クバネテス".to_string(), "Kubernetes".to_string()),
+        CorrectionEntry::new("クバネテスクラスタ".to_string(), "Kubernetes クラスタ".to_string()),
+        // 置き換え後の文字列は再度置き換えない
+        CorrectionEntry::new("Kubernetes".to_string(), "k8s".to_string()),
+    ];
+
+    let (text, hits) = corrections::apply("クバネテスクラスタとクバネテスの違い", &entries);
+    assert_eq!(text, "Kubernetes クラスタとKubernetesの違い");
+    assert_eq!(hits.len(), 2);
+    assert!(hits.contains(&(entries[0].id.clone(), 1)));
+    assert!(hits.contains(&(entries[1].id.clone(), 1)));
+
+    let mut disabled = entries[0].clone();
+    disabled.enabled = false;
+    let (text, hits) = corrections::apply("クバネテス", &[disabled]);
+    assert_eq!(text, "クバネテス");
+    assert!(hits.is_empty());
+}
+
+#[tokio::test]
+async fn test_apply_corrections_records_hit_counts() {
+    let database = Database::in_memory().unwrap();
+    let entry = CorrectionEntry::new("ジラ".to_string(), "Jira".to_string());
+    database.create_correction_entry(&entry).await.unwrap();
+    database
+        .create_correction_entry(&CorrectionEntry::new("スラック".to_string(), "Slack".to_string()))
+        .await
+        .unwrap();
+
+    let mut transcription = Transcription::new("rec-1".to_string(), "ジラのチケットをジラで更新".to_string(), "ja".to_string());
+    transcription.segments = vec![
+        TranscriptSegment::new(transcription.id.clone(), 0, 0, 1_500, "ジラのチケットを".to_string()),
+        TranscriptSegment::new(transcription.id.clone(), 1, 1_500, 3_000, "ジラで更新".to_string()),
+    ];
+    let applied = corrections::apply_corrections(&database, &mut transcription).await.unwrap();
+    assert_eq!(applied, 2);
+    assert_eq!(transcription.text, "JiraのチケットをJiraで更新");
+    let segment_texts: Vec<&str> = transcription.segments.iter().map(|segment| segment.text.as_str()).collect();
+    assert_eq!(segment_texts, vec!["Jiraのチケットを", "Jiraで更新"]);
+
+    let entries = database.get_correction_entries().await.unwrap();
+    assert_eq!(entries[0].wrong, "ジラ");
+    assert_eq!(entries[0].hit_count, 2);
+    assert!(entries[0].last_hit_at.is_some());
+    assert_eq!(entries[1].hit_count, 0);
+    assert!(entries[1].last_hit_at.is_none());
+}
@@ -0,0 +1,62 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::Recording;
+use tempfile::TempDir;
+
+fn recording(name: &str, category: &str, tags: &[&str]) -> Recording {
+    let mut recording = Recording::new(format!("{}.wav", name), format!("/recordings/{}.wav", name));
+    recording.category = Some(category.to_string());
+    recording.tags = tags.iter().map(|t| t.to_string()).collect();
+    recording.duration = Some(60);
+    recording
+}
+
+#[tokio::test]
+async fn test_aggregates_are_cached_until_write() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("cache.db");
+    let database = Database::new(&db_path).unwrap();
+
+    database.create_recording(&recording("a", "standup", &["infra"])).await.unwrap();
+
+    let stats = database.get_recording_stats().await.unwrap();
+    assert_eq!(stats.total_count, 1);
+    assert_eq!(database.get_all_categories().await.unwrap(), vec!["standup".to_string()]);
+    assert_eq!(database.get_all_tags().await.unwrap(), vec!["infra".to_string()]);
+
+    // キャッシュを経由しない直接書き込みは反映されない（= キャッシュから返っている）
+    {
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        conn.execute("UPDATE recordings SET category = 'hidden'", []).unwrap();
+    }
+    assert_eq!(database.get_recording_stats().await.unwrap().total_count, 1);
+    assert_eq!(database.get_all_categories().await.unwrap(), vec!["standup".to_string()]);
+
+    // 書き込みでキャッシュが破棄される
+    let mut second = recording("b", "planning", &["q3"]);
+    database.create_recording(&second).await.unwrap();
+    let stats = database.get_recording_stats().await.unwrap();
+    assert_eq!(stats.total_count, 2);
+    assert_eq!(
+        database.get_all_categories().await.unwrap(),
+        vec!["hidden".to_string(), "planning".to_string()]
+    );
+    assert_eq!(database.get_all_tags().await.unwrap(), vec!["infra".to_string(), "q3".to_string()]);
+
+    second.tags = vec!["design".to_string()];
+    database.update_recording(&second).await.unwrap();
+    assert_eq!(database.get_all_tags().await.unwrap(), vec!["design".to_string(), "infra".to_string()]);
+
+    database.delete_recording(&second.id).await.unwrap();
+    assert_eq!(database.get_recording_stats().await.unwrap().total_count, 1);
+    assert_eq!(database.get_all_tags().await.unwrap(), vec!["infra".to_string()]);
+}
+
+#[tokio::test]
+async fn test_cache_is_shared_between_clones() {
+    let database = Database::in_memory().unwrap();
+    let clone = database.clone();
+
+    assert_eq!(clone.get_recording_stats().await.unwrap().total_count, 0);
+    database.create_recording(&recording("c", "review", &[])).await.unwrap();
+    assert_eq!(clone.get_recording_stats().await.unwrap().total_count, 1);
+}
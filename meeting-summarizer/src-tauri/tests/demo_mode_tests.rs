@@ -0,0 +1,97 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{LLMProvider, SummaryStatus};
+use meeting_summarizer_lib::services::{corrections, demo_mode, keyword_timeline};
+use meeting_summarizer_lib::services::{LLMService, RecordingService, WhisperService};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+// デモモードはプロセス全体の設定のため、このファイルのテストは全て有効にして実行する
+
+#[tokio::test]
+async fn test_demo_flow_runs_without_devices_or_servers() {
+    demo_mode::set_enabled(true);
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let recordings_dir = temp_dir.path().join("recordings");
+    let database = Arc::new(Database::new(temp_dir.path().join("demo.db")).unwrap());
+    let recording_service = RecordingService::new(database.clone(), recordings_dir.clone()).unwrap();
+
+    // 録音：マイクの代わりに決まった波形が書き出される
+    recording_service.start_recording().await.unwrap();
+    let recording = recording_service.stop_recording().await.unwrap();
+    let reader = hound::WavReader::open(&recording.file_path).unwrap();
+    assert_eq!(reader.spec().sample_rate, 16_000);
+    assert!(reader.len() >= 16_000);
+
+    // 書き起こし：初期化せずに用意した書き起こしが返る
+    let whisper = WhisperService::new(temp_dir.path().join("model.bin"), recordings_dir);
+    let transcription = whisper
+        .transcribe_audio_file(recording.file_path.as_ref(), recording.id.clone(), None)
+        .await
+        .unwrap();
+    assert_eq!(transcription.text, demo_mode::DEMO_TRANSCRIPT);
+    assert_eq!(transcription.model_used.as_deref(), Some(demo_mode::DEMO_MODEL_NAME));
+
+    // 要約：デモ用のプロバイダーが決まった応答を返す
+    let config = demo_mode::llm_config();
+    assert!(matches!(config.provider, LLMProvider::Demo));
+    let llm = LLMService::new(config);
+    assert!(llm.check_connection().await.unwrap());
+    let summary = llm.summarize_text(&transcription.text, transcription.id.clone()).await.unwrap();
+    assert!(matches!(summary.status, SummaryStatus::Completed));
+    assert_eq!(summary.model_used, demo_mode::DEMO_MODEL_NAME);
+    assert!(summary.key_points.iter().any(|point| point.contains("再来週の月曜日")));
+    assert_eq!(summary.action_items.len(), 2);
+
+    let again = llm.summarize_text(&transcription.text, transcription.id.clone()).await.unwrap();
+    assert_eq!(again.summary_text, summary.summary_text);
+    assert_eq!(again.key_points, summary.key_points);
+}
+
+#[tokio::test]
+async fn test_saved_transcription_feeds_the_keyword_timeline() {
+    demo_mode::set_enabled(true);
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let recordings_dir = temp_dir.path().join("recordings");
+    let database = Arc::new(Database::new(temp_dir.path().join("demo.db")).unwrap());
+    let recording_service = RecordingService::new(database.clone(), recordings_dir.clone()).unwrap();
+    recording_service.start_recording().await.unwrap();
+    let recording = recording_service.stop_recording().await.unwrap();
+
+    // 画面・API から書き起こす時と同じく、補正してから保存する
+    let whisper = WhisperService::new(temp_dir.path().join("model.bin"), recordings_dir);
+    let mut transcription = whisper
+        .transcribe_audio_files(&[recording.file_path.clone().into()], recording.id.clone(), None)
+        .await
+        .unwrap();
+    corrections::apply_corrections(&database, &mut transcription).await.unwrap();
+    database.create_transcription(&transcription).await.unwrap();
+
+    let timeline = keyword_timeline::recording_timeline(&database, &recording.id, "リリース", 10)
+        .await
+        .unwrap()
+        .unwrap();
+    // 「リリース」はデモの書き起こしの2・9・11・13文目に出てくる
+    let segment_indexes: Vec<i32> = timeline.occurrences.iter().map(|o| o.segment_index).collect();
+    assert_eq!(segment_indexes, vec![1, 8, 10, 12]);
+    assert!(timeline.occurrences[0].offset_ms >= demo_mode::DEMO_SEGMENT_MS);
+    assert_eq!(timeline.density.iter().sum::<u32>(), 4);
+}
+
+#[test]
+fn test_demo_audio_is_deterministic() {
+    let first: Vec<f32> = (0..1_000).map(|i| demo_mode::demo_sample(i, 16_000)).collect();
+    let second: Vec<f32> = (0..1_000).map(|i| demo_mode::demo_sample(i, 16_000)).collect();
+    assert_eq!(first, second);
+    assert!(first.iter().any(|sample| sample.abs() > 0.1));
+    // 1秒ごとの最後の0.2秒は無音
+    assert_eq!(demo_mode::demo_sample(15_000, 16_000), 0.0);
+}
+
+#[test]
+fn test_demo_llm_answers_by_prompt_kind() {
+    assert_eq!(demo_mode::llm_response("以下のテキストに登場する人名を..."), "山田\n佐藤");
+    assert!(demo_mode::llm_response("---書き起こしテキスト---").contains("## 要約"));
+    assert_eq!("demo".parse::<LLMProvider>().map(|p| matches!(p, LLMProvider::Demo)), Ok(true));
+}
@@ -0,0 +1,23 @@
+use meeting_summarizer_lib::services::device_fallback::{self, CaptureDeviceEvent};
+
+#[test]
+fn test_device_event_serializes_with_type_tag() {
+    let event = CaptureDeviceEvent::Disconnected { device: "USB Microphone".to_string() };
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!(json["type"], "disconnected");
+    assert_eq!(json["device"], "USB Microphone");
+
+    let parsed: CaptureDeviceEvent =
+        serde_json::from_str(r#"{"type":"reconnected","device":"MacBook Pro Microphone"}"#).unwrap();
+    assert_eq!(parsed, CaptureDeviceEvent::Reconnected { device: "MacBook Pro Microphone".to_string() });
+    assert_eq!(parsed.device(), "MacBook Pro Microphone");
+}
+
+#[test]
+fn test_only_device_not_available_counts_as_disconnect() {
+    assert!(device_fallback::is_device_lost(&cpal::StreamError::DeviceNotAvailable));
+    let backend = cpal::StreamError::BackendSpecific {
+        err: cpal::BackendSpecificError { description: "buffer overrun".to_string() },
+    };
+    assert!(!device_fallback::is_device_lost(&backend));
+}
@@ -0,0 +1,121 @@
+use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, Summary, Transcription};
+use meeting_summarizer_lib::services::demo_mode;
+use meeting_summarizer_lib::services::digest_schedule::{due_period, latest_slot, run_scheduled_digest};
+use meeting_summarizer_lib::services::{
+    DigestDelivery, DigestFrequency, DigestScheduleSettings, LLMModelManager, ModelSettingsManager, OllamaPool, Summarizer,
+};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+fn jst(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<FixedOffset> {
+    FixedOffset::east_opt(9 * 3600)
+        .unwrap()
+        .with_ymd_and_hms(year, month, day, hour, minute, 0)
+        .unwrap()
+}
+
+fn summarizer(database: Database) -> Summarizer {
+    // 設定ファイルは読み書きしない（既定の設定で要約する）
+    Summarizer::new(
+        Arc::new(database),
+        Arc::new(Mutex::new(ModelSettingsManager::new(PathBuf::from("model_settings.json")))),
+        Arc::new(Mutex::new(LLMModelManager::new())),
+        Arc::new(OllamaPool::new()),
+    )
+}
+
+fn schedule(frequency: DigestFrequency, last_run_at: Option<DateTime<Utc>>) -> DigestScheduleSettings {
+    DigestScheduleSettings {
+        enabled: true,
+        frequency,
+        delivery: Some(DigestDelivery::File { directory: "/tmp".to_string() }),
+        model_config: None,
+        last_run_at,
+    }
+}
+
+#[test]
+fn test_latest_weekly_slot() {
+    // 2026-10-14 は水曜日
+    let monday_nine = DigestFrequency::Weekly { weekday: 0, hour: 9, minute: 0 };
+    assert_eq!(latest_slot(&monday_nine, &jst(2026, 10, 14, 12, 0)), Some(jst(2026, 10, 12, 9, 0)));
+    // 当日の予定時刻より前なら先週の回
+    assert_eq!(latest_slot(&monday_nine, &jst(2026, 10, 12, 8, 59)), Some(jst(2026, 10, 5, 9, 0)));
+    assert_eq!(latest_slot(&monday_nine, &jst(2026, 10, 12, 9, 0)), Some(jst(2026, 10, 12, 9, 0)));
+}
+
+#[test]
+fn test_latest_monthly_slot() {
+    let first_of_month = DigestFrequency::Monthly { day: 1, hour: 18, minute: 30 };
+    assert_eq!(latest_slot(&first_of_month, &jst(2026, 10, 14, 12, 0)), Some(jst(2026, 10, 1, 18, 30)));
+    assert_eq!(latest_slot(&first_of_month, &jst(2026, 1, 1, 10, 0)), Some(jst(2025, 12, 1, 18, 30)));
+}
+
+#[test]
+fn test_due_period_runs_each_slot_once() {
+    let frequency = DigestFrequency::Weekly { weekday: 0, hour: 9, minute: 0 };
+    let now = jst(2026, 10, 12, 9, 1);
+
+    let (from, to) = due_period(&schedule(frequency, None), &now).unwrap();
+    assert_eq!(to, jst(2026, 10, 12, 9, 0).with_timezone(&Utc));
+    assert_eq!(to - from, Duration::days(7));
+
+    assert!(due_period(&schedule(frequency, Some(to)), &now).is_none());
+    assert!(due_period(&schedule(frequency, Some(to)), &jst(2026, 10, 19, 9, 0)).is_some());
+
+    let mut disabled = schedule(frequency, None);
+    disabled.enabled = false;
+    assert!(due_period(&disabled, &now).is_none());
+}
+
+#[test]
+fn test_validation() {
+    assert!(DigestFrequency::Weekly { weekday: 7, hour: 9, minute: 0 }.validate().is_err());
+    assert!(DigestFrequency::Monthly { day: 31, hour: 9, minute: 0 }.validate().is_err());
+    assert!(DigestFrequency::Monthly { day: 28, hour: 24, minute: 0 }.validate().is_err());
+    assert!(DigestDelivery::File { directory: "relative".to_string() }.validate().is_err());
+    assert!(DigestDelivery::Webhook { url: "ftp://example.com".to_string() }.validate().is_err());
+    assert!(DigestDelivery::Webhook { url: "https://example.com/hook".to_string() }.validate().is_ok());
+}
+
+#[tokio::test]
+async fn test_scheduled_digest_written_to_file() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("定例.wav".to_string(), "/tmp/定例.wav".to_string()).with_title("定例".to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+    let summary = Summary::new(transcription.id.clone(), "llama3".to_string()).with_content(
+        "日程を確定した。".to_string(),
+        Vec::new(),
+        Vec::new(),
+    );
+    database.create_summary(&summary).await.unwrap();
+
+    let directory = tempfile::tempdir().unwrap();
+    let delivery = DigestDelivery::File {
+        directory: directory.path().to_string_lossy().to_string(),
+    };
+    let to = Utc::now() + Duration::minutes(1);
+    let run = run_scheduled_digest(&summarizer(database), Some(demo_mode::llm_config()), &delivery, to - Duration::days(7), to).await;
+
+    assert!(run.error.is_none(), "{:?}", run.error);
+    assert_eq!(run.meeting_count, 1);
+    let content = std::fs::read_to_string(run.delivered_to.unwrap()).unwrap();
+    assert!(content.starts_with("# 会議ダイジェスト"));
+    assert!(content.contains("## 決定事項"));
+    assert!(content.contains("定例"));
+}
+
+#[tokio::test]
+async fn test_scheduled_digest_reports_empty_period() {
+    let database = Database::in_memory().unwrap();
+    let delivery = DigestDelivery::File { directory: "/tmp".to_string() };
+    let to = Utc::now();
+    let run = run_scheduled_digest(&summarizer(database), Some(demo_mode::llm_config()), &delivery, to - Duration::days(7), to).await;
+    assert!(run.delivered_to.is_none());
+    assert!(run.error.is_some());
+}
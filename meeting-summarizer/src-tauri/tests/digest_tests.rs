@@ -0,0 +1,89 @@
+use chrono::{Duration, Utc};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, Summary, Transcription};
+use meeting_summarizer_lib::services::demo_mode;
+use meeting_summarizer_lib::services::digest::{self, batch_by_chars, parse_digest_response, DigestScope};
+use meeting_summarizer_lib::services::LLMService;
+
+async fn summarized_recording(database: &Database, title: &str, summary_text: &str, action_items: Vec<String>) -> Recording {
+    let recording = Recording::new(format!("{}.wav", title), format!("/tmp/{}.wav", title)).with_title(title.to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+    let summary = Summary::new(transcription.id.clone(), "llama3".to_string()).with_content(
+        summary_text.to_string(),
+        vec!["ポイント".to_string()],
+        action_items,
+    );
+    database.create_summary(&summary).await.unwrap();
+    recording
+}
+
+#[test]
+fn test_batches_stay_under_limit() {
+    let texts = vec!["a".repeat(40), "b".repeat(40), "c".repeat(40), "d".repeat(120)];
+    let batches = batch_by_chars(texts, 100);
+    let sizes: Vec<usize> = batches.iter().map(Vec::len).collect();
+    assert_eq!(sizes, vec![2, 1, 1]);
+}
+
+#[test]
+fn test_parse_digest_sections() {
+    let (overview, themes, decisions) = parse_digest_response(
+        "## 概要\n準備が進んだ。\n\n## テーマ\n- リリース\n- （複数の会議にまたがる主な話題を箇条書きで）\n\n## 決定事項\n・日程を確定",
+    );
+    assert_eq!(overview, "準備が進んだ。");
+    assert_eq!(themes, vec!["リリース".to_string()]);
+    assert_eq!(decisions, vec!["日程を確定".to_string()]);
+
+    let (overview, themes, _) = parse_digest_response("見出しの無い応答");
+    assert_eq!(overview, "見出しの無い応答");
+    assert!(themes.is_empty());
+}
+
+#[tokio::test]
+async fn test_digest_across_selected_meetings() {
+    let database = Database::in_memory().unwrap();
+    let first = summarized_recording(&database, "定例1", "日程を確認した。", vec!["山田：テストを報告する".to_string()]).await;
+    let second = summarized_recording(&database, "定例2", "日程を確定した。", Vec::new()).await;
+    let unsummarized = Recording::new("memo.wav".to_string(), "/tmp/memo.wav".to_string());
+    database.create_recording(&unsummarized).await.unwrap();
+
+    let llm = LLMService::new(demo_mode::llm_config());
+    let scope = DigestScope::Recordings {
+        recording_ids: vec![second.id.clone(), first.id.clone(), unsummarized.id.clone()],
+    };
+    let digest = digest::generate_digest(&database, &llm, &scope).await.unwrap();
+
+    let titles: Vec<&str> = digest.meetings.iter().map(|meeting| meeting.title.as_str()).collect();
+    assert_eq!(titles, vec!["定例1", "定例2"]);
+    assert_eq!(digest.skipped_recording_ids, vec![unsummarized.id.clone()]);
+    assert_eq!(digest.themes.len(), 2);
+    assert_eq!(digest.decisions.len(), 1);
+    assert_eq!(digest.open_action_items, vec!["山田：テストを報告する".to_string()]);
+    assert_eq!(digest.model_used, demo_mode::DEMO_MODEL_NAME);
+}
+
+#[tokio::test]
+async fn test_digest_by_date_range_reduces_large_inputs() {
+    let database = Database::in_memory().unwrap();
+    for index in 0..3 {
+        summarized_recording(&database, &format!("長い会議{}", index), &"議論".repeat(3_000), Vec::new()).await;
+    }
+
+    let llm = LLMService::new(demo_mode::llm_config());
+    let scope = DigestScope::DateRange {
+        from: Utc::now() - Duration::days(7),
+        to: Utc::now() + Duration::minutes(1),
+    };
+    let digest = digest::generate_digest(&database, &llm, &scope).await.unwrap();
+    assert_eq!(digest.meetings.len(), 3);
+    assert!(!digest.overview.is_empty());
+
+    // 要約のある会議が無ければエラー
+    let empty = DigestScope::DateRange {
+        from: Utc::now() - Duration::days(30),
+        to: Utc::now() - Duration::days(20),
+    };
+    assert!(digest::generate_digest(&database, &llm, &empty).await.is_err());
+}
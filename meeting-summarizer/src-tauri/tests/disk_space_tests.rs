@@ -0,0 +1,64 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::services::disk_space::{ensure_free_space, free_space, status, DiskSpaceStatus};
+use meeting_summarizer_lib::services::{demo_mode, RecordingService, StorageSettings};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[test]
+fn test_status_thresholds() {
+    assert_eq!(status(10, 0), DiskSpaceStatus::Ok);
+    assert_eq!(status(300, 100), DiskSpaceStatus::Ok);
+    assert_eq!(status(150, 100), DiskSpaceStatus::Low);
+    assert_eq!(status(99, 100), DiskSpaceStatus::Exhausted);
+
+    let settings: StorageSettings = serde_json::from_str(r#"{"quota_bytes": null, "warning_thresholds": [0.8], "candidate_min_age_days": 30}"#).unwrap();
+    assert_eq!(settings.min_free_bytes, Some(500 * 1024 * 1024));
+}
+
+#[test]
+fn test_free_space_check() {
+    let temp_dir = TempDir::new().unwrap();
+    assert!(free_space(temp_dir.path()).unwrap() > 0);
+    assert!(ensure_free_space(temp_dir.path(), 0).is_ok());
+    assert!(ensure_free_space(temp_dir.path(), 1).is_ok());
+    assert!(ensure_free_space(temp_dir.path(), u64::MAX).is_err());
+}
+
+#[tokio::test]
+async fn test_recording_is_refused_without_free_space() {
+    demo_mode::set_enabled(true);
+
+    let temp_dir = TempDir::new().unwrap();
+    let database = Arc::new(Database::new(temp_dir.path().join("disk.db")).unwrap());
+    let recording_service = RecordingService::new(database, temp_dir.path().join("recordings")).unwrap();
+
+    recording_service.set_min_free_space(Some(u64::MAX)).await;
+    assert!(recording_service.start_recording().await.is_err());
+    assert!(recording_service.current_progress().await.is_none());
+}
+
+#[tokio::test]
+async fn test_recording_stops_when_space_runs_out() {
+    demo_mode::set_enabled(true);
+
+    let temp_dir = TempDir::new().unwrap();
+    let database = Arc::new(Database::new(temp_dir.path().join("disk.db")).unwrap());
+    let recording_service = RecordingService::new(database, temp_dir.path().join("recordings")).unwrap();
+    let mut receiver = recording_service.subscribe_low_disk_space();
+
+    recording_service.set_min_free_space(Some(1)).await;
+    recording_service.start_recording().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    // 録音中に下限を上げると、次の確認で録音が止まる
+    recording_service.set_min_free_space(Some(u64::MAX)).await;
+    let notice = tokio::time::timeout(Duration::from_secs(5), receiver.recv()).await.unwrap().unwrap();
+    assert!(notice.stopped);
+    assert_eq!(notice.min_free_bytes, u64::MAX);
+
+    // 止まった録音も保存できる
+    let recording = recording_service.stop_recording().await.unwrap();
+    assert!(std::path::Path::new(&recording.file_path).exists());
+    assert!(recording_service.stop_recording().await.is_err());
+}
@@ -0,0 +1,111 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::services::capture_source::{CaptureSource, DualSourceMode};
+use meeting_summarizer_lib::services::echo_cancellation::{block_len, cancel_echo};
+use meeting_summarizer_lib::services::{RecordingProfile, RecordingService};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+const SAMPLE_RATE: u32 = 16_000;
+
+/// 再現できる白色雑音（相手の声の代わり）
+fn noise(len: usize, seed: u32) -> Vec<f32> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 8) as f32 / (1u32 << 24) as f32 * 0.4 - 0.2
+        })
+        .collect()
+}
+
+/// スピーカーからマイクへの回り込み（40ms と 75ms の遅れ）
+fn echo_of(reference: &[f32]) -> Vec<f32> {
+    (0..reference.len())
+        .map(|index| {
+            let tap = |delay: usize, gain: f32| index.checked_sub(delay).map_or(0.0, |at| reference[at] * gain);
+            tap(640, 0.4) + tap(1_200, -0.15)
+        })
+        .collect()
+}
+
+fn power(samples: &[f32]) -> f32 {
+    samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len().max(1) as f32
+}
+
+fn db(ratio: f32) -> f32 {
+    10.0 * ratio.log10()
+}
+
+#[test]
+fn test_block_len() {
+    assert_eq!(block_len(16_000), 256);
+    assert_eq!(block_len(48_000), 1_024);
+}
+
+#[test]
+fn test_echo_is_removed_from_microphone() {
+    let seconds = 8;
+    let reference = noise(SAMPLE_RATE as usize * seconds, 7);
+    let mic = echo_of(&reference);
+
+    let cleaned = cancel_echo(&mic, &reference, SAMPLE_RATE);
+    assert_eq!(cleaned.len(), mic.len());
+
+    // 収束したあとの2秒で、回り込みが 20dB 以上小さくなる
+    let tail = mic.len() - SAMPLE_RATE as usize * 2;
+    let reduction = db(power(&mic[tail..]) / power(&cleaned[tail..]));
+    assert!(reduction > 20.0, "{:.1} dB", reduction);
+}
+
+#[test]
+fn test_near_end_speech_is_kept() {
+    let seconds = 10;
+    let len = SAMPLE_RATE as usize * seconds;
+    let reference = noise(len, 11);
+    let echo = echo_of(&reference);
+    // 後半の3秒だけこちらも話す
+    let near: Vec<f32> = (0..len)
+        .map(|index| {
+            if index >= len - SAMPLE_RATE as usize * 3 {
+                (2.0 * std::f32::consts::PI * 300.0 * index as f32 / SAMPLE_RATE as f32).sin() * 0.1
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let mic: Vec<f32> = echo.iter().zip(&near).map(|(echo, near)| echo + near).collect();
+
+    let cleaned = cancel_echo(&mic, &reference, SAMPLE_RATE);
+    let tail = len - SAMPLE_RATE as usize * 2;
+    let residual: Vec<f32> = cleaned[tail..].iter().zip(&near[tail..]).map(|(cleaned, near)| cleaned - near).collect();
+    // こちらの声はほぼそのまま残り、回り込みだけが小さくなる
+    let near_to_residual = db(power(&near[tail..]) / power(&residual));
+    assert!(near_to_residual > 10.0, "{:.1} dB", near_to_residual);
+
+    // 何も再生されていなければ、マイクの音声はそのまま
+    let silent = vec![0.0; len];
+    assert_eq!(cancel_echo(&near, &silent, SAMPLE_RATE), near);
+}
+
+#[tokio::test]
+async fn test_echo_cancellation_requires_dual_source() {
+    let temp_dir = TempDir::new().unwrap();
+    let database = Arc::new(Database::new(temp_dir.path().join("echo.db")).unwrap());
+    let recording_service = RecordingService::new(database, temp_dir.path().join("recordings")).unwrap();
+
+    // システム音声を録音しなければ参照する音声が無い
+    let microphone_only = RecordingProfile {
+        source: CaptureSource::Microphone,
+        echo_cancellation: true,
+        ..RecordingProfile::default()
+    };
+    assert!(recording_service.apply_recording_profile(&microphone_only).await.is_err());
+    assert!(!recording_service.echo_cancellation().await);
+
+    let dual = RecordingProfile {
+        dual_source: DualSourceMode::Mix,
+        ..microphone_only
+    };
+    recording_service.apply_recording_profile(&dual).await.unwrap();
+    assert!(recording_service.echo_cancellation().await);
+}
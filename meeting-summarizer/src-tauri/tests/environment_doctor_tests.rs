@@ -0,0 +1,58 @@
+use meeting_summarizer_lib::services::environment_doctor::{
+    check_models, check_python_version, check_writable, parse_python_version, CheckStatus, EnvironmentReport,
+};
+use tempfile::TempDir;
+
+#[test]
+fn test_parse_python_version() {
+    assert_eq!(parse_python_version("Python 3.11.4\n"), Some((3, 11, 4)));
+    assert_eq!(parse_python_version("Python 3.12.0rc1"), Some((3, 12, 0)));
+    assert_eq!(parse_python_version("Python 3.9"), Some((3, 9, 0)));
+    assert_eq!(parse_python_version("command not found"), None);
+}
+
+#[test]
+fn test_python_version_checks() {
+    assert_eq!(check_python_version("python3", Some("Python 3.10.2")).status, CheckStatus::Ok);
+
+    let old = check_python_version("python3", Some("Python 3.7.9"));
+    assert_eq!(old.status, CheckStatus::Error);
+    assert!(old.suggestion.unwrap().contains("3.8"));
+
+    let missing = check_python_version("python3", None);
+    assert_eq!(missing.status, CheckStatus::Error);
+    assert!(missing.suggestion.is_some());
+}
+
+#[test]
+fn test_model_check() {
+    let temp_dir = TempDir::new().unwrap();
+    assert_eq!(check_models(temp_dir.path(), "base").status, CheckStatus::Warning);
+
+    std::fs::write(temp_dir.path().join("tiny.pt"), b"").unwrap();
+    let missing = check_models(temp_dir.path(), "base");
+    assert_eq!(missing.status, CheckStatus::Warning);
+    assert!(missing.detail.contains("tiny"));
+
+    std::fs::write(temp_dir.path().join("base.pt"), b"").unwrap();
+    let found = check_models(temp_dir.path(), "base");
+    assert_eq!(found.status, CheckStatus::Ok);
+    assert_eq!(found.detail, "Downloaded: base, tiny");
+}
+
+#[test]
+fn test_writable_check_and_report_health() {
+    let temp_dir = TempDir::new().unwrap();
+    let writable = check_writable("model_cache", &temp_dir.path().join("cache"));
+    assert_eq!(writable.status, CheckStatus::Ok);
+    assert!(!temp_dir.path().join("cache").join(".write-test").exists());
+
+    // 通常ファイルの下にはディレクトリを作れない
+    let file = temp_dir.path().join("file");
+    std::fs::write(&file, b"").unwrap();
+    let blocked = check_writable("recordings_dir", &file.join("sub"));
+    assert_eq!(blocked.status, CheckStatus::Error);
+
+    assert!(EnvironmentReport::new(vec![writable.clone(), check_models(temp_dir.path(), "base")]).healthy);
+    assert!(!EnvironmentReport::new(vec![writable, blocked]).healthy);
+}
@@ -0,0 +1,74 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Chapter, Recording, Summary, Transcription, TranscriptSegment};
+use meeting_summarizer_lib::services::{export, html_export};
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_html_export_contains_seekable_transcript() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio_path = temp_dir.path().join("meeting.wav");
+    std::fs::write(&audio_path, b"RIFF-test-audio").unwrap();
+
+    let database = Database::in_memory().unwrap();
+    let mut recording = Recording::new("meeting.wav".to_string(), audio_path.to_string_lossy().to_string());
+    recording.title = Some("Budget <review>".to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let transcription = Transcription::new(recording.id.clone(), "full text".to_string(), "en".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+    let mut segment = TranscriptSegment::new(transcription.id.clone(), 0, 61_500, 64_000, "Let's start & review".to_string());
+    segment.speaker = Some("Alice".to_string());
+    database.create_transcript_segments(&[segment]).await.unwrap();
+
+    let summary = Summary::new(transcription.id.clone(), "llama3".to_string()).with_content(
+        "We reviewed the budget.".to_string(),
+        vec!["Budget approved".to_string()],
+        vec!["Send minutes".to_string()],
+    );
+    database.create_summary(&summary).await.unwrap();
+    database
+        .create_chapter(&Chapter::new(recording.id.clone(), "Opening".to_string(), 60_000))
+        .await
+        .unwrap();
+
+    let html = html_export::export_recording_html(&database, &recording.id, true).await.unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<html lang=\"en\">"));
+    assert!(html.contains("<h1>Budget &lt;review&gt;</h1>"));
+    assert!(html.contains("We reviewed the budget."));
+    assert!(html.contains("<li>Send minutes</li>"));
+    assert!(html.contains("data-start=\"61.500\" data-end=\"64.000\""));
+    assert!(html.contains("<span class=\"speaker\">Alice</span>Let&#39;s start &amp; review"));
+    assert!(html.contains("data-start=\"60.000\">[00:01:00] Opening"));
+    // "RIFF-test-audio" の base64
+    assert!(html.contains("src=\"data:audio/wav;base64,UklGRi10ZXN0LWF1ZGlv\""));
+
+    // 形式指定のエクスポートでは音声を含めない
+    let without_audio = export::export_recording(&database, &recording.id, "html").await.unwrap();
+    assert!(!without_audio.contains("<audio"));
+    assert!(without_audio.contains("class=\"seg seek\""));
+}
+
+#[tokio::test]
+async fn test_html_export_estimates_timestamps_without_segments() {
+    let database = Database::in_memory().unwrap();
+    let mut recording = Recording::new("meeting.wav".to_string(), "/tmp/meeting.wav".to_string());
+    recording.duration = Some(13);
+    database.create_recording(&recording).await.unwrap();
+
+    // 時刻の無い書き起こし（取り込んだテキストなど）
+    let transcription = Transcription::new(recording.id.clone(), "始めます。議題は予算です。".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+
+    let html = html_export::export_recording_html(&database, &recording.id, false).await.unwrap();
+    assert!(html.contains("data-start=\"0.000\" data-end=\"5.000\"><span class=\"ts\">~00:00:00</span>始めます。"));
+    assert!(html.contains("data-start=\"5.000\" data-end=\"13.000\"><span class=\"ts\">~00:00:05</span>議題は予算です。"));
+}
+
+#[test]
+fn test_estimated_segments_split_on_sentence_endings() {
+    let segments = html_export::estimated_segments("Version 2.5 is out. Ship it!\nThanks", 30_000);
+    let texts: Vec<&str> = segments.iter().map(|segment| segment.text.as_str()).collect();
+    assert_eq!(texts, vec!["Version 2.5 is out.", "Ship it!", "Thanks"]);
+    assert_eq!(segments.last().unwrap().end_ms, 30_000);
+}
@@ -0,0 +1,53 @@
+use meeting_summarizer_lib::errors::AppError;
+use meeting_summarizer_lib::services::i18n::{self, missing_keys, tr_in, Locale};
+
+#[test]
+fn test_catalogs_have_same_keys() {
+    let missing = missing_keys();
+    assert!(missing.is_empty(), "missing catalog entries: {:?}", missing);
+}
+
+#[test]
+fn test_placeholders_and_fallback() {
+    assert_eq!(
+        tr_in(Locale::Ja, "summarization.connected", &[("model", &"llama3")]),
+        "llama3に接続済み"
+    );
+    assert_eq!(
+        tr_in(Locale::En, "summarization.connected", &[("model", &"llama3")]),
+        "Connected to llama3"
+    );
+    // 未登録のキーはキー自体を返す
+    assert_eq!(tr_in(Locale::Ja, "no.such.key", &[]), "no.such.key");
+}
+
+#[test]
+fn test_locale_parsing() {
+    assert_eq!("ja-JP".parse::<Locale>().unwrap(), Locale::Ja);
+    assert_eq!("en_US".parse::<Locale>().unwrap(), Locale::En);
+    assert_eq!("EN".parse::<Locale>().unwrap(), Locale::En);
+    assert!("fr".parse::<Locale>().is_err());
+}
+
+#[test]
+fn test_error_messages_follow_locale() {
+    let error = || AppError::FileNotFound {
+        path: "recordings/abc".to_string(),
+    };
+
+    i18n::set_locale(Locale::En);
+    assert_eq!(String::from(error()), "File not found: recordings/abc");
+
+    i18n::set_locale(Locale::Ja);
+    assert_eq!(String::from(error()), "ファイルが見つかりません: recordings/abc");
+
+    // パス関連の詳細は隠蔽される
+    let hidden: String = AppError::InvalidPath {
+        message: "/etc/passwd".to_string(),
+    }
+    .into();
+    assert!(!hidden.contains("/etc/passwd"));
+
+    // Display（ログ用）はロケールに関係なく英語のまま
+    assert_eq!(error().to_string(), "File not found: recordings/abc");
+}
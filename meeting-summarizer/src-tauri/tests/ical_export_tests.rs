@@ -0,0 +1,34 @@
+use chrono::NaiveDate;
+use meeting_summarizer_lib::models::Summary;
+use meeting_summarizer_lib::services::ical::{extract_due_date, render_action_items};
+
+#[test]
+fn test_extract_due_date_formats() {
+    let expected = NaiveDate::from_ymd_opt(2024, 5, 10);
+    assert_eq!(extract_due_date("Send the report by 2024-05-10"), expected);
+    assert_eq!(extract_due_date("資料を共有する（期限: 2024/5/10）"), expected);
+    assert_eq!(extract_due_date("田中さん: 2024年5月10日までに見積もり提出"), expected);
+    assert_eq!(extract_due_date("Follow up with the vendor"), None);
+}
+
+#[test]
+fn test_render_action_items_ics() {
+    let summary = Summary::new("transcription-1".to_string(), "test-model".to_string()).with_content(
+        "summary".to_string(),
+        vec![],
+        vec![
+            "Send the report by 2024-05-10".to_string(),
+            "Book a room, projector; snacks".to_string(),
+        ],
+    );
+
+    let ics = render_action_items(&[summary], Some("Weekly Sync"));
+
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert_eq!(ics.matches("BEGIN:VTODO").count(), 2);
+    // 期限付きのアイテムのみVEVENTを持つ
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+    assert!(ics.contains("DUE;VALUE=DATE:20240510"));
+    assert!(ics.contains(r"SUMMARY:Book a room\, projector\; snacks"));
+}
@@ -0,0 +1,70 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::services::input_gain::{GainStage, AUTO_GAIN_TARGET_PEAK, OUTPUT_LIMIT};
+use meeting_summarizer_lib::services::{AppSettings, InputGainSettings, RecordingService};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn fixed(gain: f32) -> InputGainSettings {
+    InputGainSettings { gain, auto_gain: false }
+}
+
+#[test]
+fn test_settings_validation() {
+    assert!(InputGainSettings::default().validate().is_ok());
+    assert!(fixed(1.0).validate().is_ok());
+    assert!(fixed(0.0).validate().is_err());
+    assert!(fixed(20.0).validate().is_err());
+    assert!(fixed(f32::NAN).validate().is_err());
+
+    // 以前の設定ファイルには無いので既定値になる
+    let settings: AppSettings = serde_json::from_str("{}").unwrap();
+    assert_eq!(settings.input_gain, InputGainSettings::default());
+}
+
+#[test]
+fn test_fixed_gain_is_applied_and_limited() {
+    let mut stage = GainStage::new(fixed(1.0));
+    let mut samples = vec![0.5, -0.8, 0.00005];
+    stage.process(&mut samples);
+    assert_eq!(samples, vec![0.5, -0.8, 0.00005]);
+
+    stage.update(fixed(3.0));
+    let mut samples = vec![0.1, 0.5, -0.5];
+    stage.process(&mut samples);
+    assert!((samples[0] - 0.3).abs() < 1e-6);
+    assert_eq!(samples[1], OUTPUT_LIMIT);
+    assert_eq!(samples[2], -OUTPUT_LIMIT);
+}
+
+#[test]
+fn test_auto_gain_follows_input_level() {
+    let mut stage = GainStage::new(InputGainSettings { gain: 1.0, auto_gain: true });
+
+    // 小さな声はゆっくり持ち上げる
+    for _ in 0..200 {
+        let mut quiet = vec![0.05, -0.05];
+        stage.process(&mut quiet);
+    }
+    assert!(stage.gain() > 5.0);
+
+    // 大きな声にはすぐに下げる
+    for _ in 0..10 {
+        let mut loud = vec![0.9, -0.9];
+        stage.process(&mut loud);
+    }
+    assert!(stage.gain() < 1.0);
+    let mut loud = vec![0.9, -0.9];
+    stage.process(&mut loud);
+    assert!(loud[0] <= AUTO_GAIN_TARGET_PEAK + 0.05);
+}
+
+#[tokio::test]
+async fn test_recording_service_input_gain() {
+    let temp_dir = TempDir::new().unwrap();
+    let database = Arc::new(Database::new(temp_dir.path().join("gain.db")).unwrap());
+    let recording_service = RecordingService::new(database, temp_dir.path().join("recordings")).unwrap();
+
+    assert_eq!(recording_service.input_gain().await, InputGainSettings::default());
+    recording_service.set_input_gain(fixed(1.5)).await;
+    assert_eq!(recording_service.input_gain().await, fixed(1.5));
+}
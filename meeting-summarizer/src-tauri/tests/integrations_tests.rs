@@ -0,0 +1,75 @@
+use meeting_summarizer_lib::models::{Summary, Transcription};
+use meeting_summarizer_lib::services::confluence::build_storage_body;
+use meeting_summarizer_lib::services::google_docs::build_document_requests;
+use meeting_summarizer_lib::services::ConfluenceSettings;
+use meeting_summarizer_lib::services::teams::build_adaptive_card;
+
+fn sample_summary() -> Summary {
+    Summary::new("transcription-1".to_string(), "llama3".to_string()).with_content(
+        "週次の進捗を確認した".to_string(),
+        vec!["リリースは予定通り".to_string()],
+        vec!["田中: テスト計画を共有".to_string()],
+    )
+}
+
+#[test]
+fn test_google_docs_requests_use_utf16_indices() {
+    let requests = build_document_requests("定例会議", &sample_summary());
+
+    let inserted = requests[0]["insertText"]["text"].as_str().unwrap();
+    assert!(inserted.starts_with("定例会議\nSummary\n"));
+
+    // タイトル段落は "定例会議\n" = UTF-16で5単位
+    let title_range = &requests[1]["updateParagraphStyle"]["range"];
+    assert_eq!(title_range["startIndex"], 1);
+    assert_eq!(title_range["endIndex"], 6);
+    assert_eq!(requests[1]["updateParagraphStyle"]["paragraphStyle"]["namedStyleType"], "TITLE");
+}
+
+#[test]
+fn test_teams_card_has_collapsible_transcript() {
+    let summary = sample_summary();
+    let transcription = Transcription::new("recording-1".to_string(), "全文".to_string(), "ja".to_string());
+
+    let card = build_adaptive_card("定例会議", &summary, Some(&transcription), Some("https://example.com/t/1"));
+
+    let body = card["body"].as_array().unwrap();
+    let transcript = body.iter().find(|item| item["id"] == "transcript").unwrap();
+    assert_eq!(transcript["isVisible"], false);
+
+    let actions = card["actions"].as_array().unwrap();
+    assert_eq!(actions[0]["type"], "Action.ToggleVisibility");
+    assert_eq!(actions[1]["url"], "https://example.com/t/1");
+}
+
+#[test]
+fn test_confluence_body_maps_action_items_to_tasks() {
+    let mut summary = sample_summary();
+    summary.key_points.push("<b>予算</b> & 体制".to_string());
+
+    let body = build_storage_body(&summary);
+
+    assert!(body.contains(r#"<ac:structured-macro ac:name="panel"><ac:parameter ac:name="title">Key Points</ac:parameter>"#));
+    assert!(body.contains("<li>&lt;b&gt;予算&lt;/b&gt; &amp; 体制</li>"));
+    assert!(body.contains(
+        "<ac:task><ac:task-id>1</ac:task-id><ac:task-status>incomplete</ac:task-status><ac:task-body>田中: テスト計画を共有</ac:task-body></ac:task>"
+    ));
+}
+
+#[test]
+fn test_confluence_settings_require_https_and_space() {
+    let mut settings = ConfluenceSettings {
+        base_url: "https://example.atlassian.net/wiki".to_string(),
+        username: "me@example.com".to_string(),
+        space_key: "TEAM".to_string(),
+        parent_page_id: None,
+    };
+    assert!(settings.validate().is_ok());
+
+    settings.base_url = "http://example.atlassian.net/wiki".to_string();
+    assert!(settings.validate().is_err());
+
+    settings.base_url = "https://example.atlassian.net/wiki".to_string();
+    settings.space_key = " ".to_string();
+    assert!(settings.validate().is_err());
+}
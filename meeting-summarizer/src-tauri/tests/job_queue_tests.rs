@@ -0,0 +1,94 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Job, JobPayload, JobStatus, Recording};
+use meeting_summarizer_lib::services::{JobQueue, LLMModelManager, ModelSettingsManager, OllamaPool, Summarizer, WhisperService};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::sync::Mutex;
+
+fn summarizer(database: &Arc<Database>) -> Arc<Summarizer> {
+    // 設定ファイルは読み書きしない（既定の設定で要約する）
+    Arc::new(Summarizer::new(
+        database.clone(),
+        Arc::new(Mutex::new(ModelSettingsManager::new(PathBuf::from("model_settings.json")))),
+        Arc::new(Mutex::new(LLMModelManager::new())),
+        Arc::new(OllamaPool::new()),
+    ))
+}
+
+fn export_payload(recording_id: &str, output_path: &str) -> JobPayload {
+    JobPayload::Export {
+        recording_id: recording_id.to_string(),
+        format: "json".to_string(),
+        output_path: output_path.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_claim_respects_priority_and_requeue() {
+    let database = Database::in_memory().unwrap();
+
+    let low = Job::new(export_payload("a", "/tmp/a.json"));
+    let high = Job::new(export_payload("b", "/tmp/b.json")).with_priority(10);
+    database.create_job(&low).await.unwrap();
+    database.create_job(&high).await.unwrap();
+
+    let claimed = database.claim_next_job().await.unwrap().unwrap();
+    assert_eq!(claimed.id, high.id);
+    assert_eq!(claimed.status, JobStatus::Running);
+    assert_eq!(claimed.attempts, 1);
+
+    // 再起動時に実行中ジョブは pending に戻る
+    assert_eq!(database.requeue_interrupted_jobs().await.unwrap(), 1);
+    let requeued = database.get_job(&high.id).await.unwrap().unwrap();
+    assert_eq!(requeued.status, JobStatus::Pending);
+    assert_eq!(requeued.attempts, 0);
+}
+
+#[tokio::test]
+async fn test_job_queue_runs_export_job() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let database = Arc::new(Database::new(temp_dir.path().join("jobs.db")).unwrap());
+    let recording = Recording::new("job.wav".to_string(), "/tmp/job.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let whisper_service = Arc::new(WhisperService::new(temp_dir.path().join("model.bin"), temp_dir.path().to_path_buf()));
+    let queue = Arc::new(JobQueue::new(database.clone(), whisper_service, summarizer(&database)));
+    queue.start(1).await.unwrap();
+
+    let output_path = temp_dir.path().join("export.json");
+    let job = queue
+        .enqueue(Job::new(export_payload(&recording.id, &output_path.to_string_lossy())))
+        .await
+        .unwrap();
+
+    let mut status = JobStatus::Pending;
+    for _ in 0..50 {
+        status = queue.get_job(&job.id).await.unwrap().unwrap().status;
+        if status == JobStatus::Completed {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(status, JobStatus::Completed);
+    assert!(output_path.exists());
+
+    // 完了済みジョブはキャンセル不可、存在しない録音のジョブは失敗後にリトライ可能
+    assert!(queue.cancel_job(&job.id).await.is_err());
+
+    let failing = queue
+        .enqueue(Job::new(export_payload("missing", "/tmp/missing.json")).with_max_attempts(1))
+        .await
+        .unwrap();
+    for _ in 0..50 {
+        status = queue.get_job(&failing.id).await.unwrap().unwrap().status;
+        if status == JobStatus::Failed {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(status, JobStatus::Failed);
+    let retried = queue.retry_job(&failing.id).await.unwrap();
+    assert_eq!(retried.status, JobStatus::Pending);
+}
@@ -0,0 +1,88 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, Transcription, WatchWord};
+use meeting_summarizer_lib::services::keyword_alerts::{self, find_keyword};
+
+#[test]
+fn test_find_keyword() {
+    let (count, snippet) = find_keyword("来期のBudgetについて。budget案は再提出、予算は据え置き", "budget").unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(snippet, "来期のBudgetについて。budget案は再提出、予算は据え置き");
+
+    // 英字の語は単語単位で一致させる
+    assert!(find_keyword("The lawsuits were settled", "lawsuit").is_none());
+    assert_eq!(find_keyword("予算と予算案", "予算").unwrap().0, 2);
+
+    let long_text = format!("{}訴訟{}", "あ".repeat(40), "い".repeat(40));
+    let (_, snippet) = find_keyword(&long_text, "訴訟").unwrap();
+    assert_eq!(snippet, format!("…{}訴訟{}…", "あ".repeat(30), "い".repeat(30)));
+}
+
+#[tokio::test]
+async fn test_new_transcription_is_scanned_tagged_and_alerted() {
+    let database = Database::in_memory().unwrap();
+    let mut receiver = database.subscribe_transcriptions();
+
+    database.create_watch_word(&WatchWord::new("予算".to_string())).await.unwrap();
+    database
+        .create_watch_word(&WatchWord::new("Acme".to_string()).with_tag("customer:acme".to_string()))
+        .await
+        .unwrap();
+    let mut disabled = WatchWord::new("訴訟".to_string());
+    disabled.enabled = false;
+    database.create_watch_word(&disabled).await.unwrap();
+
+    let recording = Recording::new("weekly.wav".to_string(), "/tmp/weekly.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(
+        recording.id.clone(),
+        "acme社の予算について。訴訟リスクも確認した。".to_string(),
+        "ja".to_string(),
+    );
+    database.create_transcription(&transcription).await.unwrap();
+
+    // 作成された書き起こしが通知される
+    let created = receiver.recv().await.unwrap();
+    assert_eq!(created.id, transcription.id);
+
+    let alerts = keyword_alerts::scan_transcription(&database, &created).await.unwrap();
+    let mut words: Vec<_> = alerts.iter().map(|alert| alert.word.as_str()).collect();
+    words.sort();
+    assert_eq!(words, vec!["Acme", "予算"]);
+
+    let tagged = database.get_recording(&recording.id).await.unwrap().unwrap();
+    assert!(tagged.tags.contains(&"watch:予算".to_string()));
+    assert!(tagged.tags.contains(&"customer:acme".to_string()));
+
+    // 同じ書き起こしの再走査では重複して通知しない
+    assert!(keyword_alerts::rescan_recording(&database, &recording.id).await.unwrap().is_empty());
+
+    let unread = database.get_keyword_alerts(true, 10).await.unwrap();
+    assert_eq!(unread.len(), 2);
+    let ids: Vec<String> = unread.iter().map(|alert| alert.id.clone()).collect();
+    assert_eq!(database.acknowledge_keyword_alerts(&ids[..1]).await.unwrap(), 1);
+    assert_eq!(database.get_keyword_alerts(true, 10).await.unwrap().len(), 1);
+    assert_eq!(database.get_keyword_alerts(false, 10).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_adding_tags_keeps_other_edits() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("weekly.wav".to_string(), "/tmp/weekly.wav".to_string())
+        .with_tags(vec!["weekly".to_string()]);
+    database.create_recording(&recording).await.unwrap();
+
+    // 走査中に画面でタイトルを変えても、タグの追加で元に戻さない
+    let mut edited = database.get_recording(&recording.id).await.unwrap().unwrap();
+    edited.title = Some("週次定例".to_string());
+    database.update_recording(&edited).await.unwrap();
+
+    let tags = vec!["watch:予算".to_string(), "weekly".to_string()];
+    assert!(database.add_recording_tags(&recording.id, &tags).await.unwrap());
+    assert!(!database.add_recording_tags(&recording.id, &tags).await.unwrap());
+    assert!(!database.add_recording_tags("missing", &tags).await.unwrap());
+
+    let tagged = database.get_recording(&recording.id).await.unwrap().unwrap();
+    assert_eq!(tagged.title.as_deref(), Some("週次定例"));
+    assert_eq!(tagged.tags, vec!["weekly".to_string(), "watch:予算".to_string()]);
+    assert!(database.get_all_tags().await.unwrap().contains(&"watch:予算".to_string()));
+}
@@ -0,0 +1,63 @@
+use chrono::{Duration, Utc};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, TranscriptSegment, Transcription};
+use meeting_summarizer_lib::services::keyword_timeline::{
+    density, occurrences_in_segments, recording_timeline, timelines_in_range,
+};
+
+fn segment(transcription_id: &str, index: i32, start_ms: i64, end_ms: i64, text: &str) -> TranscriptSegment {
+    TranscriptSegment::new(transcription_id.to_string(), index, start_ms, end_ms, text.to_string())
+}
+
+#[test]
+fn test_occurrences_are_interpolated_within_segment() {
+    let segments = vec![
+        segment("t", 0, 0, 10_000, "予算の話です予算"),
+        segment("t", 1, 20_000, 30_000, "Budget review"),
+    ];
+
+    let occurrences = occurrences_in_segments(&segments, "予算");
+    assert_eq!(occurrences.iter().map(|o| o.offset_ms).collect::<Vec<_>>(), vec![0, 7_500]);
+
+    assert_eq!(occurrences_in_segments(&segments, "budget")[0].offset_ms, 20_000);
+}
+
+#[test]
+fn test_density_counts_per_bucket() {
+    let segments = vec![segment("t", 0, 0, 1_000, "予算"), segment("t", 1, 59_000, 60_000, "予算 予算")];
+    let occurrences = occurrences_in_segments(&segments, "予算");
+
+    assert_eq!(density(&occurrences, 60_000, 4), vec![1, 0, 0, 2]);
+    assert_eq!(density(&occurrences, 0, 4), vec![0, 0, 0, 0]);
+}
+
+#[tokio::test]
+async fn test_timelines_across_recordings_in_range() {
+    let database = Database::in_memory().unwrap();
+    let mut ids = Vec::new();
+    for (days_ago, text) in [(10, "予算を確認"), (3, "予算は据え置き"), (1, "雑談のみ")] {
+        let mut recording = Recording::new(format!("{}.wav", days_ago), format!("/tmp/timeline_{}.wav", days_ago));
+        recording.created_at = Utc::now() - Duration::days(days_ago);
+        recording.duration = Some(60);
+        database.create_recording(&recording).await.unwrap();
+        let transcription = Transcription::new(recording.id.clone(), text.to_string(), "ja".to_string());
+        database.create_transcription(&transcription).await.unwrap();
+        database
+            .create_transcript_segments(&[segment(&transcription.id, 0, 30_000, 40_000, text)])
+            .await
+            .unwrap();
+        ids.push(recording.id);
+    }
+
+    let timeline = recording_timeline(&database, &ids[0], "予算", 6).await.unwrap().unwrap();
+    assert_eq!(timeline.duration_ms, 60_000);
+    assert_eq!(timeline.density, vec![0, 0, 0, 1, 0, 0]);
+
+    let timelines = timelines_in_range(&database, "予算", Some(Utc::now() - Duration::days(5)), None, 10)
+        .await
+        .unwrap();
+    assert_eq!(timelines.len(), 1);
+    assert_eq!(timelines[0].recording_id, ids[1]);
+
+    assert!(recording_timeline(&database, &ids[0], " ", 6).await.is_err());
+}
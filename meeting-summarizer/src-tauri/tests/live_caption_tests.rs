@@ -0,0 +1,64 @@
+use meeting_summarizer_lib::services::live_captions::{split_caption_text, LiveCaptionSession};
+use meeting_summarizer_lib::services::{CaptionHypothesis, LiveCaptionHub};
+
+fn hypothesis(segment_index: u64, text: &str, is_final: bool) -> CaptionHypothesis {
+    CaptionHypothesis {
+        segment_index,
+        start_ms: 0,
+        end_ms: 4_000,
+        text: text.to_string(),
+        is_final,
+    }
+}
+
+#[test]
+fn test_text_is_split_at_natural_breaks() {
+    let chunks = split_caption_text("今日は来期の予算について話します。まず営業部から説明をお願いします。", 20);
+    assert_eq!(chunks, vec!["今日は来期の予算について話します。", "まず営業部から説明をお願いします。"]);
+    assert!(split_caption_text("one two three four five six", 10).iter().all(|c| c.chars().count() <= 10));
+    assert!(split_caption_text("   ", 10).is_empty());
+}
+
+#[test]
+fn test_corrections_reuse_caption_ids() {
+    let mut session = LiveCaptionSession::new("s1".to_string()).with_max_chars(12);
+
+    let first = session.apply(&hypothesis(0, "hello world again and more", false));
+    assert_eq!(first.len(), 3);
+    assert!(first.iter().all(|e| e.revision == 1 && !e.is_final));
+    assert_eq!(first[0].start_ms, 0);
+    assert_eq!(first.last().unwrap().end_ms, 4_000);
+
+    // 訂正で短くなった場合、同じIDで置き換え、余ったチャンクは removed で通知
+    let corrected = session.apply(&hypothesis(0, "hello word", true));
+    assert_eq!(corrected[0].caption_id, first[0].caption_id);
+    assert_eq!(corrected[0].text, "hello word");
+    assert_eq!(corrected[0].revision, 2);
+    let removed: Vec<_> = corrected.iter().filter(|e| e.removed).map(|e| e.caption_id.clone()).collect();
+    assert_eq!(removed, vec![first[1].caption_id.clone(), first[2].caption_id.clone()]);
+
+    // 変化が無ければイベントは出ない
+    assert!(session.apply(&hypothesis(0, "hello word", true)).is_empty());
+
+    session.apply(&hypothesis(1, "next", false));
+    let captions: Vec<_> = session.captions().into_iter().map(|e| e.text).collect();
+    assert_eq!(captions, vec!["hello word", "next"]);
+}
+
+#[tokio::test]
+async fn test_hub_broadcasts_to_subscribers() {
+    let hub = LiveCaptionHub::new();
+    assert!(hub.publish(&hypothesis(0, "no session", false)).is_err());
+
+    let mut receiver = hub.subscribe();
+    let session_id = hub.start_session();
+    hub.publish(&hypothesis(0, "hello", false)).unwrap();
+
+    let event = receiver.recv().await.unwrap();
+    assert_eq!(event.session_id, session_id);
+    assert_eq!(event.text, "hello");
+    assert_eq!(hub.captions().len(), 1);
+
+    assert_eq!(hub.end_session(), Some(session_id));
+    assert!(hub.captions().is_empty());
+}
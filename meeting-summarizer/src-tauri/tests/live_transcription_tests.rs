@@ -0,0 +1,205 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{CorrectionEntry, Recording};
+use meeting_summarizer_lib::services::capture_feed::{AudioChunk, FEED_SAMPLE_RATE};
+use meeting_summarizer_lib::services::live_transcription::{LiveSegmenter, LiveSource};
+use meeting_summarizer_lib::services::{demo_mode, LiveCaptionHub, LiveTranscriber, RecordingService, WhisperService};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tempfile::TempDir;
+
+/// `start_ms` から `secs` 秒の、最後の `silent_secs` 秒だけ無音のチャンク
+fn chunk(index: u64, start_ms: i64, secs: f32, silent_secs: f32, is_last: bool) -> AudioChunk {
+    let frames = (FEED_SAMPLE_RATE as f32 * secs) as usize;
+    let silent = (FEED_SAMPLE_RATE as f32 * silent_secs) as usize;
+    let samples = (0..frames)
+        .map(|frame| {
+            if frame >= frames - silent {
+                0.0
+            } else {
+                0.3 * (frame as f32 * 0.05).sin()
+            }
+        })
+        .collect();
+    AudioChunk { index, start_ms, samples, is_last }
+}
+
+#[test]
+fn test_segment_grows_until_trailing_silence() {
+    let mut segmenter = LiveSegmenter::new();
+
+    // 話し続けている間は同じ発言が伸び、途中経過では新しいチャンクだけを書き起こす
+    let first = segmenter.push(&chunk(0, 0, 5.0, 0.0, false));
+    assert_eq!((first.segment_index, first.start_ms, first.end_ms, first.is_final), (0, 0, 5_000, false));
+    assert_eq!(first.samples.len(), 5 * FEED_SAMPLE_RATE as usize);
+    // 確定したら発言全体を書き起こし直す
+    let second = segmenter.push(&chunk(1, 5_000, 5.0, 1.0, false));
+    assert_eq!((second.segment_index, second.start_ms, second.end_ms, second.is_final), (0, 0, 10_000, true));
+    assert_eq!(second.samples.len(), 10 * FEED_SAMPLE_RATE as usize);
+
+    // 無音で区切った後は次の発言になる
+    let third = segmenter.push(&chunk(2, 10_000, 5.0, 0.0, false));
+    assert_eq!((third.segment_index, third.start_ms, third.is_final), (1, 10_000, false));
+}
+
+#[test]
+fn test_segment_is_final_when_long_or_last() {
+    let mut segmenter = LiveSegmenter::new();
+    for index in 0..3 {
+        let partial = segmenter.push(&chunk(index, index as i64 * 5_000, 5.0, 0.0, false));
+        assert!(!partial.is_final);
+        assert_eq!(partial.samples.len(), 5 * FEED_SAMPLE_RATE as usize);
+    }
+    let long = segmenter.push(&chunk(3, 15_000, 5.0, 0.0, false));
+    assert!(long.is_final);
+    assert_eq!((long.start_ms, long.end_ms), (0, 20_000));
+    assert_eq!(long.samples.len(), 20 * FEED_SAMPLE_RATE as usize);
+
+    let last = segmenter.push(&chunk(4, 20_000, 2.0, 0.0, true));
+    assert!(last.is_final);
+    assert_eq!((last.segment_index, last.start_ms, last.end_ms), (1, 20_000, 22_000));
+}
+
+#[tokio::test]
+async fn test_final_segments_are_saved_with_the_recording() {
+    demo_mode::set_enabled(true);
+    let temp_dir = TempDir::new().unwrap();
+    let recordings_dir = temp_dir.path().join("recordings");
+    let database = Arc::new(Database::new(temp_dir.path().join("live.db")).unwrap());
+    let recording_service = RecordingService::new(database.clone(), recordings_dir.clone()).unwrap();
+    let whisper = Arc::new(WhisperService::new(temp_dir.path().join("model.bin"), recordings_dir.clone()));
+    let captions = Arc::new(LiveCaptionHub::new());
+    let caption_session = captions.start_session();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let events = received.clone();
+    let transcriber = Arc::new(LiveTranscriber::new());
+    let recording_session_id = recording_service.start_recording().await.unwrap();
+    let session_id = transcriber.start(
+        LiveSource {
+            recording_session_id: recording_session_id.clone(),
+            chunks: recording_service.subscribe_audio_chunks(),
+        },
+        database.clone(),
+        whisper,
+        Some(captions.clone()),
+        None,
+        move |segment| events.lock().unwrap().push(segment.clone()),
+    );
+    tokio::time::sleep(Duration::from_millis(1_000)).await;
+    let mut stopped = recording_service.subscribe_stopped_recordings();
+    let recording = recording_service.stop_recording().await.unwrap();
+    let stopped = stopped.recv().await.unwrap();
+    assert_eq!((stopped.session_id.as_str(), stopped.recording.id.as_str()), (recording_session_id.as_str(), recording.id.as_str()));
+
+    // 別の録音には保存しない
+    assert!(transcriber.finish(&database, "another-session", &recording.id).await.unwrap().is_none());
+    assert!(transcriber.session_id().is_some());
+
+    let transcription = transcriber.finish(&database, &stopped.session_id, &recording.id).await.unwrap().unwrap();
+    assert_eq!(transcription.recording_id, recording.id);
+    assert!(transcription.text.contains("定例会議"));
+    assert!(transcriber.session_id().is_none());
+
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].session_id, session_id);
+    assert!(received[0].is_final);
+
+    let segments = database.get_segments_by_transcription(&transcription.id).await.unwrap();
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].start_ms, 0);
+    assert_eq!(segments[0].end_ms, received[0].end_ms);
+
+    // 開いている字幕にも流れる
+    let captions = captions.captions();
+    assert!(!captions.is_empty());
+    assert!(captions.iter().all(|caption| caption.session_id == caption_session && caption.is_final));
+
+    // 作業用のファイルは残さない
+    let leftovers = std::fs::read_dir(recordings_dir.join("converted"))
+        .map(|entries| entries.filter_map(Result::ok).filter(|entry| entry.file_name().to_string_lossy().starts_with("live-")).count())
+        .unwrap_or(0);
+    assert_eq!(leftovers, 0);
+}
+
+#[tokio::test]
+async fn test_finish_without_session_saves_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    let database = Database::new(temp_dir.path().join("idle.db")).unwrap();
+    let transcriber = LiveTranscriber::new();
+    assert!(transcriber.finish(&database, "missing", "missing").await.unwrap().is_none());
+    assert!(!transcriber.stop());
+    assert!(transcriber.segments().is_empty());
+}
+
+#[tokio::test]
+async fn test_lagged_session_transcribes_the_whole_recording() {
+    demo_mode::set_enabled(true);
+    let temp_dir = TempDir::new().unwrap();
+    let recordings_dir = temp_dir.path().join("recordings");
+    let database = Arc::new(Database::new(temp_dir.path().join("lagged.db")).unwrap());
+    let recording_service = RecordingService::new(database.clone(), recordings_dir.clone()).unwrap();
+    let whisper = Arc::new(WhisperService::new(temp_dir.path().join("model.bin"), recordings_dir.clone()));
+
+    let recording_session_id = recording_service.start_recording().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let recording = recording_service.stop_recording().await.unwrap();
+
+    // 書き起こしが始まる前に溢れたチャンクは届かない
+    let (sender, chunks) = broadcast::channel(1);
+    sender.send(chunk(0, 0, 5.0, 0.0, false)).unwrap();
+    sender.send(chunk(1, 5_000, 5.0, 1.0, true)).unwrap();
+    let transcriber = Arc::new(LiveTranscriber::new());
+    transcriber.start(
+        LiveSource { recording_session_id: recording_session_id.clone(), chunks },
+        database.clone(),
+        whisper,
+        None,
+        None,
+        |_| {},
+    );
+
+    let transcription = transcriber.finish(&database, &recording_session_id, &recording.id).await.unwrap().unwrap();
+    assert_eq!(transcription.recording_id, recording.id);
+    assert!(!transcription.model_used.unwrap_or_default().contains("(live)"));
+    assert_eq!(database.get_transcriptions_by_recording(&recording.id).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_corrections_are_counted_for_final_segments_only() {
+    demo_mode::set_enabled(true);
+    let temp_dir = TempDir::new().unwrap();
+    let recordings_dir = temp_dir.path().join("recordings");
+    let database = Arc::new(Database::new(temp_dir.path().join("corrections.db")).unwrap());
+    let whisper = Arc::new(WhisperService::new(temp_dir.path().join("model.bin"), recordings_dir.clone()));
+    let recording = Recording::new("live.wav".to_string(), recordings_dir.join("live.wav").to_string_lossy().to_string());
+    database.create_recording(&recording).await.unwrap();
+    let entry = CorrectionEntry::new("リリース".to_string(), "ローンチ".to_string());
+    database.create_correction_entry(&entry).await.unwrap();
+
+    // 途中経過と確定の2回書き起こす
+    let (sender, chunks) = broadcast::channel(4);
+    sender.send(chunk(0, 0, 5.0, 0.0, false)).unwrap();
+    sender.send(chunk(1, 5_000, 5.0, 1.0, true)).unwrap();
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let events = received.clone();
+    let transcriber = Arc::new(LiveTranscriber::new());
+    transcriber.start(
+        LiveSource { recording_session_id: "live-session".to_string(), chunks },
+        database.clone(),
+        whisper,
+        None,
+        None,
+        move |segment| events.lock().unwrap().push(segment.clone()),
+    );
+    transcriber.finish(&database, "live-session", &recording.id).await.unwrap().unwrap();
+
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.iter().map(|segment| segment.is_final).collect::<Vec<_>>(), vec![false, true]);
+    assert!(received.iter().all(|segment| segment.text.contains("ローンチ") && !segment.text.contains("リリース")));
+
+    // デモの書き起こしには「リリース」が4回出てくるが、数えるのは確定した発言の分だけ
+    let entries = database.get_correction_entries().await.unwrap();
+    assert_eq!(entries[0].hit_count, 4);
+}
@@ -0,0 +1,90 @@
+use meeting_summarizer_lib::models::{LLMAuth, LLMConfig, LLMConnection};
+use meeting_summarizer_lib::services::LLMService;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+
+fn remote(base_url: &str, connection: LLMConnection) -> LLMConfig {
+    LLMConfig {
+        base_url: base_url.to_string(),
+        timeout_seconds: 5,
+        connection,
+        ..LLMConfig::default()
+    }
+}
+
+fn bearer() -> Option<LLMAuth> {
+    Some(LLMAuth::Bearer { token: "secret".to_string() })
+}
+
+#[test]
+fn test_credentials_require_https_for_remote_hosts() {
+    let plain = remote("http://ollama.example.com:11434", LLMConnection { auth: bearer(), ..Default::default() });
+    assert!(LLMService::validate_connection(&plain).is_err());
+
+    let local = remote("http://localhost:11434", LLMConnection { auth: bearer(), ..Default::default() });
+    assert!(LLMService::validate_connection(&local).is_ok());
+
+    let tls = remote("https://ollama.example.com", LLMConnection { auth: bearer(), ..Default::default() });
+    assert!(LLMService::try_new(tls).is_ok());
+
+    assert!(LLMService::validate_connection(&remote("ftp://ollama.example.com", LLMConnection::default())).is_err());
+}
+
+#[test]
+fn test_invalid_ca_certificate_is_reported() {
+    let path = std::env::temp_dir().join("llm_connection_tests_invalid_ca.pem");
+    std::fs::write(&path, "not a certificate").unwrap();
+    let config = remote(
+        "https://ollama.example.com",
+        LLMConnection { ca_cert_path: Some(path.to_string_lossy().to_string()), auth: None },
+    );
+    assert!(LLMService::try_new(config).is_err());
+
+    let missing = remote(
+        "https://ollama.example.com",
+        LLMConnection { ca_cert_path: Some("/nonexistent/ca.pem".to_string()), auth: None },
+    );
+    assert!(LLMService::try_new(missing).is_err());
+}
+
+#[test]
+fn test_auth_serialization() {
+    let auth: LLMAuth = serde_json::from_str(r#"{"type":"basic","username":"me","password":"pw"}"#).unwrap();
+    assert!(matches!(auth, LLMAuth::Basic { .. }));
+    let config: LLMConfig = serde_json::from_value(serde_json::json!({
+        "provider": "Ollama",
+        "base_url": "http://localhost:11434",
+        "model_name": "llama3.2:3b",
+        "temperature": 0.7,
+        "max_tokens": 2048,
+        "timeout_seconds": 120
+    }))
+    .unwrap();
+    assert!(config.connection.auth.is_none());
+}
+
+#[tokio::test]
+async fn test_auth_header_is_sent_and_rejection_is_reported() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+
+    let server = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for status in ["200 OK", "401 Unauthorized"] {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buffer = [0u8; 4096];
+            let read = stream.read(&mut buffer).unwrap();
+            requests.push(String::from_utf8_lossy(&buffer[..read]).to_string());
+            let body = r#"{"models":[]}"#;
+            write!(stream, "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body).unwrap();
+        }
+        requests
+    });
+
+    let config = remote(&base_url, LLMConnection { auth: bearer(), ..Default::default() });
+    assert!(LLMService::try_new(config.clone()).unwrap().check_connection().await.unwrap());
+    assert!(LLMService::try_new(config).unwrap().check_connection().await.is_err());
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].to_lowercase().contains("authorization: bearer secret"));
+}
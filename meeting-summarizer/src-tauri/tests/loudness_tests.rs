@@ -0,0 +1,126 @@
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use meeting_summarizer_lib::services::audio_convert::is_whisper_ready;
+use meeting_summarizer_lib::services::loudness::{
+    normalization_gain, normalize_for_transcription, normalized_path, LoudnessMeter,
+};
+use meeting_summarizer_lib::services::LoudnessSettings;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn sine(sample_rate: u32, seconds: f32, amplitude: f32) -> Vec<f32> {
+    (0..(sample_rate as f32 * seconds) as usize)
+        .map(|index| (2.0 * std::f32::consts::PI * 1_000.0 * index as f32 / sample_rate as f32).sin() * amplitude)
+        .collect()
+}
+
+fn measure(samples: &[f32], sample_rate: u32) -> Option<f32> {
+    let mut meter = LoudnessMeter::new(sample_rate);
+    meter.push(samples);
+    meter.integrated()
+}
+
+fn write_wav(path: &Path, sample_rate: u32, channels: u16, samples: &[f32]) {
+    let spec = WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec).unwrap();
+    for sample in samples {
+        writer.write_sample((sample * i16::MAX as f32) as i16).unwrap();
+    }
+    writer.finalize().unwrap();
+}
+
+#[test]
+fn test_sine_loudness_matches_reference() {
+    // 1kHz・振幅 0.1 の正弦波は約 -23 LUFS
+    for sample_rate in [16_000, 48_000] {
+        let loudness = measure(&sine(sample_rate, 3.0, 0.1), sample_rate).unwrap();
+        assert!((loudness + 23.0).abs() < 0.5, "{} Hz: {}", sample_rate, loudness);
+    }
+
+    assert!(measure(&vec![0.0; 48_000], 16_000).is_none());
+    // 400ms に満たない音声は測れない
+    assert!(measure(&sine(16_000, 0.2, 0.1), 16_000).is_none());
+}
+
+#[test]
+fn test_background_noise_does_not_lower_loudness() {
+    // 話している区間だけで測るので、長い静かな区間があっても結果はほぼ変わらない
+    let mut samples = sine(16_000, 3.0, 0.1);
+    samples.extend(sine(16_000, 6.0, 0.001));
+    let loudness = measure(&samples, 16_000).unwrap();
+    assert!((loudness + 23.0).abs() < 0.5, "{}", loudness);
+}
+
+#[test]
+fn test_gain_is_limited() {
+    assert_eq!(normalization_gain(None, 0.5, -23.0), 1.0);
+    assert!((normalization_gain(Some(-33.0), 0.01, -23.0) - 10f32.powf(0.5)).abs() < 1e-4);
+    // ピークが -1 dBFS を超えない
+    assert!((normalization_gain(Some(-33.0), 0.5, -23.0) * 0.5 - 0.891).abs() < 1e-3);
+    // 上げすぎない（+30dB まで）
+    assert!((normalization_gain(Some(-90.0), 0.0001, -23.0) - 10f32.powf(1.5)).abs() < 1e-2);
+
+    assert!(LoudnessSettings::default().validate().is_ok());
+    assert!(LoudnessSettings { enabled: true, target_lufs: 0.0 }.validate().is_err());
+}
+
+#[test]
+fn test_normalized_copy_is_written_for_transcription() {
+    let temp_dir = TempDir::new().unwrap();
+    let input = temp_dir.path().join("recording_20240101_100000_abc.wav");
+    // 48kHz ステレオの小さな声（約 -35 LUFS）
+    let quiet: Vec<f32> = sine(48_000, 3.0, 0.025).into_iter().flat_map(|sample| [sample, sample]).collect();
+    write_wav(&input, 48_000, 2, &quiet);
+
+    let output = normalized_path(temp_dir.path(), &input);
+    assert_eq!(output, temp_dir.path().join("normalized").join("recording_20240101_100000_abc.wav"));
+
+    let summary = normalize_for_transcription(&input, &output, Some(-23.0)).unwrap();
+    assert!(summary.gain_db > 10.0, "{:?}", summary);
+    assert!(is_whisper_ready(&output));
+
+    let samples: Vec<f32> = WavReader::open(&output)
+        .unwrap()
+        .samples::<i16>()
+        .map(|sample| sample.unwrap() as f32 / 32_768.0)
+        .collect();
+    let loudness = measure(&samples, 16_000).unwrap();
+    assert!((loudness + 23.0).abs() < 1.0, "{}", loudness);
+
+    // 元の録音は残り、作業用のファイルは残らない
+    assert!(input.exists());
+    let leftovers: Vec<PathBuf> = std::fs::read_dir(output.parent().unwrap())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path != &output)
+        .collect();
+    assert!(leftovers.is_empty(), "{:?}", leftovers);
+}
+
+#[test]
+fn test_conversion_without_normalization_keeps_level() {
+    let temp_dir = TempDir::new().unwrap();
+    let input = temp_dir.path().join("imported.wav");
+    let stereo: Vec<f32> = sine(48_000, 1.0, 0.025).into_iter().flat_map(|sample| [sample, sample]).collect();
+    write_wav(&input, 48_000, 2, &stereo);
+    assert!(!is_whisper_ready(&input));
+
+    let output = normalized_path(temp_dir.path(), &input);
+    let summary = normalize_for_transcription(&input, &output, None).unwrap();
+    assert_eq!(summary.input_lufs, None);
+    assert_eq!(summary.gain_db, 0.0);
+    assert!(is_whisper_ready(&output));
+
+    // 16kHz モノラルに変換されるだけで、音量は変わらない
+    let reader = WavReader::open(&output).unwrap();
+    assert_eq!(reader.duration(), 16_000);
+    let peak = reader
+        .into_samples::<i16>()
+        .map(|sample| (sample.unwrap() as f32 / 32_768.0).abs())
+        .fold(0.0f32, f32::max);
+    assert!((peak - 0.025).abs() < 0.003, "{}", peak);
+}
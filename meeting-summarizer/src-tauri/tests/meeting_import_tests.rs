@@ -0,0 +1,62 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::services::meeting_import::import_meeting_folder;
+use meeting_summarizer_lib::services::MeetingSource;
+use std::fs;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_import_zoom_folder() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let folder = temp_dir.path().join("2024-05-01 Weekly Sync");
+    let recordings_dir = temp_dir.path().join("recordings");
+    fs::create_dir_all(&folder).unwrap();
+
+    fs::write(folder.join("audio_only.m4a"), b"fake audio").unwrap();
+    fs::write(folder.join("chat.txt"), "10:00:01 From Alice to Everyone: hello\n").unwrap();
+    fs::write(
+        folder.join("meeting.json"),
+        r#"{
+            "topic": "Weekly Sync",
+            "start_time": "2024-05-01T10:00:00Z",
+            "duration": 30,
+            "participants": [
+                {"name": "Alice", "user_email": "alice@example.com", "join_time": "2024-05-01T10:00:00Z", "leave_time": "2024-05-01T10:30:00Z"},
+                {"name": "Bob", "join_time": "2024-05-01T10:05:00Z"}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let database = Database::new(temp_dir.path().join("import_test.db")).unwrap();
+    let imported = import_meeting_folder(&database, &folder, &recordings_dir).await.unwrap();
+
+    assert_eq!(imported.source, MeetingSource::Zoom);
+    assert_eq!(imported.recording.title.as_deref(), Some("Weekly Sync"));
+    assert_eq!(imported.recording.duration, Some(1800));
+    assert!(std::path::Path::new(&imported.recording.file_path).exists());
+
+    // 参加者と添付ファイルが録音に紐付いていること
+    let participants = database.get_participants_by_recording(&imported.recording.id).await.unwrap();
+    assert_eq!(participants.len(), 2);
+    assert_eq!(participants[0].email.as_deref(), Some("alice@example.com"));
+
+    let attachments = database.get_attachments_by_recording(&imported.recording.id).await.unwrap();
+    assert!(attachments.iter().any(|a| a.kind == "chat"));
+    assert!(attachments.iter().any(|a| a.kind == "metadata"));
+
+    // 録音削除で子レコードも削除される
+    database.delete_recording(&imported.recording.id).await.unwrap();
+    assert!(database.get_participants_by_recording(&imported.recording.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_import_rejects_unknown_folder() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let folder = temp_dir.path().join("random");
+    fs::create_dir_all(&folder).unwrap();
+    fs::write(folder.join("notes.txt"), "nothing here").unwrap();
+
+    let database = Database::new(temp_dir.path().join("import_test.db")).unwrap();
+    let result = import_meeting_folder(&database, &folder, &temp_dir.path().join("recordings")).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,101 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{MeetingNote, Recording, Transcription};
+use meeting_summarizer_lib::services::UserProfile;
+use meeting_summarizer_lib::services::meeting_notes::{
+    ensure_important_notes, format_notes_for_prompt, notes_for_transcription, validate_note_text,
+};
+
+fn note(text: &str, offset_ms: Option<i64>, important: bool) -> MeetingNote {
+    let mut note = MeetingNote::new("rec-1".to_string(), text.to_string());
+    note.offset_ms = offset_ms;
+    note.important = important;
+    note
+}
+
+#[test]
+fn test_notes_are_formatted_with_time_and_importance() {
+    let notes = vec![
+        note("予算は据え置き", Some(125_000), true),
+        note("次回は来週火曜", None, false),
+    ];
+
+    assert_eq!(format_notes_for_prompt(&notes), "- [02:05] ★ 予算は据え置き\n- 次回は来週火曜");
+}
+
+#[test]
+fn test_missing_important_notes_are_added_to_key_points() {
+    let notes = vec![
+        note("予算は据え置き", None, true),
+        note("リリース日を 6月に延期", None, true),
+        note("雑談", None, false),
+    ];
+    let mut key_points = vec!["リリース日を6月に延期することで合意".to_string()];
+
+    ensure_important_notes(&mut key_points, &notes);
+
+    assert_eq!(
+        key_points,
+        vec!["リリース日を6月に延期することで合意".to_string(), "予算は据え置き".to_string()]
+    );
+}
+
+#[test]
+fn test_empty_notes_are_rejected() {
+    assert!(validate_note_text("   ").is_err());
+    assert_eq!(validate_note_text(" 決定事項 ").unwrap(), "決定事項");
+}
+
+#[tokio::test]
+async fn test_notes_are_loaded_for_transcription_in_meeting_order() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("notes.wav".to_string(), "/tmp/notes.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+
+    for (text, offset_ms) in [("後半のメモ", Some(60_000)), ("時刻なし", None), ("冒頭のメモ", Some(5_000))] {
+        let mut note = MeetingNote::new(recording.id.clone(), text.to_string());
+        note.offset_ms = offset_ms;
+        database.create_meeting_note(&note).await.unwrap();
+    }
+
+    let notes = notes_for_transcription(&database, &transcription.id).await.unwrap();
+    let texts: Vec<&str> = notes.iter().map(|note| note.text.as_str()).collect();
+    assert_eq!(texts, vec!["冒頭のメモ", "後半のメモ", "時刻なし"]);
+
+    database.delete_recording(&recording.id).await.unwrap();
+    assert!(database.get_meeting_notes(&recording.id).await.unwrap().is_empty());
+}
+
+#[test]
+fn test_note_author_is_shown_in_prompt() {
+    let notes = vec![note("予算は据え置き", Some(5_000), false).with_author(Some("佐藤".to_string()))];
+
+    assert_eq!(format_notes_for_prompt(&notes), "- [00:05] 予算は据え置き（佐藤）");
+}
+
+#[test]
+fn test_profile_display_name_is_used_as_author() {
+    let profile = UserProfile {
+        display_name: Some(" 佐藤 ".to_string()),
+    };
+    assert_eq!(profile.author_name(), Some("佐藤".to_string()));
+}
+
+#[tokio::test]
+async fn test_note_author_is_persisted() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("authored.wav".to_string(), "/tmp/authored.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let mut note = MeetingNote::new(recording.id.clone(), "確認事項".to_string()).with_author(Some("佐藤".to_string()));
+    database.create_meeting_note(&note).await.unwrap();
+    note.text = "確認事項（修正）".to_string();
+    note.author = Some("別のユーザー".to_string());
+    database.update_meeting_note(&note).await.unwrap();
+
+    let notes = database.get_meeting_notes(&recording.id).await.unwrap();
+    assert_eq!(notes[0].text, "確認事項（修正）");
+    // 入力者は編集しても変わらない
+    assert_eq!(notes[0].author.as_deref(), Some("佐藤"));
+}
@@ -0,0 +1,59 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{LLMConfig, LLMProvider, Recording, Transcription};
+use meeting_summarizer_lib::services::model_comparison;
+
+fn unreachable_config(model_name: &str) -> LLMConfig {
+    LLMConfig {
+        provider: LLMProvider::Ollama,
+        base_url: "http://127.0.0.1:9".to_string(),
+        model_name: model_name.to_string(),
+        temperature: 0.3,
+        max_tokens: 256,
+        timeout_seconds: 5,
+        connection: Default::default(),
+    }
+}
+
+#[test]
+fn test_model_count_is_validated() {
+    assert!(model_comparison::validate_configs(&[unreachable_config("a")]).is_err());
+    assert!(model_comparison::validate_configs(&[unreachable_config("a"), unreachable_config("b")]).is_ok());
+    let four: Vec<LLMConfig> = ["a", "b", "c", "d"].iter().map(|name| unreachable_config(name)).collect();
+    assert!(model_comparison::validate_configs(&four).is_err());
+}
+
+#[tokio::test]
+async fn test_failed_models_are_reported_per_model() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("meeting.wav".to_string(), "/tmp/meeting.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "来週までに見積もりを出します".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+
+    let results = model_comparison::compare_models(
+        &database,
+        &transcription.id,
+        vec![unreachable_config("llama3.2:3b"), unreachable_config("mistral:7b")],
+        true,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].config.model_name, "llama3.2:3b");
+    assert_eq!(results[1].config.model_name, "mistral:7b");
+    for result in &results {
+        assert!(result.summary.is_none());
+        assert!(result.error.is_some());
+        assert_eq!(result.tokens_per_second, None);
+    }
+    // 失敗した要約は保存しない
+    assert!(database.get_summaries_by_transcription(&transcription.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_missing_transcription_is_an_error() {
+    let database = Database::in_memory().unwrap();
+    let configs = vec![unreachable_config("a"), unreachable_config("b")];
+    assert!(model_comparison::compare_models(&database, "missing", configs, false).await.is_err());
+}
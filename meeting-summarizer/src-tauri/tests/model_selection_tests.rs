@@ -0,0 +1,175 @@
+use meeting_summarizer_lib::models::LLMProvider;
+use meeting_summarizer_lib::services::model_selection::{
+    estimate_tokens, heuristic_recommendations, rank_recommendations, select_model,
+};
+use meeting_summarizer_lib::services::{
+    LLMModelManager, ModelBenchmark, ModelInfo, ModelPreference, ModelSettings, PerformancePriority,
+};
+
+fn model(id: &str, parameters: &str, memory_mb: u64, context_length: u32) -> ModelInfo {
+    ModelInfo {
+        id: id.to_string(),
+        name: id.split_once(':').unwrap().1.to_string(),
+        provider: LLMProvider::Ollama,
+        description: String::new(),
+        parameter_count: Some(parameters.to_string()),
+        quantization: None,
+        memory_required: Some(memory_mb),
+        context_length: Some(context_length),
+        is_available: true,
+        download_url: None,
+        file_size: None,
+    }
+}
+
+fn models() -> Vec<ModelInfo> {
+    vec![
+        model("ollama:llama3.2:1b", "1B", 2_000, 128_000),
+        model("ollama:llama3.2:3b", "3B", 6_000, 128_000),
+        model("ollama:mistral:7b", "7B", 14_000, 8_192),
+    ]
+}
+
+fn settings(priority: PerformancePriority) -> ModelSettings {
+    ModelSettings {
+        performance_priority: priority,
+        auto_switch_enabled: true,
+        ..ModelSettings::default()
+    }
+}
+
+#[test]
+fn test_priority_drives_choice() {
+    let models = models();
+    let (speed, _) = select_model(&settings(PerformancePriority::Speed), &models, &[], 2_000, None).unwrap();
+    assert_eq!(speed, "ollama:llama3.2:1b");
+
+    let (quality, reason) = select_model(&settings(PerformancePriority::Quality), &models, &[], 2_000, None).unwrap();
+    assert_eq!(quality, "ollama:mistral:7b");
+    assert!(reason.contains("quality"));
+
+    let (balance, _) = select_model(&settings(PerformancePriority::Balance), &models, &[], 2_000, None).unwrap();
+    assert_eq!(balance, "ollama:llama3.2:3b");
+}
+
+#[test]
+fn test_long_transcripts_and_memory_limits_exclude_models() {
+    let models = models();
+    // 8k コンテキストに収まらない長さ
+    let (long, reason) = select_model(&settings(PerformancePriority::Quality), &models, &[], 60_000, None).unwrap();
+    assert_eq!(long, "ollama:llama3.2:3b");
+    assert!(reason.contains(&format!("~{} tokens", estimate_tokens(60_000) + 1024)));
+
+    let (low_memory, reason) =
+        select_model(&settings(PerformancePriority::Quality), &models, &[], 2_000, Some(4_000)).unwrap();
+    assert_eq!(low_memory, "ollama:llama3.2:1b");
+    assert!(reason.contains("4000 MB"));
+}
+
+#[test]
+fn test_benchmarks_and_preferences() {
+    let models = models();
+    let benchmarks = vec![ModelBenchmark {
+        model_id: "ollama:mistral:7b".to_string(),
+        inference_speed: Some(80.0),
+        memory_usage: None,
+        quality_score: None,
+        last_benchmarked: chrono::Utc::now(),
+    }];
+    let (fastest, _) = select_model(&settings(PerformancePriority::Speed), &models, &benchmarks, 2_000, None).unwrap();
+    assert_eq!(fastest, "ollama:mistral:7b");
+
+    let mut disabled = settings(PerformancePriority::Speed);
+    disabled.model_preferences.insert(
+        "ollama:llama3.2:1b".to_string(),
+        ModelPreference {
+            model_id: "ollama:llama3.2:1b".to_string(),
+            custom_config: None,
+            enabled: false,
+            priority: 5,
+            notes: None,
+        },
+    );
+    let (chosen, _) = select_model(&disabled, &models, &[], 2_000, None).unwrap();
+    assert_eq!(chosen, "ollama:llama3.2:3b");
+
+    assert!(select_model(&settings(PerformancePriority::Speed), &[], &[], 2_000, None).is_none());
+}
+
+#[test]
+fn test_config_for_tagged_ollama_model() {
+    let manager = LLMModelManager::new();
+    let config = manager.create_config_for_model("ollama:llama3.2:3b").unwrap();
+    assert_eq!(config.model_name, "llama3.2:3b");
+    assert!(manager.create_config_for_model("ollama").is_err());
+}
+
+#[test]
+fn test_processing_time_uses_cached_benchmark() {
+    let mut manager = LLMModelManager::new();
+    // ベンチマークが無ければモデルサイズから推定（3B: 30 tokens/sec）
+    assert!((manager.estimate_processing_time("ollama:llama3.2:3b", 1_200) - 10.0).abs() < 1e-9);
+
+    manager.insert_benchmark(ModelBenchmark {
+        model_id: "ollama:llama3.2:3b".to_string(),
+        inference_speed: Some(60.0),
+        memory_usage: None,
+        quality_score: None,
+        last_benchmarked: chrono::Utc::now(),
+    });
+    assert!((manager.estimate_processing_time("ollama:llama3.2:3b", 1_200) - 5.0).abs() < 1e-9);
+
+    // 測定できなかった速度は使わない
+    manager.insert_benchmark(ModelBenchmark {
+        model_id: "ollama:mistral:7b".to_string(),
+        inference_speed: Some(0.0),
+        memory_usage: None,
+        quality_score: None,
+        last_benchmarked: chrono::Utc::now(),
+    });
+    assert!((manager.estimate_processing_time("ollama:mistral:7b", 1_200) - 20.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_recommendations_only_include_installed_models() {
+    let candidates = heuristic_recommendations("summarization", 5_000, 8_192, 0.5);
+    assert_eq!(candidates, vec!["ollama:llama3.2:3b".to_string(), "ollama:mistral:7b".to_string()]);
+
+    let installed = vec![
+        model("ollama:mistral:7b", "7B", 7_000, 8_192),
+        model("ollama:qwen2.5:3b", "3B", 3_000, 32_000),
+        model("lmstudio:big-70b", "70B", 70_000, 4_096),
+    ];
+    let ranked = rank_recommendations(
+        &candidates,
+        &installed,
+        &ModelSettings::default(),
+        8_192,
+    );
+    // 未導入の llama3.2:3b は除き、メモリに収まらない 70B は追加しない
+    assert_eq!(ranked, vec!["ollama:mistral:7b".to_string(), "ollama:qwen2.5:3b".to_string()]);
+
+    let mut settings = ModelSettings::default();
+    settings.model_preferences.insert(
+        "ollama:qwen2.5:3b".to_string(),
+        ModelPreference {
+            model_id: "ollama:qwen2.5:3b".to_string(),
+            custom_config: None,
+            enabled: true,
+            priority: 9,
+            notes: None,
+        },
+    );
+    settings.model_preferences.insert(
+        "ollama:mistral:7b".to_string(),
+        ModelPreference {
+            model_id: "ollama:mistral:7b".to_string(),
+            custom_config: None,
+            enabled: false,
+            priority: 10,
+            notes: None,
+        },
+    );
+    let ranked = rank_recommendations(&candidates, &installed, &settings, 8_192);
+    assert_eq!(ranked, vec!["ollama:qwen2.5:3b".to_string()]);
+}
@@ -0,0 +1,96 @@
+use meeting_summarizer_lib::services::noise_suppression::{ChannelDenoiser, DenoiseStage, NoiseSuppressor};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+const SAMPLE_RATE: u32 = 16_000;
+
+/// 再現できる擬似乱数のノイズ
+fn noise(len: usize, amplitude: f32) -> Vec<f32> {
+    let mut state: u32 = 0x1234_5678;
+    (0..len)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            ((state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0) * amplitude
+        })
+        .collect()
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len().max(1) as f32).sqrt()
+}
+
+fn run(suppressor: &mut NoiseSuppressor, input: &[f32]) -> Vec<f32> {
+    let mut output = Vec::new();
+    for chunk in input.chunks(441) {
+        output.extend(suppressor.process(chunk));
+    }
+    output.extend(suppressor.flush());
+    output
+}
+
+#[test]
+fn test_output_has_same_length_as_input() {
+    let input = noise(SAMPLE_RATE as usize + 123, 0.1);
+    let output = run(&mut NoiseSuppressor::new(SAMPLE_RATE), &input);
+    assert_eq!(output.len(), input.len());
+
+    // 48kHz はそのまま、それ以外は 48kHz に変換して通しても長さは変わらない
+    for sample_rate in [48_000, 44_100] {
+        let input = noise(sample_rate as usize / 2 + 7, 0.1);
+        assert_eq!(run(&mut NoiseSuppressor::new(sample_rate), &input).len(), input.len());
+    }
+
+    let mut denoiser = ChannelDenoiser::new(SAMPLE_RATE, 2);
+    let mut stereo = denoiser.process(&noise(2_000, 0.1));
+    stereo.extend(denoiser.flush());
+    assert_eq!(stereo.len(), 2_000);
+}
+
+#[test]
+fn test_steady_noise_is_reduced() {
+    let input = noise(SAMPLE_RATE as usize * 3, 0.05);
+    let output = run(&mut NoiseSuppressor::new(SAMPLE_RATE), &input);
+
+    // 最初の1秒はノイズの推定が落ち着くまで除く
+    let tail = SAMPLE_RATE as usize..;
+    assert!(rms(&output[tail.clone()]) < rms(&input[tail]) * 0.5);
+}
+
+#[test]
+fn test_speech_like_tone_is_kept() {
+    let len = SAMPLE_RATE as usize * 3;
+    let background = noise(len, 0.01);
+    let input: Vec<f32> = background
+        .iter()
+        .enumerate()
+        .map(|(index, sample)| {
+            let time = index as f32 / SAMPLE_RATE as f32;
+            // 2秒目から大きな 440Hz の音を重ねる
+            let tone = if time >= 2.0 { (2.0 * std::f32::consts::PI * 440.0 * time).sin() * 0.3 } else { 0.0 };
+            sample + tone
+        })
+        .collect();
+    let output = run(&mut NoiseSuppressor::new(SAMPLE_RATE), &input);
+
+    let tone_range = SAMPLE_RATE as usize * 2 + 1_600..len;
+    let ratio = rms(&output[tone_range.clone()]) / rms(&input[tone_range]);
+    assert!(ratio > 0.8, "tone was attenuated to {}", ratio);
+}
+
+#[test]
+fn test_stage_follows_toggle() {
+    let enabled = Arc::new(AtomicBool::new(false));
+    let mut stage = DenoiseStage::new(enabled.clone(), SAMPLE_RATE, 1);
+
+    // 無効ならそのまま通す
+    let input = noise(1_000, 0.1);
+    assert_eq!(stage.process(input.clone()), input);
+
+    // 有効にすると遅れて出力し、無効に戻すと残りを書き出してから通す
+    enabled.store(true, Ordering::Relaxed);
+    let mut total = stage.process(noise(1_000, 0.1)).len();
+    enabled.store(false, Ordering::Relaxed);
+    total += stage.process(noise(500, 0.1)).len();
+    assert_eq!(total, 1_500);
+    assert!(stage.finish().is_empty());
+}
@@ -0,0 +1,97 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{LLMConfig, SummaryStatus};
+use meeting_summarizer_lib::services::llm::SummaryContext;
+use meeting_summarizer_lib::services::ollama_pool::normalize_hosts;
+use meeting_summarizer_lib::services::summarizer::SummaryRoute;
+use meeting_summarizer_lib::services::{LLMModelManager, ModelSettingsManager, OllamaPool, Summarizer};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+fn hosts(urls: &[&str]) -> Vec<String> {
+    urls.iter().map(|url| url.to_string()).collect()
+}
+
+#[test]
+fn test_hosts_are_normalized() {
+    assert_eq!(
+        normalize_hosts(&hosts(&[" http://desktop:11434/ ", "http://desktop:11434", "", "http://server:11434"])),
+        hosts(&["http://desktop:11434", "http://server:11434"])
+    );
+}
+
+#[test]
+fn test_least_loaded_healthy_host_first() {
+    let pool = OllamaPool::new();
+    let configured = hosts(&["http://desktop:11434", "http://server:11434", "http://laptop:11434"]);
+    assert_eq!(pool.candidates(&configured)[0], "http://desktop:11434");
+
+    let _busy = pool.lease("http://desktop:11434");
+    assert_eq!(pool.candidates(&configured)[0], "http://server:11434");
+
+    pool.record_result("http://server:11434", false);
+    let order = pool.candidates(&configured);
+    assert_eq!(order, hosts(&["http://laptop:11434", "http://desktop:11434", "http://server:11434"]));
+
+    let status = pool.status(&configured);
+    assert_eq!(status[0].in_flight, 1);
+    assert!(!status[1].healthy);
+}
+
+#[test]
+fn test_lease_is_released_on_drop() {
+    let pool = OllamaPool::new();
+    let configured = hosts(&["http://desktop:11434"]);
+    {
+        let _lease = pool.lease("http://desktop:11434");
+        assert_eq!(pool.status(&configured)[0].in_flight, 1);
+    }
+    assert_eq!(pool.status(&configured)[0].in_flight, 0);
+}
+
+#[tokio::test]
+async fn test_fails_over_to_every_host() {
+    let pool = OllamaPool::new();
+    let configured = hosts(&["http://127.0.0.1:9", "http://127.0.0.1:1"]);
+    let config = LLMConfig { timeout_seconds: 2, ..LLMConfig::default() };
+
+    let summary = pool
+        .summarize(&configured, &config, "text", &SummaryContext::default(), "t1".to_string())
+        .await
+        .unwrap();
+    assert!(matches!(summary.status, SummaryStatus::Failed(_)));
+    assert!(pool.status(&configured).iter().all(|host| !host.healthy && host.failures == 1));
+
+    assert!(pool
+        .summarize(&[], &config, "text", &SummaryContext::default(), "t1".to_string())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_shared_summarizer_spreads_over_configured_hosts() {
+    let configured = hosts(&["http://127.0.0.1:9", "http://127.0.0.1:1"]);
+    let mut settings_manager = ModelSettingsManager::new(PathBuf::from("model_settings.json"));
+    settings_manager.update_settings(|settings| settings.ollama_hosts = configured.clone());
+    let pool = Arc::new(OllamaPool::new());
+    let summarizer = Summarizer::new(
+        Arc::new(Database::in_memory().unwrap()),
+        Arc::new(Mutex::new(settings_manager)),
+        Arc::new(Mutex::new(LLMModelManager::new())),
+        pool.clone(),
+    );
+
+    let config = LLMConfig { timeout_seconds: 2, ..LLMConfig::default() };
+    assert!(matches!(
+        summarizer.route(Some(config.clone()), "text").await,
+        SummaryRoute::OllamaPool { .. }
+    ));
+
+    // ジョブキューや REST/gRPC と同じ経路で要約しても、ホストの状態が共有のプールに記録される
+    let summary = summarizer
+        .summarize("t1", "text", Some(config), None)
+        .await
+        .unwrap();
+    assert!(matches!(summary.status, SummaryStatus::Failed(_)));
+    assert!(pool.status(&configured).iter().all(|host| host.failures == 1));
+}
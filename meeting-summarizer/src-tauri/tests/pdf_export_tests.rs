@@ -0,0 +1,72 @@
+use meeting_summarizer_lib::models::Summary;
+use meeting_summarizer_lib::services::pdf_export::{render_summary_pdf, PdfProtection};
+
+fn summary() -> Summary {
+    Summary::new("tx-1".to_string(), "llama3".to_string()).with_content(
+        "来期の予算案を承認した。".repeat(40),
+        vec!["予算は前年比10%増".to_string()],
+        vec!["議事録を共有する 担当：田中".to_string()],
+    )
+}
+
+#[test]
+fn test_unprotected_pdf_is_readable() {
+    let bytes = render_summary_pdf("定例会議", &summary(), &PdfProtection::default()).unwrap();
+    assert!(bytes.starts_with(b"%PDF-2.0"));
+
+    let document = lopdf::Document::load_mem(&bytes).unwrap();
+    assert!(!document.is_encrypted());
+    assert_eq!(document.get_pages().len(), 1);
+}
+
+#[test]
+fn test_password_protected_pdf_requires_password() {
+    let protection = PdfProtection {
+        user_password: Some("open-sesame".to_string()),
+        owner_password: Some("owner-secret".to_string()),
+    };
+    let bytes = render_summary_pdf("定例会議", &summary(), &protection).unwrap();
+
+    // 本文は暗号化されている
+    let plain = render_summary_pdf("定例会議", &summary(), &PdfProtection::default()).unwrap();
+    assert_ne!(bytes.len(), 0);
+    assert!(bytes.windows(b"/Encrypt".len()).any(|w| w == b"/Encrypt"));
+    assert!(!plain.windows(b"/Encrypt".len()).any(|w| w == b"/Encrypt"));
+
+    let document = lopdf::Document::load_mem(&bytes).unwrap();
+    assert!(document.is_encrypted());
+    assert!(document.authenticate_password("wrong").is_err());
+    document.authenticate_user_password("open-sesame").unwrap();
+    document.authenticate_owner_password("owner-secret").unwrap();
+}
+
+#[test]
+fn test_owner_password_only_pdf_opens_without_password() {
+    let protection = PdfProtection {
+        user_password: None,
+        owner_password: Some("owner-secret".to_string()),
+    };
+    let bytes = render_summary_pdf("定例会議", &summary(), &protection).unwrap();
+
+    // 閲覧パスワードが空なら開けるが、権限の変更にはオーナーパスワードが必要
+    let document = lopdf::Document::load_mem(&bytes).unwrap();
+    assert!(document.is_encrypted());
+    assert!(document.authenticate_owner_password("wrong").is_err());
+    document.authenticate_owner_password("owner-secret").unwrap();
+    assert_eq!(document.get_pages().len(), 1);
+}
+
+#[test]
+fn test_user_password_only_pdf_cannot_be_unlocked_with_it() {
+    let protection = PdfProtection {
+        user_password: Some("open-sesame".to_string()),
+        owner_password: None,
+    };
+    let bytes = render_summary_pdf("定例会議", &summary(), &protection).unwrap();
+
+    // 閲覧パスワードでは開けるが、権限の解除には使えない
+    let document = lopdf::Document::load_mem(&bytes).unwrap();
+    document.authenticate_user_password("open-sesame").unwrap();
+    assert!(document.authenticate_owner_password("open-sesame").is_err());
+    assert!(document.authenticate_owner_password("").is_err());
+}
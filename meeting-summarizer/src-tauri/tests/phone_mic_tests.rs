@@ -0,0 +1,151 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::Recording;
+use meeting_summarizer_lib::services::phone_mic::{generate_token, PhoneMicSession, StreamFormat, PHONE_MIC_TAG, PHONE_TRACK_NAME};
+use meeting_summarizer_lib::services::{PhoneMicServer, RecordingService};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+#[test]
+fn test_stream_format_validation() {
+    assert!(StreamFormat { sample_rate: 48_000, channels: 1 }.validate().is_ok());
+    assert!(StreamFormat { sample_rate: 16_000, channels: 2 }.validate().is_ok());
+    assert!(StreamFormat { sample_rate: 0, channels: 1 }.validate().is_err());
+    assert!(StreamFormat { sample_rate: 48_000, channels: 0 }.validate().is_err());
+    assert!(StreamFormat { sample_rate: 48_000, channels: 6 }.validate().is_err());
+}
+
+#[test]
+fn test_session_writes_samples_split_across_messages() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let format = StreamFormat { sample_rate: 8_000, channels: 1 };
+    let mut session = PhoneMicSession::create(temp_dir.path(), format).unwrap();
+
+    // 8000サンプル（1秒）を奇数バイトの境界で分けて送る
+    let bytes: Vec<u8> = (0..8_000i16).flat_map(|sample| sample.to_le_bytes()).collect();
+    for chunk in bytes.chunks(333) {
+        session.push_pcm(chunk).unwrap();
+    }
+    assert!((session.duration_secs() - 1.0).abs() < f64::EPSILON);
+
+    let recording = session.finish().unwrap();
+    assert_eq!(recording.duration, Some(1));
+    assert_eq!(recording.sample_rate, Some(8_000));
+    assert_eq!(recording.channels, Some(1));
+    assert_eq!(recording.tags, vec![PHONE_MIC_TAG.to_string()]);
+
+    let samples: Vec<i16> = hound::WavReader::open(&recording.file_path)
+        .unwrap()
+        .into_samples::<i16>()
+        .map(|sample| sample.unwrap())
+        .collect();
+    assert_eq!(samples.len(), 8_000);
+    assert_eq!(samples[4_321], 4_321);
+}
+
+#[test]
+fn test_empty_session_is_discarded() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let format = StreamFormat { sample_rate: 48_000, channels: 1 };
+    let session = PhoneMicSession::create(temp_dir.path(), format).unwrap();
+
+    assert!(session.finish().is_err());
+    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn test_silence_pads_the_start_of_a_track() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let format = StreamFormat { sample_rate: 8_000, channels: 1 };
+
+    // 無音だけで音声が届かなければ捨てる
+    let mut session = PhoneMicSession::create(temp_dir.path(), format).unwrap();
+    session.pad_silence(500).unwrap();
+    assert!(session.finish().is_err());
+
+    let mut session = PhoneMicSession::create(temp_dir.path(), format).unwrap();
+    session.pad_silence(1_500).unwrap();
+    let bytes: Vec<u8> = (1..=4_000i16).flat_map(|sample| sample.to_le_bytes()).collect();
+    session.push_pcm(&bytes).unwrap();
+    let recording = session.finish().unwrap();
+    assert_eq!(recording.duration, Some(2));
+
+    let samples: Vec<i16> = hound::WavReader::open(&recording.file_path)
+        .unwrap()
+        .into_samples::<i16>()
+        .map(|sample| sample.unwrap())
+        .collect();
+    assert_eq!(samples.len(), 16_000);
+    assert!(samples[..12_000].iter().all(|sample| *sample == 0));
+    assert_eq!(samples[12_000], 1);
+
+    // 長い無音は何回かに分けて書く
+    let format = StreamFormat { sample_rate: 48_000, channels: 2 };
+    let mut session = PhoneMicSession::create(temp_dir.path(), format).unwrap();
+    session.pad_silence(2_000).unwrap();
+    session.push_pcm(&[1, 0, 1, 0]).unwrap();
+    let recording = session.finish().unwrap();
+    let reader = hound::WavReader::open(&recording.file_path).unwrap();
+    assert_eq!(reader.len(), 192_002);
+}
+
+#[tokio::test]
+async fn test_phone_audio_is_attached_as_a_track() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let recordings_dir = temp_dir.path().join("recordings");
+    let database = Arc::new(Database::new(temp_dir.path().join("phone.db")).unwrap());
+    let recording_service = RecordingService::new(database.clone(), recordings_dir.clone()).unwrap();
+
+    let recording = Recording::new("meeting.mp3".to_string(), recordings_dir.join("meeting.mp3").to_string_lossy().to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let mut session = PhoneMicSession::create(&recordings_dir, StreamFormat { sample_rate: 8_000, channels: 1 }).unwrap();
+    session.push_pcm(&[0u8; 16_000]).unwrap();
+    let phone = session.finish().unwrap();
+
+    let track = recording_service
+        .attach_track(&recording, std::path::Path::new(&phone.file_path), PHONE_TRACK_NAME)
+        .await
+        .unwrap();
+    assert_eq!((track.track_index, track.device_name.as_str(), track.duration_ms), (1, PHONE_TRACK_NAME, 1_000));
+    assert_eq!(track.file_path, recordings_dir.join("meeting_track01.wav").to_string_lossy());
+    assert!(!std::path::Path::new(&phone.file_path).exists());
+    assert_eq!(database.get_recording_tracks(&recording.id).await.unwrap().len(), 1);
+}
+
+#[test]
+fn test_tokens_are_unique() {
+    assert_ne!(generate_token(), generate_token());
+}
+
+#[tokio::test]
+async fn test_server_rejects_requests_without_token() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let database = Arc::new(Database::new(temp_dir.path().join("phone.db")).unwrap());
+
+    let recording_service = Arc::new(RecordingService::new(database.clone(), temp_dir.path().join("recordings")).unwrap());
+
+    let mut server = PhoneMicServer::new();
+    let address = server.start(database, recording_service, 0).await.unwrap();
+
+    let status = server.get_status("127.0.0.1");
+    assert!(status.running);
+    let url = status.url.unwrap();
+    assert!(url.starts_with("https://"));
+
+    // 自己署名証明書なので検証しない
+    let client = reqwest::Client::builder().danger_accept_invalid_certs(true).build().unwrap();
+    let response = client.get(format!("https://127.0.0.1:{}/", address.port())).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let response = client.get(format!("https://127.0.0.1:{}/?token=wrong", address.port())).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let response = client.get(&url).send().await.unwrap();
+    assert!(response.status().is_success());
+
+    // 平文の HTTP では受け付けない
+    assert!(reqwest::get(format!("http://127.0.0.1:{}/?token=wrong", address.port())).await.is_err());
+
+    server.stop().await.unwrap();
+    assert!(!server.get_status("127.0.0.1").running);
+}
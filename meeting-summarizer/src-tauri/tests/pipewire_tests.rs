@@ -0,0 +1,104 @@
+use meeting_summarizer_lib::services::capture_source::pipewire_applications;
+use meeting_summarizer_lib::services::pipewire::{parse_pw_dump, record_command};
+use meeting_summarizer_lib::services::{demo_mode, AudioSourceType, CaptureSource};
+
+const PW_DUMP: &str = r#"[
+  { "id": 30, "type": "PipeWire:Interface:Client", "info": { "props": { "application.name": "WirePlumber" } } },
+  { "id": 41, "type": "PipeWire:Interface:Node", "info": { "props": {
+      "media.class": "Audio/Source", "node.name": "alsa_input.usb-mic", "node.description": "USB Microphone", "object.serial": 41 } } },
+  { "id": 42, "type": "PipeWire:Interface:Node", "info": { "props": {
+      "media.class": "Audio/Sink", "node.name": "alsa_output.pci-speakers", "node.description": "Built-in Audio", "object.serial": 42 } } },
+  { "id": 77, "type": "PipeWire:Interface:Node", "info": { "props": {
+      "media.class": "Stream/Output/Audio", "node.name": "ZOOM VoiceEngine", "application.name": "ZOOM VoiceEngine",
+      "media.name": "playStream", "object.serial": 1205 } } },
+  { "id": 80, "type": "PipeWire:Interface:Node", "info": { "props": {
+      "media.class": "Video/Source", "node.name": "v4l2_input.webcam" } } }
+]"#;
+
+#[test]
+fn test_pw_dump_lists_devices_with_source_types() {
+    let devices = parse_pw_dump(PW_DUMP).unwrap();
+    assert_eq!(devices.len(), 3);
+
+    assert_eq!(devices[0].name, "USB Microphone");
+    assert_eq!(devices[0].source_type, AudioSourceType::Microphone);
+    assert_eq!(
+        devices[0].capture_source,
+        CaptureSource::PipeWireNode { node: "alsa_input.usb-mic".to_string(), monitor: false }
+    );
+
+    // 出力デバイスはモニターを録音する
+    assert_eq!(devices[1].name, "Built-in Audio (monitor)");
+    assert_eq!(devices[1].source_type, AudioSourceType::SystemAudio);
+    assert_eq!(
+        devices[1].capture_source,
+        CaptureSource::PipeWireNode { node: "alsa_output.pci-speakers".to_string(), monitor: true }
+    );
+
+    // アプリのストリームはシリアル番号で指定する
+    assert_eq!(devices[2].name, "ZOOM VoiceEngine: playStream");
+    assert_eq!(devices[2].source_type, AudioSourceType::Application);
+    assert_eq!(
+        devices[2].capture_source,
+        CaptureSource::PipeWireNode { node: "1205".to_string(), monitor: false }
+    );
+}
+
+#[test]
+fn test_application_streams_are_capturable_applications() {
+    let applications = pipewire_applications(parse_pw_dump(PW_DUMP).unwrap());
+    assert_eq!(applications.len(), 1);
+    assert_eq!(applications[0].name, "ZOOM VoiceEngine: playStream");
+    assert!(applications[0].running);
+    assert_eq!(
+        applications[0].capture_source,
+        CaptureSource::PipeWireNode { node: "1205".to_string(), monitor: false }
+    );
+}
+
+#[test]
+fn test_invalid_pw_dump_is_an_error() {
+    assert!(parse_pw_dump("not json").is_err());
+    assert!(parse_pw_dump("[]").unwrap().is_empty());
+}
+
+#[test]
+fn test_record_command_targets_node_and_monitor() {
+    let command = record_command(Some("alsa_output.pci-speakers"), true);
+    assert_eq!(command.get_program(), "pw-record");
+    let args: Vec<String> = command.get_args().map(|arg| arg.to_string_lossy().to_string()).collect();
+    assert_eq!(
+        args,
+        [
+            "--format", "f32", "--rate", "48000", "--channels", "1",
+            "-P", "{ stream.capture.sink = true }",
+            "--target", "alsa_output.pci-speakers",
+            "-",
+        ]
+    );
+
+    let args: Vec<String> = record_command(None, false).get_args().map(|arg| arg.to_string_lossy().to_string()).collect();
+    assert!(!args.contains(&"--target".to_string()));
+    assert!(!args.contains(&"-P".to_string()));
+}
+
+#[test]
+fn test_pipewire_node_validation() {
+    let node = CaptureSource::PipeWireNode { node: "1205".to_string(), monitor: false };
+    assert!(node.validate().is_ok());
+    assert_eq!(node.ensure_supported().is_ok(), cfg!(target_os = "linux"));
+    assert!(CaptureSource::PipeWireNode { node: " ".to_string(), monitor: false }.validate().is_err());
+
+    // monitor は省略できる
+    let parsed: CaptureSource = serde_json::from_str(r#"{"type": "pipewire_node", "node": "1205"}"#).unwrap();
+    assert_eq!(parsed, node);
+}
+
+#[test]
+fn test_demo_devices_are_typed() {
+    demo_mode::set_enabled(true);
+    let devices = meeting_summarizer_lib::services::audio_capture_cpal::get_audio_devices().unwrap();
+    assert_eq!(devices.len(), 1);
+    assert_eq!(devices[0].name, demo_mode::DEMO_AUDIO_DEVICE);
+    assert_eq!(devices[0].source_type, AudioSourceType::Microphone);
+}
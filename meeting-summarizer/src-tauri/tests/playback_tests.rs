@@ -0,0 +1,125 @@
+use hound::{SampleFormat, WavSpec, WavWriter};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, RecordingSegment};
+use meeting_summarizer_lib::services::playback::{
+    recording_tracks, OutputConverter, PlaybackDecoder, PlaybackSource, PlaybackState, PlaybackTrack,
+};
+use meeting_summarizer_lib::services::PlaybackService;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// サンプル番号がそのまま値になる 16kHz モノラルの WAV（位置を確かめやすい）
+fn write_ramp(path: &Path, start: i16, len: usize) {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: 16_000,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec).unwrap();
+    for index in 0..len {
+        writer.write_sample(start.wrapping_add(index as i16)).unwrap();
+    }
+    writer.finalize().unwrap();
+}
+
+fn to_i16(sample: f32) -> i16 {
+    (sample * 32_768.0).round() as i16
+}
+
+fn read_all(source: &mut PlaybackSource) -> Vec<f32> {
+    let mut samples = Vec::new();
+    while let Some(chunk) = source.read().unwrap() {
+        samples.extend(chunk);
+    }
+    samples
+}
+
+#[test]
+fn test_decoder_seeks_to_position() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("recording.wav");
+    write_ramp(&path, 0, 32_000);
+
+    let mut decoder = PlaybackDecoder::open(&path).unwrap();
+    assert_eq!((decoder.sample_rate(), decoder.channels()), (16_000, 1));
+    decoder.seek(1_250).unwrap();
+    let samples = decoder.next_samples().unwrap().unwrap();
+    assert_eq!(to_i16(samples[0]), 20_000);
+
+    assert!(PlaybackDecoder::open(&temp_dir.path().join("missing.wav")).is_err());
+}
+
+#[test]
+fn test_converter_matches_output_device() {
+    // 同じサンプルレートならチャンネル数だけそろえる
+    let mut converter = OutputConverter::new(16_000, 2, 16_000, 1);
+    assert_eq!(converter.convert(&[0.2, 0.4, -0.2, -0.4]), vec![0.3, -0.3]);
+
+    // 16kHz モノラル → 48kHz ステレオ
+    let mut converter = OutputConverter::new(16_000, 1, 48_000, 2);
+    let input: Vec<f32> = (0..1_600).map(|index| index as f32 / 1_600.0).collect();
+    let mut output = Vec::new();
+    for chunk in input.chunks(100) {
+        output.extend(converter.convert(chunk));
+    }
+    let frames = output.len() / 2;
+    assert!((frames as i64 - 4_800).abs() <= 3, "{}", frames);
+    assert!(output.chunks(2).all(|frame| frame[0] == frame[1]));
+    // チャンクの境目でも値が滑らかにつながる
+    assert!(output.chunks(2).zip(output.chunks(2).skip(1)).all(|(a, b)| b[0] >= a[0] && b[0] - a[0] < 0.001));
+}
+
+#[test]
+fn test_source_plays_segments_in_order() {
+    let temp_dir = TempDir::new().unwrap();
+    let first = temp_dir.path().join("long.wav");
+    let second = temp_dir.path().join("long_part002.wav");
+    write_ramp(&first, 0, 16_000);
+    write_ramp(&second, 16_000, 16_000);
+    let tracks = vec![
+        PlaybackTrack { path: first, offset_ms: 0 },
+        PlaybackTrack { path: second, offset_ms: 1_000 },
+    ];
+
+    let mut source = PlaybackSource::open(tracks.clone(), 16_000, 1).unwrap();
+    let samples = read_all(&mut source);
+    assert_eq!(samples.len(), 32_000);
+    assert_eq!(to_i16(samples[16_000]), 16_000);
+
+    // 2つ目の区間の途中へ移る
+    let mut source = PlaybackSource::open(tracks, 16_000, 1).unwrap();
+    source.seek(1_500).unwrap();
+    let samples = read_all(&mut source);
+    assert_eq!(samples.len(), 8_000);
+    assert_eq!(to_i16(samples[0]), 24_000);
+}
+
+#[tokio::test]
+async fn test_tracks_follow_recording_segments() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("long.wav".to_string(), "/tmp/long.wav".to_string()).with_duration(90);
+    database.create_recording(&recording).await.unwrap();
+
+    let (tracks, duration_ms) = recording_tracks(&database, &recording).await.unwrap();
+    assert_eq!(tracks, vec![PlaybackTrack { path: PathBuf::from("/tmp/long.wav"), offset_ms: 0 }]);
+    assert_eq!(duration_ms, 90_000);
+
+    for (index, path) in [(0, "/tmp/long.wav"), (1, "/tmp/long_part002.wav")] {
+        let segment = RecordingSegment::new(recording.id.clone(), index, path.to_string(), index as i64 * 60_000, 60_000);
+        database.create_recording_segment(&segment).await.unwrap();
+    }
+    let (tracks, duration_ms) = recording_tracks(&database, &recording).await.unwrap();
+    assert_eq!(tracks.iter().map(|track| track.offset_ms).collect::<Vec<_>>(), vec![0, 60_000]);
+    assert_eq!(duration_ms, 120_000);
+}
+
+#[test]
+fn test_controls_require_playback() {
+    let service = PlaybackService::new();
+    assert_eq!(service.status().state, PlaybackState::Stopped);
+    assert!(service.pause().is_err());
+    assert!(service.seek(1_000).is_err());
+    assert!(service.seek(-1).is_err());
+    service.stop();
+}
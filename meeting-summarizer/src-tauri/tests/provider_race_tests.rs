@@ -0,0 +1,103 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::errors::{AppError, AppResult};
+use meeting_summarizer_lib::models::{LLMConfig, LLMProvider, Summary, SummaryStatus};
+use meeting_summarizer_lib::services::provider_race::first_successful;
+use meeting_summarizer_lib::services::summarizer::SummaryRoute;
+use meeting_summarizer_lib::services::{LLMModelManager, ModelSettings, ModelSettingsManager, OllamaPool, ProviderRace, Summarizer};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+async fn delayed(ms: u64, model: &str, ok: bool) -> AppResult<Summary> {
+    tokio::time::sleep(Duration::from_millis(ms)).await;
+    let summary = Summary::new("t1".to_string(), model.to_string());
+    Ok(if ok {
+        summary.with_content("summary".to_string(), Vec::new(), Vec::new())
+    } else {
+        summary.with_error("connection refused".to_string())
+    })
+}
+
+#[tokio::test]
+async fn test_fastest_success_wins_and_loser_is_cancelled() {
+    let finished = Arc::new(AtomicBool::new(false));
+    let slow = {
+        let finished = finished.clone();
+        async move {
+            let result = delayed(500, "cloud", true).await;
+            finished.store(true, Ordering::SeqCst);
+            result
+        }
+    };
+
+    let summary = first_successful(delayed(10, "local", true), slow).await.unwrap();
+    assert_eq!(summary.model_used, "local");
+
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert!(!finished.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_falls_back_when_fast_provider_fails() {
+    let summary = first_successful(delayed(10, "local", false), delayed(50, "cloud", true)).await.unwrap();
+    assert_eq!(summary.model_used, "cloud");
+
+    let after_error = first_successful(
+        async { Err::<Summary, _>(AppError::LLMError { message: "down".to_string() }) },
+        delayed(20, "cloud", true),
+    )
+    .await
+    .unwrap();
+    assert_eq!(after_error.model_used, "cloud");
+
+    let both_failed = first_successful(delayed(10, "local", false), delayed(20, "cloud", false)).await.unwrap();
+    assert!(matches!(both_failed.status, SummaryStatus::Failed(_)));
+}
+
+#[test]
+fn test_race_requires_distinct_providers() {
+    let settings = ModelSettings {
+        provider_race: Some(ProviderRace {
+            primary: LLMConfig::default(),
+            secondary: LLMConfig::default(),
+        }),
+        ..ModelSettings::default()
+    };
+    assert!(!settings.validate().is_empty());
+}
+
+#[tokio::test]
+async fn test_shared_summarizer_races_unless_a_model_is_given() {
+    let race = ProviderRace {
+        primary: LLMConfig::default(),
+        secondary: LLMConfig {
+            provider: LLMProvider::OpenAI,
+            base_url: "https://api.openai.com".to_string(),
+            model_name: "gpt-4o-mini".to_string(),
+            ..LLMConfig::default()
+        },
+    };
+    let mut settings_manager = ModelSettingsManager::new(PathBuf::from("model_settings.json"));
+    settings_manager.update_settings(|settings| settings.provider_race = Some(race));
+    let summarizer = Summarizer::new(
+        Arc::new(Database::in_memory().unwrap()),
+        Arc::new(Mutex::new(settings_manager)),
+        Arc::new(Mutex::new(LLMModelManager::new())),
+        Arc::new(OllamaPool::new()),
+    );
+
+    // ジョブキューや REST/gRPC のように設定を指定しない経路でも競わせる
+    assert!(matches!(summarizer.route(None, "本文").await, SummaryRoute::Race(_)));
+
+    // 明示的に指定したモデルはそのまま使う
+    let explicit = LLMConfig {
+        model_name: "mistral:7b".to_string(),
+        ..LLMConfig::default()
+    };
+    match summarizer.route(Some(explicit), "本文").await {
+        SummaryRoute::Single(config) => assert_eq!(config.model_name, "mistral:7b"),
+        route => panic!("unexpected route: {:?}", route),
+    }
+}
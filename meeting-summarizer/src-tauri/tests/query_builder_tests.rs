@@ -0,0 +1,173 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{
+    ColorLabel, QueryCondition, Recording, RecordingQuery, Summary, Transcription, TranscriptionStatus,
+    TranscriptionStatusFilter,
+};
+
+struct Fixture {
+    database: Database,
+    summarized: Recording,
+    transcribed: Recording,
+    failed: Recording,
+    untouched: Recording,
+}
+
+async fn fixture() -> Fixture {
+    let database = Database::in_memory().unwrap();
+
+    let mut summarized = Recording::new("standup.wav".to_string(), "/tmp/standup.wav".to_string());
+    summarized.category = Some("engineering/standup".to_string());
+    summarized.tags = vec!["backend".to_string()];
+    let mut transcribed = Recording::new("review.wav".to_string(), "/tmp/review.wav".to_string());
+    transcribed.category = Some("design".to_string());
+    transcribed.color_label = Some(ColorLabel::Red);
+    let mut failed = Recording::new("sales_100%.wav".to_string(), "/tmp/sales.wav".to_string());
+    failed.tags = vec!["sales".to_string()];
+    let untouched = Recording::new("memo.wav".to_string(), "/tmp/memo.wav".to_string());
+    for recording in [&summarized, &transcribed, &failed, &untouched] {
+        database.create_recording(recording).await.unwrap();
+    }
+
+    let transcription = Transcription::new(summarized.id.clone(), "本文".to_string(), "ja".to_string())
+        .with_status(TranscriptionStatus::Completed);
+    database.create_transcription(&transcription).await.unwrap();
+    database
+        .create_summary(&Summary::new(transcription.id.clone(), "llama3".to_string()))
+        .await
+        .unwrap();
+    database
+        .create_transcription(
+            &Transcription::new(transcribed.id.clone(), "本文".to_string(), "ja".to_string())
+                .with_status(TranscriptionStatus::Processing),
+        )
+        .await
+        .unwrap();
+    database
+        .create_transcription(
+            &Transcription::new(failed.id.clone(), String::new(), "ja".to_string())
+                .with_status(TranscriptionStatus::Failed("timeout".to_string())),
+        )
+        .await
+        .unwrap();
+
+    Fixture { database, summarized, transcribed, failed, untouched }
+}
+
+async fn search(database: &Database, query: RecordingQuery) -> Vec<String> {
+    let mut ids: Vec<String> = database
+        .search_recordings(&query)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|recording| recording.id)
+        .collect();
+    ids.sort();
+    ids
+}
+
+fn sorted(recordings: &[&Recording]) -> Vec<String> {
+    let mut ids: Vec<String> = recordings.iter().map(|recording| recording.id.clone()).collect();
+    ids.sort();
+    ids
+}
+
+#[tokio::test]
+async fn test_has_transcription_and_summary_predicates() {
+    let f = fixture().await;
+
+    let with_transcription = RecordingQuery { has_transcription: Some(true), ..Default::default() };
+    assert_eq!(search(&f.database, with_transcription).await, sorted(&[&f.summarized, &f.transcribed, &f.failed]));
+
+    let without_transcription = RecordingQuery { has_transcription: Some(false), ..Default::default() };
+    assert_eq!(search(&f.database, without_transcription).await, sorted(&[&f.untouched]));
+
+    let without_summary = RecordingQuery { has_transcription: Some(true), has_summary: Some(false), ..Default::default() };
+    assert_eq!(search(&f.database, without_summary).await, sorted(&[&f.transcribed, &f.failed]));
+}
+
+#[tokio::test]
+async fn test_transcription_status_filter() {
+    let f = fixture().await;
+
+    let query = RecordingQuery {
+        transcription_statuses: vec![TranscriptionStatusFilter::Failed, TranscriptionStatusFilter::Processing],
+        ..Default::default()
+    };
+    assert_eq!(search(&f.database, query).await, sorted(&[&f.transcribed, &f.failed]));
+}
+
+#[tokio::test]
+async fn test_nested_and_or_groups() {
+    let f = fixture().await;
+
+    // (engineering 配下 OR 赤ラベル) AND 要約なし
+    let query = RecordingQuery {
+        condition: Some(QueryCondition::And {
+            conditions: vec![
+                QueryCondition::Or {
+                    conditions: vec![
+                        QueryCondition::Category { path: "engineering".to_string() },
+                        QueryCondition::ColorLabel { label: ColorLabel::Red },
+                    ],
+                },
+                QueryCondition::HasSummary { value: false },
+            ],
+        }),
+        ..Default::default()
+    };
+    assert_eq!(search(&f.database, query).await, sorted(&[&f.transcribed]));
+
+    // NOT はカテゴリ未設定の録音も含める
+    let query = RecordingQuery {
+        condition: Some(QueryCondition::Not {
+            condition: Box::new(QueryCondition::Category { path: "design".to_string() }),
+        }),
+        ..Default::default()
+    };
+    assert_eq!(search(&f.database, query).await, sorted(&[&f.summarized, &f.failed, &f.untouched]));
+
+    let query = RecordingQuery {
+        condition: Some(QueryCondition::Or {
+            conditions: vec![
+                QueryCondition::Tag { path: "sales".to_string() },
+                QueryCondition::Tag { path: "backend".to_string() },
+            ],
+        }),
+        ..Default::default()
+    };
+    assert_eq!(search(&f.database, query).await, sorted(&[&f.summarized, &f.failed]));
+}
+
+#[tokio::test]
+async fn test_empty_groups_and_escaped_text() {
+    let f = fixture().await;
+
+    let all = RecordingQuery { condition: Some(QueryCondition::And { conditions: Vec::new() }), ..Default::default() };
+    assert_eq!(search(&f.database, all).await.len(), 4);
+
+    let none = RecordingQuery { condition: Some(QueryCondition::Or { conditions: Vec::new() }), ..Default::default() };
+    assert!(search(&f.database, none).await.is_empty());
+
+    // % はワイルドカードとして扱わない
+    let text = RecordingQuery { condition: Some(QueryCondition::Text { text: "100%".to_string() }), ..Default::default() };
+    assert_eq!(search(&f.database, text).await, sorted(&[&f.failed]));
+    let text = RecordingQuery { condition: Some(QueryCondition::Text { text: "%".to_string() }), ..Default::default() };
+    assert_eq!(search(&f.database, text).await, sorted(&[&f.failed]));
+}
+
+#[test]
+fn test_condition_json_shape() {
+    let condition: QueryCondition = serde_json::from_str(
+        r#"{"type":"or","conditions":[{"type":"has_summary","value":true},{"type":"transcription_status","status":"failed"}]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        condition,
+        QueryCondition::Or {
+            conditions: vec![
+                QueryCondition::HasSummary { value: true },
+                QueryCondition::TranscriptionStatus { status: TranscriptionStatusFilter::Failed },
+            ],
+        }
+    );
+}
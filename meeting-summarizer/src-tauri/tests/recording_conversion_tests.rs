@@ -0,0 +1,98 @@
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::Recording;
+use meeting_summarizer_lib::services::audio_convert;
+use meeting_summarizer_lib::services::recording_conversion::{self, output_path_for};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn write_test_wav(path: &Path, seconds: u32) -> Vec<i16> {
+    let spec = WavSpec {
+        channels: 2,
+        sample_rate: 22_050,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec).unwrap();
+    let mut samples = Vec::new();
+    for i in 0..22_050 * seconds {
+        let t = i as f32 / 22_050.0;
+        let left = ((t * 440.0 * std::f32::consts::TAU).sin() * 12_000.0) as i16;
+        let right = if i % 3_000 < 1_500 { 0 } else { ((t * 220.0 * std::f32::consts::TAU).sin() * 8_000.0) as i16 };
+        for sample in [left, right] {
+            writer.write_sample(sample).unwrap();
+            samples.push(sample);
+        }
+    }
+    writer.finalize().unwrap();
+    samples
+}
+
+#[test]
+fn test_output_path_for_avoids_overwriting_input() {
+    assert_eq!(output_path_for(Path::new("/tmp/a/meeting.wav"), "flac"), Path::new("/tmp/a/meeting.flac"));
+    assert_eq!(output_path_for(Path::new("/tmp/a/meeting.wav"), "wav"), Path::new("/tmp/a/meeting_converted.wav"));
+    assert_eq!(output_path_for(Path::new("/tmp/a/meeting.m4a"), "whisper"), Path::new("/tmp/a/meeting_16k.wav"));
+}
+
+#[test]
+fn test_flac_round_trip_is_lossless() {
+    let temp_dir = TempDir::new().unwrap();
+    let wav_path = temp_dir.path().join("meeting.wav");
+    let original = write_test_wav(&wav_path, 2);
+
+    let flac_path = temp_dir.path().join("meeting.flac");
+    let converted = audio_convert::transcode(&wav_path, &flac_path, "flac").unwrap();
+    assert_eq!(converted.source_sample_rate, 22_050);
+    assert_eq!(converted.source_channels, 2);
+    assert!(std::fs::metadata(&flac_path).unwrap().len() < std::fs::metadata(&wav_path).unwrap().len());
+
+    let decoded_path = temp_dir.path().join("decoded.wav");
+    let decoded = audio_convert::transcode(&flac_path, &decoded_path, "wav").unwrap();
+    assert!((decoded.duration_secs - 2.0).abs() < 0.01);
+
+    let mut reader = WavReader::open(&decoded_path).unwrap();
+    assert_eq!(reader.spec().channels, 2);
+    assert_eq!(reader.spec().sample_rate, 22_050);
+    let samples: Vec<i16> = reader.samples::<i16>().map(Result::unwrap).collect();
+    assert_eq!(samples, original);
+}
+
+#[test]
+fn test_transcode_rejects_unsupported_format() {
+    let temp_dir = TempDir::new().unwrap();
+    let wav_path = temp_dir.path().join("meeting.wav");
+    write_test_wav(&wav_path, 1);
+
+    assert!(audio_convert::transcode(&wav_path, &temp_dir.path().join("meeting.opus"), "opus").is_err());
+}
+
+#[tokio::test]
+async fn test_convert_recording_replaces_original() {
+    let temp_dir = TempDir::new().unwrap();
+    let wav_path = temp_dir.path().join("meeting.wav");
+    write_test_wav(&wav_path, 1);
+
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("meeting.wav".to_string(), wav_path.to_string_lossy().to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let result = recording_conversion::convert_recording(&database, &recording.id, "FLAC", true)
+        .await
+        .unwrap();
+    assert!(result.replaced_original);
+    assert!(Path::new(&result.output_path).exists());
+    assert!(!wav_path.exists());
+
+    let updated = database.get_recording(&recording.id).await.unwrap().unwrap();
+    assert_eq!(updated.filename, "meeting.flac");
+    assert_eq!(updated.file_path, result.output_path);
+    assert_eq!(updated.file_size, Some(result.file_size));
+
+    // FLAC から書き起こし用の WAV へ変換できる
+    let whisper = recording_conversion::convert_recording(&database, &recording.id, "whisper", false)
+        .await
+        .unwrap();
+    assert!(audio_convert::is_whisper_ready(Path::new(&whisper.output_path)));
+    assert!((whisper.duration_secs - 1.0).abs() < 0.01);
+}
@@ -0,0 +1,46 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, RecordingSegment, RecordingTrack, Summary, TranscriptSegment, Transcription};
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_delete_recording_removes_related_rows() {
+    let temp_dir = TempDir::new().unwrap();
+    let database = Database::new(temp_dir.path().join("delete.db")).unwrap();
+    let recording = Recording::new("meeting.wav".to_string(), "/tmp/meeting.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let transcription = Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+    database
+        .create_transcript_segments(&[TranscriptSegment::new(transcription.id.clone(), 0, 0, 1_000, "本文".to_string())])
+        .await
+        .unwrap();
+    let summary = Summary::new(transcription.id.clone(), "llama3".to_string());
+    database.create_summary(&summary).await.unwrap();
+    database
+        .create_recording_segment(&RecordingSegment::new(recording.id.clone(), 0, "/tmp/meeting.wav".to_string(), 0, 1_000))
+        .await
+        .unwrap();
+    database
+        .create_recording_track(&RecordingTrack::new(
+            recording.id.clone(),
+            1,
+            "Phone".to_string(),
+            "/tmp/meeting_track01.wav".to_string(),
+            1_000,
+        ))
+        .await
+        .unwrap();
+
+    assert!(database.delete_recording(&recording.id).await.unwrap());
+    assert!(database.get_transcriptions_by_recording(&recording.id).await.unwrap().is_empty());
+    assert!(database.get_segments_by_transcription(&transcription.id).await.unwrap().is_empty());
+    assert!(database.get_summaries_by_transcription(&transcription.id).await.unwrap().is_empty());
+    assert!(database.get_recording_segments(&recording.id).await.unwrap().is_empty());
+    assert!(database.get_recording_tracks(&recording.id).await.unwrap().is_empty());
+
+    // 外部キーが有効なので、存在しない録音には書き起こしを作れない
+    let orphan = Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string());
+    assert!(database.create_transcription(&orphan).await.is_err());
+    assert!(!database.delete_recording(&recording.id).await.unwrap());
+}
@@ -0,0 +1,101 @@
+use hound::{SampleFormat, WavSpec, WavWriter};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::errors::validate_audio_format;
+use meeting_summarizer_lib::services::audio_convert::{is_whisper_ready, prepare_for_whisper};
+use meeting_summarizer_lib::services::recording_encoder::{
+    encode_or_keep, encode_recording, ensure_supported, ffmpeg_available, format_options,
+};
+use meeting_summarizer_lib::services::{demo_mode, RecordingFormat, RecordingProfile, RecordingService};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn write_wav(path: &Path, seconds: u32) {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: 16_000,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec).unwrap();
+    for index in 0..16_000 * seconds {
+        writer.write_sample(((index as f32 * 0.03).sin() * 8_000.0) as i16).unwrap();
+    }
+    writer.finalize().unwrap();
+}
+
+#[test]
+fn test_format_settings() {
+    let profile: RecordingProfile = serde_json::from_str("{}").unwrap();
+    assert_eq!(profile.format, RecordingFormat::Wav);
+    let profile: RecordingProfile = serde_json::from_str(r#"{"format": "opus"}"#).unwrap();
+    assert_eq!(profile.format, RecordingFormat::Opus);
+    assert_eq!(RecordingFormat::Mp3.extension(), "mp3");
+
+    // ffmpeg が無ければ Opus・MP3 は選択肢に出さず、設定もできない
+    let options = format_options();
+    assert_eq!(&options[..2], &[RecordingFormat::Wav, RecordingFormat::Flac]);
+    assert_eq!(options.len(), if ffmpeg_available() { 4 } else { 2 });
+    assert!(ensure_supported(RecordingFormat::Flac).is_ok());
+    assert_eq!(ensure_supported(RecordingFormat::Opus).is_ok(), ffmpeg_available());
+
+    assert!(validate_audio_format(&PathBuf::from("meeting.opus")).is_ok());
+    assert!(validate_audio_format(&PathBuf::from("meeting.aiff")).is_err());
+}
+
+#[test]
+fn test_flac_recording_is_decoded_for_whisper() {
+    let temp_dir = TempDir::new().unwrap();
+    let wav = temp_dir.path().join("meeting.wav");
+    write_wav(&wav, 2);
+    let wav_size = std::fs::metadata(&wav).unwrap().len();
+
+    assert_eq!(encode_recording(&wav, RecordingFormat::Wav).unwrap(), wav);
+    let flac = encode_recording(&wav, RecordingFormat::Flac).unwrap();
+    assert_eq!(flac, temp_dir.path().join("meeting.flac"));
+    assert!(!wav.exists());
+    assert!(std::fs::metadata(&flac).unwrap().len() < wav_size);
+
+    let converted = prepare_for_whisper(&flac, &temp_dir.path().join("converted"), "meeting").unwrap();
+    assert!(is_whisper_ready(&converted));
+    assert_eq!(hound::WavReader::open(&converted).unwrap().duration(), 32_000);
+}
+
+#[test]
+fn test_opus_without_ffmpeg_keeps_wav() {
+    let temp_dir = TempDir::new().unwrap();
+    let wav = temp_dir.path().join("meeting.wav");
+    write_wav(&wav, 1);
+
+    let saved = encode_or_keep(&wav, RecordingFormat::Opus);
+    if ffmpeg_available() {
+        assert_eq!(saved, temp_dir.path().join("meeting.opus"));
+        let converted = prepare_for_whisper(&saved, &temp_dir.path().join("converted"), "meeting").unwrap();
+        assert!(is_whisper_ready(&converted));
+    } else {
+        assert_eq!(saved, wav);
+        assert!(wav.exists());
+        assert!(!temp_dir.path().join("meeting.opus").exists());
+    }
+}
+
+#[tokio::test]
+async fn test_stopped_recording_is_saved_as_flac() {
+    demo_mode::set_enabled(true);
+
+    let temp_dir = TempDir::new().unwrap();
+    let database = Arc::new(Database::new(temp_dir.path().join("encoder.db")).unwrap());
+    let recording_service = RecordingService::new(database, temp_dir.path().join("recordings")).unwrap();
+    let profile = RecordingProfile {
+        format: RecordingFormat::Flac,
+        ..RecordingProfile::default()
+    };
+    recording_service.apply_recording_profile(&profile).await.unwrap();
+
+    recording_service.start_recording().await.unwrap();
+    let recording = recording_service.stop_recording().await.unwrap();
+    assert!(recording.file_path.ends_with(".flac"));
+    assert!(recording.filename.ends_with(".flac"));
+    assert!(Path::new(&recording.file_path).exists());
+    assert_eq!(recording.file_size, Some(std::fs::metadata(&recording.file_path).unwrap().len() as i64));
+}
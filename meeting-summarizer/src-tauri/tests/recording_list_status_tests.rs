@@ -0,0 +1,85 @@
+use chrono::{Duration, Utc};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{
+    Recording, RecordingQuery, Summary, SummaryStatus, Transcription, TranscriptionStatus,
+};
+
+#[tokio::test]
+async fn test_list_items_carry_latest_status() {
+    let database = Database::in_memory().unwrap();
+
+    let summarized = Recording::new("summarized.wav".to_string(), "/tmp/summarized.wav".to_string());
+    let retried = Recording::new("retried.wav".to_string(), "/tmp/retried.wav".to_string());
+    let untouched = Recording::new("untouched.wav".to_string(), "/tmp/untouched.wav".to_string());
+    for recording in [&summarized, &retried, &untouched] {
+        database.create_recording(recording).await.unwrap();
+    }
+
+    let transcription = Transcription::new(summarized.id.clone(), "本文".to_string(), "ja".to_string())
+        .with_status(TranscriptionStatus::Completed);
+    database.create_transcription(&transcription).await.unwrap();
+    let mut summary = Summary::new(transcription.id.clone(), "llama3".to_string());
+    summary.status = SummaryStatus::Completed;
+    database.create_summary(&summary).await.unwrap();
+
+    // 失敗した後に再実行中の書き起こしは、新しい方の状態になる
+    let mut failed = Transcription::new(retried.id.clone(), String::new(), "ja".to_string())
+        .with_status(TranscriptionStatus::Failed("timeout".to_string()));
+    failed.created_at = Utc::now() - Duration::minutes(5);
+    database.create_transcription(&failed).await.unwrap();
+    database
+        .create_transcription(
+            &Transcription::new(retried.id.clone(), String::new(), "ja".to_string())
+                .with_status(TranscriptionStatus::Processing),
+        )
+        .await
+        .unwrap();
+
+    let items = database.get_recordings_with_status(false).await.unwrap();
+    assert_eq!(items.len(), 3);
+    let item = |id: &str| items.iter().find(|item| item.recording.id == id).unwrap();
+
+    let summarized_item = item(&summarized.id);
+    assert!(summarized_item.has_transcription && summarized_item.has_summary);
+    assert!(matches!(summarized_item.latest_transcription_status, Some(TranscriptionStatus::Completed)));
+    assert!(matches!(summarized_item.latest_summary_status, Some(SummaryStatus::Completed)));
+
+    let retried_item = item(&retried.id);
+    assert!(retried_item.has_transcription && !retried_item.has_summary);
+    assert!(matches!(retried_item.latest_transcription_status, Some(TranscriptionStatus::Processing)));
+
+    let untouched_item = item(&untouched.id);
+    assert!(!untouched_item.has_transcription && !untouched_item.has_summary);
+    assert!(untouched_item.latest_transcription_status.is_none());
+}
+
+#[tokio::test]
+async fn test_search_with_status_keeps_filters_and_order() {
+    let database = Database::in_memory().unwrap();
+    let base = Utc::now();
+    for i in 0..3 {
+        let mut recording = Recording::new(format!("standup_{}.wav", i), format!("/tmp/standup_{}.wav", i));
+        recording.created_at = base - Duration::minutes(i);
+        database.create_recording(&recording).await.unwrap();
+        database
+            .create_transcription(&Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string()))
+            .await
+            .unwrap();
+    }
+    database
+        .create_recording(&Recording::new("other.wav".to_string(), "/tmp/other.wav".to_string()))
+        .await
+        .unwrap();
+
+    let query = RecordingQuery { search_text: Some("standup".to_string()), ..Default::default() };
+    let plain: Vec<String> = database.search_recordings(&query).await.unwrap().into_iter().map(|r| r.id).collect();
+    let with_status = database.search_recordings_with_status(&query).await.unwrap();
+
+    assert_eq!(with_status.iter().map(|item| item.recording.id.clone()).collect::<Vec<_>>(), plain);
+    assert!(with_status.iter().all(|item| item.has_transcription));
+
+    // 一覧の JSON は録音のフィールドに状態を並べた形
+    let json = serde_json::to_value(&with_status[0]).unwrap();
+    assert!(json.get("filename").is_some());
+    assert_eq!(json["has_transcription"], true);
+}
@@ -0,0 +1,99 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, RecordingMarker, TranscriptSegment, Transcription};
+use meeting_summarizer_lib::services::recording_markers::{
+    excerpts_for_transcription, format_excerpts_for_prompt, marker_excerpts, normalize_label, transcript_timeline,
+    TimelineItem,
+};
+
+fn segments() -> Vec<TranscriptSegment> {
+    vec![
+        TranscriptSegment::new("t".to_string(), 0, 0, 10_000, "冒頭の挨拶".to_string()),
+        TranscriptSegment::new("t".to_string(), 1, 40_000, 50_000, "予算は据え置きで決定".to_string()),
+        TranscriptSegment::new("t".to_string(), 2, 52_000, 60_000, "担当は田中".to_string()),
+        TranscriptSegment::new("t".to_string(), 3, 120_000, 130_000, "雑談".to_string()),
+    ]
+}
+
+#[test]
+fn test_excerpts_include_segments_around_marker() {
+    let markers = vec![RecordingMarker::new("rec".to_string(), 55_000, Some("決定".to_string()))];
+
+    let excerpts = marker_excerpts(&markers, &segments(), 15_000);
+
+    assert_eq!(excerpts.len(), 1);
+    assert_eq!(excerpts[0].text, "予算は据え置きで決定 担当は田中");
+    assert_eq!(format_excerpts_for_prompt(&excerpts), "- [00:55] 決定: 予算は据え置きで決定 担当は田中");
+}
+
+#[test]
+fn test_markers_without_speech_are_skipped() {
+    let markers = vec![RecordingMarker::new("rec".to_string(), 90_000, None)];
+
+    assert!(marker_excerpts(&markers, &segments(), 15_000).is_empty());
+}
+
+#[test]
+fn test_blank_labels_are_dropped() {
+    assert_eq!(normalize_label(Some("  ".to_string())), None);
+    assert_eq!(normalize_label(Some(" 決定 ".to_string())), Some("決定".to_string()));
+    assert_eq!(normalize_label(None), None);
+}
+
+#[test]
+fn test_timeline_places_markers_between_segments() {
+    let markers = vec![
+        RecordingMarker::new("rec".to_string(), 200_000, None),
+        RecordingMarker::new("rec".to_string(), 55_000, Some("決定".to_string())),
+        RecordingMarker::new("rec".to_string(), 40_000, None),
+    ];
+
+    let timeline = transcript_timeline(&segments(), &markers);
+
+    let kinds = timeline
+        .iter()
+        .map(|item| match item {
+            TimelineItem::Segment(segment) => format!("s{}", segment.segment_index),
+            TimelineItem::Marker(marker) => format!("m{}", marker.offset_ms),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(kinds, vec!["s0", "m40000", "s1", "s2", "m55000", "s3", "m200000"]);
+    assert_eq!(timeline[4].offset_ms(), 55_000);
+
+    let json = serde_json::to_value(&timeline[4]).unwrap();
+    assert_eq!(json["kind"], "marker");
+    assert_eq!(json["label"], "決定");
+}
+
+#[tokio::test]
+async fn test_markers_are_ordered_and_removed_with_recording() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("markers.wav".to_string(), "/tmp/markers.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+    let mut transcript_segments = segments();
+    for segment in &mut transcript_segments {
+        segment.transcription_id = transcription.id.clone();
+    }
+    database.create_transcript_segments(&transcript_segments).await.unwrap();
+
+    for offset_ms in [125_000, 45_000] {
+        database
+            .create_marker(&RecordingMarker::new(recording.id.clone(), offset_ms, None))
+            .await
+            .unwrap();
+    }
+
+    let markers = database.get_markers(&recording.id).await.unwrap();
+    assert_eq!(markers.iter().map(|m| m.offset_ms).collect::<Vec<_>>(), vec![45_000, 125_000]);
+
+    let excerpts = excerpts_for_transcription(&database, &transcription.id).await.unwrap();
+    assert_eq!(excerpts.len(), 2);
+    assert_eq!(excerpts[1].text, "雑談");
+
+    assert!(database.update_marker_label(&markers[0].id, Some("予算")).await.unwrap());
+    assert_eq!(database.get_markers(&recording.id).await.unwrap()[0].label.as_deref(), Some("予算"));
+
+    database.delete_recording(&recording.id).await.unwrap();
+    assert!(database.get_markers(&recording.id).await.unwrap().is_empty());
+}
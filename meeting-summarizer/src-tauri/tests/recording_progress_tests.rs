@@ -0,0 +1,29 @@
+use meeting_summarizer_lib::services::recording_progress::{wav_file_size, RecordingProgress};
+
+#[test]
+fn test_wav_file_size_matches_hound_output() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("progress.wav");
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 16_000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+    for _ in 0..16_000 {
+        writer.write_sample(0i16).unwrap();
+    }
+    writer.finalize().unwrap();
+
+    assert_eq!(wav_file_size(16_000, 1), std::fs::metadata(&path).unwrap().len());
+}
+
+#[test]
+fn test_progress_reports_size_and_recorded_duration() {
+    let progress = RecordingProgress::new("session-1".to_string(), 2_150, 32_000, 16_000, 1);
+
+    assert_eq!(progress.file_size_bytes, 44 + 64_000);
+    assert_eq!(progress.recorded_ms(), 2_000);
+    assert_eq!(progress.elapsed_ms, 2_150);
+}
@@ -0,0 +1,93 @@
+use hound::{SampleFormat, WavSpec, WavWriter};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::services::audio_convert::{is_whisper_ready, prepare_for_whisper};
+use meeting_summarizer_lib::services::recording_progress::pcm_wav_file_size;
+use meeting_summarizer_lib::services::recording_quality::presets;
+use meeting_summarizer_lib::services::{
+    demo_mode, AudioFormat, CaptureSource, RecordingProfile, RecordingQuality, RecordingService,
+};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+#[test]
+fn test_presets_formats() {
+    assert_eq!(
+        RecordingQuality::Voice.format(),
+        AudioFormat {
+            sample_rate: 16_000,
+            bits_per_sample: 16,
+            channels: 1,
+        }
+    );
+    let high = RecordingQuality::High.format();
+    assert_eq!((high.sample_rate, high.channels, high.bits_per_sample), (48_000, 2, 24));
+    assert_eq!(high.bytes_per_second(), 48_000 * 2 * 3);
+    assert_eq!(pcm_wav_file_size(48_000, 2, 24), 44 + high.bytes_per_second());
+
+    let presets = presets();
+    assert_eq!(presets.len(), 3);
+    let json = serde_json::to_value(&presets[1]).unwrap();
+    assert_eq!(json["quality"], "standard");
+    assert_eq!(json["sample_rate"], 44_100);
+}
+
+#[test]
+fn test_profile_defaults_to_voice_quality() {
+    let profile: RecordingProfile = serde_json::from_str(r#"{"source": {"type": "microphone"}}"#).unwrap();
+    assert_eq!(profile.quality, RecordingQuality::Voice);
+
+    let profile: RecordingProfile = serde_json::from_str(r#"{"quality": "high"}"#).unwrap();
+    assert_eq!(profile.quality, RecordingQuality::High);
+}
+
+#[test]
+fn test_high_quality_recording_is_converted_for_whisper() {
+    let temp_dir = TempDir::new().unwrap();
+    let input = temp_dir.path().join("high.wav");
+    let format = RecordingQuality::High.format();
+    let spec = WavSpec {
+        channels: format.channels,
+        sample_rate: format.sample_rate,
+        bits_per_sample: format.bits_per_sample,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(&input, spec).unwrap();
+    for index in 0..format.sample_rate {
+        let sample = ((index as f32 * 0.05).sin() * 4_000_000.0) as i32;
+        writer.write_sample(sample).unwrap();
+        writer.write_sample(-sample).unwrap();
+    }
+    writer.finalize().unwrap();
+    assert!(!is_whisper_ready(&input));
+
+    let converted = prepare_for_whisper(&input, &temp_dir.path().join("converted"), "high").unwrap();
+    assert_ne!(converted, input);
+    assert!(is_whisper_ready(&converted));
+    let reader = hound::WavReader::open(&converted).unwrap();
+    let frames = reader.duration();
+    assert!((15_900..=16_100).contains(&frames), "unexpected length: {}", frames);
+}
+
+#[tokio::test]
+async fn test_profile_quality_is_applied() {
+    demo_mode::set_enabled(true);
+
+    let temp_dir = TempDir::new().unwrap();
+    let database = Arc::new(Database::new(temp_dir.path().join("quality.db")).unwrap());
+    let recording_service = RecordingService::new(database, temp_dir.path().join("recordings")).unwrap();
+    assert_eq!(recording_service.quality().await, RecordingQuality::Voice);
+
+    let profile = RecordingProfile {
+        source: CaptureSource::Microphone,
+        quality: RecordingQuality::High,
+        ..RecordingProfile::default()
+    };
+    recording_service.apply_recording_profile(&profile).await.unwrap();
+    assert_eq!(recording_service.quality().await, RecordingQuality::High);
+
+    // デモモードは音質の指定に関わらず書き起こし向けの形式で書き出す
+    recording_service.start_recording_with_quality(Some(RecordingQuality::Standard)).await.unwrap();
+    let progress = recording_service.current_progress().await.unwrap();
+    assert_eq!((progress.sample_rate, progress.channels), (16_000, 1));
+    recording_service.stop_recording().await.unwrap();
+}
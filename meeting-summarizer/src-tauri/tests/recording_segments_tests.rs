@@ -0,0 +1,145 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, RecordingSegment, TranscriptSegment, Transcription};
+use meeting_summarizer_lib::services::recording_segments::{
+    audio_files, existing_segments, max_segment_duration, merge_transcriptions, segment_path,
+};
+use meeting_summarizer_lib::services::recording_spool::{read_spool, recover_files, recover_orphaned_recordings, SpoolTarget};
+use std::path::PathBuf;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[test]
+fn test_segment_paths() {
+    let output = PathBuf::from("/tmp/recording_temp_1700000000.wav");
+    assert_eq!(segment_path(&output, 0), output);
+    assert_eq!(segment_path(&output, 1), PathBuf::from("/tmp/recording_temp_1700000000_part002.wav"));
+    assert_eq!(segment_path(&output, 11), PathBuf::from("/tmp/recording_temp_1700000000_part012.wav"));
+
+    assert_eq!(max_segment_duration(None).unwrap(), None);
+    assert_eq!(max_segment_duration(Some(30)).unwrap(), Some(Duration::from_secs(1800)));
+    assert!(max_segment_duration(Some(0)).is_err());
+}
+
+#[test]
+fn test_spool_rolls_over_at_segment_length() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = temp_dir.path().join("recording_temp_1700000000.wav");
+    let target = SpoolTarget::new(&output, Some(Duration::from_secs(1)));
+
+    // 10Hz・ステレオなので1区間は10フレーム（20サンプル）
+    let mut spool = target.create(10, 2).unwrap();
+    let samples: Vec<f32> = (0..50).map(|index| index as f32 / 100.0).collect();
+    spool.append(&samples[..14]).unwrap();
+    spool.append(&samples[14..]).unwrap();
+    spool.finish().unwrap();
+
+    let lengths: Vec<usize> = (0..3)
+        .map(|index| read_spool(&target.spool_path(index)).unwrap().samples.len())
+        .collect();
+    assert_eq!(lengths, vec![20, 20, 10]);
+    assert!(!target.spool_path(3).exists());
+    assert_eq!(read_spool(&target.spool_path(1)).unwrap().samples[0], samples[20]);
+    assert!(target.system().spool_path(1).ends_with("recording_temp_1700000000_part002.system.part"));
+
+    target.remove_all();
+    assert!(!target.spool_path(0).exists());
+    assert!(!target.spool_path(2).exists());
+}
+
+#[test]
+fn test_interrupted_segments_are_recovered_as_one_recording() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = temp_dir.path().join("recording_temp_1700000000.wav");
+    let target = SpoolTarget::new(&output, Some(Duration::from_secs(1)));
+    let mut spool = target.create(16_000, 1).unwrap();
+    spool.append(&vec![0.25; 24_000]).unwrap();
+    spool.finish().unwrap();
+
+    let recovered = recover_files(temp_dir.path(), None).unwrap();
+    assert_eq!(recovered.len(), 1);
+    assert!(recovered[0].path.ends_with("recording_20231114_221320_recovered.wav"));
+    let names: Vec<String> = recovered[0]
+        .segments
+        .iter()
+        .map(|(path, _)| path.file_name().unwrap().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(names, vec!["recording_20231114_221320_recovered.wav", "recording_20231114_221320_recovered_part002.wav"]);
+    let durations: Vec<i64> = recovered[0].segments.iter().map(|(_, duration_ms)| *duration_ms).collect();
+    assert_eq!(durations, vec![1_000, 500]);
+    assert_eq!(recovered[0].duration_secs, 1);
+}
+
+#[tokio::test]
+async fn test_recovered_segments_are_linked_to_the_recording() {
+    let temp_dir = TempDir::new().unwrap();
+    let database = Database::in_memory().unwrap();
+    let target = SpoolTarget::new(&temp_dir.path().join("recording_temp_1700000000.wav"), Some(Duration::from_secs(1)));
+    let mut spool = target.create(16_000, 1).unwrap();
+    spool.append(&vec![0.25; 40_000]).unwrap();
+    spool.finish().unwrap();
+
+    let recovered = recover_orphaned_recordings(&database, temp_dir.path(), None).await.unwrap();
+    assert_eq!(recovered.len(), 1);
+    let segments = database.get_recording_segments(&recovered[0].id).await.unwrap();
+    let offsets: Vec<i64> = segments.iter().map(|segment| segment.offset_ms).collect();
+    assert_eq!(offsets, vec![0, 1_000, 2_000]);
+    assert_eq!(audio_files(&database, &recovered[0]).await.unwrap().len(), 3);
+    assert_eq!(recovered[0].file_size, Some(segments.iter().map(|segment| segment.file_size).sum()));
+}
+
+#[test]
+fn test_transcriptions_of_segments_are_joined() {
+    let parts = vec![
+        Transcription::new("rec".to_string(), "前半の議題".to_string(), "ja".to_string())
+            .with_confidence(Some(0.9))
+            .with_processing_time(Some(1_000)),
+        Transcription::new("rec".to_string(), "  ".to_string(), "ja".to_string()).with_processing_time(Some(200)),
+        Transcription::new("rec".to_string(), "後半の結論".to_string(), "ja".to_string())
+            .with_confidence(Some(0.7))
+            .with_processing_time(Some(800)),
+    ];
+    let first_id = parts[0].id.clone();
+    let mut parts = parts;
+    parts[0].segments = vec![TranscriptSegment::new(first_id.clone(), 0, 0, 1_000, "前半の議題".to_string())];
+    parts[2].segments = vec![TranscriptSegment::new(parts[2].id.clone(), 0, 60_000, 61_000, "後半の結論".to_string())];
+
+    let merged = merge_transcriptions(parts).unwrap();
+    assert_eq!(merged.id, first_id);
+    assert_eq!(merged.text, "前半の議題\n後半の結論");
+    assert_eq!(merged.processing_time_ms, Some(2_000));
+    assert!((merged.confidence.unwrap() - 0.8).abs() < 1e-6);
+    // 区間は先頭の書き起こしに付け替えて通し番号にする
+    assert_eq!(merged.segments.len(), 2);
+    assert!(merged.segments.iter().all(|segment| segment.transcription_id == first_id));
+    assert_eq!(merged.segments[1].segment_index, 1);
+    assert_eq!(merged.segments[1].start_ms, 60_000);
+
+    assert!(merge_transcriptions(Vec::new()).is_none());
+}
+
+#[tokio::test]
+async fn test_segments_are_linked_to_one_recording() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("long.wav".to_string(), "/tmp/long.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+    assert_eq!(audio_files(&database, &recording).await.unwrap(), vec![PathBuf::from("/tmp/long.wav")]);
+
+    // 登録の順に関係なく区間の順に返す
+    for (index, path) in [(1, "/tmp/long_part002.wav"), (0, "/tmp/long.wav")] {
+        let segment = RecordingSegment::new(recording.id.clone(), index, path.to_string(), index as i64 * 60_000, 60_000)
+            .with_file_size(1_024);
+        database.create_recording_segment(&segment).await.unwrap();
+    }
+
+    let segments = database.get_recording_segments(&recording.id).await.unwrap();
+    assert_eq!(segments.iter().map(|s| s.offset_ms).collect::<Vec<_>>(), vec![0, 60_000]);
+    assert_eq!(
+        audio_files(&database, &recording).await.unwrap(),
+        vec![PathBuf::from("/tmp/long.wav"), PathBuf::from("/tmp/long_part002.wav")]
+    );
+    assert_eq!(database.get_all_recording_segment_paths().await.unwrap().len(), 2);
+
+    database.delete_recording(&recording.id).await.unwrap();
+    assert!(database.get_recording_segments(&recording.id).await.unwrap().is_empty());
+    assert!(existing_segments(&PathBuf::from("/tmp/does_not_exist.wav")).is_empty());
+}
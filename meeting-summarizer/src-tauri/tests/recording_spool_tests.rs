@@ -0,0 +1,173 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::services::{demo_mode, RecordingService};
+use meeting_summarizer_lib::services::recording_spool::{
+    primary_spool_path, read_spool, recover_files, recover_orphaned_recordings, system_spool_path, SpoolWriter,
+};
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn write_spool(path: &Path, sample_rate: u32, channels: u16, seconds: u32) {
+    let mut spool = SpoolWriter::create(path, sample_rate, channels).unwrap();
+    let samples: Vec<f32> = (0..sample_rate * seconds * channels as u32)
+        .map(|index| (index as f32 * 0.01).sin() * 0.5)
+        .collect();
+    spool.append(&samples).unwrap();
+    spool.finish().unwrap();
+}
+
+#[test]
+fn test_spool_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = temp_dir.path().join("recording_temp_1700000000.wav");
+    assert_eq!(primary_spool_path(&output), temp_dir.path().join("recording_temp_1700000000.primary.part"));
+    assert_eq!(system_spool_path(&output), temp_dir.path().join("recording_temp_1700000000.system.part"));
+
+    let spool_path = primary_spool_path(&output);
+    let mut spool = SpoolWriter::create(&spool_path, 48_000, 2).unwrap();
+    spool.append(&[0.1, -0.1, 0.2, -0.2]).unwrap();
+    spool.append(&[0.3, -0.3]).unwrap();
+    spool.finish().unwrap();
+
+    let audio = read_spool(&spool_path).unwrap();
+    assert_eq!(audio.sample_rate, 48_000);
+    assert_eq!(audio.channels, 2);
+    assert_eq!(audio.samples, vec![0.1, -0.1, 0.2, -0.2, 0.3, -0.3]);
+}
+
+#[test]
+fn test_spool_is_readable_before_finish() {
+    let temp_dir = TempDir::new().unwrap();
+    let spool_path = temp_dir.path().join("recording_temp_1.primary.part");
+    let mut spool = SpoolWriter::create(&spool_path, 16_000, 1).unwrap();
+    spool.append(&[0.25; 1600]).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    // 一定時間が経つとヘッダーが書き直され、落ちても読める状態になる
+    spool.append(&[0.25; 1600]).unwrap();
+
+    let audio = read_spool(&spool_path).unwrap();
+    assert_eq!(audio.samples.len(), 3200);
+    drop(spool);
+}
+
+#[test]
+fn test_orphaned_spool_is_recovered() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_wav = temp_dir.path().join("recording_temp_1700000000.wav");
+    std::fs::File::create(&temp_wav).unwrap();
+    write_spool(&primary_spool_path(&temp_wav), 44_100, 1, 2);
+
+    let recovered = recover_files(temp_dir.path(), None).unwrap();
+    assert_eq!(recovered.len(), 1);
+    assert_eq!(recovered[0].path, temp_dir.path().join("recording_20231114_221320_recovered.wav"));
+    assert_eq!(recovered[0].duration_secs, 2);
+
+    let reader = hound::WavReader::open(&recovered[0].path).unwrap();
+    assert_eq!(reader.spec().sample_rate, 44_100);
+    assert_eq!(reader.spec().bits_per_sample, 16);
+    assert!(!temp_wav.exists());
+    assert!(!primary_spool_path(&temp_wav).exists());
+
+    // 2回目は何も残っていない
+    assert!(recover_files(temp_dir.path(), None).unwrap().is_empty());
+}
+
+#[test]
+fn test_dual_source_spools_are_mixed() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_wav = temp_dir.path().join("recording_temp_1700000000.wav");
+    write_spool(&primary_spool_path(&temp_wav), 48_000, 1, 1);
+    write_spool(&system_spool_path(&temp_wav), 48_000, 1, 1);
+
+    let recovered = recover_files(temp_dir.path(), None).unwrap();
+    assert_eq!(recovered.len(), 1);
+    let reader = hound::WavReader::open(&recovered[0].path).unwrap();
+    assert_eq!(reader.spec().channels, 1);
+    assert_eq!(reader.duration(), 48_000);
+    assert!(!system_spool_path(&temp_wav).exists());
+}
+
+#[test]
+fn test_empty_leftovers_are_discarded() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_wav = temp_dir.path().join("recording_temp_1700000001.wav");
+    std::fs::File::create(&temp_wav).unwrap();
+    SpoolWriter::create(&primary_spool_path(&temp_wav), 16_000, 1).unwrap().finish().unwrap();
+    let unrelated = temp_dir.path().join("recording_20231114_221320_abc.wav");
+    std::fs::write(&unrelated, b"keep").unwrap();
+
+    assert!(recover_files(temp_dir.path(), None).unwrap().is_empty());
+    assert!(!temp_wav.exists());
+    assert!(!primary_spool_path(&temp_wav).exists());
+    assert!(unrelated.exists());
+}
+
+#[test]
+fn test_spools_of_the_active_recording_are_left_alone() {
+    let temp_dir = TempDir::new().unwrap();
+    let active = temp_dir.path().join("recording_temp_1700000000.wav");
+    write_spool(&primary_spool_path(&active), 16_000, 1, 1);
+    write_spool(&primary_spool_path(&temp_dir.path().join("recording_temp_1700000000_part002.wav")), 16_000, 1, 1);
+    let orphan = temp_dir.path().join("recording_temp_1600000000.wav");
+    write_spool(&primary_spool_path(&orphan), 16_000, 1, 1);
+
+    let recovered = recover_files(temp_dir.path(), Some(&active)).unwrap();
+    assert_eq!(recovered.len(), 1);
+    assert!(!primary_spool_path(&orphan).exists());
+    assert!(primary_spool_path(&active).exists());
+    assert!(temp_dir.path().join("recording_temp_1700000000_part002.primary.part").exists());
+}
+
+#[tokio::test]
+async fn test_recovered_recordings_are_registered() {
+    let temp_dir = TempDir::new().unwrap();
+    let recordings_dir = temp_dir.path().join("recordings");
+    std::fs::create_dir_all(&recordings_dir).unwrap();
+    let database = Database::new(temp_dir.path().join("spool.db")).unwrap();
+    write_spool(&primary_spool_path(&recordings_dir.join("recording_temp_1700000000.wav")), 16_000, 1, 3);
+
+    let recovered = recover_orphaned_recordings(&database, &recordings_dir, None).await.unwrap();
+    assert_eq!(recovered.len(), 1);
+    assert_eq!(recovered[0].duration, Some(3));
+    assert!(recovered[0].filename.ends_with("_recovered.wav"));
+
+    let recordings = database.get_recordings(false).await.unwrap();
+    assert_eq!(recordings.len(), 1);
+    assert_eq!(recordings[0].id, recovered[0].id);
+    assert_eq!(recordings[0].file_size, Some(std::fs::metadata(&recovered[0].file_path).unwrap().len() as i64));
+
+    // 録音フォルダが無ければ何もしない
+    assert!(recover_orphaned_recordings(&database, &temp_dir.path().join("missing"), None).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_failed_save_leaves_the_recording_for_recovery() {
+    demo_mode::set_enabled(true);
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("spool.db");
+    let recordings_dir = temp_dir.path().join("recordings");
+    let database = Arc::new(Database::new(&db_path).unwrap());
+    let recording_service = RecordingService::new(database.clone(), recordings_dir.clone()).unwrap();
+
+    // 停止後の登録だけ失敗させる
+    let connection = rusqlite::Connection::open(&db_path).unwrap();
+    connection
+        .execute_batch("CREATE TRIGGER fail_recordings BEFORE INSERT ON recordings BEGIN SELECT RAISE(ABORT, 'disk I/O error'); END;")
+        .unwrap();
+
+    recording_service.start_recording().await.unwrap();
+    assert!(recording_service.stop_recording().await.is_err());
+    assert!(recording_service.current_session_id().await.is_none());
+    assert!(database.get_recordings(false).await.unwrap().is_empty());
+
+    // セッションは外れているので、次の録音を始めて保存できる
+    connection.execute_batch("DROP TRIGGER fail_recordings;").unwrap();
+    recording_service.start_recording().await.unwrap();
+    let saved = recording_service.stop_recording().await.unwrap();
+
+    // 保存できなかった録音は一時ファイルのまま残り、復元で登録される
+    let recovered = recording_service.recover_orphaned_recordings().await.unwrap();
+    assert_eq!(recovered.len(), 1);
+    assert_ne!(recovered[0].id, saved.id);
+    assert_eq!(database.get_recordings(false).await.unwrap().len(), 2);
+}
@@ -0,0 +1,176 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, RecordingTrack};
+use meeting_summarizer_lib::services::capture_source::CaptureSource;
+use meeting_summarizer_lib::services::recording_spool::{primary_spool_path, recover_orphaned_recordings, SpoolWriter};
+use meeting_summarizer_lib::services::recording_tracks::{existing_tracks, track_path, validate_extra_tracks};
+use meeting_summarizer_lib::services::{demo_mode, RecordingProfile, RecordingService};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn input(name: &str) -> CaptureSource {
+    CaptureSource::InputDevice { name: name.to_string() }
+}
+
+#[test]
+fn test_track_paths() {
+    let output = PathBuf::from("/tmp/recording_temp_1.wav");
+    assert_eq!(track_path(&output, 1), PathBuf::from("/tmp/recording_temp_1_track01.wav"));
+    assert_eq!(track_path(&output, 12), PathBuf::from("/tmp/recording_temp_1_track12.wav"));
+
+    // 録音できなかったトラックは飛ばす
+    let temp_dir = TempDir::new().unwrap();
+    let output = temp_dir.path().join("recording.wav");
+    std::fs::write(track_path(&output, 2), b"").unwrap();
+    assert_eq!(existing_tracks(&output, 3), vec![(2, track_path(&output, 2))]);
+}
+
+#[test]
+fn test_input_device_source() {
+    let source: CaptureSource = serde_json::from_str(r#"{"type":"input_device","name":"USB Mic"}"#).unwrap();
+    assert_eq!(source, input("USB Mic"));
+    assert_eq!(source.label(), "USB Mic");
+    assert!(source.ensure_supported().is_ok());
+    assert!(source.process_command().unwrap().is_none());
+    assert!(input(" ").validate().is_err());
+    assert_eq!(CaptureSource::Microphone.label(), "Default Microphone");
+}
+
+#[test]
+fn test_extra_tracks_are_validated() {
+    let source = CaptureSource::Microphone;
+    assert!(validate_extra_tracks(&source, &[]).is_ok());
+    assert!(validate_extra_tracks(&source, &[input("Room Mic A"), input("Room Mic B")]).is_ok());
+
+    // 同じ入力を2回録音しない
+    assert!(validate_extra_tracks(&source, &[CaptureSource::Microphone]).is_err());
+    assert!(validate_extra_tracks(&source, &[input("Room Mic A"), input("Room Mic A")]).is_err());
+    assert!(validate_extra_tracks(&source, &[input("")]).is_err());
+    let too_many: Vec<CaptureSource> = (0..5).map(|index| input(&format!("Mic {}", index))).collect();
+    assert!(validate_extra_tracks(&source, &too_many).is_err());
+}
+
+#[tokio::test]
+async fn test_tracks_are_linked_and_deleted_with_recording() {
+    let temp_dir = TempDir::new().unwrap();
+    let database = Arc::new(Database::new(temp_dir.path().join("tracks.db")).unwrap());
+    let recordings_dir = temp_dir.path().join("recordings");
+    std::fs::create_dir_all(&recordings_dir).unwrap();
+    let recording_service = RecordingService::new(database.clone(), recordings_dir.clone()).unwrap();
+
+    let profile = RecordingProfile {
+        extra_tracks: vec![CaptureSource::Microphone],
+        ..RecordingProfile::default()
+    };
+    assert!(recording_service.apply_recording_profile(&profile).await.is_err());
+    let profile = RecordingProfile {
+        extra_tracks: vec![input("Room Mic")],
+        ..RecordingProfile::default()
+    };
+    recording_service.apply_recording_profile(&profile).await.unwrap();
+
+    let main_path = recordings_dir.join("recording.wav");
+    std::fs::write(&main_path, b"main").unwrap();
+    let recording = Recording::new("recording.wav".to_string(), main_path.to_string_lossy().to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let paths = [track_path(&main_path, 2), track_path(&main_path, 1)];
+    for (index, path) in [2, 1].into_iter().zip(&paths) {
+        std::fs::write(path, b"track").unwrap();
+        let track = RecordingTrack::new(
+            recording.id.clone(),
+            index,
+            format!("Room Mic {}", index),
+            path.to_string_lossy().to_string(),
+            60_000,
+        )
+        .with_file_size(5);
+        database.create_recording_track(&track).await.unwrap();
+    }
+
+    let tracks = recording_service.get_recording_tracks(&recording.id).await.unwrap();
+    assert_eq!(tracks.iter().map(|track| track.track_index).collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(tracks[0].device_name, "Room Mic 1");
+    assert_eq!(database.get_all_recording_track_paths().await.unwrap().len(), 2);
+
+    assert!(recording_service.delete_recording(&recording.id).await.unwrap());
+    assert!(paths.iter().all(|path| !path.exists()));
+    assert!(database.get_recording_tracks(&recording.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_interrupted_tracks_are_recovered_with_the_recording() {
+    let temp_dir = TempDir::new().unwrap();
+    let database = Database::in_memory().unwrap();
+    let output = temp_dir.path().join("recording_temp_1700000000.wav");
+    for path in [output.clone(), track_path(&output, 1), track_path(&output, 2)] {
+        let mut spool = SpoolWriter::create(&primary_spool_path(&path), 16_000, 1).unwrap();
+        spool.append(&vec![0.25; 16_000]).unwrap();
+        spool.finish().unwrap();
+    }
+
+    let recovered = recover_orphaned_recordings(&database, temp_dir.path(), None).await.unwrap();
+    assert_eq!(recovered.len(), 1);
+    let recovered_path = PathBuf::from(&recovered[0].file_path);
+    assert!(recovered_path.ends_with("recording_20231114_221320_recovered.wav"));
+
+    let tracks = database.get_recording_tracks(&recovered[0].id).await.unwrap();
+    let indexes: Vec<i32> = tracks.iter().map(|track| track.track_index).collect();
+    assert_eq!(indexes, vec![1, 2]);
+    assert_eq!(PathBuf::from(&tracks[0].file_path), track_path(&recovered_path, 1));
+    assert_eq!(tracks[1].duration_ms, 1_000);
+    assert!(!primary_spool_path(&track_path(&output, 2)).exists());
+}
+
+#[tokio::test]
+async fn test_failed_track_save_leaves_the_recording_for_recovery() {
+    demo_mode::set_enabled(true);
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("tracks.db");
+    let database = Arc::new(Database::new(&db_path).unwrap());
+    let recordings_dir = temp_dir.path().join("recordings");
+    let recording_service = RecordingService::new(database.clone(), recordings_dir.clone()).unwrap();
+    let profile = RecordingProfile {
+        extra_tracks: vec![input("Room Mic")],
+        ..RecordingProfile::default()
+    };
+    recording_service.apply_recording_profile(&profile).await.unwrap();
+
+    // トラックの行を作るところで失敗させる
+    let connection = rusqlite::Connection::open(&db_path).unwrap();
+    connection
+        .execute_batch("CREATE TRIGGER fail_tracks BEFORE INSERT ON recording_tracks BEGIN SELECT RAISE(ABORT, 'disk I/O error'); END;")
+        .unwrap();
+
+    recording_service.start_recording().await.unwrap();
+    let spool = std::fs::read_dir(&recordings_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.to_string_lossy().ends_with(".primary.part"))
+        .unwrap();
+    let temp_wav = recordings_dir.join(spool.file_name().unwrap().to_string_lossy().replace(".primary.part", ".wav"));
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 16_000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(track_path(&temp_wav, 1), spec).unwrap();
+    for _ in 0..16_000 {
+        writer.write_sample(0i16).unwrap();
+    }
+    writer.finalize().unwrap();
+
+    assert!(recording_service.stop_recording().await.is_err());
+    assert!(recording_service.current_session_id().await.is_none());
+    assert!(database.get_recordings(false).await.unwrap().is_empty());
+    assert!(track_path(&temp_wav, 1).exists());
+
+    // 本体とトラックはまとめて復元される
+    connection.execute_batch("DROP TRIGGER fail_tracks;").unwrap();
+    let recovered = recording_service.recover_orphaned_recordings().await.unwrap();
+    assert_eq!(recovered.len(), 1);
+    let tracks = database.get_recording_tracks(&recovered[0].id).await.unwrap();
+    assert_eq!(tracks.len(), 1);
+    assert_eq!(tracks[0].duration_ms, 1_000);
+}
@@ -0,0 +1,80 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{PiiKind, Recording, Summary, Transcription};
+use meeting_summarizer_lib::services::redaction::{self, find_emails, find_phone_numbers, RedactionOptions, Redactor};
+
+fn matched<'a>(text: &'a str, ranges: &[std::ops::Range<usize>]) -> Vec<&'a str> {
+    ranges.iter().map(|range| &text[range.clone()]).collect()
+}
+
+#[test]
+fn test_detects_emails_and_phone_numbers() {
+    let text = "連絡先は tanaka.taro@example.co.jp（携帯 090-1234-5678）、海外は +81 3 1234 5678 まで。";
+    assert_eq!(matched(text, &find_emails(text)), vec!["tanaka.taro@example.co.jp"]);
+    assert_eq!(matched(text, &find_phone_numbers(text)), vec!["090-1234-5678", "+81 3 1234 5678"]);
+
+    // 日付・時刻・金額・メンションは電話番号やメールとして扱わない
+    let text = "2024-05-10 10:30 に 1,000,000 円、@tanaka が確認。0120-123-456 は窓口。";
+    assert!(find_emails(text).is_empty());
+    assert_eq!(matched(text, &find_phone_numbers(text)), vec!["0120-123-456"]);
+}
+
+#[test]
+fn test_same_value_reuses_placeholder() {
+    let mut redactor = Redactor::new("rec-1", Vec::new());
+    redactor.add_person_names(&["田中".to_string(), "Al".to_string(), "李".to_string()]);
+
+    let options = RedactionOptions {
+        person_names: true,
+        ..RedactionOptions::default()
+    };
+    let redacted = redactor.redact(
+        "田中さん(a@example.com)とAlが話した。Also a@example.com, b@example.com。李さん",
+        &options,
+    );
+    assert_eq!(
+        redacted,
+        "[NAME_1]さん([EMAIL_1])と[NAME_2]が話した。Also [EMAIL_1], [EMAIL_2]。李さん"
+    );
+    assert_eq!(redactor.redacted_count(), 5);
+    assert_eq!(redaction::restore(&redacted, redactor.entries()).matches("a@example.com").count(), 2);
+}
+
+#[tokio::test]
+async fn test_redacted_export_keeps_originals_and_mapping() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("meeting.wav".to_string(), "/tmp/meeting.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let original_text = "見積もりは sato@example.com に、急ぎなら 03-1234-5678 へ。";
+    let transcription = Transcription::new(recording.id.clone(), original_text.to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+    let summary = Summary::new(transcription.id.clone(), "llama3".to_string()).with_content(
+        "見積もりを依頼した。".to_string(),
+        Vec::new(),
+        vec!["sato@example.com に見積書を送る".to_string()],
+    );
+    database.create_summary(&summary).await.unwrap();
+
+    let options = RedactionOptions::default();
+    let export = redaction::export_recording_redacted(&database, None, &recording.id, "text", &options)
+        .await
+        .unwrap();
+    assert!(export.content.contains("見積もりは [EMAIL_1] に、急ぎなら [PHONE_1] へ。"));
+    assert!(!export.content.contains("sato@example.com"));
+    assert_eq!(export.redacted_count, 2);
+
+    // 要約でも同じプレースホルダーを使い、元データは変更しない
+    let redacted_summary = redaction::redact_summary(&database, None, &summary.id, &options).await.unwrap();
+    assert_eq!(redacted_summary.action_items, vec!["[EMAIL_1] に見積書を送る".to_string()]);
+    let stored = database.get_summary(&summary.id).await.unwrap().unwrap();
+    assert_eq!(stored.action_items, summary.action_items);
+    let stored = database.get_transcription(&transcription.id).await.unwrap().unwrap();
+    assert_eq!(stored.text, original_text);
+
+    let mappings = database.get_redaction_mappings(&recording.id).await.unwrap();
+    assert_eq!(mappings.len(), 2);
+    assert!(mappings.iter().any(|m| m.kind == PiiKind::PhoneNumber && m.original == "03-1234-5678"));
+
+    assert_eq!(database.delete_redaction_mappings(&recording.id).await.unwrap(), 2);
+    assert!(database.get_redaction_mappings(&recording.id).await.unwrap().is_empty());
+}
@@ -0,0 +1,116 @@
+use meeting_summarizer_lib::services::live_captions::CaptionEvent;
+use meeting_summarizer_lib::services::rolling_summary::estimate_tokens;
+use meeting_summarizer_lib::services::RollingSummarizer;
+use std::sync::Mutex;
+
+fn caption(session_id: &str, segment_index: u64, text: &str, is_final: bool) -> CaptionEvent {
+    CaptionEvent {
+        session_id: session_id.to_string(),
+        caption_id: format!("{}-{}-0", session_id, segment_index),
+        segment_index,
+        revision: 1,
+        text: text.to_string(),
+        start_ms: segment_index as i64 * 1_000,
+        end_ms: segment_index as i64 * 1_000 + 900,
+        is_final,
+        removed: false,
+    }
+}
+
+#[tokio::test]
+async fn test_summary_covers_only_new_final_text() {
+    let summarizer = RollingSummarizer::new();
+    summarizer.record_caption(&caption("s1", 0, "予算の確認", true));
+    summarizer.record_caption(&caption("s1", 1, "未確定の発言", false));
+
+    let calls = Mutex::new(Vec::new());
+    let first = summarizer
+        .update_with(1_000, |previous, new_text| {
+            calls.lock().unwrap().push((previous, new_text));
+            async { Ok("要約1".to_string()) }
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(first.revision, 1);
+    assert_eq!(first.covered_until_ms, 900);
+
+    // 新しい確定発言が無ければ LLM は呼ばれない
+    let none = summarizer
+        .update_with(1_000, |_, _| async { panic!("should not be called") })
+        .await
+        .unwrap();
+    assert!(none.is_none());
+
+    summarizer.record_caption(&caption("s1", 1, "スケジュールの調整", true));
+    let second = summarizer
+        .update_with(1_000, |previous, new_text| {
+            calls.lock().unwrap().push((previous, new_text));
+            async { Ok("要約2".to_string()) }
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(second.revision, 2);
+    assert_eq!(summarizer.current().unwrap().summary, "要約2");
+
+    let calls = calls.into_inner().unwrap();
+    assert_eq!(calls[0], (None, "予算の確認".to_string()));
+    assert_eq!(calls[1], (Some("要約1".to_string()), "スケジュールの調整".to_string()));
+}
+
+#[tokio::test]
+async fn test_token_budget_defers_remaining_text() {
+    let summarizer = RollingSummarizer::new();
+    for index in 0..4 {
+        summarizer.record_caption(&caption("s1", index, "あいうえおかきくけこ", true));
+    }
+    assert_eq!(estimate_tokens("あいうえおかきくけこ"), 10);
+    assert_eq!(estimate_tokens("abcdefgh"), 2);
+
+    let first = summarizer
+        .update_with(25, |_, new_text| async move {
+            assert_eq!(new_text.lines().count(), 2);
+            Ok("前半".to_string())
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(first.has_pending);
+
+    // 前回の要約（2トークン）を差し引いた予算で残りを処理する
+    let second = summarizer
+        .update_with(25, |previous, new_text| async move {
+            assert_eq!(previous.as_deref(), Some("前半"));
+            assert_eq!(new_text.lines().count(), 2);
+            Ok("全体".to_string())
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!second.has_pending);
+    assert_eq!(second.covered_until_ms, 3_900);
+}
+
+#[tokio::test]
+async fn test_new_session_resets_state() {
+    let summarizer = RollingSummarizer::new();
+    summarizer.record_caption(&caption("s1", 0, "first meeting", true));
+    summarizer
+        .update_with(1_000, |_, _| async { Ok("old".to_string()) })
+        .await
+        .unwrap();
+
+    summarizer.record_caption(&caption("s2", 0, "second meeting", true));
+    assert!(summarizer.current().is_none());
+    let summary = summarizer
+        .update_with(1_000, |previous, _| async move {
+            assert!(previous.is_none());
+            Ok("new".to_string())
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(summary.session_id, "s2");
+    assert_eq!(summary.revision, 1);
+}
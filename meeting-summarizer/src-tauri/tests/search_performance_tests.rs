@@ -0,0 +1,166 @@
+use chrono::{Duration, Utc};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, RecordingQuery};
+use std::time::Instant;
+use tempfile::TempDir;
+
+const RECORDING_COUNT: usize = 10_000;
+/// 1回の検索あたりのレイテンシ予算（デバッグビルドでも余裕を持たせた値）
+const SEARCH_BUDGET_MS: u128 = 250;
+
+const CATEGORIES: [&str; 5] = ["standup", "planning", "review", "1on1", "all-hands"];
+const TAGS: [&str; 8] = ["frontend", "backend", "design", "sales", "hiring", "infra", "q3", "urgent"];
+
+async fn seed_database(database: &Database) {
+    let base = Utc::now();
+    for i in 0..RECORDING_COUNT {
+        let mut recording = Recording::new(
+            format!("meeting_{:05}.wav", i),
+            format!("/recordings/meeting_{:05}.wav", i),
+        );
+        recording.category = Some(CATEGORIES[i % CATEGORIES.len()].to_string());
+        recording.tags = vec![
+            TAGS[i % TAGS.len()].to_string(),
+            TAGS[(i / 3) % TAGS.len()].to_string(),
+        ];
+        recording.tags.dedup();
+        recording.duration = Some((i % 7200) as i64);
+        recording.created_at = base - Duration::minutes(i as i64);
+        database.create_recording(&recording).await.unwrap();
+    }
+}
+
+fn uses_index(plan: &[String], index: &str) -> bool {
+    plan.iter().any(|detail| detail.contains(index))
+}
+
+#[tokio::test]
+async fn test_search_query_plans_use_indexes() {
+    let database = Database::in_memory().unwrap();
+
+    let category_query = RecordingQuery {
+        category: Some("planning".to_string()),
+        ..Default::default()
+    };
+    let plan = database.explain_search_query(&category_query).await.unwrap();
+    assert!(uses_index(&plan, "idx_recordings_category_created_at"), "plan: {:?}", plan);
+    assert!(!plan.iter().any(|d| d.contains("TEMP B-TREE")), "sort should come from the index: {:?}", plan);
+
+    let duration_query = RecordingQuery {
+        min_duration: Some(600),
+        max_duration: Some(900),
+        sort_by: meeting_summarizer_lib::models::SortBy::Duration,
+        ..Default::default()
+    };
+    let plan = database.explain_search_query(&duration_query).await.unwrap();
+    assert!(uses_index(&plan, "idx_recordings_duration"), "plan: {:?}", plan);
+
+    let tag_query = RecordingQuery {
+        tags: vec!["design".to_string()],
+        ..Default::default()
+    };
+    let plan = database.explain_search_query(&tag_query).await.unwrap();
+    assert!(
+        plan.iter().any(|d| d.contains("recording_tags") && !d.starts_with("SCAN")),
+        "tag filter should be an index lookup: {:?}",
+        plan
+    );
+}
+
+#[tokio::test]
+async fn test_tag_filter_matches_exact_tags() {
+    let database = Database::in_memory().unwrap();
+
+    let mut first = Recording::new("a.wav".to_string(), "/recordings/a.wav".to_string());
+    first.tags = vec!["design".to_string(), "q3".to_string()];
+    let mut second = Recording::new("b.wav".to_string(), "/recordings/b.wav".to_string());
+    second.tags = vec!["design-review".to_string()];
+    database.create_recording(&first).await.unwrap();
+    database.create_recording(&second).await.unwrap();
+
+    let query = RecordingQuery {
+        tags: vec!["design".to_string()],
+        ..Default::default()
+    };
+    let results = database.search_recordings(&query).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, first.id);
+
+    // タグ更新が正規化テーブルに反映される
+    first.tags = vec!["q3".to_string()];
+    database.update_recording(&first).await.unwrap();
+    assert!(database.search_recordings(&query).await.unwrap().is_empty());
+    assert_eq!(
+        database.get_all_tags().await.unwrap(),
+        vec!["design-review".to_string(), "q3".to_string()]
+    );
+
+    database.delete_recording(&first.id).await.unwrap();
+    assert_eq!(database.get_all_tags().await.unwrap(), vec!["design-review".to_string()]);
+}
+
+#[tokio::test]
+async fn test_search_latency_budget_on_10k_recordings() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let database = Database::new(temp_dir.path().join("search.db")).unwrap();
+    seed_database(&database).await;
+    assert_eq!(database.get_recordings_count().await.unwrap(), RECORDING_COUNT as i64);
+
+    let queries = vec![
+        RecordingQuery {
+            category: Some("review".to_string()),
+            ..Default::default()
+        },
+        RecordingQuery {
+            tags: vec!["infra".to_string(), "urgent".to_string()],
+            ..Default::default()
+        },
+        RecordingQuery {
+            category: Some("standup".to_string()),
+            date_from: Some(Utc::now() - Duration::days(3)),
+            ..Default::default()
+        },
+        RecordingQuery {
+            min_duration: Some(3600),
+            max_duration: Some(3700),
+            ..Default::default()
+        },
+    ];
+
+    for query in &queries {
+        let started = Instant::now();
+        let results = database.search_recordings(query).await.unwrap();
+        let elapsed = started.elapsed().as_millis();
+
+        assert!(!results.is_empty(), "query returned nothing: {:?}", query);
+        assert!(
+            elapsed < SEARCH_BUDGET_MS,
+            "search took {}ms (budget {}ms): {:?}",
+            elapsed,
+            SEARCH_BUDGET_MS,
+            query
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_existing_json_tags_are_backfilled() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("legacy.db");
+
+    {
+        let database = Database::new(&db_path).unwrap();
+        let mut recording = Recording::new("legacy.wav".to_string(), "/recordings/legacy.wav".to_string());
+        recording.tags = vec!["legacy".to_string()];
+        database.create_recording(&recording).await.unwrap();
+    }
+
+    // 旧バージョンのDBを再現: 正規化テーブルを空にして再オープン
+    {
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        conn.execute("DELETE FROM recording_tags", []).unwrap();
+    }
+
+    let database = Database::new(&db_path).unwrap();
+    assert_eq!(database.get_all_tags().await.unwrap(), vec!["legacy".to_string()]);
+}
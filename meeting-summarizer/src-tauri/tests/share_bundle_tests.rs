@@ -0,0 +1,71 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, Summary, Transcription};
+use meeting_summarizer_lib::services::share_bundle;
+use std::io::Read;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_bundle_is_encrypted_with_password() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio_path = temp_dir.path().join("meeting.wav");
+    std::fs::write(&audio_path, b"RIFF-audio-bytes").unwrap();
+
+    let database = Database::in_memory().unwrap();
+    let mut recording = Recording::new("meeting.wav".to_string(), audio_path.to_string_lossy().to_string());
+    recording.title = Some("Board meeting".to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "confidential text".to_string(), "en".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+    let summary = Summary::new(transcription.id.clone(), "llama3".to_string()).with_content(
+        "Decided the merger.".to_string(),
+        Vec::new(),
+        vec!["Prepare the press release".to_string()],
+    );
+    database.create_summary(&summary).await.unwrap();
+
+    let output = temp_dir.path().join("bundle.zip");
+    let bundle = share_bundle::create_share_bundle(&database, &recording.id, &output, "correct horse", true)
+        .await
+        .unwrap();
+    assert_eq!(
+        bundle.files,
+        vec!["minutes.md", "transcript.md", "transcript.html", "audio/meeting.wav"]
+    );
+    assert!(bundle.size_bytes > 0);
+    assert!(!temp_dir.path().join("bundle.zip.part").exists());
+
+    // 平文が含まれていないこと
+    let raw = std::fs::read(&output).unwrap();
+    assert!(!raw.windows(b"merger".len()).any(|w| w == b"merger"));
+
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&output).unwrap()).unwrap();
+    assert!(archive.by_name_decrypt("minutes.md", b"wrong password").is_err());
+
+    let mut minutes = String::new();
+    archive
+        .by_name_decrypt("minutes.md", b"correct horse")
+        .unwrap()
+        .read_to_string(&mut minutes)
+        .unwrap();
+    assert!(minutes.contains("Decided the merger."));
+    assert!(minutes.contains("- [ ] Prepare the press release"));
+
+    let mut audio = Vec::new();
+    archive
+        .by_name_decrypt("audio/meeting.wav", b"correct horse")
+        .unwrap()
+        .read_to_end(&mut audio)
+        .unwrap();
+    assert_eq!(audio, b"RIFF-audio-bytes");
+}
+
+#[tokio::test]
+async fn test_short_password_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let database = Database::in_memory().unwrap();
+    let output = temp_dir.path().join("bundle.zip");
+
+    let result = share_bundle::create_share_bundle(&database, "missing", &output, "short", false).await;
+    assert!(result.is_err());
+    assert!(!output.exists());
+}
@@ -0,0 +1,42 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::services::sleep_inhibitor::inhibit_command;
+use meeting_summarizer_lib::services::{demo_mode, AppSettings, RecordingService, SleepPreventionSettings};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+#[test]
+fn test_sleep_prevention_is_enabled_by_default() {
+    assert!(SleepPreventionSettings::default().enabled);
+    assert!(AppSettings::default().sleep_prevention.enabled);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_linux_inhibitor_watches_the_app_process() {
+    let command = inhibit_command("Recording a meeting").unwrap();
+    assert_eq!(command.get_program(), "systemd-inhibit");
+    let args: Vec<String> = command.get_args().map(|arg| arg.to_string_lossy().to_string()).collect();
+    assert!(args.contains(&"--what=sleep:idle".to_string()));
+    assert!(args.contains(&"--why=Recording a meeting".to_string()));
+    assert!(args.contains(&format!("--pid={}", std::process::id())));
+}
+
+#[tokio::test]
+async fn test_recording_releases_sleep_prevention_on_stop() {
+    demo_mode::set_enabled(true);
+    let temp_dir = TempDir::new().unwrap();
+    let database = Arc::new(Database::new(temp_dir.path().join("sleep.db")).unwrap());
+    let recording_service = RecordingService::new(database, temp_dir.path().join("recordings")).unwrap();
+
+    // 止められない環境（セッションバスが無いなど）でも録音はできる
+    recording_service.start_recording().await.unwrap();
+    recording_service.stop_recording().await.unwrap();
+    assert!(!recording_service.sleep_inhibited());
+
+    // 無効にすると録音中も止めない
+    recording_service.set_sleep_prevention(SleepPreventionSettings { enabled: false }).await;
+    recording_service.start_recording().await.unwrap();
+    assert!(!recording_service.sleep_inhibited());
+    recording_service.stop_recording().await.unwrap();
+    assert_eq!(recording_service.sleep_prevention(), SleepPreventionSettings { enabled: false });
+}
@@ -0,0 +1,206 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, Transcription, TranscriptSegment};
+use meeting_summarizer_lib::services::speaker_analytics::{self, compute_talk_stats};
+
+fn segment(index: i32, start_ms: i64, end_ms: i64, speaker: Option<&str>) -> TranscriptSegment {
+    let mut segment = TranscriptSegment::new("tx-1".to_string(), index, start_ms, end_ms, "発言".to_string());
+    segment.speaker = speaker.map(str::to_string);
+    segment
+}
+
+#[test]
+fn test_turns_and_longest_monologue() {
+    let segments = vec![
+        segment(0, 0, 10_000, Some("田中")),
+        segment(1, 10_000, 25_000, Some("田中")),
+        segment(2, 25_000, 30_000, Some("佐藤")),
+        segment(3, 30_000, 35_000, Some("田中")),
+        // 話者不明のセグメントはターンを区切る
+        segment(4, 35_000, 36_000, None),
+        segment(5, 36_000, 40_000, Some("田中")),
+    ];
+
+    let stats = compute_talk_stats("rec-1", &segments);
+    assert_eq!(stats.len(), 2);
+
+    let tanaka = &stats[0];
+    assert_eq!(tanaka.speaker, "田中");
+    assert_eq!(tanaka.talk_time_ms, 34_000);
+    assert_eq!(tanaka.turn_count, 3);
+    assert_eq!(tanaka.longest_monologue_ms, 25_000);
+
+    let sato = &stats[1];
+    assert_eq!(sato.speaker, "佐藤");
+    assert_eq!(sato.turn_count, 1);
+    assert_eq!(sato.longest_monologue_ms, 5_000);
+
+    assert!((tanaka.talk_share + sato.talk_share - 1.0).abs() < 1e-9);
+    assert!((sato.talk_share - 5.0 / 39.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_no_speakers_yields_no_stats() {
+    let segments = vec![segment(0, 0, 1_000, None), segment(1, 1_000, 2_000, Some("  "))];
+    assert!(compute_talk_stats("rec-1", &segments).is_empty());
+}
+
+async fn create_meeting(database: &Database, name: &str, turns: &[(&str, i64, i64)]) -> String {
+    let recording = Recording::new(format!("{}.wav", name), format!("/tmp/{}.wav", name));
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+
+    let segments: Vec<TranscriptSegment> = turns
+        .iter()
+        .enumerate()
+        .map(|(index, (speaker, start, end))| {
+            let mut segment = TranscriptSegment::new(transcription.id.clone(), index as i32, *start, *end, "発言".to_string());
+            segment.speaker = Some(speaker.to_string());
+            segment
+        })
+        .collect();
+    database.create_transcript_segments(&segments).await.unwrap();
+    recording.id
+}
+
+#[tokio::test]
+async fn test_stats_are_stored_and_aggregated_per_person() {
+    let database = Database::in_memory().unwrap();
+    let first = create_meeting(&database, "weekly", &[("田中", 0, 30_000), ("佐藤", 30_000, 40_000)]).await;
+    let second = create_meeting(&database, "planning", &[("田中", 0, 10_000), ("鈴木", 10_000, 20_000), ("田中", 20_000, 30_000)]).await;
+
+    speaker_analytics::analyze_recording(&database, &first).await.unwrap();
+    speaker_analytics::analyze_recording(&database, &second).await.unwrap();
+    // 再計算しても重複しない
+    speaker_analytics::analyze_recording(&database, &second).await.unwrap();
+
+    let stored = database.get_speaker_stats(&second).await.unwrap();
+    assert_eq!(stored.len(), 2);
+    assert_eq!(stored[0].speaker, "田中");
+    assert_eq!(stored[0].turn_count, 2);
+
+    let people = database.get_person_talk_stats().await.unwrap();
+    assert_eq!(people.len(), 3);
+    let tanaka = &people[0];
+    assert_eq!(tanaka.speaker, "田中");
+    assert_eq!(tanaka.meeting_count, 2);
+    assert_eq!(tanaka.total_talk_time_ms, 50_000);
+    assert_eq!(tanaka.total_turns, 3);
+    assert_eq!(tanaka.longest_monologue_ms, 30_000);
+
+    database.delete_recording(&first).await.unwrap();
+    let people = database.get_person_talk_stats().await.unwrap();
+    assert!(people.iter().all(|person| person.speaker != "佐藤"));
+}
+
+#[tokio::test]
+async fn test_analyze_requires_speaker_segments() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("meeting.wav".to_string(), "/tmp/meeting.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+
+    assert!(speaker_analytics::analyze_recording(&database, &recording.id).await.is_err());
+    assert!(speaker_analytics::analyze_recording(&database, "missing").await.is_err());
+}
+
+fn text_segment(index: i32, start_ms: i64, end_ms: i64, speaker: &str) -> TranscriptSegment {
+    segment(index, start_ms, end_ms, Some(speaker))
+}
+
+#[test]
+fn test_overlaps_and_interruptions_per_pair() {
+    let segments = vec![
+        text_segment(0, 0, 10_000, "田中"),
+        // 相づち：田中の発言中に始まって先に終わる
+        text_segment(1, 3_000, 4_000, "佐藤"),
+        // 割り込み：田中が話し終える前に始まり、田中より後まで話す
+        text_segment(2, 8_000, 15_000, "佐藤"),
+        text_segment(3, 15_000, 20_000, "田中"),
+        text_segment(4, 19_000, 25_000, "鈴木"),
+    ];
+
+    let overlaps = speaker_analytics::compute_overlaps(&segments);
+    assert_eq!(overlaps.len(), 2);
+
+    let tanaka_sato = overlaps
+        .iter()
+        .find(|o| o.speaker == "田中" && o.other_speaker == "佐藤")
+        .unwrap();
+    assert_eq!(tanaka_sato.overlap_count, 2);
+    assert_eq!(tanaka_sato.interruption_count, 1);
+    assert_eq!(tanaka_sato.overlap_ms, 3_000);
+
+    let tanaka_suzuki = overlaps
+        .iter()
+        .find(|o| o.speaker == "田中" && o.other_speaker == "鈴木")
+        .unwrap();
+    assert_eq!((tanaka_suzuki.overlap_count, tanaka_suzuki.interruption_count), (1, 1));
+    assert_eq!(tanaka_suzuki.overlap_ms, 1_000);
+
+    // 佐藤の発言（8〜15秒）と後続の田中（15秒〜）は接しているだけで重なりではない
+    assert!(overlaps.iter().all(|o| o.speaker != "佐藤"));
+}
+
+fn default_fillers() -> Vec<String> {
+    speaker_analytics::DEFAULT_FILLER_WORDS.iter().map(|word| word.to_string()).collect()
+}
+
+#[test]
+fn test_count_filler_words() {
+    let counts = speaker_analytics::count_filler_words(
+        "えーと、えーっと、その件は、えー、Um, I like it. It's likely fine, like, you know.",
+        &default_fillers(),
+    );
+    assert_eq!(counts.get("えーと"), Some(&1));
+    assert_eq!(counts.get("えーっと"), Some(&1));
+    // 「えーと」の一部としては数えない
+    assert_eq!(counts.get("えー"), Some(&1));
+    assert_eq!(counts.get("um"), Some(&1));
+    // "likely" には一致しない
+    assert_eq!(counts.get("like"), Some(&2));
+    assert_eq!(counts.get("you know"), Some(&1));
+}
+
+#[tokio::test]
+async fn test_filler_word_stats_per_speaker() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("practice.wav".to_string(), "/tmp/practice.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+
+    let mut first = TranscriptSegment::new(transcription.id.clone(), 0, 0, 60_000, "えー、あのー、まあ、えーと".to_string());
+    first.speaker = Some("田中".to_string());
+    let mut second = TranscriptSegment::new(transcription.id.clone(), 1, 60_000, 90_000, "はい".to_string());
+    second.speaker = Some("佐藤".to_string());
+    database.create_transcript_segments(&[first, second]).await.unwrap();
+
+    let stats = speaker_analytics::filler_word_stats(&database, &recording.id, None).await.unwrap();
+    let tanaka = stats.iter().find(|s| s.speaker.as_deref() == Some("田中")).unwrap();
+    assert_eq!(tanaka.total, 4);
+    assert_eq!(tanaka.per_minute, Some(4.0));
+    let sato = stats.iter().find(|s| s.speaker.as_deref() == Some("佐藤")).unwrap();
+    assert_eq!(sato.total, 0);
+
+    let custom = speaker_analytics::filler_word_stats(&database, &recording.id, Some(vec!["まあ".to_string()]))
+        .await
+        .unwrap();
+    assert_eq!(custom[0].total, 1);
+}
+
+#[tokio::test]
+async fn test_filler_word_stats_without_segments() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("memo.wav".to_string(), "/tmp/memo.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "えーと、um, 以上です".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+
+    let stats = speaker_analytics::filler_word_stats(&database, &recording.id, None).await.unwrap();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].speaker, None);
+    assert_eq!(stats[0].total, 2);
+    assert_eq!(stats[0].per_minute, None);
+}
@@ -0,0 +1,88 @@
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, Transcription, TranscriptSegment};
+use meeting_summarizer_lib::services::speaker_tracks::{self, speaker_ranges};
+use tempfile::TempDir;
+
+fn segment(index: i32, start_ms: i64, end_ms: i64, speaker: Option<&str>) -> TranscriptSegment {
+    let mut segment = TranscriptSegment::new("tx-1".to_string(), index, start_ms, end_ms, "発言".to_string());
+    segment.speaker = speaker.map(str::to_string);
+    segment
+}
+
+#[test]
+fn test_speaker_ranges_are_merged() {
+    let segments = vec![
+        segment(0, 0, 1_000, Some("田中")),
+        segment(1, 1_000, 2_000, Some("田中")),
+        segment(2, 1_500, 3_000, Some("佐藤")),
+        segment(3, 2_500, 4_000, Some("田中")),
+        segment(4, 4_000, 5_000, None),
+    ];
+
+    let ranges = speaker_ranges(&segments);
+    assert_eq!(ranges.len(), 2);
+    assert_eq!(ranges["田中"], vec![(0, 2_000), (2_500, 4_000)]);
+    assert_eq!(ranges["佐藤"], vec![(1_500, 3_000)]);
+}
+
+#[tokio::test]
+async fn test_export_speaker_tracks_mutes_other_speakers() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio_path = temp_dir.path().join("meeting.wav");
+    let spec = WavSpec {
+        channels: 2,
+        sample_rate: 8_000,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(&audio_path, spec).unwrap();
+    for _ in 0..8_000 * 3 {
+        writer.write_sample(10_000i16).unwrap();
+        writer.write_sample(10_000i16).unwrap();
+    }
+    writer.finalize().unwrap();
+
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("meeting.wav".to_string(), audio_path.to_string_lossy().to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+    let segments: Vec<TranscriptSegment> = [(0, 1_000, "Alice"), (1_000, 3_000, "Bob")]
+        .iter()
+        .enumerate()
+        .map(|(index, (start, end, speaker))| {
+            let mut segment = TranscriptSegment::new(transcription.id.clone(), index as i32, *start, *end, "発言".to_string());
+            segment.speaker = Some(speaker.to_string());
+            segment
+        })
+        .collect();
+    database.create_transcript_segments(&segments).await.unwrap();
+
+    let output_dir = temp_dir.path().join("tracks");
+    let tracks = speaker_tracks::export_speaker_tracks(&database, &recording.id, &output_dir)
+        .await
+        .unwrap();
+    assert_eq!(tracks.len(), 2);
+
+    let alice = tracks.iter().find(|track| track.speaker == "Alice").unwrap();
+    assert_eq!(alice.talk_time_ms, 1_000);
+    let samples: Vec<i16> = WavReader::open(&alice.path)
+        .unwrap()
+        .samples::<i16>()
+        .map(Result::unwrap)
+        .collect();
+    // 元と同じ長さ（モノラル）で、1秒目までだけ音がある
+    assert_eq!(samples.len(), 8_000 * 3);
+    assert!(samples[..8_000].iter().all(|s| (*s - 10_000).abs() <= 1));
+    assert!(samples[8_000..].iter().all(|s| *s == 0));
+
+    let bob = tracks.iter().find(|track| track.speaker == "Bob").unwrap();
+    let samples: Vec<i16> = WavReader::open(&bob.path)
+        .unwrap()
+        .samples::<i16>()
+        .map(Result::unwrap)
+        .collect();
+    assert!(samples[..8_000].iter().all(|s| *s == 0));
+    assert!(samples[8_000..].iter().all(|s| *s != 0));
+}
@@ -0,0 +1,86 @@
+use chrono::{Duration, Utc};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::Recording;
+use meeting_summarizer_lib::services::storage_quota::{
+    compute_usage, crossed_threshold, directory_size, reclaim_candidates, StorageQuotaMonitor,
+};
+use meeting_summarizer_lib::services::StorageSettings;
+use tempfile::TempDir;
+
+fn quota_settings(quota_bytes: u64) -> StorageSettings {
+    StorageSettings {
+        quota_bytes: Some(quota_bytes),
+        ..StorageSettings::default()
+    }
+}
+
+#[test]
+fn test_crossed_threshold_picks_highest() {
+    let thresholds = [0.8, 0.95];
+    assert_eq!(crossed_threshold(0.5, &thresholds), None);
+    assert_eq!(crossed_threshold(0.85, &thresholds), Some(0.8));
+    assert_eq!(crossed_threshold(1.2, &thresholds), Some(0.95));
+}
+
+#[test]
+fn test_usage_without_quota_never_warns() {
+    let usage = compute_usage(10_000, &StorageSettings::default());
+    assert_eq!(usage.usage_ratio, None);
+    assert_eq!(usage.crossed_threshold, None);
+}
+
+#[test]
+fn test_monitor_warns_only_when_crossing_higher_threshold() {
+    let monitor = StorageQuotaMonitor::new();
+    let settings = quota_settings(1_000);
+
+    assert!(!monitor.should_warn(&compute_usage(500, &settings)));
+    assert!(monitor.should_warn(&compute_usage(850, &settings)));
+    assert!(!monitor.should_warn(&compute_usage(900, &settings)));
+    assert!(monitor.should_warn(&compute_usage(960, &settings)));
+    assert!(!monitor.should_warn(&compute_usage(990, &settings)));
+
+    // 容量を空けた後に再び超えたら再度警告する
+    assert!(!monitor.should_warn(&compute_usage(100, &settings)));
+    assert!(monitor.should_warn(&compute_usage(800, &settings)));
+}
+
+#[test]
+fn test_directory_size_includes_subdirectories() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("a.wav"), vec![0u8; 100]).unwrap();
+    std::fs::create_dir_all(temp_dir.path().join("snippets")).unwrap();
+    std::fs::write(temp_dir.path().join("snippets").join("b.wav"), vec![0u8; 50]).unwrap();
+
+    assert_eq!(directory_size(temp_dir.path()).unwrap(), 150);
+    assert_eq!(directory_size(&temp_dir.path().join("missing")).unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_reclaim_candidates_are_old_recordings_largest_first() {
+    let temp_dir = TempDir::new().unwrap();
+    let database = Database::in_memory().unwrap();
+
+    for (name, size) in [("small.wav", 100usize), ("large.wav", 1_000), ("medium.wav", 500)] {
+        let path = temp_dir.path().join(name);
+        std::fs::write(&path, vec![0u8; size]).unwrap();
+        let recording = Recording::new(name.to_string(), path.to_string_lossy().to_string());
+        database.create_recording(&recording).await.unwrap();
+    }
+    // ファイルが無い録音は候補にしない
+    let missing = Recording::new(
+        "missing.wav".to_string(),
+        temp_dir.path().join("missing.wav").to_string_lossy().to_string(),
+    );
+    database.create_recording(&missing).await.unwrap();
+
+    // 作成直後の録音は候補にならない
+    assert!(reclaim_candidates(&database, 30, Utc::now()).await.unwrap().is_empty());
+
+    let candidates = reclaim_candidates(&database, 30, Utc::now() + Duration::days(31))
+        .await
+        .unwrap();
+    let names: Vec<&str> = candidates.iter().map(|c| c.filename.as_str()).collect();
+    assert_eq!(names, vec!["large.wav", "medium.wav", "small.wav"]);
+    assert_eq!(candidates[0].file_size, 1_000);
+}
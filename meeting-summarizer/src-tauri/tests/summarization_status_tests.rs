@@ -0,0 +1,59 @@
+use meeting_summarizer_lib::services::SummarizationStatusRegistry;
+
+#[test]
+fn test_status_lifecycle_is_tracked_per_transcription() {
+    let registry = SummarizationStatusRegistry::new();
+
+    let started = registry.start("tx-1", "initializing".to_string());
+    registry.start("tx-2", "initializing".to_string());
+    assert_eq!(registry.active().len(), 2);
+
+    registry.update("tx-1", |status| {
+        status.stage = "processing".to_string();
+        status.progress = 0.3;
+    });
+    registry.update("tx-1", |status| {
+        status.stage = "saving".to_string();
+        status.progress = 0.8;
+        status.summary_id = Some("sum-1".to_string());
+        status.partial_text_length = 120;
+    });
+
+    let status = registry.get("tx-1").unwrap();
+    assert_eq!(status.stage, "saving");
+    assert_eq!(status.summary_id.as_deref(), Some("sum-1"));
+    assert_eq!(status.partial_text_length, 120);
+    assert_eq!(status.started_at, started.started_at);
+    assert!(status.updated_at >= started.updated_at);
+
+    registry.update("tx-1", |status| {
+        status.stage = "completed".to_string();
+        status.progress = 1.0;
+        status.completed = true;
+    });
+
+    // 完了済みは取得できるが、進行中一覧には含まれない
+    assert!(registry.get("tx-1").unwrap().is_finished());
+    let active = registry.active();
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].transcription_id, "tx-2");
+
+    assert!(registry.get("unknown").is_none());
+}
+
+#[test]
+fn test_restart_replaces_previous_status() {
+    let registry = SummarizationStatusRegistry::new();
+
+    registry.start("tx-1", "first".to_string());
+    registry.update("tx-1", |status| {
+        status.error = Some("LLM unavailable".to_string());
+    });
+    assert!(registry.active().is_empty());
+
+    registry.start("tx-1", "retry".to_string());
+    let status = registry.get("tx-1").unwrap();
+    assert_eq!(status.message, "retry");
+    assert!(status.error.is_none());
+    assert_eq!(registry.active().len(), 1);
+}
@@ -0,0 +1,104 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, ReviewStatus, Summary, Transcription};
+use meeting_summarizer_lib::services::summary_review;
+use tempfile::TempDir;
+
+async fn create_summary(database: &Database) -> Summary {
+    let recording = Recording::new("review.wav".to_string(), "/tmp/review.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+    let transcription = Transcription::new(recording.id.clone(), "本文".to_string(), "ja".to_string());
+    database.create_transcription(&transcription).await.unwrap();
+
+    let summary = Summary::new(transcription.id.clone(), "llama3".to_string()).with_content(
+        "予算案を承認した".to_string(),
+        vec![],
+        vec![],
+    );
+    database.create_summary(&summary).await.unwrap();
+    summary
+}
+
+#[tokio::test]
+async fn test_summaries_start_as_draft() {
+    let database = Database::in_memory().unwrap();
+    let summary = create_summary(&database).await;
+
+    let stored = database.get_summary(&summary.id).await.unwrap().unwrap();
+    assert_eq!(stored.review_status, ReviewStatus::Draft);
+    assert!(!stored.locked);
+    assert!(stored.approved_at.is_none());
+}
+
+#[tokio::test]
+async fn test_locked_summary_blocks_regeneration_and_edits() {
+    let database = Database::in_memory().unwrap();
+    let summary = create_summary(&database).await;
+
+    let approved = summary_review::approve_summary(&database, &summary.id, Some("田中".to_string()), true)
+        .await
+        .unwrap();
+    assert_eq!(approved.review_status, ReviewStatus::Approved);
+    assert_eq!(approved.approved_by.as_deref(), Some("田中"));
+    assert!(approved.approved_at.is_some());
+    assert!(approved.locked);
+
+    assert!(summary_review::ensure_can_regenerate(&database, &summary.transcription_id).await.is_err());
+    let regenerated = Summary::new(summary.transcription_id.clone(), "llama3".to_string());
+    assert!(database.create_summary(&regenerated).await.is_err());
+    assert!(database.update_summary(&approved).await.is_err());
+    assert!(database.delete_summary(&summary.id).await.is_err());
+
+    // 固定を解除すれば再生成できる
+    summary_review::set_summary_locked(&database, &summary.id, false).await.unwrap();
+    summary_review::ensure_can_regenerate(&database, &summary.transcription_id).await.unwrap();
+    database.create_summary(&regenerated).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_only_approved_summaries_can_be_locked() {
+    let database = Database::in_memory().unwrap();
+    let summary = create_summary(&database).await;
+
+    assert!(summary_review::set_summary_locked(&database, &summary.id, true).await.is_err());
+
+    summary_review::approve_summary(&database, &summary.id, None, true).await.unwrap();
+    let reopened = summary_review::reopen_summary(&database, &summary.id).await.unwrap();
+    assert_eq!(reopened.review_status, ReviewStatus::Draft);
+    assert!(reopened.approved_by.is_none());
+    assert!(!reopened.locked);
+}
+
+#[tokio::test]
+async fn test_existing_database_gains_review_columns() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("old.db");
+    {
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        conn.execute(
+            "CREATE TABLE summaries (
+                id TEXT PRIMARY KEY,
+                transcription_id TEXT NOT NULL,
+                summary_text TEXT NOT NULL,
+                key_points TEXT,
+                action_items TEXT,
+                model_used TEXT NOT NULL,
+                processing_time_ms INTEGER,
+                status TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO summaries VALUES ('s-1', 't-1', '旧要約', '[]', '[]', 'llama3', NULL, 'completed', '2024-01-01T00:00:00+00:00', '2024-01-01T00:00:00+00:00')",
+            [],
+        )
+        .unwrap();
+    }
+
+    let database = Database::new(&db_path).unwrap();
+    let summary = database.get_summary("s-1").await.unwrap().unwrap();
+    assert_eq!(summary.review_status, ReviewStatus::Draft);
+    assert!(!summary.locked);
+}
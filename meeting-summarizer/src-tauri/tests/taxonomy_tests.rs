@@ -0,0 +1,89 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, RecordingQuery, TaxonomyAssignment};
+use meeting_summarizer_lib::services::taxonomy::{ancestors, build_tree, is_within, normalize_path};
+use meeting_summarizer_lib::services::transcription_language::resolve_language;
+
+fn assignment(recording_id: &str, path: &str, duration: i64) -> TaxonomyAssignment {
+    TaxonomyAssignment {
+        recording_id: recording_id.to_string(),
+        path: path.to_string(),
+        duration,
+    }
+}
+
+#[test]
+fn test_paths_are_normalized() {
+    assert_eq!(normalize_path(" Clients / ACME// Project-X "), "Clients/ACME/Project-X");
+    assert_eq!(ancestors("Clients/ACME/Project-X"), vec!["Clients", "Clients/ACME", "Clients/ACME/Project-X"]);
+    assert!(is_within("Clients/ACME/Project-X", "Clients/ACME"));
+    assert!(!is_within("Clients/ACME2", "Clients/ACME"));
+}
+
+#[test]
+fn test_tree_rolls_up_distinct_recordings() {
+    let tree = build_tree(&[
+        assignment("r1", "Clients/ACME/Project-X", 600),
+        assignment("r1", "Clients/ACME/Project-Y", 600),
+        assignment("r2", "Clients/ACME", 300),
+        assignment("r3", "Internal", 100),
+    ]);
+
+    assert_eq!(tree.iter().map(|node| node.path.as_str()).collect::<Vec<_>>(), vec!["Clients", "Internal"]);
+    let clients = &tree[0];
+    assert_eq!(clients.count, 0);
+    assert_eq!(clients.total_count, 2);
+    assert_eq!(clients.total_duration, 900);
+
+    let acme = &clients.children[0];
+    assert_eq!(acme.name, "ACME");
+    assert_eq!(acme.count, 1);
+    assert_eq!(acme.children.len(), 2);
+    assert_eq!(acme.children[0].total_count, 1);
+}
+
+#[tokio::test]
+async fn test_search_by_category_and_tag_path() {
+    let database = Database::in_memory().unwrap();
+    for (name, category, tag) in [
+        ("x", "Clients/ACME/Project-X", "topic/budget/2025"),
+        ("acme2", "Clients/ACME2", "topic/budget"),
+        ("internal", "Internal", "topic/hiring"),
+    ] {
+        let mut recording = Recording::new(format!("{}.wav", name), format!("/tmp/taxonomy_{}.wav", name));
+        recording.category = Some(category.to_string());
+        recording.tags = vec![tag.to_string()];
+        database.create_recording(&recording).await.unwrap();
+    }
+
+    let search = |category_path: Option<&str>, tag_paths: Vec<&str>| RecordingQuery {
+        category_path: category_path.map(str::to_string),
+        tag_paths: tag_paths.into_iter().map(str::to_string).collect(),
+        ..Default::default()
+    };
+
+    let acme = database.search_recordings(&search(Some("Clients/ACME"), vec![])).await.unwrap();
+    assert_eq!(acme.iter().map(|r| r.filename.as_str()).collect::<Vec<_>>(), vec!["x.wav"]);
+
+    let clients = database.search_recordings(&search(Some("Clients"), vec![])).await.unwrap();
+    assert_eq!(clients.len(), 2);
+
+    let budget = database.search_recordings(&search(None, vec!["topic/budget"])).await.unwrap();
+    assert_eq!(budget.len(), 2);
+
+    // LIKE のワイルドカードは文字どおりに扱う
+    assert!(database.search_recordings(&search(Some("Clients/AC_E"), vec![])).await.unwrap().is_empty());
+
+    let tree = build_tree(&database.get_tag_assignments().await.unwrap());
+    assert_eq!(tree[0].path, "topic");
+    assert_eq!(tree[0].total_count, 3);
+}
+
+#[tokio::test]
+async fn test_language_is_inherited_from_parent_category() {
+    let database = Database::in_memory().unwrap();
+    database.set_category_language("Clients/ACME", "en").await.unwrap();
+    let mut recording = Recording::new("inherit.wav".to_string(), "/tmp/taxonomy_inherit.wav".to_string());
+    recording.category = Some("Clients/ACME/Project-X".to_string());
+
+    assert_eq!(resolve_language(&database, &recording, None).await.unwrap().as_deref(), Some("en"));
+}
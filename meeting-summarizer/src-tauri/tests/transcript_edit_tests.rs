@@ -0,0 +1,100 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, TranscriptEditSource, TranscriptSegment, Transcription};
+use meeting_summarizer_lib::services::{confidence_regions, demo_mode, transcript_edits};
+
+fn segments(transcription_id: &str, texts: &[&str]) -> Vec<TranscriptSegment> {
+    texts
+        .iter()
+        .enumerate()
+        .map(|(index, text)| {
+            let start = index as i64 * 2_000;
+            TranscriptSegment::new(transcription_id.to_string(), index as i32, start, start + 2_000, text.to_string())
+        })
+        .collect()
+}
+
+#[test]
+fn test_splice_replaces_occurrence_after_previous_segment() {
+    let segments = segments("tx", &["はい", "議題です", "はい"]);
+    // 本文がセグメントの連結と異なる場合、同じ文言が複数あっても該当位置を置き換える
+    let full = "はい。議題です。はい。";
+    assert_eq!(
+        transcript_edits::splice_text(full, &segments, &segments[2].id, "以上です"),
+        "はい。議題です。以上です。"
+    );
+    assert_eq!(
+        transcript_edits::splice_text(full, &segments, &segments[0].id, "では"),
+        "では。議題です。はい。"
+    );
+
+    // 一致箇所が無ければセグメントから組み立て直す
+    assert_eq!(
+        transcript_edits::splice_text("別の本文", &segments, &segments[1].id, "報告です"),
+        "はい\n報告です\nはい"
+    );
+}
+
+#[tokio::test]
+async fn test_manual_edit_updates_segment_text_and_history() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("meeting.wav".to_string(), "/tmp/meeting.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    let transcription = Transcription::new(
+        recording.id.clone(),
+        "Alice: 開始します\nBob: くばねてすの件".to_string(),
+        "ja".to_string(),
+    );
+    database.create_transcription(&transcription).await.unwrap();
+    let mut stored = segments(&transcription.id, &["開始します", "くばねてすの件"]);
+    stored[0].speaker = Some("Alice".to_string());
+    stored[1].speaker = Some("Bob".to_string());
+    stored[1].confidence = Some(0.3);
+    database.create_transcript_segments(&stored).await.unwrap();
+
+    let regions = confidence_regions::get_low_confidence_regions(&database, &transcription.id, None).await.unwrap();
+    assert_eq!(regions.len(), 1);
+
+    let edit = transcript_edits::edit_segment_text(&database, &stored[1].id, " Kubernetes の件 ").await.unwrap();
+    assert_eq!(edit.previous_text, "くばねてすの件");
+    assert_eq!(edit.new_text, "Kubernetes の件");
+    assert_eq!(edit.source, TranscriptEditSource::Manual);
+
+    let updated = database.get_transcription(&transcription.id).await.unwrap().unwrap();
+    assert_eq!(updated.text, "Alice: 開始します\nBob: Kubernetes の件");
+    let segment = database.get_segment(&stored[1].id).await.unwrap().unwrap();
+    assert_eq!(segment.text, "Kubernetes の件");
+    // 修正済みのセグメントは要確認箇所から外れる
+    assert!(confidence_regions::get_low_confidence_regions(&database, &transcription.id, None).await.unwrap().is_empty());
+
+    let history = database.get_transcript_edits(&transcription.id).await.unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].segment_id, stored[1].id);
+
+    assert!(transcript_edits::edit_segment_text(&database, &stored[1].id, "  ").await.is_err());
+    assert!(transcript_edits::edit_segment_text(&database, "missing", "text").await.is_err());
+}
+
+#[tokio::test]
+async fn test_edit_segment_saved_with_whisper_transcription() {
+    let database = Database::in_memory().unwrap();
+    let recording = Recording::new("meeting.wav".to_string(), "/tmp/meeting.wav".to_string());
+    database.create_recording(&recording).await.unwrap();
+
+    // Whisper と同じく、本文はセグメントを区切らずに連結したもので、セグメントは書き起こしと一緒に保存される
+    let transcription = demo_mode::demo_transcription(recording.id.clone(), None);
+    database.create_transcription(&transcription).await.unwrap();
+    let stored = database.get_segments_by_transcription(&transcription.id).await.unwrap();
+    assert_eq!(stored.len(), transcription.segments.len());
+
+    // 「山田さん」で始まる文は2つあるが、指定したセグメントの方だけを置き換える
+    let target = stored.iter().find(|segment| segment.text.starts_with("山田さんは")).unwrap();
+    transcript_edits::edit_segment_text(&database, &target.id, "山田さんはテスト結果を木曜日に報告してください。")
+        .await
+        .unwrap();
+
+    let updated = database.get_transcription(&transcription.id).await.unwrap().unwrap();
+    assert!(updated.text.contains("確定とします。山田さんはテスト結果を木曜日に報告してください。佐藤さんは"));
+    assert!(updated.text.starts_with("それでは定例会議を始めます。"));
+    assert!(updated.text.contains("山田さん、開発の状況を教えてください。"));
+}
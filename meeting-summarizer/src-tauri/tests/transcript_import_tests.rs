@@ -0,0 +1,100 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::services::transcript_import::{detect_format, import_transcript_file, parse_transcript};
+use meeting_summarizer_lib::services::{RecordingService, TranscriptFormat};
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+const WEBVTT: &str = "WEBVTT
+
+1
+00:00:01.000 --> 00:00:04.500
+<v Alice>Good morning everyone</v>
+
+2
+00:00:05.000 --> 00:00:07.000
+Bob: Let's get started
+";
+
+const WHISPER_CPP_JSON: &str = r#"{
+    "result": {"language": "ja"},
+    "transcription": [
+        {"timestamps": {"from": "00:00:00,000", "to": "00:00:03,000"}, "offsets": {"from": 0, "to": 3000}, "text": " こんにちは"},
+        {"timestamps": {"from": "00:00:03,000", "to": "00:00:06,000"}, "offsets": {"from": 3000, "to": 6000}, "text": " 会議を始めます"}
+    ]
+}"#;
+
+const OTTER_TEXT: &str = "Speaker 1  0:03
+Thanks for joining.
+
+Speaker 2  0:10
+Happy to be here.
+";
+
+#[test]
+fn test_detect_and_parse_formats() {
+    assert_eq!(detect_format(Path::new("a.vtt"), WEBVTT), Some(TranscriptFormat::WebVtt));
+    assert_eq!(detect_format(Path::new("a.json"), WHISPER_CPP_JSON), Some(TranscriptFormat::WhisperJson));
+    assert_eq!(detect_format(Path::new("a.txt"), OTTER_TEXT), Some(TranscriptFormat::Otter));
+
+    let vtt = parse_transcript(WEBVTT, TranscriptFormat::WebVtt).unwrap();
+    assert_eq!(vtt.segments.len(), 2);
+    assert_eq!(vtt.segments[0].speaker.as_deref(), Some("Alice"));
+    assert_eq!(vtt.segments[0].end_ms, 4500);
+    assert_eq!(vtt.segments[1].speaker.as_deref(), Some("Bob"));
+
+    let whisper = parse_transcript(WHISPER_CPP_JSON, TranscriptFormat::WhisperJson).unwrap();
+    assert_eq!(whisper.language.as_deref(), Some("ja"));
+    assert_eq!(whisper.segments[1].start_ms, 3000);
+
+    let otter = parse_transcript(OTTER_TEXT, TranscriptFormat::Otter).unwrap();
+    assert_eq!(otter.segments.len(), 2);
+    assert_eq!(otter.segments[0].start_ms, 3000);
+    assert_eq!(otter.segments[0].end_ms, 10000);
+}
+
+#[tokio::test]
+async fn test_import_transcript_creates_segments() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let file_path = temp_dir.path().join("archive.vtt");
+    std::fs::write(&file_path, WEBVTT).unwrap();
+
+    let database = Database::new(temp_dir.path().join("transcript_import.db")).unwrap();
+    let imported = import_transcript_file(&database, &temp_dir.path().join("recordings"), &file_path, None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(imported.format, TranscriptFormat::WebVtt);
+    assert_eq!(imported.recording.duration, Some(7));
+    assert!(imported.transcription.text.contains("Alice: Good morning everyone"));
+
+    let segments = database.get_segments_by_transcription(&imported.transcription.id).await.unwrap();
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[1].segment_index, 1);
+
+    let transcriptions = database.get_transcriptions_by_recording(&imported.recording.id).await.unwrap();
+    assert_eq!(transcriptions.len(), 1);
+}
+
+#[tokio::test]
+async fn test_imported_file_is_not_owned_by_the_recording() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let recordings_dir = temp_dir.path().join("recordings");
+    let file_path = temp_dir.path().join("archive.vtt");
+    std::fs::write(&file_path, WEBVTT).unwrap();
+
+    let database = Arc::new(Database::new(temp_dir.path().join("reimport.db")).unwrap());
+    let first = import_transcript_file(&database, &recordings_dir, &file_path, None, None).await.unwrap();
+    assert_ne!(Path::new(&first.recording.file_path), file_path.as_path());
+    assert!(Path::new(&first.recording.file_path).starts_with(&recordings_dir));
+
+    // 同じファイルを何度でも取り込める
+    let second = import_transcript_file(&database, &recordings_dir, &file_path, None, None).await.unwrap();
+    assert_ne!(first.recording.id, second.recording.id);
+
+    // 録音を削除しても元のファイルは残る
+    let recording_service = RecordingService::new(database.clone(), recordings_dir).unwrap();
+    assert!(recording_service.delete_recording(&first.recording.id).await.unwrap());
+    assert!(file_path.exists());
+    assert!(database.get_recording(&first.recording.id).await.unwrap().is_none());
+}
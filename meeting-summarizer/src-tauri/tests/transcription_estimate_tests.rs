@@ -0,0 +1,82 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, Transcription, TranscriptionStatus, TranscriptionThroughput};
+use meeting_summarizer_lib::services::transcription_estimate::{estimate, estimate_for_recording};
+use meeting_summarizer_lib::services::whisper_acceleration::WhisperDevice;
+
+#[test]
+fn test_estimate_uses_defaults_without_history() {
+    let small = estimate("small", 600, None, Some(4096));
+    let medium = estimate("medium", 600, None, Some(4096));
+
+    assert_eq!(small.history_runs, 0);
+    assert!(small.estimated_seconds < medium.estimated_seconds);
+    assert_eq!(small.fits_in_memory, Some(true));
+    assert_eq!(medium.fits_in_memory, Some(false));
+    assert_eq!(estimate("small", 600, None, None).fits_in_memory, None);
+}
+
+#[test]
+fn test_estimate_prefers_history() {
+    let history = TranscriptionThroughput {
+        model: "small".to_string(),
+        runs: 2,
+        audio_seconds: 1000,
+        processing_ms: 500_000,
+    };
+    let result = estimate("small", 600, Some(&history), None);
+
+    assert_eq!(result.history_runs, 2);
+    assert!((result.realtime_factor - 0.5).abs() < f64::EPSILON);
+    assert_eq!(result.estimated_seconds, 300);
+}
+
+#[test]
+fn test_gpu_speedup_applies_to_default_estimates() {
+    let cpu = estimate("medium", 600, None, None);
+    let gpu = estimate("medium", 600, None, None).accelerated(WhisperDevice::Cuda, 4.0);
+    assert_eq!(cpu.device, WhisperDevice::Cpu);
+    assert_eq!(gpu.device, WhisperDevice::Cuda);
+    assert_eq!(gpu.estimated_seconds, (cpu.estimated_seconds as f64 / 4.0).ceil() as i64);
+
+    // 実績はそのデバイスでの実測なので割り引かない
+    let history = TranscriptionThroughput {
+        model: "medium".to_string(),
+        runs: 1,
+        audio_seconds: 600,
+        processing_ms: 120_000,
+    };
+    let measured = estimate("medium", 600, Some(&history), None).accelerated(WhisperDevice::Cuda, 4.0);
+    assert_eq!(measured.estimated_seconds, 120);
+}
+
+#[tokio::test]
+async fn test_estimate_for_recording_reads_throughput() {
+    let database = Database::in_memory().unwrap();
+
+    let mut past = Recording::new("past.wav".to_string(), "/tmp/estimate_past.wav".to_string());
+    past.duration = Some(100);
+    database.create_recording(&past).await.unwrap();
+    let transcription = Transcription::new(past.id.clone(), "text".to_string(), "ja".to_string())
+        .with_processing_time(Some(20_000))
+        .with_model_used(Some("base".to_string()))
+        .with_status(TranscriptionStatus::Completed);
+    database.create_transcription(&transcription).await.unwrap();
+
+    let stored = database.get_transcription(&transcription.id).await.unwrap().unwrap();
+    assert_eq!(stored.model_used.as_deref(), Some("base"));
+
+    let mut target = Recording::new("next.wav".to_string(), "/tmp/estimate_next.wav".to_string());
+    target.duration = Some(300);
+    database.create_recording(&target).await.unwrap();
+
+    let estimates = estimate_for_recording(&database, &target.id, &["base".to_string(), "medium".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(estimates[0].history_runs, 1);
+    assert_eq!(estimates[0].estimated_seconds, 60);
+    assert_eq!(estimates[1].history_runs, 0);
+
+    let unknown = Recording::new("unknown.wav".to_string(), "/tmp/estimate_unknown.wav".to_string());
+    database.create_recording(&unknown).await.unwrap();
+    assert!(estimate_for_recording(&database, &unknown.id, &["base".to_string()]).await.is_err());
+}
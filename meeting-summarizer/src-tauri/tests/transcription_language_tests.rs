@@ -0,0 +1,49 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::Recording;
+use meeting_summarizer_lib::services::transcription_language::resolve_language;
+
+fn recording_in(category: Option<&str>) -> Recording {
+    let mut recording = Recording::new("call.wav".to_string(), "/tmp/call.wav".to_string());
+    recording.category = category.map(str::to_string);
+    recording
+}
+
+#[tokio::test]
+async fn test_category_language_is_used_when_not_requested() {
+    let database = Database::in_memory().unwrap();
+    database.set_category_language("EN customer calls", "en").await.unwrap();
+
+    let recording = recording_in(Some("EN customer calls"));
+    assert_eq!(resolve_language(&database, &recording, None).await.unwrap(), Some("en".to_string()));
+    // カテゴリ名の大文字小文字は区別しない
+    let recording = recording_in(Some("en customer calls"));
+    assert_eq!(resolve_language(&database, &recording, None).await.unwrap(), Some("en".to_string()));
+}
+
+#[tokio::test]
+async fn test_requested_language_overrides_category_default() {
+    let database = Database::in_memory().unwrap();
+    database.set_category_language("EN customer calls", "en").await.unwrap();
+
+    let recording = recording_in(Some("EN customer calls"));
+    assert_eq!(
+        resolve_language(&database, &recording, Some("ja".to_string())).await.unwrap(),
+        Some("ja".to_string())
+    );
+    assert_eq!(resolve_language(&database, &recording_in(None), None).await.unwrap(), None);
+    assert_eq!(resolve_language(&database, &recording_in(Some("社内")), None).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_category_language_can_be_updated_and_removed() {
+    let database = Database::in_memory().unwrap();
+    database.set_category_language("営業", "en").await.unwrap();
+    database.set_category_language("営業", "ja").await.unwrap();
+
+    let languages = database.get_category_languages().await.unwrap();
+    assert_eq!(languages.len(), 1);
+    assert_eq!(languages[0].language, "ja");
+
+    assert!(database.delete_category_language("営業").await.unwrap());
+    assert_eq!(database.get_category_language("営業").await.unwrap(), None);
+}
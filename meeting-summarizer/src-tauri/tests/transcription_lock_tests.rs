@@ -0,0 +1,61 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Job, JobPayload};
+use meeting_summarizer_lib::services::{JobQueue, LLMModelManager, ModelSettingsManager, OllamaPool, Summarizer, TranscriptionLocks, WhisperService};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+fn summarizer(database: &Arc<Database>) -> Arc<Summarizer> {
+    // 設定ファイルは読み書きしない（既定の設定で要約する）
+    Arc::new(Summarizer::new(
+        database.clone(),
+        Arc::new(Mutex::new(ModelSettingsManager::new(PathBuf::from("model_settings.json")))),
+        Arc::new(Mutex::new(LLMModelManager::new())),
+        Arc::new(OllamaPool::new()),
+    ))
+}
+
+#[test]
+fn test_second_acquire_returns_existing_job() {
+    let locks = Arc::new(TranscriptionLocks::new());
+
+    let guard = locks.try_acquire("rec-1").unwrap();
+    let existing = locks.try_acquire("rec-1").err().unwrap();
+    assert_eq!(existing.job_id, guard.job_id());
+    assert_eq!(existing.recording_id, "rec-1");
+
+    // 別の録音は並行して実行できる
+    let other = locks.try_acquire("rec-2").unwrap();
+    assert_eq!(locks.list().len(), 2);
+
+    drop(guard);
+    assert!(locks.get("rec-1").is_none());
+    let reacquired = locks.try_acquire("rec-1").unwrap();
+    assert_ne!(reacquired.job_id(), other.job_id());
+}
+
+#[tokio::test]
+async fn test_enqueue_deduplicates_transcription_jobs() {
+    let database = Arc::new(Database::in_memory().unwrap());
+    let whisper_service = Arc::new(WhisperService::new(PathBuf::from("/tmp/model.bin"), PathBuf::from("/tmp")));
+    // ワーカーは起動せず、登録のみを検証する
+    let queue = JobQueue::new(database.clone(), whisper_service, summarizer(&database));
+
+    let payload = |recording_id: &str| JobPayload::Transcription {
+        recording_id: recording_id.to_string(),
+        language: None,
+    };
+
+    let first = queue.enqueue(Job::new(payload("rec-1"))).await.unwrap();
+    let duplicate = queue.enqueue(Job::new(payload("rec-1"))).await.unwrap();
+    let other = queue.enqueue(Job::new(payload("rec-2"))).await.unwrap();
+
+    assert_eq!(duplicate.id, first.id);
+    assert_ne!(other.id, first.id);
+    assert_eq!(queue.list_jobs(None).await.unwrap().len(), 2);
+
+    // キャンセル済みなら新しく登録できる
+    queue.cancel_job(&first.id).await.unwrap();
+    let requeued = queue.enqueue(Job::new(payload("rec-1"))).await.unwrap();
+    assert_ne!(requeued.id, first.id);
+}
@@ -0,0 +1,80 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::services::recording_progress::RecordingProgress;
+use meeting_summarizer_lib::services::tray::format_elapsed;
+use meeting_summarizer_lib::services::{demo_mode, RecordingService, TrayAction, TrayRecordingState, TrayState};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+#[test]
+fn test_menu_ids_round_trip() {
+    for action in TrayAction::ALL {
+        assert_eq!(TrayAction::from_id(action.id()), Some(action));
+        assert!(!action.label().starts_with("tray."));
+    }
+    assert_eq!(TrayAction::from_id("unknown"), None);
+    assert!(TrayAction::Pause.is_recording_control());
+    assert!(!TrayAction::Quit.is_recording_control());
+}
+
+#[test]
+fn test_menu_follows_recording_state() {
+    let idle = TrayState::from_progress(None);
+    assert_eq!(idle.recording, TrayRecordingState::Idle);
+    assert!(idle.is_enabled(TrayAction::Start));
+    assert!(!idle.is_enabled(TrayAction::Pause));
+    assert!(!idle.is_enabled(TrayAction::Stop));
+    assert_eq!(idle.title(), None);
+
+    let progress = RecordingProgress::new("session".to_string(), 75_000, 16_000 * 75, 16_000, 1);
+    let recording = TrayState::from_progress(Some(&progress));
+    assert_eq!(recording.recording, TrayRecordingState::Recording);
+    assert_eq!(recording.session_id.as_deref(), Some("session"));
+    assert!(!recording.is_enabled(TrayAction::Start));
+    assert!(recording.is_enabled(TrayAction::Pause));
+    assert!(!recording.is_enabled(TrayAction::Resume));
+    assert!(recording.is_enabled(TrayAction::Stop));
+    assert_eq!(recording.title().as_deref(), Some("● 01:15"));
+    assert!(recording.tooltip().contains("01:15"));
+
+    let paused = TrayState::from_progress(Some(&progress.with_paused(true)));
+    assert_eq!(paused.recording, TrayRecordingState::Paused);
+    assert!(paused.is_enabled(TrayAction::Resume));
+    assert!(!paused.is_enabled(TrayAction::Pause));
+    assert!(paused.is_enabled(TrayAction::Stop));
+}
+
+#[test]
+fn test_format_elapsed() {
+    assert_eq!(format_elapsed(0), "00:00");
+    assert_eq!(format_elapsed(59_999), "00:59");
+    assert_eq!(format_elapsed(3_723_000), "1:02:03");
+}
+
+#[tokio::test]
+async fn test_pause_and_resume_recording() {
+    demo_mode::set_enabled(true);
+
+    let temp_dir = TempDir::new().unwrap();
+    let database = Arc::new(Database::new(temp_dir.path().join("tray.db")).unwrap());
+    let recording_service = RecordingService::new(database, temp_dir.path().join("recordings")).unwrap();
+    assert!(recording_service.pause_recording().await.is_err());
+
+    recording_service.start_recording().await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    recording_service.pause_recording().await.unwrap();
+    assert!(recording_service.is_paused().await);
+
+    // 一時停止中は音声が増えない
+    let paused_progress = recording_service.current_progress().await.unwrap();
+    assert!(paused_progress.paused);
+    tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+    let still_paused = recording_service.current_progress().await.unwrap();
+    assert_eq!(still_paused.sample_count, paused_progress.sample_count);
+    assert_eq!(TrayState::from_progress(Some(&still_paused)).recording, TrayRecordingState::Paused);
+
+    recording_service.resume_recording().await.unwrap();
+    assert!(!recording_service.is_paused().await);
+    let recording = recording_service.stop_recording().await.unwrap();
+    assert!(std::path::Path::new(&recording.file_path).exists());
+    assert!(recording_service.current_progress().await.is_none());
+}
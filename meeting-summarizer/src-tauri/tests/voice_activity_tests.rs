@@ -0,0 +1,201 @@
+use hound::{SampleFormat, WavSpec, WavWriter};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{Recording, RecordingQuery};
+use meeting_summarizer_lib::services::voice_activity::{
+    self, compute_metrics, detect_speech, kept_ranges, trim_silence, trim_silence_in_file, SpeechRegion,
+};
+use meeting_summarizer_lib::services::{AppSettings, SilenceTrimSettings};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// (秒数, 音を鳴らすか) の並びで 16kHz の信号を作る
+fn signal(pattern: &[(f32, bool)], sample_rate: u32) -> Vec<f32> {
+    let mut samples = Vec::new();
+    for (seconds, voiced) in pattern {
+        let frames = (sample_rate as f32 * seconds) as usize;
+        for i in 0..frames {
+            let value = if *voiced {
+                (i as f32 * 220.0 * 2.0 * std::f32::consts::PI / sample_rate as f32).sin() * 0.3
+            } else {
+                // 背景ノイズ
+                if i % 2 == 0 { 0.001 } else { -0.001 }
+            };
+            samples.push(value);
+        }
+    }
+    samples
+}
+
+fn write_wav(path: &Path, samples: &[f32], sample_rate: u32) {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec).unwrap();
+    for sample in samples {
+        writer.write_sample((sample * i16::MAX as f32) as i16).unwrap();
+    }
+    writer.finalize().unwrap();
+}
+
+#[test]
+fn test_detect_speech_regions() {
+    let samples = signal(&[(1.0, false), (2.0, true), (0.1, false), (1.0, true), (3.0, false), (1.0, true)], 16_000);
+    let regions = detect_speech(&samples, 16_000);
+
+    // 0.1秒の息継ぎは同じ区間としてまとめる
+    assert_eq!(regions.len(), 2, "regions: {:?}", regions);
+    assert!((regions[0].start_ms - 1_000).abs() <= 30);
+    assert!((regions[0].end_ms - 4_100).abs() <= 30);
+    assert!((regions[1].start_ms - 7_100).abs() <= 30);
+}
+
+#[test]
+fn test_silent_signal_has_no_speech() {
+    let samples = signal(&[(5.0, false)], 16_000);
+    assert!(detect_speech(&samples, 16_000).is_empty());
+}
+
+#[test]
+fn test_compute_metrics() {
+    let regions = vec![
+        SpeechRegion { start_ms: 0, end_ms: 10_000 },
+        SpeechRegion { start_ms: 12_000, end_ms: 20_000 },
+        SpeechRegion { start_ms: 24_000, end_ms: 30_000 },
+    ];
+    let metrics = compute_metrics("rec-1", &regions, 60_000);
+
+    assert_eq!(metrics.speech_ms, 24_000);
+    assert!((metrics.silence_ratio - 0.6).abs() < 1e-9);
+    assert_eq!(metrics.pause_count, 2);
+    assert_eq!(metrics.average_pause_ms, 3_000);
+    assert!((metrics.speech_density - 3.0).abs() < 1e-9);
+
+    let empty = compute_metrics("rec-1", &[], 0);
+    assert_eq!(empty.silence_ratio, 0.0);
+    assert_eq!(empty.average_pause_ms, 0);
+}
+
+#[tokio::test]
+async fn test_analyze_recording_and_filter_by_silence() {
+    let temp_dir = TempDir::new().unwrap();
+    let database = Database::in_memory().unwrap();
+
+    let busy_path = temp_dir.path().join("busy.wav");
+    write_wav(&busy_path, &signal(&[(4.0, true), (1.0, false), (5.0, true)], 16_000), 16_000);
+    let busy = Recording::new("busy.wav".to_string(), busy_path.to_string_lossy().to_string());
+    database.create_recording(&busy).await.unwrap();
+
+    let quiet_path = temp_dir.path().join("quiet.wav");
+    write_wav(&quiet_path, &signal(&[(1.0, true), (9.0, false)], 16_000), 16_000);
+    let quiet = Recording::new("quiet.wav".to_string(), quiet_path.to_string_lossy().to_string());
+    database.create_recording(&quiet).await.unwrap();
+
+    let unanalyzed = Recording::new("other.wav".to_string(), "/tmp/other.wav".to_string());
+    database.create_recording(&unanalyzed).await.unwrap();
+
+    let busy_metrics = voice_activity::analyze_recording(&database, &busy.id).await.unwrap();
+    assert!(busy_metrics.silence_ratio < 0.2, "{:?}", busy_metrics);
+    assert!((busy_metrics.duration_ms - 10_000).abs() <= 30);
+    let quiet_metrics = voice_activity::analyze_recording(&database, &quiet.id).await.unwrap();
+    assert!(quiet_metrics.silence_ratio > 0.8, "{:?}", quiet_metrics);
+
+    let stored = database.get_speech_metrics(&quiet.id).await.unwrap().unwrap();
+    assert_eq!(stored.speech_ms, quiet_metrics.speech_ms);
+
+    let dead_air = database
+        .search_recordings(&RecordingQuery {
+            min_silence_ratio: Some(0.7),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(dead_air.iter().map(|r| r.id.as_str()).collect::<Vec<_>>(), vec![quiet.id.as_str()]);
+
+    let lively = database
+        .search_recordings(&RecordingQuery {
+            max_silence_ratio: Some(0.5),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(lively.iter().map(|r| r.id.as_str()).collect::<Vec<_>>(), vec![busy.id.as_str()]);
+
+    database.delete_recording(&quiet.id).await.unwrap();
+    assert!(database.get_speech_metrics(&quiet.id).await.unwrap().is_none());
+}
+
+#[test]
+fn test_long_silence_is_trimmed() {
+    let settings = SilenceTrimSettings::default();
+    let regions = [
+        SpeechRegion { start_ms: 1_000, end_ms: 2_000 },
+        SpeechRegion { start_ms: 3_000, end_ms: 4_000 },
+        SpeechRegion { start_ms: 10_000, end_ms: 11_000 },
+    ];
+    // 短い間はそのまま残し、長い無音だけを取り除く
+    assert_eq!(
+        kept_ranges(&regions, 12_000, &settings),
+        vec![
+            SpeechRegion { start_ms: 700, end_ms: 4_300 },
+            SpeechRegion { start_ms: 9_700, end_ms: 11_300 },
+        ]
+    );
+
+    let samples = signal(&[(2.0, false), (1.0, true), (6.0, false), (1.0, true), (1.0, false)], 16_000);
+    let regions = detect_speech(&samples, 16_000);
+    let (trimmed, summary) = trim_silence(&samples, 16_000, &regions, &settings);
+    assert_eq!(summary.original_ms, 11_000);
+    assert!(summary.removed_ms() > 6_000, "removed only {} ms", summary.removed_ms());
+    assert_eq!(trimmed.len() as i64, summary.trimmed_ms * 16);
+    assert!(!summary.is_silent());
+
+    // 書き起こした区間の時刻は元の音声での位置に戻せる
+    let second = summary.kept_ranges[1];
+    let first_length = summary.kept_ranges[0].duration_ms();
+    assert_eq!(summary.original_ms(0), summary.kept_ranges[0].start_ms);
+    assert_eq!(summary.original_ms(first_length + 100), second.start_ms + 100);
+}
+
+#[test]
+fn test_trim_silence_in_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let settings = SilenceTrimSettings::default();
+
+    // 長い無音が無ければ書き出さない
+    let speech = temp_dir.path().join("speech.wav");
+    write_wav(&speech, &signal(&[(0.5, false), (3.0, true), (0.5, false)], 16_000), 16_000);
+    let output = temp_dir.path().join("speech-trimmed.wav");
+    assert_eq!(trim_silence_in_file(&speech, &output, &settings).unwrap(), None);
+    assert!(!output.exists());
+
+    let gaps = temp_dir.path().join("gaps.wav");
+    write_wav(&gaps, &signal(&[(1.0, true), (8.0, false), (1.0, true)], 16_000), 16_000);
+    let output = temp_dir.path().join("gaps-trimmed.wav");
+    let summary = trim_silence_in_file(&gaps, &output, &settings).unwrap().unwrap();
+    assert!(summary.trimmed_ms < 4_000);
+    assert_eq!(hound::WavReader::open(&output).unwrap().duration() as i64, summary.trimmed_ms * 16);
+
+    let silence = temp_dir.path().join("silence.wav");
+    write_wav(&silence, &signal(&[(5.0, false)], 16_000), 16_000);
+    let summary = trim_silence_in_file(&silence, &temp_dir.path().join("silence-trimmed.wav"), &settings)
+        .unwrap()
+        .unwrap();
+    assert!(summary.is_silent());
+}
+
+#[test]
+fn test_silence_trim_settings() {
+    let settings: AppSettings = serde_json::from_str("{}").unwrap();
+    assert!(settings.silence_trim.enabled);
+    assert!(settings.silence_trim.validate().is_ok());
+
+    let invalid = SilenceTrimSettings {
+        min_silence_ms: 1_000,
+        keep_silence_ms: 600,
+        ..SilenceTrimSettings::default()
+    };
+    assert!(invalid.validate().is_err());
+}
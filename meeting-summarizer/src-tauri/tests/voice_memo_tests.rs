@@ -0,0 +1,90 @@
+use chrono::{Local, TimeZone};
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::services::demo_mode;
+use meeting_summarizer_lib::services::voice_memo::{append_to_daily_note, SilenceDetector, VoiceMemoRecorder};
+use meeting_summarizer_lib::services::{RecordingService, VoiceMemoSettings, WhisperService};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[test]
+fn test_silence_after_speech_stops_capture() {
+    let mut detector = SilenceDetector::new(0.05, Duration::from_secs(2));
+    let at = Duration::from_millis;
+
+    // 話し始める前の無音では止めない
+    assert!(!detector.observe(0.0, at(0)));
+    assert!(!detector.observe(0.0, at(5_000)));
+
+    assert!(!detector.observe(0.3, at(5_200)));
+    assert!(!detector.observe(0.01, at(5_400)));
+    // 途中で声が入ると無音の計測をやり直す
+    assert!(!detector.observe(0.2, at(6_800)));
+    assert!(!detector.observe(0.01, at(7_000)));
+    assert!(!detector.observe(0.01, at(8_800)));
+    assert!(detector.observe(0.01, at(9_000)));
+}
+
+#[tokio::test]
+async fn test_daily_note_appends_with_heading_once() {
+    let temp_dir = TempDir::new().unwrap();
+    let morning = Local.with_ymd_and_hms(2026, 10, 18, 9, 5, 0).unwrap();
+    let evening = Local.with_ymd_and_hms(2026, 10, 18, 18, 30, 0).unwrap();
+
+    let path = append_to_daily_note(temp_dir.path(), morning, "牛乳を買う").await.unwrap();
+    let same = append_to_daily_note(temp_dir.path(), evening, "資料の\n見直し").await.unwrap();
+    assert_eq!(path, same);
+    assert_eq!(path.file_name().unwrap(), "2026-10-18.md");
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert_eq!(content, "# 2026-10-18\n\n- 09:05 牛乳を買う\n- 18:30 資料の 見直し\n");
+}
+
+#[test]
+fn test_settings_validation() {
+    assert!(VoiceMemoSettings::default().validate().is_ok());
+    let too_long = VoiceMemoSettings { max_duration_secs: 3_600, ..VoiceMemoSettings::default() };
+    assert!(too_long.validate().is_err());
+    let relative = VoiceMemoSettings { daily_notes_directory: Some("notes".to_string()), ..VoiceMemoSettings::default() };
+    assert!(relative.validate().is_err());
+}
+
+#[tokio::test]
+async fn test_capture_until_stopped_in_demo_mode() {
+    demo_mode::set_enabled(true);
+
+    let temp_dir = TempDir::new().unwrap();
+    let recordings_dir = temp_dir.path().join("recordings");
+    let database = Arc::new(Database::new(temp_dir.path().join("memo.db")).unwrap());
+    let recording_service = RecordingService::new(database.clone(), recordings_dir.clone()).unwrap();
+    let whisper = WhisperService::new(temp_dir.path().join("model.bin"), recordings_dir);
+    let recorder = Arc::new(VoiceMemoRecorder::new());
+    let notes_dir = temp_dir.path().join("notes");
+    let settings = VoiceMemoSettings {
+        daily_notes_directory: Some(notes_dir.to_string_lossy().to_string()),
+        ..VoiceMemoSettings::default()
+    };
+
+    // デモの波形は無音が続かないので、停止の操作で止める
+    let stopper = {
+        let recorder = recorder.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(1_200)).await;
+            assert!(recorder.is_active());
+            recorder.request_stop()
+        })
+    };
+    let memo = recorder.capture(&database, &recording_service, &whisper, &settings).await.unwrap();
+    assert!(stopper.await.unwrap());
+    assert!(!recorder.is_active());
+
+    assert_eq!(memo.recording.category.as_deref(), Some("memos"));
+    assert!(memo.recording.title.as_deref().unwrap().starts_with("ボイスメモ"));
+    assert_eq!(memo.transcription.text, demo_mode::DEMO_TRANSCRIPT);
+    let stored = database.get_transcriptions_by_recording(&memo.recording.id).await.unwrap();
+    assert_eq!(stored.len(), 1);
+
+    let note = std::fs::read_to_string(memo.daily_note_path.unwrap()).unwrap();
+    assert!(note.contains("定例会議を始めます"));
+    assert!(!recorder.request_stop());
+}
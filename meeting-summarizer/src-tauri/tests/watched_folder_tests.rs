@@ -0,0 +1,116 @@
+use meeting_summarizer_lib::database::Database;
+use meeting_summarizer_lib::models::{JobPayload, JobStatus};
+use meeting_summarizer_lib::services::watched_folders::{job_for_rule, scan_folder, settled_audio_files, validate_rule};
+use meeting_summarizer_lib::services::{JobQueue, LLMModelManager, ModelSettingsManager, OllamaPool, Summarizer, WatchedFolderRule, WhisperService};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tempfile::TempDir;
+use tokio::sync::Mutex;
+
+fn summarizer(database: &Arc<Database>) -> Arc<Summarizer> {
+    // 設定ファイルは読み書きしない（既定の設定で要約する）
+    Arc::new(Summarizer::new(
+        database.clone(),
+        Arc::new(Mutex::new(ModelSettingsManager::new(PathBuf::from("model_settings.json")))),
+        Arc::new(Mutex::new(LLMModelManager::new())),
+        Arc::new(OllamaPool::new()),
+    ))
+}
+
+fn rule(path: &Path) -> WatchedFolderRule {
+    WatchedFolderRule {
+        id: "zoom".to_string(),
+        path: path.to_string_lossy().to_string(),
+        enabled: true,
+        transcribe: true,
+        summarize: true,
+        language: Some("ja".to_string()),
+        category: Some("client-a".to_string()),
+        tags: vec!["zoom".to_string()],
+        model_config: None,
+        separate_channels: false,
+        channel_speakers: Vec::new(),
+    }
+}
+
+/// 書き込み完了とみなされるよう更新日時を過去にしたファイルを作る
+fn write_settled(path: &Path) {
+    fs::write(path, b"RIFF").unwrap();
+    File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(SystemTime::now() - Duration::from_secs(120))
+        .unwrap();
+}
+
+#[test]
+fn test_only_settled_audio_files_are_picked_up() {
+    let dir = TempDir::new().unwrap();
+    write_settled(&dir.path().join("audio_only.m4a"));
+    write_settled(&dir.path().join("chat.txt"));
+    fs::write(dir.path().join("recording.wav"), b"RIFF").unwrap();
+
+    let files = settled_audio_files(dir.path(), SystemTime::now()).unwrap();
+
+    assert_eq!(files, vec![dir.path().join("audio_only.m4a")]);
+}
+
+#[test]
+fn test_rule_decides_follow_up_job() {
+    let dir = TempDir::new().unwrap();
+    let mut rule = rule(dir.path());
+    assert!(matches!(
+        job_for_rule(&rule, "rec").unwrap().payload,
+        JobPayload::TranscribeAndSummarize { .. }
+    ));
+
+    rule.summarize = false;
+    assert!(matches!(job_for_rule(&rule, "rec").unwrap().payload, JobPayload::Transcription { .. }));
+
+    rule.separate_channels = true;
+    rule.channel_speakers = vec!["自分".to_string(), "相手".to_string()];
+    match job_for_rule(&rule, "rec").unwrap().payload {
+        JobPayload::ChannelTranscription { speakers, summarize, .. } => {
+            assert_eq!(speakers, rule.channel_speakers);
+            assert!(!summarize);
+        }
+        other => panic!("unexpected job: {:?}", other),
+    }
+    rule.separate_channels = false;
+
+    rule.transcribe = false;
+    assert!(job_for_rule(&rule, "rec").is_none());
+
+    rule.summarize = true;
+    assert!(validate_rule(&rule).is_err());
+}
+
+#[tokio::test]
+async fn test_new_files_are_imported_once_and_queued() {
+    let watched = TempDir::new().unwrap();
+    let data = TempDir::new().unwrap();
+    write_settled(&watched.path().join("weekly.m4a"));
+
+    let database = Arc::new(Database::in_memory().unwrap());
+    let whisper_service = Arc::new(WhisperService::new(data.path().join("model.bin"), data.path().to_path_buf()));
+    let queue = JobQueue::new(database.clone(), whisper_service, summarizer(&database));
+    let recordings_dir = data.path().join("recordings");
+    let rule = rule(watched.path());
+
+    let imports = scan_folder(&database, &queue, &rule, &recordings_dir).await.unwrap();
+    assert_eq!(imports.len(), 1);
+    let recording = &imports[0].recording;
+    assert_eq!(recording.title.as_deref(), Some("weekly"));
+    assert_eq!(recording.category.as_deref(), Some("client-a"));
+    assert!(Path::new(&recording.file_path).starts_with(&recordings_dir));
+
+    let job = queue.get_job(imports[0].job_id.as_ref().unwrap()).await.unwrap().unwrap();
+    assert_eq!(job.status, JobStatus::Pending);
+
+    // 取り込み済みのファイルは録音を削除しても再取り込みしない
+    database.delete_recording(&recording.id).await.unwrap();
+    assert!(scan_folder(&database, &queue, &rule, &recordings_dir).await.unwrap().is_empty());
+}
@@ -0,0 +1,94 @@
+use chrono::Utc;
+use meeting_summarizer_lib::services::whisper_acceleration::{
+    acceleration_info, acceleration_status, expected_speedup, gpu_device, is_apple_silicon, parse_nvidia_smi,
+    AccelerationBenchmark, WhisperDevice,
+};
+use meeting_summarizer_lib::services::whisper_cpp::WhisperBackend;
+use meeting_summarizer_lib::services::{AppSettings, WhisperAccelerationSettings, WhisperService};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn benchmark(model: &str, cpu_ms: u64, accelerated_ms: u64) -> AccelerationBenchmark {
+    AccelerationBenchmark {
+        model: model.to_string(),
+        audio_seconds: 60.0,
+        cpu_ms,
+        accelerated_ms,
+        measured_at: Utc::now(),
+    }
+}
+
+#[test]
+fn test_benchmarks_replace_previous_measurement() {
+    let mut settings = WhisperAccelerationSettings::default();
+    assert!(settings.enabled);
+
+    settings.record_benchmark(benchmark("medium", 90_000, 30_000));
+    settings.record_benchmark(benchmark("base", 8_000, 4_000));
+    settings.record_benchmark(benchmark("medium", 96_000, 24_000));
+
+    assert_eq!(settings.benchmarks.len(), 2);
+    assert_eq!(settings.speedup_for("medium"), Some(4.0));
+    assert_eq!(settings.speedup_for("base"), Some(2.0));
+    assert_eq!(settings.speedup_for("large"), None);
+
+    let info = acceleration_info(&settings, WhisperDevice::Cpu, "medium");
+    assert_eq!(info.current_speedup, Some(4.0));
+    assert_eq!(info.available, gpu_device().is_some());
+}
+
+#[test]
+fn test_settings_default_when_missing() {
+    let settings: AppSettings = serde_json::from_str("{}").unwrap();
+    assert!(settings.whisper_acceleration.enabled);
+    assert!(settings.whisper_acceleration.benchmarks.is_empty());
+    assert_eq!(serde_json::to_value(WhisperDevice::Metal).unwrap(), "metal");
+}
+
+#[tokio::test]
+async fn test_device_follows_toggle_and_platform() {
+    let temp_dir = TempDir::new().unwrap();
+    let whisper = WhisperService::new(temp_dir.path().join("model.bin"), temp_dir.path().join("recordings"));
+
+    let accelerated = gpu_device().unwrap_or(WhisperDevice::Cpu);
+    assert_eq!(whisper.device(), accelerated);
+    if is_apple_silicon() {
+        assert_eq!(accelerated, WhisperDevice::Metal);
+    }
+    whisper.set_acceleration_enabled(false);
+    assert_eq!(whisper.device(), WhisperDevice::Cpu);
+
+    if gpu_device().is_none() {
+        // GPU を使えない環境では計測できない
+        let result = whisper.benchmark_acceleration(Path::new("/tmp/missing.wav"), 10.0, "base").await;
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn test_nvidia_smi_reports_first_gpu() {
+    assert_eq!(
+        parse_nvidia_smi("\nNVIDIA GeForce RTX 4070\nNVIDIA GeForce RTX 3060\n").as_deref(),
+        Some("NVIDIA GeForce RTX 4070")
+    );
+    assert_eq!(parse_nvidia_smi(""), None);
+    assert_eq!(serde_json::to_value(WhisperDevice::Cuda).unwrap(), "cuda");
+    assert_eq!(WhisperDevice::Cuda.torch_name(), "cuda");
+}
+
+#[test]
+fn test_expected_speedup_prefers_measurement() {
+    let mut settings = WhisperAccelerationSettings::default();
+    settings.record_benchmark(benchmark("medium", 90_000, 18_000));
+
+    assert_eq!(expected_speedup(&settings, WhisperDevice::Cuda, "medium"), 5.0);
+    assert_eq!(expected_speedup(&settings, WhisperDevice::Cuda, "small"), WhisperDevice::Cuda.default_speedup());
+    assert_eq!(expected_speedup(&settings, WhisperDevice::Cpu, "medium"), 1.0);
+
+    let status = acceleration_status(&settings, WhisperDevice::Metal, WhisperBackend::Python, "medium");
+    assert_eq!(status.expected_speedup, 5.0);
+    assert!(status.measured);
+    let status = acceleration_status(&settings, WhisperDevice::Cpu, WhisperBackend::WhisperCpp, "medium");
+    assert_eq!(status.expected_speedup, 1.0);
+    assert!(!status.measured);
+}
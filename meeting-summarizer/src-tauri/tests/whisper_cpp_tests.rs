@@ -0,0 +1,63 @@
+use meeting_summarizer_lib::services::whisper_cpp::{
+    build_segment, ggml_model_file_name, ggml_model_url, WhisperBackend, WhisperOutput,
+};
+use meeting_summarizer_lib::services::{AppSettings, WhisperBackendSettings, WhisperService};
+use tempfile::TempDir;
+
+#[test]
+fn test_ggml_model_names() {
+    assert_eq!(ggml_model_file_name("base"), "ggml-base.bin");
+    assert_eq!(ggml_model_file_name("medium"), "ggml-medium.bin");
+    assert_eq!(ggml_model_file_name("large"), "ggml-large-v3.bin");
+    assert!(ggml_model_url("small").ends_with("/ggml-small.bin"));
+}
+
+#[test]
+fn test_build_segment_keeps_times_and_confidence() {
+    // whisper.cpp の時刻は 10ms 単位
+    let segment = build_segment(150, 420, "  こんにちは。 ", &[0.9, 0.7]).unwrap();
+    assert_eq!(segment.start_ms, 1500);
+    assert_eq!(segment.end_ms, 4200);
+    assert_eq!(segment.text, "こんにちは。");
+    assert!((segment.confidence.unwrap() - 0.8).abs() < 1e-6);
+
+    assert_eq!(build_segment(0, 100, "本日の議題です。", &[]).unwrap().confidence, None);
+    assert!(build_segment(0, 100, "   ", &[0.9]).is_none());
+}
+
+#[test]
+fn test_output_joins_segment_text() {
+    let segments = vec![
+        build_segment(0, 150, "こんにちは。", &[0.9]).unwrap(),
+        build_segment(150, 420, "本日の議題です。", &[0.8]).unwrap(),
+    ];
+    let output = WhisperOutput::from_segments(segments);
+    assert_eq!(output.text, "こんにちは。\n本日の議題です。");
+    assert_eq!(output.segments.len(), 2);
+}
+
+#[test]
+fn test_backend_settings() {
+    let settings: AppSettings = serde_json::from_str("{}").unwrap();
+    assert_eq!(settings.whisper_backend.backend, WhisperBackend::Python);
+
+    // 以前の設定ファイルにあった実行ファイルのパスは無視する
+    let settings: WhisperBackendSettings =
+        serde_json::from_str(r#"{"backend":"whisper_cpp","whisper_cpp_path":"/opt/whisper/whisper-cli"}"#).unwrap();
+    assert_eq!(settings.backend, WhisperBackend::WhisperCpp);
+}
+
+#[tokio::test]
+async fn test_switching_backend_requires_reinitialization() {
+    let temp_dir = TempDir::new().unwrap();
+    let whisper = WhisperService::new(temp_dir.path().join("model.bin"), temp_dir.path().join("recordings"));
+    assert_eq!(whisper.ggml_model_path("base"), temp_dir.path().join("ggml-base.bin"));
+
+    whisper
+        .set_backend(WhisperBackendSettings {
+            backend: WhisperBackend::WhisperCpp,
+        })
+        .await;
+    assert_eq!(whisper.backend().backend, WhisperBackend::WhisperCpp);
+    assert!(!whisper.is_initialized().await);
+}
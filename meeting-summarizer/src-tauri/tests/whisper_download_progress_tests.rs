@@ -0,0 +1,22 @@
+use meeting_summarizer_lib::services::whisper_download_progress::{parse_progress_line, ProgressLineSplitter};
+
+#[test]
+fn test_parse_tqdm_progress_line() {
+    let (percent, downloaded, total) =
+        parse_progress_line(" 45%|████▌     | 207M/461M [00:10<00:12, 20.1MiB/s]").unwrap();
+    assert_eq!(percent, 45.0);
+    assert_eq!(downloaded.as_deref(), Some("207M"));
+    assert_eq!(total.as_deref(), Some("461M"));
+
+    assert_eq!(parse_progress_line("100%|██████████| 461M/461M").unwrap().0, 100.0);
+    assert!(parse_progress_line("Loading model: small").is_none());
+}
+
+#[test]
+fn test_splitter_handles_carriage_returns_across_chunks() {
+    let mut splitter = ProgressLineSplitter::new();
+    assert!(splitter.push(b"  1%|  | 4M/4").is_empty());
+    let lines = splitter.push(b"61M\r  2%|  | 9M/461M\rwarn");
+    assert_eq!(lines, vec!["  1%|  | 4M/461M".to_string(), "  2%|  | 9M/461M".to_string()]);
+    assert_eq!(splitter.finish().as_deref(), Some("warn"));
+}
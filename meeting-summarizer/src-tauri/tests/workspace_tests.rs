@@ -0,0 +1,52 @@
+use meeting_summarizer_lib::services::credentials::workspace_key;
+use meeting_summarizer_lib::services::workspaces::{WorkspaceManager, DEFAULT_WORKSPACE_ID};
+use tempfile::TempDir;
+
+#[test]
+fn test_default_workspace_uses_app_data_dir() {
+    let dir = TempDir::new().unwrap();
+    let manager = WorkspaceManager::load(dir.path()).unwrap();
+
+    assert_eq!(manager.active().id, DEFAULT_WORKSPACE_ID);
+    let paths = manager.active_paths().unwrap();
+    assert_eq!(paths.db_path, dir.path().join("recordings.db"));
+    assert_eq!(paths.recordings_dir, dir.path().join("recordings"));
+}
+
+#[test]
+fn test_created_workspace_is_isolated_and_switch_persists() {
+    let dir = TempDir::new().unwrap();
+    let mut manager = WorkspaceManager::load(dir.path()).unwrap();
+
+    let client = manager.create("Client A").unwrap();
+    assert!(manager.create("client a").is_err());
+    assert!(manager.create("  ").is_err());
+
+    let paths = manager.paths(&client.id).unwrap();
+    assert!(paths.root.starts_with(dir.path().join("workspaces")));
+    assert_ne!(paths.db_path, manager.paths(DEFAULT_WORKSPACE_ID).unwrap().db_path);
+    assert!(paths.root.exists());
+
+    manager.switch(&client.id).unwrap();
+    let reloaded = WorkspaceManager::load(dir.path()).unwrap();
+    assert_eq!(reloaded.active().id, client.id);
+    assert_eq!(reloaded.list().len(), 2);
+    assert_eq!(reloaded.active_paths().unwrap().app_settings_path, paths.root.join("app_settings.json"));
+}
+
+#[test]
+fn test_switching_to_unknown_workspace_fails() {
+    let dir = TempDir::new().unwrap();
+    let mut manager = WorkspaceManager::load(dir.path()).unwrap();
+
+    assert!(manager.switch("missing").is_err());
+    assert_eq!(manager.active().id, DEFAULT_WORKSPACE_ID);
+}
+
+#[test]
+fn test_credentials_are_namespaced_by_workspace() {
+    // 既定のワークスペースは従来のキーのまま読める
+    assert_eq!(workspace_key(DEFAULT_WORKSPACE_ID, "teams_webhook_url"), "teams_webhook_url");
+    assert_eq!(workspace_key("client-a", "teams_webhook_url"), "workspaces/client-a/teams_webhook_url");
+    assert_ne!(workspace_key("client-a", "control_token"), workspace_key("client-b", "control_token"));
+}
@@ -160,7 +160,10 @@ export class TauriService {
         throw new Error(`Expected array of devices, got: ${typeof result}`);
       }
       
-      return result.filter(device => typeof device === 'string');
+      // 種類付きの一覧（{ name, source_type, capture_source }）から名前だけを返す
+      return result
+        .map(device => (typeof device === 'string' ? device : device?.name))
+        .filter((name): name is string => typeof name === 'string');
     } catch (error) {
       throw new Error(`Failed to get audio devices: ${error}`);
     }